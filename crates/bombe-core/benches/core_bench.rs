@@ -28,8 +28,9 @@ use rusqlite::Connection;
 
 // Re-export crate under a friendlier alias.  The lib target is called
 // `_bombe_core` (matching the Python extension module name).
-use _bombe_core::indexer::callgraph::build_call_edges;
-use _bombe_core::indexer::pagerank::recompute_pagerank_impl;
+use _bombe_core::indexer::callgraph::{build_call_edges, ExtractionMode};
+use _bombe_core::indexer::interval_index::IntervalIndex;
+use _bombe_core::indexer::pagerank::{recompute_pagerank_impl, PagerankWeights};
 use _bombe_core::indexer::symbols::{
     build_parameters, extract_symbols, to_module_name, visibility,
 };
@@ -37,6 +38,7 @@ use _bombe_core::query::blast::get_blast_radius_impl;
 use _bombe_core::query::change_impact::change_impact_impl;
 use _bombe_core::query::context::get_context_impl;
 use _bombe_core::query::data_flow::trace_data_flow_impl;
+use _bombe_core::query::filter_dsl::parse as parse_filter;
 use _bombe_core::query::guards::{
     adaptive_graph_cap, clamp_budget, clamp_depth, clamp_int, clamp_limit, truncate_query,
 };
@@ -45,9 +47,11 @@ use _bombe_core::query::references::get_references_impl;
 use _bombe_core::query::search::search_symbols_impl;
 use _bombe_core::query::structure::get_structure_impl;
 use _bombe_core::query::tokenizer::estimate_tokens;
+use _bombe_core::store::batch_writer::{insert_symbols_batch, BatchSymbolRow};
 use _bombe_core::store::schema::{
     migrate_schema, FTS_STATEMENTS, SCHEMA_STATEMENTS, SCHEMA_VERSION,
 };
+use _bombe_core::store::snapshot::{hydrate_if_fresh, save_snapshot};
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -478,15 +482,19 @@ fn bench_hybrid_scoring(c: &mut Criterion) {
     // -- structural_score ----------------------------------------------------
 
     group.bench_function("structural_score_high_traffic", |b| {
-        b.iter(|| structural_score(black_box(0.85), black_box(50), black_box(30)));
+        b.iter(|| structural_score(black_box(0.85), black_box(50), black_box(30), black_box(None)));
     });
 
     group.bench_function("structural_score_leaf_node", |b| {
-        b.iter(|| structural_score(black_box(0.001), black_box(0), black_box(0)));
+        b.iter(|| structural_score(black_box(0.001), black_box(0), black_box(0), black_box(None)));
     });
 
     group.bench_function("structural_score_zero_pagerank", |b| {
-        b.iter(|| structural_score(black_box(0.0), black_box(10), black_box(5)));
+        b.iter(|| structural_score(black_box(0.0), black_box(10), black_box(5), black_box(None)));
+    });
+
+    group.bench_function("structural_score_rooted", |b| {
+        b.iter(|| structural_score(black_box(0.85), black_box(50), black_box(30), black_box(Some(0.2))));
     });
 
     // -- rank_symbol (composite) ---------------------------------------------
@@ -502,6 +510,8 @@ fn bench_hybrid_scoring(c: &mut Criterion) {
                 black_box(0.5),
                 black_box(10),
                 black_box(3),
+                black_box(None),
+                black_box(None),
             )
         });
     });
@@ -517,6 +527,8 @@ fn bench_hybrid_scoring(c: &mut Criterion) {
                 black_box(0.1),
                 black_box(2),
                 black_box(1),
+                black_box(None),
+                black_box(None),
             )
         });
     });
@@ -578,6 +590,28 @@ fn bench_symbol_helpers(c: &mut Criterion) {
         b.iter(|| build_parameters(black_box(""), black_box("java")));
     });
 
+    group.bench_function("interval_index_insert_500", |b| {
+        b.iter(|| {
+            let mut index = IntervalIndex::new();
+            for i in 0..500i64 {
+                index.insert(i * 3, i * 3 + 2, i).unwrap();
+            }
+            black_box(&index);
+        });
+    });
+
+    group.bench_function("interval_index_containment_lookup_500", |b| {
+        let mut index = IntervalIndex::new();
+        for i in 0..500i64 {
+            index.insert(i * 3, i * 3 + 2, i).unwrap();
+        }
+        b.iter(|| {
+            for i in 0..500i64 {
+                black_box(index.symbol_at_line(black_box(i * 3 + 1)));
+            }
+        });
+    });
+
     group.finish();
 }
 
@@ -605,12 +639,78 @@ fn bench_pagerank(c: &mut Criterion) {
                         conn
                     },
                     |conn| {
-                        recompute_pagerank_impl(&conn, 0.85, 1e-6).unwrap();
+                        recompute_pagerank_impl(&conn, 0.85, 1e-6, &PagerankWeights::default()).unwrap();
+                        black_box(&conn);
+                    },
+                );
+            },
+        );
+    }
+
+    group.finish();
+}
+
+// ---------------------------------------------------------------------------
+// Benchmark: cold start via snapshot hydration vs. full recompute
+// ---------------------------------------------------------------------------
+
+/// Compares a cold start that hydrates PageRank scores from a graph
+/// snapshot against one that pays the full `recompute_pagerank_impl` power
+/// iteration — the exact tradeoff `hydrate_if_fresh` exists for.
+/// `populate_graph` already feeds `bench_indexing`'s extract/build-edges
+/// path, so this reuses it rather than re-deriving symbols from source.
+fn bench_snapshot_cold_start(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snapshot_cold_start");
+    group.measurement_time(std::time::Duration::from_secs(10));
+
+    for &node_count in &[100, 500] {
+        let snapshot_path =
+            std::env::temp_dir().join(format!("bombe_bench_snapshot_{node_count}.bin"));
+        {
+            let conn = setup_db();
+            populate_graph(&conn, node_count);
+            recompute_pagerank_impl(&conn, 0.85, 1e-6, &PagerankWeights::default()).unwrap();
+            save_snapshot(&conn, &snapshot_path).unwrap();
+        }
+
+        group.bench_with_input(
+            BenchmarkId::new("hydrate_from_snapshot", node_count),
+            &node_count,
+            |b, &n| {
+                b.iter_with_setup(
+                    || {
+                        let conn = setup_db();
+                        populate_graph(&conn, n);
+                        conn
+                    },
+                    |conn| {
+                        let hydrated = hydrate_if_fresh(&conn, &snapshot_path).unwrap();
+                        black_box((conn, hydrated));
+                    },
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("full_recompute", node_count),
+            &node_count,
+            |b, &n| {
+                b.iter_with_setup(
+                    || {
+                        let conn = setup_db();
+                        populate_graph(&conn, n);
+                        conn
+                    },
+                    |conn| {
+                        recompute_pagerank_impl(&conn, 0.85, 1e-6, &PagerankWeights::default())
+                            .unwrap();
                         black_box(&conn);
                     },
                 );
             },
         );
+
+        std::fs::remove_file(&snapshot_path).ok();
     }
 
     group.finish();
@@ -890,6 +990,32 @@ fn bench_query_engines(c: &mut Criterion) {
         });
     });
 
+    // ---- filter_dsl compiled to SQL ------------------------------------------
+
+    group.bench_function("filter_dsl_compiled_multi_clause", |b| {
+        let conn = create_bench_db("medium");
+        let expr = parse_filter(
+            r#"kind == "function" && pagerank > 0.1 && language == "java" && !qualified_name.starts_with("test.")"#,
+        )
+        .unwrap();
+        b.iter(|| {
+            let mut sql = "SELECT id FROM symbols WHERE ".to_string();
+            let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+            let mut param_idx = 1usize;
+            expr.push_sql(&mut sql, &mut params, &mut param_idx);
+            sql.push(';');
+            let mut stmt = conn.prepare_cached(&sql).unwrap();
+            let param_refs: Vec<&dyn rusqlite::types::ToSql> =
+                params.iter().map(|p| p.as_ref()).collect();
+            let ids: Vec<i64> = stmt
+                .query_map(param_refs.as_slice(), |row| row.get(0))
+                .unwrap()
+                .filter_map(|r| r.ok())
+                .collect();
+            black_box(ids);
+        });
+    });
+
     group.finish();
 }
 
@@ -1121,6 +1247,7 @@ fn bench_indexing(c: &mut Criterion) {
                 &syms,
                 None,
                 None,
+                ExtractionMode::Ast,
             );
             black_box(edges);
         });
@@ -1141,6 +1268,7 @@ fn bench_indexing(c: &mut Criterion) {
                 &syms,
                 None,
                 None,
+                ExtractionMode::Ast,
             );
             black_box(edges);
         });
@@ -1157,6 +1285,7 @@ fn bench_indexing(c: &mut Criterion) {
                 &syms,
                 None,
                 None,
+                ExtractionMode::Ast,
             );
             black_box(edges);
         });
@@ -1205,7 +1334,9 @@ fn bench_indexing(c: &mut Criterion) {
                     }
 
                     // 4. Build call edges
-                    let edges = build_call_edges(source, path, lang, &syms, &syms, None, None);
+                    let edges = build_call_edges(
+                        source, path, lang, &syms, &syms, None, None, ExtractionMode::Ast,
+                    );
 
                     // 5. Insert edges
                     for edge in &edges {
@@ -1311,6 +1442,7 @@ fn bench_indexing(c: &mut Criterion) {
                                 .collect();
                             let edges = build_call_edges(
                                 source, path, "java", &file_syms, &all_syms, None, None,
+                                ExtractionMode::Ast,
                             );
                             for edge in &edges {
                                 let _ = conn.execute(
@@ -1331,7 +1463,7 @@ fn bench_indexing(c: &mut Criterion) {
                         }
 
                         // PageRank
-                        recompute_pagerank_impl(&conn, 0.85, 1e-6).unwrap();
+                        recompute_pagerank_impl(&conn, 0.85, 1e-6, &PagerankWeights::default()).unwrap();
 
                         black_box(&conn);
                     },
@@ -1408,6 +1540,35 @@ fn bench_indexing(c: &mut Criterion) {
         );
     });
 
+    // ---- Batched symbol insert throughput (see `symbol_insert_500` above) -
+
+    group.bench_function("symbol_insert_500_batch", |b| {
+        b.iter_with_setup(
+            || {
+                let conn = setup_db();
+                conn.execute(
+                    "INSERT INTO files(path, language, content_hash) VALUES ('bench.java', 'java', 'x');",
+                    [],
+                ).unwrap();
+                let rows: Vec<BatchSymbolRow> = (0..500)
+                    .map(|i| BatchSymbolRow {
+                        name: format!("func_{i}"),
+                        qualified_name: format!("pkg.func_{i}"),
+                        kind: "function".to_string(),
+                        file_path: "bench.java".to_string(),
+                        start_line: i * 10,
+                        end_line: i * 10 + 8,
+                    })
+                    .collect();
+                (conn, rows)
+            },
+            |(conn, rows)| {
+                insert_symbols_batch(&conn, &rows).unwrap();
+                black_box(&conn);
+            },
+        );
+    });
+
     group.finish();
 }
 
@@ -1425,6 +1586,7 @@ criterion_group!(
     bench_hybrid_scoring,
     bench_symbol_helpers,
     bench_pagerank,
+    bench_snapshot_cold_start,
     bench_schema_version_check,
     bench_query_engines,
     bench_indexing,
@@ -0,0 +1,147 @@
+//! Builds a synthetic database from a [`super::descriptor::GraphParams`]
+//! description — a generalization of `core_bench.rs`'s
+//! `populate_realistic_graph` with configurable per-relationship edge
+//! density and an FTS on/off switch, so a workload file can approximate a
+//! specific codebase's graph shape instead of the one fixed shape
+//! `core_bench.rs` hard-codes.
+
+use rusqlite::Connection;
+
+use _bombe_core::store::schema::{migrate_schema, FTS_STATEMENTS, SCHEMA_STATEMENTS};
+
+use super::descriptor::GraphParams;
+
+/// Create a fresh in-memory database with the full Bombe schema applied and
+/// migrated to the latest version — identical to `core_bench.rs::setup_db`.
+pub fn setup_db() -> Connection {
+    let conn = Connection::open_in_memory().expect("open in-memory db");
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .expect("enable foreign keys");
+    for stmt in SCHEMA_STATEMENTS {
+        conn.execute_batch(stmt).expect("apply schema statement");
+    }
+    for stmt in FTS_STATEMENTS {
+        let _ = conn.execute_batch(stmt);
+    }
+    migrate_schema(&conn).expect("migrate schema");
+    conn
+}
+
+/// Populates `conn` per `params`: `n_files` files (languages rotated across
+/// java/python/typescript/go, as in `core_bench.rs`) each with
+/// `symbols_per_file` symbols, FTS rows if `fts_enabled`, then one
+/// edge-insertion pass per `edge_density` entry. A density of `d` wires
+/// roughly one edge of that relationship for every `1/d` symbols — `1.0`
+/// gives a dense chain, `0.1` a sparse one — which is a coarse but
+/// workload-tunable stand-in for how call/extends/implements density
+/// differs by relationship type in a real codebase.
+pub fn populate(conn: &Connection, params: &GraphParams) {
+    let languages = ["java", "python", "typescript", "go"];
+    let kinds = ["class", "function", "method", "interface"];
+
+    for f in 0..params.n_files {
+        let lang = languages[f % languages.len()];
+        let ext = match lang {
+            "java" => "java",
+            "python" => "py",
+            "typescript" => "ts",
+            "go" => "go",
+            _ => "txt",
+        };
+        conn.execute(
+            "INSERT OR IGNORE INTO files(path, language, content_hash) VALUES (?1, ?2, ?3);",
+            rusqlite::params![
+                format!("src/pkg{}/module_{f}.{ext}", f / 4),
+                lang,
+                format!("hash_{f}"),
+            ],
+        )
+        .unwrap();
+    }
+
+    let file_paths: Vec<String> = {
+        let mut stmt = conn
+            .prepare("SELECT path FROM files ORDER BY path;")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect()
+    };
+
+    let mut sym_count: usize = 0;
+    for fp in &file_paths {
+        for s in 0..params.symbols_per_file {
+            let kind = kinds[s % kinds.len()];
+            let name = match kind {
+                "class" => format!("Class{sym_count}"),
+                "interface" => format!("IService{sym_count}"),
+                "method" => format!("process_{sym_count}"),
+                _ => format!("func_{sym_count}"),
+            };
+            let qname = format!("pkg.{name}");
+            let sig = format!("{kind} {name}(arg0: i32, arg1: String) -> Result");
+            let pagerank = 1.0 / (1.0 + sym_count as f64);
+            conn.execute(
+                "INSERT INTO symbols(name, qualified_name, kind, file_path, \
+                 start_line, end_line, signature, pagerank_score) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
+                rusqlite::params![
+                    name,
+                    qname,
+                    kind,
+                    fp,
+                    (s * 20 + 1) as i64,
+                    (s * 20 + 15) as i64,
+                    sig,
+                    pagerank,
+                ],
+            )
+            .unwrap();
+            sym_count += 1;
+        }
+    }
+
+    let ids: Vec<i64> = {
+        let mut stmt = conn.prepare("SELECT id FROM symbols ORDER BY id;").unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect()
+    };
+
+    for (relationship, density) in &params.edge_density {
+        if ids.is_empty() || *density <= 0.0 {
+            continue;
+        }
+        let step = (1.0 / density).round().max(1.0) as usize;
+        for i in (0..ids.len()).step_by(step) {
+            let target = (i + step) % ids.len();
+            if target == i {
+                continue;
+            }
+            let _ = conn.execute(
+                "INSERT OR IGNORE INTO edges(source_id, target_id, source_type, target_type, relationship) \
+                 VALUES (?1, ?2, 'symbol', 'symbol', ?3);",
+                rusqlite::params![ids[i], ids[target], relationship],
+            );
+        }
+    }
+
+    if params.fts_enabled {
+        for &id in &ids {
+            let row: (String, String, String) = conn
+                .query_row(
+                    "SELECT name, qualified_name, COALESCE(signature, '') FROM symbols WHERE id = ?1;",
+                    rusqlite::params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .unwrap();
+            let _ = conn.execute(
+                "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
+                 VALUES (?1, ?2, ?3, '', ?4);",
+                rusqlite::params![id, row.0, row.1, row.2],
+            );
+        }
+    }
+}
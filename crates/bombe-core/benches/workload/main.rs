@@ -0,0 +1,267 @@
+//! Workload-driven benchmark runner for `bombe-core`'s query engines.
+//!
+//! Unlike `core_bench.rs` (which exercises a handful of hard-coded graph
+//! shapes and query inputs through `criterion`'s statistical harness),
+//! this target replays user-supplied workload descriptors — JSON files
+//! naming a graph shape plus an ordered list of query operations — so a
+//! specific codebase's profile (a Java monolith vs. a Python service) can
+//! be characterized, and tracked for regressions across commits via
+//! `--baseline`.
+//!
+//! `criterion`'s own `Criterion`/`Bencher` API reports its statistics to
+//! `target/criterion/.../estimates.json`, not as a value the calling code
+//! can inspect — there's no way to get a `{workload, op, median_ns, p95_ns,
+//! rows_returned}` report or a CI-friendly exit code out of it without
+//! scraping its output files. So this runner uses `criterion::black_box`
+//! (to stop the optimizer eliding op results the way `core_bench.rs`
+//! already does) with its own `Instant`-based sampling and percentile
+//! math — the same pattern `query::eval`, `indexer::bench`, and
+//! `query::planner_bench` already use for exactly this reason.
+//!
+//! ## Usage
+//!
+//! ```sh
+//! cargo bench --manifest-path crates/bombe-core/Cargo.toml --bench workload -- \
+//!     --workload benches/workloads --out /tmp/results.json
+//!
+//! # Gate CI on a stored baseline, failing if any op regressed > 15%:
+//! cargo bench --manifest-path crates/bombe-core/Cargo.toml --bench workload -- \
+//!     --workload benches/workloads --baseline /tmp/baseline.json --threshold 15
+//! ```
+
+mod descriptor;
+mod graph_gen;
+mod ops;
+mod report;
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use criterion::black_box;
+
+use descriptor::Workload;
+use report::OpResult;
+
+const DEFAULT_WORKLOAD_DIR: &str = "benches/workloads";
+const DEFAULT_ITERATIONS: usize = 30;
+const DEFAULT_THRESHOLD_PCT: f64 = 10.0;
+
+struct Args {
+    workload_paths: Vec<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold_pct: f64,
+    out: Option<PathBuf>,
+    iterations: usize,
+}
+
+/// Hand-rolled flag parsing: `cargo bench` benchmark targets get their own
+/// argv after `--`, and criterion's `black_box` is the only piece of
+/// criterion this runner actually needs, so there's no existing arg parser
+/// to defer to here.
+fn parse_args() -> Args {
+    let mut workload_paths = Vec::new();
+    let mut baseline = None;
+    let mut threshold_pct = DEFAULT_THRESHOLD_PCT;
+    let mut out = None;
+    let mut iterations = DEFAULT_ITERATIONS;
+
+    let mut raw = std::env::args().skip(1).peekable();
+    while let Some(arg) = raw.next() {
+        match arg.as_str() {
+            "--workload" => {
+                if let Some(path) = raw.next() {
+                    workload_paths.push(PathBuf::from(path));
+                }
+            }
+            "--baseline" => baseline = raw.next().map(PathBuf::from),
+            "--threshold" => {
+                if let Some(value) = raw.next() {
+                    threshold_pct = value.parse().unwrap_or(DEFAULT_THRESHOLD_PCT);
+                }
+            }
+            "--out" => out = raw.next().map(PathBuf::from),
+            "--iterations" => {
+                if let Some(value) = raw.next() {
+                    iterations = value.parse().unwrap_or(DEFAULT_ITERATIONS);
+                }
+            }
+            // Ignore criterion/cargo-bench's own flags (e.g. `--bench`) so
+            // this binary can still be invoked the usual `cargo bench` way.
+            _ => {}
+        }
+    }
+
+    if workload_paths.is_empty() {
+        workload_paths.push(PathBuf::from(DEFAULT_WORKLOAD_DIR));
+    }
+
+    Args {
+        workload_paths,
+        baseline,
+        threshold_pct,
+        out,
+        iterations,
+    }
+}
+
+fn discover_workload_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let Ok(entries) = std::fs::read_dir(path) else {
+                continue;
+            };
+            let mut dir_files: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+                .collect();
+            dir_files.sort();
+            files.extend(dir_files);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files
+}
+
+fn round_ns(value: f64) -> u64 {
+    value.round().max(0.0) as u64
+}
+
+/// Nearest-rank median/p95/p99, in nanoseconds.
+fn percentiles_ns(mut samples: Vec<u64>) -> (u64, u64, u64) {
+    if samples.is_empty() {
+        return (0, 0, 0);
+    }
+    samples.sort_unstable();
+    let pick = |p: f64| -> u64 {
+        let rank = ((samples.len() as f64) * p).ceil() as usize;
+        samples[rank.saturating_sub(1).min(samples.len() - 1)]
+    };
+    (
+        round_ns(pick(0.50) as f64),
+        round_ns(pick(0.95) as f64),
+        round_ns(pick(0.99) as f64),
+    )
+}
+
+/// Builds `workload`'s database once, then times every configured query op
+/// over `iterations` samples, reporting the ops this runner doesn't know
+/// how to replay (see `ops::run_op`) to stderr rather than silently
+/// dropping them from the report.
+fn run_workload(workload: &Workload, iterations: usize) -> Vec<OpResult> {
+    let conn = graph_gen::setup_db();
+    graph_gen::populate(&conn, &workload.graph);
+
+    let mut results = Vec::with_capacity(workload.queries.len());
+    for spec in &workload.queries {
+        let mut samples = Vec::with_capacity(iterations);
+        let mut rows_returned = 0usize;
+        let mut supported = true;
+
+        for _ in 0..iterations {
+            let started = Instant::now();
+            match ops::run_op(black_box(&conn), black_box(spec)) {
+                Ok(Some(rows)) => {
+                    samples.push(started.elapsed().as_nanos() as u64);
+                    rows_returned = rows;
+                }
+                Ok(None) => {
+                    supported = false;
+                    break;
+                }
+                Err(err) => {
+                    eprintln!(
+                        "workload {:?} op {:?}: {err}",
+                        workload.name, spec.op
+                    );
+                    supported = false;
+                    break;
+                }
+            }
+        }
+
+        if !supported {
+            eprintln!(
+                "workload {:?}: skipping unsupported/failed op {:?}",
+                workload.name, spec.op
+            );
+            continue;
+        }
+
+        let (median_ns, p95_ns, p99_ns) = percentiles_ns(samples);
+        results.push(OpResult {
+            workload: workload.name.clone(),
+            op: spec.op.clone(),
+            median_ns,
+            p95_ns,
+            p99_ns,
+            rows_returned,
+        });
+    }
+    results
+}
+
+fn main() {
+    let args = parse_args();
+    let workload_files = discover_workload_files(&args.workload_paths);
+    if workload_files.is_empty() {
+        eprintln!(
+            "no workload files found under {:?}",
+            args.workload_paths
+        );
+        std::process::exit(2);
+    }
+
+    let mut all_results = Vec::new();
+    for path in &workload_files {
+        match descriptor::load_workload(path) {
+            Ok(workload) => all_results.extend(run_workload(&workload, args.iterations)),
+            Err(err) => eprintln!("skipping {}: {err}", path.display()),
+        }
+    }
+
+    if let Some(out_path) = &args.out {
+        if let Err(err) = report::write_results(out_path, &all_results) {
+            eprintln!("writing {}: {err}", out_path.display());
+            std::process::exit(2);
+        }
+    } else {
+        for result in &all_results {
+            println!(
+                "{}::{} median={}ns p95={}ns p99={}ns rows={}",
+                result.workload,
+                result.op,
+                result.median_ns,
+                result.p95_ns,
+                result.p99_ns,
+                result.rows_returned
+            );
+        }
+    }
+
+    if let Some(baseline_path) = &args.baseline {
+        let baseline = match report::load_results(baseline_path) {
+            Ok(baseline) => baseline,
+            Err(err) => {
+                eprintln!("loading baseline {}: {err}", baseline_path.display());
+                std::process::exit(2);
+            }
+        };
+        let regressions = report::diff_against_baseline(&all_results, &baseline, args.threshold_pct);
+        if !regressions.is_empty() {
+            for regression in &regressions {
+                eprintln!(
+                    "REGRESSION {}::{} (p95): {}ns -> {}ns ({:+.1}% > {:.1}% threshold)",
+                    regression.workload,
+                    regression.op,
+                    regression.baseline_p95_ns,
+                    regression.current_p95_ns,
+                    regression.pct_change,
+                    args.threshold_pct,
+                );
+            }
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Runs one workload query-op against a connection, returning its row
+//! count for the results report. Mirrors `indexer::bench::run_query_op`'s
+//! by-name dispatch and "unsupported op" convention (report it, don't
+//! silently skip it or fake a result), extended to the op names a
+//! `benches/workload` descriptor can name.
+
+use rusqlite::Connection;
+
+use _bombe_core::errors::BombeResult;
+use _bombe_core::query::blast::get_blast_radius_impl;
+use _bombe_core::query::bounds::BoundsRange;
+use _bombe_core::query::change_impact::change_impact_impl;
+use _bombe_core::query::context::get_context_impl;
+use _bombe_core::query::data_flow::trace_data_flow_impl;
+use _bombe_core::query::references::get_references_impl;
+use _bombe_core::query::search::search_symbols_impl;
+use _bombe_core::query::structure::get_structure_impl;
+
+use super::descriptor::QueryOpSpec;
+
+/// Resolves `args.symbol_offset` (an index into symbols ordered by
+/// descending pagerank, matching `core_bench.rs::get_bench_symbol`) to a
+/// concrete `qualified_name`, falling back to the single highest-pagerank
+/// symbol if the offset is out of range.
+fn resolve_symbol_offset(conn: &Connection, offset: i64) -> String {
+    conn.query_row(
+        "SELECT qualified_name FROM symbols ORDER BY pagerank_score DESC LIMIT 1 OFFSET ?1;",
+        rusqlite::params![offset.max(0)],
+        |row| row.get(0),
+    )
+    .or_else(|_| {
+        conn.query_row(
+            "SELECT qualified_name FROM symbols ORDER BY pagerank_score DESC LIMIT 1;",
+            [],
+            |row| row.get(0),
+        )
+    })
+    .unwrap_or_default()
+}
+
+/// Heuristic row count for a query-op result: an array's length, the
+/// length of the first array-valued field of an object, 0 for `null`,
+/// otherwise 1 (a scalar/string result, e.g. `get_structure_impl`).
+fn estimate_rows_returned(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Array(items) => items.len(),
+        serde_json::Value::Object(fields) => fields
+            .values()
+            .find_map(|v| v.as_array().map(|items| items.len()))
+            .unwrap_or(1),
+        serde_json::Value::Null => 0,
+        _ => 1,
+    }
+}
+
+/// Runs `spec` against `conn` and returns `Some(rows_returned)`, or `None`
+/// for an op name this runner doesn't (yet) replay — the caller reports
+/// that as "unsupported" rather than treating it as a zero-row result.
+pub fn run_op(conn: &Connection, spec: &QueryOpSpec) -> BombeResult<Option<usize>> {
+    let args = &spec.args;
+    let result: serde_json::Value = match spec.op.as_str() {
+        "search" => {
+            let query = args.get("query").and_then(|v| v.as_str()).unwrap_or("");
+            let kind = args.get("kind").and_then(|v| v.as_str()).unwrap_or("any");
+            let file_pattern = args.get("file_pattern").and_then(|v| v.as_str());
+            let limit = args.get("limit").and_then(|v| v.as_i64()).unwrap_or(20);
+            search_symbols_impl(
+                conn,
+                query,
+                kind,
+                file_pattern,
+                &BoundsRange::unbounded(),
+                &BoundsRange::unbounded(),
+                &BoundsRange::unbounded(),
+                &BoundsRange::unbounded(),
+                limit,
+                false,
+                None,
+            )?
+        }
+        "blast_radius" => {
+            let offset = args
+                .get("symbol_offset")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let symbol = resolve_symbol_offset(conn, offset);
+            let change_type = args
+                .get("change_type")
+                .and_then(|v| v.as_str())
+                .unwrap_or("modified");
+            let depth = args.get("depth").and_then(|v| v.as_i64()).unwrap_or(3);
+            serde_json::to_value(get_blast_radius_impl(conn, &symbol, change_type, depth)?)
+                .unwrap_or(serde_json::Value::Null)
+        }
+        "references" => {
+            let offset = args
+                .get("symbol_offset")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let symbol = resolve_symbol_offset(conn, offset);
+            let direction = args
+                .get("direction")
+                .and_then(|v| v.as_str())
+                .unwrap_or("callers");
+            let depth = args.get("depth").and_then(|v| v.as_i64()).unwrap_or(2);
+            let include_source = args
+                .get("include_source")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            get_references_impl(conn, &symbol, direction, depth, include_source)?
+        }
+        "change_impact" => {
+            let offset = args
+                .get("symbol_offset")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let symbol = resolve_symbol_offset(conn, offset);
+            let change_type = args
+                .get("change_type")
+                .and_then(|v| v.as_str())
+                .unwrap_or("behavior");
+            let depth = args.get("depth").and_then(|v| v.as_i64()).unwrap_or(3);
+            change_impact_impl(conn, &symbol, change_type, depth)?
+        }
+        "data_flow" => {
+            let offset = args
+                .get("symbol_offset")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let symbol = resolve_symbol_offset(conn, offset);
+            let direction = args
+                .get("direction")
+                .and_then(|v| v.as_str())
+                .unwrap_or("both");
+            let depth = args.get("depth").and_then(|v| v.as_i64()).unwrap_or(3);
+            let relationships = args
+                .get("relationships")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_else(|| vec!["CALLS".to_string()]);
+            trace_data_flow_impl(conn, &symbol, direction, depth, &relationships)?
+        }
+        "context" => {
+            let query = args.get("query").and_then(|v| v.as_str()).unwrap_or("");
+            let entry_points: Vec<String> = args
+                .get("entry_points")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let token_budget = args
+                .get("token_budget")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(4000);
+            let expansion_depth = args.get("depth").and_then(|v| v.as_i64()).unwrap_or(2);
+            get_context_impl(
+                conn,
+                query,
+                &entry_points,
+                token_budget,
+                false,
+                expansion_depth,
+                false,
+                0.0,
+                None,
+                0.85,
+                None,
+                None,
+                None,
+                0.5,
+                "rrf",
+                60.0,
+                None,
+                0.0,
+                "",
+            )?
+        }
+        "structure" => {
+            let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+            let token_budget = args
+                .get("token_budget")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(4000);
+            let include_signatures = args
+                .get("include_signatures")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let tokenizer = args.get("tokenizer").and_then(|v| v.as_str()).unwrap_or("");
+            let file_order = args
+                .get("file_order")
+                .and_then(|v| v.as_str())
+                .unwrap_or("path");
+            serde_json::Value::String(get_structure_impl(
+                conn,
+                path,
+                token_budget,
+                include_signatures,
+                tokenizer,
+                file_order,
+            )?)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(estimate_rows_returned(&result)))
+}
@@ -0,0 +1,109 @@
+//! Results report for one `benches/workload` run, plus baseline-vs-current
+//! regression diffing.
+//!
+//! Follows the crate's established convention (no `serde` derive macros
+//! anywhere in `bombe-core`) of representing results as dynamic
+//! `serde_json::Value` via hand-written `to_json`/`from_json`.
+
+/// One `{workload, op, median_ns, p95_ns, p99_ns, rows_returned}` measurement.
+pub struct OpResult {
+    pub workload: String,
+    pub op: String,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+    pub rows_returned: usize,
+}
+
+impl OpResult {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "workload": self.workload,
+            "op": self.op,
+            "median_ns": self.median_ns,
+            "p95_ns": self.p95_ns,
+            "p99_ns": self.p99_ns,
+            "rows_returned": self.rows_returned,
+        })
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            workload: value.get("workload")?.as_str()?.to_string(),
+            op: value.get("op")?.as_str()?.to_string(),
+            median_ns: value.get("median_ns")?.as_u64()?,
+            p95_ns: value.get("p95_ns")?.as_u64()?,
+            // Older baseline files predate p99 tracking — fall back to
+            // p95_ns rather than rejecting the whole baseline.
+            p99_ns: value
+                .get("p99_ns")
+                .and_then(|v| v.as_u64())
+                .unwrap_or_else(|| value.get("p95_ns").and_then(|v| v.as_u64()).unwrap_or(0)),
+            rows_returned: value.get("rows_returned")?.as_u64()? as usize,
+        })
+    }
+}
+
+pub fn write_results(path: &std::path::Path, results: &[OpResult]) -> std::io::Result<()> {
+    let json: Vec<serde_json::Value> = results.iter().map(OpResult::to_json).collect();
+    std::fs::write(path, serde_json::Value::Array(json).to_string())
+}
+
+pub fn load_results(path: &std::path::Path) -> Result<Vec<OpResult>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("reading {}: {e}", path.display()))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("parsing {}: {e}", path.display()))?;
+    let entries = value
+        .as_array()
+        .ok_or_else(|| format!("{}: expected a JSON array of results", path.display()))?;
+    Ok(entries.iter().filter_map(OpResult::from_json).collect())
+}
+
+/// One op whose `p95_ns` grew by more than the configured threshold
+/// compared to its baseline measurement.
+pub struct Regression {
+    pub workload: String,
+    pub op: String,
+    pub baseline_p95_ns: u64,
+    pub current_p95_ns: u64,
+    pub pct_change: f64,
+}
+
+/// Compares `current` against `baseline`, matching ops by `(workload, op)`,
+/// and returns every op whose p95 latency regressed by more than
+/// `threshold_pct` percent. p95 (not the median) is the gating signal,
+/// since that's what the tail-latency regressions this harness exists to
+/// catch actually show up in. An op present in `current` but missing from
+/// `baseline` (a newly added workload/op) is not a regression and is
+/// silently skipped — there's nothing to compare it against yet.
+pub fn diff_against_baseline(
+    current: &[OpResult],
+    baseline: &[OpResult],
+    threshold_pct: f64,
+) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+    for result in current {
+        let Some(base) = baseline
+            .iter()
+            .find(|b| b.workload == result.workload && b.op == result.op)
+        else {
+            continue;
+        };
+        if base.p95_ns == 0 {
+            continue;
+        }
+        let pct_change =
+            (result.p95_ns as f64 - base.p95_ns as f64) / base.p95_ns as f64 * 100.0;
+        if pct_change > threshold_pct {
+            regressions.push(Regression {
+                workload: result.workload.clone(),
+                op: result.op.clone(),
+                baseline_p95_ns: base.p95_ns,
+                current_p95_ns: result.p95_ns,
+                pct_change,
+            });
+        }
+    }
+    regressions
+}
@@ -0,0 +1,92 @@
+//! Thread-local allocation counters for opt-in per-shard profiling (see
+//! `query::federated::executor`), in the style of the `stats_alloc` crate:
+//! wrap the system allocator so callers can snapshot per-thread counters
+//! immediately before and after a region of interest and diff the two to
+//! get that region's allocation activity, without any global lock or
+//! cross-thread interference.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static BYTES_ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static REALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Global allocator that forwards every call to [`System`] while
+/// incrementing the thread-local counters [`Region::snapshot`] reads.
+/// Installed crate-wide via `#[global_allocator]` in `lib.rs`; the
+/// bookkeeping is a couple of non-atomic `Cell` increments per call, so it
+/// costs essentially nothing even when no profiling is in progress —
+/// opting into profiling only changes whether a caller bothers to snapshot
+/// and diff these counters, not whether they're kept.
+pub struct StatsAlloc;
+
+unsafe impl GlobalAlloc for StatsAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        BYTES_ALLOCATED.with(|bytes| bytes.set(bytes.get() + layout.size() as u64));
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCATIONS.with(|count| count.set(count.get() + 1));
+        let grown = (new_size as u64).saturating_sub(layout.size() as u64);
+        BYTES_ALLOCATED.with(|bytes| bytes.set(bytes.get() + grown));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// A point-in-time snapshot of the calling thread's allocation counters.
+/// Diff an earlier snapshot against a later one with [`Region::delta`] to
+/// get the allocation activity of whatever ran in between — the same
+/// two-snapshot pattern as the `stats_alloc` crate's `Region`, reimplemented
+/// against thread-local counters so concurrent shard threads (see
+/// `query::federated::executor::fan_out_with_timeout`) never see each
+/// other's allocations.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Region {
+    bytes_allocated: u64,
+    allocations: u64,
+    reallocations: u64,
+}
+
+impl Region {
+    pub fn snapshot() -> Self {
+        Self {
+            bytes_allocated: BYTES_ALLOCATED.with(Cell::get),
+            allocations: ALLOCATIONS.with(Cell::get),
+            reallocations: REALLOCATIONS.with(Cell::get),
+        }
+    }
+
+    /// Allocation activity between `self` (an earlier snapshot) and `later`.
+    pub fn delta(&self, later: &Region) -> AllocDelta {
+        AllocDelta {
+            bytes_allocated: later.bytes_allocated.saturating_sub(self.bytes_allocated),
+            allocations: later.allocations.saturating_sub(self.allocations),
+            reallocations: later.reallocations.saturating_sub(self.reallocations),
+        }
+    }
+}
+
+/// The allocation activity of one [`Region::snapshot`] diff.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AllocDelta {
+    pub bytes_allocated: u64,
+    pub allocations: u64,
+    pub reallocations: u64,
+}
+
+impl AllocDelta {
+    pub fn accumulate(&mut self, other: AllocDelta) {
+        self.bytes_allocated += other.bytes_allocated;
+        self.allocations += other.allocations;
+        self.reallocations += other.reallocations;
+    }
+}
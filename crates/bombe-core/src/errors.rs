@@ -15,6 +15,9 @@ pub enum BombeError {
     #[error("Query error: {0}")]
     Query(String),
 
+    #[error("Foreign database: {0}")]
+    ForeignDatabase(String),
+
     #[error("Parse error: {0}")]
     Parse(String),
 
@@ -36,6 +39,7 @@ impl From<BombeError> for PyErr {
             }
             BombeError::Index(_) => PyRuntimeError::new_err(err.to_string()),
             BombeError::Query(_) => PyValueError::new_err(err.to_string()),
+            BombeError::ForeignDatabase(_) => PyValueError::new_err(err.to_string()),
             BombeError::Parse(_) => PyValueError::new_err(err.to_string()),
             BombeError::Io(_) => PyIOError::new_err(err.to_string()),
             BombeError::Json(_) => PyValueError::new_err(err.to_string()),
@@ -0,0 +1,281 @@
+//! Indexing/query benchmark harness driven by a JSON workload file.
+//!
+//! Mirrors `query::eval`'s workload-replay pattern but targets the
+//! indexing pipeline itself: for each worker count in the workload, runs
+//! [`run_index_impl`] end-to-end against `repo_root`/`db_path` and records
+//! its per-phase timings, then replays the workload's configured query
+//! operations against the freshly-indexed database and reports per-op
+//! latency percentiles — so a `parallel_extract`/`scan_repo_files`/query
+//! backend regression shows up as a number instead of a guess.
+
+use std::time::Instant;
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+
+use crate::errors::{BombeError, BombeResult};
+use crate::indexer::pipeline::run_index_impl;
+use crate::store::database::Database;
+
+/// One `workload.queries[]` entry: an operation name plus its JSON args,
+/// replayed `repeat` times against the database left by the last worker
+/// count in the sweep.
+struct QueryOp {
+    op: String,
+    args: serde_json::Value,
+}
+
+fn parse_query_ops(workload: &serde_json::Value) -> Vec<QueryOp> {
+    workload
+        .get("queries")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let op = entry.get("op")?.as_str()?.to_string();
+            let args = entry.get("args").cloned().unwrap_or_else(|| serde_json::json!({}));
+            Some(QueryOp { op, args })
+        })
+        .collect()
+}
+
+/// Runs one query op against `conn` and discards its result — only the
+/// caller-measured latency matters here. Returns `Ok(false)` for an
+/// unrecognized op name, notably `federated_plan`: a federated plan needs
+/// a live Python `ShardRouter`/`FederatedQueryPlanner` pair, which a
+/// static JSON workload file can't express, so it's reported as
+/// unsupported rather than silently skipped or faked.
+fn run_query_op(conn: &Connection, op: &QueryOp) -> BombeResult<bool> {
+    match op.op.as_str() {
+        "get_structure" => {
+            let path = op.args.get("path").and_then(|v| v.as_str()).unwrap_or("");
+            let token_budget = op
+                .args
+                .get("token_budget")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(4000);
+            let include_signatures = op
+                .args
+                .get("include_signatures")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let tokenizer = op.args.get("tokenizer").and_then(|v| v.as_str()).unwrap_or("");
+            let file_order = op
+                .args
+                .get("file_order")
+                .and_then(|v| v.as_str())
+                .unwrap_or("path");
+            crate::query::structure::get_structure_impl(
+                conn,
+                path,
+                token_budget,
+                include_signatures,
+                tokenizer,
+                file_order,
+            )?;
+            Ok(true)
+        }
+        "trace_data_flow" => {
+            let symbol_name = op
+                .args
+                .get("symbol_name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let direction = op
+                .args
+                .get("direction")
+                .and_then(|v| v.as_str())
+                .unwrap_or("both");
+            let max_depth = op.args.get("max_depth").and_then(|v| v.as_i64()).unwrap_or(3);
+            let relationships = query_op_relationships(op);
+            crate::query::data_flow::trace_data_flow_impl(
+                conn,
+                symbol_name,
+                direction,
+                max_depth,
+                &relationships,
+            )?;
+            Ok(true)
+        }
+        "trace_data_flow_between" => {
+            let source = op.args.get("source").and_then(|v| v.as_str()).unwrap_or("");
+            let target = op.args.get("target").and_then(|v| v.as_str()).unwrap_or("");
+            let max_depth = op.args.get("max_depth").and_then(|v| v.as_i64()).unwrap_or(6);
+            let relationships = query_op_relationships(op);
+            crate::query::data_flow::trace_data_flow_between_impl(
+                conn,
+                source,
+                target,
+                &relationships,
+                max_depth,
+            )?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+fn query_op_relationships(op: &QueryOp) -> Vec<String> {
+    op.args
+        .get("relationships")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_else(|| vec!["CALLS".to_string()])
+}
+
+fn round_ms(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Nearest-rank p50/p95/p99 over a latency sample, in milliseconds.
+fn percentiles(mut samples: Vec<f64>) -> serde_json::Value {
+    if samples.is_empty() {
+        return serde_json::json!({"p50_ms": 0.0, "p95_ms": 0.0, "p99_ms": 0.0, "samples": 0});
+    }
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let pick = |p: f64| -> f64 {
+        let rank = ((samples.len() as f64) * p).ceil() as usize;
+        samples[rank.saturating_sub(1).min(samples.len() - 1)]
+    };
+    serde_json::json!({
+        "p50_ms": round_ms(pick(0.50)),
+        "p95_ms": round_ms(pick(0.95)),
+        "p99_ms": round_ms(pick(0.99)),
+        "samples": samples.len(),
+    })
+}
+
+/// Replays the workload at `workload_path`: sweeps `worker_counts`,
+/// re-indexing `repo_root` into `db_path` at each point and recording
+/// per-phase timings via [`run_index_impl`] (so the scaling curve reflects
+/// `scan_repo_files`/`parallel_extract`/persist/pagerank separately, not
+/// just a single wall-clock number), then replays `queries` `repeat` times
+/// against the database left by the last worker count and reports latency
+/// percentiles per op. Returns `{scaling, queries, summary}`.
+pub fn run_bench_impl(workload_path: &str) -> BombeResult<serde_json::Value> {
+    let content = std::fs::read_to_string(workload_path)?;
+    let workload: serde_json::Value = serde_json::from_str(&content)?;
+
+    let repo_root = workload
+        .get("repo_root")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| BombeError::Query("workload file must set \"repo_root\"".to_string()))?;
+    let db_path = workload
+        .get("db_path")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| BombeError::Query("workload file must set \"db_path\"".to_string()))?;
+    let worker_counts: Vec<i64> = workload
+        .get("worker_counts")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_i64()).collect::<Vec<i64>>())
+        .filter(|counts| !counts.is_empty())
+        .unwrap_or_else(|| vec![4]);
+    let repeat = workload
+        .get("repeat")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(5)
+        .max(1) as usize;
+    let query_ops = parse_query_ops(&workload);
+
+    let mut scaling = Vec::with_capacity(worker_counts.len());
+    let mut conn: Option<Connection> = None;
+
+    for workers in &worker_counts {
+        let run_id = format!("bench-{workers}");
+        let run = run_index_impl(repo_root, db_path, &run_id, *workers)?;
+
+        scaling.push(serde_json::json!({
+            "workers": workers,
+            "files_seen": run.stats.files_seen,
+            "files_indexed": run.stats.files_indexed,
+            "files_deleted": run.files_deleted,
+            "symbols_indexed": run.stats.symbols_indexed,
+            "edges_indexed": run.stats.edges_indexed,
+            "scan_ms": run.timings.scan_ms,
+            "extract_ms": run.timings.extract_ms,
+            "persist_ms": run.timings.persist_ms,
+            "pagerank_ms": run.timings.pagerank_ms,
+            "elapsed_ms": run.stats.elapsed_ms,
+        }));
+
+        let db = Database::new(std::path::PathBuf::from(db_path), None, None)
+            .map_err(|e| BombeError::Index(e.to_string()))?;
+        conn = Some(db.connect_internal()?);
+    }
+
+    let conn = conn.ok_or_else(|| {
+        BombeError::Index("workload must set a non-empty \"worker_counts\"".to_string())
+    })?;
+
+    let mut query_reports = Vec::with_capacity(query_ops.len());
+    let mut unsupported = 0i64;
+    for (index, op) in query_ops.iter().enumerate() {
+        let mut samples = Vec::with_capacity(repeat);
+        let mut supported = true;
+        for _ in 0..repeat {
+            let started = Instant::now();
+            match run_query_op(&conn, op)? {
+                true => samples.push(started.elapsed().as_secs_f64() * 1000.0),
+                false => {
+                    supported = false;
+                    break;
+                }
+            }
+        }
+
+        if !supported {
+            unsupported += 1;
+            query_reports.push(serde_json::json!({
+                "index": index,
+                "op": op.op,
+                "status": "unsupported",
+            }));
+            continue;
+        }
+
+        let mut report = percentiles(samples);
+        report["index"] = serde_json::json!(index);
+        report["op"] = serde_json::json!(op.op);
+        report["status"] = serde_json::json!("ok");
+        query_reports.push(report);
+    }
+
+    let summary = format!(
+        "Indexed {} across worker count(s) [{}], replayed {} quer{} from the workload ({} unsupported).",
+        repo_root,
+        worker_counts
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+        query_ops.len(),
+        if query_ops.len() == 1 { "y" } else { "ies" },
+        unsupported,
+    );
+
+    Ok(serde_json::json!({
+        "scaling": scaling,
+        "queries": query_reports,
+        "summary": summary,
+    }))
+}
+
+/// Benchmarking entry point exposed to Python: replays the workload JSON
+/// file at `workload_path` and returns a `{scaling, queries, summary}`
+/// report, so maintainers can track indexing/query throughput across
+/// changes the same way `query::eval::run_workload` tracks retrieval
+/// quality.
+#[pyfunction]
+pub fn run_bench(py: Python<'_>, workload_path: &str) -> PyResult<PyObject> {
+    let result = run_bench_impl(workload_path)?;
+    let json_str = serde_json::to_string(&result)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let json_module = py.import("json")?;
+    json_module
+        .call_method1("loads", (json_str,))
+        .map(|o| o.into())
+}
@@ -2,15 +2,18 @@
 //!
 //! Port of the Python `callgraph.py` (594 LOC). Constructs call-graph edges
 //! from source text, file-level symbols, and a global candidate symbol table.
-//! Resolution is cascading: class-scoped > type-hinted > alias > receiver >
-//! qualified-name > same-file > import-scoped > global.
+//! Resolution scores every name-matching candidate against independent
+//! signals (class/self scope, type hints, alias, receiver, same-file,
+//! import-scoped, wildcard import, arity) and ranks the result — see
+//! [`resolve_targets`] — rather than returning on the first matching
+//! strategy in a fixed cascade.
 
 use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
 use regex::Regex;
 
-use crate::indexer::symbols::ExtractedSymbol;
+use crate::indexer::symbols::{ExtractedParameter, ExtractedSymbol};
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -22,6 +25,29 @@ pub struct CallSite {
     pub callee_name: String,
     pub line_number: i64,
     pub receiver_name: Option<String>,
+    /// The full receiver chain for a chained call, outermost-first — for
+    /// `a.b().c()`, the call to `c` carries `["a", "b"]`. Only the AST
+    /// extraction path can see past the innermost dot, so the regex path
+    /// always leaves this empty even when it does find a `receiver_name`.
+    pub receiver_chain: Vec<String>,
+    /// Number of top-level arguments passed at this call-site, feeding the
+    /// arity signal in [`resolve_targets`]. `None` when it couldn't be
+    /// determined — e.g. the regex pass's argument list spans past the end
+    /// of the line it's scanning.
+    pub argument_count: Option<i64>,
+}
+
+/// Selects how [`build_call_edges`] turns source text into [`CallSite`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionMode {
+    /// Walk the tree-sitter CST when a grammar is loaded for the language,
+    /// falling back to [`extract_regex_calls`] otherwise (e.g. Python, or
+    /// any language without a registered grammar).
+    Ast,
+    /// Always use the line-scanning regex pass, even for languages with a
+    /// loaded grammar — mainly useful for benchmarking the two against each
+    /// other, or as an escape hatch if an AST walk misbehaves on a file.
+    Regex,
 }
 
 /// A resolved call edge between two symbols.
@@ -45,11 +71,13 @@ static CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"\b(?:([A-Za-z_][A-Za-z0-9_]*)\s*\.\s*)?([A-Za-z_][A-Za-z0-9_]*)\s*\(").unwrap()
 });
 
-static PY_FROM_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"from\s+([A-Za-z0-9_\.]+)\s+import").unwrap());
-
-static PY_IMPORT_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"import\s+([A-Za-z0-9_\.]+)").unwrap());
+// Like `CALL_RE`, but also recognizes `::` as a receiver separator, so
+// `Type::new()` (associated/static calls) and `mod::func()` (path calls)
+// are captured alongside plain `foo()` and method calls `x.do_thing()`.
+static RUST_CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:([A-Za-z_][A-Za-z0-9_]*)\s*(?:::|\.)\s*)?([A-Za-z_][A-Za-z0-9_]*)\s*\(")
+        .unwrap()
+});
 
 static PY_FROM_ALIAS_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^\s*from\s+([A-Za-z0-9_\.]+)\s+import\s+(.+)$").unwrap());
@@ -80,7 +108,7 @@ static GO_SHORT_DECL_TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^\s*([A-Za-z_][A-Za-z0-9_]*)\s*:=\s*&?([A-Za-z_][A-Za-z0-9_]*)\s*\{").unwrap()
 });
 
-// Additional import patterns used in _import_hints
+// Additional import patterns used by ImportEnv
 static TS_IMPORT_HINT_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r#"import(?:\s+type)?\s+.*?\s+from\s+['"]([^'"]+)['"]"#).unwrap());
 
@@ -89,7 +117,6 @@ static JAVA_IMPORT_RE: LazyLock<Regex> =
 
 static GO_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#""([^"]+)""#).unwrap());
 
-// Additional import patterns used in _import_aliases
 static TS_NAMED_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r#"^\s*import(?:\s+type)?\s+\{([^}]*)\}\s+from\s+['"][^'"]+['"]"#).unwrap()
 });
@@ -99,6 +126,25 @@ static TS_DEFAULT_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
         .unwrap()
 });
 
+static RUST_USE_BRACE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*use\s+([A-Za-z0-9_:]+)::\{([^}]*)\}\s*;?\s*$").unwrap());
+
+static RUST_USE_SIMPLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*use\s+([A-Za-z0-9_:]+)::([A-Za-z_][A-Za-z0-9_]*)(?:\s+as\s+([A-Za-z_][A-Za-z0-9_]*))?\s*;?\s*$")
+        .unwrap()
+});
+
+static RUST_USE_GLOB_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*use\s+([A-Za-z0-9_:]+)::\*\s*;?\s*$").unwrap());
+
+static GO_DOT_IMPORT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^import\s+\.\s+"([^"]+)"\s*$"#).unwrap());
+
+static TS_NAMESPACE_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*import\s+\*\s+as\s+[A-Za-z_][A-Za-z0-9_]*\s+from\s+['"]([^'"]+)['"]"#)
+        .unwrap()
+});
+
 // ---------------------------------------------------------------------------
 // Call keywords to skip
 // ---------------------------------------------------------------------------
@@ -107,7 +153,20 @@ static TS_DEFAULT_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
 fn is_call_keyword(name: &str) -> bool {
     matches!(
         name,
-        "if" | "for" | "while" | "switch" | "return" | "new" | "function" | "class" | "catch"
+        "if" | "for"
+            | "while"
+            | "switch"
+            | "return"
+            | "new"
+            | "function"
+            | "class"
+            | "catch"
+            | "fn"
+            | "let"
+            | "match"
+            | "loop"
+            | "impl"
+            | "trait"
     )
 }
 
@@ -118,7 +177,7 @@ fn is_call_keyword(name: &str) -> bool {
 /// Compute a CRC32 hash of the qualified name, masked to a positive i64.
 ///
 /// This matches the Python `int(zlib.crc32(qualified_name.encode('utf-8')) & 0x7FFFFFFF)`.
-fn symbol_id(qualified_name: &str) -> i64 {
+pub(crate) fn symbol_id(qualified_name: &str) -> i64 {
     // IEEE CRC32 — same table as zlib
     let crc = crc32_ieee(qualified_name.as_bytes());
     (crc & 0x7FFF_FFFF) as i64
@@ -147,12 +206,20 @@ fn crc32_ieee(data: &[u8]) -> u32 {
 /// Extract call-sites from source text using regex scanning.
 ///
 /// Skips language keywords and lines that look like definitions (prefixed
-/// with `def`, `function`, `func`, `class`, or `new`).
-fn extract_regex_calls(source: &str, _language: &str) -> Vec<CallSite> {
+/// with `def`, `function`, `func`, `class`, or `new`). Comment and
+/// string-literal content is masked via [`mask_noncode`] first, so a
+/// function name mentioned in a docstring or comment doesn't read as a call.
+fn extract_regex_calls(source: &str, language: &str) -> Vec<CallSite> {
+    let source = mask_noncode(source, language);
+    let call_re: &Regex = if language == "rust" {
+        &RUST_CALL_RE
+    } else {
+        &CALL_RE
+    };
     let mut callsites = Vec::new();
     for (index, line) in source.lines().enumerate() {
         let line_number = (index as i64) + 1;
-        for caps in CALL_RE.captures_iter(line) {
+        for caps in call_re.captures_iter(line) {
             let receiver = caps.get(1).map(|m| m.as_str().to_string());
             let name = match caps.get(2) {
                 Some(m) => m.as_str(),
@@ -171,283 +238,976 @@ fn extract_regex_calls(source: &str, _language: &str) -> Vec<CallSite> {
                 || prefix.ends_with("func")
                 || prefix.ends_with("class")
                 || prefix.ends_with("new")
+                || prefix.ends_with("fn")
             {
                 continue;
             }
 
+            let open_paren_idx = caps.get(0).unwrap().end() - 1;
             callsites.push(CallSite {
                 callee_name: name.to_string(),
                 line_number,
                 receiver_name: receiver,
+                receiver_chain: Vec::new(),
+                argument_count: count_regex_call_arguments(line, open_paren_idx),
             });
         }
     }
     callsites
 }
 
+/// Counts the top-level, comma-separated arguments in a call's parenthesized
+/// argument list, scanning forward from the `(` at `open_paren_idx` on the
+/// same line. Returns `None` when the matching `)` isn't found on this line
+/// — the regex pass is line-scoped, so a call whose arguments wrap onto
+/// another line simply contributes no arity signal.
+fn count_regex_call_arguments(line: &str, open_paren_idx: usize) -> Option<i64> {
+    let bytes = line.as_bytes();
+    let mut depth = 0i32;
+    let mut count = 0i64;
+    let mut saw_any_char = false;
+    let mut idx = open_paren_idx;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(if saw_any_char { count + 1 } else { 0 });
+                }
+            }
+            b',' if depth == 1 => count += 1,
+            c if depth >= 1 && !c.is_ascii_whitespace() => saw_any_char = true,
+            _ => {}
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// Extract call-sites for `language` using `mode`, falling back to the
+/// regex pass when [`ExtractionMode::Ast`] is requested but no tree-sitter
+/// grammar is loaded (Python, or any language without a registered
+/// grammar — see [`crate::indexer::parser::parse_source_native`]).
+fn extract_calls(source: &str, language: &str, mode: ExtractionMode) -> Vec<CallSite> {
+    if mode == ExtractionMode::Ast {
+        if let Some(tree) = crate::indexer::parser::parse_source_native(source, language) {
+            return extract_ast_calls(&tree, source, language);
+        }
+    }
+    extract_regex_calls(source, language)
+}
+
 // ---------------------------------------------------------------------------
-// Import hints
+// AST-based call extraction
 // ---------------------------------------------------------------------------
 
-/// Extract module name hints from all import styles in source.
+/// Walk `tree`'s root, emitting one [`CallSite`] per call expression.
 ///
-/// Returns a set of module names and their trailing components (e.g.
-/// `foo.bar` yields both `foo.bar` and `bar`).
-fn import_hints(source: &str) -> HashSet<String> {
-    let mut hints = HashSet::new();
-
-    for line in source.lines() {
-        let normalized = line.trim();
-
-        // Python: from X import ...
-        if let Some(caps) = PY_FROM_RE.captures(normalized) {
-            if let Some(m) = caps.get(1) {
-                let value = m.as_str().trim();
-                hints.insert(value.to_string());
-                if let Some(last) = value.rsplit('.').next() {
-                    hints.insert(last.to_string());
+/// Unlike [`extract_regex_calls`], this never mistakes `if (...)`, `for
+/// (...)`, or a `def`/`function`/`new` declaration for a call — those are
+/// distinct node kinds in the grammar, not just a name followed by `(` — so
+/// there is no keyword denylist or definition-prefix check to maintain here.
+fn extract_ast_calls(tree: &tree_sitter::Tree, source: &str, language: &str) -> Vec<CallSite> {
+    let mut callsites = Vec::new();
+    visit_ast_calls(
+        tree.root_node(),
+        source.as_bytes(),
+        language,
+        &mut callsites,
+    );
+    callsites
+}
+
+fn visit_ast_calls(
+    node: tree_sitter::Node,
+    source: &[u8],
+    language: &str,
+    out: &mut Vec<CallSite>,
+) {
+    if let Some(callsite) = call_site_for_node(node, source, language) {
+        out.push(callsite);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_ast_calls(child, source, language, out);
+    }
+}
+
+fn node_text<'a>(node: tree_sitter::Node, source: &'a [u8]) -> Option<&'a str> {
+    node.utf8_text(source).ok()
+}
+
+/// Number of named children (i.e. actual argument expressions, not the
+/// surrounding parens/commas) under a call node's `arguments` field.
+fn ast_argument_count(node: tree_sitter::Node) -> Option<i64> {
+    node.child_by_field_name("arguments")
+        .map(|args| args.named_child_count() as i64)
+}
+
+fn call_site_for_node(node: tree_sitter::Node, source: &[u8], language: &str) -> Option<CallSite> {
+    let line_number = (node.start_position().row + 1) as i64;
+    match language {
+        // `method_invocation` covers both `foo()` and `a.b.foo()` — the
+        // `object` field is absent for the former, present for the latter.
+        "java" => {
+            if node.kind() != "method_invocation" {
+                return None;
+            }
+            let callee_name = node_text(node.child_by_field_name("name")?, source)?.to_string();
+            let chain = node
+                .child_by_field_name("object")
+                .map(|object| java_receiver_chain(object, source))
+                .unwrap_or_default();
+            Some(CallSite {
+                callee_name,
+                line_number,
+                receiver_name: chain.last().cloned(),
+                receiver_chain: chain,
+                argument_count: ast_argument_count(node),
+            })
+        }
+        // `call_expression`'s `function` field is either a bare identifier
+        // (`foo()`) or a `member_expression` (`a.b.foo()`, or `a().b.foo()`
+        // when the receiver chain itself contains a call).
+        "typescript" => {
+            if node.kind() != "call_expression" {
+                return None;
+            }
+            let function = node.child_by_field_name("function")?;
+            let (callee_name, chain) = match function.kind() {
+                "member_expression" => {
+                    let name =
+                        node_text(function.child_by_field_name("property")?, source)?.to_string();
+                    let chain = js_receiver_chain(function.child_by_field_name("object")?, source);
+                    (name, chain)
                 }
+                "identifier" => (node_text(function, source)?.to_string(), Vec::new()),
+                _ => return None,
+            };
+            Some(CallSite {
+                callee_name,
+                line_number,
+                receiver_name: chain.last().cloned(),
+                receiver_chain: chain,
+                argument_count: ast_argument_count(node),
+            })
+        }
+        // `call_expression`'s `function` field is either a bare identifier
+        // (`foo()`) or a `selector_expression` (`a.b.Foo()`), the same
+        // shape as TypeScript's `member_expression` but with Go's own field
+        // names (`operand`/`field` instead of `object`/`property`).
+        "go" => {
+            if node.kind() != "call_expression" {
+                return None;
             }
+            let function = node.child_by_field_name("function")?;
+            let (callee_name, chain) = match function.kind() {
+                "selector_expression" => {
+                    let name =
+                        node_text(function.child_by_field_name("field")?, source)?.to_string();
+                    let chain = go_receiver_chain(function.child_by_field_name("operand")?, source);
+                    (name, chain)
+                }
+                "identifier" => (node_text(function, source)?.to_string(), Vec::new()),
+                _ => return None,
+            };
+            Some(CallSite {
+                callee_name,
+                line_number,
+                receiver_name: chain.last().cloned(),
+                receiver_chain: chain,
+                argument_count: ast_argument_count(node),
+            })
         }
+        _ => None,
+    }
+}
 
-        // Python: import X (only if line starts with "import ")
-        if let Some(caps) = PY_IMPORT_RE.captures(normalized) {
-            if normalized.starts_with("import ") {
-                if let Some(m) = caps.get(1) {
-                    let value = m.as_str().trim();
-                    hints.insert(value.to_string());
-                    if let Some(last) = value.rsplit('.').next() {
-                        hints.insert(last.to_string());
-                    }
+/// Decomposes a Java receiver expression into its dotted chain,
+/// outermost-first — `a.b.c()`'s `object` (`a.b`) yields `["a", "b"]`, and
+/// a chained call like `a.b().c()`'s `object` (`a.b()`) yields `["a", "b"]`
+/// too, recursing through the nested `method_invocation`.
+fn java_receiver_chain(node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut chain = Vec::new();
+    collect_java_chain(node, source, &mut chain);
+    chain
+}
+
+fn collect_java_chain(node: tree_sitter::Node, source: &[u8], chain: &mut Vec<String>) {
+    match node.kind() {
+        "field_access" => {
+            if let Some(object) = node.child_by_field_name("object") {
+                collect_java_chain(object, source, chain);
+            }
+            if let Some(field) = node.child_by_field_name("field") {
+                if let Some(text) = node_text(field, source) {
+                    chain.push(text.to_string());
                 }
             }
         }
-
-        // TypeScript: import ... from '...'
-        if let Some(caps) = TS_IMPORT_HINT_RE.captures(normalized) {
-            if let Some(m) = caps.get(1) {
-                let value = m.as_str().trim();
-                hints.insert(value.to_string());
-                if let Some(last) = value.rsplit('/').next() {
-                    hints.insert(last.to_string());
+        "method_invocation" => {
+            if let Some(object) = node.child_by_field_name("object") {
+                collect_java_chain(object, source, chain);
+            }
+            if let Some(name) = node.child_by_field_name("name") {
+                if let Some(text) = node_text(name, source) {
+                    chain.push(text.to_string());
                 }
             }
         }
+        _ => {
+            if let Some(text) = node_text(node, source) {
+                chain.push(text.to_string());
+            }
+        }
+    }
+}
 
-        // Java: import X;
-        if let Some(caps) = JAVA_IMPORT_RE.captures(normalized) {
-            if let Some(m) = caps.get(1) {
-                let value = m.as_str().trim().trim_end_matches(".*");
-                hints.insert(value.to_string());
-                if let Some(last) = value.rsplit('.').next() {
-                    hints.insert(last.to_string());
+/// Same idea as [`java_receiver_chain`] but for TypeScript/JavaScript's
+/// `member_expression` (`object`/`property`) and `call_expression` nodes.
+fn js_receiver_chain(node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut chain = Vec::new();
+    collect_js_chain(node, source, &mut chain);
+    chain
+}
+
+fn collect_js_chain(node: tree_sitter::Node, source: &[u8], chain: &mut Vec<String>) {
+    match node.kind() {
+        "member_expression" => {
+            if let Some(object) = node.child_by_field_name("object") {
+                collect_js_chain(object, source, chain);
+            }
+            if let Some(property) = node.child_by_field_name("property") {
+                if let Some(text) = node_text(property, source) {
+                    chain.push(text.to_string());
                 }
             }
         }
+        "call_expression" => {
+            if let Some(function) = node.child_by_field_name("function") {
+                collect_js_chain(function, source, chain);
+            }
+        }
+        _ => {
+            if let Some(text) = node_text(node, source) {
+                chain.push(text.to_string());
+            }
+        }
+    }
+}
 
-        // Go: import "..."
-        if normalized.starts_with("import ") && normalized.contains('"') {
-            if let Some(caps) = GO_IMPORT_RE.captures(normalized) {
-                if let Some(m) = caps.get(1) {
-                    let value = m.as_str().trim();
-                    hints.insert(value.to_string());
-                    if let Some(last) = value.rsplit('/').next() {
-                        hints.insert(last.to_string());
-                    }
+/// Same idea as [`java_receiver_chain`] but for Go's `selector_expression`
+/// (`operand`/`field`) and `call_expression` nodes.
+fn go_receiver_chain(node: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut chain = Vec::new();
+    collect_go_chain(node, source, &mut chain);
+    chain
+}
+
+fn collect_go_chain(node: tree_sitter::Node, source: &[u8], chain: &mut Vec<String>) {
+    match node.kind() {
+        "selector_expression" => {
+            if let Some(operand) = node.child_by_field_name("operand") {
+                collect_go_chain(operand, source, chain);
+            }
+            if let Some(field) = node.child_by_field_name("field") {
+                if let Some(text) = node_text(field, source) {
+                    chain.push(text.to_string());
                 }
             }
         }
+        "call_expression" => {
+            if let Some(function) = node.child_by_field_name("function") {
+                collect_go_chain(function, source, chain);
+            }
+        }
+        _ => {
+            if let Some(text) = node_text(node, source) {
+                chain.push(text.to_string());
+            }
+        }
     }
-
-    hints
 }
 
 // ---------------------------------------------------------------------------
-// Import aliases
+// Lexical masking
 // ---------------------------------------------------------------------------
 
-/// Extract import aliases — maps alias name to a set of possible original names.
-fn import_aliases(source: &str) -> HashMap<String, HashSet<String>> {
-    let mut aliases: HashMap<String, HashSet<String>> = HashMap::new();
+/// Blank out comment spans and string-literal contents in `source`, so the
+/// regex scanners below (`extract_regex_calls`, [`ImportEnv::build`]) don't
+/// mistake a function name mentioned in a docstring, comment, or string
+/// literal for a real call-site or import. Masked
+/// characters are replaced with spaces (comment markers and all), except
+/// string-literal quote delimiters, which are kept intact since the import
+/// regexes key off them (e.g. Go's `import "fmt"`). Newlines are always
+/// preserved, so `line_number` accounting in the caller stays correct.
+///
+/// Languages with no recognized comment/string syntax here (notably none of
+/// the four the indexer currently scans lack one) pass through unchanged.
+///
+/// Go and TypeScript spell an import's target as a string literal
+/// (`import "fmt"`, `from './module'`), so string masking is skipped on
+/// lines that look like an import/from statement — otherwise masking would
+/// blank out the exact text [`ImportEnv::build`] needs to read.
+pub(crate) fn mask_noncode(source: &str, language: &str) -> String {
+    let line_comment = match language {
+        "python" => Some("#"),
+        "java" | "typescript" | "go" | "rust" => Some("//"),
+        _ => None,
+    };
+    let block_comment = match language {
+        "java" | "typescript" | "go" | "rust" => Some(("/*", "*/")),
+        _ => None,
+    };
+    let triple_quote = language == "python";
+    let backtick_string = matches!(language, "typescript" | "go");
 
-    for raw_line in source.lines() {
-        let normalized = raw_line.trim();
-        if normalized.is_empty() {
-            continue;
+    if line_comment.is_none() && block_comment.is_none() && !triple_quote {
+        return source.to_string();
+    }
+
+    let chars: Vec<char> = source.chars().collect();
+    let n = chars.len();
+    let starts_with = |i: usize, needle: &str| -> bool {
+        let needle: Vec<char> = needle.chars().collect();
+        i + needle.len() <= n && chars[i..i + needle.len()] == needle[..]
+    };
+
+    // Per-char line number (0-indexed) and whether that line, trimmed, opens
+    // an import statement — computed once so the string-masking branch below
+    // can cheaply skip lines whose string literal *is* the import target.
+    let mut line_of_char = Vec::with_capacity(n);
+    let mut line_no = 0usize;
+    for &c in &chars {
+        line_of_char.push(line_no);
+        if c == '\n' {
+            line_no += 1;
         }
+    }
+    let import_lines: Vec<bool> = source
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            trimmed.starts_with("import ") || trimmed.starts_with("from ")
+        })
+        .collect();
 
-        // Python: from X import a, b as c, ...
-        if let Some(caps) = PY_FROM_ALIAS_RE.captures(normalized) {
-            if let Some(items_match) = caps.get(2) {
-                let items = items_match.as_str();
-                for chunk in items.split(',') {
-                    let token = chunk.trim();
-                    if token.is_empty() {
-                        continue;
+    let mut out = String::with_capacity(source.len());
+    let mut i = 0;
+    while i < n {
+        // Line comment: blank through end of line, newline untouched.
+        if let Some(marker) = line_comment {
+            if starts_with(i, marker) {
+                while i < n && chars[i] != '\n' {
+                    out.push(' ');
+                    i += 1;
+                }
+                continue;
+            }
+        }
+
+        // Block comment: blank everything up to and including the closer.
+        if let Some((open, close)) = block_comment {
+            if starts_with(i, open) {
+                for _ in 0..open.chars().count() {
+                    out.push(' ');
+                    i += 1;
+                }
+                while i < n && !starts_with(i, close) {
+                    out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                    i += 1;
+                }
+                for _ in 0..close.chars().count() {
+                    if i < n {
+                        out.push(' ');
+                        i += 1;
                     }
-                    let parts: Vec<&str> = token.splitn(2, " as ").map(|s| s.trim()).collect();
-                    let imported = parts[0];
-                    let alias = if parts.len() > 1 { parts[1] } else { imported };
-                    let last = imported.rsplit('.').next().unwrap_or(imported);
-                    aliases
-                        .entry(alias.to_string())
-                        .or_default()
-                        .insert(last.to_string());
                 }
+                continue;
             }
-            continue;
         }
 
-        // Python: import X (as Y)?
-        if let Some(caps) = PY_IMPORT_ALIAS_RE.captures(normalized) {
-            if let Some(module_match) = caps.get(1) {
-                let imported_module = module_match.as_str();
-                let alias = caps.get(2).map(|m| m.as_str()).unwrap_or_else(|| {
-                    imported_module
-                        .rsplit('.')
-                        .next()
-                        .unwrap_or(imported_module)
-                });
-                let last = imported_module
-                    .rsplit('.')
-                    .next()
-                    .unwrap_or(imported_module);
-                aliases
-                    .entry(alias.to_string())
-                    .or_default()
-                    .insert(last.to_string());
+        // Python triple-quoted strings: blank the body, keep nothing of the
+        // delimiters either — unlike single-line strings, nothing downstream
+        // looks for a triple-quote span.
+        if triple_quote && (starts_with(i, "\"\"\"") || starts_with(i, "'''")) {
+            let quote: String = chars[i..i + 3].iter().collect();
+            for _ in 0..3 {
+                out.push(' ');
+                i += 1;
+            }
+            while i < n && !starts_with(i, &quote) {
+                out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                i += 1;
+            }
+            for _ in 0..3 {
+                if i < n {
+                    out.push(' ');
+                    i += 1;
+                }
             }
             continue;
         }
 
-        // TypeScript: import { a, b as c } from '...'
-        if let Some(caps) = TS_NAMED_IMPORT_RE.captures(normalized) {
-            if let Some(items_match) = caps.get(1) {
-                let items = items_match.as_str();
-                for chunk in items.split(',') {
-                    let token = chunk.trim();
-                    if token.is_empty() {
-                        continue;
-                    }
-                    let parts: Vec<&str> = token.splitn(2, " as ").map(|s| s.trim()).collect();
-                    let imported = parts[0];
-                    let alias = if parts.len() > 1 { parts[1] } else { imported };
-                    aliases
-                        .entry(alias.to_string())
-                        .or_default()
-                        .insert(imported.to_string());
+        // Single-line string literals: keep the quote delimiters, blank the
+        // body so the surrounding regex (which checks for the quotes) still
+        // matches, but any call-like or import-like text inside doesn't.
+        let is_string_quote =
+            matches!(chars[i], '"' | '\'') || (backtick_string && chars[i] == '`');
+        if is_string_quote {
+            let on_import_line = import_lines.get(line_of_char[i]).copied().unwrap_or(false);
+            let quote = chars[i];
+            out.push(quote);
+            i += 1;
+            while i < n && chars[i] != quote {
+                if on_import_line {
+                    out.push(chars[i]);
+                    i += 1;
+                    continue;
                 }
+                if chars[i] == '\\' && i + 1 < n && chars[i + 1] != '\n' {
+                    out.push(' ');
+                    out.push(' ');
+                    i += 2;
+                    continue;
+                }
+                out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                i += 1;
+            }
+            if i < n {
+                out.push(quote);
+                i += 1;
             }
             continue;
         }
 
-        // TypeScript: import X from '...'
+        out.push(chars[i]);
+        i += 1;
+    }
+
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Import environment
+// ---------------------------------------------------------------------------
+
+/// One local binding introduced by an import statement: the fully-qualified
+/// module it came from, and the name it was declared under at that module
+/// (equal to the binding's own key unless the import renamed it, e.g. `from
+/// a.b import c as d` or `import { Foo as Bar } from './m'`).
+#[derive(Debug, Clone)]
+struct ImportBinding {
+    module: String,
+    original_name: String,
+}
+
+/// A file's import environment: the local name a callee or receiver would
+/// actually use, resolved to every module it could have come from (more
+/// than one only when the same name is imported twice, e.g. from both a
+/// `try`/`except ImportError` fallback pair). Built once per
+/// [`build_call_edges`] call — replaces the old `import_hints`/
+/// `import_aliases` pair, which handed `resolve_targets` an unstructured
+/// bag of substrings to `contains`/`ends_with` against, with real per-name
+/// resolution, in the spirit of racer's per-file name tables.
+#[derive(Debug, Clone, Default)]
+struct ImportEnv {
+    bindings: HashMap<String, Vec<ImportBinding>>,
+    /// Every module/package the file wildcard-imports (`from pkg import
+    /// *`, `import pkg.*;`, Go's dot-import `import . "pkg"`, TS's `import
+    /// * as ns from 'pkg'`): no single local name covers what these pull
+    /// into scope, so they're tracked separately from `bindings` for the
+    /// glob-expansion strategy in `resolve_targets`.
+    wildcard_modules: Vec<String>,
+}
+
+impl ImportEnv {
+    fn build(source: &str, language: &str) -> Self {
+        let source = mask_noncode(source, language);
+        let mut bindings: HashMap<String, Vec<ImportBinding>> = HashMap::new();
+        let mut wildcard_modules: Vec<String> = Vec::new();
+        for raw_line in source.lines() {
+            let normalized = raw_line.trim();
+            if normalized.is_empty() {
+                continue;
+            }
+            match language {
+                "python" => {
+                    Self::parse_python_line(normalized, &mut bindings, &mut wildcard_modules)
+                }
+                "java" => Self::parse_java_line(normalized, &mut bindings, &mut wildcard_modules),
+                "typescript" => {
+                    Self::parse_typescript_line(normalized, &mut bindings, &mut wildcard_modules)
+                }
+                "go" => Self::parse_go_line(normalized, &mut bindings, &mut wildcard_modules),
+                "rust" => Self::parse_rust_line(normalized, &mut bindings, &mut wildcard_modules),
+                _ => {}
+            }
+        }
+        Self {
+            bindings,
+            wildcard_modules,
+        }
+    }
+
+    fn wildcard_modules(&self) -> &[String] {
+        &self.wildcard_modules
+    }
+
+    fn bind(
+        bindings: &mut HashMap<String, Vec<ImportBinding>>,
+        local_name: &str,
+        module: &str,
+        original_name: &str,
+    ) {
+        bindings
+            .entry(local_name.to_string())
+            .or_default()
+            .push(ImportBinding {
+                module: module.to_string(),
+                original_name: original_name.to_string(),
+            });
+    }
+
+    // `from a.b import c as d` binds `d` to module `a.b`, original name `c`;
+    // `import a.b.c as x` binds `x` to module `a.b.c`, original name `c`
+    // (its last dotted segment); a bare `import a.b.c` binds the module's
+    // own last segment to itself, since that's the name the importing code
+    // actually references. `from a.b import *` names no single binding, so
+    // it's recorded as a wildcard module instead.
+    fn parse_python_line(
+        normalized: &str,
+        bindings: &mut HashMap<String, Vec<ImportBinding>>,
+        wildcard_modules: &mut Vec<String>,
+    ) {
+        if let Some(caps) = PY_FROM_ALIAS_RE.captures(normalized) {
+            let module = caps[1].trim();
+            if caps[2].trim() == "*" {
+                wildcard_modules.push(module.to_string());
+                return;
+            }
+            for chunk in caps[2].split(',') {
+                let token = chunk.trim();
+                if token.is_empty() {
+                    continue;
+                }
+                let parts: Vec<&str> = token.splitn(2, " as ").map(str::trim).collect();
+                let original = parts[0];
+                let local = if parts.len() > 1 { parts[1] } else { original };
+                Self::bind(bindings, local, module, original);
+            }
+            return;
+        }
+        if let Some(caps) = PY_IMPORT_ALIAS_RE.captures(normalized) {
+            let module = caps[1].trim();
+            let last = module.rsplit('.').next().unwrap_or(module);
+            let local = caps.get(2).map_or(last, |m| m.as_str());
+            Self::bind(bindings, local, module, last);
+        }
+    }
+
+    // `import com.example.Foo;` binds `Foo` to package `com.example`. A
+    // wildcard `import com.example.*;` introduces no single name the
+    // importing code could call by, so it's recorded as a wildcard module
+    // instead of a binding.
+    fn parse_java_line(
+        normalized: &str,
+        bindings: &mut HashMap<String, Vec<ImportBinding>>,
+        wildcard_modules: &mut Vec<String>,
+    ) {
+        let Some(caps) = JAVA_IMPORT_RE.captures(normalized) else {
+            return;
+        };
+        let value = caps[1].trim();
+        if let Some(package) = value.strip_suffix(".*") {
+            wildcard_modules.push(package.to_string());
+            return;
+        }
+        let Some((package, name)) = value.rsplit_once('.') else {
+            return;
+        };
+        Self::bind(bindings, name, package, name);
+    }
+
+    // `import { Foo as Bar, Baz } from './m'` binds `Bar` to relative module
+    // `./m`, original name `Foo`, and `Baz` to itself; `import Foo from
+    // './m'` binds the default-import name to itself, same as a named
+    // import with no `as`. `import * as ns from './m'` is TS's closest
+    // equivalent to a glob import — `ns` only re-exposes `./m`'s members
+    // through a namespace object, so it's recorded as a wildcard module
+    // rather than a binding of `ns` itself.
+    fn parse_typescript_line(
+        normalized: &str,
+        bindings: &mut HashMap<String, Vec<ImportBinding>>,
+        wildcard_modules: &mut Vec<String>,
+    ) {
+        if let Some(caps) = TS_NAMESPACE_IMPORT_RE.captures(normalized) {
+            wildcard_modules.push(caps[1].trim().to_string());
+            return;
+        }
+        if let Some(caps) = TS_NAMED_IMPORT_RE.captures(normalized) {
+            for chunk in caps[1].split(',') {
+                let token = chunk.trim();
+                if token.is_empty() {
+                    continue;
+                }
+                let parts: Vec<&str> = token.splitn(2, " as ").map(str::trim).collect();
+                let original = parts[0];
+                let local = if parts.len() > 1 { parts[1] } else { original };
+                Self::bind(bindings, local, module_of(normalized), original);
+            }
+            return;
+        }
         if let Some(caps) = TS_DEFAULT_IMPORT_RE.captures(normalized) {
-            if let Some(m) = caps.get(1) {
-                let alias = m.as_str();
-                aliases
-                    .entry(alias.to_string())
-                    .or_default()
-                    .insert(alias.to_string());
+            let local = caps[1].trim();
+            Self::bind(bindings, local, module_of(normalized), local);
+        }
+    }
+
+    // `import "fmt"` binds the import path's last segment (`fmt`) to the
+    // full path, same as the name Go code refers to the package by. A
+    // dot-import `import . "fmt"` is Go's wildcard: it pulls every exported
+    // name into unqualified scope, so it's recorded as a wildcard module
+    // instead.
+    fn parse_go_line(
+        normalized: &str,
+        bindings: &mut HashMap<String, Vec<ImportBinding>>,
+        wildcard_modules: &mut Vec<String>,
+    ) {
+        if !normalized.starts_with("import ") || !normalized.contains('"') {
+            return;
+        }
+        if let Some(caps) = GO_DOT_IMPORT_RE.captures(normalized) {
+            wildcard_modules.push(caps[1].trim().to_string());
+            return;
+        }
+        let Some(caps) = GO_IMPORT_RE.captures(normalized) else {
+            return;
+        };
+        let module = caps[1].trim();
+        let local = module.rsplit('/').next().unwrap_or(module);
+        Self::bind(bindings, local, module, local);
+    }
+
+    // `use a::b::{c, d as e};` binds `c` to module `a::b`, original name
+    // `c`, and `d` (renamed `e`) to module `a::b`, original name `d`; a
+    // plain `use a::b::c;` / `use a::b::c as d;` is the same, one item at a
+    // time, via [`RUST_USE_SIMPLE_RE`]. A glob `use a::b::*;` names no
+    // single binding the importing code could call by, so it's recorded as
+    // a wildcard module instead.
+    fn parse_rust_line(
+        normalized: &str,
+        bindings: &mut HashMap<String, Vec<ImportBinding>>,
+        wildcard_modules: &mut Vec<String>,
+    ) {
+        if let Some(caps) = RUST_USE_GLOB_RE.captures(normalized) {
+            wildcard_modules.push(caps[1].trim().to_string());
+            return;
+        }
+        if let Some(caps) = RUST_USE_BRACE_RE.captures(normalized) {
+            let module = caps[1].trim();
+            for chunk in caps[2].split(',') {
+                let token = chunk.trim();
+                if token.is_empty() || token == "*" {
+                    continue;
+                }
+                let parts: Vec<&str> = token.splitn(2, " as ").map(str::trim).collect();
+                let original = parts[0];
+                let local = if parts.len() > 1 { parts[1] } else { original };
+                Self::bind(bindings, local, module, original);
             }
+            return;
+        }
+        if let Some(caps) = RUST_USE_SIMPLE_RE.captures(normalized) {
+            let module = caps[1].trim();
+            let original = caps[2].trim();
+            let local = caps.get(3).map_or(original, |m| m.as_str());
+            Self::bind(bindings, local, module, original);
         }
     }
 
-    aliases
+    /// Original names `local_name` could stand in for — the replacement for
+    /// `import_aliases(...).get(name)` at call sites that only cared about
+    /// the re-export name, not which module it came from.
+    fn original_names(&self, local_name: &str) -> HashSet<&str> {
+        self.bindings
+            .get(local_name)
+            .map(|bs| bs.iter().map(|b| b.original_name.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    fn bindings_for(&self, local_name: &str) -> &[ImportBinding] {
+        self.bindings.get(local_name).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// The module a TypeScript `import ... from '...'` line names — pulled out
+/// separately from [`TS_NAMED_IMPORT_RE`]/[`TS_DEFAULT_IMPORT_RE`] since
+/// both care only about the braced/bare name list, not the quoted path.
+fn module_of(normalized: &str) -> &str {
+    TS_IMPORT_HINT_RE
+        .captures(normalized)
+        .and_then(|caps| caps.get(1))
+        .map_or("", |m| m.as_str())
+}
+
+/// Whether `symbol` plausibly lives under `module` — the only signal
+/// available here, since (unlike [`crate::indexer::imports::resolve_imports`])
+/// `resolve_targets` has no repo-wide file listing to resolve a module path
+/// against exactly. Checked against both the qualified name (so a matching
+/// Python/Java dotted module shows up even nested inside a class) and the
+/// file path's `.py`/`.ts`/`.go` suffix form of the module path.
+fn symbol_under_module(symbol: &ExtractedSymbol, module: &str) -> bool {
+    if module.is_empty() {
+        return false;
+    }
+    if symbol.qualified_name.contains(module) {
+        return true;
+    }
+    let module_path = module
+        .trim_start_matches("./")
+        .trim_start_matches("../")
+        .replace('.', "/");
+    ["py", "ts", "go"].iter().any(|ext| {
+        let suffix = format!("{module_path}.{ext}");
+        symbol.file_path == suffix || symbol.file_path.ends_with(&format!("/{suffix}"))
+    })
 }
 
 // ---------------------------------------------------------------------------
-// Lexical receiver type hints
+// Flow-sensitive receiver type inference
 // ---------------------------------------------------------------------------
 
-/// Scan backwards from `line_number` (up to `window` lines) looking for
-/// variable assignments that reveal the type of `receiver_name`.
-fn lexical_receiver_type_hints(
-    source: &str,
-    receiver_name: Option<&str>,
+static COPY_ASSIGN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*([A-Za-z_][A-Za-z0-9_]*)\s*=\s*([A-Za-z_][A-Za-z0-9_]*)\s*;?\s*$").unwrap()
+});
+
+// Rust: let (mut)? receiver (: Type)? = Type::ctor(...)
+static RUST_LET_ASSOC_TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*let\s+(?:mut\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*(?::\s*([A-Za-z_][A-Za-z0-9_<>:]*))?\s*=\s*([A-Za-z_][A-Za-z0-9_]*)::[A-Za-z_][A-Za-z0-9_]*\s*\(",
+    )
+    .unwrap()
+});
+
+// Rust: let (mut)? receiver (: Type)? = Type { ... }
+static RUST_LET_STRUCT_TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*let\s+(?:mut\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*(?::\s*([A-Za-z_][A-Za-z0-9_<>:]*))?\s*=\s*([A-Za-z_][A-Za-z0-9_]*)\s*\{",
+    )
+    .unwrap()
+});
+
+// Rust: let (mut)? receiver : Type = ... (no `::`/`{` constructor shape to
+// pull a second type from — the annotation alone is the only signal).
+static RUST_LET_TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*let\s+(?:mut\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*:\s*([A-Za-z_][A-Za-z0-9_<>:]*)\s*=",
+    )
+    .unwrap()
+});
+
+/// One variable-type assignment observed while walking a function body —
+/// the unit [`infer_type_environment`] produces and [`type_at_line`]
+/// queries by line number.
+#[derive(Debug, Clone)]
+struct TypeBinding {
     line_number: i64,
-    window: usize,
-) -> HashSet<String> {
-    let receiver = match receiver_name {
-        Some(r) => r.trim(),
-        None => return HashSet::new(),
-    };
-    if receiver.is_empty() {
-        return HashSet::new();
+    variable: String,
+    types: HashSet<String>,
+}
+
+/// Walks a function body once in source order, recording the variable ->
+/// type binding introduced at each assignment, like the `Expr<()>` ->
+/// `Expr<Option<Type>>` folding pass in a real type checker.
+///
+/// This replaces a prior whole-function union (every type a variable was
+/// ever assigned, order ignored) with a binding log [`type_at_line`] can
+/// query for the type in effect at an exact line: reassignment overwrites
+/// rather than unions, so a later assignment can't leak backward into an
+/// earlier call-site.
+///
+/// Seeded from the caller's declared parameter types (in effect from its
+/// `start_line`), then from the existing per-language constructor-assignment
+/// patterns (`PY_ASSIGN_TYPE_RE`, `JAVA_NEW_TYPE_RE`, `TS_NEW_TYPE_RE`,
+/// `GO_SHORT_DECL_TYPE_RE`, `RUST_LET_ASSOC_TYPE_RE`, `RUST_LET_STRUCT_TYPE_RE`,
+/// `RUST_LET_TYPE_RE`) — `PY_ASSIGN_TYPE_RE` also catches `x =
+/// foo(...)` calls, resolved against `foo`'s declared return type in
+/// `candidate_symbols` when one is known — and finally copy assignments
+/// (`a = b`), each resolved against whatever `b` was bound to immediately
+/// before that line.
+///
+/// Runs once per function body (callers should cache by `(qualified_name,
+/// file_path)` across call-sites in the same function) rather than once per
+/// call-site, since the log doesn't depend on which call triggered it.
+fn infer_type_environment(
+    caller: &ExtractedSymbol,
+    body_lines: &[(i64, &str)],
+    candidate_symbols: &[ExtractedSymbol],
+) -> Vec<TypeBinding> {
+    let mut return_types: HashMap<&str, &str> = HashMap::new();
+    for symbol in candidate_symbols {
+        if let Some(ret) = symbol.return_type.as_deref() {
+            if !ret.is_empty() {
+                return_types.insert(symbol.name.as_str(), ret);
+            }
+        }
     }
 
-    let lines: Vec<&str> = source.lines().collect();
-    let end_index = (line_number - 1).max(0) as usize;
-    let end_index = end_index.min(lines.len());
-    let begin_index = end_index.saturating_sub(window);
+    let mut env: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut bindings: Vec<TypeBinding> = Vec::new();
 
-    let mut hints = HashSet::new();
+    for param in &caller.parameters {
+        let Some(type_) = param.type_.as_deref() else {
+            continue;
+        };
+        if type_.is_empty() {
+            continue;
+        }
+        let types: HashSet<String> = [type_.to_string()].into_iter().collect();
+        env.insert(param.name.clone(), types.clone());
+        bindings.push(TypeBinding {
+            line_number: caller.start_line,
+            variable: param.name.clone(),
+            types,
+        });
+    }
 
-    for index in (begin_index..end_index).rev() {
-        let line = lines[index];
+    for &(line_number, line) in body_lines {
+        let mut assign = |lhs: String, types: HashSet<String>| {
+            env.insert(lhs.clone(), types.clone());
+            bindings.push(TypeBinding {
+                line_number,
+                variable: lhs,
+                types,
+            });
+        };
 
-        // Python: receiver = TypeName(...)
+        // `receiver = TypeName(...)` / `receiver = foo(...)` — shared
+        // across languages despite the `PY_` name.
         if let Some(caps) = PY_ASSIGN_TYPE_RE.captures(line) {
-            if caps.get(1).map(|m| m.as_str()) == Some(receiver) {
-                if let Some(m) = caps.get(2) {
-                    hints.insert(m.as_str().to_string());
-                }
+            let lhs = caps[1].to_string();
+            let rhs_name = &caps[2];
+            let mut types: HashSet<String> = [rhs_name.to_string()].into_iter().collect();
+            if let Some(ret) = return_types.get(rhs_name.as_str()) {
+                types.insert(ret.to_string());
             }
+            assign(lhs, types);
         }
 
         // Java: TypeName receiver = new ConstructorName(...)
         if let Some(caps) = JAVA_NEW_TYPE_RE.captures(line) {
-            if caps.get(2).map(|m| m.as_str()) == Some(receiver) {
-                if let Some(m) = caps.get(1) {
-                    let declared = m
-                        .as_str()
-                        .trim()
-                        .split('<')
-                        .next()
-                        .unwrap_or("")
-                        .to_string();
+            if let Some(lhs) = caps.get(2) {
+                let mut types = HashSet::new();
+                if let Some(declared) = caps.get(1) {
+                    let declared = declared.as_str().trim().split('<').next().unwrap_or("");
                     if !declared.is_empty() {
-                        hints.insert(declared);
+                        types.insert(declared.to_string());
                     }
                 }
-                if let Some(m) = caps.get(3) {
-                    hints.insert(m.as_str().trim().to_string());
+                if let Some(ctor) = caps.get(3) {
+                    types.insert(ctor.as_str().trim().to_string());
                 }
+                assign(lhs.as_str().to_string(), types);
             }
         }
 
         // TypeScript: const/let/var receiver (: Type)? = new Constructor(...)
         if let Some(caps) = TS_NEW_TYPE_RE.captures(line) {
-            if caps.get(1).map(|m| m.as_str()) == Some(receiver) {
-                if let Some(m) = caps.get(2) {
-                    let declared = m
-                        .as_str()
-                        .trim()
-                        .split('<')
-                        .next()
-                        .unwrap_or("")
-                        .to_string();
+            if let Some(lhs) = caps.get(1) {
+                let mut types = HashSet::new();
+                if let Some(declared) = caps.get(2) {
+                    let declared = declared.as_str().trim().split('<').next().unwrap_or("");
                     if !declared.is_empty() {
-                        hints.insert(declared);
+                        types.insert(declared.to_string());
                     }
                 }
-                if let Some(m) = caps.get(3) {
-                    hints.insert(m.as_str().trim().to_string());
+                if let Some(ctor) = caps.get(3) {
+                    types.insert(ctor.as_str().trim().to_string());
                 }
+                assign(lhs.as_str().to_string(), types);
             }
         }
 
         // Go: receiver := &?TypeName{...}
         if let Some(caps) = GO_SHORT_DECL_TYPE_RE.captures(line) {
-            if caps.get(1).map(|m| m.as_str()) == Some(receiver) {
-                if let Some(m) = caps.get(2) {
-                    hints.insert(m.as_str().to_string());
+            if let (Some(lhs), Some(ty)) = (caps.get(1), caps.get(2)) {
+                assign(
+                    lhs.as_str().to_string(),
+                    [ty.as_str().to_string()].into_iter().collect(),
+                );
+            }
+        }
+
+        // Rust: `let x = Foo::new(...)` / `let x: Foo = Bar::new(...)` /
+        // `let x = Foo { .. }` — mutually exclusive shapes of the same
+        // `let` statement, so checked as one `if`/`else if` chain rather
+        // than independent `if`s like the patterns above.
+        if let Some(caps) = RUST_LET_ASSOC_TYPE_RE.captures(line) {
+            let lhs = caps[1].to_string();
+            let mut types = HashSet::new();
+            if let Some(declared) = caps.get(2) {
+                types.insert(declared.as_str().trim().to_string());
+            }
+            types.insert(caps[3].trim().to_string());
+            assign(lhs, types);
+        } else if let Some(caps) = RUST_LET_STRUCT_TYPE_RE.captures(line) {
+            let lhs = caps[1].to_string();
+            let mut types = HashSet::new();
+            if let Some(declared) = caps.get(2) {
+                types.insert(declared.as_str().trim().to_string());
+            }
+            types.insert(caps[3].trim().to_string());
+            assign(lhs, types);
+        } else if let Some(caps) = RUST_LET_TYPE_RE.captures(line) {
+            let lhs = caps[1].to_string();
+            assign(lhs, [caps[2].trim().to_string()].into_iter().collect());
+        }
+
+        // Copy assignment (`a = b`) — bind `a` to whatever `b` currently
+        // holds, so a type reaches every alias however many hops it flows
+        // through, as long as each hop appears after the one before it.
+        if let Some(caps) = COPY_ASSIGN_RE.captures(line) {
+            let lhs = &caps[1];
+            let rhs = &caps[2];
+            if lhs != rhs {
+                if let Some(rhs_types) = env.get(rhs).cloned() {
+                    assign(lhs.to_string(), rhs_types);
                 }
             }
         }
     }
 
-    hints
+    bindings
+}
+
+/// The type(s) bound to `variable` at the most recent assignment at or
+/// before `line_number` — the in-effect type at that exact point in the
+/// function, per [`infer_type_environment`]'s overwrite-on-reassignment
+/// semantics. Empty if `variable` was never assigned, or only assigned
+/// after `line_number`.
+fn type_at_line(bindings: &[TypeBinding], line_number: i64, variable: &str) -> HashSet<String> {
+    bindings
+        .iter()
+        .rev()
+        .find(|b| b.variable == variable && b.line_number <= line_number)
+        .map(|b| b.types.clone())
+        .unwrap_or_default()
+}
+
+/// The source lines spanning `symbol`'s declaration, 1-indexed inclusive,
+/// paired with their line numbers — the function body
+/// [`infer_type_environment`] walks its dataflow pass over.
+fn body_lines<'a>(source: &'a str, symbol: &ExtractedSymbol) -> Vec<(i64, &'a str)> {
+    source
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| ((idx as i64) + 1, line))
+        .filter(|(line_number, _)| {
+            *line_number >= symbol.start_line && *line_number <= symbol.end_line
+        })
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
@@ -504,53 +1264,189 @@ fn type_name_tokens(type_name: &str) -> HashSet<String> {
     tokens
 }
 
+// ---------------------------------------------------------------------------
+// Symbol index
+// ---------------------------------------------------------------------------
+
+/// Precomputed lookups over the whole-repository candidate symbol table,
+/// built once per [`build_call_edges`] call instead of re-filtering the full
+/// slice at every call-site (the prior approach made whole-repo graph
+/// construction O(callsites × symbols)).
+///
+/// `by_name` answers "which symbols have this bare name" — candidate-name
+/// resolution, including alias expansions. `by_owner_token` answers "which
+/// method symbols are owned by a class whose name lowers to this token" — the
+/// type-hint strategies' owner-match check — sparing `resolve_targets` from
+/// re-deriving [`method_owner_name`]/[`type_name_tokens`] for the same
+/// symbol on every call-site that happens to share its name.
+///
+/// A later phase may want fuzzy (prefix/typo-tolerant) callee lookups; at
+/// that point `by_name`'s keys could be backed by an `fst::Map` instead of a
+/// `HashMap` without changing this struct's shape.
+struct SymbolIndex<'a> {
+    symbols: &'a [ExtractedSymbol],
+    by_name: HashMap<&'a str, Vec<usize>>,
+    by_owner_token: HashMap<String, Vec<usize>>,
+}
+
+impl<'a> SymbolIndex<'a> {
+    fn build(symbols: &'a [ExtractedSymbol]) -> Self {
+        let mut by_name: HashMap<&'a str, Vec<usize>> = HashMap::new();
+        let mut by_owner_token: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, symbol) in symbols.iter().enumerate() {
+            by_name.entry(symbol.name.as_str()).or_default().push(idx);
+            if symbol.kind == "method" {
+                for token in type_name_tokens(&method_owner_name(symbol)) {
+                    by_owner_token.entry(token).or_default().push(idx);
+                }
+            }
+        }
+        Self {
+            symbols,
+            by_name,
+            by_owner_token,
+        }
+    }
+
+    /// Indices of every symbol whose bare name is in `names`, deduplicated.
+    fn indices_by_name(&self, names: &HashSet<&str>) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        for name in names {
+            if let Some(idxs) = self.by_name.get(name) {
+                for &idx in idxs {
+                    if seen.insert(idx) {
+                        out.push(idx);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn get(&self, idx: usize) -> &'a ExtractedSymbol {
+        &self.symbols[idx]
+    }
+}
+
+/// Method symbols from `index.by_owner_token` whose owner-class token
+/// matches one of `type_tokens` AND whose index is already in
+/// `match_idx_set` (i.e. also matched the call-site's callee name) — the
+/// hash-lookup replacement for the `matches.iter().filter(owner match)` scan
+/// the type-hint strategies used to run per call-site.
+fn owner_token_matches<'a>(
+    index: &SymbolIndex<'a>,
+    type_tokens: &HashSet<String>,
+    match_idx_set: &HashSet<usize>,
+) -> Vec<&'a ExtractedSymbol> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for token in type_tokens {
+        let Some(idxs) = index.by_owner_token.get(token) else {
+            continue;
+        };
+        for &idx in idxs {
+            if match_idx_set.contains(&idx) && seen.insert(idx) {
+                out.push(index.get(idx));
+            }
+        }
+    }
+    out
+}
+
 // ---------------------------------------------------------------------------
 // Target resolution
 // ---------------------------------------------------------------------------
 
-/// Resolve call-site targets using cascading strategies.
+/// Weight contributed by each independent corroborating signal toward a
+/// candidate's raw score in [`resolve_targets`]. A candidate satisfying
+/// several signals at once (e.g. class-scoped *and* same-file *and*
+/// import-matched) accumulates their sum, rather than being judged on
+/// whichever single signal a cascade happened to check first.
+const CLASS_SCOPE_WEIGHT: f64 = 0.4;
+const RECEIVER_TYPE_WEIGHT: f64 = 0.5;
+const ALIAS_WEIGHT: f64 = 0.2;
+const SAME_FILE_WEIGHT: f64 = 0.2;
+const IMPORT_SCOPED_WEIGHT: f64 = 0.25;
+const WILDCARD_IMPORT_WEIGHT: f64 = 0.15;
+const ARITY_WEIGHT: f64 = 0.15;
+
+/// Denominator for squashing a raw weighted score into a (0, 1] confidence:
+/// the smallest individual signal weight, so a candidate need only satisfy
+/// *one* signal to saturate before the uniqueness penalty (division by how
+/// many candidates share the top score) is applied — matching the old
+/// cascade's implicit "any single matching strategy alone is worth 1.0".
+const CONFIDENCE_SQUASH: f64 = ARITY_WEIGHT;
+
+/// How close to the top score a candidate's own score must be to still be
+/// reported as a ranked alternative rather than discarded outright.
+const TOP_SCORE_DELTA: f64 = 0.05;
+
+/// Tolerance for comparing two summed signal weights as "the same score" —
+/// looser than `f64::EPSILON` to absorb floating-point rounding from
+/// summing the same weights in the same order across different candidates.
+const SCORE_TIE_EPSILON: f64 = 1e-9;
+
+fn effective_param_count(symbol: &ExtractedSymbol) -> i64 {
+    let mut count = symbol.parameters.len() as i64;
+    if symbol.kind == "method" {
+        if let Some(first) = symbol.parameters.first() {
+            if matches!(first.name.as_str(), "self" | "cls" | "this") {
+                count -= 1;
+            }
+        }
+    }
+    count
+}
+
+/// Resolve call-site targets by scoring every name-matching candidate
+/// against a set of independent signals and ranking the result, rather than
+/// returning on the first non-empty cascade strategy (the pre-chunk14-5
+/// design — a candidate satisfying several signals at once used to get the
+/// same confidence as one satisfying only a single weak signal, since
+/// whichever strategy matched first won outright).
 ///
-/// Returns matched symbols and a confidence score. Strategies tried in order:
+/// Interface/trait dispatch is resolved first and kept outside the scoring
+/// pass: it deliberately fans out to every implementor and reports a
+/// `"dispatch"` relationship rather than ranking `"CALLS"` candidates, so it
+/// doesn't fit the same per-candidate confidence model.
 ///
-/// 1. Class-scoped methods (same class as caller, self/cls/this receiver)
-/// 2. Combined type hints (receiver_type + lexical + semantic)
-/// 3. Alias-based type hints
-/// 4. Direct receiver name match
-/// 5. Receiver in qualified_name
-/// 6. Same file
-/// 7. Import-scoped
+/// Otherwise every candidate sharing the callee's name is scored by summing
+/// the weights of the signals it satisfies (class/self scope, receiver-type
+/// match, alias-resolved type match, same-file, import-scoped, wildcard
+/// import, arity match against the caller's argument count), the score is
+/// squashed into a confidence, and every candidate within [`TOP_SCORE_DELTA`]
+/// of the best score is returned — each carrying its own confidence — so
+/// downstream consumers see ranked alternatives instead of one arbitrary
+/// winner.
 #[allow(clippy::too_many_arguments)]
-/// 8. All matches (fallback)
 fn resolve_targets<'a>(
     callsite: &CallSite,
     caller: &ExtractedSymbol,
-    candidate_symbols: &'a [ExtractedSymbol],
-    import_hint_set: &HashSet<String>,
-    alias_hints: &HashMap<String, HashSet<String>>,
+    index: &SymbolIndex<'a>,
+    import_env: &ImportEnv,
     receiver_type_hints: &HashSet<String>,
     lexical_hints: &HashSet<String>,
     semantic_hints: &HashSet<String>,
-) -> (Vec<&'a ExtractedSymbol>, f64) {
+    interface_implementors: &HashMap<String, Vec<String>>,
+) -> (Vec<(&'a ExtractedSymbol, f64)>, &'static str) {
     let callee_name = &callsite.callee_name;
 
-    // Build the set of candidate names (including aliases)
+    // Build the set of candidate names (including import aliases)
     let mut candidate_names: HashSet<&str> = HashSet::new();
     candidate_names.insert(callee_name.as_str());
-    if let Some(alias_set) = alias_hints.get(callee_name.as_str()) {
-        for a in alias_set {
-            candidate_names.insert(a.as_str());
-        }
+    for original in import_env.original_names(callee_name.as_str()) {
+        candidate_names.insert(original);
     }
 
-    // Filter candidates by name
-    let matches: Vec<&ExtractedSymbol> = candidate_symbols
-        .iter()
-        .filter(|s| candidate_names.contains(s.name.as_str()))
-        .collect();
-
-    if matches.is_empty() {
-        return (Vec::new(), 0.0);
+    // Resolve candidates by name through the prebuilt index instead of
+    // scanning every symbol in the repository.
+    let match_indices = index.indices_by_name(&candidate_names);
+    if match_indices.is_empty() {
+        return (Vec::new(), "CALLS");
     }
+    let match_idx_set: HashSet<usize> = match_indices.iter().copied().collect();
+    let matches: Vec<&ExtractedSymbol> = match_indices.iter().map(|&idx| index.get(idx)).collect();
 
     let receiver = callsite
         .receiver_name
@@ -558,167 +1454,237 @@ fn resolve_targets<'a>(
         .unwrap_or("")
         .trim()
         .to_lowercase();
+    let is_self_receiver =
+        receiver.is_empty() || receiver == "self" || receiver == "cls" || receiver == "this";
+    let raw_receiver = callsite.receiver_name.as_deref().unwrap_or("").trim();
 
-    // Strategy (a): Class-scoped methods
-    if caller.kind == "method" {
-        let class_prefix = match caller.qualified_name.rsplit_once('.') {
-            Some((prefix, _)) => prefix,
-            None => "",
-        };
-        if !class_prefix.is_empty() {
-            let prefix_dot = format!("{class_prefix}.");
-            let class_scoped: Vec<&ExtractedSymbol> = matches
+    let mut combined_type_hints: HashSet<String> = receiver_type_hints.clone();
+    combined_type_hints.extend(lexical_hints.iter().cloned());
+    combined_type_hints.extend(semantic_hints.iter().cloned());
+    let mut type_tokens: HashSet<String> = HashSet::new();
+    for hint in &combined_type_hints {
+        type_tokens.extend(type_name_tokens(hint));
+    }
+
+    // Interface/trait dispatch — when a receiver's type hint names an
+    // interface (Java/TS `interface`, a TS `type` alias to one, or a
+    // Go/Python structural equivalent once `supertypes` covers those), the
+    // concrete target may be any implementor's matching method.
+    if !type_tokens.is_empty() {
+        let implementor_owners: HashSet<&str> = type_tokens
+            .iter()
+            .filter_map(|token| interface_implementors.get(token))
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        if !implementor_owners.is_empty() {
+            let dispatch_matches: Vec<&ExtractedSymbol> = matches
                 .iter()
-                .filter(|s| s.kind == "method" && s.qualified_name.starts_with(&prefix_dot))
+                .filter(|s| {
+                    s.kind == "method" && implementor_owners.contains(method_owner_name(s).as_str())
+                })
                 .copied()
                 .collect();
-            if !class_scoped.is_empty()
-                && (receiver.is_empty()
-                    || receiver == "self"
-                    || receiver == "cls"
-                    || receiver == "this")
-            {
-                let confidence = if class_scoped.len() == 1 { 1.0 } else { 0.78 };
-                return (class_scoped, confidence);
+            if !dispatch_matches.is_empty() {
+                let confidence = 0.5 / dispatch_matches.len() as f64;
+                let targets = dispatch_matches
+                    .into_iter()
+                    .map(|s| (s, confidence))
+                    .collect();
+                return (targets, "dispatch");
             }
         }
     }
 
-    // Strategy (b): Combined type hints
-    let mut combined_type_hints: HashSet<String> = receiver_type_hints.clone();
-    combined_type_hints.extend(lexical_hints.iter().cloned());
-    combined_type_hints.extend(semantic_hints.iter().cloned());
+    // A name unique across the whole candidate table is unambiguous
+    // regardless of how much (or how little) corroborating evidence exists.
+    if matches.len() == 1 {
+        return (vec![(matches[0], 1.0)], "CALLS");
+    }
+
+    let class_prefix = (caller.kind == "method")
+        .then(|| caller.qualified_name.rsplit_once('.'))
+        .flatten()
+        .map(|(prefix, _)| prefix)
+        .filter(|prefix| !prefix.is_empty());
+
+    let typed_matches: HashSet<(&str, &str)> = if type_tokens.is_empty() {
+        HashSet::new()
+    } else {
+        owner_token_matches(index, &type_tokens, &match_idx_set)
+            .into_iter()
+            .map(|s| (s.qualified_name.as_str(), s.file_path.as_str()))
+            .collect()
+    };
 
-    if !combined_type_hints.is_empty() {
-        let mut type_tokens: HashSet<String> = HashSet::new();
-        for hint in &combined_type_hints {
-            type_tokens.extend(type_name_tokens(hint));
-        }
-        let typed_matches: Vec<&ExtractedSymbol> = matches
-            .iter()
-            .filter(|s| {
-                if s.kind != "method" {
-                    return false;
-                }
-                let owner = method_owner_name(s);
-                let owner_tokens = type_name_tokens(&owner);
-                !owner_tokens.is_disjoint(&type_tokens)
-            })
-            .copied()
-            .collect();
-        if !typed_matches.is_empty() {
-            let confidence = if typed_matches.len() == 1 { 1.0 } else { 0.84 };
-            return (typed_matches, confidence);
-        }
+    // Alias-based type hints: the receiver resolves (through an import
+    // alias) to a name that is itself a class/type elsewhere.
+    let alias_receiver_hints = import_env.original_names(raw_receiver);
+    let mut alias_tokens: HashSet<String> = HashSet::new();
+    for hint in &alias_receiver_hints {
+        alias_tokens.extend(type_name_tokens(hint));
     }
+    let alias_matches: HashSet<(&str, &str)> = if alias_tokens.is_empty() {
+        HashSet::new()
+    } else {
+        owner_token_matches(index, &alias_tokens, &match_idx_set)
+            .into_iter()
+            .map(|s| (s.qualified_name.as_str(), s.file_path.as_str()))
+            .collect()
+    };
 
-    // Strategy (c): Alias-based type hints
-    let alias_receiver_hints = alias_hints
-        .get(callsite.receiver_name.as_deref().unwrap_or(""))
-        .cloned()
-        .unwrap_or_default();
-    if !alias_receiver_hints.is_empty() {
-        let mut alias_tokens: HashSet<String> = HashSet::new();
-        for hint in &alias_receiver_hints {
-            alias_tokens.extend(type_name_tokens(hint));
-        }
-        let alias_typed_matches: Vec<&ExtractedSymbol> = matches
+    // Direct receiver match — the receiver literally names the candidate's
+    // owning class, or appears as a dotted segment of its qualified name.
+    // `owner` is intentionally compared against `receiver` (already
+    // lowercased) without itself being lowercased, preserving a
+    // pre-existing case-sensitivity quirk of this check.
+    let direct_receiver_matches: HashSet<(&str, &str)> = if is_self_receiver {
+        HashSet::new()
+    } else {
+        let needle = format!(".{receiver}.");
+        matches
             .iter()
             .filter(|s| {
-                if s.kind != "method" {
-                    return false;
-                }
-                let owner = method_owner_name(s);
-                !type_name_tokens(&owner).is_disjoint(&alias_tokens)
+                s.kind == "method"
+                    && (method_owner_name(s) == receiver || s.qualified_name.contains(&needle))
             })
-            .copied()
-            .collect();
-        if !alias_typed_matches.is_empty() {
-            let confidence = if alias_typed_matches.len() == 1 {
-                1.0
+            .map(|s| (s.qualified_name.as_str(), s.file_path.as_str()))
+            .collect()
+    };
+
+    // Import-scoped — a module-qualified call (`np.array(...)`) looks the
+    // receiver up as a local binding; a bare call to an imported name
+    // (`helper()` after `from mod import helper`) looks the callee name up
+    // instead. Either way, a match must carry the binding's original name
+    // and plausibly live under its module.
+    let binding_key = if raw_receiver.is_empty() {
+        callee_name.as_str()
+    } else {
+        raw_receiver
+    };
+    let import_scoped_matches: HashSet<(&str, &str)> = import_env
+        .bindings_for(binding_key)
+        .iter()
+        .flat_map(|binding| {
+            let expected_name = if raw_receiver.is_empty() {
+                binding.original_name.as_str()
             } else {
-                0.83
+                callee_name.as_str()
             };
-            return (alias_typed_matches, confidence);
-        }
-    }
+            matches
+                .iter()
+                .filter(move |s| s.name == expected_name && symbol_under_module(s, &binding.module))
+                .copied()
+        })
+        .map(|s| (s.qualified_name.as_str(), s.file_path.as_str()))
+        .collect();
 
-    // Strategy (d): Direct receiver name match
-    if !receiver.is_empty() && receiver != "self" && receiver != "cls" && receiver != "this" {
-        let class_receiver: Vec<&ExtractedSymbol> = matches
+    // Glob/wildcard import expansion — an unqualified call might be one of
+    // the names a `*`/dot-import silently pulled into scope.
+    let wildcard_matches: HashSet<(&str, &str)> = if raw_receiver.is_empty() {
+        import_env
+            .wildcard_modules()
             .iter()
-            .filter(|s| {
-                if s.kind != "method" {
-                    return false;
-                }
-                let parts: Vec<&str> = s.qualified_name.split('.').collect();
-                let owner = if parts.len() >= 2 {
-                    parts[parts.len() - 2]
-                } else {
-                    ""
-                };
-                owner == receiver
+            .flat_map(|module| {
+                matches
+                    .iter()
+                    .filter(move |s| symbol_under_module(s, module))
+                    .copied()
             })
-            .copied()
-            .collect();
-        if !class_receiver.is_empty() {
-            let confidence = if class_receiver.len() == 1 { 1.0 } else { 0.79 };
-            return (class_receiver, confidence);
-        }
+            .map(|s| (s.qualified_name.as_str(), s.file_path.as_str()))
+            .collect()
+    } else {
+        HashSet::new()
+    };
 
-        // Strategy (e): Receiver in qualified_name
-        let needle = format!(".{receiver}.");
-        let receiver_scoped: Vec<&ExtractedSymbol> = matches
-            .iter()
-            .filter(|s| s.kind == "method" && s.qualified_name.contains(&needle))
-            .copied()
-            .collect();
-        if !receiver_scoped.is_empty() {
-            let confidence = if receiver_scoped.len() == 1 {
-                1.0
-            } else {
-                0.75
-            };
-            return (receiver_scoped, confidence);
+    let mut scored: Vec<(&ExtractedSymbol, f64)> = Vec::with_capacity(matches.len());
+    for symbol in &matches {
+        let key = (symbol.qualified_name.as_str(), symbol.file_path.as_str());
+        let mut score = 0.0;
+
+        if let Some(prefix) = class_prefix {
+            if is_self_receiver
+                && symbol.kind == "method"
+                && symbol.qualified_name.starts_with(&format!("{prefix}."))
+            {
+                score += CLASS_SCOPE_WEIGHT;
+            }
         }
+        if typed_matches.contains(&key) || direct_receiver_matches.contains(&key) {
+            score += RECEIVER_TYPE_WEIGHT;
+        }
+        if alias_matches.contains(&key) {
+            score += ALIAS_WEIGHT;
+        }
+        if symbol.file_path == caller.file_path {
+            score += SAME_FILE_WEIGHT;
+        }
+        if import_scoped_matches.contains(&key) {
+            score += IMPORT_SCOPED_WEIGHT;
+        } else if wildcard_matches.contains(&key) {
+            score += WILDCARD_IMPORT_WEIGHT;
+        }
+        if let Some(argument_count) = callsite.argument_count {
+            if effective_param_count(symbol) == argument_count {
+                score += ARITY_WEIGHT;
+            }
+        }
+
+        scored.push((*symbol, score));
     }
 
-    // Strategy (f): Same file
-    let same_file: Vec<&ExtractedSymbol> = matches
+    let max_score = scored
         .iter()
-        .filter(|s| s.file_path == caller.file_path)
-        .copied()
-        .collect();
-    if !same_file.is_empty() {
-        let confidence = if same_file.len() == 1 { 1.0 } else { 0.8 };
-        return (same_file, confidence);
+        .map(|(_, score)| *score)
+        .fold(0.0_f64, f64::max);
+    if max_score <= 0.0 {
+        // No signal distinguishes any candidate — the old cascade's
+        // unresolvable-ambiguity fallback.
+        let targets = scored.into_iter().map(|(s, _)| (s, 0.5)).collect();
+        return (targets, "CALLS");
     }
 
-    // Strategy (g): Import-scoped
-    let import_scoped: Vec<&ExtractedSymbol> = matches
+    let tie_count = scored
         .iter()
-        .filter(|s| {
-            import_hint_set.iter().any(|hint| {
-                if hint.is_empty() {
-                    return false;
-                }
-                hint.contains(&s.qualified_name)
-                    || s.qualified_name.contains(hint.as_str())
-                    || s.file_path.ends_with(&format!("/{hint}.py"))
-                    || s.file_path.ends_with(&format!("/{hint}.ts"))
-                    || s.file_path.ends_with(&format!("/{hint}.go"))
-            })
+        .filter(|(_, score)| (*score - max_score).abs() < SCORE_TIE_EPSILON)
+        .count();
+    let targets: Vec<(&ExtractedSymbol, f64)> = scored
+        .into_iter()
+        .filter(|(_, score)| *score >= max_score - TOP_SCORE_DELTA)
+        .map(|(s, score)| {
+            let squashed = (score / CONFIDENCE_SQUASH).min(1.0);
+            let confidence = if (score - max_score).abs() < SCORE_TIE_EPSILON {
+                squashed / tie_count as f64
+            } else {
+                squashed
+            };
+            (s, confidence)
         })
-        .copied()
         .collect();
-    if !import_scoped.is_empty() {
-        let confidence = if import_scoped.len() == 1 { 1.0 } else { 0.7 };
-        return (import_scoped, confidence);
-    }
+    (targets, "CALLS")
+}
 
-    // Strategy (h): All matches (fallback)
-    let confidence = if matches.len() == 1 { 1.0 } else { 0.5 };
-    (matches, confidence)
+/// Maps each declared supertype's lowercase simple name (and its last dotted
+/// segment, via [`type_name_tokens`]) to the simple names of every symbol
+/// whose `supertypes` lists it. Built once per [`build_call_edges`] call so
+/// `resolve_targets`'s interface-dispatch strategy can find every
+/// implementor of an interface-typed receiver, not just a class whose own
+/// name happens to match the hint.
+fn build_interface_implementors(
+    candidate_symbols: &[ExtractedSymbol],
+) -> HashMap<String, Vec<String>> {
+    let mut implementors: HashMap<String, Vec<String>> = HashMap::new();
+    for symbol in candidate_symbols {
+        for supertype in &symbol.supertypes {
+            for token in type_name_tokens(supertype) {
+                implementors
+                    .entry(token)
+                    .or_default()
+                    .push(symbol.name.clone());
+            }
+        }
+    }
+    implementors
 }
 
 // ---------------------------------------------------------------------------
@@ -738,10 +1704,14 @@ fn resolve_targets<'a>(
 ///   When `None`, ids are derived from `crc32(qualified_name) & 0x7FFFFFFF`.
 /// * `semantic_receiver_type_hints` - Optional map from `(line_number, receiver_name)`
 ///   to a set of possible type names, supplied by the semantic hints layer.
+/// * `mode` - Call-site extraction strategy; see [`ExtractionMode`]. Ast mode
+///   transparently falls back to the regex pass for languages with no
+///   loaded tree-sitter grammar.
 ///
 /// # Returns
 ///
 /// A deduplicated list of `CallEdge` values, sorted by `(line_number, source_id, target_id)`.
+#[allow(clippy::too_many_arguments)]
 pub fn build_call_edges(
     source: &str,
     file_path: &str,
@@ -750,13 +1720,19 @@ pub fn build_call_edges(
     candidate_symbols: &[ExtractedSymbol],
     symbol_id_lookup: Option<&HashMap<(String, String), i64>>,
     semantic_receiver_type_hints: Option<&HashMap<(i64, String), HashSet<String>>>,
+    mode: ExtractionMode,
 ) -> Vec<CallEdge> {
-    let callsites = extract_regex_calls(source, language);
-    let hints = import_hints(source);
-    let alias_hint_map = import_aliases(source);
+    let callsites = extract_calls(source, language, mode);
+    let import_env = ImportEnv::build(source, language);
+    let interface_implementors = build_interface_implementors(candidate_symbols);
+    let symbol_index = SymbolIndex::build(candidate_symbols);
 
     let mut edges: Vec<CallEdge> = Vec::new();
     let mut seen: HashSet<(i64, i64, i64)> = HashSet::new();
+    // One dataflow pass per distinct caller, not per call-site — every
+    // call-site in the same function queries the same binding log, just at
+    // its own line number.
+    let mut type_bindings_by_caller: HashMap<(String, String), Vec<TypeBinding>> = HashMap::new();
 
     for callsite in &callsites {
         let caller = match caller_for_line(callsite.line_number, file_symbols) {
@@ -764,13 +1740,28 @@ pub fn build_call_edges(
             None => continue,
         };
 
-        // Gather lexical receiver type hints for this call-site
-        let lexical_hints = lexical_receiver_type_hints(
-            source,
-            callsite.receiver_name.as_deref(),
-            callsite.line_number,
-            60,
-        );
+        let bindings = type_bindings_by_caller
+            .entry((caller.qualified_name.clone(), caller.file_path.clone()))
+            .or_insert_with(|| {
+                infer_type_environment(caller, &body_lines(source, caller), candidate_symbols)
+            });
+        let mut lexical_hints = callsite
+            .receiver_name
+            .as_deref()
+            .map(str::trim)
+            .map(|receiver| type_at_line(bindings, callsite.line_number, receiver))
+            .unwrap_or_default();
+        // Rust `Type::method()` names its owning type directly as the
+        // receiver rather than through a variable binding — by convention
+        // a type name starts uppercase, so feed it straight into the type
+        // hints the class-scoped/combined-hints strategies already consume.
+        if language == "rust" {
+            if let Some(receiver) = callsite.receiver_name.as_deref().map(str::trim) {
+                if receiver.starts_with(|c: char| c.is_uppercase()) {
+                    lexical_hints.insert(receiver.to_string());
+                }
+            }
+        }
 
         // Gather semantic receiver type hints for this call-site
         let semantic_hints = match semantic_receiver_type_hints {
@@ -789,18 +1780,18 @@ pub fn build_call_edges(
             None => HashSet::new(),
         };
 
-        let (targets, confidence) = resolve_targets(
+        let (targets, relationship) = resolve_targets(
             callsite,
             caller,
-            candidate_symbols,
-            &hints,
-            &alias_hint_map,
+            &symbol_index,
+            &import_env,
             &HashSet::new(), // receiver_type_hints from Python AST (not available in regex path)
             &lexical_hints,
             &semantic_hints,
+            &interface_implementors,
         );
 
-        for target in targets {
+        for (target, confidence) in targets {
             let (source_id, target_id) = if let Some(lookup) = symbol_id_lookup {
                 let src_key = (caller.qualified_name.clone(), caller.file_path.clone());
                 let tgt_key = (target.qualified_name.clone(), target.file_path.clone());
@@ -826,7 +1817,7 @@ pub fn build_call_edges(
                 target_id,
                 source_type: "symbol".to_string(),
                 target_type: "symbol".to_string(),
-                relationship: "CALLS".to_string(),
+                relationship: relationship.to_string(),
                 file_path: file_path.to_string(),
                 line_number: callsite.line_number,
                 confidence,
@@ -844,6 +1835,139 @@ pub fn build_call_edges(
     edges
 }
 
+// ---------------------------------------------------------------------------
+// Virtual dispatch expansion
+// ---------------------------------------------------------------------------
+
+/// Fetch a single `i64` column from `stmt(params)`, returning `Ok(None)`
+/// rather than an error when the query matches no row — the same
+/// no-rows-is-fine convention `query/blast.rs` and `query/change_impact.rs`
+/// use for `resolve_symbol`, just without their extra row shape.
+fn query_optional_i64(
+    stmt: &mut rusqlite::Statement<'_>,
+    param: &str,
+) -> crate::errors::BombeResult<Option<i64>> {
+    match stmt.query_row(rusqlite::params![param], |row| row.get(0)) {
+        Ok(id) => Ok(Some(id)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Re-derives every synthetic virtual-dispatch `CALLS` edge from the
+/// `direct` edges and the `IMPLEMENTS`/`EXTENDS` edges already in `conn`.
+///
+/// A direct `CALLS` edge that targets a `method` symbol declared on an
+/// `interface` symbol (e.g. `IService.process`) only tells callers what
+/// the call looks like *lexically* — at runtime it could dispatch to any
+/// concrete override. For each such edge, this follows every
+/// `IMPLEMENTS`/`EXTENDS` edge into the interface to find implementing
+/// classes, then looks up that class's same-named method and materializes
+/// a `CALLS` edge from the original caller straight to it, tagged
+/// `dispatch = 'virtual'` so `get_blast_radius_impl`/`change_impact_impl`
+/// can tell it apart from a name-resolved direct call.
+///
+/// Re-run over the whole graph on every index (existing virtual edges are
+/// dropped and rebuilt first), the same way `pagerank::recompute_pagerank_impl`
+/// recomputes from scratch rather than patching incrementally — a changed
+/// `IMPLEMENTS` edge anywhere can add or remove a valid dispatch target for
+/// an interface call site in a file that wasn't itself reindexed.
+///
+/// `fanout_cap` bounds how many implementors are expanded per interface
+/// call-site, via [`crate::query::guards::adaptive_graph_cap`] at the call
+/// site, so a widely-implemented interface can't blow up the edge count.
+/// Implementors beyond the cap are simply not expanded — not an error.
+///
+/// Returns the number of virtual edges inserted.
+pub fn expand_virtual_dispatch_edges(
+    conn: &rusqlite::Connection,
+    fanout_cap: i64,
+) -> crate::errors::BombeResult<i64> {
+    conn.execute("DELETE FROM edges WHERE dispatch = 'virtual';", [])?;
+
+    let mut candidate_stmt = conn.prepare(
+        "SELECT e.source_id, e.target_id, e.file_path, e.line_number, \
+                t.name, t.qualified_name \
+         FROM edges e JOIN symbols t ON t.id = e.target_id \
+         WHERE e.relationship = 'CALLS' AND e.target_type = 'symbol' \
+           AND e.dispatch = 'direct' AND t.kind = 'method';",
+    )?;
+    let candidates: Vec<(i64, i64, Option<String>, Option<i64>, String, String)> =
+        candidate_stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+    let mut owner_id_stmt =
+        conn.prepare("SELECT id FROM symbols WHERE qualified_name = ?1 AND kind = 'interface';")?;
+    let mut implementor_stmt = conn.prepare(
+        "SELECT source_id FROM edges \
+         WHERE relationship IN ('EXTENDS', 'IMPLEMENTS') AND target_type = 'symbol' \
+           AND target_id = ?1;",
+    )?;
+    let mut class_qname_stmt =
+        conn.prepare("SELECT qualified_name FROM symbols WHERE id = ?1;")?;
+    let mut override_id_stmt =
+        conn.prepare("SELECT id FROM symbols WHERE kind = 'method' AND qualified_name = ?1;")?;
+    let mut insert_stmt = conn.prepare(
+        "INSERT OR IGNORE INTO edges ( \
+             source_id, target_id, source_type, target_type, relationship, \
+             file_path, line_number, confidence, dispatch \
+         ) VALUES (?1, ?2, 'symbol', 'symbol', 'CALLS', ?3, ?4, 0.6, 'virtual');",
+    )?;
+
+    let mut inserted = 0i64;
+    for (caller_id, abstract_method_id, file_path, line_number, method_name, method_qname) in
+        candidates
+    {
+        let Some((owner_qname, _)) = method_qname.rsplit_once('.') else {
+            continue;
+        };
+        let Some(owner_id) = query_optional_i64(&mut owner_id_stmt, owner_qname)? else {
+            continue;
+        };
+
+        let implementor_ids: Vec<i64> = implementor_stmt
+            .query_map(rusqlite::params![owner_id], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        for &class_id in implementor_ids.iter().take(fanout_cap.max(0) as usize) {
+            let class_qname: String = match class_qname_stmt
+                .query_row(rusqlite::params![class_id], |row| row.get(0))
+            {
+                Ok(q) => q,
+                Err(_) => continue,
+            };
+            let override_qname = format!("{class_qname}.{method_name}");
+            let Some(override_id) = query_optional_i64(&mut override_id_stmt, &override_qname)?
+            else {
+                continue;
+            };
+            if override_id == abstract_method_id {
+                continue;
+            }
+            inserted += insert_stmt.execute(rusqlite::params![
+                caller_id,
+                override_id,
+                file_path,
+                line_number,
+            ])? as i64;
+        }
+    }
+
+    Ok(inserted)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -875,6 +1999,22 @@ mod tests {
             is_static: false,
             docstring: None,
             parameters: Vec::new(),
+            supertypes: Vec::new(),
+        }
+    }
+
+    fn make_symbol_implementing(
+        name: &str,
+        qualified_name: &str,
+        kind: &str,
+        file_path: &str,
+        start_line: i64,
+        end_line: i64,
+        supertypes: &[&str],
+    ) -> ExtractedSymbol {
+        ExtractedSymbol {
+            supertypes: supertypes.iter().map(|s| s.to_string()).collect(),
+            ..make_symbol(name, qualified_name, kind, file_path, start_line, end_line)
         }
     }
 
@@ -939,73 +2079,342 @@ mod tests {
         assert!(!names.contains(&"bar"));
     }
 
+    fn ast_calls(source: &str, language: &str) -> Vec<CallSite> {
+        let tree = crate::indexer::parser::parse_source_native(source, language)
+            .unwrap_or_else(|| panic!("no grammar loaded for {language}"));
+        extract_ast_calls(&tree, source, language)
+    }
+
+    #[test]
+    fn test_extract_ast_calls_java_chained_receiver() {
+        let source = "class Foo {\n  void run() {\n    a.b().c();\n  }\n}\n";
+        let calls = ast_calls(source, "java");
+        let call = calls
+            .iter()
+            .find(|c| c.callee_name == "c")
+            .expect("call to c() not found");
+        assert_eq!(call.receiver_chain, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(call.receiver_name.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_extract_ast_calls_java_skips_if_and_new() {
+        let source = "class Foo {\n  void run() {\n    if (ready()) {\n      new Bar();\n    }\n  }\n}\n";
+        let calls = ast_calls(source, "java");
+        let names: Vec<&str> = calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert!(!names.contains(&"if"));
+        assert!(!names.contains(&"Bar"));
+        assert!(names.contains(&"ready"));
+    }
+
+    #[test]
+    fn test_extract_ast_calls_typescript_chained_receiver() {
+        let source = "function run() {\n  a.b().c();\n}\n";
+        let calls = ast_calls(source, "typescript");
+        let call = calls
+            .iter()
+            .find(|c| c.callee_name == "c")
+            .expect("call to c() not found");
+        assert_eq!(call.receiver_chain, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_ast_calls_go_chained_receiver() {
+        let source = "package main\n\nfunc run() {\n\ta.b().c()\n}\n";
+        let calls = ast_calls(source, "go");
+        let call = calls
+            .iter()
+            .find(|c| c.callee_name == "c")
+            .expect("call to c() not found");
+        assert_eq!(call.receiver_chain, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_calls_ast_mode_falls_back_to_regex_for_python() {
+        let source = "x = foo()\n";
+        let calls = extract_calls(source, "python", ExtractionMode::Ast);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].callee_name, "foo");
+    }
+
+    #[test]
+    fn test_extract_calls_regex_mode_ignores_loaded_grammar() {
+        // Forcing regex mode on a language with a grammar should still use
+        // the line-scanning pass, so the chained call reads as two matches
+        // instead of the AST's single `c()` call with a receiver chain.
+        let source = "a.b().c();\n";
+        let calls = extract_calls(source, "java", ExtractionMode::Regex);
+        let names: Vec<&str> = calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert_eq!(names, vec!["b", "c"]);
+    }
+
     #[test]
-    fn test_import_hints_python() {
+    fn test_import_env_python_binds_from_import_and_plain_import() {
         let source = "from os.path import join\nimport collections\n";
-        let hints = import_hints(source);
-        assert!(hints.contains("os.path"));
-        assert!(hints.contains("path"));
-        assert!(hints.contains("collections"));
+        let env = ImportEnv::build(source, "python");
+        let join_bindings = env.bindings_for("join");
+        assert_eq!(join_bindings.len(), 1);
+        assert_eq!(join_bindings[0].module, "os.path");
+        assert_eq!(join_bindings[0].original_name, "join");
+        let collections_bindings = env.bindings_for("collections");
+        assert_eq!(collections_bindings[0].module, "collections");
     }
 
     #[test]
-    fn test_import_hints_java() {
+    fn test_import_env_java_binds_class_name_skips_wildcard() {
         let source = "import com.example.MyClass;\nimport java.util.*;\n";
-        let hints = import_hints(source);
-        assert!(hints.contains("com.example.MyClass"));
-        assert!(hints.contains("MyClass"));
-        assert!(hints.contains("java.util"));
-        assert!(hints.contains("util"));
+        let env = ImportEnv::build(source, "java");
+        let bindings = env.bindings_for("MyClass");
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].module, "com.example");
+        assert!(env.bindings_for("*").is_empty());
+        assert_eq!(env.wildcard_modules(), &["java.util".to_string()]);
+    }
+
+    #[test]
+    fn test_import_env_python_wildcard_import_recorded_not_bound() {
+        let source = "from utils import *\n";
+        let env = ImportEnv::build(source, "python");
+        assert!(env.bindings_for("*").is_empty());
+        assert_eq!(env.wildcard_modules(), &["utils".to_string()]);
+    }
+
+    #[test]
+    fn test_import_env_go_dot_import_recorded_as_wildcard() {
+        let source = "import . \"fmt\"\n";
+        let env = ImportEnv::build(source, "go");
+        assert!(env.bindings_for("fmt").is_empty());
+        assert_eq!(env.wildcard_modules(), &["fmt".to_string()]);
+    }
+
+    #[test]
+    fn test_import_env_typescript_namespace_import_recorded_as_wildcard() {
+        let source = "import * as ns from './module'\n";
+        let env = ImportEnv::build(source, "typescript");
+        assert!(env.bindings_for("ns").is_empty());
+        assert_eq!(env.wildcard_modules(), &["./module".to_string()]);
     }
 
     #[test]
-    fn test_import_aliases_python() {
+    fn test_import_env_python_aliases_resolve_to_original_name() {
         let source = "from os.path import join as pjoin\nimport numpy as np\n";
-        let aliases = import_aliases(source);
-        assert!(aliases.contains_key("pjoin"));
-        assert!(aliases["pjoin"].contains("join"));
-        assert!(aliases.contains_key("np"));
-        assert!(aliases["np"].contains("numpy"));
+        let env = ImportEnv::build(source, "python");
+        assert_eq!(env.original_names("pjoin"), HashSet::from(["join"]));
+        assert_eq!(env.original_names("np"), HashSet::from(["numpy"]));
+        assert_eq!(env.bindings_for("np")[0].module, "numpy");
     }
 
     #[test]
-    fn test_import_aliases_typescript() {
+    fn test_import_env_typescript_named_and_default_imports() {
         let source = "import { Foo as Bar, Baz } from './module'\nimport Default from './other'\n";
-        let aliases = import_aliases(source);
-        assert!(aliases.contains_key("Bar"));
-        assert!(aliases["Bar"].contains("Foo"));
-        assert!(aliases.contains_key("Baz"));
-        assert!(aliases["Baz"].contains("Baz"));
-        assert!(aliases.contains_key("Default"));
+        let env = ImportEnv::build(source, "typescript");
+        assert_eq!(env.original_names("Bar"), HashSet::from(["Foo"]));
+        assert_eq!(env.bindings_for("Bar")[0].module, "./module");
+        assert_eq!(env.original_names("Baz"), HashSet::from(["Baz"]));
+        assert_eq!(env.original_names("Default"), HashSet::from(["Default"]));
+        assert_eq!(env.bindings_for("Default")[0].module, "./other");
+    }
+
+    #[test]
+    fn test_extract_regex_calls_rust_recognizes_free_method_and_path_calls() {
+        let source =
+            "fn run() {\n    foo();\n    x.do_thing();\n    Type::new();\n    mod::func();\n}\n";
+        let calls = extract_regex_calls(source, "rust");
+        let names: Vec<(Option<&str>, &str)> = calls
+            .iter()
+            .map(|c| (c.receiver_name.as_deref(), c.callee_name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (None, "foo"),
+                (Some("x"), "do_thing"),
+                (Some("Type"), "new"),
+                (Some("mod"), "func"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_env_rust_binds_use_paths_skips_glob() {
+        let source = "use a::b::c;\nuse a::b::{d, e as f};\nuse a::b::*;\n";
+        let env = ImportEnv::build(source, "rust");
+        assert_eq!(env.bindings_for("c")[0].module, "a::b");
+        assert_eq!(env.bindings_for("d")[0].module, "a::b");
+        assert_eq!(env.original_names("f"), HashSet::from(["e"]));
+        assert!(env.bindings_for("*").is_empty());
+        assert_eq!(env.wildcard_modules(), &["a::b".to_string()]);
+    }
+
+    #[test]
+    fn test_infer_type_environment_rust_let_patterns() {
+        let caller = make_symbol("f", "mod.f", "function", "mod.rs", 1, 3);
+        let body = [
+            "let x = Foo::new();",
+            "let y: Bar = Bar::default();",
+            "let z = Baz { value: 1 };",
+        ];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        assert!(type_at_line(&bindings, 1, "x").contains("Foo"));
+        let y_types = type_at_line(&bindings, 2, "y");
+        assert!(y_types.contains("Bar"));
+        assert!(type_at_line(&bindings, 3, "z").contains("Baz"));
+    }
+
+    #[test]
+    fn test_mask_noncode_preserves_line_count() {
+        let source = "// a comment\nfoo();\n/* block\nspans lines */\nbar();\n";
+        let masked = mask_noncode(source, "java");
+        assert_eq!(masked.lines().count(), source.lines().count());
+    }
+
+    #[test]
+    fn test_mask_noncode_strips_call_like_text_from_line_comment() {
+        // A URL containing parens right after the call-like text shouldn't
+        // survive masking either.
+        let source = "doReal(1);\n// see docs(arg) at https://example.com/path(1)\n";
+        let calls = extract_regex_calls(source, "java");
+        let names: Vec<&str> = calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert_eq!(names, vec!["doReal"]);
+    }
+
+    #[test]
+    fn test_mask_noncode_strips_call_like_text_from_python_docstring() {
+        let source =
+            "def run():\n    \"\"\"\n    Example: do_thing(x)\n    \"\"\"\n    real_call()\n";
+        let calls = extract_regex_calls(source, "python");
+        let names: Vec<&str> = calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert_eq!(names, vec!["real_call"]);
+    }
+
+    #[test]
+    fn test_mask_noncode_keeps_string_quotes_for_go_import() {
+        let source = "import \"fmt\"\n";
+        let masked = mask_noncode(source, "go");
+        let env = ImportEnv::build(&masked, "go");
+        assert_eq!(env.bindings_for("fmt")[0].module, "fmt");
+    }
+
+    #[test]
+    fn test_mask_noncode_keeps_typescript_import_path() {
+        let source = "import { Foo } from './module'\n";
+        let masked = mask_noncode(source, "typescript");
+        let env = ImportEnv::build(&masked, "typescript");
+        assert_eq!(env.bindings_for("Foo")[0].module, "./module");
+    }
+
+    #[test]
+    fn test_mask_noncode_blanks_call_like_text_in_non_import_string() {
+        // A plain string literal elsewhere in the file still gets its body
+        // blanked — only an import statement's own string is exempt.
+        let source = "logger.info(\"fetchUser(id)\");\n";
+        let calls = extract_regex_calls(source, "java");
+        let names: Vec<&str> = calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert_eq!(names, vec!["info"]);
+    }
+
+    #[test]
+    fn test_mask_noncode_blanks_block_comment_body() {
+        let source = "/* callLike(x) */\nreal();\n";
+        let calls = extract_regex_calls(source, "java");
+        let names: Vec<&str> = calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert_eq!(names, vec!["real"]);
+    }
+
+    fn numbered(body: &[&str]) -> Vec<(i64, &str)> {
+        body.iter()
+            .enumerate()
+            .map(|(idx, line)| ((idx as i64) + 1, *line))
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_type_environment_python_constructor() {
+        let caller = make_symbol("f", "mod.f", "function", "mod.py", 1, 2);
+        let body = ["x = MyClass()", "x.do_thing()"];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        assert!(type_at_line(&bindings, 2, "x").contains("MyClass"));
+    }
+
+    #[test]
+    fn test_infer_type_environment_java_new() {
+        let caller = make_symbol("f", "mod.f", "method", "mod.java", 1, 2);
+        let body = ["MyClass x = new MyClass();", "x.doThing();"];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        assert!(type_at_line(&bindings, 2, "x").contains("MyClass"));
+    }
+
+    #[test]
+    fn test_infer_type_environment_ts_new() {
+        let caller = make_symbol("f", "mod.f", "function", "mod.ts", 1, 2);
+        let body = ["const x: Foo = new Bar();", "x.doThing();"];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        let types = type_at_line(&bindings, 2, "x");
+        assert!(types.contains("Foo"));
+        assert!(types.contains("Bar"));
+    }
+
+    #[test]
+    fn test_infer_type_environment_go_short_decl() {
+        let caller = make_symbol("f", "mod.f", "function", "mod.go", 1, 2);
+        let body = ["x := &MyStruct{}", "x.DoThing()"];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        assert!(type_at_line(&bindings, 2, "x").contains("MyStruct"));
     }
 
     #[test]
-    fn test_lexical_receiver_type_hints_python() {
-        let source = "x = MyClass()\nx.do_thing()\n";
-        let hints = lexical_receiver_type_hints(source, Some("x"), 2, 60);
-        assert!(hints.contains("MyClass"));
+    fn test_infer_type_environment_propagates_through_copies() {
+        // `x`'s type only reaches `z` by hopping through `y` first — the
+        // windowed lexical scan this replaced couldn't see past one hop.
+        let caller = make_symbol("f", "mod.f", "function", "mod.py", 1, 4);
+        let body = ["x = MyClass()", "y = x", "z = y", "z.do_thing()"];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        assert!(type_at_line(&bindings, 4, "z").contains("MyClass"));
     }
 
     #[test]
-    fn test_lexical_receiver_type_hints_java() {
-        let source = "MyClass x = new MyClass();\nx.doThing();\n";
-        let hints = lexical_receiver_type_hints(source, Some("x"), 2, 60);
-        assert!(hints.contains("MyClass"));
+    fn test_infer_type_environment_reassignment_overwrites() {
+        // Reassignment replaces rather than unions: the type at line 1 is
+        // `Foo` alone, and only line 2 onward sees `Bar`. A later
+        // reassignment must not leak backward into an earlier call-site.
+        let caller = make_symbol("f", "mod.f", "function", "mod.py", 1, 2);
+        let body = ["x = Foo()", "x = Bar()"];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        assert_eq!(
+            type_at_line(&bindings, 1, "x"),
+            HashSet::from(["Foo".to_string()])
+        );
+        assert_eq!(
+            type_at_line(&bindings, 2, "x"),
+            HashSet::from(["Bar".to_string()])
+        );
     }
 
     #[test]
-    fn test_lexical_receiver_type_hints_ts() {
-        let source = "const x: Foo = new Bar();\nx.doThing();\n";
-        let hints = lexical_receiver_type_hints(source, Some("x"), 2, 60);
-        assert!(hints.contains("Foo"));
-        assert!(hints.contains("Bar"));
+    fn test_infer_type_environment_call_assignment_uses_declared_return_type() {
+        let mut factory = make_symbol("make_widget", "mod.make_widget", "function", "mod.py", 1, 1);
+        factory.return_type = Some("Widget".to_string());
+        let caller = make_symbol("f", "mod.f", "function", "mod.py", 1, 2);
+        let body = ["x = make_widget()", "x.spin()"];
+        let bindings =
+            infer_type_environment(&caller, &numbered(&body), std::slice::from_ref(&factory));
+        let types = type_at_line(&bindings, 2, "x");
+        assert!(types.contains("Widget"));
+        // The call-target name itself is also recorded as a fallback type.
+        assert!(types.contains("make_widget"));
     }
 
     #[test]
-    fn test_lexical_receiver_type_hints_go() {
-        let source = "x := &MyStruct{}\nx.DoThing()\n";
-        let hints = lexical_receiver_type_hints(source, Some("x"), 2, 60);
-        assert!(hints.contains("MyStruct"));
+    fn test_infer_type_environment_seeds_from_parameter_types() {
+        let mut caller = make_symbol("f", "mod.f", "method", "mod.py", 1, 2);
+        caller.parameters.push(ExtractedParameter {
+            name: "gateway".to_string(),
+            type_: Some("PaymentGateway".to_string()),
+            position: 0,
+        });
+        let body = ["gateway.charge(amount)"];
+        let bindings = infer_type_environment(&caller, &numbered(&body), &[]);
+        assert!(type_at_line(&bindings, 1, "gateway").contains("PaymentGateway"));
     }
 
     #[test]
@@ -1076,6 +2485,7 @@ mod tests {
             &candidate_symbols,
             None,
             None,
+            ExtractionMode::Ast,
         );
         assert_eq!(edges.len(), 1);
         assert_eq!(edges[0].relationship, "CALLS");
@@ -1111,6 +2521,7 @@ mod tests {
             &candidate_symbols,
             None,
             None,
+            ExtractionMode::Ast,
         );
         assert_eq!(edges.len(), 1);
     }
@@ -1138,6 +2549,7 @@ mod tests {
             &candidate_symbols,
             None,
             None,
+            ExtractionMode::Ast,
         );
         assert_eq!(edges.len(), 2);
         assert!(edges[0].line_number <= edges[1].line_number);
@@ -1175,6 +2587,7 @@ mod tests {
             &candidate_symbols,
             Some(&lookup),
             None,
+            ExtractionMode::Ast,
         );
         assert_eq!(edges.len(), 1);
         assert_eq!(edges[0].source_id, 100);
@@ -1213,10 +2626,37 @@ mod tests {
             &candidate_symbols,
             Some(&lookup),
             None,
+            ExtractionMode::Ast,
         );
         assert!(edges.is_empty());
     }
 
+    #[test]
+    fn test_build_call_edges_rust_associated_call_resolves_via_type_name_receiver() {
+        let source = "fn run() {\n    Widget::build();\n}\n";
+        let file_symbols = vec![make_symbol("run", "mod.run", "function", "mod.rs", 1, 3)];
+        let candidate_symbols = vec![make_symbol(
+            "build",
+            "mod.Widget.build",
+            "method",
+            "mod.rs",
+            10,
+            12,
+        )];
+        let edges = build_call_edges(
+            source,
+            "mod.rs",
+            "rust",
+            &file_symbols,
+            &candidate_symbols,
+            None,
+            None,
+            ExtractionMode::Ast,
+        );
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].relationship, "CALLS");
+    }
+
     #[test]
     fn test_resolve_class_scoped() {
         let caller = make_symbol("do_thing", "mod.MyClass.do_thing", "method", "a.py", 1, 10);
@@ -1228,21 +2668,139 @@ mod tests {
             callee_name: "helper".to_string(),
             line_number: 5,
             receiver_name: Some("self".to_string()),
+            receiver_chain: Vec::new(),
+            argument_count: None,
         };
 
-        let (targets, confidence) = resolve_targets(
+        let index = SymbolIndex::build(&candidates);
+        let (targets, relationship) = resolve_targets(
             &callsite,
             &caller,
-            &candidates,
+            &index,
+            &ImportEnv::default(),
+            &HashSet::new(),
+            &HashSet::new(),
             &HashSet::new(),
             &HashMap::new(),
+        );
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].0.qualified_name, "mod.MyClass.helper");
+        assert_eq!(targets[0].1, 1.0);
+        assert_eq!(relationship, "CALLS");
+    }
+
+    #[test]
+    fn test_resolve_glob_import_expansion_filters_to_wildcard_module() {
+        let caller = make_symbol("run", "mod.main.run", "function", "main.py", 1, 10);
+        let target = make_symbol("helper", "utils.helper", "function", "utils.py", 1, 5);
+        let decoy = make_symbol("helper", "other.helper", "function", "other.py", 1, 5);
+        let candidates = vec![target, decoy];
+
+        let callsite = CallSite {
+            callee_name: "helper".to_string(),
+            line_number: 5,
+            receiver_name: None,
+            receiver_chain: Vec::new(),
+            argument_count: None,
+        };
+
+        let index = SymbolIndex::build(&candidates);
+        let import_env = ImportEnv::build("from utils import *\n", "python");
+        let (targets, relationship) = resolve_targets(
+            &callsite,
+            &caller,
+            &index,
+            &import_env,
             &HashSet::new(),
             &HashSet::new(),
             &HashSet::new(),
+            &HashMap::new(),
         );
         assert_eq!(targets.len(), 1);
-        assert_eq!(targets[0].qualified_name, "mod.MyClass.helper");
-        assert_eq!(confidence, 1.0);
+        assert_eq!(targets[0].0.qualified_name, "utils.helper");
+        assert_eq!(targets[0].1, 1.0);
+        assert_eq!(relationship, "CALLS");
+    }
+
+    #[test]
+    fn test_resolve_interface_dispatch_fans_out_to_every_implementor() {
+        let caller = make_symbol("run", "mod.main.run", "function", "a.java", 1, 10);
+        let stripe_class = make_symbol_implementing(
+            "StripeGateway",
+            "mod.StripeGateway",
+            "class",
+            "gateway/stripe.java",
+            1,
+            10,
+            &["PaymentGateway"],
+        );
+        let paypal_class = make_symbol_implementing(
+            "PaypalGateway",
+            "mod.PaypalGateway",
+            "class",
+            "gateway/paypal.java",
+            1,
+            10,
+            &["PaymentGateway"],
+        );
+        let stripe_method = make_symbol(
+            "charge",
+            "mod.StripeGateway.charge",
+            "method",
+            "gateway/stripe.java",
+            5,
+            8,
+        );
+        let paypal_method = make_symbol(
+            "charge",
+            "mod.PaypalGateway.charge",
+            "method",
+            "gateway/paypal.java",
+            5,
+            8,
+        );
+        let unrelated = make_symbol("charge", "mod.Billing.charge", "method", "a.java", 20, 25);
+        let candidates = vec![
+            stripe_class,
+            paypal_class,
+            stripe_method,
+            paypal_method,
+            unrelated,
+        ];
+        let implementors = build_interface_implementors(&candidates);
+
+        let callsite = CallSite {
+            callee_name: "charge".to_string(),
+            line_number: 5,
+            receiver_name: Some("gateway".to_string()),
+            receiver_chain: Vec::new(),
+            argument_count: None,
+        };
+        let mut receiver_type_hints = HashSet::new();
+        receiver_type_hints.insert("PaymentGateway".to_string());
+
+        let index = SymbolIndex::build(&candidates);
+        let (targets, relationship) = resolve_targets(
+            &callsite,
+            &caller,
+            &index,
+            &ImportEnv::default(),
+            &receiver_type_hints,
+            &HashSet::new(),
+            &HashSet::new(),
+            &implementors,
+        );
+        let mut owners: Vec<&str> = targets
+            .iter()
+            .map(|(s, _)| s.qualified_name.as_str())
+            .collect();
+        owners.sort();
+        assert_eq!(
+            owners,
+            vec!["mod.PaypalGateway.charge", "mod.StripeGateway.charge"]
+        );
+        assert!(targets.iter().all(|(_, confidence)| *confidence == 0.25));
+        assert_eq!(relationship, "dispatch");
     }
 
     #[test]
@@ -1256,21 +2814,25 @@ mod tests {
             callee_name: "helper".to_string(),
             line_number: 10,
             receiver_name: None,
+            receiver_chain: Vec::new(),
+            argument_count: None,
         };
 
-        let (targets, confidence) = resolve_targets(
+        let index = SymbolIndex::build(&candidates);
+        let (targets, relationship) = resolve_targets(
             &callsite,
             &caller,
-            &candidates,
-            &HashSet::new(),
-            &HashMap::new(),
+            &index,
+            &ImportEnv::default(),
             &HashSet::new(),
             &HashSet::new(),
             &HashSet::new(),
+            &HashMap::new(),
         );
         assert_eq!(targets.len(), 1);
-        assert_eq!(targets[0].file_path, "a.py");
-        assert_eq!(confidence, 1.0);
+        assert_eq!(targets[0].0.file_path, "a.py");
+        assert_eq!(targets[0].1, 1.0);
+        assert_eq!(relationship, "CALLS");
     }
 
     #[test]
@@ -0,0 +1,188 @@
+//! Syntax-aware code chunking for embedding/indexing pipelines.
+//!
+//! Splitting source purely on character counts cuts through function and
+//! class bodies, which hurts embedding quality. This walks the tree-sitter
+//! tree depth-first and only splits at syntax-node boundaries, falling back
+//! to recursing into a node's children when the node itself is too large.
+
+use pyo3::prelude::*;
+use tree_sitter::Node;
+
+use super::parser::RustParsedUnit;
+
+/// A syntax-respecting chunk of source, ready for embedding.
+#[derive(Clone, Debug)]
+pub struct Chunk {
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+}
+
+/// Walk `node`'s children, accumulating contiguous siblings into chunks no
+/// larger than `max_chars`. A child that alone exceeds the budget is
+/// recursed into instead of emitted as one oversized chunk; a leaf that
+/// still exceeds the budget (no children to split further) is emitted whole
+/// rather than cut mid-token.
+fn chunk_node(node: Node, source: &[u8], max_chars: usize, out: &mut Vec<(usize, usize)>) {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+
+    if children.is_empty() {
+        out.push((node.start_byte(), node.end_byte()));
+        return;
+    }
+
+    let mut run_start: Option<usize> = None;
+    let mut run_end = 0usize;
+
+    for child in children {
+        let child_len = child.end_byte() - child.start_byte();
+
+        if child_len > max_chars {
+            if let Some(start) = run_start.take() {
+                out.push((start, run_end));
+            }
+            chunk_node(child, source, max_chars, out);
+            continue;
+        }
+
+        match run_start {
+            None => {
+                run_start = Some(child.start_byte());
+                run_end = child.end_byte();
+            }
+            Some(start) => {
+                if child.end_byte() - start > max_chars {
+                    out.push((start, run_end));
+                    run_start = Some(child.start_byte());
+                    run_end = child.end_byte();
+                } else {
+                    run_end = child.end_byte();
+                }
+            }
+        }
+    }
+
+    if let Some(start) = run_start {
+        out.push((start, run_end));
+    }
+}
+
+fn build_chunks(
+    source: &str,
+    ranges: &[(usize, usize)],
+    overlap: usize,
+) -> Vec<Chunk> {
+    let bytes = source.as_bytes();
+    let line_of = |byte: usize| -> usize { source[..byte.min(source.len())].matches('\n').count() };
+
+    let mut chunks = Vec::with_capacity(ranges.len());
+    let mut prev_tail = String::new();
+    for &(start, end) in ranges {
+        let mut text = String::from_utf8_lossy(&bytes[start..end]).into_owned();
+        if overlap > 0 && !prev_tail.is_empty() {
+            text = format!("{prev_tail}{text}");
+        }
+        chunks.push(Chunk {
+            start_byte: start,
+            end_byte: end,
+            start_line: line_of(start) + 1,
+            end_line: line_of(end) + 1,
+            text: text.clone(),
+        });
+        if overlap > 0 {
+            let tail_start = text.len().saturating_sub(overlap);
+            prev_tail = text[tail_start..].to_string();
+        }
+    }
+    chunks
+}
+
+/// Split a parsed unit into syntax-respecting chunks for embedding.
+///
+/// `max_chars` bounds each chunk's size; `overlap` (if non-zero) prepends
+/// the tail `overlap` characters of the previous chunk to each chunk after
+/// the first, for embedding context continuity.
+///
+/// Python units (`tree: None`) are not supported here — callers should use
+/// the Python-side `ast`-span based chunker exposed to Python directly,
+/// since Python parsing itself is delegated to CPython.
+pub fn chunk_unit(unit: &RustParsedUnit, max_chars: usize, overlap: usize) -> Result<Vec<Chunk>, String> {
+    let tree = unit.tree.as_ref().ok_or_else(|| {
+        "Syntax-aware chunking requires a native tree-sitter tree; Python units must be \
+         chunked on the Python side via ast node spans"
+            .to_string()
+    })?;
+
+    let mut ranges = Vec::new();
+    chunk_node(tree.root_node(), unit.source.as_bytes(), max_chars, &mut ranges);
+    Ok(build_chunks(&unit.source, &ranges, overlap))
+}
+
+/// PyO3 entry point: chunk a parsed file into a list of chunk dicts.
+#[pyfunction]
+#[pyo3(signature = (path, language, max_chars=2000, overlap=0))]
+pub fn chunk_file(
+    py: Python<'_>,
+    path: &str,
+    language: &str,
+    max_chars: usize,
+    overlap: usize,
+) -> PyResult<PyObject> {
+    let unit = super::parser::parse_file_native(std::path::Path::new(path), language)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    let chunks =
+        chunk_unit(&unit, max_chars, overlap).map_err(pyo3::exceptions::PyValueError::new_err)?;
+
+    let list = pyo3::types::PyList::empty(py);
+    for c in chunks {
+        let entry = pyo3::types::PyDict::new(py);
+        entry.set_item("start_byte", c.start_byte)?;
+        entry.set_item("end_byte", c.end_byte)?;
+        entry.set_item("start_line", c.start_line)?;
+        entry.set_item("end_line", c.end_line)?;
+        entry.set_item("text", c.text)?;
+        list.append(entry)?;
+    }
+    Ok(list.into_any().unbind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::parser::parse_file_native;
+
+    #[test]
+    fn test_chunk_respects_function_boundaries() {
+        let mut path = std::env::temp_dir();
+        path.push("bombe_chunking_test.go");
+        std::fs::write(
+            &path,
+            "package main\n\nfunc a() {}\n\nfunc b() {}\n\nfunc c() {}\n",
+        )
+        .unwrap();
+
+        let unit = parse_file_native(&path, "go").unwrap();
+        let chunks = chunk_unit(&unit, 5, 0).unwrap();
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.end_byte > chunk.start_byte);
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_chunk_python_unit_errors() {
+        let unit = RustParsedUnit {
+            path: "foo.py".to_string(),
+            language: "python".to_string(),
+            source: "def foo(): pass".to_string(),
+            tree: None,
+            grammar: None,
+        };
+        assert!(chunk_unit(&unit, 100, 0).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Export CodeXGLUE-style (signature, docstring, body) training/evaluation
+//! samples from indexed symbols.
+//!
+//! Mirrors the CodeXGLUE method-generation dataset contract: one JSONL
+//! record per function/method carrying its `signature`, `docstring`, raw
+//! `body` source text, and a stable hierarchical `id` (e.g. `f3:c0:m5` —
+//! file index, nested class index, method index) built by walking each
+//! symbol's `parent_symbol_id` chain, so the same method keeps the same id
+//! across re-exports as long as its ancestry doesn't change.
+
+use std::collections::{HashMap, HashSet};
+
+use pyo3::prelude::*;
+
+use crate::errors::BombeError;
+use crate::models::{FileRecord, SymbolRecord};
+
+/// Symbol kinds that contribute a `c<n>` (class) segment to a hierarchical
+/// id.
+fn is_class_like(kind: &str) -> bool {
+    matches!(kind, "class" | "interface" | "struct")
+}
+
+/// Symbol kinds eligible for export as a training sample, and that
+/// contribute an `f<n>` (nested function) segment when they're an ancestor
+/// rather than the leaf.
+fn is_function_like(kind: &str) -> bool {
+    matches!(kind, "function" | "method")
+}
+
+/// Read the raw source text spanning `start_line..end_line` (1-indexed,
+/// inclusive) from `file_path`, mirroring
+/// [`crate::query::context::source_fragment`]'s span-slicing logic. Returns
+/// `None` if the file can't be read or the span is empty/out of bounds.
+fn read_body_span(file_path: &str, start_line: i64, end_line: i64) -> Option<String> {
+    let content = std::fs::read_to_string(file_path).ok()?;
+    let lines: Vec<&str> = content.split('\n').collect();
+    let start_idx = (start_line - 1).max(0) as usize;
+    let end_idx = (end_line as usize).min(lines.len());
+    if start_idx >= lines.len() || start_idx >= end_idx {
+        return None;
+    }
+    Some(lines[start_idx..end_idx].join("\n"))
+}
+
+/// Rank of `(id, symbol)` among all symbols sharing its `parent_symbol_id`
+/// and file, restricted to the same ancestry bucket (class-like vs
+/// function-like) — ties break on `(start_line, end_line, id)` so the rank
+/// is stable across runs.
+fn sibling_rank(
+    id: i64,
+    symbol: &SymbolRecord,
+    by_id: &HashMap<i64, &SymbolRecord>,
+    class_bucket: bool,
+) -> usize {
+    let bucket_matches = |kind: &str| {
+        if class_bucket {
+            is_class_like(kind)
+        } else {
+            is_function_like(kind)
+        }
+    };
+    let mut siblings: Vec<(i64, i64, i64)> = by_id
+        .iter()
+        .filter(|(_, other)| {
+            other.parent_symbol_id == symbol.parent_symbol_id
+                && other.file_path == symbol.file_path
+                && bucket_matches(&other.kind)
+        })
+        .map(|(&other_id, other)| (other.start_line, other.end_line, other_id))
+        .collect();
+    siblings.sort();
+    siblings
+        .iter()
+        .position(|&(_, _, other_id)| other_id == id)
+        .unwrap_or(0)
+}
+
+/// Build the hierarchical id path for `(id, symbol)`: `f<file>` plus one
+/// `c<n>`/`f<n>` segment per ancestor (root-most first), plus a trailing
+/// `m<n>` segment for the symbol itself — so a top-level function gets just
+/// `f<i>:m<j>` and a method nested in a class gets `f<i>:c<k>:m<j>`.
+fn build_id_path(
+    id: i64,
+    symbol: &SymbolRecord,
+    by_id: &HashMap<i64, &SymbolRecord>,
+    file_index: &HashMap<&str, usize>,
+) -> String {
+    let mut ancestors: Vec<i64> = Vec::new();
+    let mut cursor = symbol.parent_symbol_id;
+    let mut seen = HashSet::new();
+    while let Some(parent_id) = cursor {
+        if !seen.insert(parent_id) {
+            break; // defend against a cyclic parent chain
+        }
+        ancestors.push(parent_id);
+        cursor = by_id.get(&parent_id).and_then(|p| p.parent_symbol_id);
+    }
+    ancestors.reverse(); // root-most ancestor first
+
+    let file_idx = file_index
+        .get(symbol.file_path.as_str())
+        .copied()
+        .unwrap_or(0);
+    let mut path = format!("f{file_idx}");
+    for ancestor_id in ancestors {
+        let Some(ancestor) = by_id.get(&ancestor_id) else {
+            continue;
+        };
+        let class_bucket = is_class_like(&ancestor.kind);
+        let letter = if class_bucket { 'c' } else { 'f' };
+        let rank = sibling_rank(ancestor_id, ancestor, by_id, class_bucket);
+        path.push_str(&format!(":{letter}{rank}"));
+    }
+    let rank = sibling_rank(id, symbol, by_id, false);
+    path.push_str(&format!(":m{rank}"));
+    path
+}
+
+/// Export `(signature, docstring, body, id)` training samples from a flat
+/// list of `(row_id, SymbolRecord)` pairs plus the `FileRecord`s they belong
+/// to, CodeXGLUE method-generation style — one JSONL line per function or
+/// method.
+///
+/// `row_id` is each symbol's database row id, the same value other symbols'
+/// `parent_symbol_id` points at — needed to walk ancestry since
+/// `SymbolRecord` itself doesn't carry its own id. `body` is taken from
+/// `SymbolRecord.body` when the caller already populated it, otherwise read
+/// fresh from the file's source span. Records whose `kind` isn't
+/// `"function"`/`"method"`, or whose body ends up empty, are skipped so the
+/// output matches the dataset contract.
+///
+/// Returns the samples as newline-joined JSON objects (JSONL text).
+#[pyfunction]
+pub fn export_method_samples(
+    symbols: Vec<(i64, SymbolRecord)>,
+    files: Vec<FileRecord>,
+) -> PyResult<String> {
+    let file_index: HashMap<&str, usize> = files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f.path.as_str(), i))
+        .collect();
+    let by_id: HashMap<i64, &SymbolRecord> = symbols.iter().map(|(id, s)| (*id, s)).collect();
+
+    let mut lines = Vec::new();
+    for (id, symbol) in &symbols {
+        if !is_function_like(&symbol.kind) {
+            continue;
+        }
+        let body = match &symbol.body {
+            Some(b) if !b.trim().is_empty() => b.clone(),
+            _ => match read_body_span(&symbol.file_path, symbol.start_line, symbol.end_line) {
+                Some(b) if !b.trim().is_empty() => b,
+                _ => continue,
+            },
+        };
+
+        let sample_id = build_id_path(*id, symbol, &by_id, &file_index);
+        let record = serde_json::json!({
+            "id": sample_id,
+            "signature": symbol.signature,
+            "docstring": symbol.docstring,
+            "body": body,
+        });
+        lines.push(
+            serde_json::to_string(&record).map_err(|e| {
+                BombeError::Query(format!("failed to serialize method sample: {e}"))
+            })?,
+        );
+    }
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(
+        name: &str,
+        kind: &str,
+        file_path: &str,
+        start_line: i64,
+        end_line: i64,
+        parent_symbol_id: Option<i64>,
+    ) -> SymbolRecord {
+        SymbolRecord {
+            name: name.to_string(),
+            qualified_name: name.to_string(),
+            kind: kind.to_string(),
+            file_path: file_path.to_string(),
+            start_line,
+            end_line,
+            signature: Some(format!("fn {name}()")),
+            return_type: None,
+            visibility: None,
+            is_async: false,
+            is_static: false,
+            parent_symbol_id,
+            docstring: Some("docs".to_string()),
+            body: Some("body text".to_string()),
+            structural_id: None,
+            pagerank_score: 0.0,
+            parameters: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_top_level_function_gets_file_and_method_segments() {
+        let files = vec![FileRecord {
+            path: "a.py".to_string(),
+            language: "python".to_string(),
+            content_hash: "h".to_string(),
+            size_bytes: None,
+        }];
+        let symbols = vec![(1, symbol("foo", "function", "a.py", 1, 2, None))];
+
+        let output = export_method_samples(symbols, files).unwrap();
+        assert!(output.contains("\"id\":\"f0:m0\""));
+    }
+
+    #[test]
+    fn test_method_nested_in_class_gets_class_segment() {
+        let files = vec![FileRecord {
+            path: "a.py".to_string(),
+            language: "python".to_string(),
+            content_hash: "h".to_string(),
+            size_bytes: None,
+        }];
+        let symbols = vec![
+            (1, symbol("Foo", "class", "a.py", 1, 10, None)),
+            (2, symbol("bar", "method", "a.py", 2, 3, Some(1))),
+        ];
+
+        let output = export_method_samples(symbols, files).unwrap();
+        assert!(output.contains("\"id\":\"f0:c0:m0\""));
+    }
+
+    #[test]
+    fn test_non_function_kind_is_skipped() {
+        let files = vec![FileRecord {
+            path: "a.py".to_string(),
+            language: "python".to_string(),
+            content_hash: "h".to_string(),
+            size_bytes: None,
+        }];
+        let symbols = vec![(1, symbol("Foo", "class", "a.py", 1, 10, None))];
+
+        let output = export_method_samples(symbols, files).unwrap();
+        assert!(output.is_empty());
+    }
+}
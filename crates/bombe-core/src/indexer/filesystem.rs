@@ -2,10 +2,16 @@
 
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use pyo3::prelude::*;
 use sha2::{Digest, Sha256};
 
+use super::matcher::{
+    build_default_matcher, DifferenceMatcher, ExcludeMatcher, IncludeMatcher, Matcher,
+};
+use crate::errors::BombeResult;
+
 const LANGUAGE_BY_EXTENSION: &[(&str, &str)] = &[
     (".py", "python"),
     (".java", "java"),
@@ -14,252 +20,172 @@ const LANGUAGE_BY_EXTENSION: &[(&str, &str)] = &[
     (".go", "go"),
 ];
 
-const DEFAULT_SENSITIVE_EXCLUDE_PATTERNS: &[&str] = &[
-    ".env",
-    ".env.*",
-    "*.pem",
-    "*.key",
-    "*.p12",
-    "*secret*",
-    "*secrets*",
-    "*credential*",
-    "id_rsa",
-    "id_dsa",
-];
-
 const IMPLICIT_IGNORED_DIRS: &[&str] = &[".git", ".bombe"];
 
-struct IgnoreRule {
-    pattern: String,
-    directory_only: bool,
+/// True if `rel` is, or lies under, one of [`IMPLICIT_IGNORED_DIRS`] at the
+/// repo root — used by [`crate::indexer::watch`]'s event filtering, since a
+/// watcher sees a flat path per event rather than walking down to it
+/// directory by directory. [`iter_repo_files`] does not call this; its walk
+/// prunes any directory with one of these names, at any depth.
+pub(crate) fn is_implicitly_ignored_path(rel: &str) -> bool {
+    IMPLICIT_IGNORED_DIRS
+        .iter()
+        .any(|dir| rel == *dir || rel.starts_with(&format!("{dir}/")))
 }
 
-fn load_ignore_file(path: &Path) -> Vec<IgnoreRule> {
-    if !path.exists() {
-        return vec![];
-    }
-    let content = match std::fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(_) => return vec![],
-    };
-    content
-        .lines()
-        .filter_map(|line| {
-            let stripped = line.trim();
-            if stripped.is_empty() || stripped.starts_with('#') {
-                return None;
-            }
-            let directory_only = stripped.ends_with('/');
-            let mut pattern = if directory_only {
-                stripped[..stripped.len() - 1].to_string()
-            } else {
-                stripped.to_string()
-            };
-            if pattern.starts_with("./") {
-                pattern = pattern[2..].to_string();
-            }
-            Some(IgnoreRule {
-                pattern,
-                directory_only,
-            })
-        })
-        .collect()
+fn relative_path(repo_root: &Path, path: &Path) -> String {
+    path.strip_prefix(repo_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
 }
 
-fn matches_pattern(rel_path: &str, pattern: &str) -> bool {
-    let normalized = rel_path.replace('\\', "/");
-    // Simple glob matching
-    glob_match(&normalized, pattern)
-        || glob_match(
-            Path::new(&normalized)
-                .file_name()
-                .map(|f| f.to_string_lossy().to_string())
-                .unwrap_or_default()
-                .as_str(),
-            pattern,
-        )
-}
+/// Splits `dir`'s immediate children into subdirectories that survive
+/// ignore/scope pruning and files that pass the full matcher, relative to
+/// `repo_root`. Shared by both the parallel and sequential walks below so
+/// pruning semantics (implicit `.git`/`.bombe`, `.gitignore`/`.bombeignore`,
+/// and `path:`/`rootfilesin:` scoping) stay in exactly one place.
+fn scan_dir(
+    dir: &Path,
+    repo_root: &Path,
+    matcher: &DifferenceMatcher<IncludeMatcher, ExcludeMatcher>,
+    implicit_ignored: &HashSet<&str>,
+) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return (Vec::new(), Vec::new()),
+    };
 
-fn glob_match(text: &str, pattern: &str) -> bool {
-    // Simple glob match supporting * and ?
-    let t_chars: Vec<char> = text.chars().collect();
-    let p_chars: Vec<char> = pattern.chars().collect();
-    let (tl, pl) = (t_chars.len(), p_chars.len());
-    let mut dp = vec![vec![false; pl + 1]; tl + 1];
-    dp[0][0] = true;
-    for j in 1..=pl {
-        if p_chars[j - 1] == '*' {
-            dp[0][j] = dp[0][j - 1];
-        }
-    }
-    for i in 1..=tl {
-        for j in 1..=pl {
-            if p_chars[j - 1] == '*' {
-                dp[i][j] = dp[i][j - 1] || dp[i - 1][j];
-            } else if p_chars[j - 1] == '?' || t_chars[i - 1] == p_chars[j - 1] {
-                dp[i][j] = dp[i - 1][j - 1];
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            if implicit_ignored.contains(name.as_str()) {
+                continue;
+            }
+            let rel = relative_path(repo_root, &path);
+            if matcher.exclude().matches(&rel, true) {
+                continue;
+            }
+            // A narrow `path:`/`rootfilesin:` scope can rule out a whole
+            // subtree up front, so the walk never pays to descend into
+            // directories no in-scope file could live under.
+            if !matcher.include().could_contain_dir(&rel) {
+                continue;
+            }
+            dirs.push(path);
+        } else {
+            let rel = relative_path(repo_root, &path);
+            if matcher.matches(&rel, false) {
+                files.push(path);
             }
         }
     }
-    dp[tl][pl]
+
+    (dirs, files)
 }
 
-fn is_ignored(rel_path: &str, is_dir: bool, rules: &[IgnoreRule]) -> bool {
-    let normalized = rel_path.replace('\\', "/");
-    for rule in rules {
-        if rule.directory_only && !is_dir {
-            continue;
-        }
-        if matches_pattern(&normalized, &rule.pattern) {
-            return true;
-        }
-        if normalized.starts_with(&format!("{}/", rule.pattern)) {
-            return true;
-        }
+fn walk_dir_sequential(
+    dir: &Path,
+    repo_root: &Path,
+    matcher: &DifferenceMatcher<IncludeMatcher, ExcludeMatcher>,
+    implicit_ignored: &HashSet<&str>,
+    result: &mut Vec<PathBuf>,
+) {
+    let (dirs, files) = scan_dir(dir, repo_root, matcher, implicit_ignored);
+    result.extend(files);
+    for dir_path in dirs {
+        walk_dir_sequential(&dir_path, repo_root, matcher, implicit_ignored, result);
     }
-    false
 }
 
-fn matches_any_include(rel_path: &str, include_patterns: &[String]) -> bool {
-    if include_patterns.is_empty() {
-        return true;
+/// Work-stealing counterpart of [`walk_dir_sequential`]: each directory is
+/// `read_dir`'d on whichever rayon worker thread picks it up, and every
+/// subdirectory that survives pruning is `scope.spawn`'d as its own task, so
+/// idle threads steal pending directories from busy ones the way ripgrep's
+/// walker does, instead of one thread draining the tree alone.
+fn walk_dir_parallel<'scope>(
+    scope: &rayon::Scope<'scope>,
+    dir: PathBuf,
+    repo_root: &'scope Path,
+    matcher: &'scope DifferenceMatcher<IncludeMatcher, ExcludeMatcher>,
+    implicit_ignored: &'scope HashSet<&'scope str>,
+    results: &'scope Mutex<Vec<PathBuf>>,
+) {
+    let (dirs, files) = scan_dir(&dir, repo_root, matcher, implicit_ignored);
+    if !files.is_empty() {
+        results
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .extend(files);
+    }
+    for dir_path in dirs {
+        scope.spawn(move |s| {
+            walk_dir_parallel(s, dir_path, repo_root, matcher, implicit_ignored, results)
+        });
     }
-    include_patterns
-        .iter()
-        .any(|p| matches_pattern(rel_path, p))
 }
 
+/// Scans `repo_root` for every file the include/exclude matchers admit,
+/// using a work-stealing parallel traversal (ripgrep-style) across
+/// `workers` threads — `None` defaults to [`std::thread::available_parallelism`].
+/// Falls back to a single-threaded walk if the thread pool fails to start.
+/// The result is always sorted, so downstream indexing and hashing see a
+/// deterministic file order regardless of which worker found what first.
 pub fn iter_repo_files(
     repo_root: &Path,
     include_patterns: Option<&[String]>,
     exclude_patterns: Option<&[String]>,
-) -> Vec<PathBuf> {
-    let mut rules: Vec<IgnoreRule> = Vec::new();
-    rules.extend(load_ignore_file(&repo_root.join(".gitignore")));
-    rules.extend(load_ignore_file(&repo_root.join(".bombeignore")));
-
-    let exclude_sensitive = match std::env::var("BOMBE_EXCLUDE_SENSITIVE") {
-        Ok(val) => {
-            let v = val.trim().to_lowercase();
-            !matches!(v.as_str(), "0" | "false" | "no" | "off")
-        }
-        Err(_) => true,
-    };
-    if exclude_sensitive {
-        for pattern in DEFAULT_SENSITIVE_EXCLUDE_PATTERNS {
-            rules.push(IgnoreRule {
-                pattern: pattern.to_string(),
-                directory_only: false,
-            });
-        }
-    }
-
-    let include: Vec<String> = include_patterns
-        .unwrap_or(&[])
-        .iter()
-        .filter(|p| !p.trim().is_empty())
-        .cloned()
-        .collect();
-
-    if let Some(excludes) = exclude_patterns {
-        for pattern in excludes {
-            let stripped = pattern.trim();
-            if stripped.is_empty() {
-                continue;
-            }
-            let directory_only = stripped.ends_with('/');
-            let mut p = if directory_only {
-                stripped[..stripped.len() - 1].to_string()
-            } else {
-                stripped.to_string()
-            };
-            if p.starts_with("./") {
-                p = p[2..].to_string();
-            }
-            rules.push(IgnoreRule {
-                pattern: p,
-                directory_only,
-            });
-        }
-    }
-
+    workers: Option<usize>,
+) -> BombeResult<Vec<PathBuf>> {
+    let matcher = build_default_matcher(repo_root, include_patterns, exclude_patterns)?;
     let implicit_ignored: HashSet<&str> = IMPLICIT_IGNORED_DIRS.iter().copied().collect();
-    let mut result = Vec::new();
-
-    fn walk_dir(
-        dir: &Path,
-        repo_root: &Path,
-        rules: &[IgnoreRule],
-        include: &[String],
-        implicit_ignored: &HashSet<&str>,
-        result: &mut Vec<PathBuf>,
-    ) {
-        let entries = match std::fs::read_dir(dir) {
-            Ok(e) => e,
-            Err(_) => return,
-        };
-
-        let mut dirs = Vec::new();
-        let mut files = Vec::new();
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            let name = entry.file_name().to_string_lossy().to_string();
-
-            if path.is_dir() {
-                if implicit_ignored.contains(name.as_str()) {
-                    continue;
-                }
-                let rel = path
-                    .strip_prefix(repo_root)
-                    .unwrap_or(&path)
-                    .to_string_lossy()
-                    .replace('\\', "/");
-                if is_ignored(&rel, true, rules) {
-                    continue;
-                }
-                dirs.push(path);
-            } else {
-                files.push(path);
-            }
-        }
 
-        for file_path in files {
-            let rel = file_path
-                .strip_prefix(repo_root)
-                .unwrap_or(&file_path)
-                .to_string_lossy()
-                .replace('\\', "/");
-            if is_ignored(&rel, false, rules) {
-                continue;
-            }
-            if !matches_any_include(&rel, include) {
-                continue;
-            }
-            result.push(file_path);
-        }
+    let worker_count = workers.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
 
-        for dir_path in dirs {
-            walk_dir(
-                dir_path.as_path(),
+    let mut result = match rayon::ThreadPoolBuilder::new()
+        .num_threads(worker_count.max(1))
+        .build()
+    {
+        Ok(pool) => {
+            let results = Mutex::new(Vec::new());
+            pool.scope(|s| {
+                walk_dir_parallel(
+                    s,
+                    repo_root.to_path_buf(),
+                    repo_root,
+                    &matcher,
+                    &implicit_ignored,
+                    &results,
+                );
+            });
+            results
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        }
+        Err(_) => {
+            let mut result = Vec::new();
+            walk_dir_sequential(
+                repo_root,
                 repo_root,
-                rules,
-                include,
-                implicit_ignored,
-                result,
+                &matcher,
+                &implicit_ignored,
+                &mut result,
             );
+            result
         }
-    }
+    };
 
-    walk_dir(
-        repo_root,
-        repo_root,
-        &rules,
-        &include,
-        &implicit_ignored,
-        &mut result,
-    );
-    result
+    result.sort();
+    Ok(result)
 }
 
 #[pyfunction]
@@ -282,3 +208,72 @@ pub fn compute_content_hash(path: &str) -> PyResult<String> {
     hasher.update(&data);
     Ok(format!("{:x}", hasher.finalize()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negated_rule_cannot_resurrect_a_file_under_an_excluded_directory() {
+        // Mirrors real git: a `dir/` rule prunes the whole directory from
+        // the walk, so a later `!dir/keep.txt` can't bring a file back — the
+        // walk never evaluates paths beneath a pruned directory.
+        let tmp =
+            std::env::temp_dir().join(format!("bombe_fs_test_negation_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(tmp.join("dir")).unwrap();
+        std::fs::write(tmp.join("dir/keep.txt"), "keep me").unwrap();
+        std::fs::write(tmp.join(".gitignore"), "dir/\n!dir/keep.txt\n").unwrap();
+
+        let files = iter_repo_files(&tmp, None, None, None).unwrap();
+        assert!(!files.iter().any(|p| p.ends_with("keep.txt")));
+
+        let _ = std::fs::remove_dir_all(&tmp);
+    }
+
+    #[test]
+    fn include_and_exclude_patterns_compose_through_iter_repo_files() {
+        let tmp =
+            std::env::temp_dir().join(format!("bombe_fs_test_include_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(&tmp).unwrap();
+        std::fs::write(tmp.join("main.py"), "pass").unwrap();
+        std::fs::write(tmp.join("main.go"), "package main").unwrap();
+
+        let files = iter_repo_files(&tmp, Some(&["*.py".to_string()]), None, None).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("main.py"));
+
+        let _ = std::fs::remove_dir_all(&tmp);
+    }
+
+    #[test]
+    fn path_scope_prunes_out_of_scope_subtrees() {
+        let tmp = std::env::temp_dir().join(format!("bombe_fs_test_scope_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(tmp.join("services/api")).unwrap();
+        std::fs::create_dir_all(tmp.join("services/web")).unwrap();
+        std::fs::write(tmp.join("services/api/main.py"), "pass").unwrap();
+        std::fs::write(tmp.join("services/web/main.py"), "pass").unwrap();
+
+        let files =
+            iter_repo_files(&tmp, Some(&["path:services/api".to_string()]), None, None).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("services/api/main.py"));
+
+        let _ = std::fs::remove_dir_all(&tmp);
+    }
+
+    #[test]
+    fn unknown_scope_prefix_errors_instead_of_matching_nothing() {
+        let tmp =
+            std::env::temp_dir().join(format!("bombe_fs_test_bad_scope_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(&tmp).unwrap();
+
+        let result = iter_repo_files(&tmp, Some(&["bogus:foo".to_string()]), None, None);
+        assert!(result.is_err());
+
+        let _ = std::fs::remove_dir_all(&tmp);
+    }
+}
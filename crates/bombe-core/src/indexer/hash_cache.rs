@@ -0,0 +1,256 @@
+//! mtime+size cache for [`compute_content_hash`], so a reindex skips the
+//! read+SHA-256 of every file whose stat looks unchanged since the cache
+//! was last written, the way a dirstate avoids re-diffing unchanged files
+//! in a version-control working copy.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use pyo3::prelude::*;
+
+use crate::indexer::filesystem::compute_content_hash;
+
+fn cache_file_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".bombe").join("hash_cache.json")
+}
+
+struct CacheEntry {
+    size: u64,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+    sha256: String,
+}
+
+fn load_cache(repo_root: &Path) -> (HashMap<String, CacheEntry>, Option<(i64, u32)>) {
+    let content = match std::fs::read_to_string(cache_file_path(repo_root)) {
+        Ok(c) => c,
+        Err(_) => return (HashMap::new(), None),
+    };
+    let value: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(_) => return (HashMap::new(), None),
+    };
+
+    let last_scan_started = value
+        .get("last_scan_started")
+        .and_then(|v| v.as_array())
+        .and_then(|pair| {
+            let secs = pair.first()?.as_i64()?;
+            let nanos = pair.get(1)?.as_u64()?;
+            Some((secs, nanos as u32))
+        });
+
+    let mut entries = HashMap::new();
+    if let Some(obj) = value.get("entries").and_then(|v| v.as_object()) {
+        for (rel_path, entry) in obj {
+            let (Some(size), Some(mtime_secs), Some(mtime_nanos), Some(sha256)) = (
+                entry.get("size").and_then(|v| v.as_u64()),
+                entry.get("mtime_secs").and_then(|v| v.as_i64()),
+                entry.get("mtime_nanos").and_then(|v| v.as_u64()),
+                entry.get("sha256").and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+            entries.insert(
+                rel_path.clone(),
+                CacheEntry {
+                    size,
+                    mtime_secs,
+                    mtime_nanos: mtime_nanos as u32,
+                    sha256: sha256.to_string(),
+                },
+            );
+        }
+    }
+    (entries, last_scan_started)
+}
+
+fn save_cache(
+    repo_root: &Path,
+    entries: &HashMap<String, CacheEntry>,
+    last_scan_started: Option<(i64, u32)>,
+) {
+    let mut entries_obj = serde_json::Map::new();
+    for (rel_path, entry) in entries {
+        entries_obj.insert(
+            rel_path.clone(),
+            serde_json::json!({
+                "size": entry.size,
+                "mtime_secs": entry.mtime_secs,
+                "mtime_nanos": entry.mtime_nanos,
+                "sha256": entry.sha256,
+            }),
+        );
+    }
+
+    let mut root = serde_json::Map::new();
+    if let Some((secs, nanos)) = last_scan_started {
+        root.insert(
+            "last_scan_started".to_string(),
+            serde_json::json!([secs, nanos]),
+        );
+    }
+    root.insert(
+        "entries".to_string(),
+        serde_json::Value::Object(entries_obj),
+    );
+
+    let path = cache_file_path(repo_root);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(serialized) = serde_json::to_string(&serde_json::Value::Object(root)) {
+        let _ = std::fs::write(path, serialized);
+    }
+}
+
+fn mtime_parts(metadata: &std::fs::Metadata) -> (i64, u32) {
+    let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+    let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    (since_epoch.as_secs() as i64, since_epoch.subsec_nanos())
+}
+
+/// Computes (and caches) `relative_path`'s content hash under `repo_root`,
+/// skipping the read+SHA-256 when size and mtime both match the cached
+/// entry. A file whose mtime exactly equals the cache's recorded
+/// `last_scan_started` timestamp is treated as ambiguous and always
+/// re-hashed: on filesystems with coarse (e.g. 1-second) mtime resolution,
+/// an edit landing in the same tick the previous scan started in would
+/// otherwise look unchanged, the same same-second race Mercurial's
+/// dirstate-v2 guards against. Call [`record_scan_start`] once per scan,
+/// before hashing any files, so this timestamp is set.
+#[pyfunction]
+pub fn compute_content_hash_cached(repo_root: &str, relative_path: &str) -> PyResult<String> {
+    let repo_root = Path::new(repo_root);
+    let full_path = repo_root.join(relative_path);
+    let metadata = std::fs::metadata(&full_path)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+    let size = metadata.len();
+    let (mtime_secs, mtime_nanos) = mtime_parts(&metadata);
+
+    let (mut entries, last_scan_started) = load_cache(repo_root);
+    let ambiguous = last_scan_started
+        .map(|(secs, nanos)| secs == mtime_secs && nanos == mtime_nanos)
+        .unwrap_or(false);
+
+    if !ambiguous {
+        if let Some(cached) = entries.get(relative_path) {
+            if cached.size == size
+                && cached.mtime_secs == mtime_secs
+                && cached.mtime_nanos == mtime_nanos
+            {
+                return Ok(cached.sha256.clone());
+            }
+        }
+    }
+
+    let sha256 = compute_content_hash(&full_path.to_string_lossy())?;
+    entries.insert(
+        relative_path.to_string(),
+        CacheEntry {
+            size,
+            mtime_secs,
+            mtime_nanos,
+            sha256: sha256.clone(),
+        },
+    );
+    save_cache(repo_root, &entries, last_scan_started);
+    Ok(sha256)
+}
+
+/// Records `repo_root`'s hash cache as having started a new scan at the
+/// current time, so the next round of [`compute_content_hash_cached`] calls
+/// knows which mtimes would be ambiguous against it.
+#[pyfunction]
+pub fn record_scan_start(repo_root: &str) -> PyResult<()> {
+    let repo_root = Path::new(repo_root);
+    let (entries, _) = load_cache(repo_root);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    save_cache(
+        repo_root,
+        &entries,
+        Some((now.as_secs() as i64, now.subsec_nanos())),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("bombe_hash_cache_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn unchanged_file_is_served_from_cache() {
+        let repo_root = scratch_dir("unchanged");
+        std::fs::write(repo_root.join("a.py"), "one").unwrap();
+        compute_content_hash_cached(&repo_root.to_string_lossy(), "a.py").unwrap();
+
+        // Overwrite the cached sha256 with a sentinel, leaving the recorded
+        // size/mtime untouched — only a genuine cache hit (not a re-hash of
+        // the unchanged file) can make the next call return the sentinel.
+        let (mut entries, last_scan_started) = load_cache(&repo_root);
+        entries.get_mut("a.py").unwrap().sha256 = "sentinel-hash".to_string();
+        save_cache(&repo_root, &entries, last_scan_started);
+
+        let hash = compute_content_hash_cached(&repo_root.to_string_lossy(), "a.py").unwrap();
+        assert_eq!(hash, "sentinel-hash");
+
+        let _ = std::fs::remove_dir_all(&repo_root);
+    }
+
+    #[test]
+    fn size_change_invalidates_cache() {
+        let repo_root = scratch_dir("size_change");
+        std::fs::write(repo_root.join("a.py"), "one").unwrap();
+        let first = compute_content_hash_cached(&repo_root.to_string_lossy(), "a.py").unwrap();
+
+        std::fs::write(repo_root.join("a.py"), "a much longer second version").unwrap();
+        let second = compute_content_hash_cached(&repo_root.to_string_lossy(), "a.py").unwrap();
+
+        assert_ne!(first, second);
+        let (entries, _) = load_cache(&repo_root);
+        assert_eq!(
+            entries.get("a.py").unwrap().size,
+            "a much longer second version".len() as u64
+        );
+
+        let _ = std::fs::remove_dir_all(&repo_root);
+    }
+
+    #[test]
+    fn mtime_matching_last_scan_start_is_treated_as_ambiguous() {
+        let repo_root = scratch_dir("ambiguous");
+        std::fs::write(repo_root.join("a.py"), "one").unwrap();
+        let metadata = std::fs::metadata(repo_root.join("a.py")).unwrap();
+        let (mtime_secs, mtime_nanos) = mtime_parts(&metadata);
+
+        // Pretend a scan started at exactly this file's mtime, then cache
+        // it with that same mtime — the hallmark of the same-second race.
+        let mut entries = HashMap::new();
+        entries.insert(
+            "a.py".to_string(),
+            CacheEntry {
+                size: 3,
+                mtime_secs,
+                mtime_nanos,
+                sha256: "stale-hash-that-would-be-wrong".to_string(),
+            },
+        );
+        save_cache(&repo_root, &entries, Some((mtime_secs, mtime_nanos)));
+
+        let hash = compute_content_hash_cached(&repo_root.to_string_lossy(), "a.py").unwrap();
+        assert_ne!(hash, "stale-hash-that-would-be-wrong");
+
+        let _ = std::fs::remove_dir_all(&repo_root);
+    }
+}
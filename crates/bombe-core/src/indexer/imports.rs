@@ -1,9 +1,13 @@
 //! Import resolution from language-specific import records to repository files.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
 
-use crate::indexer::symbols::ExtractedImport;
+use regex::Regex;
+
+use crate::indexer::callgraph::symbol_id;
+use crate::indexer::symbols::{to_module_name, ExtractedExport, ExtractedImport, ExtractedSymbol};
 
 pub struct ImportEdge {
     pub source_id: i64,
@@ -14,6 +18,12 @@ pub struct ImportEdge {
     pub file_path: String,
     pub line_number: i64,
     pub confidence: f64,
+    /// Declared version of the target package, for `DEPENDS_ON` edges
+    /// produced by [`classify_external_deps`]. `None` for file-to-file
+    /// `IMPORTS`/`CIRCULAR_IMPORT` edges, and also for a `DEPENDS_ON` whose
+    /// package never turned up in a manifest (stdlib, or just undeclared) —
+    /// that absence is itself the "unknown" marker callers filter on.
+    pub version: Option<String>,
 }
 
 pub struct ExternalDep {
@@ -23,6 +33,16 @@ pub struct ExternalDep {
     pub line_number: Option<i64>,
 }
 
+/// One edge of the [`build_module_graph`] graph: `source_file_path` names
+/// `target_file_path` via some import, both paths relative to the repo
+/// root. Unlike [`ImportEdge`] this carries no symbol-resolution metadata
+/// (confidence, relationship, ids) — it's the plain file-to-file linkage a
+/// caller walks to traverse the import graph.
+pub struct ModuleGraphEdge {
+    pub source_file_path: String,
+    pub target_file_path: String,
+}
+
 fn file_id(path: &str) -> i64 {
     (crc32fast::hash(path.as_bytes()) & 0x7FFFFFFF) as i64
 }
@@ -58,7 +78,10 @@ fn resolve_python(
     candidates.into_iter().find(|c| all_files.contains_key(c))
 }
 
-fn resolve_java(module_name: &str, all_files: &HashMap<String, String>) -> Option<String> {
+/// Every `.java` file `module_name` resolves to: a wildcard `package.*`
+/// fans out to every file directly under that package directory, while a
+/// plain `package.ClassName` import names exactly one file (or none).
+fn resolve_java(module_name: &str, all_files: &HashMap<String, String>) -> Vec<String> {
     if let Some(stripped) = module_name.strip_suffix(".*") {
         let package_prefix = stripped.replace('.', "/");
         let mut candidates: Vec<String> = all_files
@@ -67,38 +90,55 @@ fn resolve_java(module_name: &str, all_files: &HashMap<String, String>) -> Optio
             .cloned()
             .collect();
         candidates.sort();
-        return candidates.into_iter().next();
+        return candidates;
     }
     let candidate = format!("{}.java", module_name.replace('.', "/"));
     if all_files.contains_key(&candidate) {
-        Some(candidate)
+        vec![candidate]
     } else {
-        None
+        Vec::new()
     }
 }
 
 fn resolve_typescript(
+    repo_root: &str,
     source_path: &str,
     module_name: &str,
     all_files: &HashMap<String, String>,
 ) -> Option<String> {
-    if !module_name.starts_with('.') {
-        return None;
+    if module_name.starts_with('.') {
+        let source_dir = Path::new(source_path).parent().unwrap_or(Path::new(""));
+        let resolved_base = normalize_posix_path(
+            &source_dir
+                .join(module_name)
+                .to_string_lossy()
+                .replace('\\', "/"),
+        );
+        return expand_ts_suffixes(&resolved_base, all_files);
     }
-    let source_dir = Path::new(source_path).parent().unwrap_or(Path::new(""));
-    let joined = source_dir.join(module_name);
-    let resolved_base = normalize_posix_path(&joined.to_string_lossy().replace('\\', "/"));
 
+    let tsconfig = load_tsconfig(repo_root)?;
+    for base in tsconfig.candidate_bases(module_name) {
+        if let Some(resolved) = expand_ts_suffixes(&base, all_files) {
+            return Some(resolved);
+        }
+    }
+    None
+}
+
+/// Tries `base`, then each `.ts`/`.tsx`/`.js`/`.jsx` suffix, then each
+/// `/index.*` variant, returning the first that names a real file.
+fn expand_ts_suffixes(base: &str, all_files: &HashMap<String, String>) -> Option<String> {
     let candidates = [
-        resolved_base.clone(),
-        format!("{resolved_base}.ts"),
-        format!("{resolved_base}.tsx"),
-        format!("{resolved_base}.js"),
-        format!("{resolved_base}.jsx"),
-        format!("{resolved_base}/index.ts"),
-        format!("{resolved_base}/index.tsx"),
-        format!("{resolved_base}/index.js"),
-        format!("{resolved_base}/index.jsx"),
+        base.to_string(),
+        format!("{base}.ts"),
+        format!("{base}.tsx"),
+        format!("{base}.js"),
+        format!("{base}.jsx"),
+        format!("{base}/index.ts"),
+        format!("{base}/index.tsx"),
+        format!("{base}/index.js"),
+        format!("{base}/index.jsx"),
     ];
     for candidate in &candidates {
         let normalized = normalize_posix_path(candidate);
@@ -109,12 +149,214 @@ fn resolve_typescript(
     None
 }
 
+// ---------------------------------------------------------------------------
+// tsconfig.json baseUrl / paths resolution
+// ---------------------------------------------------------------------------
+
+/// How deep an `extends` chain may go before it's treated as a (likely
+/// circular) misconfiguration and abandoned — same purpose as the
+/// traversal guards in `query::guards`, just sized for config-file nesting
+/// rather than graph depth.
+const MAX_TSCONFIG_EXTENDS_DEPTH: u32 = 8;
+
+struct TsConfig {
+    /// Directory `baseUrl`-relative bare specifiers resolve against;
+    /// defaults to the tsconfig's own directory when `baseUrl` is unset.
+    base_dir: String,
+    /// `(pattern, targets)` pairs from `compilerOptions.paths`, sorted so
+    /// the longest (most specific) pattern prefix is tried first.
+    paths: Vec<(String, Vec<String>)>,
+}
+
+impl TsConfig {
+    /// Every path worth trying for `module_name`, longest/most-specific
+    /// `paths` match first, `baseUrl`-relative resolution last.
+    fn candidate_bases(&self, module_name: &str) -> Vec<String> {
+        let mut bases = Vec::new();
+        for (pattern, targets) in &self.paths {
+            if let Some(captured) = match_paths_pattern(pattern, module_name) {
+                for target in targets {
+                    let substituted = target.replacen('*', &captured, 1);
+                    bases.push(normalize_posix_path(&format!(
+                        "{}/{}",
+                        self.base_dir, substituted
+                    )));
+                }
+            }
+        }
+        bases.push(normalize_posix_path(&format!(
+            "{}/{}",
+            self.base_dir, module_name
+        )));
+        bases
+    }
+}
+
+/// Matches a `paths` pattern (at most one `*`) against `module_name`,
+/// returning the text the `*` captured (empty string for an exact,
+/// wildcard-free match).
+fn match_paths_pattern(pattern: &str, module_name: &str) -> Option<String> {
+    match pattern.split_once('*') {
+        None => (pattern == module_name).then(|| String::new()),
+        Some((prefix, suffix)) => module_name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+            .map(|captured| captured.to_string()),
+    }
+}
+
+fn strip_jsonc_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn read_tsconfig_json(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_jsonc_comments(&content)).ok()
+}
+
+/// Resolves `tsconfig.json`'s `extends` chain, merging each parent's
+/// `compilerOptions` underneath the child's (child wins on conflicting
+/// keys, same as TypeScript itself). Bails out after
+/// [`MAX_TSCONFIG_EXTENDS_DEPTH`] hops rather than looping forever on a
+/// self-referential `extends`.
+fn resolve_tsconfig_chain(path: &Path, depth: u32) -> Option<serde_json::Value> {
+    if depth > MAX_TSCONFIG_EXTENDS_DEPTH {
+        return None;
+    }
+    let mut value = read_tsconfig_json(path)?;
+    if let Some(extends) = value
+        .get("extends")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+    {
+        let parent_path = path.parent().unwrap_or(Path::new("")).join(&extends);
+        let parent_path = if parent_path.extension().is_some() {
+            parent_path
+        } else {
+            parent_path.with_extension("json")
+        };
+        if let Some(parent_value) = resolve_tsconfig_chain(&parent_path, depth + 1) {
+            merge_compiler_options(&mut value, &parent_value);
+        }
+    }
+    Some(value)
+}
+
+/// Fills any `compilerOptions` key missing from `child` in with `parent`'s
+/// value — `child`'s own keys are left untouched.
+fn merge_compiler_options(child: &mut serde_json::Value, parent: &serde_json::Value) {
+    let parent_options = parent.get("compilerOptions");
+    let Some(parent_options) = parent_options.and_then(|v| v.as_object()) else {
+        return;
+    };
+    let child_options = child
+        .as_object_mut()
+        .unwrap()
+        .entry("compilerOptions")
+        .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+    let child_options = child_options.as_object_mut().unwrap();
+    for (key, value) in parent_options {
+        child_options
+            .entry(key.clone())
+            .or_insert_with(|| value.clone());
+    }
+}
+
+fn load_tsconfig(repo_root: &str) -> Option<TsConfig> {
+    let path = Path::new(repo_root).join("tsconfig.json");
+    let config = resolve_tsconfig_chain(&path, 0)?;
+    let compiler_options = config.get("compilerOptions")?;
+
+    let base_url = compiler_options
+        .get("baseUrl")
+        .and_then(|v| v.as_str())
+        .unwrap_or(".");
+    let base_dir = normalize_posix_path(
+        &Path::new(repo_root)
+            .join(base_url)
+            .to_string_lossy()
+            .replace('\\', "/"),
+    );
+
+    let mut paths: Vec<(String, Vec<String>)> = compiler_options
+        .get("paths")
+        .and_then(|v| v.as_object())
+        .map(|map| {
+            map.iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets
+                        .as_array()
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|v| v.as_str().map(str::to_string))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    (pattern.clone(), targets)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Longest prefix (text before the first `*`, or the whole pattern for
+    // an exact match) first, so a more specific alias wins over a broader
+    // catch-all one.
+    paths.sort_by_key(|(pattern, _)| {
+        std::cmp::Reverse(pattern.split('*').next().unwrap_or(pattern).len())
+    });
+
+    Some(TsConfig { base_dir, paths })
+}
+
+/// Every `.go` file `module_name` names: a Go import always names a whole
+/// package directory, not a single file, so the real dependency is every
+/// `.go` file in it — not just the lexicographically-first one.
 fn resolve_go(
     repo_root: &str,
     source_path: &str,
     module_name: &str,
     all_files: &HashMap<String, String>,
-) -> Option<String> {
+) -> Vec<String> {
     if module_name.starts_with('.') {
         let source_dir = Path::new(source_path).parent().unwrap_or(Path::new(""));
         let normalized = normalize_posix_path(
@@ -129,12 +371,14 @@ fn resolve_go(
             .cloned()
             .collect();
         candidates.sort();
-        return candidates.into_iter().next();
+        return candidates;
     }
 
-    let root_module = read_go_module(repo_root)?;
+    let Some(root_module) = read_go_module(repo_root) else {
+        return Vec::new();
+    };
     if !module_name.starts_with(&root_module) {
-        return None;
+        return Vec::new();
     }
     let rel_pkg = module_name[root_module.len()..].trim_start_matches('/');
     let prefix = if rel_pkg.is_empty() {
@@ -148,7 +392,196 @@ fn resolve_go(
         .cloned()
         .collect();
     candidates.sort();
-    candidates.into_iter().next()
+    candidates
+}
+
+/// Resolves a single Rust `mod`/`use` path to the file it names.
+///
+/// A `mod foo;` import (identified by `import_statement` rather than
+/// `module_name`'s shape, since a bare crate name and a bare `mod` name look
+/// identical) resolves relative to the *declaring file's* directory, per
+/// Rust's file-per-module convention — unless it carries a `path:`-prefixed
+/// `module_name` from an explicit `#[path = "..."]` attribute, which is
+/// resolved as a literal relative path instead.
+///
+/// A `use` path walks the module tree from one of three starting points:
+/// `crate::...` from `src/lib.rs`/`src/main.rs`, `super::...` from the
+/// declaring file's parent module, and `self::...` from the declaring
+/// file's own module — mirroring the same `{name}.rs` / `{name}/mod.rs`
+/// convention at each step. Anything else (`use serde::Deserialize`, a bare
+/// `use some_crate;`) isn't part of this crate's own module tree and is left
+/// unresolved so the caller treats it as an external dependency instead.
+fn resolve_rust(
+    repo_root: &str,
+    source_path: &str,
+    module_name: &str,
+    import_statement: &str,
+    all_files: &HashMap<String, String>,
+) -> Option<String> {
+    if is_rust_mod_decl(import_statement) {
+        let dir = rust_submodule_dir(source_path);
+        return match module_name.strip_prefix("path:") {
+            Some(explicit) => {
+                let normalized = normalize_posix_path(&format!("{dir}/{explicit}"));
+                all_files.contains_key(&normalized).then_some(normalized)
+            }
+            None => {
+                let candidate_rs = normalize_posix_path(&format!("{dir}/{module_name}.rs"));
+                let candidate_mod = normalize_posix_path(&format!("{dir}/{module_name}/mod.rs"));
+                if all_files.contains_key(&candidate_rs) {
+                    Some(candidate_rs)
+                } else if all_files.contains_key(&candidate_mod) {
+                    Some(candidate_mod)
+                } else {
+                    None
+                }
+            }
+        };
+    }
+
+    let segments: Vec<&str> = module_name.split("::").filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() {
+        return None;
+    }
+    let head = segments[0];
+    let rest = &segments[1..];
+    match head {
+        "crate" => {
+            let root_file = crate_root_file(repo_root, all_files)?;
+            if rest.is_empty() {
+                Some(root_file)
+            } else {
+                walk_rust_modules(&rust_submodule_dir(&root_file), rest, all_files)
+            }
+        }
+        "self" => {
+            if rest.is_empty() {
+                Some(source_path.to_string())
+            } else {
+                walk_rust_modules(&rust_submodule_dir(source_path), rest, all_files)
+            }
+        }
+        "super" => {
+            if rest.is_empty() {
+                None
+            } else {
+                walk_rust_modules(&rust_super_dir(source_path), rest, all_files)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether `import_statement` is a `mod foo;` declaration rather than a
+/// `use` path — the only structural cue available once both forms have
+/// already been collapsed into the same `ExtractedImport` shape.
+fn is_rust_mod_decl(import_statement: &str) -> bool {
+    let stmt = import_statement.trim_start();
+    let stmt = stmt.strip_prefix("pub").map_or(stmt, |rest| {
+        let rest = rest.trim_start();
+        match rest.strip_prefix('(') {
+            Some(after_paren) => after_paren
+                .splitn(2, ')')
+                .nth(1)
+                .unwrap_or(rest)
+                .trim_start(),
+            None => rest,
+        }
+    });
+    stmt.starts_with("mod ")
+}
+
+/// Whether `import_statement` is a `pub use`/`pub(...) use` re-export —
+/// these still resolve like any other `use`, but the re-exported name isn't
+/// necessarily what the importing file actually reaches for, so edges from
+/// it are down-weighted rather than treated as a precise `IMPORTS`.
+fn is_rust_pub_reexport(import_statement: &str) -> bool {
+    let stmt = import_statement.trim_start();
+    let Some(rest) = stmt.strip_prefix("pub") else {
+        return false;
+    };
+    let rest = rest.trim_start();
+    let rest = match rest.strip_prefix('(') {
+        Some(after_paren) => after_paren.splitn(2, ')').nth(1).unwrap_or(""),
+        None => rest,
+    };
+    rest.trim_start().starts_with("use ")
+}
+
+/// The directory a Rust file's own `mod foo;` children resolve against:
+/// for `mod.rs`/`lib.rs`/`main.rs` (files that already *are* a module's
+/// defining file) that's their own parent directory; for a plain
+/// `name.rs` leaf it's `parent/name/`, since that file's submodules live
+/// alongside it in a same-named subdirectory.
+fn rust_submodule_dir(file_path: &str) -> String {
+    let path = Path::new(file_path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let parent = path.parent().unwrap_or(Path::new(""));
+    if matches!(stem, "mod" | "lib" | "main") {
+        normalize_posix_path(&parent.to_string_lossy().replace('\\', "/"))
+    } else {
+        normalize_posix_path(&parent.join(stem).to_string_lossy().replace('\\', "/"))
+    }
+}
+
+/// The directory the *parent* module's children resolve against — one
+/// level up the module tree from [`rust_submodule_dir`], not the
+/// filesystem: for `mod.rs`/`lib.rs`/`main.rs` that's two directories up
+/// (their own submodule dir is their parent, so the parent module's is the
+/// grandparent), for a plain `name.rs` leaf it's just its parent directory.
+fn rust_super_dir(file_path: &str) -> String {
+    let path = Path::new(file_path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let parent = path.parent().unwrap_or(Path::new(""));
+    if matches!(stem, "mod" | "lib" | "main") {
+        let grandparent = parent.parent().unwrap_or(Path::new(""));
+        normalize_posix_path(&grandparent.to_string_lossy().replace('\\', "/"))
+    } else {
+        normalize_posix_path(&parent.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+/// The crate root file, `{repo_root}/src/lib.rs` or `{repo_root}/src/main.rs`
+/// — looked up in `all_files` at a fixed repo-root-relative path rather
+/// than found by scanning every entry of it.
+fn crate_root_file(repo_root: &str, all_files: &HashMap<String, String>) -> Option<String> {
+    for candidate in ["src/lib.rs", "src/main.rs"] {
+        let joined = normalize_posix_path(
+            &Path::new(repo_root)
+                .join(candidate)
+                .to_string_lossy()
+                .replace('\\', "/"),
+        );
+        if all_files.contains_key(&joined) {
+            return Some(joined);
+        }
+    }
+    None
+}
+
+/// Walks `segments` as nested Rust module directories starting from
+/// `base_dir`, returning whichever of `{path}.rs` / `{path}/mod.rs` defines
+/// the final segment.
+fn walk_rust_modules(
+    base_dir: &str,
+    segments: &[&str],
+    all_files: &HashMap<String, String>,
+) -> Option<String> {
+    let mut dir = base_dir.to_string();
+    let mut resolved = None;
+    for segment in segments {
+        let candidate_rs = normalize_posix_path(&format!("{dir}/{segment}.rs"));
+        let candidate_mod = normalize_posix_path(&format!("{dir}/{segment}/mod.rs"));
+        if all_files.contains_key(&candidate_rs) {
+            resolved = Some(candidate_rs);
+        } else if all_files.contains_key(&candidate_mod) {
+            resolved = Some(candidate_mod);
+        } else {
+            return None;
+        }
+        dir = format!("{dir}/{segment}");
+    }
+    resolved
 }
 
 fn read_go_module(repo_root: &str) -> Option<String> {
@@ -178,6 +611,148 @@ fn normalize_posix_path(path: &str) -> String {
     stack.join("/")
 }
 
+// ---------------------------------------------------------------------------
+// Language-agnostic path resolution (for building a module graph directly
+// from files on disk, rather than the in-memory `all_files` listing the
+// per-language resolvers above use)
+// ---------------------------------------------------------------------------
+
+/// Collapse `path`'s `.`/`..` `Component`s by hand — no `canonicalize`, so
+/// this works even when the target doesn't exist on disk yet and never
+/// follows symlinks. Returns `None` if a `..` would walk back past the
+/// start of `path` itself (before `base` is even considered).
+fn normalize_components(path: &Path) -> Option<PathBuf> {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !result.pop() {
+                    return None;
+                }
+            }
+            other => result.push(other.as_os_str()),
+        }
+    }
+    Some(result)
+}
+
+/// `path` with `suffix` appended to its file name (not [`Path::with_extension`],
+/// which would replace a dot already in the name, e.g. turn `a.config` into
+/// `a.ts` instead of `a.config.ts`).
+fn append_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Candidate extensions tried, in order, when a resolved TypeScript/JS
+/// specifier names no file directly — [`expand_ts_suffixes`]'s order,
+/// without its `.jsx`.
+const TS_CANDIDATE_EXTENSIONS: &[&str] = &[".ts", ".tsx", ".js"];
+
+/// Resolve a relative import `specifier` written inside `referrer` to an
+/// absolute path under `base` — the repo root. Unlike [`resolve_typescript`]
+/// and friends above, this works directly against the real filesystem
+/// rather than an in-memory `all_files` listing, for callers building a
+/// module graph straight from files already checked out on disk.
+///
+/// A specifier that doesn't start with `.`/`..` (`fmt`, `react`, a bare
+/// package name) names an external dependency, not a file in this repo, so
+/// it resolves to `None` rather than being guessed at. The joined path is
+/// normalized via [`normalize_components`] — no filesystem access — and
+/// rejected (`None`) if it walks outside `base`; only then, if the literal
+/// path has no extension, do candidate extensions and `index.*` fallbacks
+/// get tried against the real filesystem to find the file the specifier
+/// actually names.
+pub fn resolve_import_path(base: &Path, specifier: &str, referrer: &Path) -> Option<PathBuf> {
+    if !specifier.starts_with('.') {
+        return None;
+    }
+    let referrer_dir = referrer.parent().unwrap_or(Path::new(""));
+    let normalized = normalize_components(&referrer_dir.join(specifier))?;
+    if !normalized.starts_with(base) {
+        return None;
+    }
+    if normalized.extension().is_some() {
+        return Some(normalized);
+    }
+    for ext in TS_CANDIDATE_EXTENSIONS {
+        let candidate = append_suffix(&normalized, ext);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+    for ext in TS_CANDIDATE_EXTENSIONS {
+        let candidate = normalized.join(format!("index{ext}"));
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+/// Resolve every relative import in `imports` against the real filesystem
+/// under `base`, producing the file-to-file module graph consumers
+/// traverse. Imports that resolve_import_path can't place (bare
+/// specifiers, escapes, dangling relative paths) are simply dropped —
+/// there's no file on either end of that edge to record.
+pub fn build_module_graph(base: &Path, imports: &[ExtractedImport]) -> Vec<ModuleGraphEdge> {
+    imports
+        .iter()
+        .filter_map(|import| {
+            let referrer = base.join(&import.source_file_path);
+            let resolved = resolve_import_path(base, &import.module_name, &referrer)?;
+            let target = resolved.strip_prefix(base).unwrap_or(&resolved);
+            Some(ModuleGraphEdge {
+                source_file_path: import.source_file_path.clone(),
+                target_file_path: target.to_string_lossy().replace('\\', "/"),
+            })
+        })
+        .collect()
+}
+
+/// Groups `candidate_symbols` by file, and within each file by exported
+/// name, so [`resolve_imports`] can turn `from mod import foo` into an edge
+/// at a specific function/class/const instead of just the file it lives
+/// in. Built once per `resolve_imports` call (modeled on rust-analyzer's
+/// per-crate `nameres` maps) and reused for every import statement in the
+/// source file, not recomputed per imported name.
+fn group_exported_symbols(
+    candidate_symbols: &[ExtractedSymbol],
+) -> HashMap<&str, HashMap<&str, Vec<&ExtractedSymbol>>> {
+    let mut by_file: HashMap<&str, HashMap<&str, Vec<&ExtractedSymbol>>> = HashMap::new();
+    for symbol in candidate_symbols {
+        if symbol.visibility.as_deref() == Some("private") {
+            continue;
+        }
+        by_file
+            .entry(symbol.file_path.as_str())
+            .or_default()
+            .entry(symbol.name.as_str())
+            .or_default()
+            .push(symbol);
+    }
+    by_file
+}
+
+fn symbol_target_id(
+    symbol: &ExtractedSymbol,
+    symbol_id_lookup: Option<&HashMap<(String, String), i64>>,
+) -> i64 {
+    match symbol_id_lookup {
+        Some(lookup) => {
+            let key = (symbol.qualified_name.clone(), symbol.file_path.clone());
+            lookup
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| symbol_id(&symbol.qualified_name))
+        }
+        None => symbol_id(&symbol.qualified_name),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn resolve_imports(
     repo_root: &str,
     source_path: &str,
@@ -185,6 +760,8 @@ pub fn resolve_imports(
     imports: &[ExtractedImport],
     all_files: &HashMap<String, String>,
     file_id_lookup: Option<&HashMap<String, i64>>,
+    candidate_symbols: &[ExtractedSymbol],
+    symbol_id_lookup: Option<&HashMap<(String, String), i64>>,
 ) -> (Vec<ImportEdge>, Vec<ExternalDep>) {
     let mut edges = Vec::new();
     let mut external = Vec::new();
@@ -192,44 +769,1523 @@ pub fn resolve_imports(
         .and_then(|m| m.get(source_path))
         .copied()
         .unwrap_or_else(|| file_id(source_path));
+    let exported_by_file = group_exported_symbols(candidate_symbols);
 
     for import in imports {
         let module_name = &import.module_name;
-        let resolved_path = match language {
-            "python" => resolve_python(source_path, module_name, all_files),
+        let resolved_paths: Vec<String> = match language {
+            "python" => resolve_python(source_path, module_name, all_files)
+                .into_iter()
+                .collect(),
             "java" => resolve_java(module_name, all_files),
-            "typescript" => resolve_typescript(source_path, module_name, all_files),
+            "typescript" => resolve_typescript(repo_root, source_path, module_name, all_files)
+                .into_iter()
+                .collect(),
             "go" => resolve_go(repo_root, source_path, module_name, all_files),
-            _ => None,
+            "rust" => resolve_rust(
+                repo_root,
+                source_path,
+                module_name,
+                &import.import_statement,
+                all_files,
+            )
+            .into_iter()
+            .collect(),
+            _ => Vec::new(),
         };
 
-        match resolved_path {
-            None => {
-                external.push(ExternalDep {
-                    file_path: source_path.to_string(),
-                    import_statement: import.import_statement.clone(),
-                    module_name: module_name.clone(),
-                    line_number: Some(import.line_number),
-                });
+        // A `pub use`/`pub(...) use` re-export resolves the same as any
+        // other `use`, but what it hands the importing file is one hop
+        // removed from where the name is actually defined, so the edge it
+        // produces is down-weighted rather than treated as a precise import.
+        let confidence_scale =
+            if language == "rust" && is_rust_pub_reexport(&import.import_statement) {
+                0.7
+            } else {
+                1.0
+            };
+
+        if resolved_paths.is_empty() {
+            external.push(ExternalDep {
+                file_path: source_path.to_string(),
+                import_statement: import.import_statement.clone(),
+                module_name: module_name.clone(),
+                line_number: Some(import.line_number),
+            });
+            continue;
+        }
+
+        // Symbol-level resolution only makes sense against one precise
+        // target file — a wildcard/package-level import that already fanned
+        // out to several files (tagged `WILDCARD_IMPORTS` below) stays a
+        // file edge, since there's no single file to look a name up in.
+        if let [resolved] = resolved_paths.as_slice() {
+            if !import.imported_names.is_empty() {
+                if let Some(exported) = exported_by_file.get(resolved.as_str()) {
+                    push_symbol_edges(
+                        &mut edges,
+                        source_id,
+                        source_path,
+                        import,
+                        resolved,
+                        exported,
+                        file_id_lookup,
+                        symbol_id_lookup,
+                        confidence_scale,
+                    );
+                    continue;
+                }
+            }
+        }
+
+        // A wildcard/package-level import that fans out to more than one
+        // file is a coarser signal than a precise single-file import, so
+        // it's tagged and down-weighted rather than treated as equally
+        // certain `IMPORTS` edges.
+        let (relationship, confidence) = if resolved_paths.len() > 1 {
+            ("WILDCARD_IMPORTS", 0.5)
+        } else {
+            ("IMPORTS", 1.0)
+        };
+        let confidence = confidence * confidence_scale;
+        for resolved in &resolved_paths {
+            let target_id = file_id_lookup
+                .and_then(|m| m.get(resolved))
+                .copied()
+                .unwrap_or_else(|| file_id(resolved));
+            edges.push(ImportEdge {
+                source_id,
+                target_id,
+                source_type: "file".to_string(),
+                target_type: "file".to_string(),
+                relationship: relationship.to_string(),
+                file_path: source_path.to_string(),
+                line_number: import.line_number,
+                confidence,
+                version: None,
+            });
+        }
+    }
+
+    (edges, external)
+}
+
+/// Resolves every name `import` binds against `exported` (the target
+/// file's name -> symbol(s) map): a `*` star import fans out to the whole
+/// file at a reduced confidence, a name matching exactly one exported
+/// symbol becomes a precise `symbol` edge, a name matching more than one
+/// (re-exported under the same name) still resolves but at a reduced
+/// confidence, and a name matching nothing falls back to a plain file edge
+/// so the import isn't silently dropped.
+#[allow(clippy::too_many_arguments)]
+fn push_symbol_edges(
+    edges: &mut Vec<ImportEdge>,
+    source_id: i64,
+    source_path: &str,
+    import: &ExtractedImport,
+    resolved_file: &str,
+    exported: &HashMap<&str, Vec<&ExtractedSymbol>>,
+    file_id_lookup: Option<&HashMap<String, i64>>,
+    symbol_id_lookup: Option<&HashMap<(String, String), i64>>,
+    confidence_scale: f64,
+) {
+    let file_target_id = || {
+        file_id_lookup
+            .and_then(|m| m.get(resolved_file))
+            .copied()
+            .unwrap_or_else(|| file_id(resolved_file))
+    };
+
+    for name in &import.imported_names {
+        if name == "*" {
+            for symbols in exported.values() {
+                for &symbol in symbols {
+                    edges.push(ImportEdge {
+                        source_id,
+                        target_id: symbol_target_id(symbol, symbol_id_lookup),
+                        source_type: "file".to_string(),
+                        target_type: "symbol".to_string(),
+                        relationship: "IMPORTS".to_string(),
+                        file_path: source_path.to_string(),
+                        line_number: import.line_number,
+                        confidence: 0.4 * confidence_scale,
+                        version: None,
+                    });
+                }
+            }
+            continue;
+        }
+
+        match exported.get(name.as_str()) {
+            Some(matches) => {
+                let confidence = (if matches.len() == 1 { 1.0 } else { 0.6 }) * confidence_scale;
+                for &symbol in matches {
+                    edges.push(ImportEdge {
+                        source_id,
+                        target_id: symbol_target_id(symbol, symbol_id_lookup),
+                        source_type: "file".to_string(),
+                        target_type: "symbol".to_string(),
+                        relationship: "IMPORTS".to_string(),
+                        file_path: source_path.to_string(),
+                        line_number: import.line_number,
+                        confidence,
+                        version: None,
+                    });
+                }
             }
-            Some(resolved) => {
-                let target_id = file_id_lookup
-                    .and_then(|m| m.get(&resolved))
-                    .copied()
-                    .unwrap_or_else(|| file_id(&resolved));
+            None => {
                 edges.push(ImportEdge {
                     source_id,
-                    target_id,
+                    target_id: file_target_id(),
                     source_type: "file".to_string(),
                     target_type: "file".to_string(),
                     relationship: "IMPORTS".to_string(),
                     file_path: source_path.to_string(),
                     line_number: import.line_number,
-                    confidence: 1.0,
+                    confidence: confidence_scale,
+                    version: None,
                 });
             }
         }
     }
+}
 
-    (edges, external)
+// ---------------------------------------------------------------------------
+// In-memory qualified-name resolution (no filesystem access)
+// ---------------------------------------------------------------------------
+
+/// One `ExtractedImport` resolved to a concrete symbol it refers to.
+pub struct SymbolReference {
+    pub source_file_path: String,
+    pub import_line: i64,
+    pub resolved_qualified_name: String,
+}
+
+/// An import that could not be matched against any known symbol, either
+/// because its module wasn't found among the symbols given to
+/// [`resolve_symbol_references`] or none of its `imported_names` are
+/// exported there.
+pub struct UnresolvedImport {
+    pub source_file_path: String,
+    pub module_name: String,
+    pub line_number: i64,
+}
+
+/// Group `symbols` by the module they live in, keyed both by
+/// `to_module_name(file_path)` (how Python/TypeScript/Rust import paths line
+/// up with a single file) and by the file's immediate directory name (how a
+/// Go import path names a whole package directory rather than one file).
+fn group_symbols_by_module(symbols: &[ExtractedSymbol]) -> HashMap<String, Vec<&ExtractedSymbol>> {
+    let mut by_module: HashMap<String, Vec<&ExtractedSymbol>> = HashMap::new();
+    for symbol in symbols {
+        by_module
+            .entry(to_module_name(&symbol.file_path))
+            .or_default()
+            .push(symbol);
+        if let Some(dir) = Path::new(&symbol.file_path)
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|f| f.to_str())
+        {
+            by_module.entry(dir.to_string()).or_default().push(symbol);
+        }
+    }
+    by_module
+}
+
+/// Resolve `module_name` to the symbols that live there, trying an exact
+/// module match first and falling back to the last path segment -- a Go
+/// import path like `github.com/org/repo/pkg/util` is referenced in code by
+/// just `util`, the package name its directory spells.
+fn resolve_module_candidates<'a>(
+    module_name: &str,
+    by_module: &HashMap<String, Vec<&'a ExtractedSymbol>>,
+) -> Option<Vec<&'a ExtractedSymbol>> {
+    if let Some(candidates) = by_module.get(module_name) {
+        return Some(candidates.clone());
+    }
+    module_name
+        .rsplit('/')
+        .next()
+        .and_then(|pkg| by_module.get(pkg))
+        .cloned()
+}
+
+/// Link each `ExtractedImport` in `imports` to the concrete symbol(s) it
+/// refers to among `symbols`, collected once over a whole tree -- the
+/// in-memory analogue of [`resolve_imports`], which instead resolves module
+/// paths against a listing of the repository's files. A Java wildcard (`x.*`) resolves against
+/// every public symbol directly under package `x`; a Go package import
+/// binds the whole package (see [`group_symbols_by_module`]) since a bare
+/// `import "pkg"` names no symbols of its own; a TypeScript relative
+/// specifier (`./foo`, `../bar/baz`) is path-joined against the importing
+/// file and normalized through `to_module_name`. Imports that don't match
+/// any known symbol are returned separately rather than dropped, so callers
+/// can flag them as missing dependencies instead of silently losing them.
+pub fn resolve_symbol_references(
+    symbols: &[ExtractedSymbol],
+    imports: &[ExtractedImport],
+) -> (Vec<SymbolReference>, Vec<UnresolvedImport>) {
+    let by_module = group_symbols_by_module(symbols);
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+
+    for import in imports {
+        let candidates = if let Some(package) = import.module_name.strip_suffix(".*") {
+            let matches: Vec<&ExtractedSymbol> = symbols
+                .iter()
+                .filter(|s| {
+                    s.qualified_name
+                        .strip_prefix(package)
+                        .and_then(|rest| rest.strip_prefix('.'))
+                        .is_some_and(|rest| !rest.contains('.'))
+                })
+                .collect();
+            (!matches.is_empty()).then_some(matches)
+        } else if import.module_name.starts_with('.') {
+            let source_dir = Path::new(&import.source_file_path)
+                .parent()
+                .unwrap_or(Path::new(""));
+            let joined = source_dir
+                .join(&import.module_name)
+                .to_string_lossy()
+                .into_owned();
+            by_module.get(&to_module_name(&joined)).cloned()
+        } else {
+            resolve_module_candidates(&import.module_name, &by_module)
+        };
+
+        let Some(candidates) = candidates else {
+            unresolved.push(UnresolvedImport {
+                source_file_path: import.source_file_path.clone(),
+                module_name: import.module_name.clone(),
+                line_number: import.line_number,
+            });
+            continue;
+        };
+
+        let owned_wildcard;
+        let names: &[String] = if import.imported_names.is_empty() {
+            owned_wildcard = ["*".to_string()];
+            &owned_wildcard
+        } else {
+            &import.imported_names
+        };
+
+        let mut any_matched = false;
+        for name in names {
+            if name == "*" {
+                for &symbol in candidates
+                    .iter()
+                    .filter(|s| s.visibility.as_deref() != Some("private"))
+                {
+                    resolved.push(SymbolReference {
+                        source_file_path: import.source_file_path.clone(),
+                        import_line: import.line_number,
+                        resolved_qualified_name: symbol.qualified_name.clone(),
+                    });
+                    any_matched = true;
+                }
+            } else if let Some(&symbol) = candidates.iter().find(|s| &s.name == name) {
+                resolved.push(SymbolReference {
+                    source_file_path: import.source_file_path.clone(),
+                    import_line: import.line_number,
+                    resolved_qualified_name: symbol.qualified_name.clone(),
+                });
+                any_matched = true;
+            }
+        }
+
+        if !any_matched {
+            unresolved.push(UnresolvedImport {
+                source_file_path: import.source_file_path.clone(),
+                module_name: import.module_name.clone(),
+                line_number: import.line_number,
+            });
+        }
+    }
+
+    (resolved, unresolved)
+}
+
+/// The reverse index [`build_symbol_index`] produces: every file that
+/// exports a given name, answering "which module provides symbol X" and
+/// letting a tool suggest an import for an undefined identifier.
+pub struct SymbolIndex {
+    pub by_name: HashMap<String, Vec<String>>,
+    pub resolved: Vec<SymbolReference>,
+    pub unresolved: Vec<UnresolvedImport>,
+}
+
+/// Build the reverse `exported name -> file(s)` index from `exports`, then
+/// resolve each of `imports`' `imported_names` against it. Keyed by
+/// `exported_name` rather than `ExtractedSymbol::qualified_name` (unlike
+/// [`resolve_symbol_references`]) because [`ExtractedExport`] is the only
+/// record that knows about aliasing (`export { a as b }`) and re-exports
+/// (`export { x } from './m'`) — a re-exporting file is recorded as a
+/// provider of `x` too, since that's exactly what asking for it there would
+/// get a consumer.
+pub fn build_symbol_index(exports: &[ExtractedExport], imports: &[ExtractedImport]) -> SymbolIndex {
+    let mut by_name: HashMap<String, Vec<String>> = HashMap::new();
+    for export in exports {
+        by_name
+            .entry(export.exported_name.clone())
+            .or_default()
+            .push(export.source_file_path.clone());
+    }
+
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+    for import in imports {
+        let mut any_matched = false;
+        for name in &import.imported_names {
+            let Some(files) = by_name.get(name) else {
+                continue;
+            };
+            for file in files {
+                resolved.push(SymbolReference {
+                    source_file_path: import.source_file_path.clone(),
+                    import_line: import.line_number,
+                    resolved_qualified_name: format!("{file}::{name}"),
+                });
+                any_matched = true;
+            }
+        }
+        if !any_matched {
+            unresolved.push(UnresolvedImport {
+                source_file_path: import.source_file_path.clone(),
+                module_name: import.module_name.clone(),
+                line_number: import.line_number,
+            });
+        }
+    }
+
+    SymbolIndex {
+        by_name,
+        resolved,
+        unresolved,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Manifest-backed classification of external dependencies
+// ---------------------------------------------------------------------------
+
+fn package_id(name: &str) -> i64 {
+    (crc32fast::hash(format!("pkg::{name}").as_bytes()) & 0x7FFFFFFF) as i64
+}
+
+/// The package an import belongs to, by language convention: `numpy.linalg`
+/// -> `numpy`, `@scope/pkg/sub` -> `@scope/pkg`, a Java wildcard's `.*` is
+/// dropped but the dotted package is otherwise left intact for prefix
+/// matching against a `groupId`, and a Go import path is left as-is since
+/// [`lookup_declared_version`] below already matches it by longest
+/// `require` prefix.
+fn owning_package(language: &str, module_name: &str) -> String {
+    match language {
+        "python" => module_name
+            .split('.')
+            .next()
+            .unwrap_or(module_name)
+            .to_string(),
+        "typescript" => {
+            let mut segments = module_name.splitn(3, '/');
+            let first = segments.next().unwrap_or("");
+            if first.starts_with('@') {
+                match segments.next() {
+                    Some(second) => format!("{first}/{second}"),
+                    None => first.to_string(),
+                }
+            } else {
+                first.to_string()
+            }
+        }
+        "java" => module_name.trim_end_matches(".*").to_string(),
+        "rust" => module_name
+            .split("::")
+            .next()
+            .unwrap_or(module_name)
+            .to_string(),
+        _ => module_name.to_string(),
+    }
+}
+
+/// Declared package versions for `language`, read from whatever manifest or
+/// lockfile that language's tooling uses, keyed by [`owning_package`]'s
+/// output (Go entries are keyed by the `require`d module path itself, since
+/// Go import paths are looked up by longest-prefix match instead).
+fn manifest_versions(repo_root: &str, language: &str) -> HashMap<String, String> {
+    match language {
+        "python" => parse_python_manifest(repo_root),
+        "typescript" => parse_package_json(repo_root),
+        "go" => parse_go_mod_requires(repo_root),
+        "java" => parse_java_manifest(repo_root),
+        "rust" => parse_cargo_manifest(repo_root),
+        _ => HashMap::new(),
+    }
+}
+
+static PY_REQUIREMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z0-9_.-]+)\s*(?:\[[^\]]*\])?\s*(?:[=<>!~]=?\s*([A-Za-z0-9_.*+!-]+))?")
+        .unwrap()
+});
+
+static PYPROJECT_DEP_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^([A-Za-z0-9_.-]+)\s*=\s*"?\^?([A-Za-z0-9_.*+-]*)"?"#).unwrap());
+
+fn parse_python_manifest(repo_root: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    if let Ok(content) = std::fs::read_to_string(Path::new(repo_root).join("requirements.txt")) {
+        versions.extend(parse_requirements_txt(&content));
+    }
+    if let Ok(content) = std::fs::read_to_string(Path::new(repo_root).join("pyproject.toml")) {
+        for (name, version) in parse_pyproject_dependencies(&content) {
+            versions.entry(name).or_insert(version);
+        }
+    }
+    versions
+}
+
+fn parse_requirements_txt(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() || line.starts_with('-') {
+            continue;
+        }
+        if let Some(caps) = PY_REQUIREMENT.captures(line) {
+            let name = caps[1].to_string();
+            let version = caps.get(2).map_or("unspecified", |m| m.as_str());
+            versions.insert(name, version.to_string());
+        }
+    }
+    versions
+}
+
+fn parse_pyproject_dependencies(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    let mut in_dependencies_table = false;
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            in_dependencies_table = section.ends_with(".dependencies") || section == "project";
+            continue;
+        }
+        if !in_dependencies_table {
+            continue;
+        }
+        if let Some(caps) = PYPROJECT_DEP_LINE.captures(line) {
+            let name = caps[1].to_string();
+            if name == "python" {
+                continue;
+            }
+            let version = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+            let version = if version.is_empty() {
+                "unspecified"
+            } else {
+                version
+            };
+            versions.insert(name, version.to_string());
+        }
+    }
+    versions
+}
+
+fn parse_package_json(repo_root: &str) -> HashMap<String, String> {
+    match std::fs::read_to_string(Path::new(repo_root).join("package.json")) {
+        Ok(content) => parse_package_json_content(&content),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn parse_package_json_content(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(content) else {
+        return versions;
+    };
+    for field in ["dependencies", "devDependencies", "peerDependencies"] {
+        if let Some(deps) = parsed.get(field).and_then(|v| v.as_object()) {
+            for (name, version) in deps {
+                versions
+                    .entry(name.clone())
+                    .or_insert_with(|| version.as_str().unwrap_or("unspecified").to_string());
+            }
+        }
+    }
+    versions
+}
+
+static GO_REQUIRE_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([^\s]+)\s+(v[^\s]+)").unwrap());
+
+fn parse_go_mod_requires(repo_root: &str) -> HashMap<String, String> {
+    match std::fs::read_to_string(Path::new(repo_root).join("go.mod")) {
+        Ok(content) => parse_go_mod_content(&content),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn parse_go_mod_content(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    let mut in_require_block = false;
+    for raw_line in content.lines() {
+        let line = raw_line.split("//").next().unwrap_or("").trim();
+        if line == "require (" {
+            in_require_block = true;
+            continue;
+        }
+        if in_require_block && line == ")" {
+            in_require_block = false;
+            continue;
+        }
+        let entry = if in_require_block {
+            Some(line)
+        } else {
+            line.strip_prefix("require ")
+        };
+        if let Some(entry) = entry {
+            if let Some(caps) = GO_REQUIRE_LINE.captures(entry.trim()) {
+                versions.insert(caps[1].to_string(), caps[2].to_string());
+            }
+        }
+    }
+    versions
+}
+
+static GRADLE_DEP_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"['"]([^:'"]+):([^:'"]+):([^:'"]+)['"]"#).unwrap());
+
+fn parse_java_manifest(repo_root: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    if let Ok(content) = std::fs::read_to_string(Path::new(repo_root).join("pom.xml")) {
+        versions.extend(parse_pom_xml(&content));
+    }
+    for gradle_file in ["build.gradle", "build.gradle.kts"] {
+        if let Ok(content) = std::fs::read_to_string(Path::new(repo_root).join(gradle_file)) {
+            versions.extend(parse_gradle_content(&content));
+        }
+    }
+    versions
+}
+
+fn parse_pom_xml(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    for block in content.split("<dependency>").skip(1) {
+        let block = block.split("</dependency>").next().unwrap_or("");
+        let group_id = xml_tag_text(block, "groupId");
+        let version = xml_tag_text(block, "version");
+        if let (Some(group_id), Some(version)) = (group_id, version) {
+            versions.insert(group_id, version);
+        }
+    }
+    versions
+}
+
+fn parse_gradle_content(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    for caps in GRADLE_DEP_LINE.captures_iter(content) {
+        versions.insert(caps[1].to_string(), caps[3].to_string());
+    }
+    versions
+}
+
+static CARGO_DEP_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^([A-Za-z0-9_-]+)\s*=\s*(?:"([^"]*)"|\{[^}]*?version\s*=\s*"([^"]*)"[^}]*\}|\{.*\})"#,
+    )
+    .unwrap()
+});
+
+static CARGO_VERSION_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^version\s*=\s*"([^"]*)""#).unwrap());
+
+fn parse_cargo_manifest(repo_root: &str) -> HashMap<String, String> {
+    match std::fs::read_to_string(Path::new(repo_root).join("Cargo.toml")) {
+        Ok(content) => parse_cargo_toml_dependencies(&content),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Reads `[dependencies]`/`[dev-dependencies]`/`[build-dependencies]` (both
+/// the inline-table form, `name = "1.0"` or `name = { version = "1.0" }`,
+/// and the long `[dependencies.name]` table-header form).
+fn parse_cargo_toml_dependencies(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    let mut in_deps_section = false;
+    let mut long_form_name: Option<String> = None;
+
+    for raw_line in content.lines() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let long_form = ["dependencies.", "dev-dependencies.", "build-dependencies."]
+                .iter()
+                .find_map(|prefix| section.strip_prefix(prefix));
+            match long_form {
+                Some(name) => {
+                    long_form_name = Some(name.trim_matches('"').to_string());
+                    in_deps_section = false;
+                }
+                None => {
+                    long_form_name = None;
+                    in_deps_section = matches!(
+                        section,
+                        "dependencies" | "dev-dependencies" | "build-dependencies"
+                    );
+                }
+            }
+            continue;
+        }
+
+        if let Some(name) = &long_form_name {
+            if let Some(caps) = CARGO_VERSION_LINE.captures(line) {
+                versions.insert(name.clone(), caps[1].to_string());
+            }
+            continue;
+        }
+
+        if in_deps_section {
+            if let Some(caps) = CARGO_DEP_LINE.captures(line) {
+                let name = caps[1].to_string();
+                let version = caps
+                    .get(2)
+                    .or_else(|| caps.get(3))
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_else(|| "unspecified".to_string());
+                versions.insert(name, version);
+            }
+        }
+    }
+
+    versions
+}
+
+fn xml_tag_text(block: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let start = block.find(&open)? + open.len();
+    let end = block[start..].find(&close)? + start;
+    Some(block[start..end].trim().to_string())
+}
+
+/// Looks up `package`'s declared version in `versions`, trying successively
+/// shorter `.`/`/`-separated prefixes so a Go import path like
+/// `github.com/foo/bar/pkg/sub` matches a `require github.com/foo/bar`
+/// entry, and a Java import like `com.fasterxml.jackson.databind` matches a
+/// `groupId` of `com.fasterxml.jackson`.
+fn lookup_declared_version(versions: &HashMap<String, String>, package: &str) -> Option<String> {
+    if let Some(version) = versions.get(package) {
+        return Some(version.clone());
+    }
+    let mut prefix = package;
+    while let Some(cut) = prefix.rfind(['/', '.']) {
+        prefix = &prefix[..cut];
+        if let Some(version) = versions.get(prefix) {
+            return Some(version.clone());
+        }
+    }
+    None
+}
+
+/// Maps every [`ExternalDep`] back to the third-party package it's imported
+/// from and emits a `DEPENDS_ON` edge to a synthetic package node carrying
+/// the version declared in the repo's manifest/lockfile for that language —
+/// `requirements.txt`/`pyproject.toml` for Python, `package.json` for
+/// TypeScript/JS, `go.mod` for Go, `pom.xml`/`build.gradle` for Java,
+/// `Cargo.toml` for Rust. A
+/// module with no matching manifest entry (stdlib, or simply undeclared)
+/// still gets an edge, just with `version: None`, so callers can tell a
+/// resolved dependency from an unknown one without recomputing the lookup.
+pub fn classify_external_deps(
+    repo_root: &str,
+    language: &str,
+    externals: &[ExternalDep],
+    file_id_lookup: Option<&HashMap<String, i64>>,
+) -> Vec<ImportEdge> {
+    let versions = manifest_versions(repo_root, language);
+    externals
+        .iter()
+        .map(|dep| {
+            let package = owning_package(language, &dep.module_name);
+            let version = lookup_declared_version(&versions, &package);
+            let source_id = file_id_lookup
+                .and_then(|m| m.get(&dep.file_path))
+                .copied()
+                .unwrap_or_else(|| file_id(&dep.file_path));
+            ImportEdge {
+                source_id,
+                target_id: package_id(&package),
+                source_type: "file".to_string(),
+                target_type: "package".to_string(),
+                relationship: "DEPENDS_ON".to_string(),
+                file_path: dep.file_path.clone(),
+                line_number: dep.line_number.unwrap_or(0),
+                confidence: 1.0,
+                version,
+            }
+        })
+        .collect()
+}
+
+/// Post-resolution pass over the full set of `ImportEdge`s (spanning every
+/// file, unlike `resolve_imports` which only ever sees one file at a time):
+/// finds strongly-connected components via Tarjan's algorithm and flags any
+/// component bigger than one node — or a single node with a self-loop — as
+/// a circular import. Returns the cyclic file-id groups and, as a side
+/// effect, rewrites `relationship` to `"CIRCULAR_IMPORT"` on every edge
+/// whose endpoints both fall in the same cyclic component, so a downstream
+/// consumer can find them with a plain `relationship = 'CIRCULAR_IMPORT'`
+/// query instead of recomputing SCCs itself.
+pub fn detect_import_cycles(edges: &mut [ImportEdge]) -> Vec<Vec<i64>> {
+    let mut adjacency: HashMap<i64, Vec<i64>> = HashMap::new();
+    let mut nodes: Vec<i64> = Vec::new();
+    let mut seen: HashSet<i64> = HashSet::new();
+    for edge in edges.iter() {
+        adjacency
+            .entry(edge.source_id)
+            .or_default()
+            .push(edge.target_id);
+        for &id in &[edge.source_id, edge.target_id] {
+            if seen.insert(id) {
+                nodes.push(id);
+            }
+        }
+    }
+
+    let mut counter = 0i64;
+    let mut index: HashMap<i64, i64> = HashMap::new();
+    let mut lowlink: HashMap<i64, i64> = HashMap::new();
+    let mut on_stack: HashSet<i64> = HashSet::new();
+    let mut stack: Vec<i64> = Vec::new();
+    let mut components: Vec<Vec<i64>> = Vec::new();
+
+    for &start in &nodes {
+        if !index.contains_key(&start) {
+            strong_connect(
+                start,
+                &adjacency,
+                &mut counter,
+                &mut index,
+                &mut lowlink,
+                &mut on_stack,
+                &mut stack,
+                &mut components,
+            );
+        }
+    }
+
+    let mut node_component: HashMap<i64, usize> = HashMap::new();
+    for (i, component) in components.iter().enumerate() {
+        for &node in component {
+            node_component.insert(node, i);
+        }
+    }
+
+    let is_cyclic = |component: &[i64]| {
+        component.len() > 1
+            || component
+                .first()
+                .is_some_and(|&n| adjacency.get(&n).is_some_and(|succs| succs.contains(&n)))
+    };
+
+    for edge in edges.iter_mut() {
+        let same_cyclic_component = match (
+            node_component.get(&edge.source_id),
+            node_component.get(&edge.target_id),
+        ) {
+            (Some(&sc), Some(&tc)) if sc == tc => is_cyclic(&components[sc]),
+            _ => false,
+        };
+        if same_cyclic_component {
+            edge.relationship = "CIRCULAR_IMPORT".to_string();
+        }
+    }
+
+    components
+        .into_iter()
+        .filter(|component| is_cyclic(component))
+        .collect()
+}
+
+/// One node of Tarjan's SCC algorithm: assigns `index`/`lowlink`, pushes
+/// itself onto the (explicit, shared) `stack`, recurses into every
+/// unvisited successor propagating its `lowlink`, treats an on-stack
+/// successor's `index` as a back-edge bound, and — once `lowlink == index`
+/// — pops its finished component off `stack`.
+#[allow(clippy::too_many_arguments)]
+fn strong_connect(
+    node: i64,
+    adjacency: &HashMap<i64, Vec<i64>>,
+    counter: &mut i64,
+    index: &mut HashMap<i64, i64>,
+    lowlink: &mut HashMap<i64, i64>,
+    on_stack: &mut HashSet<i64>,
+    stack: &mut Vec<i64>,
+    components: &mut Vec<Vec<i64>>,
+) {
+    index.insert(node, *counter);
+    lowlink.insert(node, *counter);
+    *counter += 1;
+    stack.push(node);
+    on_stack.insert(node);
+
+    if let Some(successors) = adjacency.get(&node) {
+        for &successor in successors {
+            if !index.contains_key(&successor) {
+                strong_connect(
+                    successor, adjacency, counter, index, lowlink, on_stack, stack, components,
+                );
+                let candidate = lowlink[&successor];
+                let current = lowlink[&node];
+                lowlink.insert(node, current.min(candidate));
+            } else if on_stack.contains(&successor) {
+                let candidate = index[&successor];
+                let current = lowlink[&node];
+                lowlink.insert(node, current.min(candidate));
+            }
+        }
+    }
+
+    if lowlink[&node] == index[&node] {
+        let mut component = Vec::new();
+        loop {
+            let member = stack.pop().expect("node's own frame is still on the stack");
+            on_stack.remove(&member);
+            component.push(member);
+            if member == node {
+                break;
+            }
+        }
+        components.push(component);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(source_id: i64, target_id: i64) -> ImportEdge {
+        ImportEdge {
+            source_id,
+            target_id,
+            source_type: "file".to_string(),
+            target_type: "file".to_string(),
+            relationship: "IMPORTS".to_string(),
+            file_path: format!("file_{source_id}.py"),
+            line_number: 1,
+            confidence: 1.0,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn flags_no_cycles_in_a_dag() {
+        let mut edges = vec![edge(1, 2), edge(2, 3)];
+        let cycles = detect_import_cycles(&mut edges);
+        assert!(cycles.is_empty());
+        assert!(edges.iter().all(|e| e.relationship == "IMPORTS"));
+    }
+
+    #[test]
+    fn finds_a_three_node_cycle_and_marks_its_edges() {
+        let mut edges = vec![edge(1, 2), edge(2, 3), edge(3, 1), edge(3, 4)];
+        let mut cycles = detect_import_cycles(&mut edges);
+        assert_eq!(cycles.len(), 1);
+        let mut cycle = cycles.pop().unwrap();
+        cycle.sort_unstable();
+        assert_eq!(cycle, vec![1, 2, 3]);
+
+        let marked: Vec<(i64, i64)> = edges
+            .iter()
+            .filter(|e| e.relationship == "CIRCULAR_IMPORT")
+            .map(|e| (e.source_id, e.target_id))
+            .collect();
+        assert_eq!(marked.len(), 3);
+        assert!(marked.contains(&(1, 2)));
+        assert!(marked.contains(&(2, 3)));
+        assert!(marked.contains(&(3, 1)));
+        // 3 -> 4 leaves the cycle, so it's untouched.
+        assert!(edges
+            .iter()
+            .any(|e| e.source_id == 3 && e.target_id == 4 && e.relationship == "IMPORTS"));
+    }
+
+    #[test]
+    fn flags_a_self_import_as_a_single_node_cycle() {
+        let mut edges = vec![edge(1, 1)];
+        let cycles = detect_import_cycles(&mut edges);
+        assert_eq!(cycles, vec![vec![1]]);
+        assert_eq!(edges[0].relationship, "CIRCULAR_IMPORT");
+    }
+
+    #[test]
+    fn resolve_java_wildcard_returns_every_file_in_the_package() {
+        let all_files: HashMap<String, String> = [
+            ("com/acme/Foo.java".to_string(), String::new()),
+            ("com/acme/Bar.java".to_string(), String::new()),
+            ("com/acme/sub/Baz.java".to_string(), String::new()),
+        ]
+        .into_iter()
+        .collect();
+        let mut resolved = resolve_java("com.acme.*", &all_files);
+        resolved.sort();
+        assert_eq!(resolved, vec!["com/acme/Bar.java", "com/acme/Foo.java"]);
+    }
+
+    #[test]
+    fn resolve_rust_mod_decl_relative_to_declaring_file() {
+        let all_files: HashMap<String, String> = [
+            ("src/handlers/mod.rs".to_string(), String::new()),
+            ("src/handlers/auth.rs".to_string(), String::new()),
+        ]
+        .into_iter()
+        .collect();
+        let resolved = resolve_rust("", "src/handlers/mod.rs", "auth", "mod auth;", &all_files);
+        assert_eq!(resolved, Some("src/handlers/auth.rs".to_string()));
+    }
+
+    #[test]
+    fn resolve_rust_mod_decl_honors_path_attribute() {
+        let all_files: HashMap<String, String> =
+            [("src/imp/windows.rs".to_string(), String::new())]
+                .into_iter()
+                .collect();
+        let resolved = resolve_rust(
+            "",
+            "src/lib.rs",
+            "path:imp/windows.rs",
+            "mod platform;",
+            &all_files,
+        );
+        assert_eq!(resolved, Some("src/imp/windows.rs".to_string()));
+    }
+
+    #[test]
+    fn resolve_rust_crate_path_walks_from_the_crate_root() {
+        let all_files: HashMap<String, String> = [
+            ("src/lib.rs".to_string(), String::new()),
+            ("src/config/mod.rs".to_string(), String::new()),
+            ("src/config/settings.rs".to_string(), String::new()),
+        ]
+        .into_iter()
+        .collect();
+        let resolved = resolve_rust(
+            "",
+            "src/other.rs",
+            "crate::config::settings",
+            "use crate::config::settings::Settings;",
+            &all_files,
+        );
+        assert_eq!(resolved, Some("src/config/settings.rs".to_string()));
+    }
+
+    #[test]
+    fn resolve_rust_super_path_reaches_a_sibling_module() {
+        let all_files: HashMap<String, String> = [
+            ("src/handlers/auth.rs".to_string(), String::new()),
+            ("src/handlers/routes.rs".to_string(), String::new()),
+        ]
+        .into_iter()
+        .collect();
+        let resolved = resolve_rust(
+            "",
+            "src/handlers/auth.rs",
+            "super::routes",
+            "use super::routes::Router;",
+            &all_files,
+        );
+        assert_eq!(resolved, Some("src/handlers/routes.rs".to_string()));
+    }
+
+    #[test]
+    fn resolve_rust_external_crate_is_unresolved() {
+        let all_files: HashMap<String, String> = [("src/lib.rs".to_string(), String::new())]
+            .into_iter()
+            .collect();
+        let resolved = resolve_rust(
+            "",
+            "src/lib.rs",
+            "serde",
+            "use serde::Deserialize;",
+            &all_files,
+        );
+        assert_eq!(resolved, None);
+    }
+
+    #[test]
+    fn resolve_imports_discounts_rust_pub_use_reexports() {
+        let all_files: HashMap<String, String> = [
+            ("src/lib.rs".to_string(), String::new()),
+            ("src/inner.rs".to_string(), String::new()),
+        ]
+        .into_iter()
+        .collect();
+        let imports = vec![ExtractedImport {
+            source_file_path: "src/lib.rs".to_string(),
+            module_name: "self::inner".to_string(),
+            import_statement: "pub use self::inner::Thing;".to_string(),
+            imported_names: vec!["Thing".to_string()],
+            line_number: 1,
+        }];
+        let (edges, _) = resolve_imports(
+            "",
+            "src/lib.rs",
+            "rust",
+            &imports,
+            &all_files,
+            None,
+            &[],
+            None,
+        );
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].target_type, "file");
+        assert_eq!(edges[0].confidence, 0.7);
+    }
+
+    #[test]
+    fn parse_cargo_toml_dependencies_reads_inline_and_table_forms() {
+        let versions = parse_cargo_toml_dependencies(
+            "[package]\nname = \"demo\"\n\n[dependencies]\nserde = \"1.0\"\ntokio = { version = \"1.28\", features = [\"full\"] }\n\n[dependencies.axum]\nversion = \"0.7\"\n",
+        );
+        assert_eq!(versions.get("serde"), Some(&"1.0".to_string()));
+        assert_eq!(versions.get("tokio"), Some(&"1.28".to_string()));
+        assert_eq!(versions.get("axum"), Some(&"0.7".to_string()));
+    }
+
+    #[test]
+    fn resolve_imports_tags_wildcard_fanout_distinctly_from_precise_imports() {
+        let all_files: HashMap<String, String> = [
+            ("com/acme/Foo.java".to_string(), String::new()),
+            ("com/acme/Bar.java".to_string(), String::new()),
+        ]
+        .into_iter()
+        .collect();
+        let imports = vec![ExtractedImport {
+            source_file_path: "App.java".to_string(),
+            module_name: "com.acme.*".to_string(),
+            import_statement: "import com.acme.*;".to_string(),
+            imported_names: Vec::new(),
+            line_number: 1,
+        }];
+        let (edges, external) = resolve_imports(
+            "",
+            "App.java",
+            "java",
+            &imports,
+            &all_files,
+            None,
+            &[],
+            None,
+        );
+        assert!(external.is_empty());
+        assert_eq!(edges.len(), 2);
+        assert!(edges
+            .iter()
+            .all(|e| e.relationship == "WILDCARD_IMPORTS" && e.confidence == 0.5));
+    }
+
+    fn symbol(name: &str, file_path: &str) -> ExtractedSymbol {
+        ExtractedSymbol {
+            name: name.to_string(),
+            qualified_name: format!("{file_path}::{name}"),
+            kind: "function".to_string(),
+            file_path: file_path.to_string(),
+            start_line: 1,
+            end_line: 2,
+            signature: None,
+            return_type: None,
+            visibility: Some("public".to_string()),
+            is_async: false,
+            is_static: false,
+            docstring: None,
+            parameters: Vec::new(),
+            supertypes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn resolve_imports_emits_symbol_edges_for_named_imports() {
+        let all_files: HashMap<String, String> = [("util.ts".to_string(), String::new())]
+            .into_iter()
+            .collect();
+        let candidate_symbols = vec![symbol("helper", "util.ts"), symbol("other", "util.ts")];
+        let imports = vec![ExtractedImport {
+            source_file_path: "app.ts".to_string(),
+            module_name: "./util".to_string(),
+            import_statement: "import { helper } from './util';".to_string(),
+            imported_names: vec!["helper".to_string()],
+            line_number: 1,
+        }];
+        let (edges, external) = resolve_imports(
+            "",
+            "app.ts",
+            "typescript",
+            &imports,
+            &all_files,
+            None,
+            &candidate_symbols,
+            None,
+        );
+        assert!(external.is_empty());
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].target_type, "symbol");
+        assert_eq!(edges[0].confidence, 1.0);
+        assert_eq!(edges[0].target_id, symbol_id("util.ts::helper"));
+    }
+
+    #[test]
+    fn resolve_imports_falls_back_to_a_file_edge_for_an_unmatched_name() {
+        let all_files: HashMap<String, String> = [("util.ts".to_string(), String::new())]
+            .into_iter()
+            .collect();
+        let candidate_symbols = vec![symbol("helper", "util.ts")];
+        let imports = vec![ExtractedImport {
+            source_file_path: "app.ts".to_string(),
+            module_name: "./util".to_string(),
+            import_statement: "import { missing } from './util';".to_string(),
+            imported_names: vec!["missing".to_string()],
+            line_number: 1,
+        }];
+        let (edges, _) = resolve_imports(
+            "",
+            "app.ts",
+            "typescript",
+            &imports,
+            &all_files,
+            None,
+            &candidate_symbols,
+            None,
+        );
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].target_type, "file");
+        assert_eq!(edges[0].relationship, "IMPORTS");
+    }
+
+    #[test]
+    fn resolve_imports_fans_a_star_import_out_at_reduced_confidence() {
+        let all_files: HashMap<String, String> = [("util.ts".to_string(), String::new())]
+            .into_iter()
+            .collect();
+        let candidate_symbols = vec![symbol("a", "util.ts"), symbol("b", "util.ts")];
+        let imports = vec![ExtractedImport {
+            source_file_path: "app.ts".to_string(),
+            module_name: "./util".to_string(),
+            import_statement: "import * as util from './util';".to_string(),
+            imported_names: vec!["*".to_string()],
+            line_number: 1,
+        }];
+        let (edges, _) = resolve_imports(
+            "",
+            "app.ts",
+            "typescript",
+            &imports,
+            &all_files,
+            None,
+            &candidate_symbols,
+            None,
+        );
+        assert_eq!(edges.len(), 2);
+        assert!(edges
+            .iter()
+            .all(|e| e.target_type == "symbol" && e.confidence == 0.4));
+    }
+
+    #[test]
+    fn owning_package_follows_each_language_convention() {
+        assert_eq!(owning_package("python", "numpy.linalg"), "numpy");
+        assert_eq!(owning_package("typescript", "@scope/pkg/sub"), "@scope/pkg");
+        assert_eq!(owning_package("typescript", "lodash/debounce"), "lodash");
+        assert_eq!(
+            owning_package("java", "com.fasterxml.jackson.*"),
+            "com.fasterxml.jackson"
+        );
+        assert_eq!(
+            owning_package("go", "github.com/foo/bar/pkg/sub"),
+            "github.com/foo/bar/pkg/sub"
+        );
+    }
+
+    #[test]
+    fn parses_requirements_txt_with_comments_and_extras() {
+        let versions = parse_requirements_txt(
+            "numpy==1.24.0\nrequests[security]>=2.0  # pinned\n-r other.txt\n",
+        );
+        assert_eq!(versions.get("numpy"), Some(&"1.24.0".to_string()));
+        assert_eq!(versions.get("requests"), Some(&"2.0".to_string()));
+    }
+
+    #[test]
+    fn parses_package_json_dependency_fields() {
+        let versions = parse_package_json_content(
+            r#"{"dependencies": {"react": "^18.2.0"}, "devDependencies": {"@scope/pkg": "1.0.0"}}"#,
+        );
+        assert_eq!(versions.get("react"), Some(&"^18.2.0".to_string()));
+        assert_eq!(versions.get("@scope/pkg"), Some(&"1.0.0".to_string()));
+    }
+
+    #[test]
+    fn parses_go_mod_require_block_and_single_line() {
+        let versions = parse_go_mod_content(
+            "module example.com/app\n\nrequire github.com/single/dep v1.2.3\n\nrequire (\n\tgithub.com/foo/bar v0.9.0\n\tgithub.com/baz/qux v2.0.0 // indirect\n)\n",
+        );
+        assert_eq!(
+            versions.get("github.com/single/dep"),
+            Some(&"v1.2.3".to_string())
+        );
+        assert_eq!(
+            versions.get("github.com/foo/bar"),
+            Some(&"v0.9.0".to_string())
+        );
+        assert_eq!(
+            versions.get("github.com/baz/qux"),
+            Some(&"v2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn lookup_declared_version_matches_longest_prefix() {
+        let mut versions = HashMap::new();
+        versions.insert("github.com/foo/bar".to_string(), "v1.2.3".to_string());
+        assert_eq!(
+            lookup_declared_version(&versions, "github.com/foo/bar/pkg/sub"),
+            Some("v1.2.3".to_string())
+        );
+        assert_eq!(lookup_declared_version(&versions, "github.com/other"), None);
+    }
+
+    #[test]
+    fn classify_external_deps_marks_unknown_modules_distinctly() {
+        let externals = vec![
+            ExternalDep {
+                file_path: "app.py".to_string(),
+                import_statement: "import numpy".to_string(),
+                module_name: "numpy".to_string(),
+                line_number: Some(1),
+            },
+            ExternalDep {
+                file_path: "app.py".to_string(),
+                import_statement: "import totally_unknown_pkg".to_string(),
+                module_name: "totally_unknown_pkg".to_string(),
+                line_number: Some(2),
+            },
+        ];
+        // No manifest on disk for this made-up repo_root, so only the
+        // classification structure (not the version lookup) is under test
+        // here; manifest parsing itself is covered above.
+        let edges = classify_external_deps("/nonexistent-repo-root", "python", &externals, None);
+        assert_eq!(edges.len(), 2);
+        assert!(edges.iter().all(|e| e.relationship == "DEPENDS_ON"));
+        assert!(edges.iter().all(|e| e.target_type == "package"));
+        assert!(edges.iter().all(|e| e.version.is_none()));
+    }
+
+    fn java_symbol(name: &str, qualified_name: &str, file_path: &str) -> ExtractedSymbol {
+        ExtractedSymbol {
+            name: name.to_string(),
+            qualified_name: qualified_name.to_string(),
+            kind: "class".to_string(),
+            file_path: file_path.to_string(),
+            start_line: 1,
+            end_line: 2,
+            signature: None,
+            return_type: None,
+            visibility: Some("public".to_string()),
+            is_async: false,
+            is_static: false,
+            docstring: None,
+            parameters: Vec::new(),
+            supertypes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn resolve_symbol_references_expands_java_wildcard_to_every_package_member() {
+        let symbols = vec![
+            java_symbol("Foo", "com.acme.Foo", "com/acme/Foo.java"),
+            java_symbol("Bar", "com.acme.Bar", "com/acme/Bar.java"),
+            java_symbol("Baz", "com.acme.sub.Baz", "com/acme/sub/Baz.java"),
+        ];
+        let imports = vec![ExtractedImport {
+            source_file_path: "com/acme/App.java".to_string(),
+            import_statement: "import com.acme.*;".to_string(),
+            module_name: "com.acme.*".to_string(),
+            imported_names: vec!["*".to_string()],
+            line_number: 3,
+        }];
+        let (resolved, unresolved) = resolve_symbol_references(&symbols, &imports);
+        assert!(unresolved.is_empty());
+        let mut names: Vec<&str> = resolved
+            .iter()
+            .map(|r| r.resolved_qualified_name.as_str())
+            .collect();
+        names.sort_unstable();
+        // `com.acme.sub.Baz` lives one package deeper, so the wildcard on
+        // `com.acme.*` doesn't reach it.
+        assert_eq!(names, vec!["com.acme.Bar", "com.acme.Foo"]);
+    }
+
+    #[test]
+    fn resolve_symbol_references_binds_go_package_import_by_directory_name() {
+        let symbols = vec![java_symbol("Helper", "util::Helper", "pkg/util/helper.go")];
+        let imports = vec![ExtractedImport {
+            source_file_path: "main.go".to_string(),
+            import_statement: "import \"example.com/app/pkg/util\"".to_string(),
+            module_name: "example.com/app/pkg/util".to_string(),
+            imported_names: Vec::new(),
+            line_number: 5,
+        }];
+        let (resolved, unresolved) = resolve_symbol_references(&symbols, &imports);
+        assert!(unresolved.is_empty());
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].resolved_qualified_name, "util::Helper");
+    }
+
+    #[test]
+    fn resolve_symbol_references_resolves_typescript_relative_specifier() {
+        let symbols = vec![symbol("helper", "src/util.ts")];
+        let imports = vec![ExtractedImport {
+            source_file_path: "src/app.ts".to_string(),
+            import_statement: "import { helper } from './util';".to_string(),
+            module_name: "./util".to_string(),
+            imported_names: vec!["helper".to_string()],
+            line_number: 1,
+        }];
+        let (resolved, unresolved) = resolve_symbol_references(&symbols, &imports);
+        assert!(unresolved.is_empty());
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].resolved_qualified_name, "src/util.ts::helper");
+    }
+
+    #[test]
+    fn resolve_symbol_references_reports_unmatched_imports_separately() {
+        let symbols = vec![symbol("helper", "src/util.ts")];
+        let imports = vec![ExtractedImport {
+            source_file_path: "src/app.ts".to_string(),
+            import_statement: "import { missing } from './util';".to_string(),
+            module_name: "./util".to_string(),
+            imported_names: vec!["missing".to_string()],
+            line_number: 1,
+        }];
+        let (resolved, unresolved) = resolve_symbol_references(&symbols, &imports);
+        assert!(resolved.is_empty());
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].module_name, "./util");
+    }
+
+    #[test]
+    fn resolve_import_path_resolves_parent_relative_specifier() {
+        let base = Path::new("/repo");
+        let referrer = Path::new("/repo/src/nested/file.ts");
+        let resolved = resolve_import_path(base, "../a.ts", referrer);
+        assert_eq!(resolved, Some(PathBuf::from("/repo/src/a.ts")));
+    }
+
+    #[test]
+    fn resolve_import_path_rejects_a_path_that_escapes_base() {
+        let base = Path::new("/repo/sub");
+        let referrer = Path::new("/repo/sub/src/file.ts");
+        let resolved = resolve_import_path(base, "../../../outside/x.ts", referrer);
+        assert_eq!(resolved, None);
+    }
+
+    #[test]
+    fn resolve_import_path_passes_bare_specifiers_through_as_unresolved() {
+        let base = Path::new("/repo");
+        let referrer = Path::new("/repo/src/app.ts");
+        assert_eq!(resolve_import_path(base, "react", referrer), None);
+        assert_eq!(resolve_import_path(base, "fmt", referrer), None);
+    }
+
+    #[test]
+    fn build_module_graph_links_source_files_to_resolved_relative_imports() {
+        let imports = vec![
+            ExtractedImport {
+                source_file_path: "src/app.ts".to_string(),
+                import_statement: "import { helper } from './util.ts';".to_string(),
+                module_name: "./util.ts".to_string(),
+                imported_names: vec!["helper".to_string()],
+                line_number: 1,
+            },
+            ExtractedImport {
+                source_file_path: "src/app.ts".to_string(),
+                import_statement: "import React from 'react';".to_string(),
+                module_name: "react".to_string(),
+                imported_names: vec![],
+                line_number: 2,
+            },
+        ];
+        let edges = build_module_graph(Path::new("/repo"), &imports);
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].source_file_path, "src/app.ts");
+        assert_eq!(edges[0].target_file_path, "src/util.ts");
+    }
+
+    #[test]
+    fn build_symbol_index_resolves_named_import_to_exporting_file() {
+        let exports = vec![ExtractedExport {
+            source_file_path: "src/util.ts".to_string(),
+            exported_name: "helper".to_string(),
+            local_name: "helper".to_string(),
+            re_export_module: None,
+            line_number: 1,
+        }];
+        let imports = vec![ExtractedImport {
+            source_file_path: "src/app.ts".to_string(),
+            import_statement: "import { helper } from './util';".to_string(),
+            module_name: "./util".to_string(),
+            imported_names: vec!["helper".to_string()],
+            line_number: 1,
+        }];
+        let index = build_symbol_index(&exports, &imports);
+        assert_eq!(
+            index.by_name.get("helper"),
+            Some(&vec!["src/util.ts".to_string()])
+        );
+        assert!(index.unresolved.is_empty());
+        assert_eq!(index.resolved.len(), 1);
+        assert_eq!(
+            index.resolved[0].resolved_qualified_name,
+            "src/util.ts::helper"
+        );
+    }
+
+    #[test]
+    fn build_symbol_index_records_a_re_exporting_file_as_a_provider() {
+        let exports = vec![ExtractedExport {
+            source_file_path: "src/index.ts".to_string(),
+            exported_name: "helper".to_string(),
+            local_name: "helper".to_string(),
+            re_export_module: Some("./util".to_string()),
+            line_number: 1,
+        }];
+        let imports = vec![ExtractedImport {
+            source_file_path: "src/app.ts".to_string(),
+            import_statement: "import { helper } from './index';".to_string(),
+            module_name: "./index".to_string(),
+            imported_names: vec!["helper".to_string()],
+            line_number: 1,
+        }];
+        let index = build_symbol_index(&exports, &imports);
+        assert_eq!(
+            index.by_name.get("helper"),
+            Some(&vec!["src/index.ts".to_string()])
+        );
+        assert!(index.unresolved.is_empty());
+    }
+
+    #[test]
+    fn build_symbol_index_reports_an_import_with_no_matching_export_as_unresolved() {
+        let exports: Vec<ExtractedExport> = Vec::new();
+        let imports = vec![ExtractedImport {
+            source_file_path: "src/app.ts".to_string(),
+            import_statement: "import { missing } from './util';".to_string(),
+            module_name: "./util".to_string(),
+            imported_names: vec!["missing".to_string()],
+            line_number: 1,
+        }];
+        let index = build_symbol_index(&exports, &imports);
+        assert!(index.resolved.is_empty());
+        assert_eq!(index.unresolved.len(), 1);
+        assert_eq!(index.unresolved[0].module_name, "./util");
+    }
 }
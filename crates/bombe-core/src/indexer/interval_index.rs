@@ -0,0 +1,372 @@
+//! Per-file order-statistics interval index over `(start_line, end_line,
+//! symbol_id)` tuples, answering "which symbol encloses line N" and "which
+//! symbols overlap lines A..B" in `O(log n)` without a `symbols` table scan,
+//! and supporting cheap suffix shifts so incremental re-indexing can apply a
+//! line delta after an edit without re-extracting the whole file's symbols.
+//!
+//! An AVL tree keyed by `start_line` (ties broken by `symbol_id` for a
+//! total order), augmented per node with `subtree_max_end`: the largest
+//! `end_line` anywhere in that node's subtree. Since every node satisfies
+//! `start_line <= end_line`, `subtree_max_end < x` at a node proves *no*
+//! node in its subtree has `start_line >= x` either — the same bound prunes
+//! both [`IntervalIndex::symbols_overlapping`] and [`IntervalIndex::apply_shift`].
+//!
+//! Ranges are inclusive on both ends, matching `symbols.start_line`/
+//! `end_line` (see `indexer::callgraph`'s `start_line <= line_number &&
+//! line_number <= end_line` containment check).
+
+use crate::errors::{BombeError, BombeResult};
+
+/// One `(start_line, end_line, symbol_id)` tuple as returned by a range
+/// query, ordered by ascending `start_line`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntervalEntry {
+    pub start_line: i64,
+    pub end_line: i64,
+    pub symbol_id: i64,
+}
+
+struct Node {
+    start_line: i64,
+    end_line: i64,
+    symbol_id: i64,
+    subtree_max_end: i64,
+    height: i32,
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Node {
+    fn new(start_line: i64, end_line: i64, symbol_id: i64) -> Self {
+        Node {
+            start_line,
+            end_line,
+            symbol_id,
+            subtree_max_end: end_line,
+            height: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn height(node: &Option<Box<Node>>) -> i32 {
+        node.as_ref().map_or(0, |n| n.height)
+    }
+
+    fn subtree_max_end(node: &Option<Box<Node>>) -> i64 {
+        node.as_ref().map_or(i64::MIN, |n| n.subtree_max_end)
+    }
+
+    /// Recomputes `height`/`subtree_max_end` from `left`/`right`/`self`;
+    /// every structural change (insert, rotation) calls this on its way
+    /// back up so the augmentation never goes stale.
+    fn recompute(&mut self) {
+        self.height = 1 + Self::height(&self.left).max(Self::height(&self.right));
+        self.subtree_max_end = self
+            .end_line
+            .max(Self::subtree_max_end(&self.left))
+            .max(Self::subtree_max_end(&self.right));
+    }
+
+    fn balance_factor(&self) -> i32 {
+        Self::height(&self.left) - Self::height(&self.right)
+    }
+
+    fn rotate_left(mut self: Box<Self>) -> Box<Self> {
+        let mut new_root = self.right.take().expect("rotate_left requires a right child");
+        self.right = new_root.left.take();
+        self.recompute();
+        new_root.left = Some(self);
+        new_root.recompute();
+        new_root
+    }
+
+    fn rotate_right(mut self: Box<Self>) -> Box<Self> {
+        let mut new_root = self.left.take().expect("rotate_right requires a left child");
+        self.left = new_root.right.take();
+        self.recompute();
+        new_root.right = Some(self);
+        new_root.recompute();
+        new_root
+    }
+
+    fn rebalance(mut self: Box<Self>) -> Box<Self> {
+        self.recompute();
+        let balance = self.balance_factor();
+        if balance > 1 {
+            if self.left.as_ref().is_some_and(|n| n.balance_factor() < 0) {
+                self.left = Some(self.left.take().unwrap().rotate_left());
+            }
+            return self.rotate_right();
+        }
+        if balance < -1 {
+            if self.right.as_ref().is_some_and(|n| n.balance_factor() > 0) {
+                self.right = Some(self.right.take().unwrap().rotate_right());
+            }
+            return self.rotate_left();
+        }
+        self
+    }
+
+    fn insert(self: Box<Self>, start_line: i64, end_line: i64, symbol_id: i64) -> Box<Self> {
+        let mut node = self;
+        let key = (start_line, symbol_id);
+        if key < (node.start_line, node.symbol_id) {
+            node.left = Some(match node.left.take() {
+                Some(left) => left.insert(start_line, end_line, symbol_id),
+                None => Box::new(Node::new(start_line, end_line, symbol_id)),
+            });
+        } else {
+            node.right = Some(match node.right.take() {
+                Some(right) => right.insert(start_line, end_line, symbol_id),
+                None => Box::new(Node::new(start_line, end_line, symbol_id)),
+            });
+        }
+        node.rebalance()
+    }
+
+    /// Appends every entry overlapping `[query_start, query_end]` to `out`,
+    /// in ascending `start_line` order. Prunes a subtree via
+    /// `subtree_max_end < query_start` (nothing in it can reach far enough
+    /// right to overlap), and stops descending right once `start_line >
+    /// query_end` (every entry further right starts later still).
+    fn collect_overlapping(&self, query_start: i64, query_end: i64, out: &mut Vec<IntervalEntry>) {
+        if self.subtree_max_end < query_start {
+            return;
+        }
+        if let Some(left) = &self.left {
+            left.collect_overlapping(query_start, query_end, out);
+        }
+        if self.start_line <= query_end && self.end_line >= query_start {
+            out.push(IntervalEntry {
+                start_line: self.start_line,
+                end_line: self.end_line,
+                symbol_id: self.symbol_id,
+            });
+        }
+        if self.start_line > query_end {
+            return;
+        }
+        if let Some(right) = &self.right {
+            right.collect_overlapping(query_start, query_end, out);
+        }
+    }
+
+    /// Validates that shifting every entry touched by `cutoff`/`delta`
+    /// would leave `start_line <= end_line`, without mutating anything.
+    /// Pruned the same way as `collect_overlapping`: `subtree_max_end <
+    /// cutoff` means every entry in this subtree has both endpoints below
+    /// `cutoff`, so the shift is a no-op for it.
+    fn validate_shift(&self, cutoff: i64, delta: i64) -> BombeResult<()> {
+        if self.subtree_max_end < cutoff {
+            return Ok(());
+        }
+        if let Some(left) = &self.left {
+            left.validate_shift(cutoff, delta)?;
+        }
+        let new_start = if self.start_line >= cutoff {
+            self.start_line + delta
+        } else {
+            self.start_line
+        };
+        let new_end = if self.end_line >= cutoff {
+            self.end_line + delta
+        } else {
+            self.end_line
+        };
+        if new_start > new_end {
+            return Err(BombeError::Index(format!(
+                "shift at line {cutoff} by {delta} would invert symbol {} ({new_start}..{new_end})",
+                self.symbol_id
+            )));
+        }
+        if let Some(right) = &self.right {
+            right.validate_shift(cutoff, delta)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the shift already validated by [`Node::validate_shift`], then
+    /// recomputes `subtree_max_end` bottom-up. Never returns an error —
+    /// callers must validate first.
+    fn apply_shift(&mut self, cutoff: i64, delta: i64) {
+        if self.subtree_max_end < cutoff {
+            return;
+        }
+        if let Some(left) = &mut self.left {
+            left.apply_shift(cutoff, delta);
+        }
+        if self.start_line >= cutoff {
+            self.start_line += delta;
+        }
+        if self.end_line >= cutoff {
+            self.end_line += delta;
+        }
+        if let Some(right) = &mut self.right {
+            right.apply_shift(cutoff, delta);
+        }
+        self.recompute();
+    }
+
+    fn len(&self) -> usize {
+        1 + self.left.as_ref().map_or(0, |n| n.len()) + self.right.as_ref().map_or(0, |n| n.len())
+    }
+}
+
+/// An order-statistics interval index for one file's symbols. See the
+/// module docs for the augmentation and pruning strategy.
+#[derive(Default)]
+pub struct IntervalIndex {
+    root: Option<Box<Node>>,
+}
+
+impl IntervalIndex {
+    pub fn new() -> Self {
+        IntervalIndex { root: None }
+    }
+
+    /// Inserts `(start_line, end_line, symbol_id)`. Rejects an inverted
+    /// range outright — the invariant a later [`Self::apply_shift`] must
+    /// also preserve.
+    pub fn insert(&mut self, start_line: i64, end_line: i64, symbol_id: i64) -> BombeResult<()> {
+        if start_line > end_line {
+            return Err(BombeError::Index(format!(
+                "symbol {symbol_id} has start_line {start_line} > end_line {end_line}"
+            )));
+        }
+        self.root = Some(match self.root.take() {
+            Some(root) => root.insert(start_line, end_line, symbol_id),
+            None => Box::new(Node::new(start_line, end_line, symbol_id)),
+        });
+        Ok(())
+    }
+
+    /// Every symbol whose `[start_line, end_line]` overlaps
+    /// `[query_start, query_end]` (both inclusive), ordered by ascending
+    /// `start_line`.
+    pub fn symbols_overlapping(&self, query_start: i64, query_end: i64) -> Vec<IntervalEntry> {
+        let mut out = Vec::new();
+        if let Some(root) = &self.root {
+            root.collect_overlapping(query_start, query_end, &mut out);
+        }
+        out
+    }
+
+    /// The innermost symbol enclosing `line`, i.e. the matching entry with
+    /// the greatest `start_line` — for properly nested symbols (a method
+    /// inside a class, say) that's the most specific one.
+    pub fn symbol_at_line(&self, line: i64) -> Option<i64> {
+        self.symbols_overlapping(line, line)
+            .into_iter()
+            .max_by_key(|entry| entry.start_line)
+            .map(|entry| entry.symbol_id)
+    }
+
+    /// Shifts every line reference at or after `cutoff` by `delta` — each
+    /// of `start_line`/`end_line` moves independently if it's at or past
+    /// `cutoff`, modeling "lines from here on moved by `delta`". Validates
+    /// every touched entry first and applies nothing if any would end up
+    /// with `start_line > end_line`.
+    pub fn apply_shift(&mut self, cutoff: i64, delta: i64) -> BombeResult<()> {
+        if delta == 0 {
+            return Ok(());
+        }
+        if let Some(root) = &self.root {
+            root.validate_shift(cutoff, delta)?;
+        }
+        if let Some(root) = &mut self.root {
+            root.apply_shift(cutoff, delta);
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_index() -> IntervalIndex {
+        let mut index = IntervalIndex::new();
+        // A class spanning 1..50, with two methods nested inside it.
+        index.insert(1, 50, 1).unwrap();
+        index.insert(5, 10, 2).unwrap();
+        index.insert(20, 30, 3).unwrap();
+        index
+    }
+
+    #[test]
+    fn rejects_an_inverted_range_on_insert() {
+        let mut index = IntervalIndex::new();
+        assert!(index.insert(10, 5, 1).is_err());
+    }
+
+    #[test]
+    fn finds_the_innermost_enclosing_symbol() {
+        let index = sample_index();
+        assert_eq!(index.symbol_at_line(7), Some(2));
+        assert_eq!(index.symbol_at_line(40), Some(1));
+        assert_eq!(index.symbol_at_line(100), None);
+    }
+
+    #[test]
+    fn range_query_returns_overlaps_ordered_by_start_line() {
+        let index = sample_index();
+        let overlapping = index.symbols_overlapping(8, 22);
+        let ids: Vec<i64> = overlapping.iter().map(|e| e.symbol_id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn shift_moves_entries_at_or_after_the_cutoff() {
+        let mut index = sample_index();
+        // Ten lines inserted before line 20: everything at/after 20 moves
+        // down by 10, nothing before it does.
+        index.apply_shift(20, 10).unwrap();
+        assert_eq!(index.symbol_at_line(7), Some(2));
+        // Symbol 3 (originally 20..30) moved to 30..40; symbol 2 (5..10,
+        // before the cutoff) stayed put and no longer overlaps it.
+        let shifted = index.symbols_overlapping(31, 39);
+        assert_eq!(
+            shifted.iter().map(|e| e.symbol_id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(shifted.iter().any(|e| e.symbol_id == 3
+            && e.start_line == 30
+            && e.end_line == 40));
+    }
+
+    #[test]
+    fn rejects_a_shift_that_would_invert_a_straddling_entry() {
+        let mut index = sample_index();
+        // Symbol 1 spans 1..50; shifting everything from line 10 onward
+        // back by 50 drags its end_line (50 -> 0) below its untouched
+        // start_line (1), which must be rejected rather than silently
+        // corrupting the index.
+        let err = index.apply_shift(10, -50);
+        assert!(err.is_err());
+        // And the rejected shift must not have partially applied.
+        assert_eq!(index.symbol_at_line(7), Some(2));
+    }
+
+    #[test]
+    fn many_insertions_stay_queryable_in_ascending_order() {
+        let mut index = IntervalIndex::new();
+        for i in 0..500i64 {
+            index.insert(i * 2, i * 2 + 1, i).unwrap();
+        }
+        assert_eq!(index.len(), 500);
+        let all = index.symbols_overlapping(0, 2000);
+        let starts: Vec<i64> = all.iter().map(|e| e.start_line).collect();
+        let mut sorted = starts.clone();
+        sorted.sort_unstable();
+        assert_eq!(starts, sorted);
+    }
+}
@@ -0,0 +1,527 @@
+//! Composable path matchers for repo scanning.
+//!
+//! Modeled on Mercurial's matcher composition: small, independently
+//! testable matcher primitives compose into the include/exclude decision
+//! `iter_repo_files` needs, instead of growing the ad-hoc rule list further.
+//! [`Matcher::matches`] answers "would this path be selected" for a single
+//! relative path, so [`would_index_path`] can expose the same decision to
+//! Python without running a full scan.
+
+use std::path::Path;
+
+use pyo3::prelude::*;
+use regex::Regex;
+
+use crate::errors::{BombeError, BombeResult};
+
+/// Whether a relative path (file or directory) is selected.
+pub trait Matcher {
+    fn matches(&self, rel: &str, is_dir: bool) -> bool;
+}
+
+/// Matches every path — the identity element for composition when no
+/// include patterns were given.
+pub struct AlwaysMatcher;
+
+impl Matcher for AlwaysMatcher {
+    fn matches(&self, _rel: &str, _is_dir: bool) -> bool {
+        true
+    }
+}
+
+/// Matches nothing.
+pub struct NeverMatcher;
+
+impl Matcher for NeverMatcher {
+    fn matches(&self, _rel: &str, _is_dir: bool) -> bool {
+        false
+    }
+}
+
+/// A gitignore-style glob compiled once into a `Regex`, plus whether it's
+/// anchored to the full relative path or just the basename.
+///
+/// A pattern containing a non-trailing `/` is anchored (gitignore matches it
+/// against the path relative to the ignore file); everything else is a
+/// basename pattern, matched against the file/dir name alone so e.g. `*.log`
+/// or `build` matches at any depth.
+struct CompiledGlob {
+    anchored: bool,
+    regex: Regex,
+}
+
+impl CompiledGlob {
+    fn new(pattern: &str) -> Self {
+        let anchored = pattern.contains('/');
+        let translated = translate_glob_to_regex(pattern);
+        let regex = Regex::new(&format!("^{translated}$"))
+            .unwrap_or_else(|_| Regex::new("$^").expect("literal regex always compiles"));
+        Self { anchored, regex }
+    }
+
+    fn matches(&self, rel_path: &str) -> bool {
+        let normalized = rel_path.replace('\\', "/");
+        if self.anchored {
+            self.regex.is_match(&normalized)
+        } else {
+            let basename = Path::new(&normalized)
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default();
+            self.regex.is_match(&basename)
+        }
+    }
+}
+
+/// Translates a single gitignore glob into the body of an anchored regex
+/// (the caller wraps it in `^...$`): `**/` becomes an optional "any depth of
+/// directories" prefix, a trailing `**` becomes "anything including `/`",
+/// `*`/`?` stay within a single path segment, `[...]`/`[!...]` bracket
+/// expressions pass through as regex character classes (negation spelled
+/// `[!...]` in gitignore becomes `[^...]` in regex), and every other
+/// character is escaped if it's a regex metacharacter.
+fn translate_glob_to_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let len = chars.len();
+    let mut regex = String::with_capacity(len * 2);
+    let mut i = 0;
+    while i < len {
+        if chars[i] == '*' && i + 2 < len && chars[i + 1] == '*' && chars[i + 2] == '/' {
+            regex.push_str("(?:.*/)?");
+            i += 3;
+        } else if chars[i] == '*' && i + 1 < len && chars[i + 1] == '*' {
+            regex.push_str(".*");
+            i += 2;
+        } else if chars[i] == '*' {
+            regex.push_str("[^/]*");
+            i += 1;
+        } else if chars[i] == '?' {
+            regex.push_str("[^/]");
+            i += 1;
+        } else if chars[i] == '[' {
+            match chars[i + 1..].iter().position(|&c| c == ']') {
+                Some(offset) => {
+                    let close = i + 1 + offset;
+                    regex.push('[');
+                    let mut j = i + 1;
+                    if j < close && chars[j] == '!' {
+                        regex.push('^');
+                        j += 1;
+                    }
+                    while j < close {
+                        regex.push(chars[j]);
+                        j += 1;
+                    }
+                    regex.push(']');
+                    i = close + 1;
+                }
+                None => {
+                    regex.push_str("\\[");
+                    i += 1;
+                }
+            }
+        } else {
+            let c = chars[i];
+            if "\\.+^$(){}|".contains(c) {
+                regex.push('\\');
+            }
+            regex.push(c);
+            i += 1;
+        }
+    }
+    regex
+}
+
+/// One parsed include entry: a plain glob, or a narrow-clone-style scope
+/// directive (`path:` / `rootfilesin:`) borrowed from Mercurial's narrow
+/// specs so a caller can index a subtree of a huge monorepo without paying
+/// to walk the rest of it.
+enum IncludeEntry {
+    Glob(CompiledGlob),
+    /// `path:foo/bar` — matches `foo/bar` itself and everything under it.
+    Path(String),
+    /// `rootfilesin:foo/bar` — matches only files directly inside
+    /// `foo/bar`, not its subdirectories.
+    RootFilesIn(String),
+}
+
+impl IncludeEntry {
+    fn matches(&self, rel: &str) -> bool {
+        match self {
+            IncludeEntry::Glob(g) => g.matches(rel),
+            IncludeEntry::Path(p) => rel == p || rel.starts_with(&format!("{p}/")),
+            IncludeEntry::RootFilesIn(p) => Path::new(rel)
+                .parent()
+                .map(|parent| parent.to_string_lossy() == *p)
+                .unwrap_or(p.is_empty()),
+        }
+    }
+
+    /// Whether the directory `rel_dir` could still lead to a path this
+    /// entry selects — either as an ancestor on the way down to an in-scope
+    /// path, or as (a subtree of) an in-scope path itself. A plain glob
+    /// isn't anchored to a subtree, so it can't rule any directory out.
+    fn could_contain_dir(&self, rel_dir: &str) -> bool {
+        match self {
+            IncludeEntry::Glob(_) => true,
+            IncludeEntry::Path(p) => {
+                rel_dir == p
+                    || rel_dir.starts_with(&format!("{p}/"))
+                    || p.starts_with(&format!("{rel_dir}/"))
+            }
+            IncludeEntry::RootFilesIn(p) => rel_dir == p || p.starts_with(&format!("{rel_dir}/")),
+        }
+    }
+}
+
+/// Parses one include pattern, recognizing the `path:` and `rootfilesin:`
+/// scope prefixes; anything else is a plain glob. A `word:`-shaped prefix
+/// that isn't one of the two recognized ones is rejected outright rather
+/// than silently compiled as a (very unlikely to be intended) literal glob.
+fn parse_include_entry(pattern: &str) -> BombeResult<IncludeEntry> {
+    if let Some(rest) = pattern.strip_prefix("path:") {
+        return Ok(IncludeEntry::Path(rest.trim_matches('/').to_string()));
+    }
+    if let Some(rest) = pattern.strip_prefix("rootfilesin:") {
+        return Ok(IncludeEntry::RootFilesIn(
+            rest.trim_matches('/').to_string(),
+        ));
+    }
+    if let Some(colon) = pattern.find(':') {
+        let prefix = &pattern[..colon];
+        if !prefix.is_empty()
+            && prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(BombeError::Index(format!(
+                "unknown include scope prefix '{prefix}:' in pattern '{pattern}' — expected 'path:' or 'rootfilesin:'"
+            )));
+        }
+    }
+    Ok(IncludeEntry::Glob(CompiledGlob::new(pattern)))
+}
+
+/// Matches any of a set of include entries — empty means "include
+/// everything", mirroring `iter_repo_files`'s pre-refactor default.
+pub struct IncludeMatcher {
+    entries: Vec<IncludeEntry>,
+}
+
+impl IncludeMatcher {
+    pub fn new(patterns: &[String]) -> BombeResult<Self> {
+        let entries = patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(parse_include_entry)
+            .collect::<BombeResult<Vec<_>>>()?;
+        Ok(Self { entries })
+    }
+
+    /// Whether `rel_dir` could contain a path this matcher selects —
+    /// `iter_repo_files` uses this to prune traversal under narrow scopes.
+    pub fn could_contain_dir(&self, rel_dir: &str) -> bool {
+        rel_dir.is_empty()
+            || self.entries.is_empty()
+            || self.entries.iter().any(|e| e.could_contain_dir(rel_dir))
+    }
+}
+
+impl Matcher for IncludeMatcher {
+    fn matches(&self, rel: &str, _is_dir: bool) -> bool {
+        self.entries.is_empty() || self.entries.iter().any(|e| e.matches(rel))
+    }
+}
+
+/// One gitignore-style exclude rule: `pattern` compiled to [`CompiledGlob`],
+/// `directory_only` set by a trailing `/`, `negated` set by a leading `!`.
+pub struct IgnoreRule {
+    pattern: String,
+    directory_only: bool,
+    negated: bool,
+    matcher: CompiledGlob,
+}
+
+impl IgnoreRule {
+    pub fn new(pattern: String, directory_only: bool, negated: bool) -> Self {
+        let matcher = CompiledGlob::new(&pattern);
+        Self {
+            pattern,
+            directory_only,
+            negated,
+            matcher,
+        }
+    }
+
+    /// Parses one gitignore-syntax line (no comment/blank-line handling —
+    /// the caller filters those first) into a rule: strips a leading `!`
+    /// into `negated`, a trailing `/` into `directory_only`, and a leading
+    /// `./` anchor prefix gitignore treats as redundant.
+    pub fn parse_line(line: &str) -> Self {
+        let negated = line.starts_with('!');
+        let stripped = if negated { &line[1..] } else { line };
+        let directory_only = stripped.ends_with('/');
+        let mut pattern = if directory_only {
+            stripped[..stripped.len() - 1].to_string()
+        } else {
+            stripped.to_string()
+        };
+        if pattern.starts_with("./") {
+            pattern = pattern[2..].to_string();
+        }
+        Self::new(pattern, directory_only, negated)
+    }
+}
+
+/// Excludes a path iff the *last* rule (in list order) that matches it is
+/// non-negated — mirroring gitignore's "later lines override earlier ones"
+/// precedence, including a `!pattern` re-including a path an earlier rule
+/// excluded. A negated rule can't resurrect a file whose ancestor directory
+/// was already pruned by a `dir/` rule elsewhere in the walk — `matches`
+/// only ever sees the one path it's asked about.
+pub struct ExcludeMatcher {
+    rules: Vec<IgnoreRule>,
+}
+
+impl ExcludeMatcher {
+    pub fn new(rules: Vec<IgnoreRule>) -> Self {
+        Self { rules }
+    }
+}
+
+impl Matcher for ExcludeMatcher {
+    fn matches(&self, rel: &str, is_dir: bool) -> bool {
+        let normalized = rel.replace('\\', "/");
+        let mut ignored = false;
+        for rule in &self.rules {
+            let hit = if rule.directory_only && !is_dir {
+                // A `dir/` rule reaches a file only through one of its
+                // ancestor directories.
+                normalized
+                    .match_indices('/')
+                    .any(|(i, _)| rule.matcher.matches(&normalized[..i]))
+            } else {
+                rule.matcher.matches(&normalized)
+                    || normalized.starts_with(&format!("{}/", rule.pattern))
+            };
+            if hit {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// Include-minus-exclude: a path is selected iff `include` selects it and
+/// `exclude` doesn't.
+pub struct DifferenceMatcher<I, E> {
+    include: I,
+    exclude: E,
+}
+
+impl<I: Matcher, E: Matcher> DifferenceMatcher<I, E> {
+    pub fn new(include: I, exclude: E) -> Self {
+        Self { include, exclude }
+    }
+
+    /// The exclude half alone — `iter_repo_files` uses this to prune
+    /// directories, which aren't subject to include-pattern filtering.
+    pub fn exclude(&self) -> &E {
+        &self.exclude
+    }
+
+    /// The include half alone — `iter_repo_files` uses this to prune
+    /// traversal under a narrow `path:`/`rootfilesin:` scope.
+    pub fn include(&self) -> &I {
+        &self.include
+    }
+}
+
+impl<I: Matcher, E: Matcher> Matcher for DifferenceMatcher<I, E> {
+    fn matches(&self, rel: &str, is_dir: bool) -> bool {
+        self.include.matches(rel, is_dir) && !self.exclude.matches(rel, is_dir)
+    }
+}
+
+const DEFAULT_SENSITIVE_EXCLUDE_PATTERNS: &[&str] = &[
+    ".env",
+    ".env.*",
+    "*.pem",
+    "*.key",
+    "*.p12",
+    "*secret*",
+    "*secrets*",
+    "*credential*",
+    "id_rsa",
+    "id_dsa",
+];
+
+fn load_ignore_file(path: &Path) -> Vec<IgnoreRule> {
+    load_plain_lines(path)
+        .into_iter()
+        .map(|line| IgnoreRule::parse_line(&line))
+        .collect()
+}
+
+/// Reads a newline-delimited rule file, dropping blank lines and `#`
+/// comments; shared by `.gitignore`/`.bombeignore` (fed to [`IgnoreRule`])
+/// and `.bombescope` (fed to [`IncludeMatcher`]).
+fn load_plain_lines(path: &Path) -> Vec<String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return vec![],
+    };
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Builds the `iter_repo_files` default matcher: include entries from
+/// `.bombescope` plus `include_patterns` (glob or `path:`/`rootfilesin:`
+/// scope), minus exclude rules from (default sensitive-file excludes,
+/// `.gitignore`, `.bombeignore`, then `exclude_patterns`, in that order so
+/// each later group can negate an earlier one — see `ExcludeMatcher`'s
+/// last-match-wins rule).
+pub fn build_default_matcher(
+    repo_root: &Path,
+    include_patterns: Option<&[String]>,
+    exclude_patterns: Option<&[String]>,
+) -> BombeResult<DifferenceMatcher<IncludeMatcher, ExcludeMatcher>> {
+    let mut rules: Vec<IgnoreRule> = Vec::new();
+
+    let exclude_sensitive = match std::env::var("BOMBE_EXCLUDE_SENSITIVE") {
+        Ok(val) => {
+            let v = val.trim().to_lowercase();
+            !matches!(v.as_str(), "0" | "false" | "no" | "off")
+        }
+        Err(_) => true,
+    };
+    if exclude_sensitive {
+        for pattern in DEFAULT_SENSITIVE_EXCLUDE_PATTERNS {
+            rules.push(IgnoreRule::new(pattern.to_string(), false, false));
+        }
+    }
+
+    rules.extend(load_ignore_file(&repo_root.join(".gitignore")));
+    rules.extend(load_ignore_file(&repo_root.join(".bombeignore")));
+
+    if let Some(excludes) = exclude_patterns {
+        for pattern in excludes {
+            let stripped = pattern.trim();
+            if stripped.is_empty() {
+                continue;
+            }
+            rules.push(IgnoreRule::parse_line(stripped));
+        }
+    }
+
+    let mut include_patterns_combined = load_plain_lines(&repo_root.join(".bombescope"));
+    if let Some(patterns) = include_patterns {
+        include_patterns_combined.extend(patterns.iter().cloned());
+    }
+    let include = IncludeMatcher::new(&include_patterns_combined)?;
+    Ok(DifferenceMatcher::new(include, ExcludeMatcher::new(rules)))
+}
+
+/// Tests whether `rel_path` (relative to `repo_root`) would be selected by
+/// the same include/exclude rules `iter_repo_files` applies during a scan,
+/// without walking the tree.
+#[pyfunction]
+#[pyo3(signature = (repo_root, rel_path, is_dir, include_patterns=None, exclude_patterns=None))]
+pub fn would_index_path(
+    repo_root: &str,
+    rel_path: &str,
+    is_dir: bool,
+    include_patterns: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
+) -> PyResult<bool> {
+    let matcher = build_default_matcher(
+        Path::new(repo_root),
+        include_patterns.as_deref(),
+        exclude_patterns.as_deref(),
+    )?;
+    if is_dir {
+        Ok(!matcher.exclude().matches(rel_path, true))
+    } else {
+        Ok(matcher.matches(rel_path, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exclude_from_lines(lines: &[&str]) -> ExcludeMatcher {
+        ExcludeMatcher::new(lines.iter().map(|l| IgnoreRule::parse_line(l)).collect())
+    }
+
+    #[test]
+    fn always_and_never_matchers() {
+        assert!(AlwaysMatcher.matches("anything", false));
+        assert!(!NeverMatcher.matches("anything", false));
+    }
+
+    #[test]
+    fn include_matcher_defaults_to_everything() {
+        let include = IncludeMatcher::new(&[]).unwrap();
+        assert!(include.matches("src/main.py", false));
+    }
+
+    #[test]
+    fn include_matcher_filters_by_glob() {
+        let include = IncludeMatcher::new(&["*.py".to_string()]).unwrap();
+        assert!(include.matches("main.py", false));
+        assert!(!include.matches("main.go", false));
+    }
+
+    #[test]
+    fn path_prefix_matches_subtree_and_prunes_siblings() {
+        let include = IncludeMatcher::new(&["path:services/api".to_string()]).unwrap();
+        assert!(include.matches("services/api/main.py", false));
+        assert!(include.matches("services/api", false));
+        assert!(!include.matches("services/web/main.py", false));
+
+        assert!(include.could_contain_dir("services"));
+        assert!(include.could_contain_dir("services/api"));
+        assert!(include.could_contain_dir("services/api/nested"));
+        assert!(!include.could_contain_dir("services/web"));
+    }
+
+    #[test]
+    fn rootfilesin_prefix_matches_only_direct_children() {
+        let include = IncludeMatcher::new(&["rootfilesin:services/api".to_string()]).unwrap();
+        assert!(include.matches("services/api/main.py", false));
+        assert!(!include.matches("services/api/nested/main.py", false));
+
+        assert!(include.could_contain_dir("services"));
+        assert!(include.could_contain_dir("services/api"));
+        assert!(!include.could_contain_dir("services/api/nested"));
+    }
+
+    #[test]
+    fn unknown_scope_prefix_is_rejected() {
+        let err = IncludeMatcher::new(&["bogus:services/api".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("bogus:"));
+    }
+
+    #[test]
+    fn exclude_matcher_respects_negation_order() {
+        let exclude = exclude_from_lines(&["*.log", "!keep.log"]);
+        assert!(exclude.matches("debug.log", false));
+        assert!(!exclude.matches("keep.log", false));
+    }
+
+    #[test]
+    fn difference_matcher_is_include_minus_exclude() {
+        let include = IncludeMatcher::new(&["*.py".to_string()]).unwrap();
+        let exclude = exclude_from_lines(&["vendor/"]);
+        let matcher = DifferenceMatcher::new(include, exclude);
+        assert!(matcher.matches("src/main.py", false));
+        assert!(!matcher.matches("main.go", false));
+        assert!(!matcher.matches("vendor/main.py", false));
+    }
+}
@@ -1,8 +1,20 @@
+pub mod bench;
 pub mod callgraph;
+pub mod chunking;
+pub mod dataset_export;
+pub mod embedding;
 pub mod filesystem;
+pub mod hash_cache;
 pub mod imports;
+pub mod interval_index;
+pub mod matcher;
 pub mod pagerank;
 pub mod parser;
 pub mod pipeline;
 pub mod semantic;
+pub mod structural_id;
 pub mod symbols;
+pub mod ts_symbols;
+pub mod tsquery;
+pub mod type_normalize;
+pub mod watch;
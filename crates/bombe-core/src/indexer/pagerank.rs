@@ -1,6 +1,6 @@
 //! PageRank computation over symbol graph edges.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use pyo3::prelude::*;
 use rusqlite::Connection;
@@ -9,7 +9,67 @@ use crate::errors::BombeResult;
 
 const PAGERANK_RELATIONSHIPS: &[&str] = &["CALLS", "IMPORTS_SYMBOL", "EXTENDS", "IMPLEMENTS"];
 
-pub fn recompute_pagerank_impl(conn: &Connection, damping: f64, epsilon: f64) -> BombeResult<()> {
+/// Per-relationship transition weights for PageRank's power iteration: a
+/// node's score flows to `v` in proportion to `w(u→v) / Σ w(u→*)` rather than
+/// uniformly over `1/outdeg`, so e.g. a `CALLS` edge carries more of a
+/// function's importance forward than an `EXTENDS`/`IMPLEMENTS` edge does.
+/// Deliberately a separate type from [`crate::query::code_graph::RelationshipWeights`]:
+/// that one spans the broader relationship set `CodeGraph` loads for context
+/// expansion, while this one only ever needs to weigh [`PAGERANK_RELATIONSHIPS`].
+#[derive(Clone, Debug)]
+pub struct PagerankWeights {
+    pub calls: f64,
+    pub imports_symbol: f64,
+    pub extends: f64,
+    pub implements: f64,
+}
+
+impl Default for PagerankWeights {
+    fn default() -> Self {
+        PagerankWeights {
+            calls: 2.0,
+            imports_symbol: 2.0,
+            extends: 1.0,
+            implements: 1.0,
+        }
+    }
+}
+
+impl PagerankWeights {
+    /// Applies caller-supplied overrides (e.g. from a Python dict keyed by
+    /// relationship name) on top of the defaults.
+    pub fn with_overrides(overrides: &HashMap<String, f64>) -> Self {
+        let mut weights = PagerankWeights::default();
+        for (relationship, weight) in overrides {
+            match relationship.as_str() {
+                "CALLS" => weights.calls = *weight,
+                "IMPORTS_SYMBOL" => weights.imports_symbol = *weight,
+                "EXTENDS" => weights.extends = *weight,
+                "IMPLEMENTS" => weights.implements = *weight,
+                _ => {}
+            }
+        }
+        weights
+    }
+
+    fn weight_for(&self, relationship: &str) -> f64 {
+        match relationship {
+            "CALLS" => self.calls,
+            "IMPORTS_SYMBOL" => self.imports_symbol,
+            "EXTENDS" => self.extends,
+            "IMPLEMENTS" => self.implements,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Loads every symbol id plus its weighted outgoing adjacency over the
+/// relationships PageRank cares about, shared by the global and personalized
+/// variants below so they agree on exactly what graph they're walking.
+fn load_pagerank_graph(
+    conn: &Connection,
+    weights: &PagerankWeights,
+) -> BombeResult<(Vec<i64>, HashMap<i64, Vec<(i64, f64)>>)> {
     let mut stmt = conn.prepare("SELECT id FROM symbols ORDER BY id;")?;
     let symbol_ids: Vec<i64> = stmt
         .query_map([], |row| row.get(0))?
@@ -17,11 +77,11 @@ pub fn recompute_pagerank_impl(conn: &Connection, damping: f64, epsilon: f64) ->
         .collect();
 
     if symbol_ids.is_empty() {
-        return Ok(());
+        return Ok((symbol_ids, HashMap::new()));
     }
 
-    let id_set: std::collections::HashSet<i64> = symbol_ids.iter().copied().collect();
-    let mut adjacency: HashMap<i64, Vec<i64>> =
+    let id_set: HashSet<i64> = symbol_ids.iter().copied().collect();
+    let mut adjacency: HashMap<i64, Vec<(i64, f64)>> =
         symbol_ids.iter().map(|&id| (id, Vec::new())).collect();
 
     let placeholders: String = PAGERANK_RELATIONSHIPS
@@ -30,7 +90,7 @@ pub fn recompute_pagerank_impl(conn: &Connection, damping: f64, epsilon: f64) ->
         .collect::<Vec<_>>()
         .join(", ");
     let sql = format!(
-        "SELECT source_id, target_id FROM edges \
+        "SELECT source_id, target_id, relationship FROM edges \
          WHERE source_type = 'symbol' AND target_type = 'symbol' \
          AND relationship IN ({placeholders});"
     );
@@ -39,45 +99,75 @@ pub fn recompute_pagerank_impl(conn: &Connection, damping: f64, epsilon: f64) ->
         .iter()
         .map(|r| r as &dyn rusqlite::types::ToSql)
         .collect();
-    let edges: Vec<(i64, i64)> = edge_stmt
-        .query_map(params.as_slice(), |row| Ok((row.get(0)?, row.get(1)?)))?
+    let edges: Vec<(i64, i64, String)> = edge_stmt
+        .query_map(params.as_slice(), |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
         .filter_map(|r| r.ok())
         .collect();
 
-    for (source, target) in edges {
+    for (source, target, relationship) in edges {
         if id_set.contains(&source) && id_set.contains(&target) {
-            adjacency.entry(source).or_default().push(target);
+            adjacency
+                .entry(source)
+                .or_default()
+                .push((target, weights.weight_for(&relationship)));
         }
     }
 
-    let node_count = symbol_ids.len() as f64;
-    let base_score = 1.0 / node_count;
-    let mut scores: HashMap<i64, f64> = symbol_ids.iter().map(|&id| (id, base_score)).collect();
+    Ok((symbol_ids, adjacency))
+}
+
+/// Shared power-iteration loop: advances `initial_scores` under `teleport`
+/// (the restart/teleport vector — uniform for the global recompute, rooted
+/// for personalized) until the L1 delta between successive iterations drops
+/// below `tol`. Dangling mass — weight-less nodes, i.e. no out-edges — is
+/// redistributed according to `teleport`, same as the restart step, so it
+/// never leaks out of the walk or dilutes whichever rooting is in effect.
+fn power_iterate(
+    symbol_ids: &[i64],
+    adjacency: &HashMap<i64, Vec<(i64, f64)>>,
+    damping: f64,
+    tol: f64,
+    initial_scores: HashMap<i64, f64>,
+    teleport: impl Fn(&i64) -> f64,
+) -> HashMap<i64, f64> {
+    // Each source's total outgoing weight never changes across rounds, so
+    // sum it once here rather than re-summing its edge list every iteration.
+    // A source with zero or negative total weight (e.g. all its relationship
+    // weights were overridden to 0) can't propagate a share to any neighbor,
+    // so it's treated the same as a dangling (no-out-edge) node below.
+    let total_weight: HashMap<i64, f64> = adjacency
+        .iter()
+        .map(|(&id, edges)| (id, edges.iter().map(|(_, w)| w).sum()))
+        .collect();
+
+    let mut scores = initial_scores;
 
     let mut delta = 1.0;
-    while delta > epsilon {
+    while delta > tol {
         let mut next_scores: HashMap<i64, f64> = symbol_ids
             .iter()
-            .map(|&id| (id, (1.0 - damping) / node_count))
+            .map(|&id| (id, (1.0 - damping) * teleport(&id)))
             .collect();
 
         let dangling_mass: f64 = adjacency
             .iter()
-            .filter(|(_, targets)| targets.is_empty())
+            .filter(|(id, edges)| edges.is_empty() || total_weight[id] <= 0.0)
             .map(|(id, _)| scores[id])
             .sum();
-        let dangling_contrib = damping * dangling_mass / node_count;
-
-        for &id in &symbol_ids {
-            *next_scores.get_mut(&id).unwrap() += dangling_contrib;
+        for &id in symbol_ids {
+            *next_scores.get_mut(&id).unwrap() += damping * dangling_mass * teleport(&id);
         }
 
-        for (&source, targets) in &adjacency {
-            if targets.is_empty() {
+        for (source, edges) in adjacency {
+            let source_total = total_weight[source];
+            if edges.is_empty() || source_total <= 0.0 {
                 continue;
             }
-            let share = damping * scores[&source] / targets.len() as f64;
-            for &target in targets {
+            let source_score = scores[source];
+            for &(target, weight) in edges {
+                let share = damping * source_score * weight / source_total;
                 *next_scores.get_mut(&target).unwrap() += share;
             }
         }
@@ -89,24 +179,219 @@ pub fn recompute_pagerank_impl(conn: &Connection, damping: f64, epsilon: f64) ->
         scores = next_scores;
     }
 
-    let mut update_stmt = conn.prepare("UPDATE symbols SET pagerank_score = ?1 WHERE id = ?2;")?;
-    for &id in &symbol_ids {
-        update_stmt.execute(rusqlite::params![scores[&id], id])?;
+    scores
+}
+
+/// `column` is trusted, internal-only ("pagerank_score" or
+/// "personalized_pagerank_score") — never caller-supplied — so interpolating
+/// it into the statement text is safe despite `rusqlite` having no way to
+/// bind a column name as a parameter.
+fn write_pagerank_scores(
+    conn: &Connection,
+    scores: &HashMap<i64, f64>,
+    column: &str,
+) -> BombeResult<()> {
+    let sql = format!("UPDATE symbols SET {column} = ?1 WHERE id = ?2;");
+    let mut update_stmt = conn.prepare(&sql)?;
+    for (&id, &score) in scores {
+        update_stmt.execute(rusqlite::params![score, id])?;
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip(conn, weights), fields(operation = "recompute_pagerank", node_count = tracing::field::Empty))]
+pub fn recompute_pagerank_impl(
+    conn: &Connection,
+    damping: f64,
+    epsilon: f64,
+    weights: &PagerankWeights,
+) -> BombeResult<()> {
+    crate::telemetry::timed_query("recompute_pagerank", || {
+        let (symbol_ids, adjacency) = load_pagerank_graph(conn, weights)?;
+
+        if symbol_ids.is_empty() {
+            return Ok(());
+        }
+        tracing::Span::current().record("node_count", symbol_ids.len() as i64);
+
+        let node_count = symbol_ids.len() as f64;
+        let base_score = 1.0 / node_count;
+        let teleport = move |_: &i64| 1.0 / node_count;
+        let initial: HashMap<i64, f64> = symbol_ids.iter().map(|&id| (id, base_score)).collect();
+        let scores = power_iterate(&symbol_ids, &adjacency, damping, epsilon, initial, teleport);
+
+        write_pagerank_scores(conn, &scores, "pagerank_score")?;
+        // Note: caller is responsible for commit
+
+        Ok(())
+    })
+}
+
+/// Incremental recompute: instead of restarting power iteration from a
+/// uniform vector (the cold-start cost `recompute_pagerank_impl` pays on
+/// every reindex), warm-starts from each symbol's previously stored
+/// `pagerank_score` — which for a small diff is already close to the fixed
+/// point, so convergence takes a handful of iterations instead of dozens.
+/// `changed_ids` are the symbols (or symbols touching changed edges) from
+/// this reindex; their stored score was computed against a now-stale graph,
+/// so they restart from the uniform baseline instead of trusting it.
+pub fn recompute_pagerank_incremental_impl(
+    conn: &Connection,
+    changed_ids: &[i64],
+    damping: f64,
+    tol: f64,
+    weights: &PagerankWeights,
+) -> BombeResult<()> {
+    let (symbol_ids, adjacency) = load_pagerank_graph(conn, weights)?;
+
+    if symbol_ids.is_empty() {
+        return Ok(());
     }
+
+    let mut stored_stmt = conn.prepare("SELECT id, pagerank_score FROM symbols;")?;
+    let stored: HashMap<i64, f64> = stored_stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1).unwrap_or(0.0)))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let node_count = symbol_ids.len() as f64;
+    let base_score = 1.0 / node_count;
+    let changed: HashSet<i64> = changed_ids.iter().copied().collect();
+    let initial: HashMap<i64, f64> = symbol_ids
+        .iter()
+        .map(|&id| {
+            let warm_start = if changed.contains(&id) {
+                base_score
+            } else {
+                stored.get(&id).copied().unwrap_or(base_score)
+            };
+            (id, warm_start)
+        })
+        .collect();
+
+    let teleport = move |_: &i64| 1.0 / node_count;
+    let scores = power_iterate(&symbol_ids, &adjacency, damping, tol, initial, teleport);
+
+    write_pagerank_scores(conn, &scores, "pagerank_score")?;
     // Note: caller is responsible for commit
 
     Ok(())
 }
 
+/// Rooted (personalized) PageRank: same power iteration as
+/// [`recompute_pagerank_impl`], but the teleport vector is concentrated on
+/// `seed_ids` instead of spread uniformly, so the resulting scores measure
+/// importance relative to that seed set rather than the whole graph.
+/// Dangling mass — symbols with no out-edges — is likewise redistributed onto
+/// the seed set, not the full node set, so it can't dilute the rooting.
+///
+/// Returns an empty map when there are no symbols or no (graph-reachable)
+/// seeds, since a teleport vector with nothing to teleport to is undefined.
+pub fn personalized_pagerank_impl(
+    conn: &Connection,
+    seed_ids: &[i64],
+    damping: f64,
+    tol: f64,
+    weights: &PagerankWeights,
+) -> BombeResult<HashMap<i64, f64>> {
+    let (symbol_ids, adjacency) = load_pagerank_graph(conn, weights)?;
+
+    if symbol_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let id_set: HashSet<i64> = symbol_ids.iter().copied().collect();
+    let seed_set: HashSet<i64> = seed_ids
+        .iter()
+        .copied()
+        .filter(|id| id_set.contains(id))
+        .collect();
+    if seed_set.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let seed_count = seed_set.len() as f64;
+    let teleport = move |id: &i64| if seed_set.contains(id) { 1.0 / seed_count } else { 0.0 };
+
+    let initial: HashMap<i64, f64> = symbol_ids.iter().map(|&id| (id, teleport(&id))).collect();
+    Ok(power_iterate(&symbol_ids, &adjacency, damping, tol, initial, teleport))
+}
+
+/// Same rooted PageRank as [`personalized_pagerank_impl`], but persists the
+/// result to `symbols.personalized_pagerank_score` instead of handing the
+/// scores back as a map — a separate column from `pagerank_score` so a
+/// query-focused recompute never clobbers the global ranking, and the two
+/// can be read side by side.
+pub fn recompute_pagerank_personalized_impl(
+    conn: &Connection,
+    seed_ids: &[i64],
+    damping: f64,
+    tol: f64,
+    weights: &PagerankWeights,
+) -> BombeResult<()> {
+    let scores = personalized_pagerank_impl(conn, seed_ids, damping, tol, weights)?;
+    write_pagerank_scores(conn, &scores, "personalized_pagerank_score")?;
+    // Note: caller is responsible for commit
+    Ok(())
+}
+
 #[pyfunction]
-#[pyo3(signature = (db, damping=0.85, epsilon=1e-6))]
+#[pyo3(signature = (db, damping=0.85, epsilon=1e-6, relationship_weights=None))]
 pub fn recompute_pagerank(
     db: &crate::store::database::Database,
     damping: f64,
     epsilon: f64,
+    relationship_weights: Option<HashMap<String, f64>>,
+) -> PyResult<()> {
+    let weights = match &relationship_weights {
+        Some(overrides) => PagerankWeights::with_overrides(overrides),
+        None => PagerankWeights::default(),
+    };
+    let conn = db.connect_internal()?;
+    recompute_pagerank_impl(&conn, damping, epsilon, &weights)?;
+    conn.execute_batch("COMMIT;").ok();
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(signature = (db, changed_ids, damping=0.85, tol=1e-6, relationship_weights=None))]
+pub fn recompute_pagerank_incremental(
+    db: &crate::store::database::Database,
+    changed_ids: Vec<i64>,
+    damping: f64,
+    tol: f64,
+    relationship_weights: Option<HashMap<String, f64>>,
+) -> PyResult<()> {
+    let weights = match &relationship_weights {
+        Some(overrides) => PagerankWeights::with_overrides(overrides),
+        None => PagerankWeights::default(),
+    };
+    let conn = db.connect_internal()?;
+    recompute_pagerank_incremental_impl(&conn, &changed_ids, damping, tol, &weights)?;
+    conn.execute_batch("COMMIT;").ok();
+    Ok(())
+}
+
+/// Query-focused PageRank: teleports to `seeds` instead of the whole graph,
+/// weights edges per `weights` the same way [`recompute_pagerank`] does, and
+/// persists to `symbols.personalized_pagerank_score` rather than
+/// `pagerank_score`, so the two coexist.
+#[pyfunction]
+#[pyo3(signature = (db, seeds, damping=0.85, epsilon=1e-6, relationship_weights=None))]
+pub fn recompute_pagerank_personalized(
+    db: &crate::store::database::Database,
+    seeds: Vec<i64>,
+    damping: f64,
+    epsilon: f64,
+    relationship_weights: Option<HashMap<String, f64>>,
 ) -> PyResult<()> {
+    let weights = match &relationship_weights {
+        Some(overrides) => PagerankWeights::with_overrides(overrides),
+        None => PagerankWeights::default(),
+    };
     let conn = db.connect_internal()?;
-    recompute_pagerank_impl(&conn, damping, epsilon)?;
+    recompute_pagerank_personalized_impl(&conn, &seeds, damping, epsilon, &weights)?;
     conn.execute_batch("COMMIT;").ok();
     Ok(())
 }
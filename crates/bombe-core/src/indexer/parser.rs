@@ -4,11 +4,220 @@
 //! Python tree_sitter_languages. Python AST parsing is delegated
 //! back to Python via PyO3 for the Python language.
 
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{Mutex, OnceLock};
 
 use pyo3::prelude::*;
+use tree_sitter::Language;
 
-const SUPPORTED_LANGUAGES: &[&str] = &["python", "java", "typescript", "go"];
+/// A language entry known to the [`GrammarRegistry`], either linked in at
+/// compile time or loaded from a shared library at runtime.
+struct GrammarEntry {
+    language: Option<Language>,
+    backend: &'static str,
+    /// Set when loading failed; kept so capability reports can explain why.
+    load_error: Option<String>,
+}
+
+/// Registry of tree-sitter grammars, keyed by language name.
+///
+/// Built-in languages are registered from their compiled `LanguageFn` at
+/// construction time. Additional grammars can be loaded at runtime from a
+/// shared library via [`GrammarRegistry::register_dylib`], the same way
+/// editors like Helix extend language coverage without a recompile.
+///
+/// `Library` handles for dynamically loaded grammars are leaked so the
+/// `Language` values they produce (which borrow the library's code) stay
+/// valid for the lifetime of the process.
+pub struct GrammarRegistry {
+    entries: Mutex<HashMap<String, GrammarEntry>>,
+}
+
+impl GrammarRegistry {
+    fn new() -> Self {
+        let mut entries = HashMap::new();
+        entries.insert(
+            "java".to_string(),
+            GrammarEntry {
+                language: Some(tree_sitter_java::LANGUAGE.into()),
+                backend: "static",
+                load_error: None,
+            },
+        );
+        entries.insert(
+            "typescript".to_string(),
+            GrammarEntry {
+                language: Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+                backend: "static",
+                load_error: None,
+            },
+        );
+        entries.insert(
+            "go".to_string(),
+            GrammarEntry {
+                language: Some(tree_sitter_go::LANGUAGE.into()),
+                backend: "static",
+                load_error: None,
+            },
+        );
+        // Python has no tree-sitter entry: parsing is delegated to Python's ast.
+        Self {
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Load a grammar from a shared library (`libtree-sitter-<lang>.so/.dll`)
+    /// and register it under `name`. The library's `tree_sitter_<name>` symbol
+    /// is resolved and wrapped into a [`tree_sitter::Language`].
+    ///
+    /// The `Library` handle is leaked (never dropped) because the returned
+    /// `Language` borrows code owned by the library; dropping it would leave
+    /// dangling function pointers behind.
+    pub fn register_dylib(&self, name: &str, path: &Path) -> Result<(), String> {
+        let lib = unsafe { libloading::Library::new(path) }
+            .map_err(|e| format!("Failed to load grammar library {}: {e}", path.display()))?;
+        let symbol_name = format!("tree_sitter_{name}");
+        let language = unsafe {
+            let symbol: libloading::Symbol<unsafe extern "C" fn() -> *const ()> = lib
+                .get(symbol_name.as_bytes())
+                .map_err(|e| format!("Failed to resolve symbol {symbol_name}: {e}"))?;
+            let raw = symbol();
+            tree_sitter_language::LanguageFn::from_raw(raw as _)
+        };
+        // Leak the library so the Language's borrowed code stays alive forever.
+        std::mem::forget(lib);
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            name.to_string(),
+            GrammarEntry {
+                language: Some(language.into()),
+                backend: "dynamic",
+                load_error: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Record a failed dynamic-load attempt so capability reports can surface
+    /// the reason instead of silently omitting the language.
+    pub fn register_dylib_failure(&self, name: &str, reason: String) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            name.to_string(),
+            GrammarEntry {
+                language: None,
+                backend: "dynamic",
+                load_error: Some(reason),
+            },
+        );
+    }
+
+    fn get(&self, name: &str) -> Option<Language> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(name).and_then(|e| e.language.clone())
+    }
+
+    /// Snapshot of `(name, backend, available, reason)` for every registered
+    /// language, used by [`tree_sitter_capability_report`].
+    fn snapshot(&self) -> Vec<(String, &'static str, bool, Option<String>)> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .iter()
+            .map(|(name, entry)| {
+                (
+                    name.clone(),
+                    entry.backend,
+                    entry.language.is_some(),
+                    entry.load_error.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Names of languages with a successfully loaded grammar, built-in or
+    /// dynamic, used to extend extension-based detection.
+    fn available_languages(&self) -> Vec<String> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .iter()
+            .filter(|(_, e)| e.language.is_some())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+/// Built-in extension-to-language mapping, mirrored from
+/// `indexer::filesystem::detect_language`'s Python-facing map.
+const EXTENSION_BY_LANGUAGE: &[(&str, &str)] = &[
+    (".py", "python"),
+    (".java", "java"),
+    (".ts", "typescript"),
+    (".tsx", "typescript"),
+    (".go", "go"),
+];
+
+/// Detect a language name from a file's extension, covering both built-in
+/// grammars and any registered via [`register_dylib`] (matched by a `.<name>`
+/// extension, e.g. `libtree-sitter-zig` registered as `zig` is detected from
+/// `.zig` files).
+pub fn detect_language(path: &Path) -> Option<&'static str> {
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy().to_lowercase()))?;
+
+    if let Some((_, lang)) = EXTENSION_BY_LANGUAGE.iter().find(|(e, _)| *e == ext) {
+        return Some(lang);
+    }
+
+    // Dynamically registered grammars default to a `.<name>` extension.
+    // Interning here (once per distinct name) lets us hand back a `'static`
+    // str without keeping the registry's lock held by the caller.
+    static INTERNED: OnceLock<Mutex<HashMap<String, &'static str>>> = OnceLock::new();
+    let table = INTERNED.get_or_init(|| Mutex::new(HashMap::new()));
+
+    let name = registry()
+        .available_languages()
+        .into_iter()
+        .find(|name| ext == format!(".{name}"))?;
+
+    let mut table = table.lock().unwrap();
+    Some(*table.entry(name.clone()).or_insert_with(|| Box::leak(name.into_boxed_str())))
+}
+
+/// Detect the language from `path`'s extension, then parse it. Returns a
+/// clear error for extensions with no known grammar instead of forcing
+/// callers to resolve the language themselves.
+pub fn parse_file_auto(path: &Path) -> Result<RustParsedUnit, String> {
+    let language = detect_language(path)
+        .ok_or_else(|| format!("Unknown file extension for: {}", path.display()))?;
+    parse_file_native(path, language)
+}
+
+fn registry() -> &'static GrammarRegistry {
+    static REGISTRY: OnceLock<GrammarRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(GrammarRegistry::new)
+}
+
+/// Load an additional grammar from a shared library at runtime. See
+/// [`GrammarRegistry::register_dylib`] for the loading contract.
+pub fn register_dylib(name: &str, path: &Path) -> Result<(), String> {
+    registry().register_dylib(name, path)
+}
+
+/// Parse `source` in memory for `language`, without touching the
+/// filesystem. Unlike [`parse_file_native`], which always has a path to
+/// read, callers like `callgraph`'s AST extraction pass only ever have
+/// source text already loaded (possibly from a DB row, not a file). Returns
+/// `None` for `python` and any language without a loaded grammar, so
+/// callers can fall back to a non-AST extraction path transparently.
+pub fn parse_source_native(source: &str, language: &str) -> Option<tree_sitter::Tree> {
+    let ts_language = registry().get(language)?;
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&ts_language).ok()?;
+    parser.parse(source.as_bytes(), None)
+}
 
 /// Parsed source unit — mirrors the Python ParsedUnit but holds raw source.
 /// Tree-sitter tree objects stay in Rust; for Python files, parsing
@@ -19,10 +228,14 @@ pub struct RustParsedUnit {
     pub source: String,
     /// For non-Python languages: native tree-sitter tree.
     pub tree: Option<tree_sitter::Tree>,
+    /// The grammar used to produce `tree`, kept alongside it so the unit can
+    /// be re-parsed incrementally without re-routing through the language
+    /// `match`/registry lookup. `None` for Python units.
+    pub grammar: Option<Language>,
 }
 
 pub fn parse_file_native(path: &Path, language: &str) -> Result<RustParsedUnit, String> {
-    if !SUPPORTED_LANGUAGES.contains(&language) {
+    if language != "python" && registry().get(language).is_none() {
         return Err(format!("Unsupported language: {language}"));
     }
 
@@ -36,19 +249,17 @@ pub fn parse_file_native(path: &Path, language: &str) -> Result<RustParsedUnit,
             language: language.to_string(),
             source,
             tree: None,
+            grammar: None,
         });
     }
 
-    let ts_language = match language {
-        "java" => tree_sitter_java::LANGUAGE,
-        "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
-        "go" => tree_sitter_go::LANGUAGE,
-        _ => return Err(format!("No tree-sitter grammar for: {language}")),
-    };
+    let ts_language = registry()
+        .get(language)
+        .ok_or_else(|| format!("No tree-sitter grammar for: {language}"))?;
 
     let mut parser = tree_sitter::Parser::new();
     parser
-        .set_language(&ts_language.into())
+        .set_language(&ts_language)
         .map_err(|e| format!("Failed to set language: {e}"))?;
 
     let tree = parser
@@ -60,36 +271,121 @@ pub fn parse_file_native(path: &Path, language: &str) -> Result<RustParsedUnit,
         language: language.to_string(),
         source,
         tree: Some(tree),
+        grammar: Some(ts_language),
     })
 }
 
+/// A single-range edit to apply to a previously parsed tree, mirroring
+/// `tree_sitter::InputEdit` but in plain byte offsets + row/column points so
+/// callers don't need to depend on the tree-sitter crate directly.
+pub struct TextEdit {
+    pub start_byte: usize,
+    pub old_end_byte: usize,
+    pub new_end_byte: usize,
+    pub start_point: (usize, usize),
+    pub old_end_point: (usize, usize),
+    pub new_end_point: (usize, usize),
+}
+
+/// Apply an edit to `unit`'s stored tree and re-parse `new_source`
+/// incrementally, reusing unchanged subtrees via `Tree::edit` +
+/// `Parser::parse(.., Some(&old_tree))`.
+///
+/// Returns the updated unit plus the byte ranges `Tree::changed_ranges`
+/// reports as different, so extraction passes can re-run only over the
+/// affected regions instead of the whole file.
+///
+/// Python units have no native tree to edit incrementally (Python parsing
+/// is delegated to CPython's `ast` module), so this always errors for them.
+pub fn reparse_edit(
+    unit: &RustParsedUnit,
+    edit: TextEdit,
+    new_source: String,
+) -> Result<(RustParsedUnit, Vec<(usize, usize)>), String> {
+    let mut old_tree = unit
+        .tree
+        .clone()
+        .ok_or_else(|| "incremental parsing unavailable for python".to_string())?;
+    let grammar = unit
+        .grammar
+        .clone()
+        .ok_or_else(|| "incremental parsing unavailable for python".to_string())?;
+
+    old_tree.edit(&tree_sitter::InputEdit {
+        start_byte: edit.start_byte,
+        old_end_byte: edit.old_end_byte,
+        new_end_byte: edit.new_end_byte,
+        start_position: tree_sitter::Point::new(edit.start_point.0, edit.start_point.1),
+        old_end_position: tree_sitter::Point::new(edit.old_end_point.0, edit.old_end_point.1),
+        new_end_position: tree_sitter::Point::new(edit.new_end_point.0, edit.new_end_point.1),
+    });
+
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&grammar)
+        .map_err(|e| format!("Failed to set language: {e}"))?;
+
+    let new_tree = parser
+        .parse(new_source.as_bytes(), Some(&old_tree))
+        .ok_or_else(|| "Failed to incrementally re-parse".to_string())?;
+
+    let changed_ranges = new_tree
+        .changed_ranges(&old_tree)
+        .map(|r| (r.start_byte, r.end_byte))
+        .collect();
+
+    let new_unit = RustParsedUnit {
+        path: unit.path.clone(),
+        language: unit.language.clone(),
+        source: new_source,
+        tree: Some(new_tree),
+        grammar: Some(grammar),
+    };
+
+    Ok((new_unit, changed_ranges))
+}
+
 #[pyfunction]
 pub fn tree_sitter_capability_report(py: Python<'_>) -> PyResult<PyObject> {
     let required = vec!["python", "java", "typescript", "go"];
 
     let languages = pyo3::types::PyList::empty(py);
     let mut all_available = true;
+    let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
 
     for lang in &required {
-        let available = match *lang {
-            "java" | "typescript" | "go" => true,
-            "python" => true, // handled via Python ast
-            _ => {
-                all_available = false;
-                false
+        let (backend, available, reason) = if *lang == "python" {
+            ("python-ast", true, "ok".to_string())
+        } else {
+            match registry().get(lang) {
+                Some(_) => ("static", true, "ok".to_string()),
+                None => {
+                    all_available = false;
+                    ("static", false, "parser_unavailable".to_string())
+                }
             }
         };
+        seen.insert(lang.to_string());
         let entry = pyo3::types::PyDict::new(py);
         entry.set_item("language", *lang)?;
-        entry.set_item("backend", *lang)?;
+        entry.set_item("backend", backend)?;
+        entry.set_item("available", available)?;
+        entry.set_item("reason", reason)?;
+        languages.append(entry)?;
+    }
+
+    // Enumerate dynamically registered grammars beyond the required set.
+    for (name, backend, available, load_error) in registry().snapshot() {
+        if seen.contains(&name) {
+            continue;
+        }
+        let entry = pyo3::types::PyDict::new(py);
+        entry.set_item("language", &name)?;
+        entry.set_item("backend", backend)?;
         entry.set_item("available", available)?;
         entry.set_item(
             "reason",
-            if available {
-                "ok"
-            } else {
-                "parser_unavailable"
-            },
+            load_error.unwrap_or_else(|| "ok".to_string()),
         )?;
         languages.append(entry)?;
     }
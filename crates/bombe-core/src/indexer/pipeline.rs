@@ -1,13 +1,19 @@
 //! Indexing pipeline orchestration with Rayon-based parallelism.
 
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use pyo3::prelude::*;
 use rayon::prelude::*;
+use regex::Regex;
+use rusqlite::{params, Connection};
 
+use crate::indexer::callgraph::{build_call_edges, ExtractionMode};
+use crate::indexer::embedding::{chunk_symbols, SymbolChunk};
 use crate::indexer::filesystem::{detect_language, iter_repo_files};
 use crate::indexer::symbols::{extract_symbols, ExtractedImport, ExtractedSymbol};
+use crate::store::database::Database;
 
 pub struct FileRecord {
     pub path: String,
@@ -23,6 +29,10 @@ pub struct ExtractionResult {
     source: String,
     symbols: Vec<ExtractedSymbol>,
     imports: Vec<ExtractedImport>,
+    /// One chunk per extracted symbol, along its own source span — see
+    /// `indexer::embedding::chunk_symbols`. Empty for files that failed to
+    /// read and for files with no extracted symbols.
+    symbol_chunks: Vec<SymbolChunk>,
     error_stage: Option<String>,
     error_message: Option<String>,
 }
@@ -38,29 +48,15 @@ fn extract_file_worker(repo_root: &str, relative_path: &str, language: &str) ->
                 source: String::new(),
                 symbols: vec![],
                 imports: vec![],
+                symbol_chunks: vec![],
                 error_stage: Some("parse".to_string()),
                 error_message: Some(e.to_string()),
             }
         }
     };
 
-    // For Python, symbols extraction requires Python's ast module
-    // which is handled on the Python side. For Java/TypeScript/Go,
-    // we extract natively in Rust.
-    if language == "python" {
-        // Return source only; Python extraction done on Python side
-        return ExtractionResult {
-            file_path: relative_path.to_string(),
-            language: language.to_string(),
-            source,
-            symbols: vec![],
-            imports: vec![],
-            error_stage: None,
-            error_message: None,
-        };
-    }
-
     let (symbols, imports) = extract_symbols(&source, relative_path, language);
+    let symbol_chunks = chunk_symbols(&source, &symbols);
 
     ExtractionResult {
         file_path: relative_path.to_string(),
@@ -68,6 +64,7 @@ fn extract_file_worker(repo_root: &str, relative_path: &str, language: &str) ->
         source,
         symbols,
         imports,
+        symbol_chunks,
         error_stage: None,
         error_message: None,
     }
@@ -106,6 +103,125 @@ pub fn parallel_extract(
     }
 }
 
+/// Translates one glob pattern into a self-contained regex fragment with no
+/// outer `^`/`$` of its own — [`compile_glob_set`] OR-combines fragments
+/// behind a single pair of anchors so a candidate path is tested in one
+/// regex pass instead of one pass per pattern. `*` becomes `[^/]*`, `**`
+/// becomes `.*`, `?` becomes `[^/]`, a `[...]` bracket expression passes
+/// through verbatim (`[!` rewritten to `[^`), and every other regex
+/// metacharacter is escaped. A leading `/` anchors the pattern to the repo
+/// root; without one, the fragment may start at any path depth. A trailing
+/// `/` restricts the pattern to directories — since this filter only ever
+/// sees file paths, that means "anything under this directory" rather than
+/// the directory path itself.
+fn translate_extraction_glob(pattern: &str) -> String {
+    let anchored = pattern.starts_with('/');
+    let body = pattern.strip_prefix('/').unwrap_or(pattern);
+    let directory_only = body.len() > 1 && body.ends_with('/');
+    let body = if directory_only {
+        &body[..body.len() - 1]
+    } else {
+        body
+    };
+
+    let chars: Vec<char> = body.chars().collect();
+    let len = chars.len();
+    let mut regex = String::with_capacity(len * 2);
+    let mut i = 0;
+    while i < len {
+        if chars[i] == '*' && i + 1 < len && chars[i + 1] == '*' {
+            regex.push_str(".*");
+            i += 2;
+        } else if chars[i] == '*' {
+            regex.push_str("[^/]*");
+            i += 1;
+        } else if chars[i] == '?' {
+            regex.push_str("[^/]");
+            i += 1;
+        } else if chars[i] == '[' {
+            match chars[i + 1..].iter().position(|&c| c == ']') {
+                Some(offset) => {
+                    let close = i + 1 + offset;
+                    regex.push('[');
+                    let mut j = i + 1;
+                    if j < close && chars[j] == '!' {
+                        regex.push('^');
+                        j += 1;
+                    }
+                    while j < close {
+                        regex.push(chars[j]);
+                        j += 1;
+                    }
+                    regex.push(']');
+                    i = close + 1;
+                }
+                None => {
+                    regex.push_str("\\[");
+                    i += 1;
+                }
+            }
+        } else {
+            let c = chars[i];
+            if "\\.+^$(){}|".contains(c) {
+                regex.push('\\');
+            }
+            regex.push(c);
+            i += 1;
+        }
+    }
+
+    let fragment = if anchored {
+        regex
+    } else {
+        format!("(?:.*/)?{regex}")
+    };
+    if directory_only {
+        format!("{fragment}/.*")
+    } else {
+        fragment
+    }
+}
+
+/// Compiles a set of glob patterns into one combined regex, OR-ing every
+/// pattern's [`translate_extraction_glob`] fragment together. `None` means
+/// no patterns were given — the caller treats that as "matches everything"
+/// for includes and "matches nothing" for excludes.
+fn compile_glob_set(patterns: &[String]) -> Option<Regex> {
+    let fragments: Vec<String> = patterns
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .map(translate_extraction_glob)
+        .collect();
+    if fragments.is_empty() {
+        return None;
+    }
+    Regex::new(&format!("^(?:{})$", fragments.join("|"))).ok()
+}
+
+/// Filters `files` down to the repo-relative paths that should actually be
+/// routed to per-language extraction: `include_patterns` selects (empty
+/// means "everything"), then `exclude_patterns` removes from that — a path
+/// matching both an include and an exclude pattern is dropped, mirroring
+/// `.gitignore`'s exclude-wins precedence. Lets a caller skip vendored or
+/// generated trees at the point files are handed to the extractors, rather
+/// than extracting everything and discarding the result.
+pub fn filter_files_for_extraction(
+    files: &[FileRecord],
+    include_patterns: &[String],
+    exclude_patterns: &[String],
+) -> Vec<String> {
+    let include = compile_glob_set(include_patterns);
+    let exclude = compile_glob_set(exclude_patterns);
+    files
+        .iter()
+        .map(|f| &f.path)
+        .filter(|path| include.as_ref().is_none_or(|r| r.is_match(path)))
+        .filter(|path| !exclude.as_ref().is_some_and(|r| r.is_match(path)))
+        .cloned()
+        .collect()
+}
+
 pub struct IndexStats {
     pub files_seen: i64,
     pub files_indexed: i64,
@@ -120,8 +236,9 @@ pub fn scan_repo_files(
     repo_root: &Path,
     include_patterns: Option<&[String]>,
     exclude_patterns: Option<&[String]>,
-) -> (i64, Vec<FileRecord>) {
-    let all_files = iter_repo_files(repo_root, include_patterns, exclude_patterns);
+    workers: Option<usize>,
+) -> crate::errors::BombeResult<(i64, Vec<FileRecord>)> {
+    let all_files = iter_repo_files(repo_root, include_patterns, exclude_patterns, workers)?;
     let mut files_seen = 0i64;
     let mut records = Vec::new();
 
@@ -149,29 +266,865 @@ pub fn scan_repo_files(
         });
     }
 
-    (files_seen, records)
+    Ok((files_seen, records))
+}
+
+/// Prior `(file_path -> content_hash)` state, as already written to the
+/// `files` table, to diff the freshly scanned records against.
+fn load_prior_file_hashes(conn: &Connection) -> crate::errors::BombeResult<HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT path, content_hash FROM files;")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// Classify a freshly scanned file list against `prior` hashes: a file
+/// whose hash is new or has changed needs re-extraction; a file present in
+/// `prior` but missing from the scan has been deleted. A file whose hash is
+/// unchanged is left out of both lists entirely — that's the whole point
+/// of incremental indexing.
+fn classify_files(
+    prior: &HashMap<String, String>,
+    scanned: &[FileRecord],
+) -> (Vec<FileRecord>, Vec<String>, i64) {
+    let (changed, deleted, unchanged_count, _added_count) = classify_files_with_counts(prior, scanned);
+    (changed, deleted, unchanged_count)
+}
+
+/// Same diff as [`classify_files`], but also reports how many of `changed`
+/// are brand new (no prior hash at all) vs. modified (hash present but
+/// different) — the `added`/`updated` split [`IngestSummary`] reports.
+fn classify_files_with_counts(
+    prior: &HashMap<String, String>,
+    scanned: &[FileRecord],
+) -> (Vec<FileRecord>, Vec<String>, i64, i64) {
+    let mut changed = Vec::new();
+    let mut unchanged_count = 0i64;
+    let mut added_count = 0i64;
+    let mut seen_paths: std::collections::HashSet<&str> = std::collections::HashSet::new();
+
+    for record in scanned {
+        seen_paths.insert(record.path.as_str());
+        match prior.get(&record.path) {
+            Some(prior_hash) if prior_hash == &record.content_hash => unchanged_count += 1,
+            Some(_) => changed.push(FileRecord {
+                path: record.path.clone(),
+                language: record.language.clone(),
+                content_hash: record.content_hash.clone(),
+                size_bytes: record.size_bytes,
+            }),
+            None => {
+                added_count += 1;
+                changed.push(FileRecord {
+                    path: record.path.clone(),
+                    language: record.language.clone(),
+                    content_hash: record.content_hash.clone(),
+                    size_bytes: record.size_bytes,
+                });
+            }
+        }
+    }
+
+    let deleted: Vec<String> = prior
+        .keys()
+        .filter(|path| !seen_paths.contains(path.as_str()))
+        .cloned()
+        .collect();
+
+    (changed, deleted, unchanged_count, added_count)
+}
+
+/// Constraints on what an ingest run touches, for
+/// [`run_index_impl_with_constraints`] callers that want something narrower
+/// than "diff the whole repo" (`rust_full_index` always uses the defaults).
+/// `force_full` bypasses the `content_hash` diff entirely — every scanned
+/// file is treated as changed, regardless of what's already stored —
+/// for a deliberate full rebuild (e.g. after a schema change that
+/// invalidates stored symbols).
+#[derive(Debug, Clone, Default)]
+pub struct IngestConstraints {
+    /// Only ingest files whose detected language is in this set. `None`
+    /// (the default) ingests every supported language.
+    pub languages: Option<Vec<String>>,
+    /// Only ingest files whose repo-relative path starts with one of these
+    /// prefixes. `None` (the default) ingests the whole repo.
+    pub path_prefixes: Option<Vec<String>>,
+    /// Gitignore-style glob patterns (see [`filter_files_for_extraction`])
+    /// a path must match to be extracted. `None` ingests every path that
+    /// otherwise passes `languages`/`path_prefixes`.
+    pub extract_include_globs: Option<Vec<String>>,
+    /// Glob patterns excluded from extraction, taking precedence over
+    /// `extract_include_globs` — for skipping vendored/generated trees
+    /// that happen to match an include pattern too.
+    pub extract_exclude_globs: Option<Vec<String>>,
+    /// Skip the `content_hash` diff and re-ingest every matching file.
+    pub force_full: bool,
+}
+
+impl IngestConstraints {
+    fn matches(&self, record: &FileRecord) -> bool {
+        if let Some(languages) = &self.languages {
+            if !languages.iter().any(|l| l == &record.language) {
+                return false;
+            }
+        }
+        if let Some(prefixes) = &self.path_prefixes {
+            if !prefixes.iter().any(|p| record.path.starts_with(p.as_str())) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Added/updated/skipped/removed file counts for one ingest run, reported
+/// alongside [`IndexRunResult`]'s existing symbol/edge/timing stats.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IngestSummary {
+    pub added: i64,
+    pub updated: i64,
+    pub skipped: i64,
+    pub removed: i64,
+}
+
+/// Delete all graph data (symbols, edges, parameters, FTS, file row) for a
+/// removed file. Mirrors `Database::delete_file_graph`'s SQL; duplicated
+/// rather than shared because that method lives behind a `#[pymethods]`
+/// `&self` receiver while this pipeline works directly against a borrowed
+/// `Connection` inside its own transaction, matching how `query::context`/
+/// `query::data_flow`/`query::structure` are written against `&Connection`
+/// rather than through `Database`.
+fn delete_file_graph_sql(conn: &Connection, file_path: &str) -> crate::errors::BombeResult<()> {
+    let mut id_stmt = conn.prepare("SELECT id FROM symbols WHERE file_path = ?1;")?;
+    let symbol_ids: Vec<i64> = id_stmt
+        .query_map(params![file_path], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    drop(id_stmt);
+
+    for sid in &symbol_ids {
+        match conn.execute("DELETE FROM symbol_fts WHERE symbol_id = ?1;", params![sid]) {
+            Ok(_) => {}
+            Err(_) => break, // FTS table may not exist
+        }
+    }
+
+    conn.execute("DELETE FROM edges WHERE file_path = ?1;", params![file_path])?;
+    conn.execute(
+        "DELETE FROM external_deps WHERE file_path = ?1;",
+        params![file_path],
+    )?;
+    conn.execute(
+        "DELETE FROM parameters WHERE symbol_id IN \
+         (SELECT id FROM symbols WHERE file_path = ?1);",
+        params![file_path],
+    )?;
+    conn.execute("DELETE FROM symbols WHERE file_path = ?1;", params![file_path])?;
+    conn.execute("DELETE FROM files WHERE path = ?1;", params![file_path])?;
+    Ok(())
+}
+
+fn upsert_file_row_sql(conn: &Connection, record: &FileRecord) -> crate::errors::BombeResult<()> {
+    conn.execute(
+        "INSERT INTO files (path, language, content_hash, size_bytes) \
+         VALUES (?1, ?2, ?3, ?4) \
+         ON CONFLICT(path) DO UPDATE SET \
+             language = excluded.language, \
+             content_hash = excluded.content_hash, \
+             size_bytes = excluded.size_bytes, \
+             last_indexed_at = CURRENT_TIMESTAMP;",
+        params![
+            record.path,
+            record.language,
+            record.content_hash,
+            record.size_bytes
+        ],
+    )?;
+    Ok(())
+}
+
+/// Replace all symbols (parameters + FTS entries) for `file_path`, deduping
+/// by `qualified_name` same as `Database::_replace_file_symbols`. Returns
+/// the number of symbols inserted.
+fn replace_file_symbols_sql(
+    conn: &Connection,
+    file_path: &str,
+    symbols: &[ExtractedSymbol],
+) -> crate::errors::BombeResult<i64> {
+    let mut old_id_stmt = conn.prepare("SELECT id FROM symbols WHERE file_path = ?1;")?;
+    let old_ids: Vec<i64> = old_id_stmt
+        .query_map(params![file_path], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    drop(old_id_stmt);
+    for sid in &old_ids {
+        match conn.execute("DELETE FROM symbol_fts WHERE symbol_id = ?1;", params![sid]) {
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    conn.execute(
+        "DELETE FROM parameters WHERE symbol_id IN \
+         (SELECT id FROM symbols WHERE file_path = ?1);",
+        params![file_path],
+    )?;
+    conn.execute("DELETE FROM symbols WHERE file_path = ?1;", params![file_path])?;
+
+    let mut insert_symbol = conn.prepare(
+        "INSERT INTO symbols ( \
+             name, qualified_name, kind, file_path, start_line, end_line, \
+             signature, return_type, visibility, is_async, is_static, \
+             parent_symbol_id, docstring, pagerank_score, supertypes \
+         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15);",
+    )?;
+    let mut insert_param = conn.prepare(
+        "INSERT INTO parameters (symbol_id, name, type, position, default_value) \
+         VALUES (?1, ?2, ?3, ?4, ?5);",
+    )?;
+    let mut insert_fts = conn.prepare(
+        "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
+         VALUES (?1, ?2, ?3, ?4, ?5);",
+    )?;
+
+    let mut inserted = 0i64;
+    let mut seen: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    for symbol in symbols {
+        if !seen.insert(symbol.qualified_name.as_str()) {
+            continue;
+        }
+        insert_symbol.execute(params![
+            symbol.name,
+            symbol.qualified_name,
+            symbol.kind,
+            symbol.file_path,
+            symbol.start_line,
+            symbol.end_line,
+            symbol.signature,
+            symbol.return_type,
+            symbol.visibility,
+            symbol.is_async as i64,
+            symbol.is_static as i64,
+            Option::<i64>::None,
+            symbol.docstring,
+            0.0f64,
+            if symbol.supertypes.is_empty() {
+                None
+            } else {
+                Some(symbol.supertypes.join(","))
+            },
+        ])?;
+        let symbol_id = conn.last_insert_rowid();
+        inserted += 1;
+
+        for param in &symbol.parameters {
+            insert_param.execute(params![
+                symbol_id,
+                param.name,
+                param.type_,
+                param.position,
+                Option::<String>::None,
+            ])?;
+        }
+
+        let _ = insert_fts.execute(params![
+            symbol_id,
+            symbol.name,
+            symbol.qualified_name,
+            symbol.docstring.as_deref().unwrap_or(""),
+            symbol.signature.as_deref().unwrap_or(""),
+        ]);
+    }
+
+    Ok(inserted)
 }
 
-/// Full indexing pipeline exposed to Python.
+fn replace_external_deps_sql(
+    conn: &Connection,
+    file_path: &str,
+    imports: &[ExtractedImport],
+) -> crate::errors::BombeResult<()> {
+    conn.execute(
+        "DELETE FROM external_deps WHERE file_path = ?1;",
+        params![file_path],
+    )?;
+    let mut stmt = conn.prepare(
+        "INSERT INTO external_deps (file_path, import_statement, module_name, line_number) \
+         VALUES (?1, ?2, ?3, ?4);",
+    )?;
+    for imp in imports {
+        stmt.execute(params![
+            file_path,
+            imp.import_statement,
+            imp.module_name,
+            imp.line_number
+        ])?;
+    }
+    Ok(())
+}
+
+fn replace_file_edges_sql(
+    conn: &Connection,
+    file_path: &str,
+    edges: &[crate::indexer::callgraph::CallEdge],
+) -> crate::errors::BombeResult<i64> {
+    conn.execute("DELETE FROM edges WHERE file_path = ?1;", params![file_path])?;
+    let mut stmt = conn.prepare(
+        "INSERT OR IGNORE INTO edges ( \
+             source_id, target_id, source_type, target_type, relationship, \
+             file_path, line_number, confidence \
+         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
+    )?;
+    let mut inserted = 0i64;
+    for edge in edges {
+        inserted += stmt.execute(params![
+            edge.source_id,
+            edge.target_id,
+            edge.source_type,
+            edge.target_type,
+            edge.relationship,
+            edge.file_path,
+            edge.line_number,
+            edge.confidence,
+        ])? as i64;
+    }
+    Ok(inserted)
+}
+
+/// Reconstruct every symbol currently in the `symbols` table as a
+/// minimal `ExtractedSymbol` (just the fields `callgraph::build_call_edges`'s
+/// target-resolution actually reads: name/qualified_name/kind/file_path/
+/// supertypes), plus a `(qualified_name, file_path) -> id` lookup. Has to
+/// include *every* symbol, not just the ones from changed files, because a
+/// changed file can call into a symbol that lives in an untouched file.
+fn load_candidate_symbols(
+    conn: &Connection,
+) -> crate::errors::BombeResult<(Vec<ExtractedSymbol>, HashMap<(String, String), i64>)> {
+    let mut stmt =
+        conn.prepare("SELECT id, name, qualified_name, kind, file_path, supertypes FROM symbols;")?;
+    let rows: Vec<(i64, String, String, String, String, Option<String>)> = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut lookup = HashMap::with_capacity(rows.len());
+    let mut symbols = Vec::with_capacity(rows.len());
+    for (id, name, qualified_name, kind, file_path, supertypes) in rows {
+        lookup.insert((qualified_name.clone(), file_path.clone()), id);
+        symbols.push(ExtractedSymbol {
+            name,
+            qualified_name,
+            kind,
+            file_path,
+            start_line: 0,
+            end_line: 0,
+            signature: None,
+            return_type: None,
+            visibility: None,
+            is_async: false,
+            is_static: false,
+            docstring: None,
+            parameters: vec![],
+            supertypes: split_supertypes(supertypes),
+        });
+    }
+    Ok((symbols, lookup))
+}
+
+/// Split a `symbols.supertypes` column value (comma-joined, or absent) back
+/// into the list `ExtractedSymbol::supertypes` expects.
+fn split_supertypes(raw: Option<String>) -> Vec<String> {
+    match raw {
+        Some(value) if !value.is_empty() => value.split(',').map(str::to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// File paths (outside `changed_paths`) with a call/reference edge whose
+/// target symbol lives in one of `changed_paths` — those edges are about to
+/// go stale, since `replace_file_symbols_sql` deletes and reinserts each
+/// changed file's symbols (assigning fresh autoincrement ids) and `edges`
+/// only stores numeric ids, not the qualified name they resolved from. Must
+/// run against the pre-replace state (before `delete_file_graph_sql`/
+/// `replace_file_symbols_sql` touch `changed_paths`' old symbol rows).
+fn find_cross_file_referrers(
+    conn: &Connection,
+    changed_paths: &[String],
+) -> crate::errors::BombeResult<Vec<String>> {
+    if changed_paths.is_empty() {
+        return Ok(vec![]);
+    }
+    let in_list: String = changed_paths.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+    let sql = format!(
+        "SELECT DISTINCT e.file_path FROM edges e \
+         JOIN symbols s ON s.id = e.target_id AND e.target_type = 'symbol' \
+         WHERE s.file_path IN ({in_list}) AND e.file_path NOT IN ({in_list});"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = changed_paths
+        .iter()
+        .chain(changed_paths.iter())
+        .map(|p| p as &dyn rusqlite::types::ToSql)
+        .collect();
+    let rows = stmt
+        .query_map(params.as_slice(), |row| row.get::<_, String>(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// `path -> language` for `paths`, read from the `files` table — used to
+/// re-derive a referrer file's language without re-running `detect_language`,
+/// since the referrer itself didn't change and its `files` row is untouched.
+fn load_languages(
+    conn: &Connection,
+    paths: &[String],
+) -> crate::errors::BombeResult<HashMap<String, String>> {
+    if paths.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let in_list: String = paths.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+    let sql = format!("SELECT path, language FROM files WHERE path IN ({in_list});");
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> =
+        paths.iter().map(|p| p as &dyn rusqlite::types::ToSql).collect();
+    let rows = stmt
+        .query_map(params.as_slice(), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// Reconstructs `file_path`'s own symbols with real line ranges, unlike
+/// [`load_candidate_symbols`] (which zeroes them out for every file since it
+/// only needs name/qualified_name/kind for target resolution). A referrer
+/// file's edges are rebuilt via `caller_for_line`, which needs genuine
+/// `start_line`/`end_line` spans to attribute each call site to its caller.
+fn load_file_symbols_with_lines(
+    conn: &Connection,
+    file_path: &str,
+) -> crate::errors::BombeResult<Vec<ExtractedSymbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, qualified_name, kind, file_path, start_line, end_line, \
+             signature, return_type, visibility, is_async, is_static, docstring \
+         FROM symbols WHERE file_path = ?1;",
+    )?;
+    let rows = stmt
+        .query_map(params![file_path], |row| {
+            Ok(ExtractedSymbol {
+                name: row.get(0)?,
+                qualified_name: row.get(1)?,
+                kind: row.get(2)?,
+                file_path: row.get(3)?,
+                start_line: row.get(4)?,
+                end_line: row.get(5)?,
+                signature: row.get(6)?,
+                return_type: row.get(7)?,
+                visibility: row.get(8)?,
+                is_async: row.get::<_, i64>(9)? != 0,
+                is_static: row.get::<_, i64>(10)? != 0,
+                docstring: row.get(11)?,
+                parameters: vec![],
+                supertypes: Vec::new(),
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// Symbol ids whose PageRank should restart from the uniform baseline rather
+/// than warm-start from their stored score: every symbol belonging to one of
+/// `affected_paths` (changed files plus the cross-file referrers repaired
+/// above), plus anything directly connected to one by an edge — a changed
+/// file's edges can point at (or be pointed at by) a symbol in an untouched
+/// file, and that symbol's rank is now stale too.
+fn collect_changed_symbol_ids(
+    conn: &Connection,
+    affected_paths: &[String],
+) -> crate::errors::BombeResult<Vec<i64>> {
+    if affected_paths.is_empty() {
+        return Ok(vec![]);
+    }
+    let in_list: String = affected_paths.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+    let mut ids: HashSet<i64> = HashSet::new();
+
+    let symbol_sql = format!("SELECT id FROM symbols WHERE file_path IN ({in_list});");
+    let mut symbol_stmt = conn.prepare(&symbol_sql)?;
+    let symbol_params: Vec<&dyn rusqlite::types::ToSql> =
+        affected_paths.iter().map(|p| p as &dyn rusqlite::types::ToSql).collect();
+    for id in symbol_stmt
+        .query_map(symbol_params.as_slice(), |row| row.get::<_, i64>(0))?
+        .filter_map(|r| r.ok())
+    {
+        ids.insert(id);
+    }
+
+    let edge_sql = format!(
+        "SELECT source_id, target_id FROM edges \
+         WHERE source_type = 'symbol' AND target_type = 'symbol' AND file_path IN ({in_list});"
+    );
+    let mut edge_stmt = conn.prepare(&edge_sql)?;
+    let edge_params: Vec<&dyn rusqlite::types::ToSql> =
+        affected_paths.iter().map(|p| p as &dyn rusqlite::types::ToSql).collect();
+    for (source, target) in edge_stmt
+        .query_map(edge_params.as_slice(), |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+        .filter_map(|r| r.ok())
+    {
+        ids.insert(source);
+        ids.insert(target);
+    }
+
+    Ok(ids.into_iter().collect())
+}
+
+/// Wall-clock time spent in each phase of [`run_index_impl`], in
+/// milliseconds — surfaced as-is in `rust_full_index`'s return dict, and
+/// reused by `indexer::bench` to report a per-phase breakdown per worker
+/// count swept.
+pub struct PhaseTimings {
+    pub scan_ms: i64,
+    pub extract_ms: i64,
+    pub persist_ms: i64,
+    pub pagerank_ms: i64,
+}
+
+pub struct IndexRunResult {
+    pub stats: IndexStats,
+    pub timings: PhaseTimings,
+    pub files_deleted: i64,
+    pub ingest_summary: IngestSummary,
+}
+
+/// Incremental indexing pipeline: diff the freshly scanned
+/// `(file_path, content_hash)` state against what's already in the `files`
+/// table, run [`parallel_extract`] only on added/changed files, delete the
+/// graph for removed files, and replace symbols/edges/external deps for
+/// changed ones — all inside a single transaction, so a reindex costs
+/// proportional to the size of the change set rather than the whole repo.
+/// Python source files go through the same extract/persist/link steps as
+/// every other language — `extract_file_worker` extracts them natively via
+/// `symbols::python_symbols` rather than routing back to Python's `ast`.
+///
+/// Split out from the `#[pyfunction]` below the same way every query
+/// backend (`get_structure_impl`, `trace_data_flow_impl`, ...) separates
+/// its logic from the PyO3 boundary, so `indexer::bench` can drive it
+/// directly and time each phase without going through Python.
+pub fn run_index_impl(
+    repo_root: &str,
+    db_path: &str,
+    run_id: &str,
+    workers: i64,
+) -> crate::errors::BombeResult<IndexRunResult> {
+    run_index_impl_with_constraints(repo_root, db_path, run_id, workers, &IngestConstraints::default())
+}
+
+/// Same as [`run_index_impl`], but narrowed by `constraints` (see
+/// [`IngestConstraints`]) and reporting an [`IngestSummary`] of
+/// added/updated/skipped/removed file counts alongside the usual stats.
+pub fn run_index_impl_with_constraints(
+    repo_root: &str,
+    db_path: &str,
+    run_id: &str,
+    workers: i64,
+    constraints: &IngestConstraints,
+) -> crate::errors::BombeResult<IndexRunResult> {
+    let scan_started = Instant::now();
+    let repo = Path::new(repo_root);
+    let (files_seen, mut scanned) =
+        scan_repo_files(repo, None, None, Some(workers.max(1) as usize))?;
+    scanned.retain(|record| constraints.matches(record));
+    if constraints.extract_include_globs.is_some() || constraints.extract_exclude_globs.is_some() {
+        let include = constraints.extract_include_globs.as_deref().unwrap_or(&[]);
+        let exclude = constraints.extract_exclude_globs.as_deref().unwrap_or(&[]);
+        let selected: HashSet<String> = filter_files_for_extraction(&scanned, include, exclude)
+            .into_iter()
+            .collect();
+        scanned.retain(|record| selected.contains(&record.path));
+    }
+    let scan_ms = scan_started.elapsed().as_millis() as i64;
+
+    let db = Database::new(PathBuf::from(db_path), None, None)
+        .map_err(|e| crate::errors::BombeError::Index(e.to_string()))?;
+    let conn = db.connect_internal()?;
+
+    let prior_hashes = if constraints.force_full {
+        HashMap::new()
+    } else {
+        load_prior_file_hashes(&conn)?
+    };
+    let (changed, deleted, unchanged_count, added_count) =
+        classify_files_with_counts(&prior_hashes, &scanned);
+    let ingest_summary = IngestSummary {
+        added: added_count,
+        updated: changed.len() as i64 - added_count,
+        skipped: unchanged_count,
+        removed: deleted.len() as i64,
+    };
+    let changed_paths: Vec<String> = changed.iter().map(|r| r.path.clone()).collect();
+
+    // Must run against the pre-replace graph, before `changed_paths`' old
+    // symbol rows are deleted below.
+    let referrer_paths: Vec<String> = find_cross_file_referrers(&conn, &changed_paths)?
+        .into_iter()
+        .filter(|p| !changed_paths.contains(p) && !deleted.contains(p))
+        .collect();
+
+    let extract_started = Instant::now();
+    let extracted = parallel_extract(repo_root, &changed, workers.max(1) as usize);
+    let extract_ms = extract_started.elapsed().as_millis() as i64;
+
+    let persist_started = Instant::now();
+    let tx = conn.unchecked_transaction()?;
+
+    for file_path in &deleted {
+        delete_file_graph_sql(&tx, file_path)?;
+    }
+
+    let mut symbols_indexed = 0i64;
+    for (record, result) in changed.iter().zip(extracted.iter()) {
+        if result.error_stage.is_some() {
+            continue;
+        }
+        upsert_file_row_sql(&tx, record)?;
+        symbols_indexed += replace_file_symbols_sql(&tx, &record.path, &result.symbols)?;
+        replace_external_deps_sql(&tx, &record.path, &result.imports)?;
+    }
+
+    // Resolving call targets needs every symbol currently in the table,
+    // including ones from untouched files — so this has to run after the
+    // symbol replace step above, not before.
+    let (candidate_symbols, symbol_id_lookup) = load_candidate_symbols(&tx)?;
+
+    let mut edges_indexed = 0i64;
+    for (record, result) in changed.iter().zip(extracted.iter()) {
+        if result.error_stage.is_some() || result.symbols.is_empty() {
+            continue;
+        }
+        let edges = build_call_edges(
+            &result.source,
+            &record.path,
+            &record.language,
+            &result.symbols,
+            &candidate_symbols,
+            Some(&symbol_id_lookup),
+            None,
+            ExtractionMode::Ast,
+        );
+        edges_indexed += replace_file_edges_sql(&tx, &record.path, &edges)?;
+    }
+
+    // Repair cross-file edges pointing into the change set: `referrer_paths`
+    // didn't change themselves, but each has a call edge whose target symbol
+    // just got a new id from the replace step above, so their edges (only —
+    // not their symbols, which are still correct) need rebuilding against
+    // the refreshed `symbol_id_lookup`.
+    if !referrer_paths.is_empty() {
+        let languages = load_languages(&tx, &referrer_paths)?;
+        for referrer_path in &referrer_paths {
+            let language = match languages.get(referrer_path) {
+                Some(l) => l,
+                None => continue,
+            };
+            let absolute = Path::new(repo_root).join(referrer_path);
+            let source = match std::fs::read_to_string(&absolute) {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+            let file_symbols = load_file_symbols_with_lines(&tx, referrer_path)?;
+            let edges = build_call_edges(
+                &source,
+                referrer_path,
+                language,
+                &file_symbols,
+                &candidate_symbols,
+                Some(&symbol_id_lookup),
+                None,
+                ExtractionMode::Ast,
+            );
+            edges_indexed += replace_file_edges_sql(&tx, referrer_path, &edges)?;
+        }
+    }
+
+    // Virtual-dispatch expansion needs the whole graph (a new `IMPLEMENTS`
+    // edge anywhere can add a dispatch target for an interface call site in
+    // an untouched file), so it reruns over everything rather than just the
+    // change set — same reasoning as the pagerank recompute below.
+    let total_symbols: i64 = tx
+        .query_row("SELECT COUNT(*) FROM symbols;", [], |row| row.get(0))
+        .unwrap_or(0);
+    let fanout_cap = crate::query::guards::adaptive_graph_cap(total_symbols, 64, Some(8));
+    edges_indexed += crate::indexer::callgraph::expand_virtual_dispatch_edges(&tx, fanout_cap)?;
+
+    tx.commit()?;
+    let persist_ms = persist_started.elapsed().as_millis() as i64;
+
+    // Nothing in the symbol/edge graph moved (no changed, deleted, or
+    // referrer files), so pagerank is still exactly what it was — skip the
+    // recompute entirely rather than paying a power iteration for a no-op.
+    let pagerank_started = Instant::now();
+    let pagerank_ms = if changed.is_empty() && deleted.is_empty() && referrer_paths.is_empty() {
+        0
+    } else {
+        // On a fresh connection, same as the full recompute this replaces:
+        // pagerank's graph load reads back everything, not just the change
+        // set, the same way `load_candidate_symbols` above has to. Warm-start
+        // from each symbol's previously stored score instead of a uniform
+        // vector — `affected_paths` is the part of the graph this run
+        // actually touched, so it's the only part that needs to restart cold.
+        let pagerank_conn = db.connect_internal()?;
+        let affected_paths: Vec<String> = changed_paths
+            .iter()
+            .cloned()
+            .chain(referrer_paths.iter().cloned())
+            .collect();
+        let changed_ids = collect_changed_symbol_ids(&pagerank_conn, &affected_paths)?;
+        crate::indexer::pagerank::recompute_pagerank_incremental_impl(
+            &pagerank_conn,
+            &changed_ids,
+            0.85,
+            1e-6,
+            &crate::indexer::pagerank::PagerankWeights::default(),
+        )?;
+        pagerank_conn.execute_batch("COMMIT;").ok();
+        pagerank_started.elapsed().as_millis() as i64
+    };
+
+    crate::telemetry::metrics::record_indexed(symbols_indexed.max(0) as u64, edges_indexed.max(0) as u64);
+
+    Ok(IndexRunResult {
+        stats: IndexStats {
+            files_seen,
+            files_indexed: changed.len() as i64,
+            symbols_indexed,
+            edges_indexed,
+            elapsed_ms: scan_ms + extract_ms + persist_ms + pagerank_ms,
+            run_id: run_id.to_string(),
+        },
+        timings: PhaseTimings {
+            scan_ms,
+            extract_ms,
+            persist_ms,
+            pagerank_ms,
+        },
+        files_deleted: deleted.len() as i64,
+        ingest_summary,
+    })
+}
+
+/// Incremental indexing pipeline exposed to Python. See [`run_index_impl`]
+/// for the actual logic.
 #[pyfunction]
-#[pyo3(signature = (repo_root, _db_path, workers=4))]
+#[pyo3(signature = (repo_root, db_path, run_id, workers=4))]
 pub fn rust_full_index(
     py: Python<'_>,
     repo_root: &str,
-    _db_path: &str,
+    db_path: &str,
+    run_id: &str,
     workers: i64,
 ) -> PyResult<PyObject> {
-    let started = Instant::now();
-    let repo = Path::new(repo_root);
-    let (files_seen, file_records) = scan_repo_files(repo, None, None);
-
-    let elapsed_ms = started.elapsed().as_millis() as i64;
+    let run = run_index_impl(repo_root, db_path, run_id, workers)?;
 
     let result = pyo3::types::PyDict::new(py);
-    result.set_item("files_seen", files_seen)?;
-    result.set_item("files_indexed", file_records.len() as i64)?;
-    result.set_item("elapsed_ms", elapsed_ms)?;
+    result.set_item("files_seen", run.stats.files_seen)?;
+    result.set_item("files_indexed", run.stats.files_indexed)?;
+    result.set_item("files_deleted", run.files_deleted)?;
+    result.set_item("symbols_indexed", run.stats.symbols_indexed)?;
+    result.set_item("edges_indexed", run.stats.edges_indexed)?;
+    result.set_item("elapsed_ms", run.stats.elapsed_ms)?;
+    result.set_item("run_id", run.stats.run_id)?;
     result.set_item("workers", workers)?;
+    result.set_item("scan_ms", run.timings.scan_ms)?;
+    result.set_item("extract_ms", run.timings.extract_ms)?;
+    result.set_item("persist_ms", run.timings.persist_ms)?;
+    result.set_item("pagerank_ms", run.timings.pagerank_ms)?;
+    result.set_item("files_added", run.ingest_summary.added)?;
+    result.set_item("files_updated", run.ingest_summary.updated)?;
+    result.set_item("files_skipped", run.ingest_summary.skipped)?;
+    result.set_item("files_removed", run.ingest_summary.removed)?;
 
     Ok(result.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str) -> FileRecord {
+        FileRecord {
+            path: path.to_string(),
+            language: "python".to_string(),
+            content_hash: "hash".to_string(),
+            size_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn include_empty_matches_everything() {
+        let files = vec![record("src/main.py"), record("vendor/lib.py")];
+        let selected = filter_files_for_extraction(&files, &[], &[]);
+        assert_eq!(selected, vec!["src/main.py", "vendor/lib.py"]);
+    }
+
+    #[test]
+    fn include_glob_filters_by_extension() {
+        let files = vec![record("src/main.py"), record("src/main.go")];
+        let selected = filter_files_for_extraction(&files, &["*.py".to_string()], &[]);
+        assert_eq!(selected, vec!["src/main.py"]);
+    }
+
+    #[test]
+    fn exclude_takes_precedence_over_include() {
+        let files = vec![record("src/main.py"), record("vendor/dep.py")];
+        let selected = filter_files_for_extraction(
+            &files,
+            &["**/*.py".to_string()],
+            &["vendor/**".to_string()],
+        );
+        assert_eq!(selected, vec!["src/main.py"]);
+    }
+
+    #[test]
+    fn trailing_slash_excludes_whole_directory_not_a_same_named_file() {
+        let files = vec![
+            record("vendor/dep.py"),
+            record("vendor/nested/dep.py"),
+            record("vendor"),
+        ];
+        let selected = filter_files_for_extraction(&files, &[], &["vendor/".to_string()]);
+        assert_eq!(selected, vec!["vendor"]);
+    }
+
+    #[test]
+    fn leading_slash_anchors_to_repo_root() {
+        let files = vec![record("build/out.py"), record("src/build/out.py")];
+        let selected = filter_files_for_extraction(&files, &[], &["/build/".to_string()]);
+        assert_eq!(selected, vec!["src/build/out.py"]);
+    }
+
+    #[test]
+    fn unanchored_pattern_matches_at_any_depth() {
+        let files = vec![record("out.log"), record("deep/nested/out.log")];
+        let selected = filter_files_for_extraction(&files, &[], &["*.log".to_string()]);
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn bracket_negation_is_rewritten_to_regex_negated_class() {
+        let files = vec![record("a.py"), record("b.py")];
+        let selected = filter_files_for_extraction(&files, &["[!a].py".to_string()], &[]);
+        assert_eq!(selected, vec!["b.py"]);
+    }
+}
@@ -3,6 +3,8 @@
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use pyo3::prelude::*;
+
 /// Load receiver type hints from sidecar JSON files and environment.
 pub fn load_receiver_type_hints(
     repo_root: &Path,
@@ -25,25 +27,16 @@ pub fn load_receiver_type_hints(
         let global_path = global_path.trim().to_string();
         if !global_path.is_empty() {
             let expanded = Path::new(&global_path);
-            if let Some(payload) = load_json(expanded) {
-                if let Some(files) = payload.get("files").and_then(|v| v.as_object()) {
-                    let candidates = [
-                        normalized.clone(),
-                        relative_path.to_string(),
-                        relative_path
-                            .replace('\\', "/")
-                            .trim_start_matches('/')
-                            .to_string(),
-                    ];
-                    for candidate in &candidates {
-                        if let Some(file_payload) = files.get(candidate) {
-                            if let Some(obj) = file_payload.as_object() {
-                                let val = serde_json::Value::Object(obj.clone());
-                                merge_hint_maps(&mut hints, &parse_hint_payload(&val));
-                            }
-                        }
-                    }
-                }
+            let candidates = [
+                normalized.clone(),
+                relative_path.to_string(),
+                relative_path
+                    .replace('\\', "/")
+                    .trim_start_matches('/')
+                    .to_string(),
+            ];
+            if let Some(global_hints) = load_global_hints_for(repo_root, expanded, &candidates) {
+                merge_hint_maps(&mut hints, &global_hints);
             }
         }
     }
@@ -51,6 +44,318 @@ pub fn load_receiver_type_hints(
     hints
 }
 
+/// Derives a version token for `load_receiver_type_hints(repo_root,
+/// relative_path)`'s current inputs: the sidecar file's mtime/size, the
+/// `BOMBE_SEMANTIC_HINTS_FILE` value itself (so pointing at a different
+/// file also invalidates), and that file's mtime/size. Callers pass the
+/// result as `QueryPlanner`'s `version_token` so a cached call-resolution
+/// result automatically misses the moment any contributing hint source
+/// changes, rather than waiting out the TTL. Missing sources still
+/// contribute a stable "absent" fingerprint rather than being left out, so
+/// a sidecar file appearing or disappearing is itself a token change. Uses
+/// `|` rather than `:` as the field separator so the token never collides
+/// with `QueryPlanner::_cache_key`'s own `:`-delimited format.
+#[pyfunction]
+pub fn compute_hints_version_token(repo_root: &str, relative_path: &str) -> String {
+    let repo_root = Path::new(repo_root);
+    let normalized = normalize_relative_path(relative_path);
+    let sidecar = repo_root
+        .join(".bombe")
+        .join("semantic")
+        .join(format!("{normalized}.hints.json"));
+    let sidecar_fingerprint = file_fingerprint(&sidecar);
+
+    let global_env = std::env::var("BOMBE_SEMANTIC_HINTS_FILE").unwrap_or_default();
+    let global_env = global_env.trim();
+    let global_fingerprint = if global_env.is_empty() {
+        "absent".to_string()
+    } else {
+        file_fingerprint(Path::new(global_env))
+    };
+
+    format!("sidecar={sidecar_fingerprint}|global_path={global_env}|global={global_fingerprint}")
+}
+
+/// `"absent"` if `path` doesn't exist or its metadata can't be read,
+/// otherwise `"{mtime_secs}-{size}"`.
+fn file_fingerprint(path: &Path) -> String {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return "absent".to_string();
+    };
+    let mtime_secs = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    format!("{}-{}", mtime_secs, metadata.len())
+}
+
+/// Looks up `candidates` in the global hints file, preferring the
+/// memory-mapped, offset-indexed path (see [`global_offset_index::lookup`])
+/// so a monorepo-wide hints file doesn't need a full parse on every call;
+/// falls back to the plain full-file parse if mmap-ing or index lookup
+/// fails for any reason (missing file, unreadable, stale/corrupt index).
+fn load_global_hints_for(
+    repo_root: &Path,
+    global_path: &Path,
+    candidates: &[String],
+) -> Option<HashMap<(i64, String), HashSet<String>>> {
+    if let Some(hints) =
+        global_offset_index::lookup(repo_root, global_path, candidates)
+    {
+        return Some(hints);
+    }
+
+    let payload = load_json(global_path)?;
+    let files = payload.get("files").and_then(|v| v.as_object())?;
+    let mut hints: HashMap<(i64, String), HashSet<String>> = HashMap::new();
+    for candidate in candidates {
+        if let Some(file_payload) = files.get(candidate) {
+            if let Some(obj) = file_payload.as_object() {
+                let val = serde_json::Value::Object(obj.clone());
+                merge_hint_maps(&mut hints, &parse_hint_payload(&val));
+            }
+        }
+    }
+    Some(hints)
+}
+
+/// Memory-mapped, lazily-indexed access to `BOMBE_SEMANTIC_HINTS_FILE`.
+///
+/// `load_receiver_type_hints` previously read and fully deserialized the
+/// whole hints file just to pull one `files` entry out of it, which is
+/// wasteful once that file reaches the hundreds-of-MB range a
+/// monorepo-wide hints dump can hit. This module `mmap`s the file instead
+/// and builds (once, then caches under `repo_root/.bombe/semantic/`) a
+/// small index mapping each `files` key to its byte range, so a lookup
+/// only has to deserialize the slice it actually needs.
+mod global_offset_index {
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    use memmap2::Mmap;
+    use sha2::{Digest, Sha256};
+
+    use super::{merge_hint_maps, parse_hint_payload};
+
+    struct OffsetIndex {
+        mtime_secs: i64,
+        size: u64,
+        // file key -> (start, end) byte range of its JSON object, end exclusive
+        offsets: HashMap<String, (usize, usize)>,
+    }
+
+    impl OffsetIndex {
+        fn to_json(&self) -> serde_json::Value {
+            let offsets: serde_json::Map<String, serde_json::Value> = self
+                .offsets
+                .iter()
+                .map(|(key, (start, end))| (key.clone(), serde_json::json!([start, end])))
+                .collect();
+            serde_json::json!({
+                "mtime_secs": self.mtime_secs,
+                "size": self.size,
+                "offsets": offsets,
+            })
+        }
+
+        fn from_json(value: &serde_json::Value) -> Option<Self> {
+            let mtime_secs = value.get("mtime_secs")?.as_i64()?;
+            let size = value.get("size")?.as_u64()?;
+            let offsets = value
+                .get("offsets")?
+                .as_object()?
+                .iter()
+                .filter_map(|(key, range)| {
+                    let range = range.as_array()?;
+                    let start = range.first()?.as_u64()? as usize;
+                    let end = range.get(1)?.as_u64()? as usize;
+                    Some((key.clone(), (start, end)))
+                })
+                .collect();
+            Some(Self {
+                mtime_secs,
+                size,
+                offsets,
+            })
+        }
+    }
+
+    /// Looks up `candidates` in `global_path`'s cached offset index,
+    /// building (or rebuilding, if the file's mtime/size moved on) the
+    /// index first. Returns `None` on any I/O or parse failure so the
+    /// caller can fall back to the plain full-file parse.
+    pub(super) fn lookup(
+        repo_root: &Path,
+        global_path: &Path,
+        candidates: &[String],
+    ) -> Option<super::HashMap<(i64, String), super::HashSet<String>>> {
+        let metadata = std::fs::metadata(global_path).ok()?;
+        let size = metadata.len();
+        let mtime_secs = metadata
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs() as i64;
+
+        let file = std::fs::File::open(global_path).ok()?;
+        let mmap = unsafe { Mmap::map(&file).ok()? };
+
+        let cache_path = index_cache_path(repo_root, global_path);
+        let index = load_cached_index(&cache_path, mtime_secs, size)
+            .or_else(|| build_and_cache_index(&mmap, &cache_path, mtime_secs, size));
+        let index = index?;
+
+        let mut hints: HashMap<(i64, String), super::HashSet<String>> = HashMap::new();
+        for candidate in candidates {
+            let Some(&(start, end)) = index.offsets.get(candidate) else {
+                continue;
+            };
+            let slice = mmap.get(start..end)?;
+            let value: serde_json::Value = serde_json::from_slice(slice).ok()?;
+            merge_hint_maps(&mut hints, &parse_hint_payload(&value));
+        }
+        Some(hints)
+    }
+
+    fn index_cache_path(repo_root: &Path, global_path: &Path) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(global_path.to_string_lossy().as_bytes());
+        let digest = format!("{:x}", hasher.finalize());
+        repo_root
+            .join(".bombe")
+            .join("semantic")
+            .join(format!("global-hints-{}.offsets.json", &digest[..16]))
+    }
+
+    fn load_cached_index(cache_path: &Path, mtime_secs: i64, size: u64) -> Option<OffsetIndex> {
+        let content = std::fs::read_to_string(cache_path).ok()?;
+        let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let index = OffsetIndex::from_json(&value)?;
+        if index.mtime_secs == mtime_secs && index.size == size {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    /// Scans the global hints file's top-level `files` object and records
+    /// the byte range of each entry's JSON value, relying only on brace
+    /// depth tracking (the `files` values are themselves JSON objects, so
+    /// no quoting/escaping ambiguity arises once inside a string literal is
+    /// handled).
+    fn build_and_cache_index(
+        mmap: &Mmap,
+        cache_path: &Path,
+        mtime_secs: i64,
+        size: u64,
+    ) -> Option<OffsetIndex> {
+        let offsets = scan_files_offsets(mmap)?;
+        let index = OffsetIndex {
+            mtime_secs,
+            size,
+            offsets,
+        };
+        if let Some(parent) = cache_path.parent() {
+            std::fs::create_dir_all(parent).ok()?;
+        }
+        let _ = std::fs::write(cache_path, index.to_json().to_string());
+        Some(index)
+    }
+
+    fn scan_files_offsets(bytes: &[u8]) -> Option<HashMap<String, (usize, usize)>> {
+        let files_key_pos = find_subslice(bytes, b"\"files\"")?;
+        let object_start = find_byte(bytes, files_key_pos, b'{')?;
+
+        let mut offsets = HashMap::new();
+        let mut cursor = object_start + 1;
+        let end_of_files_object = find_matching_brace(bytes, object_start)?;
+
+        while cursor < end_of_files_object {
+            // Find the next quoted key within the `files` object.
+            let Some((key, key_end)) = read_next_string(bytes, cursor, end_of_files_object) else {
+                break;
+            };
+            let Some(colon) = find_byte(bytes, key_end, b':') else {
+                break;
+            };
+            let Some(value_start) = find_byte(bytes, colon + 1, b'{') else {
+                break;
+            };
+            let Some(value_end) = find_matching_brace(bytes, value_start) else {
+                break;
+            };
+            offsets.insert(key, (value_start, value_end + 1));
+            cursor = value_end + 1;
+        }
+
+        Some(offsets)
+    }
+
+    fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    fn find_byte(bytes: &[u8], from: usize, target: u8) -> Option<usize> {
+        bytes[from..].iter().position(|&b| b == target).map(|p| p + from)
+    }
+
+    /// Reads the next `"..."` string literal starting at or after `from`,
+    /// honoring `\"` escapes, and returns `(contents, index_just_past_the_closing_quote)`.
+    fn read_next_string(bytes: &[u8], from: usize, limit: usize) -> Option<(String, usize)> {
+        let start_quote = find_byte(bytes, from, b'"')?;
+        if start_quote >= limit {
+            return None;
+        }
+        let mut i = start_quote + 1;
+        let mut out = Vec::new();
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' if i + 1 < bytes.len() => {
+                    out.push(bytes[i + 1]);
+                    i += 2;
+                }
+                b'"' => {
+                    return String::from_utf8(out).ok().map(|s| (s, i + 1));
+                }
+                b => {
+                    out.push(b);
+                    i += 1;
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds the index of the `}` matching the `{` at `open`, tracking
+    /// string literals so braces inside quoted values don't confuse depth.
+    fn find_matching_brace(bytes: &[u8], open: usize) -> Option<usize> {
+        let mut depth = 0i32;
+        let mut i = open;
+        let mut in_string = false;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' if in_string && i + 1 < bytes.len() => i += 1,
+                b'"' => in_string = !in_string,
+                b'{' if !in_string => depth += 1,
+                b'}' if !in_string => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        None
+    }
+}
+
 fn normalize_relative_path(path: &str) -> String {
     path.trim().trim_start_matches('/').replace('\\', "/")
 }
@@ -0,0 +1,159 @@
+//! Compute line-independent structural identity for symbols.
+//!
+//! `SymbolKey`'s default identity bakes in `start_line`/`end_line`, so a
+//! single line inserted earlier in a file makes every later symbol look
+//! deleted-and-re-added on the next delta, even though none of their code
+//! changed. This module computes a `structural_id` — `kind` + the symbol's
+//! nesting path (ordinal position among same-kind siblings under each
+//! `parent_symbol_id`, root to self) + signature hash — that stays stable
+//! across such shifts, for use via [`crate::models::SymbolKey::structural`]
+//! and [`crate::models::match_symbols`].
+
+use std::collections::{HashMap, HashSet};
+
+use pyo3::prelude::*;
+
+use crate::models::{_signature_hash, SymbolRecord};
+
+/// Rank of `(id, symbol)` among same-kind siblings sharing its
+/// `parent_symbol_id` and file — ties break on `(start_line, end_line, id)`
+/// only to make the rank deterministic, not as part of the identity itself.
+fn sibling_rank(id: i64, symbol: &SymbolRecord, by_id: &HashMap<i64, &SymbolRecord>) -> usize {
+    let mut siblings: Vec<(i64, i64, i64)> = by_id
+        .iter()
+        .filter(|(_, other)| {
+            other.parent_symbol_id == symbol.parent_symbol_id
+                && other.file_path == symbol.file_path
+                && other.kind == symbol.kind
+        })
+        .map(|(&other_id, other)| (other.start_line, other.end_line, other_id))
+        .collect();
+    siblings.sort();
+    siblings
+        .iter()
+        .position(|&(_, _, other_id)| other_id == id)
+        .unwrap_or(0)
+}
+
+/// Ordinal sibling-rank path from the root ancestor down to `(id, symbol)`
+/// itself, e.g. `[0, 2]` for the third same-kind sibling nested inside the
+/// first same-kind top-level symbol.
+fn nesting_path(id: i64, symbol: &SymbolRecord, by_id: &HashMap<i64, &SymbolRecord>) -> Vec<usize> {
+    let mut chain = vec![id];
+    let mut cursor = symbol.parent_symbol_id;
+    let mut seen = HashSet::new();
+    while let Some(parent_id) = cursor {
+        if !seen.insert(parent_id) {
+            break; // defend against a cyclic parent chain
+        }
+        chain.push(parent_id);
+        cursor = by_id.get(&parent_id).and_then(|p| p.parent_symbol_id);
+    }
+    chain.reverse(); // root-most ancestor first, self last
+
+    chain
+        .iter()
+        .map(|&cid| {
+            let csym = if cid == id {
+                symbol
+            } else {
+                by_id.get(&cid).copied().unwrap_or(symbol)
+            };
+            sibling_rank(cid, csym, by_id)
+        })
+        .collect()
+}
+
+/// Compose `kind` + nesting path + signature hash into a single structural
+/// id string.
+fn compose_structural_id(nesting_path: &[usize], kind: &str, signature_hash: &str) -> String {
+    let path = nesting_path
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(".");
+    format!("{kind}#{path}#{signature_hash}")
+}
+
+/// Return `symbols` with [`SymbolRecord::structural_id`] populated from each
+/// symbol's nesting path, `kind`, and signature hash.
+///
+/// `row_id` is each symbol's database row id (the value other symbols'
+/// `parent_symbol_id` points at) — needed to walk ancestry, mirroring
+/// [`crate::indexer::dataset_export::export_method_samples`]'s signature for
+/// the same reason.
+#[pyfunction]
+pub fn assign_structural_ids(symbols: Vec<(i64, SymbolRecord)>) -> Vec<SymbolRecord> {
+    let by_id: HashMap<i64, &SymbolRecord> = symbols.iter().map(|(id, s)| (*id, s)).collect();
+    let computed: Vec<String> = symbols
+        .iter()
+        .map(|(id, symbol)| {
+            let path = nesting_path(*id, symbol, &by_id);
+            let signature_hash = _signature_hash(symbol.signature.clone());
+            compose_structural_id(&path, &symbol.kind, &signature_hash)
+        })
+        .collect();
+
+    symbols
+        .into_iter()
+        .zip(computed)
+        .map(|((_, mut symbol), structural_id)| {
+            symbol.structural_id = Some(structural_id);
+            symbol
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(kind: &str, file_path: &str, parent_symbol_id: Option<i64>) -> SymbolRecord {
+        SymbolRecord {
+            name: "x".to_string(),
+            qualified_name: "x".to_string(),
+            kind: kind.to_string(),
+            file_path: file_path.to_string(),
+            start_line: 1,
+            end_line: 2,
+            signature: Some("fn x()".to_string()),
+            return_type: None,
+            visibility: None,
+            is_async: false,
+            is_static: false,
+            parent_symbol_id,
+            docstring: None,
+            body: None,
+            structural_id: None,
+            pagerank_score: 0.0,
+            parameters: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_structural_id_excludes_line_numbers() {
+        let mut a = symbol("function", "a.py", None);
+        a.start_line = 1;
+        a.end_line = 2;
+        let mut b = a.clone();
+        b.start_line = 100;
+        b.end_line = 101;
+
+        let assigned_a = assign_structural_ids(vec![(1, a)]);
+        let assigned_b = assign_structural_ids(vec![(1, b)]);
+
+        assert_eq!(assigned_a[0].structural_id, assigned_b[0].structural_id);
+    }
+
+    #[test]
+    fn test_structural_id_disambiguates_siblings() {
+        let sibling_one = symbol("method", "a.py", Some(1));
+        let mut sibling_two = symbol("method", "a.py", Some(1));
+        sibling_two.start_line = 10;
+        sibling_two.end_line = 11;
+
+        let assigned = assign_structural_ids(vec![(2, sibling_one), (3, sibling_two)]);
+
+        assert_ne!(assigned[0].structural_id, assigned[1].structural_id);
+    }
+}
@@ -0,0 +1,990 @@
+//! Tree-sitter-backed symbol extraction for Java, TypeScript and Go.
+//!
+//! [`symbols`]'s line-scanning regexes assume a declaration fits on one
+//! source line and track nesting by counting `{`/`}` characters across the
+//! whole file — both assumptions break on a method signature whose
+//! parameters wrap, a generic bound split across lines, or a brace that
+//! shows up inside a string or comment rather than real code. This module
+//! walks the tree-sitter CST instead, so a declaration's extent comes from
+//! its own AST node (correct regardless of how many lines it spans) and
+//! nesting comes from real parent/child structure (immune to brace-like
+//! text in literals). It still leans on [`symbols`]'s regexes for the
+//! fiddly text-level parsing (modifiers, parameter lists, return types,
+//! supertypes) — those are unchanged and already exercised by its test
+//! suite — just applied to a node's own source text instead of a raw line.
+//!
+//! Each language is driven through [`SymbolExtractor`] so a new language
+//! only needs a grammar registered in [`parser`] and an impl here, not a
+//! change to the dispatch in [`extract_via_tree_sitter`]. When no grammar
+//! is loaded for a language (or none is registered here, e.g. Python or
+//! Rust), [`extract_via_tree_sitter`] returns `None` and
+//! [`symbols::extract_symbols`] falls back to the regex scanner.
+//!
+//! [`symbols`]: crate::indexer::symbols
+//! [`parser`]: crate::indexer::parser
+//! [`symbols::extract_symbols`]: crate::indexer::symbols::extract_symbols
+
+use tree_sitter::{Node, Tree};
+
+use super::parser::parse_source_native;
+use super::symbols::{
+    build_parameters, go_visibility, normalize_type_name, parse_java_supertypes,
+    parse_ts_supertypes, strip_doc_comment_line, to_module_name, ExtractedSymbol, GO_CONST_RE,
+    GO_FUNCTION_RE, GO_METHOD_RE, GO_PACKAGE_RE, GO_TYPE_RE, JAVA_CLASS_RE, JAVA_METHOD_RE,
+    JAVA_PACKAGE_RE, TS_ARROW_RE, TS_CLASS_RE, TS_CONST_RE, TS_FUNCTION_RE, TS_METHOD_RE,
+};
+
+/// Extracts [`ExtractedSymbol`]s from an already-parsed tree for one
+/// language. Implementations live alongside their per-language walkers
+/// below; [`extractor_for`] is the registry new languages plug into.
+pub(crate) trait SymbolExtractor {
+    fn extract(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<ExtractedSymbol>;
+}
+
+struct JavaExtractor;
+
+impl SymbolExtractor for JavaExtractor {
+    fn extract(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<ExtractedSymbol> {
+        java_ast_symbols(tree, source, file_path)
+    }
+}
+
+struct TypeScriptExtractor;
+
+impl SymbolExtractor for TypeScriptExtractor {
+    fn extract(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<ExtractedSymbol> {
+        typescript_ast_symbols(tree, source, file_path)
+    }
+}
+
+struct GoExtractor;
+
+impl SymbolExtractor for GoExtractor {
+    fn extract(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<ExtractedSymbol> {
+        go_ast_symbols(tree, source, file_path)
+    }
+}
+
+pub(crate) fn extractor_for(language: &str) -> Option<&'static dyn SymbolExtractor> {
+    match language {
+        "java" => Some(&JavaExtractor),
+        "typescript" => Some(&TypeScriptExtractor),
+        "go" => Some(&GoExtractor),
+        _ => None,
+    }
+}
+
+/// Extract symbols for `language` via tree-sitter, if a grammar is loaded
+/// for it and a [`SymbolExtractor`] is registered. Returns `None` (not an
+/// empty `Vec`) when either is missing, so [`symbols::extract_symbols`] can
+/// tell "no AST path available" apart from "AST path found nothing" and
+/// fall back to the regex scanner only in the former case.
+///
+/// [`symbols::extract_symbols`]: crate::indexer::symbols::extract_symbols
+pub fn extract_via_tree_sitter(
+    source: &str,
+    file_path: &str,
+    language: &str,
+) -> Option<Vec<ExtractedSymbol>> {
+    let extractor = extractor_for(language)?;
+    let tree = parse_source_native(source, language)?;
+    Some(extractor.extract(&tree, source, file_path))
+}
+
+// ---------------------------------------------------------------------------
+// Shared helpers
+// ---------------------------------------------------------------------------
+
+fn node_text<'a>(node: Node, source: &'a [u8]) -> Option<&'a str> {
+    node.utf8_text(source).ok()
+}
+
+/// Collapse a (possibly multi-line) node's text down to single spaces, so
+/// the existing single-line-oriented regexes can match it unchanged.
+fn flatten(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A node's own source text, truncated right after the opening brace of its
+/// `body` field when it has one. Java/TS/Go method and class/function nodes
+/// all expose their block as a `body` field, so this keeps exactly the
+/// "declaration header" text the regexes expect — up to and including the
+/// `{` several of them require — without the arbitrary code, strings and
+/// braces inside the body confusing the match. Nodes with no `body` field
+/// (an abstract method, an interface method signature, a Go type/const
+/// declaration) fall back to their full text; see callers for how that
+/// plays with each regex.
+fn header_with_brace(node: Node, source: &[u8]) -> String {
+    match node.child_by_field_name("body") {
+        Some(body) => {
+            let end = (body.start_byte() + 1).min(source.len());
+            String::from_utf8_lossy(&source[node.start_byte()..end]).into_owned()
+        }
+        None => String::from_utf8_lossy(&source[node.start_byte()..node.end_byte()]).into_owned(),
+    }
+}
+
+fn start_line(node: Node) -> i64 {
+    (node.start_position().row + 1) as i64
+}
+
+fn end_line(node: Node) -> i64 {
+    (node.end_position().row + 1) as i64
+}
+
+/// A JSDoc'd `export function foo() {}` / `export const x = ...` wraps the
+/// declaration in an `export_statement` node, so the doc comment sits above
+/// the `export_statement`, not above the declaration itself — climb up to
+/// it before looking for a preceding comment.
+fn doc_comment_anchor(node: Node) -> Node {
+    match node.parent() {
+        Some(parent) if parent.kind() == "export_statement" => parent,
+        _ => node,
+    }
+}
+
+/// The Javadoc/JSDoc `/** ... */` block comment immediately above `node`
+/// (on the line directly preceding it, no blank line between), if any —
+/// its interior lines with the leading `*`/whitespace stripped and joined
+/// with `\n`. Comments are ordinary siblings in a tree-sitter CST, so this
+/// is just "is the previous sibling a doc-shaped comment that touches this
+/// node". Matches on `kind().contains("comment")` rather than an exact
+/// node-kind string since Java/TypeScript grammars name the comment node
+/// differently (`block_comment` vs `comment`).
+fn preceding_block_doc_comment(node: Node, source: &[u8]) -> Option<String> {
+    let anchor = doc_comment_anchor(node);
+    let prev = anchor.prev_sibling()?;
+    if !prev.kind().contains("comment") {
+        return None;
+    }
+    if anchor
+        .start_position()
+        .row
+        .saturating_sub(prev.end_position().row)
+        > 1
+    {
+        return None;
+    }
+    let text = node_text(prev, source)?;
+    let inner = text.strip_prefix("/**")?.strip_suffix("*/").unwrap_or(text);
+    let body = inner
+        .lines()
+        .map(strip_doc_comment_line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = body.trim();
+    if body.is_empty() {
+        None
+    } else {
+        Some(body.to_string())
+    }
+}
+
+/// The run of consecutive `// ...` line comments immediately above `node`
+/// (no blank line breaking the run), if any, joined with `\n` in source
+/// order — the Go doc-comment convention. Walks backward through adjacent
+/// comment siblings the same way the regex scanner's rolling buffer
+/// accumulates lines.
+fn preceding_line_doc_comment(node: Node, source: &[u8]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut current = node.prev_sibling();
+    let mut expected_row = node.start_position().row;
+    while let Some(sibling) = current {
+        if !sibling.kind().contains("comment") {
+            break;
+        }
+        let Some(text) = node_text(sibling, source) else {
+            break;
+        };
+        if !text.starts_with("//") {
+            break;
+        }
+        if expected_row.saturating_sub(sibling.end_position().row) > 1 {
+            break;
+        }
+        lines.push(text.strip_prefix("//").unwrap_or(text).trim().to_string());
+        expected_row = sibling.start_position().row;
+        current = sibling.prev_sibling();
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+// ---------------------------------------------------------------------------
+// Java
+// ---------------------------------------------------------------------------
+
+fn java_package_name(root: Node, source: &[u8]) -> String {
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        if child.kind() == "package_declaration" {
+            if let Some(text) = node_text(child, source) {
+                if let Some(caps) = JAVA_PACKAGE_RE.captures(text) {
+                    return caps[1].to_string();
+                }
+            }
+        }
+    }
+    String::new()
+}
+
+fn java_ast_symbols(tree: &Tree, source: &str, file_path: &str) -> Vec<ExtractedSymbol> {
+    let bytes = source.as_bytes();
+    let root = tree.root_node();
+    let package_name = java_package_name(root, bytes);
+    let mut symbols = Vec::new();
+    let mut class_stack: Vec<String> = Vec::new();
+    walk_java(
+        root,
+        bytes,
+        file_path,
+        &package_name,
+        &mut class_stack,
+        &mut symbols,
+    );
+    symbols
+}
+
+fn walk_java(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+    class_stack: &mut Vec<String>,
+    symbols: &mut Vec<ExtractedSymbol>,
+) {
+    match node.kind() {
+        "class_declaration" | "interface_declaration" | "enum_declaration" => {
+            if let Some(sym) = java_class_symbol(node, source, file_path, package, class_stack) {
+                class_stack.push(sym.name.clone());
+                symbols.push(sym);
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    walk_java(child, source, file_path, package, class_stack, symbols);
+                }
+                class_stack.pop();
+                return;
+            }
+        }
+        "method_declaration" => {
+            if !class_stack.is_empty() {
+                if let Some(sym) = java_method_symbol(node, source, file_path, package, class_stack)
+                {
+                    symbols.push(sym);
+                }
+            }
+        }
+        _ => {}
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        walk_java(child, source, file_path, package, class_stack, symbols);
+    }
+}
+
+fn java_qualified_name(package: &str, class_stack: &[String], name: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    if !package.is_empty() {
+        parts.push(package);
+    }
+    for class_name in class_stack {
+        parts.push(class_name);
+    }
+    parts.push(name);
+    parts.join(".")
+}
+
+fn java_class_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+    class_stack: &[String],
+) -> Option<ExtractedSymbol> {
+    let header = flatten(&header_with_brace(node, source));
+    let caps = JAVA_CLASS_RE.captures(&header)?;
+    let vis = caps
+        .get(1)
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| "package".to_string());
+    let kind = if &caps[2] == "interface" {
+        "interface"
+    } else {
+        "class"
+    };
+    let name = caps[3].to_string();
+    let supertypes = caps
+        .get(4)
+        .map(|m| parse_java_supertypes(m.as_str()))
+        .unwrap_or_default();
+    Some(ExtractedSymbol {
+        qualified_name: java_qualified_name(package, class_stack, &name),
+        name,
+        kind: kind.to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type: None,
+        visibility: Some(vis),
+        is_async: false,
+        is_static: false,
+        docstring: preceding_block_doc_comment(node, source),
+        parameters: Vec::new(),
+        supertypes,
+    })
+}
+
+fn java_method_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+    class_stack: &[String],
+) -> Option<ExtractedSymbol> {
+    let header = flatten(&header_with_brace(node, source));
+    let caps = JAVA_METHOD_RE.captures(&header)?;
+    let vis = caps
+        .get(1)
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| "package".to_string());
+    let is_static = caps.get(2).is_some();
+    let return_type = caps[3].trim().to_string();
+    let name = caps[4].to_string();
+    let parameters = build_parameters(caps[5].trim(), "java");
+    Some(ExtractedSymbol {
+        qualified_name: java_qualified_name(package, class_stack, &name),
+        name,
+        kind: "method".to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type: Some(return_type),
+        visibility: Some(vis),
+        is_async: false,
+        is_static,
+        docstring: preceding_block_doc_comment(node, source),
+        parameters,
+        supertypes: Vec::new(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// TypeScript
+// ---------------------------------------------------------------------------
+
+fn typescript_ast_symbols(tree: &Tree, source: &str, file_path: &str) -> Vec<ExtractedSymbol> {
+    let bytes = source.as_bytes();
+    let module_name = to_module_name(file_path);
+    let mut symbols = Vec::new();
+    let mut class_stack: Vec<String> = Vec::new();
+    walk_ts(
+        tree.root_node(),
+        bytes,
+        file_path,
+        &module_name,
+        &mut class_stack,
+        &mut symbols,
+    );
+    symbols
+}
+
+fn walk_ts(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    module_name: &str,
+    class_stack: &mut Vec<String>,
+    symbols: &mut Vec<ExtractedSymbol>,
+) {
+    match node.kind() {
+        "class_declaration" | "interface_declaration" | "type_alias_declaration" => {
+            if let Some(sym) = ts_class_symbol(node, source, file_path, module_name, class_stack) {
+                class_stack.push(sym.name.clone());
+                symbols.push(sym);
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    walk_ts(child, source, file_path, module_name, class_stack, symbols);
+                }
+                class_stack.pop();
+                return;
+            }
+        }
+        "method_definition" => {
+            if !class_stack.is_empty() {
+                if let Some(sym) =
+                    ts_method_symbol(node, source, file_path, module_name, class_stack)
+                {
+                    symbols.push(sym);
+                }
+            }
+        }
+        "function_declaration" => {
+            if let Some(sym) = ts_function_symbol(node, source, file_path, module_name) {
+                symbols.push(sym);
+            }
+        }
+        "lexical_declaration" => {
+            if let Some(sym) = ts_lexical_symbol(node, source, file_path, module_name) {
+                symbols.push(sym);
+            }
+            return;
+        }
+        _ => {}
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        walk_ts(child, source, file_path, module_name, class_stack, symbols);
+    }
+}
+
+fn ts_qualified_name(module_name: &str, class_stack: &[String], name: &str) -> String {
+    let mut parts: Vec<&str> = vec![module_name];
+    for class_name in class_stack {
+        parts.push(class_name);
+    }
+    parts.push(name);
+    parts.join(".")
+}
+
+fn ts_class_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    module_name: &str,
+    class_stack: &[String],
+) -> Option<ExtractedSymbol> {
+    let header = flatten(&header_with_brace(node, source));
+    let caps = TS_CLASS_RE.captures(&header)?;
+    let kind = if &caps[1] == "interface" || &caps[1] == "type" {
+        "interface"
+    } else {
+        "class"
+    };
+    let name = caps[2].to_string();
+    let supertypes = caps
+        .get(3)
+        .map(|m| parse_ts_supertypes(m.as_str()))
+        .unwrap_or_default();
+    Some(ExtractedSymbol {
+        qualified_name: ts_qualified_name(module_name, class_stack, &name),
+        name,
+        kind: kind.to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type: None,
+        visibility: Some("public".to_string()),
+        is_async: false,
+        is_static: false,
+        docstring: preceding_block_doc_comment(node, source),
+        parameters: Vec::new(),
+        supertypes,
+    })
+}
+
+fn ts_method_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    module_name: &str,
+    class_stack: &[String],
+) -> Option<ExtractedSymbol> {
+    let header = flatten(&header_with_brace(node, source));
+    let caps = TS_METHOD_RE.captures(&header)?;
+    let name = caps[1].to_string();
+    if name == "constructor" {
+        return None;
+    }
+    let parameters = build_parameters(&caps[2], "typescript");
+    let return_type = caps
+        .get(3)
+        .and_then(|m| normalize_type_name(Some(m.as_str())));
+    let is_async = header.contains("async ");
+    Some(ExtractedSymbol {
+        qualified_name: ts_qualified_name(module_name, class_stack, &name),
+        name,
+        kind: "method".to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type,
+        visibility: Some("public".to_string()),
+        is_async,
+        is_static: false,
+        docstring: preceding_block_doc_comment(node, source),
+        parameters,
+        supertypes: Vec::new(),
+    })
+}
+
+fn ts_function_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    module_name: &str,
+) -> Option<ExtractedSymbol> {
+    let header = flatten(&header_with_brace(node, source));
+    let caps = TS_FUNCTION_RE.captures(&header)?;
+    let name = caps[1].to_string();
+    let parameters = build_parameters(&caps[2], "typescript");
+    let return_type = caps
+        .get(3)
+        .and_then(|m| normalize_type_name(Some(m.as_str())));
+    let is_async = header.contains("async ");
+    Some(ExtractedSymbol {
+        qualified_name: format!("{module_name}.{name}"),
+        name,
+        kind: "function".to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type,
+        visibility: Some("public".to_string()),
+        is_async,
+        is_static: false,
+        docstring: preceding_block_doc_comment(node, source),
+        parameters,
+        supertypes: Vec::new(),
+    })
+}
+
+/// The `variable_declarator`'s `arrow_function` value, if `decl` (a
+/// `lexical_declaration`) assigns one — the AST-native replacement for the
+/// old `!line.contains("=>")` guard between [`TS_ARROW_RE`] and
+/// [`TS_CONST_RE`].
+fn arrow_value(decl: Node) -> Option<Node> {
+    let mut cursor = decl.walk();
+    for child in decl.children(&mut cursor) {
+        if child.kind() == "variable_declarator" {
+            if let Some(value) = child.child_by_field_name("value") {
+                if value.kind() == "arrow_function" {
+                    return Some(value);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn ts_lexical_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    module_name: &str,
+) -> Option<ExtractedSymbol> {
+    match arrow_value(node) {
+        Some(arrow) => {
+            // Truncate right after the arrow's own block body (if it has
+            // one) so TS_ARROW_RE's greedy-ish return-type group doesn't
+            // run on into the body text; an expression body (`=> a + b`)
+            // has no brace to truncate at, so keep the whole declaration.
+            let end = arrow
+                .child_by_field_name("body")
+                .filter(|body| body.kind() == "statement_block")
+                .map(|body| body.start_byte() + 1)
+                .unwrap_or_else(|| node.end_byte())
+                .min(source.len());
+            let header = flatten(&String::from_utf8_lossy(&source[node.start_byte()..end]));
+            let caps = TS_ARROW_RE.captures(&header)?;
+            let name = caps[1].to_string();
+            let parameters = build_parameters(&caps[2], "typescript");
+            let return_type = caps
+                .get(3)
+                .and_then(|m| normalize_type_name(Some(m.as_str())));
+            let is_async = header.contains("async ");
+            Some(ExtractedSymbol {
+                qualified_name: format!("{module_name}.{name}"),
+                name,
+                kind: "function".to_string(),
+                file_path: file_path.to_string(),
+                start_line: start_line(node),
+                end_line: end_line(node),
+                signature: Some(header),
+                return_type,
+                visibility: Some("public".to_string()),
+                is_async,
+                is_static: false,
+                docstring: preceding_block_doc_comment(node, source),
+                parameters,
+                supertypes: Vec::new(),
+            })
+        }
+        None => {
+            let header = flatten(node_text(node, source)?);
+            let caps = TS_CONST_RE.captures(&header)?;
+            let name = caps[1].to_string();
+            Some(ExtractedSymbol {
+                qualified_name: format!("{module_name}.{name}"),
+                name,
+                kind: "constant".to_string(),
+                file_path: file_path.to_string(),
+                start_line: start_line(node),
+                end_line: end_line(node),
+                signature: Some(header),
+                return_type: None,
+                visibility: Some("public".to_string()),
+                is_async: false,
+                is_static: false,
+                docstring: preceding_block_doc_comment(node, source),
+                parameters: Vec::new(),
+                supertypes: Vec::new(),
+            })
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Go
+// ---------------------------------------------------------------------------
+
+fn go_package_name(root: Node, source: &[u8]) -> String {
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        if child.kind() == "package_clause" {
+            if let Some(text) = node_text(child, source) {
+                if let Some(caps) = GO_PACKAGE_RE.captures(text) {
+                    return caps[1].to_string();
+                }
+            }
+        }
+    }
+    String::new()
+}
+
+fn go_ast_symbols(tree: &Tree, source: &str, file_path: &str) -> Vec<ExtractedSymbol> {
+    let bytes = source.as_bytes();
+    let root = tree.root_node();
+    let package = go_package_name(root, bytes);
+    let mut symbols = Vec::new();
+    walk_go(root, bytes, file_path, &package, &mut symbols);
+    symbols
+}
+
+/// `true` if `decl` is a parenthesized block (`type ( ... )` / `const ( ...
+/// )`) rather than a single `type Foo struct {}` / `const X = 1` spec. The
+/// regex scanner never matched lines inside such a block either (they don't
+/// start with the `type`/`const` keyword), so this keeps the AST path from
+/// claiming ground the fallback never covered.
+fn is_parenthesized(decl: Node) -> bool {
+    let mut cursor = decl.walk();
+    decl.children(&mut cursor).any(|c| c.kind() == "(")
+}
+
+fn walk_go(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+    symbols: &mut Vec<ExtractedSymbol>,
+) {
+    match node.kind() {
+        "function_declaration" => {
+            if let Some(sym) = go_function_symbol(node, source, file_path, package) {
+                symbols.push(sym);
+            }
+            return;
+        }
+        "method_declaration" => {
+            if let Some(sym) = go_method_symbol(node, source, file_path, package) {
+                symbols.push(sym);
+            }
+            return;
+        }
+        "type_declaration" => {
+            if !is_parenthesized(node) {
+                if let Some(sym) = go_type_symbol(node, source, file_path, package) {
+                    symbols.push(sym);
+                }
+            }
+            return;
+        }
+        "const_declaration" => {
+            if !is_parenthesized(node) {
+                if let Some(sym) = go_const_symbol(node, source, file_path, package) {
+                    symbols.push(sym);
+                }
+            }
+            return;
+        }
+        _ => {}
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        walk_go(child, source, file_path, package, symbols);
+    }
+}
+
+fn go_qualified_name(package: &str, owner: &str, name: &str) -> String {
+    if owner.is_empty() {
+        if package.is_empty() {
+            name.to_string()
+        } else {
+            format!("{package}.{name}")
+        }
+    } else if package.is_empty() {
+        format!("{owner}.{name}")
+    } else {
+        format!("{package}.{owner}.{name}")
+    }
+}
+
+fn go_function_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+) -> Option<ExtractedSymbol> {
+    let header = flatten(&header_with_brace(node, source));
+    let caps = GO_FUNCTION_RE.captures(&header)?;
+    let name = caps[1].to_string();
+    let parameters = build_parameters(&caps[2], "go");
+    let return_type = caps
+        .get(3)
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|s| !s.is_empty());
+    Some(ExtractedSymbol {
+        qualified_name: go_qualified_name(package, "", &name),
+        visibility: Some(go_visibility(&name).to_string()),
+        name,
+        kind: "function".to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type,
+        is_async: false,
+        is_static: false,
+        docstring: preceding_line_doc_comment(node, source),
+        parameters,
+        supertypes: Vec::new(),
+    })
+}
+
+fn go_method_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+) -> Option<ExtractedSymbol> {
+    let header = flatten(&header_with_brace(node, source));
+    let caps = GO_METHOD_RE.captures(&header)?;
+    let receiver_raw = caps[1].trim().to_string();
+    let name = caps[2].to_string();
+    let parameters = build_parameters(&caps[3], "go");
+    let return_type = caps
+        .get(4)
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|s| !s.is_empty());
+    let receiver_tokens: Vec<&str> = receiver_raw.split(' ').filter(|s| !s.is_empty()).collect();
+    let receiver_type = match receiver_tokens.last() {
+        Some(last) => last.replace('*', ""),
+        None => "Receiver".to_string(),
+    };
+    Some(ExtractedSymbol {
+        qualified_name: go_qualified_name(package, &receiver_type, &name),
+        visibility: Some(go_visibility(&name).to_string()),
+        name,
+        kind: "method".to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type,
+        is_async: false,
+        is_static: false,
+        docstring: preceding_line_doc_comment(node, source),
+        parameters,
+        supertypes: Vec::new(),
+    })
+}
+
+fn go_type_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+) -> Option<ExtractedSymbol> {
+    let header = flatten(node_text(node, source)?);
+    let caps = GO_TYPE_RE.captures(&header)?;
+    let name = caps[1].to_string();
+    let kind = if &caps[2] == "interface" {
+        "interface"
+    } else {
+        "class"
+    };
+    Some(ExtractedSymbol {
+        qualified_name: go_qualified_name(package, "", &name),
+        visibility: Some(go_visibility(&name).to_string()),
+        name,
+        kind: kind.to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type: None,
+        is_async: false,
+        is_static: false,
+        docstring: preceding_line_doc_comment(node, source),
+        parameters: Vec::new(),
+        supertypes: Vec::new(),
+    })
+}
+
+fn go_const_symbol(
+    node: Node,
+    source: &[u8],
+    file_path: &str,
+    package: &str,
+) -> Option<ExtractedSymbol> {
+    let header = flatten(node_text(node, source)?);
+    let caps = GO_CONST_RE.captures(&header)?;
+    let name = caps[1].to_string();
+    Some(ExtractedSymbol {
+        qualified_name: go_qualified_name(package, "", &name),
+        visibility: Some(go_visibility(&name).to_string()),
+        name,
+        kind: "constant".to_string(),
+        file_path: file_path.to_string(),
+        start_line: start_line(node),
+        end_line: end_line(node),
+        signature: Some(header),
+        return_type: None,
+        is_async: false,
+        is_static: false,
+        docstring: preceding_line_doc_comment(node, source),
+        parameters: Vec::new(),
+        supertypes: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ast_symbols(source: &str, language: &str) -> Vec<ExtractedSymbol> {
+        extract_via_tree_sitter(source, "Test.src", language)
+            .unwrap_or_else(|| panic!("no {language} grammar/extractor available"))
+    }
+
+    #[test]
+    fn java_method_with_parameters_split_across_lines() {
+        let source = "package com.example;\npublic class Service {\n    public static void doWork(\n        int count,\n        String label\n    ) {\n        System.out.println(label);\n    }\n}\n";
+        let symbols = ast_symbols(source, "java");
+        let method = symbols
+            .iter()
+            .find(|s| s.name == "doWork")
+            .expect("doWork not found");
+        assert_eq!(method.qualified_name, "com.example.Service.doWork");
+        assert_eq!(method.parameters.len(), 2);
+        assert_eq!(method.parameters[0].name, "count");
+        assert_eq!(method.parameters[1].name, "label");
+        assert!(method.is_static);
+        assert_eq!(method.start_line, 3);
+        assert!(method.end_line > method.start_line);
+    }
+
+    #[test]
+    fn java_brace_inside_string_literal_does_not_confuse_class_span() {
+        // The line-scanning regex path counts `{`/`}` characters anywhere on
+        // a line, including inside a string literal — the brace in the
+        // message below would make it think the class ended one line early.
+        // The AST path can't make that mistake since it never counts braces.
+        let source = "package com.example;\npublic class Service {\n    public void log() {\n        String s = \"unexpected }\";\n    }\n}\n";
+        let symbols = ast_symbols(source, "java");
+        let class_sym = symbols
+            .iter()
+            .find(|s| s.name == "Service")
+            .expect("Service not found");
+        assert_eq!(class_sym.start_line, 2);
+        assert_eq!(class_sym.end_line, 6);
+    }
+
+    #[test]
+    fn typescript_multiline_arrow_function_with_block_body() {
+        let source =
+            "export const compute = (\n    a: number,\n    b: number\n): number => {\n    return a + b;\n};\n";
+        let symbols = ast_symbols(source, "typescript");
+        let function = symbols
+            .iter()
+            .find(|s| s.name == "compute")
+            .expect("compute not found");
+        assert_eq!(function.kind, "function");
+        assert_eq!(function.parameters.len(), 2);
+        assert_eq!(function.return_type.as_deref(), Some("number"));
+    }
+
+    #[test]
+    fn typescript_multiline_function_signature() {
+        let source = "export async function fetchAll(\n    url: string,\n    retries: number\n): Promise<Response> {\n    return fetch(url);\n}\n";
+        let symbols = ast_symbols(source, "typescript");
+        let function = symbols
+            .iter()
+            .find(|s| s.name == "fetchAll")
+            .expect("fetchAll not found");
+        assert!(function.is_async);
+        assert_eq!(function.parameters.len(), 2);
+    }
+
+    #[test]
+    fn go_method_signature_split_across_lines() {
+        let source = "package http\n\nfunc (s *Server) ListenAndServe(\n\taddr string,\n) error {\n\treturn nil\n}\n";
+        let symbols = ast_symbols(source, "go");
+        let method = symbols
+            .iter()
+            .find(|s| s.name == "ListenAndServe")
+            .expect("ListenAndServe not found");
+        assert_eq!(method.qualified_name, "http.Server.ListenAndServe");
+        assert_eq!(method.parameters.len(), 1);
+        assert_eq!(method.return_type.as_deref(), Some("error"));
+    }
+
+    #[test]
+    fn extract_via_tree_sitter_returns_none_without_a_grammar() {
+        assert!(extract_via_tree_sitter("x = 1\n", "a.py", "python").is_none());
+    }
+
+    #[test]
+    fn java_javadoc_attaches_to_class_and_method() {
+        let source = "package com.example;\n\n/**\n * A widget.\n */\npublic class Widget {\n    /**\n     * Renders it.\n     */\n    public void render() {}\n}\n";
+        let symbols = ast_symbols(source, "java");
+        let class_sym = symbols.iter().find(|s| s.name == "Widget").unwrap();
+        assert_eq!(class_sym.docstring.as_deref(), Some("A widget."));
+        let method = symbols.iter().find(|s| s.name == "render").unwrap();
+        assert_eq!(method.docstring.as_deref(), Some("Renders it."));
+    }
+
+    #[test]
+    fn java_doc_comment_separated_by_blank_line_does_not_attach() {
+        let source = "package com.example;\n\n/**\n * Stale.\n */\n\npublic class Widget {\n}\n";
+        let symbols = ast_symbols(source, "java");
+        let class_sym = symbols.iter().find(|s| s.name == "Widget").unwrap();
+        assert_eq!(class_sym.docstring, None);
+    }
+
+    #[test]
+    fn typescript_jsdoc_attaches_to_function() {
+        let source = "/**\n * Adds two numbers.\n */\nexport function add(a: number, b: number): number {\n    return a + b;\n}\n";
+        let symbols = ast_symbols(source, "typescript");
+        let function = symbols.iter().find(|s| s.name == "add").unwrap();
+        assert_eq!(function.docstring.as_deref(), Some("Adds two numbers."));
+    }
+
+    #[test]
+    fn go_doc_comment_lines_attach_to_function() {
+        let source = "package util\n\n// Sum adds two integers.\n// It never overflows in practice.\nfunc Sum(a int, b int) int {\n\treturn a + b\n}\n";
+        let symbols = ast_symbols(source, "go");
+        let function = symbols.iter().find(|s| s.name == "Sum").unwrap();
+        assert_eq!(
+            function.docstring.as_deref(),
+            Some("Sum adds two integers.\nIt never overflows in practice.")
+        );
+    }
+}
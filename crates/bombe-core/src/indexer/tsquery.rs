@@ -0,0 +1,187 @@
+//! Tree-sitter query subsystem for locating definitions and references.
+//!
+//! Extraction passes need more than a raw `tree_sitter::Tree` — they need to
+//! find specific node kinds (function definitions, class bodies, call
+//! expressions, ...). This module runs "tags"-style capture queries
+//! (the same capture-name conventions used by `tree-sitter-tags` /
+//! `nvim-treesitter`, e.g. `@definition.function`, `@definition.class`,
+//! `@name`, `@reference.call`) against a [`RustParsedUnit`] and returns
+//! structured matches instead of raw nodes.
+
+use pyo3::prelude::*;
+use tree_sitter::{Query, QueryCursor};
+
+use super::parser::RustParsedUnit;
+
+/// A single capture produced by running a query against a parsed tree.
+#[derive(Clone, Debug)]
+pub struct QueryCapture {
+    /// Capture name from the query, e.g. `"definition.function"`.
+    pub capture_name: String,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start_row: usize,
+    pub start_column: usize,
+    pub end_row: usize,
+    pub end_column: usize,
+    /// Source text sliced from the node's byte range.
+    pub text: String,
+}
+
+/// Bundled default "tags" query strings per language. These are intentionally
+/// small — just enough to locate definitions, names, and call references —
+/// and can be overridden by passing a custom query to [`run_query`].
+pub fn default_query_for(language: &str) -> Option<&'static str> {
+    match language {
+        "java" => Some(
+            r#"
+            (class_declaration name: (identifier) @name) @definition.class
+            (interface_declaration name: (identifier) @name) @definition.class
+            (method_declaration name: (identifier) @name) @definition.function
+            (method_invocation name: (identifier) @name) @reference.call
+            "#,
+        ),
+        "typescript" => Some(
+            r#"
+            (function_declaration name: (identifier) @name) @definition.function
+            (class_declaration name: (type_identifier) @name) @definition.class
+            (interface_declaration name: (type_identifier) @name) @definition.class
+            (method_definition name: (property_identifier) @name) @definition.function
+            (call_expression function: (identifier) @name) @reference.call
+            "#,
+        ),
+        "go" => Some(
+            r#"
+            (function_declaration name: (identifier) @name) @definition.function
+            (method_declaration name: (field_identifier) @name) @definition.function
+            (type_declaration (type_spec name: (type_identifier) @name)) @definition.class
+            (call_expression function: (identifier) @name) @reference.call
+            "#,
+        ),
+        _ => None,
+    }
+}
+
+/// Run a tags-style query against a parsed unit's tree.
+///
+/// Python units carry no native tree (`unit.tree` is `None`, since Python
+/// parsing is delegated to CPython's `ast` module), so this returns a clear
+/// error instead of panicking. Query compilation errors are likewise
+/// reported per-language rather than panicking, since a malformed query for
+/// one grammar should not take down extraction for the others.
+pub fn run_query(unit: &RustParsedUnit, query_src: &str) -> Result<Vec<QueryCapture>, String> {
+    let tree = unit.tree.as_ref().ok_or_else(|| {
+        format!(
+            "No native tree-sitter tree for language '{}' (Python units are parsed via Python's ast module)",
+            unit.language
+        )
+    })?;
+
+    let ts_language = tree.language();
+    let query = Query::new(&ts_language, query_src)
+        .map_err(|e| format!("Failed to compile query for '{}': {e}", unit.language))?;
+
+    let mut cursor = QueryCursor::new();
+    let source_bytes = unit.source.as_bytes();
+    let mut matches = cursor.matches(&query, tree.root_node(), source_bytes);
+
+    let mut results = Vec::new();
+    while let Some(m) = matches.next() {
+        for capture in m.captures {
+            let node = capture.node;
+            let capture_name = query.capture_names()[capture.index as usize].to_string();
+            let text = node
+                .utf8_text(source_bytes)
+                .unwrap_or_default()
+                .to_string();
+            results.push(QueryCapture {
+                capture_name,
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                start_row: node.start_position().row,
+                start_column: node.start_position().column,
+                end_row: node.end_position().row,
+                end_column: node.end_position().column,
+                text,
+            });
+        }
+    }
+    Ok(results)
+}
+
+/// PyO3 entry point: run the default (or a caller-supplied) query against a
+/// parsed file and return the captures as a list of dicts.
+#[pyfunction]
+#[pyo3(signature = (path, language, query_src=None))]
+pub fn run_query_on_file(
+    py: Python<'_>,
+    path: &str,
+    language: &str,
+    query_src: Option<String>,
+) -> PyResult<PyObject> {
+    let unit = super::parser::parse_file_native(std::path::Path::new(path), language)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+
+    let query_src = match query_src {
+        Some(q) => q,
+        None => default_query_for(language)
+            .ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "No default query bundled for language '{language}'"
+                ))
+            })?
+            .to_string(),
+    };
+
+    let captures =
+        run_query(&unit, &query_src).map_err(pyo3::exceptions::PyValueError::new_err)?;
+
+    let list = pyo3::types::PyList::empty(py);
+    for c in captures {
+        let entry = pyo3::types::PyDict::new(py);
+        entry.set_item("capture", &c.capture_name)?;
+        entry.set_item("start_byte", c.start_byte)?;
+        entry.set_item("end_byte", c.end_byte)?;
+        entry.set_item("start_row", c.start_row)?;
+        entry.set_item("start_column", c.start_column)?;
+        entry.set_item("end_row", c.end_row)?;
+        entry.set_item("end_column", c.end_column)?;
+        entry.set_item("text", &c.text)?;
+        list.append(entry)?;
+    }
+    Ok(list.into_any().unbind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::parser::parse_file_native;
+
+    #[test]
+    fn test_run_query_go_function() {
+        let mut path = std::env::temp_dir();
+        path.push("bombe_tsquery_test_hello.go");
+        std::fs::write(&path, "package main\n\nfunc hello() {}\n").unwrap();
+
+        let unit = parse_file_native(&path, "go").unwrap();
+        let query = default_query_for("go").unwrap();
+        let captures = run_query(&unit, query).unwrap();
+        assert!(captures
+            .iter()
+            .any(|c| c.capture_name == "name" && c.text == "hello"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_run_query_python_errors() {
+        let unit = RustParsedUnit {
+            path: "foo.py".to_string(),
+            language: "python".to_string(),
+            source: "def foo(): pass".to_string(),
+            tree: None,
+            grammar: None,
+        };
+        assert!(run_query(&unit, "(module) @m").is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! Cross-language type normalization for `ParameterRecord.type_` /
+//! `SymbolRecord.return_type` free-form strings.
+//!
+//! Every language spells the same concept differently (`int` vs `i64` vs
+//! `number`, `Optional[str]` vs `Option<String>` vs `string | null`), which
+//! makes comparing signatures across a polyglot monorepo unreliable. This
+//! mirrors the string->typed-value `Conversion` idea common in log/ETL
+//! pipelines: collapse the noisy per-language spelling into a small
+//! [`CanonicalType`], then use that canonical form (rather than the raw
+//! string) wherever two signatures need to compare as equivalent — see
+//! [`normalized_signature_hash`].
+
+use sha2::{Digest, Sha256};
+
+use crate::models::ParameterRecord;
+
+/// A type normalized across languages, stripped of borrows/generics noise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonicalType {
+    Int,
+    Float,
+    Bool,
+    String,
+    Bytes,
+    List(Box<CanonicalType>),
+    Optional(Box<CanonicalType>),
+    Map,
+    /// A named (non-primitive) type this module doesn't otherwise model,
+    /// e.g. a user-defined class or interface — kept verbatim so two
+    /// identically-named types across languages still compare equal.
+    Named(String),
+    /// Empty or unparseable strings, and TypeScript's `any`/`unknown`/`object`
+    /// catch-alls. Python's `Any`/`object` and Go's `interface{}` are not
+    /// mapped here and come out as [`CanonicalType::Named`].
+    Unknown,
+}
+
+impl CanonicalType {
+    /// Render as the same compact string both `normalize_type` (the Python
+    /// boundary) and [`canonical_type_from_str`] (its inverse) use — PyO3
+    /// can't expose this enum's boxed/recursive variants directly, so this
+    /// string form is the wire format, the same way
+    /// [`crate::store::sharding::stdlib_registry::DependencyClass`] exposes
+    /// itself via `as_str()` instead of as a `#[pyclass]` enum.
+    pub fn to_canonical_string(&self) -> String {
+        match self {
+            CanonicalType::Int => "int".to_string(),
+            CanonicalType::Float => "float".to_string(),
+            CanonicalType::Bool => "bool".to_string(),
+            CanonicalType::String => "string".to_string(),
+            CanonicalType::Bytes => "bytes".to_string(),
+            CanonicalType::List(inner) => format!("list<{}>", inner.to_canonical_string()),
+            CanonicalType::Optional(inner) => format!("optional<{}>", inner.to_canonical_string()),
+            CanonicalType::Map => "map".to_string(),
+            CanonicalType::Named(name) => format!("named:{name}"),
+            CanonicalType::Unknown => "unknown".to_string(),
+        }
+    }
+}
+
+/// Inverse of [`CanonicalType::to_canonical_string`], for callers (like
+/// [`parse_default`]) that only have the string form a prior `normalize_type`
+/// call returned.
+fn canonical_type_from_str(s: &str) -> CanonicalType {
+    match s {
+        "int" => CanonicalType::Int,
+        "float" => CanonicalType::Float,
+        "bool" => CanonicalType::Bool,
+        "string" => CanonicalType::String,
+        "bytes" => CanonicalType::Bytes,
+        "map" => CanonicalType::Map,
+        _ => {
+            if let Some(inner) = s.strip_prefix("list<").and_then(|r| r.strip_suffix('>')) {
+                CanonicalType::List(Box::new(canonical_type_from_str(inner)))
+            } else if let Some(inner) = s
+                .strip_prefix("optional<")
+                .and_then(|r| r.strip_suffix('>'))
+            {
+                CanonicalType::Optional(Box::new(canonical_type_from_str(inner)))
+            } else if let Some(name) = s.strip_prefix("named:") {
+                CanonicalType::Named(name.to_string())
+            } else {
+                CanonicalType::Unknown
+            }
+        }
+    }
+}
+
+/// Strip borrows/references (`&`, `&mut `) a Rust type annotation may carry.
+fn strip_rust_refs(raw: &str) -> &str {
+    raw.trim()
+        .trim_start_matches('&')
+        .trim_start()
+        .trim_start_matches("mut ")
+        .trim()
+}
+
+/// Pull the inner type out of a single-argument wrapper like `Optional[X]`,
+/// `List[X]`, `Vec<X>`, `X[]`, returning `None` if `raw` isn't shaped that way.
+fn unwrap_generic<'a>(raw: &'a str, open: char, close: char, prefixes: &[&str]) -> Option<&'a str> {
+    for prefix in prefixes {
+        if let Some(rest) = raw.strip_prefix(prefix) {
+            let rest = rest.trim();
+            if rest.starts_with(open) && rest.ends_with(close) {
+                return Some(rest[1..rest.len() - 1].trim());
+            }
+        }
+    }
+    None
+}
+
+/// Normalize a single raw type annotation string for `language` into a
+/// [`CanonicalType`], stripping borrows/references, `Optional[...]`/`... |
+/// None` wrappers, and generic brackets, and mapping primitive aliases
+/// across Python/Rust/TypeScript onto the same variant.
+fn normalize_type_inner(raw: &str, language: &str) -> CanonicalType {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return CanonicalType::Unknown;
+    }
+
+    match language {
+        "rust" => {
+            let raw = strip_rust_refs(raw);
+            if raw == "Vec<u8>" || raw == "[u8]" {
+                return CanonicalType::Bytes;
+            }
+            if let Some(inner) = unwrap_generic(raw, '<', '>', &["Option"]) {
+                return CanonicalType::Optional(Box::new(normalize_type_inner(inner, language)));
+            }
+            if let Some(inner) = unwrap_generic(raw, '<', '>', &["Vec"]) {
+                return CanonicalType::List(Box::new(normalize_type_inner(inner, language)));
+            }
+            if raw.starts_with('[') && raw.ends_with(']') {
+                return CanonicalType::List(Box::new(normalize_type_inner(
+                    &raw[1..raw.len() - 1],
+                    language,
+                )));
+            }
+            if raw.starts_with("HashMap<") || raw.starts_with("BTreeMap<") {
+                return CanonicalType::Map;
+            }
+            match raw {
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                | "u128" | "usize" => CanonicalType::Int,
+                "f32" | "f64" => CanonicalType::Float,
+                "bool" => CanonicalType::Bool,
+                "String" | "str" | "&str" => CanonicalType::String,
+                _ => named_or_unknown(raw),
+            }
+        }
+        "python" => {
+            if let Some(inner) = unwrap_generic(raw, '[', ']', &["Optional"]) {
+                return CanonicalType::Optional(Box::new(normalize_type_inner(inner, language)));
+            }
+            if raw.ends_with("| None") {
+                let inner = raw.trim_end_matches("| None").trim_end_matches('|').trim();
+                return CanonicalType::Optional(Box::new(normalize_type_inner(inner, language)));
+            }
+            if let Some(inner) = unwrap_generic(raw, '[', ']', &["List", "list"]) {
+                return CanonicalType::List(Box::new(normalize_type_inner(inner, language)));
+            }
+            if raw.starts_with("Dict[") || raw.starts_with("dict[") {
+                return CanonicalType::Map;
+            }
+            match raw {
+                "int" => CanonicalType::Int,
+                "float" => CanonicalType::Float,
+                "bool" => CanonicalType::Bool,
+                "str" => CanonicalType::String,
+                "bytes" => CanonicalType::Bytes,
+                _ => named_or_unknown(raw),
+            }
+        }
+        "typescript" | "javascript" => {
+            if raw.ends_with("| null") || raw.ends_with("| undefined") {
+                let inner = raw
+                    .trim_end_matches("| undefined")
+                    .trim_end_matches("| null")
+                    .trim_end_matches('|')
+                    .trim();
+                return CanonicalType::Optional(Box::new(normalize_type_inner(inner, language)));
+            }
+            if let Some(inner) = raw.strip_suffix("[]") {
+                return CanonicalType::List(Box::new(normalize_type_inner(inner, language)));
+            }
+            if let Some(inner) = unwrap_generic(raw, '<', '>', &["Array"]) {
+                return CanonicalType::List(Box::new(normalize_type_inner(inner, language)));
+            }
+            if raw.starts_with("Record<") || raw.starts_with("Map<") {
+                return CanonicalType::Map;
+            }
+            match raw {
+                "number" => CanonicalType::Float,
+                "boolean" => CanonicalType::Bool,
+                "string" => CanonicalType::String,
+                "any" | "unknown" | "object" => CanonicalType::Unknown,
+                _ => named_or_unknown(raw),
+            }
+        }
+        _ => named_or_unknown(raw),
+    }
+}
+
+/// Anything starting with a letter (of either case) is treated as a named
+/// type; anything else (operators, stray punctuation) collapses to `Unknown`.
+fn named_or_unknown(raw: &str) -> CanonicalType {
+    if raw.chars().next().is_some_and(|c| c.is_alphabetic()) {
+        CanonicalType::Named(raw.to_string())
+    } else {
+        CanonicalType::Unknown
+    }
+}
+
+/// Normalize a raw per-language type annotation into its canonical string
+/// form (e.g. `normalize_type("Optional[int]", "python")` and
+/// `normalize_type("Option<i64>", "rust")` both return `"optional<int>"`).
+#[pyo3::pyfunction]
+pub fn normalize_type(raw: &str, language: &str) -> String {
+    normalize_type_inner(raw, language).to_canonical_string()
+}
+
+/// Turn a literal default-value string into a real typed Python constant,
+/// where `canonical` (as returned by [`normalize_type`]) names a primitive.
+/// Returns `None` for non-primitive canonical types or unparseable literals.
+#[pyo3::pyfunction]
+pub fn parse_default(
+    py: pyo3::Python<'_>,
+    raw: &str,
+    canonical: &str,
+) -> pyo3::PyResult<Option<pyo3::PyObject>> {
+    use pyo3::IntoPyObject;
+
+    let raw = raw.trim();
+    let value = match canonical_type_from_str(canonical) {
+        CanonicalType::Int => raw
+            .parse::<i64>()
+            .ok()
+            .map(|v| v.into_pyobject(py).unwrap().into_any().unbind()),
+        CanonicalType::Float => raw
+            .parse::<f64>()
+            .ok()
+            .map(|v| v.into_pyobject(py).unwrap().into_any().unbind()),
+        CanonicalType::Bool => match raw {
+            "true" | "True" => Some(
+                true.into_pyobject(py)
+                    .unwrap()
+                    .to_owned()
+                    .into_any()
+                    .unbind(),
+            ),
+            "false" | "False" => Some(
+                false
+                    .into_pyobject(py)
+                    .unwrap()
+                    .to_owned()
+                    .into_any()
+                    .unbind(),
+            ),
+            _ => None,
+        },
+        CanonicalType::String => {
+            let stripped = raw
+                .strip_prefix('"')
+                .and_then(|r| r.strip_suffix('"'))
+                .or_else(|| raw.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')))
+                .unwrap_or(raw);
+            Some(stripped.into_pyobject(py).unwrap().into_any().unbind())
+        }
+        _ => None,
+    };
+    Ok(value)
+}
+
+/// Hash a function/method's parameter and return types after canonicalizing
+/// each one, so two signatures that differ only in per-language spelling
+/// (`(x: int) -> bool` vs `(x: i64) -> bool`) collapse to the same hash —
+/// unlike [`crate::models::_signature_hash`], which hashes the raw signature
+/// string verbatim.
+pub fn normalized_signature_hash(
+    parameters: &[ParameterRecord],
+    return_type: Option<&str>,
+    language: &str,
+) -> String {
+    let param_types: Vec<String> = parameters
+        .iter()
+        .map(|p| match &p.type_ {
+            Some(t) => normalize_type_inner(t, language).to_canonical_string(),
+            None => CanonicalType::Unknown.to_canonical_string(),
+        })
+        .collect();
+    let return_canonical = return_type
+        .map(|t| normalize_type_inner(t, language).to_canonical_string())
+        .unwrap_or_else(|| CanonicalType::Unknown.to_canonical_string());
+
+    let input = format!("({})->{}", param_types.join(","), return_canonical);
+    let mut hasher = Sha256::new();
+    hasher.update(input.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optional_int_matches_across_languages() {
+        assert_eq!(
+            normalize_type("Optional[int]", "python"),
+            normalize_type("Option<i64>", "rust"),
+        );
+    }
+
+    #[test]
+    fn test_list_of_strings_matches_across_languages() {
+        assert_eq!(
+            normalize_type("List[str]", "python"),
+            normalize_type("string[]", "typescript"),
+        );
+    }
+
+    #[test]
+    fn test_named_type_is_preserved() {
+        assert_eq!(normalize_type("UserProfile", "python"), "named:UserProfile");
+    }
+
+    #[test]
+    fn test_normalized_signature_hash_ignores_spelling() {
+        let py_params = vec![ParameterRecord {
+            name: "x".to_string(),
+            position: 0,
+            type_: Some("int".to_string()),
+            default_value: None,
+        }];
+        let rust_params = vec![ParameterRecord {
+            name: "x".to_string(),
+            position: 0,
+            type_: Some("i64".to_string()),
+            default_value: None,
+        }];
+
+        let py_hash = normalized_signature_hash(&py_params, Some("bool"), "python");
+        let rust_hash = normalized_signature_hash(&rust_params, Some("bool"), "rust");
+        assert_eq!(py_hash, rust_hash);
+    }
+}
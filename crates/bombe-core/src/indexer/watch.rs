@@ -0,0 +1,191 @@
+//! Filesystem watch mode for incremental re-indexing.
+//!
+//! `iter_repo_files` only does one-shot full walks, which is wasteful for a
+//! long-running indexer that just wants to know what changed since the last
+//! pass. [`start_repo_watch`] spawns a `notify` OS watcher scoped to
+//! `repo_root`, filters every raw event through the same include/exclude
+//! [`Matcher`] pipeline `iter_repo_files` uses (so `.git`, `.bombe`,
+//! sensitive files, and out-of-scope paths never surface), and hands back a
+//! [`RepoWatchHandle`] whose `poll_changes` method debounces bursts of
+//! events into a coalesced changeset of normalized relative paths.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{recommended_watcher, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use pyo3::prelude::*;
+
+use super::filesystem::is_implicitly_ignored_path;
+use super::matcher::{
+    build_default_matcher, DifferenceMatcher, ExcludeMatcher, IncludeMatcher, Matcher,
+};
+use crate::errors::BombeError;
+
+/// Coalesced change kind for a single path, collapsed from whatever raw
+/// `notify` events touched it during one [`RepoWatchHandle::poll_changes`]
+/// window. Mirrors a dirstate's create/modify/delete vocabulary rather than
+/// notify's more granular, platform-dependent event taxonomy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl ChangeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Deleted => "deleted",
+        }
+    }
+
+    /// Later events win, with one exception: a `Deleted` already recorded
+    /// for this path in the current window is never downgraded back to
+    /// `Modified` by a stray trailing event (renames raise one on some
+    /// platforms) — the path is gone, and that's the fact callers need.
+    fn coalesce(prev: ChangeKind, next: ChangeKind) -> ChangeKind {
+        if prev == ChangeKind::Deleted && next == ChangeKind::Modified {
+            prev
+        } else {
+            next
+        }
+    }
+}
+
+fn classify_event_kind(kind: &EventKind) -> Option<ChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(ChangeKind::Created),
+        EventKind::Modify(_) => Some(ChangeKind::Modified),
+        EventKind::Remove(_) => Some(ChangeKind::Deleted),
+        _ => None,
+    }
+}
+
+/// Handle returned by [`start_repo_watch`]. Holds the live OS watcher (so it
+/// isn't dropped and torn down) plus the raw event receiver; Python callers
+/// drive it by repeatedly calling `poll_changes`.
+#[pyclass]
+pub struct RepoWatchHandle {
+    repo_root: PathBuf,
+    matcher: DifferenceMatcher<IncludeMatcher, ExcludeMatcher>,
+    receiver: Receiver<notify::Result<Event>>,
+    // Kept alive only for its `Drop` impl, which stops the OS watch.
+    _watcher: RecommendedWatcher,
+}
+
+impl RepoWatchHandle {
+    fn normalize(&self, path: &Path) -> Option<String> {
+        let rel = path
+            .strip_prefix(&self.repo_root)
+            .ok()?
+            .to_string_lossy()
+            .replace('\\', "/");
+        if rel.is_empty() {
+            None
+        } else {
+            Some(rel)
+        }
+    }
+
+    /// Applies the repo's include/exclude scoping to one raw event,
+    /// updating `changes` with the normalized relative paths that survive.
+    /// Filters with `is_dir = false` throughout: the indexer only ever
+    /// re-hashes and re-parses files, and a deleted path can no longer be
+    /// stat'd to tell whether it used to be a directory.
+    fn absorb(&self, event: notify::Result<Event>, changes: &mut HashMap<String, ChangeKind>) {
+        let Ok(event) = event else { return };
+        let Some(kind) = classify_event_kind(&event.kind) else {
+            return;
+        };
+        for path in &event.paths {
+            let Some(rel) = self.normalize(path) else {
+                continue;
+            };
+            if is_implicitly_ignored_path(&rel) || !self.matcher.matches(&rel, false) {
+                continue;
+            }
+            let coalesced = changes
+                .get(&rel)
+                .map_or(kind, |prev| ChangeKind::coalesce(*prev, kind));
+            changes.insert(rel, coalesced);
+        }
+    }
+}
+
+#[pymethods]
+impl RepoWatchHandle {
+    /// Waits up to `debounce_ms` milliseconds for a first raw watcher event,
+    /// then keeps collecting until `debounce_ms` pass with no further event,
+    /// and returns the coalesced changeset as a list of
+    /// `(relative_path, change_kind)` tuples (`change_kind` one of
+    /// `"created"`, `"modified"`, `"deleted"`). Returns an empty list if no
+    /// events arrive inside the window rather than blocking indefinitely, so
+    /// a caller can poll this in a loop without stalling shutdown.
+    #[pyo3(signature = (debounce_ms=200))]
+    fn poll_changes(&self, debounce_ms: u64) -> PyResult<Vec<(String, String)>> {
+        let window = Duration::from_millis(debounce_ms);
+        let mut changes: HashMap<String, ChangeKind> = HashMap::new();
+
+        // Block for the first event so callers can poll in a tight loop
+        // without busy-waiting, then keep draining events until a full
+        // debounce window passes with none arriving; each event restarts it.
+        match self.receiver.recv_timeout(window) {
+            Ok(event) => self.absorb(event, &mut changes),
+            Err(RecvTimeoutError::Timeout) => return Ok(vec![]),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(BombeError::Index("repo watcher disconnected".to_string()).into())
+            }
+        }
+        loop {
+            match self.receiver.recv_timeout(window) {
+                Ok(event) => self.absorb(event, &mut changes),
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        Ok(changes
+            .into_iter()
+            .map(|(path, kind)| (path, kind.as_str().to_string()))
+            .collect())
+    }
+}
+
+/// Starts watching `repo_root` for filesystem changes, scoped by the same
+/// include/exclude patterns [`super::filesystem::iter_repo_files`] would
+/// apply to a full scan. Returns a [`RepoWatchHandle`]; call its
+/// `poll_changes` method in a loop to drain coalesced changesets and
+/// re-index only the touched files instead of rescanning the tree.
+#[pyfunction]
+#[pyo3(signature = (repo_root, include_patterns=None, exclude_patterns=None))]
+pub fn start_repo_watch(
+    repo_root: &str,
+    include_patterns: Option<Vec<String>>,
+    exclude_patterns: Option<Vec<String>>,
+) -> PyResult<RepoWatchHandle> {
+    let repo_root_path = PathBuf::from(repo_root);
+    let matcher = build_default_matcher(
+        &repo_root_path,
+        include_patterns.as_deref(),
+        exclude_patterns.as_deref(),
+    )?;
+
+    let (tx, rx) = channel();
+    let mut watcher = recommended_watcher(move |event| {
+        let _ = tx.send(event);
+    })
+    .map_err(|e| BombeError::Index(format!("failed to start repo watcher: {e}")))?;
+    watcher
+        .watch(&repo_root_path, RecursiveMode::Recursive)
+        .map_err(|e| BombeError::Index(format!("failed to watch {repo_root}: {e}")))?;
+
+    Ok(RepoWatchHandle {
+        repo_root: repo_root_path,
+        matcher,
+        receiver: rx,
+        _watcher: watcher,
+    })
+}
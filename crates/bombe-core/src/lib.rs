@@ -5,26 +5,39 @@
 //! a Python extension module (`_bombe_core`) via PyO3 and can be used as a
 //! drop-in replacement for the pure-Python implementations.
 
+pub mod alloc_stats;
 pub mod errors;
 pub mod indexer;
 pub mod models;
 pub mod query;
 pub mod store;
+pub mod telemetry;
 
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 
+// `query::federated::executor`'s opt-in per-shard allocation profiling reads
+// this allocator's thread-local counters via `alloc_stats::Region`; see that
+// module for why wrapping the global allocator costs nothing when profiling
+// is off.
+#[global_allocator]
+static GLOBAL_ALLOC: alloc_stats::StatsAlloc = alloc_stats::StatsAlloc;
+
 // ---------------------------------------------------------------------------
 // Top-level Python module: _bombe_core
 // ---------------------------------------------------------------------------
 
 #[pymodule]
 fn _bombe_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
-    // -- Models (constants, helper functions, 33 dataclass pyclasses) --------
+    // -- Observability: OTLP traces/metrics, or a local no-op subscriber ----
+    telemetry::init();
+
+    // -- Models (constants, helper functions, 36 dataclass pyclasses) --------
     models::register_models(m)?;
 
     // -- Store layer --------------------------------------------------------
     m.add_class::<store::database::Database>()?;
+    m.add_class::<store::database::ConnectionOptions>()?;
 
     // -- Sharding -----------------------------------------------------------
     m.add_class::<store::sharding::catalog::ShardCatalog>()?;
@@ -60,6 +73,7 @@ fn _bombe_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
         "MAX_CROSS_REPO_EDGES_PER_QUERY",
         query::guards::MAX_CROSS_REPO_EDGES_PER_QUERY,
     )?;
+    m.add("MAX_RDF_EXPORT_EDGES", query::guards::MAX_RDF_EXPORT_EDGES)?;
 
     m.add_function(wrap_pyfunction!(query::guards::clamp_int, m)?)?;
     m.add_function(wrap_pyfunction!(query::guards::clamp_depth, m)?)?;
@@ -74,6 +88,8 @@ fn _bombe_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // -- Query: hybrid scoring ----------------------------------------------
     m.add_function(wrap_pyfunction!(query::hybrid::hybrid_search_enabled, m)?)?;
     m.add_function(wrap_pyfunction!(query::hybrid::semantic_vector_enabled, m)?)?;
+    m.add_function(wrap_pyfunction!(query::hybrid::rooted_pagerank_enabled, m)?)?;
+    m.add_function(wrap_pyfunction!(query::hybrid::bm25_lexical_scoring_enabled, m)?)?;
     m.add_function(wrap_pyfunction!(query::hybrid::lexical_score, m)?)?;
     m.add_function(wrap_pyfunction!(query::hybrid::structural_score, m)?)?;
     m.add_function(wrap_pyfunction!(query::hybrid::semantic_score, m)?)?;
@@ -83,18 +99,50 @@ fn _bombe_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(query::search::search_symbols, m)?)?;
     m.add_function(wrap_pyfunction!(query::references::get_references, m)?)?;
     m.add_function(wrap_pyfunction!(query::context::get_context, m)?)?;
+    m.add_function(wrap_pyfunction!(query::eval::run_workload, m)?)?;
     m.add_function(wrap_pyfunction!(query::blast::get_blast_radius, m)?)?;
     m.add_function(wrap_pyfunction!(query::data_flow::trace_data_flow, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        query::data_flow::trace_data_flow_between,
+        m
+    )?)?;
     m.add_function(wrap_pyfunction!(query::change_impact::change_impact, m)?)?;
     m.add_function(wrap_pyfunction!(query::structure::get_structure, m)?)?;
+    m.add_function(wrap_pyfunction!(query::semantic_index::semantic_search, m)?)?;
+    m.add_function(wrap_pyfunction!(query::call_path::get_call_path, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        query::symbol_resolution::suggest_symbols,
+        m
+    )?)?;
 
     // -- Query: planner (LRU cache) -----------------------------------------
     m.add_class::<query::planner::QueryPlanner>()?;
+    m.add_function(wrap_pyfunction!(
+        query::planner_bench::run_planner_bench,
+        m
+    )?)?;
 
     // -- Query: federated ---------------------------------------------------
     m.add_class::<query::federated::planner::ShardQueryPlan>()?;
     m.add_class::<query::federated::planner::FederatedQueryPlanner>()?;
     m.add_class::<query::federated::executor::FederatedQueryExecutor>()?;
+    m.add(
+        "FederatedQuorumError",
+        m.py().get_type::<query::federated::executor::FederatedQuorumError>(),
+    )?;
+    m.add_function(wrap_pyfunction!(
+        query::federated::triples::build_graph_triples,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(query::federated::triples::query_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        query::federated::triples::federated_graph_query,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        query::federated::semantic::federated_semantic_search,
+        m
+    )?)?;
 
     // -- Sharding: resolver functions ----------------------------------------
     m.add_function(wrap_pyfunction!(
@@ -109,6 +157,36 @@ fn _bombe_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
         store::sharding::resolver::post_index_cross_repo_sync,
         m
     )?)?;
+    m.add_function(wrap_pyfunction!(
+        store::sharding::backend::convert_shard,
+        m
+    )?)?;
+    m.add_class::<store::sharding::backend::ShardConnectionPool>()?;
+    m.add_function(wrap_pyfunction!(
+        store::sharding::rdf_export::export_cross_repo_graph,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        store::sharding::rdf_export::query_cross_repo_graph,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        store::sharding::merkle::compute_shard_merkle_root,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        store::sharding::merkle::diff_shard_entries,
+        m
+    )?)?;
+    m.add_class::<store::sharding::path_vector::PathVectorTable>()?;
+    m.add_function(wrap_pyfunction!(
+        store::sharding::path_vector::route_lookup,
+        m
+    )?)?;
+
+    // -- Query: symbol-pattern DSL -------------------------------------------
+    m.add_class::<query::search_dsl::CompiledSymbolPattern>()?;
+    m.add_function(wrap_pyfunction!(query::search_dsl::compile_query, m)?)?;
 
     // -- Indexer: functions --------------------------------------------------
     m.add_function(wrap_pyfunction!(indexer::filesystem::detect_language, m)?)?;
@@ -116,12 +194,45 @@ fn _bombe_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
         indexer::filesystem::compute_content_hash,
         m
     )?)?;
+    m.add_function(wrap_pyfunction!(indexer::matcher::would_index_path, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        indexer::hash_cache::compute_content_hash_cached,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(indexer::hash_cache::record_scan_start, m)?)?;
+    m.add_class::<indexer::watch::RepoWatchHandle>()?;
+    m.add_function(wrap_pyfunction!(indexer::watch::start_repo_watch, m)?)?;
     m.add_function(wrap_pyfunction!(
         indexer::parser::tree_sitter_capability_report,
         m
     )?)?;
     m.add_function(wrap_pyfunction!(indexer::pagerank::recompute_pagerank, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        indexer::pagerank::recompute_pagerank_incremental,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        indexer::pagerank::recompute_pagerank_personalized,
+        m
+    )?)?;
     m.add_function(wrap_pyfunction!(indexer::pipeline::rust_full_index, m)?)?;
+    m.add_function(wrap_pyfunction!(indexer::bench::run_bench, m)?)?;
+    m.add_function(wrap_pyfunction!(indexer::tsquery::run_query_on_file, m)?)?;
+    m.add_function(wrap_pyfunction!(indexer::chunking::chunk_file, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        indexer::semantic::compute_hints_version_token,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        indexer::dataset_export::export_method_samples,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        indexer::structural_id::assign_structural_ids,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(indexer::type_normalize::normalize_type, m)?)?;
+    m.add_function(wrap_pyfunction!(indexer::type_normalize::parse_default, m)?)?;
 
     Ok(())
 }
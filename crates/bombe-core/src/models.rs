@@ -1,5 +1,7 @@
 //! Shared typed models used across indexing, storage, and query layers.
 
+use std::collections::HashMap;
+
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyTuple};
 use sha2::{Digest, Sha256};
@@ -137,6 +139,20 @@ pub struct SymbolRecord {
     pub is_static: bool,
     pub parent_symbol_id: Option<i64>,
     pub docstring: Option<String>,
+    /// Raw source text spanning `start_line..end_line`, populated from the
+    /// file's source span (e.g. by
+    /// [`crate::indexer::dataset_export::export_method_samples`]'s fallback
+    /// reader) when a caller didn't already fill it in. `None` for symbols
+    /// where body text isn't needed, to avoid inflating every delta/context
+    /// payload with full source text by default.
+    pub body: Option<String>,
+    /// Line-independent identity: `kind` + nesting path (ordinal position
+    /// among same-kind siblings under each `parent_symbol_id`, root to self)
+    /// + signature hash, deliberately excluding `start_line`/`end_line` so a
+    /// symbol shifted by an edit elsewhere in the file keeps the same id.
+    /// Populated by [`crate::indexer::structural_id::assign_structural_ids`];
+    /// `None` until a symbol has been run through it. See [`SymbolKey::structural`].
+    pub structural_id: Option<String>,
     pub pagerank_score: f64,
     pub parameters: Vec<ParameterRecord>,
 }
@@ -158,6 +174,8 @@ impl SymbolRecord {
         is_static=false,
         parent_symbol_id=None,
         docstring=None,
+        body=None,
+        structural_id=None,
         pagerank_score=0.0,
         parameters=Vec::new(),
     ))]
@@ -176,6 +194,8 @@ impl SymbolRecord {
         is_static: bool,
         parent_symbol_id: Option<i64>,
         docstring: Option<String>,
+        body: Option<String>,
+        structural_id: Option<String>,
         pagerank_score: f64,
         parameters: Vec<ParameterRecord>,
     ) -> Self {
@@ -193,6 +213,8 @@ impl SymbolRecord {
             is_static,
             parent_symbol_id,
             docstring,
+            body,
+            structural_id,
             pagerank_score,
             parameters,
         }
@@ -217,6 +239,15 @@ impl SymbolRecord {
 // ---------------------------------------------------------------------------
 
 /// Unique identity key for a symbol (qualified_name + file + line range + sig hash).
+///
+/// `structural_id` is a second, line-independent identity derived from
+/// [`SymbolRecord::structural_id`] (nesting path + kind + signature hash,
+/// see [`crate::indexer::structural_id::assign_structural_ids`]) — it is
+/// *not* part of [`SymbolKey::__eq__`]/`__hash__`, which stay exactly as
+/// before, but [`match_symbols`] uses it as a fallback so a symbol moved by
+/// N lines still matches its previous version instead of looking like a
+/// delete+add. Empty (`""`) when the key wasn't built with structural
+/// context (e.g. via [`SymbolKey::new`]/`from_fields`).
 #[pyclass(frozen, get_all)]
 #[derive(Clone, Debug)]
 pub struct SymbolKey {
@@ -225,17 +256,30 @@ pub struct SymbolKey {
     pub start_line: i64,
     pub end_line: i64,
     pub signature_hash: String,
+    pub structural_id: String,
+}
+
+/// Line-based identity comparison, shared by [`SymbolKey::__eq__`] and
+/// [`match_symbols`]'s exact-match pass.
+fn symbol_keys_equal(a: &SymbolKey, b: &SymbolKey) -> bool {
+    a.qualified_name == b.qualified_name
+        && a.file_path == b.file_path
+        && a.start_line == b.start_line
+        && a.end_line == b.end_line
+        && a.signature_hash == b.signature_hash
 }
 
 #[pymethods]
 impl SymbolKey {
     #[new]
+    #[pyo3(signature = (qualified_name, file_path, start_line, end_line, signature_hash, structural_id="".to_string()))]
     fn new(
         qualified_name: String,
         file_path: String,
         start_line: i64,
         end_line: i64,
         signature_hash: String,
+        structural_id: String,
     ) -> Self {
         Self {
             qualified_name,
@@ -243,6 +287,7 @@ impl SymbolKey {
             start_line,
             end_line,
             signature_hash,
+            structural_id,
         }
     }
 
@@ -255,6 +300,7 @@ impl SymbolKey {
             symbol.start_line,
             symbol.end_line,
             symbol.signature.clone(),
+            String::new(),
         )
     }
 
@@ -269,7 +315,66 @@ impl SymbolKey {
         end_line: i64,
         signature: Option<String>,
     ) -> Self {
-        Self::_from_fields(qualified_name, file_path, start_line, end_line, signature)
+        Self::_from_fields(
+            qualified_name,
+            file_path,
+            start_line,
+            end_line,
+            signature,
+            String::new(),
+        )
+    }
+
+    /// Build a ``SymbolKey`` whose ``structural_id`` is the symbol's
+    /// line-independent identity (nesting path + kind + signature hash)
+    /// rather than an empty placeholder — use with [`match_symbols`] so a
+    /// symbol that only moved lines still matches its previous version.
+    /// Falls back to a flat `kind`+signature id when `symbol` wasn't run
+    /// through [`crate::indexer::structural_id::assign_structural_ids`]
+    /// first (no sibling/nesting context available).
+    #[classmethod]
+    fn structural(_cls: &Bound<'_, pyo3::types::PyType>, symbol: &SymbolRecord) -> Self {
+        let signature_hash = _signature_hash(symbol.signature.clone());
+        let structural_id = symbol
+            .structural_id
+            .clone()
+            .unwrap_or_else(|| format!("{}##{}", symbol.kind, signature_hash));
+        Self {
+            qualified_name: symbol.qualified_name.clone(),
+            file_path: symbol.file_path.clone(),
+            start_line: symbol.start_line,
+            end_line: symbol.end_line,
+            signature_hash,
+            structural_id,
+        }
+    }
+
+    /// Build a ``SymbolKey`` whose ``signature_hash`` is derived from
+    /// canonicalized parameter/return types (see
+    /// [`crate::indexer::type_normalize::normalized_signature_hash`])
+    /// instead of hashing the raw signature string, so two signatures that
+    /// are equivalent but spelled differently across languages (e.g.
+    /// ``(x: int) -> bool`` vs ``(x: i64) -> bool``) collapse to the same
+    /// hash.
+    #[classmethod]
+    fn from_symbol_canonical(
+        _cls: &Bound<'_, pyo3::types::PyType>,
+        symbol: &SymbolRecord,
+        language: &str,
+    ) -> Self {
+        let signature_hash = crate::indexer::type_normalize::normalized_signature_hash(
+            &symbol.parameters,
+            symbol.return_type.as_deref(),
+            language,
+        );
+        Self {
+            qualified_name: symbol.qualified_name.clone(),
+            file_path: symbol.file_path.clone(),
+            start_line: symbol.start_line,
+            end_line: symbol.end_line,
+            signature_hash,
+            structural_id: String::new(),
+        }
     }
 
     /// Return the key as a Python tuple.
@@ -308,11 +413,7 @@ impl SymbolKey {
     }
 
     fn __eq__(&self, other: &SymbolKey) -> bool {
-        self.qualified_name == other.qualified_name
-            && self.file_path == other.file_path
-            && self.start_line == other.start_line
-            && self.end_line == other.end_line
-            && self.signature_hash == other.signature_hash
+        symbol_keys_equal(self, other)
     }
 
     fn __hash__(&self) -> u64 {
@@ -334,6 +435,7 @@ impl SymbolKey {
         start_line: i64,
         end_line: i64,
         signature: Option<String>,
+        structural_id: String,
     ) -> Self {
         let sig_hash = _signature_hash(signature);
         Self {
@@ -342,10 +444,57 @@ impl SymbolKey {
             start_line,
             end_line,
             signature_hash: sig_hash,
+            structural_id,
         }
     }
 }
 
+/// Pair old/new ``SymbolKey``s for delta reporting: first by exact key
+/// equality (unchanged), then — among what's left — by ``structural_id``
+/// (moved/unchanged: same nesting path, kind, and signature, just shifted
+/// lines), leaving anything still unmatched as a pure add or pure delete.
+///
+/// Each pair is `(old, new)`; a pure delete is `(Some(old), None)`, a pure
+/// add is `(None, Some(new))`.
+#[pyfunction]
+pub fn match_symbols(
+    old: Vec<SymbolKey>,
+    new: Vec<SymbolKey>,
+) -> Vec<(Option<SymbolKey>, Option<SymbolKey>)> {
+    let mut pairs = Vec::new();
+    let mut remaining_new = new;
+    let mut unmatched_old = Vec::with_capacity(old.len());
+
+    for old_key in old {
+        match remaining_new
+            .iter()
+            .position(|n| symbol_keys_equal(n, &old_key))
+        {
+            Some(pos) => pairs.push((Some(old_key), Some(remaining_new.remove(pos)))),
+            None => unmatched_old.push(old_key),
+        }
+    }
+
+    let mut still_unmatched_old = Vec::with_capacity(unmatched_old.len());
+    for old_key in unmatched_old {
+        let structural_match = (!old_key.structural_id.is_empty())
+            .then(|| {
+                remaining_new
+                    .iter()
+                    .position(|n| n.structural_id == old_key.structural_id)
+            })
+            .flatten();
+        match structural_match {
+            Some(pos) => pairs.push((Some(old_key), Some(remaining_new.remove(pos)))),
+            None => still_unmatched_old.push(old_key),
+        }
+    }
+
+    pairs.extend(still_unmatched_old.into_iter().map(|k| (Some(k), None)));
+    pairs.extend(remaining_new.into_iter().map(|k| (None, Some(k))));
+    pairs
+}
+
 // ---------------------------------------------------------------------------
 // 5. EdgeKey
 // ---------------------------------------------------------------------------
@@ -865,6 +1014,33 @@ impl QualityStats {
     }
 }
 
+impl QualityStats {
+    /// Combine two samples covering different ranges of the same stream:
+    /// counts sum, and `ambiguity_rate` becomes the weighted average of the
+    /// two rates, weighted by each side's `unresolved_imports +
+    /// parse_failures` (the population each rate was actually computed
+    /// over). Falls back to an unweighted average when both sides carry
+    /// zero weight, so two clean deltas combine to a clean delta instead of
+    /// `0.0 / 0.0`. Weighting by the summed counts (rather than e.g. symbol
+    /// count) keeps repeated aggregation associative, since the weight of a
+    /// combined sample is exactly the sum of its inputs' weights.
+    fn aggregate(a: &QualityStats, b: &QualityStats) -> QualityStats {
+        let weight_a = (a.unresolved_imports + a.parse_failures).max(0) as f64;
+        let weight_b = (b.unresolved_imports + b.parse_failures).max(0) as f64;
+        let total_weight = weight_a + weight_b;
+        let ambiguity_rate = if total_weight > 0.0 {
+            (a.ambiguity_rate * weight_a + b.ambiguity_rate * weight_b) / total_weight
+        } else {
+            (a.ambiguity_rate + b.ambiguity_rate) / 2.0
+        };
+        QualityStats {
+            ambiguity_rate,
+            unresolved_imports: a.unresolved_imports + b.unresolved_imports,
+            parse_failures: a.parse_failures + b.parse_failures,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 17. IndexDelta
 // ---------------------------------------------------------------------------
@@ -926,6 +1102,547 @@ impl IndexDelta {
             self.edge_deletes.len(),
         )
     }
+
+    /// Fold `self` then `next` (a later delta against the same lineage)
+    /// into one delta spanning both transitions. Net effect per symbol/edge
+    /// identity is "whichever delta touched it last": a delete after an
+    /// upsert collapses to a delete, an upsert after a delete collapses to
+    /// an upsert, and an upsert after an upsert keeps the later record --
+    /// exactly what a plain last-write-wins merge over (self's net ops,
+    /// then next's net ops) gives, which is why `compose` is associative
+    /// regardless of how a chain of deltas is grouped. `file_changes` merge
+    /// the same way, by `path`. The header keeps `self`'s `parent_snapshot`
+    /// but advances every other field to `next`'s, since the composed delta
+    /// describes the same overall transition `self.parent_snapshot ->
+    /// next.local_snapshot`.
+    fn compose(&self, next: &IndexDelta) -> IndexDelta {
+        let symbol_ops = fold_ops(
+            net_symbol_ops(self)
+                .into_iter()
+                .chain(net_symbol_ops(next))
+                .collect(),
+        );
+        let mut symbol_upserts = Vec::new();
+        let mut symbol_deletes = Vec::new();
+        for (_, op) in symbol_ops {
+            match op {
+                SymbolOp::Upsert(record) => symbol_upserts.push(record),
+                SymbolOp::Delete(key) => symbol_deletes.push(key),
+            }
+        }
+
+        let edge_ops = fold_ops(
+            net_edge_ops(self)
+                .into_iter()
+                .chain(net_edge_ops(next))
+                .collect(),
+        );
+        let mut edge_upserts = Vec::new();
+        let mut edge_deletes = Vec::new();
+        for (_, op) in edge_ops {
+            match op {
+                EdgeOp::Upsert(edge) => edge_upserts.push(edge),
+                EdgeOp::Delete(edge) => edge_deletes.push(edge),
+            }
+        }
+
+        let file_ops = fold_ops(
+            self.file_changes
+                .iter()
+                .map(|fd| (fd.path.clone(), fd.clone()))
+                .chain(
+                    next.file_changes
+                        .iter()
+                        .map(|fd| (fd.path.clone(), fd.clone())),
+                )
+                .collect(),
+        );
+        let file_changes = file_ops.into_iter().map(|(_, fd)| fd).collect();
+
+        IndexDelta {
+            header: DeltaHeader {
+                repo_id: self.header.repo_id.clone(),
+                parent_snapshot: self.header.parent_snapshot.clone(),
+                local_snapshot: next.header.local_snapshot.clone(),
+                tool_version: next.header.tool_version.clone(),
+                schema_version: next.header.schema_version,
+                created_at_utc: next.header.created_at_utc.clone(),
+            },
+            file_changes,
+            symbol_upserts,
+            symbol_deletes,
+            edge_upserts,
+            edge_deletes,
+            quality_stats: QualityStats::aggregate(&self.quality_stats, &next.quality_stats),
+        }
+    }
+
+    /// Produce the delta that reverses this one, given the full symbol/edge
+    /// state exactly as it stood *before* this delta was applied -- so
+    /// `delta.apply(symbols, edges)` followed by
+    /// `delta.invert(previous_symbols, previous_edges).apply(symbols,
+    /// edges)` is a no-op round trip back to `previous_symbols`/
+    /// `previous_edges`. A delete inverts to an upsert of the prior record
+    /// held for that identity (nothing to restore if it wasn't actually
+    /// present before). An upsert inverts to an upsert of the prior record
+    /// if one existed for that identity, else to a delete, since the
+    /// upsert was really an add.
+    fn invert(
+        &self,
+        previous_symbols: Vec<SymbolRecord>,
+        previous_edges: Vec<EdgeContractRecord>,
+    ) -> IndexDelta {
+        let prev_symbols: HashMap<SymbolIdentity, SymbolRecord> = previous_symbols
+            .into_iter()
+            .map(|record| (symbol_record_identity(&record), record))
+            .collect();
+        let prev_edges: HashMap<EdgeIdentity, EdgeContractRecord> = previous_edges
+            .into_iter()
+            .map(|edge| (edge_identity(&edge), edge))
+            .collect();
+
+        let mut symbol_upserts = Vec::new();
+        let mut symbol_deletes = Vec::new();
+        for key in &self.symbol_deletes {
+            if let Some(prior) = prev_symbols.get(&symbol_key_identity(key)) {
+                symbol_upserts.push(prior.clone());
+            }
+        }
+        for record in &self.symbol_upserts {
+            match prev_symbols.get(&symbol_record_identity(record)) {
+                Some(prior) => symbol_upserts.push(prior.clone()),
+                None => symbol_deletes.push(SymbolKey::_from_fields(
+                    record.qualified_name.clone(),
+                    record.file_path.clone(),
+                    record.start_line,
+                    record.end_line,
+                    record.signature.clone(),
+                    String::new(),
+                )),
+            }
+        }
+
+        let mut edge_upserts = Vec::new();
+        let mut edge_deletes = Vec::new();
+        for edge in &self.edge_deletes {
+            if let Some(prior) = prev_edges.get(&edge_identity(edge)) {
+                edge_upserts.push(prior.clone());
+            }
+        }
+        for edge in &self.edge_upserts {
+            match prev_edges.get(&edge_identity(edge)) {
+                Some(prior) => edge_upserts.push(prior.clone()),
+                None => edge_deletes.push(edge.clone()),
+            }
+        }
+
+        IndexDelta {
+            header: DeltaHeader {
+                repo_id: self.header.repo_id.clone(),
+                parent_snapshot: Some(self.header.local_snapshot.clone()),
+                local_snapshot: self.header.parent_snapshot.clone().unwrap_or_default(),
+                tool_version: self.header.tool_version.clone(),
+                schema_version: self.header.schema_version,
+                created_at_utc: self.header.created_at_utc.clone(),
+            },
+            file_changes: self.file_changes.iter().map(invert_file_delta).collect(),
+            symbol_upserts,
+            symbol_deletes,
+            edge_upserts,
+            edge_deletes,
+            quality_stats: self.quality_stats.clone(),
+        }
+    }
+
+    /// Apply this delta's symbol/edge changes to `symbols`/`edges` in
+    /// place -- deletes first, then upserts, so an upsert landing on the
+    /// same identity a delete just freed up still takes effect.
+    fn apply(&self, symbols: &Bound<'_, PyList>, edges: &Bound<'_, PyList>) -> PyResult<()> {
+        for key in &self.symbol_deletes {
+            remove_symbol(symbols, &symbol_key_identity(key))?;
+        }
+        for record in &self.symbol_upserts {
+            upsert_symbol(symbols, record)?;
+        }
+        for edge in &self.edge_deletes {
+            remove_edge(edges, &edge_identity(edge))?;
+        }
+        for edge in &self.edge_upserts {
+            upsert_edge(edges, edge)?;
+        }
+        Ok(())
+    }
+}
+
+/// Symbol identity for delta-algebra purposes: the same fields
+/// [`symbol_keys_equal`] compares, tuple-ified so it can be used as a
+/// `HashMap` key (`SymbolKey` itself derives neither `Eq` nor `Hash`, since
+/// its Python-visible `__eq__`/`__hash__` are hand-written).
+type SymbolIdentity = (String, String, i64, i64, String);
+
+fn symbol_key_identity(key: &SymbolKey) -> SymbolIdentity {
+    (
+        key.qualified_name.clone(),
+        key.file_path.clone(),
+        key.start_line,
+        key.end_line,
+        key.signature_hash.clone(),
+    )
+}
+
+fn symbol_record_identity(record: &SymbolRecord) -> SymbolIdentity {
+    symbol_key_identity(&SymbolKey::_from_fields(
+        record.qualified_name.clone(),
+        record.file_path.clone(),
+        record.start_line,
+        record.end_line,
+        record.signature.clone(),
+        String::new(),
+    ))
+}
+
+/// Edge identity for delta-algebra purposes: the underlying `EdgeKey`,
+/// tuple-ified the same way [`SymbolIdentity`] is.
+type EdgeIdentity = (SymbolIdentity, SymbolIdentity, String, i64);
+
+fn edge_identity(edge: &EdgeContractRecord) -> EdgeIdentity {
+    (
+        symbol_key_identity(&edge.source),
+        symbol_key_identity(&edge.target),
+        edge.relationship.clone(),
+        edge.line_number,
+    )
+}
+
+enum SymbolOp {
+    Upsert(SymbolRecord),
+    Delete(SymbolKey),
+}
+
+enum EdgeOp {
+    Upsert(EdgeContractRecord),
+    Delete(EdgeContractRecord),
+}
+
+/// Fold a delta's own `symbol_deletes` then `symbol_upserts` (the same
+/// deletes-then-upserts order [`IndexDelta::apply`] uses) into the net
+/// per-identity operation, so a key touched by both within one delta
+/// collapses to its actual net effect before cross-delta composition sees
+/// it.
+fn net_symbol_ops(delta: &IndexDelta) -> Vec<(SymbolIdentity, SymbolOp)> {
+    let mut ops = Vec::new();
+    for key in &delta.symbol_deletes {
+        ops.push((symbol_key_identity(key), SymbolOp::Delete(key.clone())));
+    }
+    for record in &delta.symbol_upserts {
+        ops.push((
+            symbol_record_identity(record),
+            SymbolOp::Upsert(record.clone()),
+        ));
+    }
+    fold_ops(ops)
+}
+
+fn net_edge_ops(delta: &IndexDelta) -> Vec<(EdgeIdentity, EdgeOp)> {
+    let mut ops = Vec::new();
+    for edge in &delta.edge_deletes {
+        ops.push((edge_identity(edge), EdgeOp::Delete(edge.clone())));
+    }
+    for edge in &delta.edge_upserts {
+        ops.push((edge_identity(edge), EdgeOp::Upsert(edge.clone())));
+    }
+    fold_ops(ops)
+}
+
+/// Stable-order last-write-wins fold: a key's value is whichever entry for
+/// it appears last in `ops`, but the key keeps the position of its first
+/// occurrence -- the same semantics as repeatedly assigning into a Python
+/// dict. This is the core of [`IndexDelta::compose`]'s associativity: the
+/// final value for a key depends only on the last delta that touched it,
+/// never on how the composition was grouped.
+fn fold_ops<K, V>(ops: Vec<(K, V)>) -> Vec<(K, V)>
+where
+    K: Eq + std::hash::Hash + Clone,
+{
+    let mut order: Vec<K> = Vec::new();
+    let mut map: HashMap<K, V> = HashMap::new();
+    for (key, value) in ops {
+        if !map.contains_key(&key) {
+            order.push(key.clone());
+        }
+        map.insert(key, value);
+    }
+    order
+        .into_iter()
+        .map(|key| {
+            let value = map.remove(&key).unwrap();
+            (key, value)
+        })
+        .collect()
+}
+
+/// Reverse one file-level change: an add/delete flips to a delete/add, a
+/// rename swaps `path`/`old_path` back, and anything else (a plain
+/// `modified`) keeps its status. `content_hash`/`size_bytes` are carried
+/// through as-is since this delta doesn't retain the pre-change hash/size
+/// to restore exactly.
+fn invert_file_delta(change: &FileDelta) -> FileDelta {
+    let status = match change.status.as_str() {
+        "added" => "deleted",
+        "deleted" => "added",
+        other => other,
+    }
+    .to_string();
+    let (path, old_path) = match &change.old_path {
+        Some(old_path) => (old_path.clone(), Some(change.path.clone())),
+        None => (change.path.clone(), None),
+    };
+    FileDelta {
+        status,
+        path,
+        old_path,
+        content_hash: change.content_hash.clone(),
+        size_bytes: change.size_bytes,
+    }
+}
+
+fn find_symbol_index(list: &Bound<'_, PyList>, target: &SymbolIdentity) -> PyResult<Option<usize>> {
+    for (i, item) in list.iter().enumerate() {
+        let record: SymbolRecord = item.extract()?;
+        if symbol_record_identity(&record) == *target {
+            return Ok(Some(i));
+        }
+    }
+    Ok(None)
+}
+
+fn remove_symbol(list: &Bound<'_, PyList>, target: &SymbolIdentity) -> PyResult<()> {
+    if let Some(i) = find_symbol_index(list, target)? {
+        list.del_item(i)?;
+    }
+    Ok(())
+}
+
+fn upsert_symbol(list: &Bound<'_, PyList>, record: &SymbolRecord) -> PyResult<()> {
+    match find_symbol_index(list, &symbol_record_identity(record))? {
+        Some(i) => list.set_item(i, record.clone()),
+        None => list.append(record.clone()),
+    }
+}
+
+fn find_edge_index(list: &Bound<'_, PyList>, target: &EdgeIdentity) -> PyResult<Option<usize>> {
+    for (i, item) in list.iter().enumerate() {
+        let edge: EdgeContractRecord = item.extract()?;
+        if edge_identity(&edge) == *target {
+            return Ok(Some(i));
+        }
+    }
+    Ok(None)
+}
+
+fn remove_edge(list: &Bound<'_, PyList>, target: &EdgeIdentity) -> PyResult<()> {
+    if let Some(i) = find_edge_index(list, target)? {
+        list.del_item(i)?;
+    }
+    Ok(())
+}
+
+fn upsert_edge(list: &Bound<'_, PyList>, edge: &EdgeContractRecord) -> PyResult<()> {
+    match find_edge_index(list, &edge_identity(edge))? {
+        Some(i) => list.set_item(i, edge.clone()),
+        None => list.append(edge.clone()),
+    }
+}
+
+#[cfg(test)]
+mod delta_algebra_tests {
+    use super::*;
+
+    fn header(parent: &str, local: &str) -> DeltaHeader {
+        DeltaHeader {
+            repo_id: "repo".to_string(),
+            parent_snapshot: Some(parent.to_string()),
+            local_snapshot: local.to_string(),
+            tool_version: "1.0".to_string(),
+            schema_version: 1,
+            created_at_utc: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn symbol(qualified_name: &str, signature: Option<&str>) -> SymbolRecord {
+        SymbolRecord {
+            name: qualified_name.to_string(),
+            qualified_name: qualified_name.to_string(),
+            kind: "function".to_string(),
+            file_path: "a.py".to_string(),
+            start_line: 1,
+            end_line: 2,
+            signature: signature.map(|s| s.to_string()),
+            return_type: None,
+            visibility: None,
+            is_async: false,
+            is_static: false,
+            parent_symbol_id: None,
+            docstring: None,
+            body: None,
+            structural_id: None,
+            pagerank_score: 0.0,
+            parameters: Vec::new(),
+        }
+    }
+
+    fn delta(
+        parent: &str,
+        local: &str,
+        upserts: Vec<SymbolRecord>,
+        deletes: Vec<SymbolKey>,
+    ) -> IndexDelta {
+        IndexDelta {
+            header: header(parent, local),
+            file_changes: Vec::new(),
+            symbol_upserts: upserts,
+            symbol_deletes: deletes,
+            edge_upserts: Vec::new(),
+            edge_deletes: Vec::new(),
+            quality_stats: QualityStats {
+                ambiguity_rate: 0.0,
+                unresolved_imports: 0,
+                parse_failures: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_compose_later_upsert_overrides_earlier() {
+        let a = delta("s0", "s1", vec![symbol("foo", Some("(a)"))], Vec::new());
+        let mut later = symbol("foo", Some("(a)"));
+        later.docstring = Some("v2".to_string());
+        let b = delta("s1", "s2", vec![later], Vec::new());
+        let composed = a.compose(&b);
+        assert_eq!(composed.symbol_upserts.len(), 1);
+        assert_eq!(
+            composed.symbol_upserts[0].docstring.as_deref(),
+            Some("v2")
+        );
+        assert_eq!(composed.header.parent_snapshot.as_deref(), Some("s0"));
+        assert_eq!(composed.header.local_snapshot, "s2");
+    }
+
+    #[test]
+    fn test_compose_upsert_then_delete_collapses_to_delete() {
+        let foo = symbol("foo", Some("(a)"));
+        let key = symbol_record_identity(&foo);
+        let a = delta("s0", "s1", vec![foo], Vec::new());
+        let b = delta(
+            "s1",
+            "s2",
+            Vec::new(),
+            vec![SymbolKey::_from_fields(
+                "foo".to_string(),
+                "a.py".to_string(),
+                1,
+                2,
+                Some("(a)".to_string()),
+                String::new(),
+            )],
+        );
+        let composed = a.compose(&b);
+        assert!(composed.symbol_upserts.is_empty());
+        assert_eq!(composed.symbol_deletes.len(), 1);
+        assert_eq!(symbol_key_identity(&composed.symbol_deletes[0]), key);
+    }
+
+    #[test]
+    fn test_compose_is_associative() {
+        let a = delta("s0", "s1", vec![symbol("foo", Some("(a)"))], Vec::new());
+        let b = delta(
+            "s1",
+            "s2",
+            Vec::new(),
+            vec![SymbolKey::_from_fields(
+                "foo".to_string(),
+                "a.py".to_string(),
+                1,
+                2,
+                Some("(a)".to_string()),
+                String::new(),
+            )],
+        );
+        let c = delta("s2", "s3", vec![symbol("foo", Some("(a, b)"))], Vec::new());
+
+        let left = a.compose(&b).compose(&c);
+        let right = a.compose(&b.compose(&c));
+        assert_eq!(left.symbol_upserts.len(), right.symbol_upserts.len());
+        assert_eq!(left.symbol_deletes.len(), right.symbol_deletes.len());
+        for (l, r) in left.symbol_upserts.iter().zip(&right.symbol_upserts) {
+            assert_eq!(symbol_record_identity(l), symbol_record_identity(r));
+            assert_eq!(l.signature, r.signature);
+        }
+    }
+
+    #[test]
+    fn test_invert_then_apply_is_no_op_round_trip() {
+        let mut foo = symbol("foo", Some("(a)"));
+        foo.docstring = Some("v1".to_string());
+        let previous_symbols = vec![foo.clone()];
+        let mut updated = symbol("foo", Some("(a)"));
+        updated.docstring = Some("v2".to_string());
+        let forward = delta("s0", "s1", vec![updated], Vec::new());
+        let backward = forward.invert(previous_symbols, Vec::new());
+
+        assert_eq!(backward.symbol_upserts.len(), 1);
+        assert_eq!(backward.symbol_upserts[0].docstring.as_deref(), Some("v1"));
+        assert_eq!(backward.header.local_snapshot, "s0");
+        assert_eq!(backward.header.parent_snapshot.as_deref(), Some("s1"));
+    }
+
+    #[test]
+    fn test_invert_of_fresh_add_is_a_delete() {
+        let foo = symbol("foo", Some("(a)"));
+        let forward = delta("s0", "s1", vec![foo.clone()], Vec::new());
+        let backward = forward.invert(Vec::new(), Vec::new());
+        assert!(backward.symbol_upserts.is_empty());
+        assert_eq!(backward.symbol_deletes.len(), 1);
+        assert_eq!(
+            symbol_key_identity(&backward.symbol_deletes[0]),
+            symbol_record_identity(&foo)
+        );
+    }
+
+    #[test]
+    fn test_quality_stats_aggregate_weighted_average() {
+        let a = QualityStats {
+            ambiguity_rate: 0.2,
+            unresolved_imports: 8,
+            parse_failures: 2,
+        };
+        let b = QualityStats {
+            ambiguity_rate: 0.5,
+            unresolved_imports: 0,
+            parse_failures: 0,
+        };
+        let combined = QualityStats::aggregate(&a, &b);
+        assert_eq!(combined.unresolved_imports, 8);
+        assert_eq!(combined.parse_failures, 2);
+        // b carries zero weight, so the weighted average collapses to a's rate.
+        assert!((combined.ambiguity_rate - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quality_stats_aggregate_falls_back_to_unweighted_when_both_zero_weight() {
+        let a = QualityStats {
+            ambiguity_rate: 0.0,
+            unresolved_imports: 0,
+            parse_failures: 0,
+        };
+        let b = QualityStats {
+            ambiguity_rate: 0.4,
+            unresolved_imports: 0,
+            parse_failures: 0,
+        };
+        let combined = QualityStats::aggregate(&a, &b);
+        assert!((combined.ambiguity_rate - 0.2).abs() < 1e-9);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1018,6 +1735,51 @@ impl ArtifactBundle {
             self.artifact_id, self.repo_id, self.snapshot_id,
         )
     }
+
+    /// Compute this bundle's canonical content checksum (SHA-256 hex) —
+    /// does not sign or mutate `checksum`; see
+    /// [`crate::store::artifact_checksum::compute_checksum`].
+    fn compute_checksum(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(crate::store::artifact_checksum::compute_checksum(py, self)?)
+    }
+
+    /// Recompute the checksum and sign it with Ed25519, returning a new,
+    /// stamped `ArtifactBundle` (this type is frozen, so signing can't
+    /// mutate `self` in place) with `signature_algo`, `signing_key_id`,
+    /// `checksum`, and `signature` populated. `private_key_hex` is a
+    /// hex-encoded 32-byte Ed25519 seed.
+    fn sign(&self, py: Python<'_>, private_key_hex: &str, key_id: String) -> PyResult<Self> {
+        let (checksum, signature) =
+            crate::store::artifact_checksum::sign(py, self, private_key_hex)?;
+        Ok(Self {
+            artifact_id: self.artifact_id.clone(),
+            repo_id: self.repo_id.clone(),
+            snapshot_id: self.snapshot_id.clone(),
+            parent_snapshot: self.parent_snapshot.clone(),
+            tool_version: self.tool_version.clone(),
+            schema_version: self.schema_version,
+            created_at_utc: self.created_at_utc.clone(),
+            promoted_symbols: self.promoted_symbols.clone(),
+            promoted_edges: self.promoted_edges.clone(),
+            impact_priors: self.impact_priors.clone_ref(py),
+            flow_hints: self.flow_hints.clone_ref(py),
+            signature_algo: Some("ed25519".to_string()),
+            signing_key_id: Some(key_id),
+            checksum: Some(checksum),
+            signature: Some(signature),
+        })
+    }
+
+    /// Recompute the checksum (rejecting on mismatch) and verify
+    /// `signature` over it under `public_key_hex` (a hex-encoded 32-byte
+    /// Ed25519 public key). `false` if `self` isn't signed at all.
+    fn verify(&self, py: Python<'_>, public_key_hex: &str) -> PyResult<bool> {
+        Ok(crate::store::artifact_checksum::verify(
+            py,
+            self,
+            public_key_hex,
+        )?)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1097,10 +1859,109 @@ impl IndexStats {
     }
 }
 
+/// Accepted `kind` filters for [`SymbolSearchRequest`], matching the values
+/// [`crate::indexer::symbols`] actually assigns plus the `"any"` sentinel
+/// that skips filtering. Parses case-insensitively and accepts a couple of
+/// short aliases; anything else is rejected with a `PyValueError` listing
+/// the accepted values, instead of silently matching zero symbols the way a
+/// raw string filter would.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SymbolKind {
+    Any,
+    Function,
+    Method,
+    Class,
+    Interface,
+    Constant,
+}
+
+impl SymbolKind {
+    const ACCEPTED: &'static [&'static str] = &[
+        "any",
+        "function",
+        "method",
+        "class",
+        "interface",
+        "constant",
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            SymbolKind::Any => "any",
+            SymbolKind::Function => "function",
+            SymbolKind::Method => "method",
+            SymbolKind::Class => "class",
+            SymbolKind::Interface => "interface",
+            SymbolKind::Constant => "constant",
+        }
+    }
+}
+
+impl std::str::FromStr for SymbolKind {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "any" => Ok(SymbolKind::Any),
+            "function" | "func" => Ok(SymbolKind::Function),
+            "method" => Ok(SymbolKind::Method),
+            "class" => Ok(SymbolKind::Class),
+            "interface" => Ok(SymbolKind::Interface),
+            "constant" | "const" => Ok(SymbolKind::Constant),
+            other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid kind {other:?}; expected one of {:?}",
+                SymbolKind::ACCEPTED
+            ))),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 20. SymbolSearchRequest
 // ---------------------------------------------------------------------------
 
+/// How `SymbolSearchRequest.query` should be interpreted: free text fed to
+/// the lexical/fuzzy search engine as before, a raw `regex` crate pattern,
+/// or source for [`crate::query::search_dsl`]'s readable pattern-matching
+/// expression language (quantified literals, character-class sugar,
+/// alternation, named captures) -- see
+/// [`crate::query::search_dsl::compile_symbol_pattern`] for where a
+/// non-`Text` mode actually gets compiled and matched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum QueryMode {
+    Text,
+    Regex,
+    Dsl,
+}
+
+impl QueryMode {
+    const ACCEPTED: &'static [&'static str] = &["text", "regex", "dsl"];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            QueryMode::Text => "text",
+            QueryMode::Regex => "regex",
+            QueryMode::Dsl => "dsl",
+        }
+    }
+}
+
+impl std::str::FromStr for QueryMode {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(QueryMode::Text),
+            "regex" => Ok(QueryMode::Regex),
+            "dsl" => Ok(QueryMode::Dsl),
+            other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid query_mode {other:?}; expected one of {:?}",
+                QueryMode::ACCEPTED
+            ))),
+        }
+    }
+}
+
 /// Parameters for a symbol-search query.
 #[pyclass(frozen, get_all)]
 #[derive(Clone, Debug)]
@@ -1109,29 +1970,86 @@ pub struct SymbolSearchRequest {
     pub kind: String,
     pub file_pattern: Option<String>,
     pub limit: i64,
+    pub query_mode: String,
 }
 
 #[pymethods]
 impl SymbolSearchRequest {
     #[new]
-    #[pyo3(signature = (query, kind="any".to_string(), file_pattern=None, limit=20))]
-    fn new(query: String, kind: String, file_pattern: Option<String>, limit: i64) -> Self {
-        Self {
+    #[pyo3(signature = (query, kind="any".to_string(), file_pattern=None, limit=20, query_mode="text".to_string()))]
+    fn new(
+        query: String,
+        kind: String,
+        file_pattern: Option<String>,
+        limit: i64,
+        query_mode: String,
+    ) -> PyResult<Self> {
+        let kind = kind.parse::<SymbolKind>()?.as_str().to_string();
+        let query_mode = query_mode.parse::<QueryMode>()?.as_str().to_string();
+        Ok(Self {
             query,
             kind,
             file_pattern,
             limit,
-        }
+            query_mode,
+        })
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "SymbolSearchRequest(query={:?}, kind={:?}, limit={})",
-            self.query, self.kind, self.limit,
+            "SymbolSearchRequest(query={:?}, kind={:?}, limit={}, query_mode={:?})",
+            self.query, self.kind, self.limit, self.query_mode,
         )
     }
 }
 
+/// Accepted `direction` values for [`ReferenceRequest`], matching the walks
+/// [`crate::query::references::get_references_impl_inner`] knows how to
+/// perform. Parses case-insensitively and accepts a singular alias for each
+/// plural direction; anything else is rejected with a `PyValueError` instead
+/// of silently falling back to `"both"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Callers,
+    Callees,
+    Both,
+    Implementors,
+    Supers,
+}
+
+impl Direction {
+    const ACCEPTED: &'static [&'static str] =
+        &["callers", "callees", "both", "implementors", "supers"];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Direction::Callers => "callers",
+            Direction::Callees => "callees",
+            Direction::Both => "both",
+            Direction::Implementors => "implementors",
+            Direction::Supers => "supers",
+        }
+    }
+}
+
+impl std::str::FromStr for Direction {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "caller" | "callers" => Ok(Direction::Callers),
+            "callee" | "callees" => Ok(Direction::Callees),
+            "both" => Ok(Direction::Both),
+            "implementor" | "implementors" => Ok(Direction::Implementors),
+            "super" | "supers" => Ok(Direction::Supers),
+            other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid direction {other:?}; expected one of {:?}",
+                Direction::ACCEPTED
+            ))),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 21. ReferenceRequest
 // ---------------------------------------------------------------------------
@@ -1150,13 +2068,19 @@ pub struct ReferenceRequest {
 impl ReferenceRequest {
     #[new]
     #[pyo3(signature = (symbol_name, direction="both".to_string(), depth=1, include_source=false))]
-    fn new(symbol_name: String, direction: String, depth: i64, include_source: bool) -> Self {
-        Self {
+    fn new(
+        symbol_name: String,
+        direction: String,
+        depth: i64,
+        include_source: bool,
+    ) -> PyResult<Self> {
+        let direction = direction.parse::<Direction>()?.as_str().to_string();
+        Ok(Self {
             symbol_name,
             direction,
             depth,
             include_source,
-        }
+        })
     }
 
     fn __repr__(&self) -> String {
@@ -1249,6 +2173,49 @@ impl StructureRequest {
     }
 }
 
+/// Accepted `change_type` values for [`BlastRadiusRequest`], matching
+/// [`crate::query::blast::impact_edges_for_change_type`]'s cases. Parses
+/// case-insensitively and accepts `"removal"` as an alias for `"delete"`;
+/// anything else is rejected with a `PyValueError` instead of silently
+/// falling back to the CALLS-callers-only default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChangeType {
+    Signature,
+    Delete,
+    Rename,
+    Behavior,
+}
+
+impl ChangeType {
+    const ACCEPTED: &'static [&'static str] = &["signature", "delete", "rename", "behavior"];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ChangeType::Signature => "signature",
+            ChangeType::Delete => "delete",
+            ChangeType::Rename => "rename",
+            ChangeType::Behavior => "behavior",
+        }
+    }
+}
+
+impl std::str::FromStr for ChangeType {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "signature" => Ok(ChangeType::Signature),
+            "delete" | "removal" => Ok(ChangeType::Delete),
+            "rename" => Ok(ChangeType::Rename),
+            "behavior" => Ok(ChangeType::Behavior),
+            other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid change_type {other:?}; expected one of {:?}",
+                ChangeType::ACCEPTED
+            ))),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 24. BlastRadiusRequest
 // ---------------------------------------------------------------------------
@@ -1266,12 +2233,13 @@ pub struct BlastRadiusRequest {
 impl BlastRadiusRequest {
     #[new]
     #[pyo3(signature = (symbol_name, change_type="behavior".to_string(), max_depth=3))]
-    fn new(symbol_name: String, change_type: String, max_depth: i64) -> Self {
-        Self {
+    fn new(symbol_name: String, change_type: String, max_depth: i64) -> PyResult<Self> {
+        let change_type = change_type.parse::<ChangeType>()?.as_str().to_string();
+        Ok(Self {
             symbol_name,
             change_type,
             max_depth,
-        }
+        })
     }
 
     fn __repr__(&self) -> String {
@@ -1282,6 +2250,72 @@ impl BlastRadiusRequest {
     }
 }
 
+#[cfg(test)]
+mod request_enum_tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_kind_parses_case_insensitively_and_aliases() {
+        assert_eq!(
+            "Function".parse::<SymbolKind>().unwrap(),
+            SymbolKind::Function
+        );
+        assert_eq!("func".parse::<SymbolKind>().unwrap(), SymbolKind::Function);
+        assert_eq!("CONST".parse::<SymbolKind>().unwrap(), SymbolKind::Constant);
+        assert!("bogus".parse::<SymbolKind>().is_err());
+    }
+
+    #[test]
+    fn test_direction_parses_singular_aliases() {
+        assert_eq!("caller".parse::<Direction>().unwrap(), Direction::Callers);
+        assert_eq!("Both".parse::<Direction>().unwrap(), Direction::Both);
+        assert!("upstream".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_change_type_accepts_removal_alias_for_delete() {
+        assert_eq!("removal".parse::<ChangeType>().unwrap(), ChangeType::Delete);
+        assert_eq!("DELETE".parse::<ChangeType>().unwrap(), ChangeType::Delete);
+        assert!("refactor".parse::<ChangeType>().is_err());
+    }
+
+    #[test]
+    fn test_symbol_search_request_rejects_invalid_kind() {
+        let err = SymbolSearchRequest::new(
+            "q".to_string(),
+            "bogus".to_string(),
+            None,
+            20,
+            "text".to_string(),
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_symbol_search_request_rejects_invalid_query_mode() {
+        let err = SymbolSearchRequest::new(
+            "q".to_string(),
+            "any".to_string(),
+            None,
+            20,
+            "bogus".to_string(),
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_reference_request_normalizes_alias_to_canonical_string() {
+        let req = ReferenceRequest::new("foo".to_string(), "caller".to_string(), 1, false).unwrap();
+        assert_eq!(req.direction, "callers");
+    }
+
+    #[test]
+    fn test_blast_radius_request_normalizes_removal_to_delete() {
+        let req = BlastRadiusRequest::new("foo".to_string(), "removal".to_string(), 3).unwrap();
+        assert_eq!(req.change_type, "delete");
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 25. SymbolSearchResponse
 // ---------------------------------------------------------------------------
@@ -1382,6 +2416,59 @@ impl BlastRadiusResponse {
 // 29. GlobalSymbolURI
 // ---------------------------------------------------------------------------
 
+/// Percent-encode every byte of `s` that isn't an RFC 3986 "unreserved"
+/// character (`A-Z a-z 0-9 - . _ ~`), so the structural delimiters `/` and
+/// `#` used by [`GlobalSymbolURI::uri`] can never appear inside an encoded
+/// component. Operates byte-wise so non-ASCII text round-trips via its UTF-8
+/// encoding, matching [`percent_decode_component`]'s UTF-8 validation.
+fn percent_encode_component(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// Inverse of [`percent_encode_component`]: decode `%XX` escapes back to
+/// bytes and validate the result as UTF-8.
+fn percent_decode_component(s: &str, uri: &str) -> PyResult<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|h| std::str::from_utf8(h).ok());
+            let value = hex.and_then(|h| u8::from_str_radix(h, 16).ok());
+            match value {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 3;
+                }
+                None => {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "Invalid GlobalSymbolURI (bad percent-encoding): {uri}"
+                    )));
+                }
+            }
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid GlobalSymbolURI (invalid UTF-8 after decoding): {uri}"
+        ))
+    })
+}
+
 /// Globally unique symbol identifier across repositories.
 #[pyclass(frozen, get_all)]
 #[derive(Clone, Debug)]
@@ -1402,16 +2489,25 @@ impl GlobalSymbolURI {
         }
     }
 
-    /// The canonical URI string: ``bombe://<repo_id>/<qualified_name>#<file_path>``.
+    /// The canonical URI string: ``bombe://<repo_id>/<qualified_name>#<file_path>``,
+    /// with each component percent-encoded per RFC 3986 so `/` and `#`
+    /// inside a component can't be mistaken for the structural delimiters
+    /// (see [`Self::from_uri`] for the inverse).
     #[getter]
     fn uri(&self) -> String {
         format!(
             "bombe://{}/{}#{}",
-            self.repo_id, self.qualified_name, self.file_path
+            percent_encode_component(&self.repo_id),
+            percent_encode_component(&self.qualified_name),
+            percent_encode_component(&self.file_path)
         )
     }
 
-    /// Parse a ``bombe://`` URI string into a ``GlobalSymbolURI``.
+    /// Parse a ``bombe://`` URI string into a ``GlobalSymbolURI``, splitting
+    /// on the first structural `/` and `#` only, then percent-decoding each
+    /// component -- the inverse of [`Self::uri`]. `from_uri(x.uri())` always
+    /// reproduces `x`, even when its fields contain `/`, `#`, spaces, or
+    /// non-ASCII text.
     #[classmethod]
     fn from_uri(_cls: &Bound<'_, pyo3::types::PyType>, uri: String) -> PyResult<Self> {
         const PREFIX: &str = "bombe://";
@@ -1426,15 +2522,15 @@ impl GlobalSymbolURI {
                 "Invalid GlobalSymbolURI (missing /): {uri}"
             ))
         })?;
-        let repo_id = rest[..slash_idx].to_string();
+        let repo_id = percent_decode_component(&rest[..slash_idx], &uri)?;
         let remainder = &rest[slash_idx + 1..];
         let hash_idx = remainder.find('#').ok_or_else(|| {
             pyo3::exceptions::PyValueError::new_err(format!(
                 "Invalid GlobalSymbolURI (missing #): {uri}"
             ))
         })?;
-        let qualified_name = remainder[..hash_idx].to_string();
-        let file_path = remainder[hash_idx + 1..].to_string();
+        let qualified_name = percent_decode_component(&remainder[..hash_idx], &uri)?;
+        let file_path = percent_decode_component(&remainder[hash_idx + 1..], &uri)?;
         Ok(Self {
             repo_id,
             qualified_name,
@@ -1461,6 +2557,59 @@ impl GlobalSymbolURI {
     }
 }
 
+#[cfg(test)]
+mod global_symbol_uri_tests {
+    use super::*;
+
+    fn round_trip(repo_id: &str, qualified_name: &str, file_path: &str) {
+        let original = GlobalSymbolURI {
+            repo_id: repo_id.to_string(),
+            qualified_name: qualified_name.to_string(),
+            file_path: file_path.to_string(),
+        };
+        Python::with_gil(|py| {
+            let cls_obj = py.get_type::<GlobalSymbolURI>();
+            let parsed = GlobalSymbolURI::from_uri(&cls_obj, original.uri()).unwrap();
+            assert_eq!(parsed.repo_id, original.repo_id);
+            assert_eq!(parsed.qualified_name, original.qualified_name);
+            assert_eq!(parsed.file_path, original.file_path);
+        });
+    }
+
+    #[test]
+    fn test_round_trip_plain_components() {
+        round_trip("repo", "pkg.Foo.bar", "src/pkg/foo.py");
+    }
+
+    #[test]
+    fn test_round_trip_qualified_name_with_slash() {
+        round_trip("repo", "Vec<Box<dyn Foo>>/bar", "a.rs");
+    }
+
+    #[test]
+    fn test_round_trip_file_path_with_hash() {
+        round_trip("repo", "foo", "weird#file.py");
+    }
+
+    #[test]
+    fn test_round_trip_spaces_and_non_ascii() {
+        round_trip(
+            "my repo",
+            "caf\u{e9}.fn \u{6587}\u{5b57}",
+            "dir with spaces/\u{e9}.py",
+        );
+    }
+
+    #[test]
+    fn test_from_uri_rejects_bad_percent_encoding() {
+        Python::with_gil(|py| {
+            let cls_obj = py.get_type::<GlobalSymbolURI>();
+            let err = GlobalSymbolURI::from_uri(&cls_obj, "bombe://repo/%zz#file.py".to_string());
+            assert!(err.is_err());
+        });
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 30. ShardInfo
 // ---------------------------------------------------------------------------
@@ -1476,6 +2625,13 @@ pub struct ShardInfo {
     pub last_indexed_at: Option<String>,
     pub symbol_count: i64,
     pub edge_count: i64,
+    /// Root hash of this shard's symbol-set Merkle-Patricia trie (see
+    /// [`crate::store::sharding::merkle`]), or `None` if it hasn't been
+    /// computed yet. Two shards with equal roots are known to hold
+    /// identical symbol sets without comparing a single symbol; unequal
+    /// roots are the trigger for [`crate::store::sharding::merkle::diff_shards`]
+    /// to find exactly what changed.
+    pub merkle_root: Option<String>,
 }
 
 #[pymethods]
@@ -1489,6 +2645,7 @@ impl ShardInfo {
         last_indexed_at=None,
         symbol_count=0,
         edge_count=0,
+        merkle_root=None,
     ))]
     fn new(
         repo_id: String,
@@ -1498,6 +2655,7 @@ impl ShardInfo {
         last_indexed_at: Option<String>,
         symbol_count: i64,
         edge_count: i64,
+        merkle_root: Option<String>,
     ) -> Self {
         Self {
             repo_id,
@@ -1507,6 +2665,7 @@ impl ShardInfo {
             last_indexed_at,
             symbol_count,
             edge_count,
+            merkle_root,
         }
     }
 
@@ -1617,6 +2776,13 @@ pub struct FederatedQueryResult {
     pub shards_queried: i64,
     pub shards_failed: i64,
     pub elapsed_ms: i64,
+    /// The route(s) actually used to reach the queried shard(s), as chosen
+    /// by [`crate::store::sharding::path_vector::route_shards_for_group`]
+    /// from a `PathVectorTable`'s learned reachability -- each entry a
+    /// `{"shard_id": ..., "path": [...]}` dict. Empty when the query
+    /// broadcast to every enabled shard instead of routing (no learned
+    /// route covered the lookup, or the caller didn't route at all).
+    pub routes: Py<PyAny>,
 }
 
 #[pymethods]
@@ -1629,6 +2795,7 @@ impl FederatedQueryResult {
         shards_queried=0,
         shards_failed=0,
         elapsed_ms=0,
+        routes=None,
     ))]
     fn new(
         py: Python<'_>,
@@ -1638,9 +2805,11 @@ impl FederatedQueryResult {
         shards_queried: i64,
         shards_failed: i64,
         elapsed_ms: i64,
+        routes: Option<Py<PyAny>>,
     ) -> Self {
         let results = results.unwrap_or_else(|| PyList::empty(py).into_any().unbind());
         let shard_reports = shard_reports.unwrap_or_else(|| PyList::empty(py).into_any().unbind());
+        let routes = routes.unwrap_or_else(|| PyList::empty(py).into_any().unbind());
         Self {
             results,
             shard_reports,
@@ -1648,6 +2817,7 @@ impl FederatedQueryResult {
             shards_queried,
             shards_failed,
             elapsed_ms,
+            routes,
         }
     }
 
@@ -1660,6 +2830,124 @@ impl FederatedQueryResult {
     }
 }
 
+// ---------------------------------------------------------------------------
+// 34. BackupReport
+// ---------------------------------------------------------------------------
+
+/// Outcome of a `Database.backup_to` call.
+#[pyclass(frozen, get_all)]
+pub struct BackupReport {
+    pub path: String,
+    pub pages_copied: i64,
+    pub restarted: bool,
+}
+
+#[pymethods]
+impl BackupReport {
+    #[new]
+    fn new(path: String, pages_copied: i64, restarted: bool) -> Self {
+        Self {
+            path,
+            pages_copied,
+            restarted,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BackupReport(path={:?}, pages_copied={}, restarted={})",
+            self.path, self.pages_copied, self.restarted,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 35. PoolStats
+// ---------------------------------------------------------------------------
+
+/// Idle/active/max-size snapshot of `Database`'s read and write connection
+/// pools, as returned by `Database.pool_stats()`.
+#[pyclass(frozen, get_all)]
+pub struct PoolStats {
+    pub read_idle: i64,
+    pub read_active: i64,
+    pub read_max: i64,
+    pub write_idle: i64,
+    pub write_active: i64,
+    pub write_max: i64,
+}
+
+#[pymethods]
+impl PoolStats {
+    #[new]
+    fn new(
+        read_idle: i64,
+        read_active: i64,
+        read_max: i64,
+        write_idle: i64,
+        write_active: i64,
+        write_max: i64,
+    ) -> Self {
+        Self {
+            read_idle,
+            read_active,
+            read_max,
+            write_idle,
+            write_active,
+            write_max,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PoolStats(read={}/{} idle, write={}/{} idle)",
+            self.read_idle, self.read_max, self.write_idle, self.write_max,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 36. SemanticSearchRequest
+// ---------------------------------------------------------------------------
+
+/// Parameters for an embedding-vector similarity search, as opposed to
+/// [`SymbolSearchRequest`]'s name/text-driven query. `embedding` is the
+/// caller's query vector (already embedded -- see
+/// [`crate::indexer::embedding`] for turning text into one), ranked against
+/// stored per-symbol vectors by [`crate::query::semantic_index`]'s
+/// cosine-similarity HNSW index, locally or (via
+/// [`crate::query::federated::semantic::federated_semantic_search`])
+/// fanned out across every shard in a group.
+#[pyclass(frozen, get_all)]
+#[derive(Clone, Debug)]
+pub struct SemanticSearchRequest {
+    pub embedding: Vec<f32>,
+    pub top_k: i64,
+    pub model: String,
+}
+
+#[pymethods]
+impl SemanticSearchRequest {
+    #[new]
+    #[pyo3(signature = (embedding, top_k=10, model="default".to_string()))]
+    fn new(embedding: Vec<f32>, top_k: i64, model: String) -> Self {
+        Self {
+            embedding,
+            top_k,
+            model,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SemanticSearchRequest(dim={}, top_k={}, model={:?})",
+            self.embedding.len(),
+            self.top_k,
+            self.model,
+        )
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Module registration helper
 // ---------------------------------------------------------------------------
@@ -1674,6 +2962,7 @@ pub fn register_models(m: &Bound<'_, pyo3::types::PyModule>) -> PyResult<()> {
     // Helper functions
     m.add_function(wrap_pyfunction!(_signature_hash, m)?)?;
     m.add_function(wrap_pyfunction!(_repo_id_from_path, m)?)?;
+    m.add_function(wrap_pyfunction!(match_symbols, m)?)?;
 
     // Classes
     m.add_class::<FileRecord>()?;
@@ -1709,6 +2998,9 @@ pub fn register_models(m: &Bound<'_, pyo3::types::PyModule>) -> PyResult<()> {
     m.add_class::<CrossRepoEdge>()?;
     m.add_class::<ShardGroupConfig>()?;
     m.add_class::<FederatedQueryResult>()?;
+    m.add_class::<BackupReport>()?;
+    m.add_class::<PoolStats>()?;
+    m.add_class::<SemanticSearchRequest>()?;
 
     Ok(())
 }
@@ -3,7 +3,7 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use pyo3::prelude::*;
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 
 use crate::errors::BombeResult;
 
@@ -30,76 +30,286 @@ fn resolve_symbol(
     }
 }
 
-fn risk_level(direct: usize, transitive: usize) -> &'static str {
-    let total = direct + transitive;
-    if total >= 10 {
+/// One relationship a blast radius traversal follows: `sql` binds `?1` to
+/// the current frontier node and returns `(next_id, line_number, name,
+/// file_path, dispatch, pagerank_score)` rows; `via` labels hits found
+/// through it in the `impact` object so a caller can tell a CALLS caller
+/// from an IMPLEMENTS implementor.
+struct ImpactEdge {
+    sql: &'static str,
+    via: &'static str,
+}
+
+const CALLERS_EDGE: ImpactEdge = ImpactEdge {
+    sql: "SELECT e.source_id, e.line_number, s.name, s.file_path, e.dispatch, s.pagerank_score \
+          FROM edges e JOIN symbols s ON s.id = e.source_id \
+          WHERE e.relationship = 'CALLS' AND e.target_type = 'symbol' AND e.target_id = ?1;",
+    via: "CALLS",
+};
+const IMPLEMENTORS_EDGE: ImpactEdge = ImpactEdge {
+    sql: "SELECT e.source_id, e.line_number, s.name, s.file_path, e.dispatch, s.pagerank_score \
+          FROM edges e JOIN symbols s ON s.id = e.source_id \
+          WHERE e.relationship = 'IMPLEMENTS' AND e.target_type = 'symbol' AND e.target_id = ?1;",
+    via: "IMPLEMENTS",
+};
+const SUBCLASSES_EDGE: ImpactEdge = ImpactEdge {
+    sql: "SELECT e.source_id, e.line_number, s.name, s.file_path, e.dispatch, s.pagerank_score \
+          FROM edges e JOIN symbols s ON s.id = e.source_id \
+          WHERE e.relationship = 'EXTENDS' AND e.target_type = 'symbol' AND e.target_id = ?1;",
+    via: "EXTENDS",
+};
+// Mirrors the reference `walk`'s `"supers"` direction: the super
+// type/method this symbol itself extends/implements, reached by walking
+// the edge the opposite way (source_id = the symbol, not the target).
+const SUPERS_EDGE: ImpactEdge = ImpactEdge {
+    sql: "SELECT e.target_id, e.line_number, s.name, s.file_path, e.dispatch, s.pagerank_score \
+          FROM edges e JOIN symbols s ON s.id = e.target_id \
+          WHERE e.relationship IN ('EXTENDS', 'IMPLEMENTS') AND e.source_type = 'symbol' AND e.source_id = ?1;",
+    via: "SUPERS",
+};
+
+/// Which relationships a blast radius follows for a given `change_type`,
+/// and the effective traversal depth (`"behavior"` only looks at direct
+/// callers, regardless of the caller's `max_depth`).
+///
+/// - `"signature"`: CALLS callers only — an argument/return contract break
+///   only affects call sites.
+/// - `"delete"`: CALLS callers, plus IMPLEMENTS/EXTENDS subclasses and
+///   implementors — removing a trait method breaks every implementor.
+/// - `"rename"`: everything `"delete"` follows, plus `SUPERS` so an
+///   overridden super method/interface is flagged too.
+/// - `"behavior"`: direct CALLS callers only, depth capped at 1 — deeper
+///   impact from a behavior change is informational, not traceable via the
+///   call graph alone.
+/// - anything else: falls back to the original CALLS-callers-only behavior.
+fn impact_edges_for_change_type(change_type: &str, max_depth: i64) -> (Vec<ImpactEdge>, i64) {
+    match change_type {
+        "signature" => (vec![CALLERS_EDGE], max_depth),
+        "delete" => (
+            vec![CALLERS_EDGE, IMPLEMENTORS_EDGE, SUBCLASSES_EDGE],
+            max_depth,
+        ),
+        "rename" => (
+            vec![
+                CALLERS_EDGE,
+                IMPLEMENTORS_EDGE,
+                SUBCLASSES_EDGE,
+                SUPERS_EDGE,
+            ],
+            max_depth,
+        ),
+        "behavior" => (vec![CALLERS_EDGE], 1),
+        _ => (vec![CALLERS_EDGE], max_depth),
+    }
+}
+
+/// Classifies the CALLS edge from `caller_id` to `callee_id` by whether the
+/// caller's variable actually consumes the callee's value, using a
+/// `DATA_FLOW` edge (`source_id` = the callee's returned value, `target_id`
+/// = the caller's variable) recorded alongside the `CALLS` edge: a `return`
+/// dispatch means the caller assigns/consumes the return value, any other
+/// `DATA_FLOW` hit means the value reaches the caller via an argument, and
+/// no hit at all means the caller invokes the callee but discards whatever
+/// it produces.
+fn classify_dataflow(
+    conn: &Connection,
+    callee_id: i64,
+    caller_id: i64,
+) -> BombeResult<&'static str> {
+    let mut stmt = conn.prepare(
+        "SELECT dispatch FROM edges \
+         WHERE relationship = 'DATA_FLOW' AND source_id = ?1 AND target_id = ?2 LIMIT 1;",
+    )?;
+    let dispatch: Option<Option<String>> = stmt
+        .query_row(rusqlite::params![callee_id, caller_id], |row| row.get(0))
+        .optional()?;
+    Ok(match dispatch {
+        Some(Some(ref d)) if d == "return" => "consumes_return",
+        Some(_) => "passes_argument",
+        None => "call_only",
+    })
+}
+
+/// Buckets risk by whichever signal is larger: the raw caller count (the
+/// original behavior) or the pagerank-weighted impact normalized against
+/// the index-wide mean pagerank. Normalizing this way means "a couple of
+/// callers worth one mean-importance symbol each" reads as roughly the same
+/// risk as "one caller worth two mean-importance symbols" — so a handful of
+/// high-centrality callers escalates the bucket the same way a crowd of
+/// low-importance ones would, instead of getting diluted by raw count.
+fn risk_level(
+    direct: usize,
+    transitive: usize,
+    weighted_impact: f64,
+    mean_pagerank: f64,
+) -> (&'static str, f64) {
+    let total = (direct + transitive) as f64;
+    let normalized_impact = if mean_pagerank > 0.0 {
+        weighted_impact / mean_pagerank
+    } else {
+        0.0
+    };
+    let escalation = total.max(normalized_impact);
+    let level = if escalation >= 10.0 {
         "high"
-    } else if total >= 3 {
+    } else if escalation >= 3.0 {
         "medium"
     } else {
         "low"
-    }
+    };
+    (level, normalized_impact)
 }
 
+#[tracing::instrument(
+    skip(conn),
+    fields(operation = "get_blast_radius", max_depth, result_count = tracing::field::Empty)
+)]
 pub fn get_blast_radius_impl(
     conn: &Connection,
     symbol_name: &str,
     change_type: &str,
     max_depth: i64,
+    mode: &str,
 ) -> BombeResult<HashMap<String, serde_json::Value>> {
-    let target = resolve_symbol(conn, symbol_name)?.ok_or_else(|| {
-        crate::errors::BombeError::Query(format!("Symbol not found: {symbol_name}"))
+    let result = crate::telemetry::timed_query("get_blast_radius", || {
+        get_blast_radius_impl_inner(conn, symbol_name, change_type, max_depth, mode)
     })?;
+    if let Some(total) = result
+        .get("impact")
+        .and_then(|v| v.get("total_affected_symbols"))
+        .and_then(|v| v.as_i64())
+    {
+        tracing::Span::current().record("result_count", total);
+    }
+    Ok(result)
+}
+
+fn get_blast_radius_impl_inner(
+    conn: &Connection,
+    symbol_name: &str,
+    change_type: &str,
+    max_depth: i64,
+    mode: &str,
+) -> BombeResult<HashMap<String, serde_json::Value>> {
+    let target = match resolve_symbol(conn, symbol_name)? {
+        Some(t) => t,
+        None => {
+            let candidate =
+                crate::query::symbol_resolution::resolve_symbol_fuzzy(conn, symbol_name)?
+                    .ok_or_else(|| {
+                        crate::errors::BombeError::Query(format!("Symbol not found: {symbol_name}"))
+                    })?;
+            (candidate.id, candidate.name, candidate.file_path)
+        }
+    };
     let (target_id, target_name, target_file) = target;
 
+    let (edge_specs, effective_depth) = impact_edges_for_change_type(change_type, max_depth);
+    let mut edge_stmts: Vec<(rusqlite::Statement<'_>, &'static str)> = Vec::new();
+    for edge in &edge_specs {
+        edge_stmts.push((conn.prepare(edge.sql)?, edge.via));
+    }
+
     let mut queue: VecDeque<(i64, i64)> = VecDeque::new();
     queue.push_back((target_id, 0));
     let mut visited: HashSet<i64> = HashSet::new();
     visited.insert(target_id);
     let mut direct_callers: Vec<serde_json::Value> = Vec::new();
     let mut transitive_callers: Vec<serde_json::Value> = Vec::new();
-
-    let mut stmt = conn.prepare(
-        "SELECT e.source_id, e.line_number, s.name, s.file_path \
-         FROM edges e JOIN symbols s ON s.id = e.source_id \
-         WHERE e.relationship = 'CALLS' AND e.target_type = 'symbol' AND e.target_id = ?1;",
-    )?;
+    let mut tainted_paths: Vec<serde_json::Value> = Vec::new();
+    let mut node_names: HashMap<i64, String> = HashMap::new();
+    node_names.insert(target_id, target_name.clone());
+    let dataflow_mode = mode == "dataflow";
 
     while let Some((current, depth)) = queue.pop_front() {
-        if depth >= max_depth {
+        if depth >= effective_depth {
             continue;
         }
-        let rows: Vec<(i64, Option<i64>, String, String)> = stmt
-            .query_map(rusqlite::params![current], |row| {
-                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        for (source_id, line_number, name, file_path) in rows {
-            if visited.contains(&source_id) {
-                continue;
-            }
-            visited.insert(source_id);
-            let next_depth = depth + 1;
-            let item = serde_json::json!({
-                "name": name,
-                "file": file_path,
-                "line": line_number.unwrap_or(0),
-            });
-            if next_depth == 1 {
-                direct_callers.push(item);
-            } else {
-                let mut item = item;
-                item.as_object_mut()
-                    .unwrap()
-                    .insert("depth".to_string(), serde_json::json!(next_depth));
-                transitive_callers.push(item);
+        for (stmt, via) in edge_stmts.iter_mut() {
+            let rows: Vec<(
+                i64,
+                Option<i64>,
+                String,
+                String,
+                Option<String>,
+                Option<f64>,
+            )> = stmt
+                .query_map(rusqlite::params![current], |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                    ))
+                })?
+                .filter_map(|r| r.ok())
+                .collect();
+
+            for (source_id, line_number, name, file_path, dispatch, pagerank_score) in rows {
+                if visited.contains(&source_id) {
+                    continue;
+                }
+                visited.insert(source_id);
+                node_names.insert(source_id, name.clone());
+                let next_depth = depth + 1;
+                let pagerank_score = pagerank_score.unwrap_or(0.0);
+                let mut should_enqueue = true;
+                let mut item = serde_json::json!({
+                    "name": name,
+                    "file": file_path,
+                    "line": line_number.unwrap_or(0),
+                    "dispatch": dispatch,
+                    "via": via,
+                    "pagerank_score": pagerank_score,
+                });
+                if dataflow_mode && *via == "CALLS" {
+                    let classification = classify_dataflow(conn, current, source_id)?;
+                    item.as_object_mut()
+                        .unwrap()
+                        .insert("dataflow".to_string(), serde_json::json!(classification));
+                    if classification == "call_only" {
+                        should_enqueue = false;
+                    } else {
+                        tainted_paths.push(serde_json::json!({
+                            "from": node_names.get(&current).cloned().unwrap_or_default(),
+                            "to": name,
+                            "classification": classification,
+                        }));
+                    }
+                }
+                if next_depth == 1 {
+                    direct_callers.push(item);
+                } else {
+                    item.as_object_mut()
+                        .unwrap()
+                        .insert("depth".to_string(), serde_json::json!(next_depth));
+                    transitive_callers.push(item);
+                }
+                if should_enqueue {
+                    queue.push_back((source_id, next_depth));
+                }
             }
-            queue.push_back((source_id, next_depth));
         }
     }
 
+    let caller_pagerank = |item: &serde_json::Value| -> f64 {
+        item.get("pagerank_score")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0)
+    };
+    direct_callers.sort_by(|a, b| {
+        caller_pagerank(b)
+            .partial_cmp(&caller_pagerank(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    transitive_callers.sort_by(|a, b| {
+        caller_pagerank(b)
+            .partial_cmp(&caller_pagerank(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
     let mut affected_files: HashSet<String> = HashSet::new();
     affected_files.insert(target_file.clone());
     for caller in direct_callers.iter().chain(transitive_callers.iter()) {
@@ -110,9 +320,24 @@ pub fn get_blast_radius_impl(
     let mut affected_files: Vec<String> = affected_files.into_iter().collect();
     affected_files.sort();
 
-    let risk = risk_level(direct_callers.len(), transitive_callers.len());
+    let mean_pagerank: f64 = conn
+        .query_row("SELECT AVG(pagerank_score) FROM symbols;", [], |row| {
+            row.get(0)
+        })
+        .unwrap_or(0.0);
+    let weighted_impact: f64 = direct_callers
+        .iter()
+        .chain(transitive_callers.iter())
+        .map(caller_pagerank)
+        .sum();
+    let (risk, normalized_impact) = risk_level(
+        direct_callers.len(),
+        transitive_callers.len(),
+        weighted_impact,
+        mean_pagerank,
+    );
     let summary = format!(
-        "{risk} - {} direct callers, {} transitive dependents",
+        "{risk} - {} direct callers, {} transitive dependents (weighted impact {weighted_impact:.2}, normalized {normalized_impact:.2})",
         direct_callers.len(),
         transitive_callers.len()
     );
@@ -135,22 +360,43 @@ pub fn get_blast_radius_impl(
             "total_affected_symbols": direct_callers.len() + transitive_callers.len(),
             "total_affected_files": affected_files.len(),
             "risk_assessment": summary,
+            "risk_level": risk,
+            "weighted_impact": weighted_impact,
+            "normalized_impact": normalized_impact,
         }),
     );
+    result.insert("mode".to_string(), serde_json::json!(mode));
+    if dataflow_mode {
+        result.insert(
+            "tainted_paths".to_string(),
+            serde_json::json!(tainted_paths),
+        );
+    }
+    if change_type == "behavior" {
+        result.insert(
+            "note".to_string(),
+            serde_json::json!(
+                "behavior changes only report direct callers; transitive impact beyond depth 1 \
+                 isn't traceable via the call graph and is informational only."
+            ),
+        );
+    }
 
     Ok(result)
 }
 
 #[pyfunction]
+#[pyo3(signature = (db, symbol_name, change_type, max_depth, mode="default"))]
 pub fn get_blast_radius(
     py: Python<'_>,
     db: &crate::store::database::Database,
     symbol_name: &str,
     change_type: &str,
     max_depth: i64,
+    mode: &str,
 ) -> PyResult<PyObject> {
     let conn = db.connect_internal()?;
-    let result = get_blast_radius_impl(&conn, symbol_name, change_type, max_depth)?;
+    let result = get_blast_radius_impl(&conn, symbol_name, change_type, max_depth, mode)?;
     let json_str = serde_json::to_string(&result)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
     let json_module = py.import("json")?;
@@ -0,0 +1,115 @@
+//! Generic inclusive/exclusive/unbounded numeric range filters.
+//!
+//! Several query endpoints want to bound a numeric symbol attribute
+//! (pagerank, line span, caller/callee counts, ...) without committing to
+//! inclusive-vs-exclusive semantics up front. [`Bound`] represents one side
+//! of a range; [`BoundsRange`] pairs a lower and upper `Bound` and knows how
+//! to push itself into a SQL `WHERE` clause (for attributes the database can
+//! filter directly) or test a value in-process (for attributes, like
+//! caller/callee counts, that are only known after a post-query lookup).
+
+use rusqlite::types::ToSql;
+
+/// One side of a numeric range: inclusive, exclusive, or absent.
+#[derive(Clone, Copy, Debug)]
+pub enum Bound<T> {
+    Inclusive(T),
+    Exclusive(T),
+    Unbounded,
+}
+
+/// A lower/upper pair of [`Bound`]s over one numeric attribute.
+#[derive(Clone, Copy, Debug)]
+pub struct BoundsRange<T> {
+    pub lower: Bound<T>,
+    pub upper: Bound<T>,
+}
+
+impl<T> BoundsRange<T> {
+    pub fn unbounded() -> Self {
+        BoundsRange {
+            lower: Bound::Unbounded,
+            upper: Bound::Unbounded,
+        }
+    }
+}
+
+impl BoundsRange<f64> {
+    /// Builds a range from the flat `min`/`max` (+ exclusive flags) shape
+    /// `search_symbols`'s pyfunction signature accepts them in.
+    pub fn from_min_max(
+        min: Option<f64>,
+        min_exclusive: bool,
+        max: Option<f64>,
+        max_exclusive: bool,
+    ) -> Self {
+        let lower = match min {
+            Some(v) if min_exclusive => Bound::Exclusive(v),
+            Some(v) => Bound::Inclusive(v),
+            None => Bound::Unbounded,
+        };
+        let upper = match max {
+            Some(v) if max_exclusive => Bound::Exclusive(v),
+            Some(v) => Bound::Inclusive(v),
+            None => Bound::Unbounded,
+        };
+        BoundsRange { lower, upper }
+    }
+
+    /// Appends ` AND <expr> <op> ?N` for each bounded side of this range to
+    /// `sql`, pushing the matching parameter and advancing `param_idx`.
+    /// `expr` is a raw SQL expression (a column name or computation over
+    /// one), not itself parameterized.
+    pub fn push_sql(
+        &self,
+        sql: &mut String,
+        params: &mut Vec<Box<dyn ToSql>>,
+        param_idx: &mut usize,
+        expr: &str,
+    ) {
+        if let Some((op, v)) = match self.lower {
+            Bound::Inclusive(v) => Some((">=", v)),
+            Bound::Exclusive(v) => Some((">", v)),
+            Bound::Unbounded => None,
+        } {
+            sql.push_str(&format!(" AND {expr} {op} ?{param_idx}"));
+            params.push(Box::new(v));
+            *param_idx += 1;
+        }
+        if let Some((op, v)) = match self.upper {
+            Bound::Inclusive(v) => Some(("<=", v)),
+            Bound::Exclusive(v) => Some(("<", v)),
+            Bound::Unbounded => None,
+        } {
+            sql.push_str(&format!(" AND {expr} {op} ?{param_idx}"));
+            params.push(Box::new(v));
+            *param_idx += 1;
+        }
+    }
+}
+
+impl BoundsRange<i64> {
+    /// Builds an inclusive-only range from plain `min`/`max` values — the
+    /// shape post-filtered derived counts (callers/callees) need, since
+    /// there's no SQL clause to phrase an exclusive variant against.
+    pub fn from_min_max(min: Option<i64>, max: Option<i64>) -> Self {
+        BoundsRange {
+            lower: min.map_or(Bound::Unbounded, Bound::Inclusive),
+            upper: max.map_or(Bound::Unbounded, Bound::Inclusive),
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let lower_ok = match self.lower {
+            Bound::Inclusive(b) => value >= b,
+            Bound::Exclusive(b) => value > b,
+            Bound::Unbounded => true,
+        };
+        let upper_ok = match self.upper {
+            Bound::Inclusive(b) => value <= b,
+            Bound::Exclusive(b) => value < b,
+            Bound::Unbounded => true,
+        };
+        lower_ok && upper_ok
+    }
+}
@@ -0,0 +1,119 @@
+//! Model-aware BPE token counting for context-budget enforcement.
+//!
+//! [`crate::query::tokenizer::estimate_tokens`] is a byte-length heuristic and
+//! can drift far from what a real tokenizer would count, which makes
+//! `query::context`'s token budget loose. This loads a real byte-pair-encoding
+//! merge table for a small set of named encodings (`cl100k`, `o200k` — the
+//! encodings behind the common GPT-family models) and counts tokens the same
+//! way those encodings actually would: repeatedly merge the highest-priority
+//! adjacent byte-pair until no merge applies, then count what's left.
+//!
+//! Merge tables are plain text files of `"<token_a> <token_b>"` pairs ordered
+//! by merge priority (rank = line number), resolved from `BOMBE_BPE_TABLE_DIR`
+//! (falling back to `<crate_root>/data/bpe/<encoding>.bpe`). An encoding whose
+//! table can't be found falls back to [`estimate_tokens`].
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// Named encodings recognized by the `tokenizer` parameter on `get_context`.
+/// Anything else (including the empty string) falls back to the heuristic.
+const KNOWN_ENCODINGS: &[&str] = &["cl100k", "o200k"];
+
+/// Loaded merge table for one named encoding, cached process-wide so repeat
+/// `get_context` calls don't re-read and re-parse the table file.
+pub struct BpeEncoder {
+    encoding: String,
+    /// Merge rank by byte-pair; lower rank merges first, as in tiktoken.
+    merge_ranks: HashMap<(Vec<u8>, Vec<u8>), u32>,
+}
+
+static ENCODER_CACHE: LazyLock<Mutex<HashMap<String, Option<Arc<BpeEncoder>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the cached encoder for `encoding`, loading and caching it on first
+/// use. `None` means `encoding` is unrecognized or its table couldn't be
+/// found, in which case callers should fall back to `estimate_tokens`.
+pub fn load_cached(encoding: &str) -> Option<Arc<BpeEncoder>> {
+    let mut cache = ENCODER_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(entry) = cache.get(encoding) {
+        return entry.clone();
+    }
+    let loaded = BpeEncoder::load(encoding).map(Arc::new);
+    cache.insert(encoding.to_string(), loaded.clone());
+    loaded
+}
+
+impl BpeEncoder {
+    /// Loads the merge table for `encoding`, returning `None` if `encoding`
+    /// isn't recognized or no table file can be found for it.
+    fn load(encoding: &str) -> Option<Self> {
+        if !KNOWN_ENCODINGS.contains(&encoding) {
+            return None;
+        }
+        let content = std::fs::read_to_string(Self::table_path(encoding)).ok()?;
+        let mut merge_ranks = HashMap::new();
+        for (rank, line) in content.lines().enumerate() {
+            let mut parts = line.split_whitespace();
+            let (Some(a), Some(b)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            merge_ranks.insert((a.as_bytes().to_vec(), b.as_bytes().to_vec()), rank as u32);
+        }
+        if merge_ranks.is_empty() {
+            return None;
+        }
+        Some(BpeEncoder {
+            encoding: encoding.to_string(),
+            merge_ranks,
+        })
+    }
+
+    fn table_path(encoding: &str) -> PathBuf {
+        if let Ok(dir) = std::env::var("BOMBE_BPE_TABLE_DIR") {
+            return PathBuf::from(dir).join(format!("{encoding}.bpe"));
+        }
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("data/bpe")
+            .join(format!("{encoding}.bpe"))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.encoding
+    }
+
+    /// Counts tokens in `text` by repeatedly merging the highest-priority
+    /// adjacent byte-pair (lowest rank) until none of the remaining adjacent
+    /// pairs appear in the merge table; the token count is the number of
+    /// symbols left when the loop stops.
+    pub fn count_tokens(&self, text: &str) -> i64 {
+        if text.is_empty() {
+            return 0;
+        }
+        let mut symbols: Vec<Vec<u8>> = text.bytes().map(|b| vec![b]).collect();
+        loop {
+            let mut best: Option<(usize, u32)> = None;
+            for i in 0..symbols.len().saturating_sub(1) {
+                if let Some(&rank) = self.merge_ranks.get(&(symbols[i].clone(), symbols[i + 1].clone())) {
+                    let better = match best {
+                        Some((_, best_rank)) => rank < best_rank,
+                        None => true,
+                    };
+                    if better {
+                        best = Some((i, rank));
+                    }
+                }
+            }
+            let Some((i, _)) = best else {
+                break;
+            };
+            let mut merged = symbols[i].clone();
+            merged.extend_from_slice(&symbols[i + 1]);
+            symbols.splice(i..=i + 1, [merged]);
+        }
+        symbols.len() as i64
+    }
+}
@@ -0,0 +1,272 @@
+//! Shortest-call-path query backend.
+//!
+//! `get_blast_radius`/`trace_data_flow_between` answer "what's reachable"
+//! and "is there a route", but a reader staring at a blast radius full of
+//! transitive callers often wants the concrete chain connecting two specific
+//! symbols (e.g. "how does `handle_request` end up calling `unsafe_free`").
+//! `get_call_path_impl` answers that directly: the shortest path of CALLS
+//! edges from `from_symbol` to `to_symbol`, as an ordered list of hops.
+//!
+//! Implemented as a bidirectional BFS: one frontier expands forward from
+//! `from` along `source_id -> target_id` CALLS edges, one expands backward
+//! from `to` along `target_id -> source_id` CALLS edges, alternating
+//! expansion of whichever frontier is currently smaller each round (the
+//! standard bidirectional-BFS optimization — it keeps combined frontier
+//! growth roughly the square root of a one-sided search's). When a node
+//! turns up in both `forward_seen` and `backward_seen`, the path is
+//! reconstructed by walking `forward_parent` back to `from` and
+//! `backward_parent` forward to `to`, then splicing the two halves together
+//! at the meeting node.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+
+use crate::errors::{BombeError, BombeResult};
+use crate::query::guards::{
+    adaptive_graph_cap, clamp_depth, truncate_query, MAX_GRAPH_EDGES, MAX_GRAPH_VISITED,
+    MAX_REFERENCE_DEPTH,
+};
+
+/// One hop of a call path.
+#[derive(Clone, Debug)]
+struct CallHop {
+    name: String,
+    file_path: String,
+    line_number: i64,
+}
+
+fn resolve_symbol(
+    conn: &Connection,
+    symbol_name: &str,
+) -> BombeResult<Option<(i64, String, String, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, file_path, line_number FROM symbols \
+         WHERE qualified_name = ?1 OR name = ?1 \
+         ORDER BY pagerank_score DESC LIMIT 1;",
+    )?;
+    let result = stmt.query_row(rusqlite::params![symbol_name], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<i64>>(3)?.unwrap_or(0),
+        ))
+    });
+    match result {
+        Ok(r) => Ok(Some(r)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Shortest CALLS path from `from_symbol` to `to_symbol`, as an ordered list
+/// of hops starting with `from_symbol` itself and ending with `to_symbol`.
+/// Returns an empty path (not an error) when no route exists within
+/// `max_depth`, or when either symbol can't be resolved.
+pub fn get_call_path_impl(
+    conn: &Connection,
+    from_symbol: &str,
+    to_symbol: &str,
+    max_depth: i64,
+) -> BombeResult<Vec<serde_json::Value>> {
+    let from_symbol = truncate_query(from_symbol);
+    let to_symbol = truncate_query(to_symbol);
+    let bounded_depth = clamp_depth(max_depth, MAX_REFERENCE_DEPTH);
+
+    let from = resolve_symbol(conn, &from_symbol)?
+        .ok_or_else(|| BombeError::Query(format!("Symbol not found: {from_symbol}")))?;
+    let to = resolve_symbol(conn, &to_symbol)?
+        .ok_or_else(|| BombeError::Query(format!("Symbol not found: {to_symbol}")))?;
+    let (from_id, from_name, from_file, from_line) = from;
+    let (to_id, to_name, to_file, to_line) = to;
+
+    let mut nodes: HashMap<i64, CallHop> = HashMap::new();
+    nodes.insert(
+        from_id,
+        CallHop {
+            name: from_name,
+            file_path: from_file,
+            line_number: from_line,
+        },
+    );
+    nodes.insert(
+        to_id,
+        CallHop {
+            name: to_name,
+            file_path: to_file,
+            line_number: to_line,
+        },
+    );
+
+    if from_id == to_id {
+        return Ok(vec![hop_json(&nodes[&from_id])]);
+    }
+
+    let total_symbols: i64 = conn
+        .query_row("SELECT COUNT(*) FROM symbols;", [], |row| row.get(0))
+        .unwrap_or(0);
+    let dynamic_visited_cap = adaptive_graph_cap(total_symbols, MAX_GRAPH_VISITED, Some(128));
+    let dynamic_edge_cap = 256i64.max(MAX_GRAPH_EDGES.min(dynamic_visited_cap * 2));
+
+    let mut forward_stmt = conn.prepare(
+        "SELECT e.target_id, e.line_number, s.name, s.file_path \
+         FROM edges e JOIN symbols s ON s.id = e.target_id \
+         WHERE e.relationship = 'CALLS' AND e.source_type = 'symbol' AND e.source_id = ?1;",
+    )?;
+    let mut backward_stmt = conn.prepare(
+        "SELECT e.source_id, e.line_number, s.name, s.file_path \
+         FROM edges e JOIN symbols s ON s.id = e.source_id \
+         WHERE e.relationship = 'CALLS' AND e.target_type = 'symbol' AND e.target_id = ?1;",
+    )?;
+
+    let mut forward_parent: HashMap<i64, i64> = HashMap::new();
+    let mut backward_parent: HashMap<i64, i64> = HashMap::new();
+    let mut forward_seen: HashSet<i64> = HashSet::new();
+    let mut backward_seen: HashSet<i64> = HashSet::new();
+    forward_seen.insert(from_id);
+    backward_seen.insert(to_id);
+    let mut forward_frontier: VecDeque<i64> = VecDeque::from([from_id]);
+    let mut backward_frontier: VecDeque<i64> = VecDeque::from([to_id]);
+    let mut edges_explored = 0i64;
+    let mut meeting_node: Option<i64> = None;
+
+    for _ in 0..bounded_depth {
+        if forward_frontier.is_empty() || backward_frontier.is_empty() {
+            break;
+        }
+        if (nodes.len() as i64) >= dynamic_visited_cap || edges_explored >= dynamic_edge_cap {
+            break;
+        }
+
+        // Alternate expanding whichever frontier is smaller, so the
+        // combined search grows roughly with the smaller branching factor
+        // instead of always paying the cost of the wider side.
+        let expand_forward = forward_frontier.len() <= backward_frontier.len();
+
+        if expand_forward {
+            for node in std::mem::take(&mut forward_frontier) {
+                let rows: Vec<(i64, Option<i64>, String, String)> = forward_stmt
+                    .query_map(rusqlite::params![node], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                    })?
+                    .filter_map(|r| r.ok())
+                    .collect();
+                for (neighbor_id, line_number, name, file_path) in rows {
+                    edges_explored += 1;
+                    if forward_seen.contains(&neighbor_id) {
+                        continue;
+                    }
+                    forward_seen.insert(neighbor_id);
+                    forward_parent.insert(neighbor_id, node);
+                    nodes.entry(neighbor_id).or_insert(CallHop {
+                        name,
+                        file_path,
+                        line_number: line_number.unwrap_or(0),
+                    });
+                    forward_frontier.push_back(neighbor_id);
+                    if backward_seen.contains(&neighbor_id) {
+                        meeting_node = Some(neighbor_id);
+                        break;
+                    }
+                }
+                if meeting_node.is_some() {
+                    break;
+                }
+            }
+        } else {
+            for node in std::mem::take(&mut backward_frontier) {
+                let rows: Vec<(i64, Option<i64>, String, String)> = backward_stmt
+                    .query_map(rusqlite::params![node], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                    })?
+                    .filter_map(|r| r.ok())
+                    .collect();
+                for (neighbor_id, line_number, name, file_path) in rows {
+                    edges_explored += 1;
+                    if backward_seen.contains(&neighbor_id) {
+                        continue;
+                    }
+                    backward_seen.insert(neighbor_id);
+                    backward_parent.insert(neighbor_id, node);
+                    nodes.entry(neighbor_id).or_insert(CallHop {
+                        name,
+                        file_path,
+                        line_number: line_number.unwrap_or(0),
+                    });
+                    backward_frontier.push_back(neighbor_id);
+                    if forward_seen.contains(&neighbor_id) {
+                        meeting_node = Some(neighbor_id);
+                        break;
+                    }
+                }
+                if meeting_node.is_some() {
+                    break;
+                }
+            }
+        }
+
+        if meeting_node.is_some() {
+            break;
+        }
+    }
+
+    let Some(meeting) = meeting_node else {
+        return Ok(Vec::new());
+    };
+
+    // from -> ... -> meeting, walking forward_parent back and reversing.
+    let mut forward_chain: Vec<i64> = Vec::new();
+    let mut cur = meeting;
+    while cur != from_id {
+        forward_chain.push(cur);
+        cur = forward_parent[&cur];
+    }
+    forward_chain.push(from_id);
+    forward_chain.reverse();
+
+    // meeting -> ... -> to, walking backward_parent forward (already points
+    // from the meeting side towards `to`, so no reversal needed).
+    let mut backward_chain: Vec<i64> = Vec::new();
+    let mut cur = meeting;
+    while cur != to_id {
+        cur = backward_parent[&cur];
+        backward_chain.push(cur);
+    }
+
+    let mut ordered_ids = forward_chain;
+    ordered_ids.extend(backward_chain);
+
+    Ok(ordered_ids
+        .into_iter()
+        .filter_map(|id| nodes.get(&id).map(hop_json))
+        .collect())
+}
+
+fn hop_json(hop: &CallHop) -> serde_json::Value {
+    serde_json::json!({
+        "name": hop.name,
+        "file_path": hop.file_path,
+        "line_number": hop.line_number,
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (db, from_symbol, to_symbol, max_depth=6))]
+pub fn get_call_path(
+    py: Python<'_>,
+    db: &crate::store::database::Database,
+    from_symbol: &str,
+    to_symbol: &str,
+    max_depth: i64,
+) -> PyResult<PyObject> {
+    let conn = db.connect_internal()?;
+    let result = get_call_path_impl(&conn, from_symbol, to_symbol, max_depth)?;
+    let json_str = serde_json::to_string(&result)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let json_module = py.import("json")?;
+    json_module
+        .call_method1("loads", (json_str,))
+        .map(|o| o.into())
+}
@@ -1,15 +1,231 @@
 //! Change impact analysis backend with graph-aware dependents.
 
-use std::collections::{HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 use pyo3::prelude::*;
 use rusqlite::Connection;
 
 use crate::errors::{BombeError, BombeResult};
+use crate::query::context::round4;
 use crate::query::guards::{
     adaptive_graph_cap, clamp_depth, truncate_query, MAX_GRAPH_EDGES, MAX_GRAPH_VISITED,
     MAX_IMPACT_DEPTH,
 };
+use crate::query::ref_cache::CallerEdgeCache;
+
+/// Max distinct low-cost caller->target paths to look for per impacted
+/// symbol, bounded further below by the dynamic edge cap.
+const IMPACT_PATH_K: usize = 3;
+
+/// Cost of one CALLS edge for Yen/Dijkstra path ranking: `1 / (1 +
+/// ln(traffic))`, where `traffic` combines the caller's own centrality
+/// (pagerank) with how many call sites reach the callee through it. A
+/// high-traffic hot path is "cheap", so it sorts first and a symbol reached
+/// only through a single strong path is distinguished from one reached
+/// through several weak, independent ones.
+fn call_edge_cost(caller_pagerank: f64, call_site_count: i64) -> f64 {
+    let traffic = caller_pagerank.max(0.0) + call_site_count.max(1) as f64;
+    1.0 / (1.0 + traffic.ln())
+}
+
+/// Summed `call_edge_cost` along every edge in `path` (a sequence of symbol
+/// ids, caller-first).
+fn path_cost(
+    pagerank_by_id: &HashMap<i64, f64>,
+    call_site_counts: &HashMap<(i64, i64), i64>,
+    path: &[i64],
+) -> f64 {
+    path.windows(2)
+        .map(|w| {
+            let caller_pagerank = pagerank_by_id.get(&w[0]).copied().unwrap_or(0.0);
+            let call_sites = call_site_counts.get(&(w[0], w[1])).copied().unwrap_or(1);
+            call_edge_cost(caller_pagerank, call_sites)
+        })
+        .sum()
+}
+
+#[derive(Clone)]
+struct DijkstraState {
+    cost: f64,
+    node: i64,
+}
+impl PartialEq for DijkstraState {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+impl Eq for DijkstraState {}
+impl PartialOrd for DijkstraState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for DijkstraState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so `BinaryHeap` (a max-heap) pops the smallest cost first.
+        other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Dijkstra over the bounded local call graph discovered during BFS,
+/// avoiding `blocked_nodes`/`blocked_edges` (Yen's spur exclusions) and
+/// stopping once `relax_cap` nodes have been popped, so a single path
+/// search can't exceed the same visited-node guard the BFS traversal
+/// already respects.
+fn local_shortest_path(
+    adjacency: &HashMap<i64, Vec<i64>>,
+    pagerank_by_id: &HashMap<i64, f64>,
+    call_site_counts: &HashMap<(i64, i64), i64>,
+    start: i64,
+    goal: i64,
+    blocked_edges: &HashSet<(i64, i64)>,
+    blocked_nodes: &HashSet<i64>,
+    relax_cap: i64,
+) -> Option<Vec<i64>> {
+    if blocked_nodes.contains(&start) || blocked_nodes.contains(&goal) {
+        return None;
+    }
+    if start == goal {
+        return Some(vec![start]);
+    }
+
+    let mut best_cost: HashMap<i64, f64> = HashMap::new();
+    let mut prev: HashMap<i64, i64> = HashMap::new();
+    let mut heap: BinaryHeap<DijkstraState> = BinaryHeap::new();
+    best_cost.insert(start, 0.0);
+    heap.push(DijkstraState {
+        cost: 0.0,
+        node: start,
+    });
+
+    let mut relaxations: i64 = 0;
+    while let Some(DijkstraState { cost, node }) = heap.pop() {
+        if node == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while current != start {
+                current = prev[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        relaxations += 1;
+        if relaxations > relax_cap {
+            break;
+        }
+        if cost > *best_cost.get(&node).unwrap_or(&f64::INFINITY) {
+            continue;
+        }
+        let Some(neighbors) = adjacency.get(&node) else {
+            continue;
+        };
+        for &neighbor in neighbors {
+            if blocked_nodes.contains(&neighbor) || blocked_edges.contains(&(node, neighbor)) {
+                continue;
+            }
+            let caller_pagerank = pagerank_by_id.get(&node).copied().unwrap_or(0.0);
+            let call_sites = call_site_counts.get(&(node, neighbor)).copied().unwrap_or(1);
+            let next_cost = cost + call_edge_cost(caller_pagerank, call_sites);
+            let improves = match best_cost.get(&neighbor) {
+                Some(&existing) => next_cost < existing - 1e-9,
+                None => true,
+            };
+            if improves {
+                best_cost.insert(neighbor, next_cost);
+                prev.insert(neighbor, node);
+                heap.push(DijkstraState {
+                    cost: next_cost,
+                    node: neighbor,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Yen-style K-shortest-paths over the local call graph from `start` to
+/// `goal`, returning up to `k` distinct paths (caller-first symbol id
+/// sequences) with their total cost, ordered by cost ascending.
+fn local_k_shortest_paths(
+    adjacency: &HashMap<i64, Vec<i64>>,
+    pagerank_by_id: &HashMap<i64, f64>,
+    call_site_counts: &HashMap<(i64, i64), i64>,
+    start: i64,
+    goal: i64,
+    k: usize,
+    relax_cap: i64,
+) -> Vec<(Vec<i64>, f64)> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let no_edges = HashSet::new();
+    let no_nodes = HashSet::new();
+    let Some(first) =
+        local_shortest_path(adjacency, pagerank_by_id, call_site_counts, start, goal, &no_edges, &no_nodes, relax_cap)
+    else {
+        return Vec::new();
+    };
+
+    let mut found: Vec<Vec<i64>> = vec![first];
+    let mut candidates: Vec<Vec<i64>> = Vec::new();
+    let mut seen: HashSet<Vec<i64>> = HashSet::new();
+    seen.insert(found[0].clone());
+
+    while found.len() < k {
+        let last_path = found.last().unwrap().clone();
+        for i in 0..last_path.len().saturating_sub(1) {
+            let spur_node = last_path[i];
+            let root = &last_path[..=i];
+
+            let mut blocked_edges: HashSet<(i64, i64)> = HashSet::new();
+            for p in found.iter().chain(candidates.iter()) {
+                if p.len() > i && p[..=i] == *root {
+                    blocked_edges.insert((p[i], p[i + 1]));
+                }
+            }
+            let blocked_nodes: HashSet<i64> = root[..i].iter().copied().collect();
+
+            let Some(spur_path) = local_shortest_path(
+                adjacency,
+                pagerank_by_id,
+                call_site_counts,
+                spur_node,
+                goal,
+                &blocked_edges,
+                &blocked_nodes,
+                relax_cap,
+            ) else {
+                continue;
+            };
+
+            let mut total_path: Vec<i64> = root[..i].to_vec();
+            total_path.extend(spur_path);
+            if seen.insert(total_path.clone()) {
+                candidates.push(total_path);
+            }
+        }
+
+        if candidates.is_empty() {
+            break;
+        }
+        candidates.sort_by(|a, b| {
+            path_cost(pagerank_by_id, call_site_counts, a)
+                .partial_cmp(&path_cost(pagerank_by_id, call_site_counts, b))
+                .unwrap_or(Ordering::Equal)
+        });
+        found.push(candidates.remove(0));
+    }
+
+    found
+        .into_iter()
+        .map(|path| {
+            let cost = path_cost(pagerank_by_id, call_site_counts, &path);
+            (path, cost)
+        })
+        .collect()
+}
 
 fn resolve_symbol(
     conn: &Connection,
@@ -30,8 +246,12 @@ fn resolve_symbol(
     }
 }
 
-fn risk_level(direct: usize, transitive: usize, type_deps: usize) -> &'static str {
-    let total = direct + transitive + type_deps;
+/// `multi_path_risk` is the count of impacted callers reachable through more
+/// than one distinct low-cost path — each one is evidence of independent
+/// blast radius that a plain depth/count tally would miss, so it's folded
+/// into the same total at double weight.
+fn risk_level(direct: usize, transitive: usize, type_deps: usize, multi_path_risk: usize) -> &'static str {
+    let total = direct + transitive + type_deps + multi_path_risk * 2;
     if total >= 12 {
         "high"
     } else if total >= 4 {
@@ -68,11 +288,21 @@ pub fn change_impact_impl(
     let mut transitive_callers: Vec<serde_json::Value> = Vec::new();
 
     let mut caller_stmt = conn.prepare(
-        "SELECT e.source_id, e.line_number, s.name, s.qualified_name, s.file_path \
+        "SELECT e.source_id, e.line_number, s.name, s.qualified_name, s.file_path, \
+                s.pagerank_score, e.dispatch \
          FROM edges e JOIN symbols s ON s.id = e.source_id \
          WHERE e.relationship = 'CALLS' AND e.target_type = 'symbol' AND e.target_id = ?1;",
     )?;
 
+    // Caller-pagerank and per-edge call-site counts, captured alongside the
+    // BFS itself so the later path-cost ranking needs no extra queries.
+    // Captured before the `visited` skip-check below, so an edge between two
+    // nodes that are both already discovered (e.g. a caller called from
+    // multiple already-visited sites) is never lost.
+    let mut pagerank_by_id: HashMap<i64, f64> = HashMap::new();
+    let mut call_edges: HashMap<(i64, i64), i64> = HashMap::new();
+    let mut caller_edge_cache = CallerEdgeCache::new();
+
     while let Some((current, depth)) = queue.pop_front() {
         if (direct_callers.len() + transitive_callers.len()) as i64 >= dynamic_edge_cap {
             break;
@@ -80,23 +310,16 @@ pub fn change_impact_impl(
         if depth >= bounded_depth {
             continue;
         }
-        let rows: Vec<(i64, Option<i64>, String, String, String)> = caller_stmt
-            .query_map(rusqlite::params![current], |row| {
-                Ok((
-                    row.get(0)?,
-                    row.get(1)?,
-                    row.get(2)?,
-                    row.get(3)?,
-                    row.get(4)?,
-                ))
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        for (source_id, line_number, name, qname, fpath) in rows {
+        let rows = caller_edge_cache
+            .get_or_query(&mut caller_stmt, current)?
+            .to_vec();
+
+        for (source_id, line_number, name, qname, fpath, pagerank, dispatch) in rows {
             if (direct_callers.len() + transitive_callers.len()) as i64 >= dynamic_edge_cap {
                 break;
             }
+            pagerank_by_id.insert(source_id, pagerank);
+            *call_edges.entry((source_id, current)).or_insert(0) += 1;
             if visited.contains(&source_id) {
                 continue;
             }
@@ -112,6 +335,7 @@ pub fn change_impact_impl(
                 "file_path": fpath,
                 "line": line_number.unwrap_or(0),
                 "depth": next_depth,
+                "dispatch": dispatch,
                 "impact_reason": format!("call_dependency:depth={next_depth}"),
             });
             if next_depth == 1 {
@@ -123,6 +347,72 @@ pub fn change_impact_impl(
         }
     }
 
+    // Rank impacted callers by cheapest path cost back to the target: build
+    // the local adjacency from the discovered call edges and run a bounded
+    // Yen's K-shortest-paths search per caller.
+    let mut adjacency: HashMap<i64, Vec<i64>> = HashMap::new();
+    for &(caller, callee) in call_edges.keys() {
+        adjacency.entry(caller).or_default().push(callee);
+    }
+    let path_k = IMPACT_PATH_K.min(dynamic_edge_cap.max(1) as usize);
+    let mut path_info: HashMap<i64, (f64, usize)> = HashMap::new();
+    for item in direct_callers.iter().chain(transitive_callers.iter()) {
+        let Some(caller_id) = item.get("id").and_then(|v| v.as_i64()) else {
+            continue;
+        };
+        if path_info.contains_key(&caller_id) {
+            continue;
+        }
+        let paths = local_k_shortest_paths(
+            &adjacency,
+            &pagerank_by_id,
+            &call_edges,
+            caller_id,
+            target_id,
+            path_k,
+            dynamic_visited_cap,
+        );
+        if let Some((_, cheapest)) = paths.first() {
+            path_info.insert(caller_id, (*cheapest, paths.len()));
+        }
+    }
+
+    let mut multi_path_risk = 0usize;
+    for item in direct_callers.iter_mut().chain(transitive_callers.iter_mut()) {
+        let Some(caller_id) = item.get("id").and_then(|v| v.as_i64()) else {
+            continue;
+        };
+        let Some(&(cost, count)) = path_info.get(&caller_id) else {
+            continue;
+        };
+        if count > 1 {
+            multi_path_risk += 1;
+        }
+        if let Some(obj) = item.as_object_mut() {
+            obj.insert("path_cost".to_string(), serde_json::json!(round4(cost)));
+            obj.insert(
+                "distinct_low_cost_paths".to_string(),
+                serde_json::json!(count),
+            );
+        }
+    }
+
+    let path_cost_of = |item: &serde_json::Value| -> f64 {
+        item.get("path_cost")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(f64::INFINITY)
+    };
+    direct_callers.sort_by(|a, b| {
+        path_cost_of(a)
+            .partial_cmp(&path_cost_of(b))
+            .unwrap_or(Ordering::Equal)
+    });
+    transitive_callers.sort_by(|a, b| {
+        path_cost_of(a)
+            .partial_cmp(&path_cost_of(b))
+            .unwrap_or(Ordering::Equal)
+    });
+
     // Type dependents (EXTENDS/IMPLEMENTS)
     let mut type_stmt = conn.prepare(
         "SELECT e.source_id, e.relationship, s.name, s.qualified_name, s.file_path \
@@ -168,6 +458,7 @@ pub fn change_impact_impl(
         direct_callers.len(),
         transitive_callers.len(),
         type_dependents.len(),
+        multi_path_risk,
     );
     let summary = format!(
         "Impact={risk}; direct={}, transitive={}, type_dependents={}, files={}",
@@ -0,0 +1,253 @@
+//! Single-pass, in-memory code graph shared across BFS expansion, personalized
+//! PageRank, and topology ordering within one [`crate::query::context`]
+//! request.
+//!
+//! Those three steps each used to run their own `SELECT source_id, target_id
+//! FROM edges` scan, redundantly re-fetching the same relationship edges.
+//! [`CodeGraph`] loads the edge set once into a CSR-style adjacency (a flat
+//! neighbor array plus a per-node offset index, with symbol ids mapped to
+//! dense `0..n` indices) so all three consumers walk the same in-memory graph.
+
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+use crate::query::context::{rel_params, rel_placeholders};
+
+/// Per-relationship transition weights used by direction-aware personalized
+/// PageRank: `CALLS`/`IMPORTS_SYMBOL` edges carry more of a node's score
+/// forward than structural `HAS_METHOD` containment edges, so retrieval
+/// leans toward call-graph proximity rather than treating every relationship
+/// kind as equally informative.
+#[derive(Clone, Debug)]
+pub struct RelationshipWeights {
+    pub calls: f64,
+    pub imports_symbol: f64,
+    pub extends: f64,
+    pub implements: f64,
+    pub has_method: f64,
+}
+
+impl Default for RelationshipWeights {
+    fn default() -> Self {
+        RelationshipWeights {
+            calls: 2.0,
+            imports_symbol: 2.0,
+            extends: 1.0,
+            implements: 1.0,
+            has_method: 0.5,
+        }
+    }
+}
+
+impl RelationshipWeights {
+    /// Applies caller-supplied overrides (e.g. from a Python dict keyed by
+    /// relationship name) on top of the defaults.
+    pub fn with_overrides(overrides: &HashMap<String, f64>) -> Self {
+        let mut weights = RelationshipWeights::default();
+        for (relationship, weight) in overrides {
+            match relationship.as_str() {
+                "CALLS" => weights.calls = *weight,
+                "IMPORTS_SYMBOL" => weights.imports_symbol = *weight,
+                "EXTENDS" => weights.extends = *weight,
+                "IMPLEMENTS" => weights.implements = *weight,
+                "HAS_METHOD" => weights.has_method = *weight,
+                _ => {}
+            }
+        }
+        weights
+    }
+
+    fn weight_for(&self, relationship: &str) -> f64 {
+        match relationship {
+            "CALLS" => self.calls,
+            "IMPORTS_SYMBOL" => self.imports_symbol,
+            "EXTENDS" => self.extends,
+            "IMPLEMENTS" => self.implements,
+            "HAS_METHOD" => self.has_method,
+            _ => 1.0,
+        }
+    }
+}
+
+/// A word-packed bitset over a graph's dense node indices. Used in place of
+/// `HashSet<i64>` for membership and reached-set tracking: a roaring bitmap
+/// over a single small, dense range of ids degenerates to exactly this, and
+/// pulling in the full crate isn't worth it for one request-scoped set.
+pub struct NodeSet {
+    bits: Vec<u64>,
+    count: usize,
+}
+
+impl NodeSet {
+    pub fn with_capacity(capacity: usize) -> Self {
+        NodeSet {
+            bits: vec![0u64; capacity.div_ceil(64).max(1)],
+            count: 0,
+        }
+    }
+
+    /// Inserts `idx`, returning `true` if it wasn't already present.
+    pub fn insert(&mut self, idx: usize) -> bool {
+        let word = idx / 64;
+        let bit = 1u64 << (idx % 64);
+        if word >= self.bits.len() {
+            self.bits.resize(word + 1, 0);
+        }
+        let was_present = self.bits[word] & bit != 0;
+        self.bits[word] |= bit;
+        if !was_present {
+            self.count += 1;
+        }
+        !was_present
+    }
+
+    pub fn contains(&self, idx: usize) -> bool {
+        let word = idx / 64;
+        word < self.bits.len() && self.bits[word] & (1u64 << (idx % 64)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+/// A symbol-to-symbol code graph loaded once per request, keyed by dense
+/// `0..n` indices rather than raw symbol ids, with CSR-style adjacency
+/// (`offsets[i]..offsets[i + 1]` slices into `neighbors`).
+pub struct CodeGraph {
+    index_of: HashMap<i64, usize>,
+    ids: Vec<i64>,
+    offsets: Vec<usize>,
+    neighbors: Vec<usize>,
+    /// Directed, relationship-weighted out-edges (CSR-style), for
+    /// direction-aware personalized PageRank and path explanations. Unlike
+    /// `neighbors`, these are not symmetrized:
+    /// `out_edges[out_offsets[i]..out_offsets[i + 1]]` is node `i`'s outgoing
+    /// `(neighbor_idx, weight, relationship)` triples only.
+    out_offsets: Vec<usize>,
+    out_edges: Vec<(usize, f64, String)>,
+}
+
+impl CodeGraph {
+    /// Loads every symbol-to-symbol edge matching [`RELATIONSHIPS`] in a
+    /// single scan and builds both a symmetrized (undirected) adjacency —
+    /// the behavior `expand` and `build_adjacency` need — and a directed,
+    /// weighted adjacency for personalized PageRank.
+    pub fn load(conn: &Connection) -> BombeResult<Self> {
+        Self::load_with_weights(conn, &RelationshipWeights::default())
+    }
+
+    /// Like [`CodeGraph::load`], but with caller-supplied relationship
+    /// transition weights for the directed adjacency used by PPR.
+    pub fn load_with_weights(conn: &Connection, weights: &RelationshipWeights) -> BombeResult<Self> {
+        let placeholders = rel_placeholders();
+        let sql = format!(
+            "SELECT source_id, target_id, relationship FROM edges \
+             WHERE source_type = 'symbol' AND target_type = 'symbol' \
+             AND relationship IN ({placeholders});"
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let params = rel_params();
+        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
+            params.iter().map(|p| p.as_ref()).collect();
+        let edges: Vec<(i64, i64, String)> = stmt
+            .query_map(param_refs.as_slice(), |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let mut index_of: HashMap<i64, usize> = HashMap::new();
+        let mut ids: Vec<i64> = Vec::new();
+        let mut intern = |id: i64, index_of: &mut HashMap<i64, usize>, ids: &mut Vec<i64>| -> usize {
+            *index_of.entry(id).or_insert_with(|| {
+                ids.push(id);
+                ids.len() - 1
+            })
+        };
+
+        let mut pairs: Vec<(usize, usize)> = Vec::with_capacity(edges.len() * 2);
+        let mut directed: Vec<(usize, usize, f64, String)> = Vec::with_capacity(edges.len());
+        for (source, target, relationship) in edges {
+            let s = intern(source, &mut index_of, &mut ids);
+            let t = intern(target, &mut index_of, &mut ids);
+            if s == t {
+                continue;
+            }
+            pairs.push((s, t));
+            pairs.push((t, s));
+            let weight = weights.weight_for(&relationship);
+            directed.push((s, t, weight, relationship));
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        let node_count = ids.len();
+        let mut offsets = vec![0usize; node_count + 1];
+        for &(from, _) in &pairs {
+            offsets[from + 1] += 1;
+        }
+        for i in 0..node_count {
+            offsets[i + 1] += offsets[i];
+        }
+        let neighbors: Vec<usize> = pairs.into_iter().map(|(_, to)| to).collect();
+
+        directed.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        let mut out_offsets = vec![0usize; node_count + 1];
+        for &(from, _, _, _) in &directed {
+            out_offsets[from + 1] += 1;
+        }
+        for i in 0..node_count {
+            out_offsets[i + 1] += out_offsets[i];
+        }
+        let out_edges: Vec<(usize, f64, String)> = directed
+            .into_iter()
+            .map(|(_, to, w, relationship)| (to, w, relationship))
+            .collect();
+
+        Ok(CodeGraph {
+            index_of,
+            ids,
+            offsets,
+            neighbors,
+            out_offsets,
+            out_edges,
+        })
+    }
+
+    /// Dense index for a symbol id, if it appears in the edge set at all.
+    pub fn dense_index(&self, id: i64) -> Option<usize> {
+        self.index_of.get(&id).copied()
+    }
+
+    pub fn node_id(&self, idx: usize) -> i64 {
+        self.ids[idx]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn neighbors(&self, idx: usize) -> &[usize] {
+        &self.neighbors[self.offsets[idx]..self.offsets[idx + 1]]
+    }
+
+    /// Node `idx`'s outgoing `(neighbor_idx, relationship_weight, relationship)` edges.
+    pub fn out_edges(&self, idx: usize) -> &[(usize, f64, String)] {
+        &self.out_edges[self.out_offsets[idx]..self.out_offsets[idx + 1]]
+    }
+
+    pub fn new_node_set(&self) -> NodeSet {
+        NodeSet::with_capacity(self.ids.len())
+    }
+}
@@ -5,23 +5,38 @@
 //! ordering, token-budget pruning, and secret redaction.
 
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
-use std::sync::LazyLock;
+use std::sync::{LazyLock, Mutex};
 
 use pyo3::prelude::*;
 use regex::Regex;
 use rusqlite::Connection;
 
-use crate::errors::BombeResult;
+use crate::errors::{BombeError, BombeResult};
 use crate::query::guards::{
     adaptive_graph_cap, clamp_budget, clamp_depth, truncate_query, MAX_CONTEXT_EXPANSION_DEPTH,
     MAX_CONTEXT_SEEDS, MAX_CONTEXT_TOKEN_BUDGET, MAX_GRAPH_VISITED, MIN_CONTEXT_TOKEN_BUDGET,
 };
+use crate::query::bpe_tokenizer::load_cached as load_bpe_encoder;
+use crate::query::code_graph::{CodeGraph, RelationshipWeights};
+use crate::query::path_explain::nearest_seed_paths;
+use crate::query::semantic_index::{cosine_similarity, decode_vector, fuse_seeds, SemanticIndex};
 use crate::query::tokenizer::estimate_tokens;
 
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
 
+/// Embedding model name used for semantic seed retrieval. Matches the
+/// `model` column written by the indexer when embeddings are populated.
+const SEMANTIC_EMBEDDING_MODEL: &str = "default";
+
+/// Candidate set size for the semantic index's greedy search (HNSW's `ef`).
+const SEMANTIC_SEARCH_EF: usize = 64;
+
+/// Number of shortest relationship paths computed per non-seed symbol when
+/// explaining how it connects back to a seed (see `query::path_explain`).
+const PATH_EXPLANATION_K: usize = 3;
+
 const RELATIONSHIPS: &[&str] = &[
     "CALLS",
     "IMPORTS_SYMBOL",
@@ -32,27 +47,97 @@ const RELATIONSHIPS: &[&str] = &[
 
 static WORD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]+").unwrap());
 
-static REDACTION_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+/// Built-in redaction rules, each tagged with a stable category name so
+/// callers can disable individual categories (via [`RedactionConfig`]) and
+/// `quality_metrics` can report hits per category instead of one aggregate.
+static REDACTION_PATTERNS: LazyLock<Vec<(&'static str, Regex, &'static str)>> = LazyLock::new(|| {
     vec![
         (
+            "openai_key",
             Regex::new(r"sk-[A-Za-z0-9]{20,}").unwrap(),
             "[REDACTED_OPENAI_KEY]",
         ),
         (
+            "aws_access_key",
             Regex::new(r"AKIA[0-9A-Z]{16}").unwrap(),
             "[REDACTED_AWS_ACCESS_KEY]",
         ),
         (
+            "generic_secret_assignment",
             Regex::new(r#"(?i)(api[_-]?key|token|secret)\s*[:=]\s*['"][^'"]+['"]"#).unwrap(),
             r#"$1="[REDACTED]""#,
         ),
         (
+            "private_key",
             Regex::new(r"(?s)-----BEGIN (?:RSA |EC |DSA )?PRIVATE KEY-----.*?-----END (?:RSA |EC |DSA )?PRIVATE KEY-----").unwrap(),
             "[REDACTED_PRIVATE_KEY]",
         ),
     ]
 });
 
+/// Process-wide cache of caller-supplied redaction regexes, keyed by pattern
+/// source. MCP clients typically pass the same custom rules on every call,
+/// so this compiles (and validates) each distinct pattern only once rather
+/// than on every `get_context` request.
+static CUSTOM_REDACTION_REGEX_CACHE: LazyLock<Mutex<HashMap<String, Regex>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn compile_custom_redaction_pattern(pattern: &str) -> BombeResult<Regex> {
+    let mut cache = CUSTOM_REDACTION_REGEX_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(compiled) = cache.get(pattern) {
+        return Ok(compiled.clone());
+    }
+    let compiled = Regex::new(pattern)
+        .map_err(|e| BombeError::Query(format!("invalid redaction pattern {pattern:?}: {e}")))?;
+    cache.insert(pattern.to_string(), compiled.clone());
+    Ok(compiled)
+}
+
+/// A caller-supplied redaction rule: a named category, a compiled pattern,
+/// and its replacement text (may reference capture groups, e.g. `$1`).
+struct RedactionRule {
+    category: String,
+    pattern: Regex,
+    replacement: String,
+}
+
+/// Extends the built-in redaction ruleset: lets callers turn off built-in
+/// categories they don't want (e.g. because they produce false positives on
+/// their corpus) and register additional `(category, pattern, replacement)`
+/// rules for secret formats the built-ins don't cover, such as internal
+/// tokens, GCP service-account keys, JWTs, or vendor-specific keys.
+#[derive(Default)]
+pub struct RedactionConfig {
+    disabled_categories: HashSet<String>,
+    custom_rules: Vec<RedactionRule>,
+}
+
+impl RedactionConfig {
+    /// Builds a config from the raw shapes passed across the PyO3 boundary:
+    /// a list of built-in category names to disable, and a list of
+    /// `(category, pattern, replacement)` custom rules. Custom patterns are
+    /// validated and compiled here, once, via `compile_custom_redaction_pattern`.
+    pub fn new(
+        disabled_categories: &[String],
+        custom_rules: &[(String, String, String)],
+    ) -> BombeResult<Self> {
+        let mut compiled_rules = Vec::with_capacity(custom_rules.len());
+        for (category, pattern, replacement) in custom_rules {
+            compiled_rules.push(RedactionRule {
+                category: category.clone(),
+                pattern: compile_custom_redaction_pattern(pattern)?,
+                replacement: replacement.clone(),
+            });
+        }
+        Ok(RedactionConfig {
+            disabled_categories: disabled_categories.iter().cloned().collect(),
+            custom_rules: compiled_rules,
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Internal helper: resolve path
 // ---------------------------------------------------------------------------
@@ -91,11 +176,91 @@ fn source_fragment(file_path: &str, start_line: i64, end_line: i64) -> String {
 // Internal helper: query terms extraction
 // ---------------------------------------------------------------------------
 
-fn query_terms(query: &str) -> HashSet<String> {
-    WORD_RE
+// ---------------------------------------------------------------------------
+// Internal helper: query-derivation graph
+// ---------------------------------------------------------------------------
+
+/// Split a camelCase or snake_case identifier into lowercased subwords, e.g.
+/// `UserAuthToken` / `user_auth_token` -> `["user", "auth", "token"]`.
+fn split_identifier(word: &str) -> Vec<String> {
+    let mut subwords = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for ch in word.chars() {
+        if ch == '_' || ch == '-' {
+            if !current.is_empty() {
+                subwords.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if ch.is_uppercase() && prev_lower {
+            if !current.is_empty() {
+                subwords.push(std::mem::take(&mut current));
+            }
+        }
+        prev_lower = ch.is_lowercase();
+        current.push(ch.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        subwords.push(current);
+    }
+    subwords
+}
+
+/// A query term derived from the raw query, with a weight reflecting how
+/// directly it reflects the user's literal input: original words score
+/// highest, subwords split out of camelCase/snake_case next, and generated
+/// adjacent-word concatenations (meant to catch queries like "user auth
+/// token" matching a symbol named `UserAuthToken`) lowest.
+pub struct DerivedTerm {
+    pub term: String,
+    pub weight: f64,
+}
+
+/// Precompute alternative interpretations of the query: the original words,
+/// their camelCase/snake_case subwords, and concatenations of adjacent words
+/// (`userauth`, `authtoken`, `userauthtoken` for "user auth token"). This is
+/// a (small, linear) query-derivation graph: each derived term is a node,
+/// weighted by how far it is from the literal query.
+fn derive_query_terms(query: &str) -> Vec<DerivedTerm> {
+    let words: Vec<String> = WORD_RE
         .find_iter(query)
         .map(|m| m.as_str().to_lowercase())
         .filter(|t| t.len() >= 2)
+        .collect();
+
+    let mut derived: HashMap<String, f64> = HashMap::new();
+    let mut bump = |term: String, weight: f64| {
+        let entry = derived.entry(term).or_insert(0.0);
+        if weight > *entry {
+            *entry = weight;
+        }
+    };
+
+    for word in &words {
+        bump(word.clone(), 1.0);
+        for subword in split_identifier(word) {
+            if subword.len() >= 2 {
+                bump(subword, 0.7);
+            }
+        }
+    }
+
+    // Concatenations of adjacent words, growing up to the whole query.
+    for start in 0..words.len() {
+        let mut concat = String::new();
+        for word in &words[start..] {
+            concat.push_str(word);
+            if concat.len() > start + word.len() {
+                bump(concat.clone(), 0.4);
+            }
+        }
+    }
+
+    derived
+        .into_iter()
+        .map(|(term, weight)| DerivedTerm { term, weight })
         .collect()
 }
 
@@ -103,55 +268,336 @@ fn query_terms(query: &str) -> HashSet<String> {
 // Internal helper: symbol-query relevance scoring
 // ---------------------------------------------------------------------------
 
-fn symbol_query_relevance(
+/// Score a symbol against the derived-term set, crediting it with the
+/// best-matching derivation's weight per term family rather than a flat
+/// per-term count. This lets "user auth token" match `UserAuthToken` through
+/// the `userauthtoken` concatenation even though no single raw word appears
+/// in the name.
+fn symbol_derived_relevance(
     name: &str,
     qualified_name: &str,
     signature: &str,
-    terms: &HashSet<String>,
-) -> i64 {
-    if terms.is_empty() {
-        return 0;
+    derived_terms: &[DerivedTerm],
+) -> f64 {
+    if derived_terms.is_empty() {
+        return 0.0;
     }
     let haystacks = [
         name.to_lowercase(),
         qualified_name.to_lowercase(),
         signature.to_lowercase(),
     ];
-    let mut score: i64 = 0;
-    for term in terms {
-        for haystack in &haystacks {
-            if haystack.contains(term.as_str()) {
-                score += 1;
-                break;
-            }
+    let mut score = 0.0;
+    for derived in derived_terms {
+        if haystacks.iter().any(|h| h.contains(derived.term.as_str())) {
+            score += derived.weight;
         }
     }
     score
 }
 
+// ---------------------------------------------------------------------------
+// Internal helper: semantic score blending
+// ---------------------------------------------------------------------------
+
+/// Loads per-symbol embedding vectors for `ids` from `symbol_embeddings`.
+/// Returns `Ok(None)` when the table doesn't exist yet (un-embedded
+/// databases), so the caller can fall back to pure structural ranking.
+fn load_symbol_embeddings(
+    conn: &Connection,
+    ids: &[i64],
+    model: &str,
+) -> BombeResult<Option<HashMap<i64, Vec<f32>>>> {
+    let table_exists: bool = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'symbol_embeddings';",
+            [],
+            |_| Ok(true),
+        )
+        .unwrap_or(false);
+    if !table_exists || ids.is_empty() {
+        return Ok(None);
+    }
+
+    let id_placeholders: String = ids
+        .iter()
+        .enumerate()
+        .map(|(i, _)| format!("?{}", i + 2))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "SELECT symbol_id, vector FROM symbol_embeddings \
+         WHERE model = ?1 AND symbol_id IN ({id_placeholders});"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut params: Vec<Box<dyn rusqlite::types::ToSql>> =
+        vec![Box::new(model.to_string())];
+    params.extend(ids.iter().map(|id| Box::new(*id) as Box<dyn rusqlite::types::ToSql>));
+    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let embeddings: HashMap<i64, Vec<f32>> = stmt
+        .query_map(param_refs.as_slice(), |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?
+        .filter_map(|r| r.ok())
+        .map(|(id, blob)| (id, decode_vector(&blob)))
+        .collect();
+    Ok(Some(embeddings))
+}
+
+/// Blends an optional semantic-similarity signal into each candidate's
+/// structural score in place: `final = semantic_ratio * sem + (1 -
+/// semantic_ratio) * struct`, where `struct` is the existing structural
+/// score min-max normalized to `[0, 1]` across the candidate set and `sem`
+/// is the cosine similarity between `query_embedding` and the symbol's
+/// stored embedding (0.0 for symbols with no embedding row). No-ops (and
+/// returns `0.0`) when `semantic_ratio <= 0.0`, no query embedding was
+/// supplied, or `symbol_embeddings` isn't populated yet. Returns the mean
+/// semantic contribution actually applied, for `quality_metrics`.
+fn blend_semantic_scores(
+    conn: &Connection,
+    scored: &mut [(f64, SymbolData)],
+    query_embedding: Option<&[f32]>,
+    semantic_ratio: f64,
+) -> BombeResult<f64> {
+    let (Some(query_vector), true) = (query_embedding, semantic_ratio > 0.0) else {
+        return Ok(0.0);
+    };
+    let ids: Vec<i64> = scored.iter().map(|(_, sym)| sym.id).collect();
+    let Some(embeddings) = load_symbol_embeddings(conn, &ids, SEMANTIC_EMBEDDING_MODEL)? else {
+        return Ok(0.0);
+    };
+    if embeddings.is_empty() {
+        return Ok(0.0);
+    }
+
+    let struct_min = scored
+        .iter()
+        .map(|(score, _)| *score)
+        .fold(f64::INFINITY, f64::min);
+    let struct_max = scored
+        .iter()
+        .map(|(score, _)| *score)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let struct_span = struct_max - struct_min;
+
+    let mut semantic_sum = 0.0;
+    for (score, sym) in scored.iter_mut() {
+        let struct_norm = if struct_span > 1e-12 {
+            (*score - struct_min) / struct_span
+        } else {
+            1.0
+        };
+        let sem = embeddings
+            .get(&sym.id)
+            .map(|vector| cosine_similarity(query_vector, vector).clamp(0.0, 1.0) as f64)
+            .unwrap_or(0.0);
+        semantic_sum += sem;
+        *score = semantic_ratio * sem + (1.0 - semantic_ratio) * struct_norm;
+    }
+
+    Ok(semantic_sum / scored.len() as f64)
+}
+
+/// The independent per-candidate signals fed into Reciprocal Rank Fusion:
+/// personalized PageRank, global (structural) PageRank, and lexical
+/// relevance, all computed once in the step-9 scoring loop and reused here
+/// instead of being recomputed.
+#[derive(Clone)]
+struct CandidateSignals {
+    ppr: f64,
+    global_pagerank: f64,
+    lexical_relevance: f64,
+}
+
+/// Names accepted in a caller-supplied `ranking_rules` pipeline. Kept in one
+/// place so `validate_ranking_rules`'s error message and the comparator in
+/// `compare_by_ranking_rules` can't drift out of sync.
+const KNOWN_RANKING_RULES: &[&str] = &[
+    "proximity",
+    "pagerank",
+    "ppr",
+    "lexical_relevance",
+    "seed_match",
+    "file_path",
+];
+
+/// Rejects any rule name not in [`KNOWN_RANKING_RULES`], so a typo in a
+/// caller's rule list fails loudly instead of silently falling through to
+/// the id tiebreak.
+fn validate_ranking_rules(rules: &[String]) -> BombeResult<()> {
+    for rule in rules {
+        if !KNOWN_RANKING_RULES.contains(&rule.as_str()) {
+            return Err(BombeError::Query(format!(
+                "unknown ranking rule {rule:?}; expected one of {KNOWN_RANKING_RULES:?}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Compares two candidates rule-by-rule in `rules` order, falling through to
+/// the next rule only when the current one is a tie; falls through to symbol
+/// id as a final deterministic tiebreak. Float-valued rules (`pagerank`,
+/// `ppr`, `lexical_relevance`) are bucketed to `epsilon`: values within
+/// `epsilon` of each other compare equal, so sorting isn't dictated by
+/// floating-point noise the rule was never meant to distinguish on.
+fn compare_by_ranking_rules(
+    rules: &[String],
+    a: (&SymbolData, &CandidateSignals),
+    b: (&SymbolData, &CandidateSignals),
+    epsilon: f64,
+) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    let (sym_a, sig_a) = a;
+    let (sym_b, sig_b) = b;
+    // Descending: higher value ranks first, within `epsilon` buckets as tied.
+    let bucketed_cmp_desc = |x: f64, y: f64| -> Ordering {
+        if (x - y).abs() <= epsilon {
+            Ordering::Equal
+        } else {
+            y.partial_cmp(&x).unwrap_or(Ordering::Equal)
+        }
+    };
+    for rule in rules {
+        let ordering = match rule.as_str() {
+            "proximity" => sym_a.depth.cmp(&sym_b.depth),
+            "pagerank" => bucketed_cmp_desc(sig_a.global_pagerank, sig_b.global_pagerank),
+            "ppr" => bucketed_cmp_desc(sig_a.ppr, sig_b.ppr),
+            "lexical_relevance" => bucketed_cmp_desc(sig_a.lexical_relevance, sig_b.lexical_relevance),
+            "seed_match" => sym_b.is_seed.cmp(&sym_a.is_seed),
+            "file_path" => sym_a.file_path.cmp(&sym_b.file_path),
+            _ => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    sym_a.id.cmp(&sym_b.id)
+}
+
+/// Ranks `values` in descending order (rank 1 = highest value), returning
+/// each original index's 1-based rank. Ties resolve by original order.
+fn rank_descending(values: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| {
+        values[b]
+            .partial_cmp(&values[a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let mut ranks = vec![0usize; values.len()];
+    for (position, idx) in order.into_iter().enumerate() {
+        ranks[idx] = position + 1;
+    }
+    ranks
+}
+
+/// Reciprocal Rank Fusion: ranks candidates independently by each available
+/// signal (PPR, global PageRank, lexical relevance, and semantic similarity
+/// when an embedding is available) and replaces each candidate's score with
+/// `Σ_signals 1 / (rrf_k + rank)`. Rewrites `scored`'s scores in place and
+/// returns, per symbol id, a debug string of each signal's rank for
+/// surfacing in `selection_reason`.
+fn fuse_scores_rrf(
+    conn: &Connection,
+    scored: &mut [(f64, SymbolData)],
+    signals: &[CandidateSignals],
+    query_embedding: Option<&[f32]>,
+    rrf_k: f64,
+) -> BombeResult<HashMap<i64, String>> {
+    let ppr_ranks = rank_descending(&signals.iter().map(|s| s.ppr).collect::<Vec<_>>());
+    let pagerank_ranks =
+        rank_descending(&signals.iter().map(|s| s.global_pagerank).collect::<Vec<_>>());
+    let lexical_ranks = rank_descending(
+        &signals
+            .iter()
+            .map(|s| s.lexical_relevance)
+            .collect::<Vec<_>>(),
+    );
+
+    let ids: Vec<i64> = scored.iter().map(|(_, sym)| sym.id).collect();
+    let semantic_ranks: Option<Vec<usize>> = match query_embedding {
+        Some(query_vector) => {
+            load_symbol_embeddings(conn, &ids, SEMANTIC_EMBEDDING_MODEL)?.map(|embeddings| {
+                let similarities: Vec<f64> = ids
+                    .iter()
+                    .map(|id| {
+                        embeddings
+                            .get(id)
+                            .map(|vector| cosine_similarity(query_vector, vector) as f64)
+                            .unwrap_or(0.0)
+                    })
+                    .collect();
+                rank_descending(&similarities)
+            })
+        }
+        None => None,
+    };
+
+    let mut debug_ranks: HashMap<i64, String> = HashMap::with_capacity(scored.len());
+    for (i, (score, sym)) in scored.iter_mut().enumerate() {
+        let mut fused = 1.0 / (rrf_k + ppr_ranks[i] as f64)
+            + 1.0 / (rrf_k + pagerank_ranks[i] as f64)
+            + 1.0 / (rrf_k + lexical_ranks[i] as f64);
+        let mut reason = format!(
+            "ppr_rank={},pagerank_rank={},lexical_rank={}",
+            ppr_ranks[i], pagerank_ranks[i], lexical_ranks[i]
+        );
+        if let Some(ranks) = &semantic_ranks {
+            fused += 1.0 / (rrf_k + ranks[i] as f64);
+            reason.push_str(&format!(",semantic_rank={}", ranks[i]));
+        }
+        *score = fused;
+        debug_ranks.insert(sym.id, reason);
+    }
+    Ok(debug_ranks)
+}
+
 // ---------------------------------------------------------------------------
 // Internal helper: redact sensitive text
 // ---------------------------------------------------------------------------
 
-fn redact_sensitive_text(text: &str) -> (String, i64) {
+/// Redacts `text` against the built-in rules (minus any disabled categories)
+/// plus `config`'s custom rules, returning the redacted text and a per-category
+/// hit count so callers can audit what kinds of secrets their corpus contains.
+fn redact_sensitive_text(text: &str, config: &RedactionConfig) -> (String, HashMap<String, i64>) {
     let mut redacted = text.to_string();
-    let mut redaction_hits: i64 = 0;
-    for (pattern, replacement) in REDACTION_PATTERNS.iter() {
+    let mut hits_by_category: HashMap<String, i64> = HashMap::new();
+    for (category, pattern, replacement) in REDACTION_PATTERNS.iter() {
+        if config.disabled_categories.contains(*category) {
+            continue;
+        }
         // Count matches first, then replace (mirrors Python's re.subn).
         let count = pattern.find_iter(&redacted).count() as i64;
-        redaction_hits += count;
         if count > 0 {
+            *hits_by_category.entry(category.to_string()).or_insert(0) += count;
             redacted = pattern.replace_all(&redacted, *replacement).into_owned();
         }
     }
-    (redacted, redaction_hits)
+    for rule in &config.custom_rules {
+        let count = rule.pattern.find_iter(&redacted).count() as i64;
+        if count > 0 {
+            *hits_by_category.entry(rule.category.clone()).or_insert(0) += count;
+            redacted = rule
+                .pattern
+                .replace_all(&redacted, rule.replacement.as_str())
+                .into_owned();
+        }
+    }
+    (redacted, hits_by_category)
+}
+
+/// Merges per-call redaction hit counts into the request-wide accumulator.
+fn merge_redaction_hits(total: &mut HashMap<String, i64>, hits: HashMap<String, i64>) {
+    for (category, count) in hits {
+        *total.entry(category).or_insert(0) += count;
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Internal helper: relationship placeholders
 // ---------------------------------------------------------------------------
 
-fn rel_placeholders() -> String {
+pub(crate) fn rel_placeholders() -> String {
     RELATIONSHIPS
         .iter()
         .enumerate()
@@ -160,13 +606,93 @@ fn rel_placeholders() -> String {
         .join(", ")
 }
 
-fn rel_params() -> Vec<Box<dyn rusqlite::types::ToSql>> {
+pub(crate) fn rel_params() -> Vec<Box<dyn rusqlite::types::ToSql>> {
     RELATIONSHIPS
         .iter()
         .map(|r| Box::new(r.to_string()) as Box<dyn rusqlite::types::ToSql>)
         .collect()
 }
 
+// ---------------------------------------------------------------------------
+// Internal helper: typo-tolerant matching
+// ---------------------------------------------------------------------------
+
+/// Maximum edit distance a candidate name may be from a query word to still
+/// be considered a typo-tolerant seed match. Kept small so the fallback
+/// doesn't pull in unrelated symbols for short identifiers.
+const MAX_TYPO_DISTANCE: usize = 2;
+
+/// Classic Levenshtein edit distance between two strings (insert/delete/substitute).
+///
+/// This is the brute-force DP form of the same bounded edit-distance search a
+/// Levenshtein automaton computes more efficiently; at the symbol-name
+/// volumes seeds are picked from, the DP table is cheap enough that building
+/// an automaton isn't worth the complexity.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (la, lb) = (a.len(), b.len());
+    let mut dp = vec![vec![0usize; lb + 1]; la + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=lb {
+        dp[0][j] = j;
+    }
+    for i in 1..=la {
+        for j in 1..=lb {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+    dp[la][lb]
+}
+
+/// Find seeds by typo-tolerant matching of query words against symbol names,
+/// within [`MAX_TYPO_DISTANCE`] edits. Used as a last-resort fallback when
+/// exact entry points, FTS, and substring matching all come up empty (e.g.
+/// the user queried "proccessRequest" for a symbol named `processRequest`).
+fn pick_seeds_fuzzy(conn: &Connection, query: &str) -> BombeResult<Vec<i64>> {
+    let words: Vec<String> = derive_query_terms(query)
+        .into_iter()
+        .map(|d| d.term)
+        .filter(|w| w.len() >= 3)
+        .collect();
+    if words.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare("SELECT id, name, pagerank_score FROM symbols;")?;
+    let rows: Vec<(i64, String, f64)> = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2).unwrap_or(0.0),
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut scored: Vec<(usize, f64, i64)> = Vec::new();
+    for (id, name, pagerank_score) in &rows {
+        let lowered = name.to_lowercase();
+        for word in &words {
+            let distance = levenshtein_distance(word, &lowered);
+            if distance <= MAX_TYPO_DISTANCE {
+                scored.push((distance, *pagerank_score, *id));
+                break;
+            }
+        }
+    }
+    scored.sort_by(|a, b| {
+        a.0.cmp(&b.0).then_with(|| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    Ok(scored.into_iter().take(8).map(|(_, _, id)| id).collect())
+}
+
 // ---------------------------------------------------------------------------
 // Internal helper: pick seeds
 // ---------------------------------------------------------------------------
@@ -216,11 +742,16 @@ fn pick_seeds(conn: &Connection, query: &str, entry_points: &[String]) -> BombeR
         }
     }
 
-    // 3. Fallback to LIKE
-    let words: Vec<String> = query_text
-        .split_whitespace()
-        .map(|w| w.trim().to_lowercase())
+    // 3. Fallback to LIKE, widened with derived terms (subwords split out of
+    // camelCase/snake_case identifiers, plus adjacent-word concatenations)
+    // so "user auth token" can still match a symbol named `UserAuthToken`.
+    let mut derived: Vec<DerivedTerm> = derive_query_terms(query_text);
+    derived.sort_by(|a, b| b.weight.partial_cmp(&a.weight).unwrap_or(std::cmp::Ordering::Equal));
+    let words: Vec<String> = derived
+        .into_iter()
+        .map(|d| d.term)
         .filter(|w| !w.is_empty())
+        .take(12)
         .collect();
     if words.is_empty() {
         return Ok(Vec::new());
@@ -256,41 +787,31 @@ fn pick_seeds(conn: &Connection, query: &str, entry_points: &[String]) -> BombeR
         .query_map(param_refs.as_slice(), |row| row.get(0))?
         .filter_map(|r| r.ok())
         .collect();
-    Ok(rows)
+    if !rows.is_empty() {
+        return Ok(rows);
+    }
+
+    // 4. Last resort: typo-tolerant matching within a bounded edit distance.
+    pick_seeds_fuzzy(conn, query_text)
 }
 
 // ---------------------------------------------------------------------------
 // Internal helper: BFS expansion
 // ---------------------------------------------------------------------------
 
-fn expand(
-    conn: &Connection,
-    seeds: &[i64],
-    depth: i64,
-    max_nodes: i64,
-) -> BombeResult<HashMap<i64, i64>> {
+fn expand(graph: &CodeGraph, seeds: &[i64], depth: i64, max_nodes: i64) -> BombeResult<HashMap<i64, i64>> {
     let mut reached: HashMap<i64, i64> = HashMap::new();
     let mut queue: VecDeque<(i64, i64)> = VecDeque::new();
+    let mut visited = graph.new_node_set();
 
     for &seed in seeds {
         reached.insert(seed, 0);
         queue.push_back((seed, 0));
+        if let Some(idx) = graph.dense_index(seed) {
+            visited.insert(idx);
+        }
     }
 
-    let placeholders = rel_placeholders();
-    let base_param_count = RELATIONSHIPS.len();
-
-    let sql = format!(
-        "SELECT source_id, target_id FROM edges \
-         WHERE source_type = 'symbol' AND target_type = 'symbol' \
-         AND relationship IN ({placeholders}) \
-         AND (source_id = ?{} OR target_id = ?{});",
-        base_param_count + 1,
-        base_param_count + 2
-    );
-
-    let mut stmt = conn.prepare(&sql)?;
-
     while let Some((current, current_depth)) = queue.pop_front() {
         if reached.len() as i64 >= max_nodes {
             break;
@@ -298,31 +819,17 @@ fn expand(
         if current_depth >= depth {
             continue;
         }
+        let Some(current_idx) = graph.dense_index(current) else {
+            continue;
+        };
 
-        let mut params = rel_params();
-        params.push(Box::new(current));
-        params.push(Box::new(current));
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            params.iter().map(|p| p.as_ref()).collect();
-
-        let rows: Vec<(i64, i64)> = stmt
-            .query_map(param_refs.as_slice(), |row| {
-                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        for (source_id, target_id) in rows {
-            let neighbor = if source_id == current {
-                target_id
-            } else {
-                source_id
-            };
-            let next_depth = current_depth + 1;
+        let next_depth = current_depth + 1;
+        for &neighbor_idx in graph.neighbors(current_idx) {
+            let neighbor = graph.node_id(neighbor_idx);
             let previous = reached.get(&neighbor).copied();
             if previous.is_none() || next_depth < previous.unwrap() {
                 reached.insert(neighbor, next_depth);
-                if (reached.len() as i64) < max_nodes {
+                if visited.insert(neighbor_idx) && (reached.len() as i64) < max_nodes {
                     queue.push_back((neighbor, next_depth));
                 }
             }
@@ -337,7 +844,7 @@ fn expand(
 // ---------------------------------------------------------------------------
 
 fn personalized_pagerank(
-    conn: &Connection,
+    graph: &CodeGraph,
     seeds: &[i64],
     nodes: &[i64],
     damping: f64,
@@ -347,37 +854,17 @@ fn personalized_pagerank(
         return Ok(HashMap::new());
     }
 
-    let node_set: HashSet<i64> = nodes.iter().copied().collect();
-    let mut adjacency: HashMap<i64, Vec<i64>> = HashMap::new();
+    // Restrict walks to the requested node set via a dense membership bitset
+    // rather than re-fetching/filtering edges from `edges` again.
+    let mut in_set = graph.new_node_set();
+    let mut dense_nodes: Vec<usize> = Vec::with_capacity(nodes.len());
     for &node in nodes {
-        adjacency.insert(node, Vec::new());
-    }
-
-    // Fetch all relevant edges
-    let placeholders = rel_placeholders();
-    let sql = format!(
-        "SELECT source_id, target_id FROM edges \
-         WHERE source_type = 'symbol' AND target_type = 'symbol' \
-         AND relationship IN ({placeholders});"
-    );
-    let mut stmt = conn.prepare(&sql)?;
-    let params = rel_params();
-    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-    let rows: Vec<(i64, i64)> = stmt
-        .query_map(param_refs.as_slice(), |row| {
-            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
-        })?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    for (source, target) in rows {
-        if node_set.contains(&source) && node_set.contains(&target) {
-            adjacency.entry(source).or_default().push(target);
-            adjacency.entry(target).or_default().push(source);
+        if let Some(idx) = graph.dense_index(node) {
+            in_set.insert(idx);
+            dense_nodes.push(idx);
         }
     }
 
-    // Build restart vector
     let seed_set: HashSet<i64> = seeds.iter().copied().collect();
     let seed_count = seed_set.len();
     let mut restart: HashMap<i64, f64> = HashMap::new();
@@ -392,20 +879,44 @@ fn personalized_pagerank(
 
     let mut scores: HashMap<i64, f64> = restart.clone();
 
+    // Direction-aware power iteration: each node pushes its score forward
+    // along outgoing edges only, proportional to the edge's relationship
+    // weight rather than uniform 1/len. Dangling nodes (no outgoing edges
+    // within the node set) would otherwise leak mass out of the walk, so
+    // their score is redistributed according to the restart vector, same as
+    // the teleport step, to preserve the stochastic invariant.
     for _ in 0..iterations {
         let mut next_scores: HashMap<i64, f64> = HashMap::new();
         for &node in nodes {
             next_scores.insert(node, (1.0 - damping) * restart[&node]);
         }
-        for (&source, targets) in &adjacency {
-            if targets.is_empty() {
+
+        let mut dangling_mass = 0.0;
+        for &source_idx in &dense_nodes {
+            let source_id = graph.node_id(source_idx);
+            let out_edges: Vec<(i64, f64)> = graph
+                .out_edges(source_idx)
+                .iter()
+                .filter(|(idx, _, _)| in_set.contains(*idx))
+                .map(|(idx, weight, _)| (graph.node_id(*idx), *weight))
+                .collect();
+            let total_weight: f64 = out_edges.iter().map(|(_, w)| w).sum();
+            if out_edges.is_empty() || total_weight <= 0.0 {
+                dangling_mass += scores[&source_id];
                 continue;
             }
-            let share = damping * scores[&source] / targets.len() as f64;
-            for &target in targets {
+            for (target, weight) in out_edges {
+                let share = damping * scores[&source_id] * weight / total_weight;
                 *next_scores.entry(target).or_insert(0.0) += share;
             }
         }
+
+        if dangling_mass > 0.0 {
+            for &node in nodes {
+                *next_scores.entry(node).or_insert(0.0) += damping * dangling_mass * restart[&node];
+            }
+        }
+
         scores = next_scores;
     }
 
@@ -416,38 +927,30 @@ fn personalized_pagerank(
 // Internal helper: adjacency for topology ordering
 // ---------------------------------------------------------------------------
 
-fn build_adjacency(conn: &Connection, nodes: &[i64]) -> BombeResult<HashMap<i64, HashSet<i64>>> {
+fn build_adjacency(graph: &CodeGraph, nodes: &[i64]) -> BombeResult<HashMap<i64, HashSet<i64>>> {
     if nodes.is_empty() {
         return Ok(HashMap::new());
     }
 
-    let node_set: HashSet<i64> = nodes.iter().copied().collect();
-    let mut adjacency: HashMap<i64, HashSet<i64>> = HashMap::new();
+    let mut in_set = graph.new_node_set();
     for &node in nodes {
-        adjacency.insert(node, HashSet::new());
+        if let Some(idx) = graph.dense_index(node) {
+            in_set.insert(idx);
+        }
     }
 
-    let placeholders = rel_placeholders();
-    let sql = format!(
-        "SELECT source_id, target_id FROM edges \
-         WHERE source_type = 'symbol' AND target_type = 'symbol' \
-         AND relationship IN ({placeholders});"
-    );
-    let mut stmt = conn.prepare(&sql)?;
-    let params = rel_params();
-    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-    let rows: Vec<(i64, i64)> = stmt
-        .query_map(param_refs.as_slice(), |row| {
-            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
-        })?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    for (source, target) in rows {
-        if node_set.contains(&source) && node_set.contains(&target) {
-            adjacency.entry(source).or_default().insert(target);
-            adjacency.entry(target).or_default().insert(source);
-        }
+    let mut adjacency: HashMap<i64, HashSet<i64>> = HashMap::new();
+    for &node in nodes {
+        let neighbors: HashSet<i64> = match graph.dense_index(node) {
+            Some(idx) => graph
+                .neighbors(idx)
+                .iter()
+                .filter(|&&n| in_set.contains(n))
+                .map(|&n| graph.node_id(n))
+                .collect(),
+            None => HashSet::new(),
+        };
+        adjacency.insert(node, neighbors);
     }
 
     Ok(adjacency)
@@ -542,7 +1045,10 @@ fn quality_metrics(
     tokens_used: i64,
     adjacency: &HashMap<i64, HashSet<i64>>,
     duplicate_skips: i64,
-    redaction_hits: i64,
+    redaction_hits: &HashMap<String, i64>,
+    semantic_ratio: f64,
+    mean_semantic_contribution: f64,
+    tokenizer_used: &str,
 ) -> serde_json::Value {
     if included_symbols.is_empty() {
         return serde_json::json!({
@@ -552,7 +1058,11 @@ fn quality_metrics(
             "avg_depth": 0.0,
             "included_count": 0,
             "dedupe_ratio": 1.0,
+            "duplicate_skips": duplicate_skips,
             "redaction_hits": redaction_hits,
+            "semantic_ratio": semantic_ratio,
+            "mean_semantic_contribution": mean_semantic_contribution,
+            "tokenizer": tokenizer_used,
         });
     }
 
@@ -601,11 +1111,15 @@ fn quality_metrics(
         "avg_depth": round4(avg_depth),
         "included_count": included_count,
         "dedupe_ratio": round4(dedupe_ratio),
+        "duplicate_skips": duplicate_skips,
         "redaction_hits": redaction_hits,
+        "semantic_ratio": semantic_ratio,
+        "mean_semantic_contribution": round4(mean_semantic_contribution),
+        "tokenizer": tokenizer_used,
     })
 }
 
-fn round4(v: f64) -> f64 {
+pub(crate) fn round4(v: f64) -> f64 {
     (v * 10000.0).round() / 10000.0
 }
 
@@ -639,12 +1153,14 @@ struct IncludedSymbol {
     included_as: String,
     source: String,
     selection_reason: String,
+    relationship_paths: Vec<serde_json::Value>,
 }
 
 // ---------------------------------------------------------------------------
 // Public implementation (pure Rust, no Python dependency)
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_context_impl(
     conn: &Connection,
     query: &str,
@@ -652,7 +1168,48 @@ pub fn get_context_impl(
     token_budget: i64,
     include_signatures_only: bool,
     expansion_depth: i64,
+    enable_semantic_seeds: bool,
+    semantic_weight: f64,
+    query_embedding: Option<Vec<f32>>,
+    pagerank_damping: f64,
+    relationship_weight_overrides: Option<HashMap<String, f64>>,
+    disabled_redaction_categories: Option<Vec<String>>,
+    custom_redaction_rules: Option<Vec<(String, String, String)>>,
+    semantic_ratio: f64,
+    scoring: &str,
+    rrf_k: f64,
+    ranking_rules: Option<Vec<String>>,
+    ranking_epsilon: f64,
+    tokenizer: &str,
 ) -> BombeResult<serde_json::Value> {
+    // Resolve the token counter once, up front, so the budget loop below
+    // reuses the same loaded encoder for every candidate instead of
+    // re-parsing its merge table per symbol. `tokenizer_used` records which
+    // one actually ran (falls back to "heuristic" when `tokenizer` is empty
+    // or names an encoding with no resolvable merge table).
+    let bpe_encoder = if tokenizer.is_empty() {
+        None
+    } else {
+        load_bpe_encoder(tokenizer)
+    };
+    let tokenizer_used = match &bpe_encoder {
+        Some(encoder) => encoder.name().to_string(),
+        None => "heuristic".to_string(),
+    };
+    let count_tokens = |text: &str| match &bpe_encoder {
+        Some(encoder) => encoder.count_tokens(text),
+        None => estimate_tokens(text, None),
+    };
+
+    let relationship_weights = match &relationship_weight_overrides {
+        Some(overrides) => RelationshipWeights::with_overrides(overrides),
+        None => RelationshipWeights::default(),
+    };
+    let redaction_config = RedactionConfig::new(
+        disabled_redaction_categories.as_deref().unwrap_or(&[]),
+        custom_redaction_rules.as_deref().unwrap_or(&[]),
+    )?;
+
     // 1. Normalize request
     let normalized_query = truncate_query(query);
     let clamped_entry_points: Vec<String> = entry_points
@@ -675,7 +1232,28 @@ pub fn get_context_impl(
     let dynamic_node_cap = adaptive_graph_cap(total_symbols, MAX_GRAPH_VISITED, Some(128));
 
     // 3. Pick seeds
-    let seeds = pick_seeds(conn, &normalized_query, &clamped_entry_points)?;
+    let lexical_seeds = pick_seeds(conn, &normalized_query, &clamped_entry_points)?;
+
+    // 3b. Fuse in semantic seeds from the embedding index, when enabled and
+    // available. Falls back silently to lexical-only seeding when no
+    // `symbol_embeddings` table exists yet, or the caller didn't supply a
+    // query embedding.
+    let seeds = if enable_semantic_seeds {
+        match query_embedding
+            .as_ref()
+            .map(|vector| -> BombeResult<Vec<i64>> {
+                let Some(index) = SemanticIndex::build(conn, SEMANTIC_EMBEDDING_MODEL)? else {
+                    return Ok(lexical_seeds.clone());
+                };
+                let semantic_hits = index.search(vector, MAX_CONTEXT_SEEDS, SEMANTIC_SEARCH_EF);
+                Ok(fuse_seeds(&lexical_seeds, &semantic_hits, semantic_weight))
+            }) {
+            Some(fused) => fused?,
+            None => lexical_seeds,
+        }
+    } else {
+        lexical_seeds
+    };
 
     // 4. If no seeds, return empty response
     if seeds.is_empty() {
@@ -693,14 +1271,18 @@ pub fn get_context_impl(
         }));
     }
 
-    // 5. Expand from seeds via BFS
-    let reached = expand(conn, &seeds, clamped_depth, dynamic_node_cap)?;
+    // 5. Load the code graph once, shared by BFS expansion, PPR, and (later)
+    // topology ordering, instead of each running its own `edges` scan.
+    let code_graph = CodeGraph::load_with_weights(conn, &relationship_weights)?;
+
+    // 6. Expand from seeds via BFS
+    let reached = expand(&code_graph, &seeds, clamped_depth, dynamic_node_cap)?;
     let symbol_ids: Vec<i64> = reached.keys().copied().collect();
 
-    // 6. Compute personalized PageRank
-    let ppr_scores = personalized_pagerank(conn, &seeds, &symbol_ids, 0.85, 20)?;
+    // 7. Compute personalized PageRank
+    let ppr_scores = personalized_pagerank(&code_graph, &seeds, &symbol_ids, pagerank_damping, 20)?;
 
-    // 7. Load symbol rows
+    // 8. Load symbol rows
     let id_placeholders: String = symbol_ids
         .iter()
         .enumerate()
@@ -737,11 +1319,12 @@ pub fn get_context_impl(
         .filter_map(|r| r.ok())
         .collect();
 
-    let terms = query_terms(&normalized_query);
+    let derived_terms = derive_query_terms(&normalized_query);
     let seed_set: HashSet<i64> = seeds.iter().copied().collect();
 
-    // 8. Compute ranking scores
+    // 9. Compute ranking scores
     let mut ranked: Vec<(f64, SymbolData)> = Vec::new();
+    let mut signals: Vec<CandidateSignals> = Vec::new();
     for row in &symbol_rows {
         let depth = reached.get(&row.id).copied().unwrap_or(0);
         let ppr = ppr_scores.get(&row.id).copied().unwrap_or(0.0);
@@ -752,11 +1335,20 @@ pub fn get_context_impl(
             _ => 0.25,
         };
         let base_score = ppr * row.pagerank_score.max(1e-9) * proximity_bonus;
-        let lexical_relevance =
-            symbol_query_relevance(&row.name, &row.qualified_name, &row.signature, &terms);
-        let lexical_boost = 1.0 + (0.08 * lexical_relevance as f64).min(0.25);
+        let lexical_relevance = symbol_derived_relevance(
+            &row.name,
+            &row.qualified_name,
+            &row.signature,
+            &derived_terms,
+        );
+        let lexical_boost = 1.0 + (0.08 * lexical_relevance).min(0.25);
         let score = base_score * lexical_boost;
 
+        signals.push(CandidateSignals {
+            ppr,
+            global_pagerank: row.pagerank_score,
+            lexical_relevance,
+        });
         ranked.push((
             score,
             SymbolData {
@@ -774,11 +1366,58 @@ pub fn get_context_impl(
         ));
     }
 
-    // Sort by score descending
-    ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    // 9b. Either blend in an optional semantic-similarity signal on top of
+    // the product score (the default, backward-compatible path), or replace
+    // the score entirely with Reciprocal Rank Fusion over each signal's
+    // independent ranking — scale-invariant, so one near-zero factor (e.g. a
+    // symbol barely reached by PPR) can't annihilate a strong lexical match.
+    let (mean_semantic_contribution, rrf_signal_ranks) = if scoring == "rrf" {
+        let ranks = fuse_scores_rrf(
+            conn,
+            &mut ranked,
+            &signals,
+            query_embedding.as_deref(),
+            rrf_k,
+        )?;
+        (0.0, ranks)
+    } else {
+        let contribution = blend_semantic_scores(
+            conn,
+            &mut ranked,
+            query_embedding.as_deref(),
+            semantic_ratio,
+        )?;
+        (contribution, HashMap::new())
+    };
+
+    // Sort either by an explicit, named ranking-rule pipeline compared
+    // lexicographically (only falling through to the next rule on a tie), or
+    // by the blended score descending — the default, matching today's
+    // behavior when no `ranking_rules` are supplied.
+    match &ranking_rules {
+        Some(rules) if !rules.is_empty() => {
+            validate_ranking_rules(rules)?;
+            let signals_by_id: HashMap<i64, CandidateSignals> = ranked
+                .iter()
+                .map(|(_, sym)| sym.id)
+                .zip(signals.iter().cloned())
+                .collect();
+            ranked.sort_by(|a, b| {
+                compare_by_ranking_rules(
+                    rules,
+                    (&a.1, &signals_by_id[&a.1.id]),
+                    (&b.1, &signals_by_id[&b.1.id]),
+                    ranking_epsilon,
+                )
+            });
+        }
+        _ => {
+            ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        }
+    }
 
-    // 9. Build adjacency and topology ordering
-    let adjacency = build_adjacency(conn, &symbol_ids)?;
+    // 10. Build adjacency and topology ordering
+    let adjacency = build_adjacency(&code_graph, &symbol_ids)?;
     let topo_order = topology_order(&ranked, &seeds, &adjacency);
 
     // Build lookup map: symbol_id -> SymbolData
@@ -787,12 +1426,12 @@ pub fn get_context_impl(
         .map(|(_, sym)| (sym.id, sym.clone()))
         .collect();
 
-    // 10. Walk topology order, adding symbols within token budget
+    // 11. Walk topology order, adding symbols within token budget
     let mut tokens_used: i64 = 0;
     let mut included_symbols: Vec<IncludedSymbol> = Vec::new();
     let mut seen_bundle_keys: HashSet<(String, String, String)> = HashSet::new();
     let mut duplicate_skips: i64 = 0;
-    let mut total_redaction_hits: i64 = 0;
+    let mut total_redaction_hits: HashMap<String, i64> = HashMap::new();
 
     for (symbol_id, topology_reason) in &topo_order {
         if included_symbols.len() as i64 >= dynamic_node_cap {
@@ -816,9 +1455,10 @@ pub fn get_context_impl(
         }
 
         // Redact
-        let (redacted_source, source_redaction_hits) = redact_sensitive_text(&source);
+        let (redacted_source, source_redaction_hits) =
+            redact_sensitive_text(&source, &redaction_config);
         source = redacted_source;
-        total_redaction_hits += source_redaction_hits;
+        merge_redaction_hits(&mut total_redaction_hits, source_redaction_hits);
 
         // Dedup via bundle key
         let bundle_key = (
@@ -831,7 +1471,7 @@ pub fn get_context_impl(
             continue;
         }
 
-        let mut symbol_tokens = estimate_tokens(&source, None);
+        let mut symbol_tokens = count_tokens(&source);
 
         if tokens_used + symbol_tokens > clamped_budget {
             if mode == "full_source" {
@@ -847,7 +1487,7 @@ pub fn get_context_impl(
                     duplicate_skips += 1;
                     continue;
                 }
-                symbol_tokens = estimate_tokens(&source, None);
+                symbol_tokens = count_tokens(&source);
                 // Update bundle_key to the fallback
                 let bundle_key = fallback_bundle_key;
                 if tokens_used + symbol_tokens > clamped_budget {
@@ -872,6 +1512,44 @@ pub fn get_context_impl(
         if symbol.is_seed {
             reason_parts.push("seed_match".to_string());
         }
+        if let Some(ranks) = rrf_signal_ranks.get(&symbol.id) {
+            reason_parts.push(ranks.clone());
+        }
+
+        // Explain non-seed symbols with up to PATH_EXPLANATION_K shortest
+        // relationship paths back to whichever seed reaches them cheapest.
+        let relationship_paths = if symbol.is_seed {
+            Vec::new()
+        } else {
+            nearest_seed_paths(
+                &code_graph,
+                &seeds,
+                symbol.id,
+                &ppr_scores,
+                PATH_EXPLANATION_K,
+                clamped_depth as usize,
+            )
+        };
+        if let Some(best_path) = relationship_paths.first() {
+            reason_parts.push(format!(
+                "via={}",
+                best_path
+                    .iter()
+                    .map(|step| step.relationship.as_str())
+                    .filter(|r| !r.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("->")
+            ));
+        }
+        let relationship_path_json: Vec<serde_json::Value> = relationship_paths
+            .iter()
+            .map(|path| {
+                path.iter()
+                    .map(|step| serde_json::json!([step.symbol_id, step.relationship]))
+                    .collect::<Vec<_>>()
+            })
+            .map(serde_json::Value::from)
+            .collect();
 
         included_symbols.push(IncludedSymbol {
             id: symbol.id,
@@ -885,10 +1563,11 @@ pub fn get_context_impl(
             included_as: mode,
             source,
             selection_reason: reason_parts.join(","),
+            relationship_paths: relationship_path_json,
         });
     }
 
-    // 11. Group by file, build file entries
+    // 12. Group by file, build file entries
     let mut files: BTreeMap<String, Vec<usize>> = BTreeMap::new();
     for (idx, sym) in included_symbols.iter().enumerate() {
         files.entry(sym.file_path.clone()).or_default().push(idx);
@@ -914,6 +1593,7 @@ pub fn get_context_impl(
                     "depth": sym.depth,
                     "qualified_name": sym.qualified_name,
                     "selection_reason": sym.selection_reason,
+                    "relationship_paths": sym.relationship_paths,
                 })
             })
             .collect();
@@ -925,7 +1605,7 @@ pub fn get_context_impl(
     }
     // file_entries is already sorted by path (BTreeMap guarantees order)
 
-    // 12. Build summary and relationship map
+    // 13. Build summary and relationship map
     let summary = format!(
         "Selected {} symbols from {} files.",
         included_symbols.len(),
@@ -938,7 +1618,7 @@ pub fn get_context_impl(
         .collect::<Vec<_>>()
         .join(" -> ");
 
-    // 13. Compute quality metrics
+    // 14. Compute quality metrics
     let qm = quality_metrics(
         &included_symbols,
         &seeds,
@@ -946,10 +1626,13 @@ pub fn get_context_impl(
         tokens_used,
         &adjacency,
         duplicate_skips,
-        total_redaction_hits,
+        &total_redaction_hits,
+        semantic_ratio,
+        mean_semantic_contribution,
+        &tokenizer_used,
     );
 
-    // 14. Build final payload
+    // 15. Build final payload
     let payload = serde_json::json!({
         "query": normalized_query,
         "context_bundle": {
@@ -975,9 +1658,13 @@ pub fn get_context_impl(
 /// Context assembly query: seeded BFS expansion + personalized PageRank +
 /// topology-aware ordering + token-budget pruning with secret redaction.
 ///
+/// `tokenizer` selects a real BPE encoding (`"cl100k"`, `"o200k"`) to count
+/// `symbol_tokens`/`tokens_used` exactly instead of the `estimate_tokens`
+/// heuristic; leave it `""` (the default) to keep the heuristic.
+///
 /// Returns the full context payload as a Python dict (via JSON round-trip).
 #[pyfunction]
-#[pyo3(signature = (db, query, entry_points=vec![], token_budget=8000, include_signatures_only=false, expansion_depth=2))]
+#[pyo3(signature = (db, query, entry_points=vec![], token_budget=8000, include_signatures_only=false, expansion_depth=2, enable_semantic_seeds=false, semantic_weight=0.35, query_embedding=None, pagerank_damping=0.85, relationship_weights=None, disabled_redaction_categories=None, custom_redaction_rules=None, semantic_ratio=0.0, scoring="product", rrf_k=60.0, ranking_rules=None, ranking_epsilon=1e-6, tokenizer=""))]
 #[allow(clippy::too_many_arguments)]
 pub fn get_context(
     py: Python<'_>,
@@ -987,6 +1674,19 @@ pub fn get_context(
     token_budget: i64,
     include_signatures_only: bool,
     expansion_depth: i64,
+    enable_semantic_seeds: bool,
+    semantic_weight: f64,
+    query_embedding: Option<Vec<f32>>,
+    pagerank_damping: f64,
+    relationship_weights: Option<HashMap<String, f64>>,
+    disabled_redaction_categories: Option<Vec<String>>,
+    custom_redaction_rules: Option<Vec<(String, String, String)>>,
+    semantic_ratio: f64,
+    scoring: &str,
+    rrf_k: f64,
+    ranking_rules: Option<Vec<String>>,
+    ranking_epsilon: f64,
+    tokenizer: &str,
 ) -> PyResult<PyObject> {
     let conn = db.connect_internal()?;
     let result = get_context_impl(
@@ -996,6 +1696,19 @@ pub fn get_context(
         token_budget,
         include_signatures_only,
         expansion_depth,
+        enable_semantic_seeds,
+        semantic_weight,
+        query_embedding,
+        pagerank_damping,
+        relationship_weights,
+        disabled_redaction_categories,
+        custom_redaction_rules,
+        semantic_ratio,
+        scoring,
+        rrf_k,
+        ranking_rules,
+        ranking_epsilon,
+        tokenizer,
     )?;
     let json_str = serde_json::to_string(&result)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
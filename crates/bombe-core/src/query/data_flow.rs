@@ -35,14 +35,37 @@ fn resolve_symbol(
     }
 }
 
+/// Build a `?1, ?2, ...` placeholder list sized to `relationships`, for an
+/// `IN (...)` clause whose membership varies per call (unlike
+/// `query::context::rel_placeholders`, which is sized to the fixed
+/// `RELATIONSHIPS` constant).
+fn relationship_clause(relationships: &[String]) -> String {
+    (1..=relationships.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn normalize_relationships(relationships: &[String]) -> Vec<String> {
+    if relationships.is_empty() {
+        vec!["CALLS".to_string()]
+    } else {
+        relationships.to_vec()
+    }
+}
+
 pub fn trace_data_flow_impl(
     conn: &Connection,
     symbol_name: &str,
     direction: &str,
     max_depth: i64,
+    relationships: &[String],
 ) -> BombeResult<serde_json::Value> {
     let normalized_symbol = truncate_query(symbol_name);
     let bounded_depth = clamp_depth(max_depth, MAX_FLOW_DEPTH);
+    let relationships = normalize_relationships(relationships);
+    let rel_clause = relationship_clause(&relationships);
+    let rel_param_index = relationships.len() + 1;
 
     let total_symbols: i64 = conn
         .query_row("SELECT COUNT(*) FROM symbols;", [], |row| row.get(0))
@@ -71,16 +94,16 @@ pub fn trace_data_flow_impl(
         }),
     );
 
-    let mut upstream_stmt = conn.prepare(
-        "SELECT e.source_id, e.line_number, s.name, s.qualified_name, s.file_path \
+    let mut upstream_stmt = conn.prepare(&format!(
+        "SELECT e.source_id, e.line_number, e.relationship, s.name, s.qualified_name, s.file_path \
          FROM edges e JOIN symbols s ON s.id = e.source_id \
-         WHERE e.relationship = 'CALLS' AND e.target_type = 'symbol' AND e.target_id = ?1;",
-    )?;
-    let mut downstream_stmt = conn.prepare(
-        "SELECT e.target_id, e.line_number, s.name, s.qualified_name, s.file_path \
+         WHERE e.relationship IN ({rel_clause}) AND e.target_type = 'symbol' AND e.target_id = ?{rel_param_index};"
+    ))?;
+    let mut downstream_stmt = conn.prepare(&format!(
+        "SELECT e.target_id, e.line_number, e.relationship, s.name, s.qualified_name, s.file_path \
          FROM edges e JOIN symbols s ON s.id = e.target_id \
-         WHERE e.relationship = 'CALLS' AND e.source_type = 'symbol' AND e.source_id = ?1;",
-    )?;
+         WHERE e.relationship IN ({rel_clause}) AND e.source_type = 'symbol' AND e.source_id = ?{rel_param_index};"
+    ))?;
 
     while let Some((current_id, depth, _role)) = queue.pop_front() {
         if paths.len() as i64 >= dynamic_edge_cap || nodes.len() as i64 >= dynamic_visited_cap {
@@ -97,19 +120,23 @@ pub fn trace_data_flow_impl(
             .to_string();
 
         if direction == "upstream" || direction == "both" {
-            let rows: Vec<(i64, Option<i64>, String, String, String)> = upstream_stmt
-                .query_map(rusqlite::params![current_id], |row| {
+            let mut bind_params: Vec<&dyn rusqlite::types::ToSql> =
+                relationships.iter().map(|r| r as &dyn rusqlite::types::ToSql).collect();
+            bind_params.push(&current_id);
+            let rows: Vec<(i64, Option<i64>, String, String, String, String)> = upstream_stmt
+                .query_map(rusqlite::params_from_iter(bind_params), |row| {
                     Ok((
                         row.get(0)?,
                         row.get(1)?,
                         row.get(2)?,
                         row.get(3)?,
                         row.get(4)?,
+                        row.get(5)?,
                     ))
                 })?
                 .filter_map(|r| r.ok())
                 .collect();
-            for (neighbor_id, line_number, name, qname, fpath) in rows {
+            for (neighbor_id, line_number, relationship, name, qname, fpath) in rows {
                 if paths.len() as i64 >= dynamic_edge_cap
                     || nodes.len() as i64 >= dynamic_visited_cap
                 {
@@ -125,7 +152,7 @@ pub fn trace_data_flow_impl(
                     "from_id": neighbor_id, "from_name": name,
                     "to_id": current_id, "to_name": current_name,
                     "line": line_number.unwrap_or(0), "depth": depth + 1,
-                    "relationship": "CALLS",
+                    "relationship": relationship,
                 }));
                 let key = (neighbor_id, "upstream".to_string());
                 if !seen.contains(&key) {
@@ -136,19 +163,23 @@ pub fn trace_data_flow_impl(
         }
 
         if direction == "downstream" || direction == "both" {
-            let rows: Vec<(i64, Option<i64>, String, String, String)> = downstream_stmt
-                .query_map(rusqlite::params![current_id], |row| {
+            let mut bind_params: Vec<&dyn rusqlite::types::ToSql> =
+                relationships.iter().map(|r| r as &dyn rusqlite::types::ToSql).collect();
+            bind_params.push(&current_id);
+            let rows: Vec<(i64, Option<i64>, String, String, String, String)> = downstream_stmt
+                .query_map(rusqlite::params_from_iter(bind_params), |row| {
                     Ok((
                         row.get(0)?,
                         row.get(1)?,
                         row.get(2)?,
                         row.get(3)?,
                         row.get(4)?,
+                        row.get(5)?,
                     ))
                 })?
                 .filter_map(|r| r.ok())
                 .collect();
-            for (neighbor_id, line_number, name, qname, fpath) in rows {
+            for (neighbor_id, line_number, relationship, name, qname, fpath) in rows {
                 if paths.len() as i64 >= dynamic_edge_cap
                     || nodes.len() as i64 >= dynamic_visited_cap
                 {
@@ -164,7 +195,7 @@ pub fn trace_data_flow_impl(
                     "from_id": current_id, "from_name": current_name,
                     "to_id": neighbor_id, "to_name": name,
                     "line": line_number.unwrap_or(0), "depth": depth + 1,
-                    "relationship": "CALLS",
+                    "relationship": relationship,
                 }));
                 let key = (neighbor_id, "downstream".to_string());
                 if !seen.contains(&key) {
@@ -197,8 +228,9 @@ pub fn trace_data_flow_impl(
     });
 
     let summary = format!(
-        "Traced {} call edges across {} symbols (direction={direction}, depth<={bounded_depth}).",
+        "Traced {} {} edge(s) across {} symbols (direction={direction}, depth<={bounded_depth}).",
         paths.len(),
+        relationships.join("/"),
         node_list.len()
     );
 
@@ -209,6 +241,7 @@ pub fn trace_data_flow_impl(
         },
         "direction": direction,
         "max_depth": bounded_depth,
+        "relationships": relationships,
         "summary": summary,
         "nodes": node_list,
         "paths": paths,
@@ -216,16 +249,279 @@ pub fn trace_data_flow_impl(
 }
 
 #[pyfunction]
-#[pyo3(signature = (db, symbol_name, direction="both", max_depth=3))]
+#[pyo3(signature = (db, symbol_name, direction="both", max_depth=3, relationships=vec!["CALLS".to_string()]))]
 pub fn trace_data_flow(
     py: Python<'_>,
     db: &crate::store::database::Database,
     symbol_name: &str,
     direction: &str,
     max_depth: i64,
+    relationships: Vec<String>,
+) -> PyResult<PyObject> {
+    let conn = db.connect_internal()?;
+    let result = trace_data_flow_impl(&conn, symbol_name, direction, max_depth, &relationships)?;
+    let json_str = serde_json::to_string(&result)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let json_module = py.import("json")?;
+    json_module
+        .call_method1("loads", (json_str,))
+        .map(|o| o.into())
+}
+
+/// Bidirectional BFS between two symbols: expand downstream from `source`
+/// and upstream from `target` one layer at a time (alternating sides),
+/// capped by `MAX_GRAPH_VISITED`, stopping at the first node both
+/// frontiers reach, then reconstruct the connecting path by following
+/// parent pointers back from each side. Answers "how does tainted input
+/// from X reach sink Y" directly, rather than making a caller manually
+/// diff two [`trace_data_flow_impl`] traces.
+pub fn trace_data_flow_between_impl(
+    conn: &Connection,
+    source_name: &str,
+    target_name: &str,
+    relationships: &[String],
+    max_depth: i64,
+) -> BombeResult<serde_json::Value> {
+    let bounded_depth = clamp_depth(max_depth, MAX_FLOW_DEPTH);
+    let relationships = normalize_relationships(relationships);
+    let rel_clause = relationship_clause(&relationships);
+    let rel_param_index = relationships.len() + 1;
+
+    let total_symbols: i64 = conn
+        .query_row("SELECT COUNT(*) FROM symbols;", [], |row| row.get(0))
+        .unwrap_or(0);
+    let dynamic_visited_cap = adaptive_graph_cap(total_symbols, MAX_GRAPH_VISITED, Some(128));
+
+    let source = resolve_symbol(conn, &truncate_query(source_name))?
+        .ok_or_else(|| BombeError::Query(format!("Symbol not found: {source_name}")))?;
+    let target = resolve_symbol(conn, &truncate_query(target_name))?
+        .ok_or_else(|| BombeError::Query(format!("Symbol not found: {target_name}")))?;
+    let (source_id, source_sym_name, source_qname, source_file) = source;
+    let (target_id, target_sym_name, target_qname, target_file) = target;
+
+    let mut nodes: HashMap<i64, serde_json::Value> = HashMap::new();
+    nodes.insert(
+        source_id,
+        serde_json::json!({
+            "id": source_id, "name": source_sym_name, "qualified_name": source_qname,
+            "file_path": source_file, "role": "source",
+        }),
+    );
+    nodes.insert(
+        target_id,
+        serde_json::json!({
+            "id": target_id, "name": target_sym_name, "qualified_name": target_qname,
+            "file_path": target_file, "role": "target",
+        }),
+    );
+
+    if source_id == target_id {
+        let summary = format!("{source_name} and {target_name} resolve to the same symbol.");
+        return Ok(serde_json::json!({
+            "source": nodes[&source_id], "target": nodes[&target_id],
+            "relationships": relationships, "found": true, "path_length": 0,
+            "summary": summary, "nodes": nodes.into_values().collect::<Vec<_>>(), "path": [],
+        }));
+    }
+
+    let downstream_sql = format!(
+        "SELECT e.target_id, e.relationship, e.line_number, s.name, s.qualified_name, s.file_path \
+         FROM edges e JOIN symbols s ON s.id = e.target_id \
+         WHERE e.relationship IN ({rel_clause}) AND e.source_type = 'symbol' AND e.source_id = ?{rel_param_index};"
+    );
+    let upstream_sql = format!(
+        "SELECT e.source_id, e.relationship, e.line_number, s.name, s.qualified_name, s.file_path \
+         FROM edges e JOIN symbols s ON s.id = e.source_id \
+         WHERE e.relationship IN ({rel_clause}) AND e.target_type = 'symbol' AND e.target_id = ?{rel_param_index};"
+    );
+    let mut downstream_stmt = conn.prepare(&downstream_sql)?;
+    let mut upstream_stmt = conn.prepare(&upstream_sql)?;
+
+    // `forward_parent[n] = (p, relationship, line)` means the BFS from
+    // `source` reached `n` via the edge `p -> n`. `backward_parent[n] = (p,
+    // relationship, line)` means the BFS from `target` reached `n` via the
+    // edge `n -> p` (upstream expansion walks callers, so the edge points
+    // from the newly discovered node towards the node already in the tree).
+    let mut forward_parent: HashMap<i64, (i64, String, i64)> = HashMap::new();
+    let mut backward_parent: HashMap<i64, (i64, String, i64)> = HashMap::new();
+    let mut forward_seen: HashSet<i64> = HashSet::new();
+    let mut backward_seen: HashSet<i64> = HashSet::new();
+    forward_seen.insert(source_id);
+    backward_seen.insert(target_id);
+    let mut forward_frontier: VecDeque<i64> = VecDeque::from([source_id]);
+    let mut backward_frontier: VecDeque<i64> = VecDeque::from([target_id]);
+    let mut visited_count = 2i64;
+    let mut meeting_node: Option<i64> = None;
+
+    'layers: for _ in 0..bounded_depth {
+        if forward_frontier.is_empty() && backward_frontier.is_empty() {
+            break;
+        }
+
+        let mut next_forward = VecDeque::new();
+        for node in forward_frontier.drain(..) {
+            if visited_count >= dynamic_visited_cap {
+                break;
+            }
+            let mut bind_params: Vec<&dyn rusqlite::types::ToSql> =
+                relationships.iter().map(|r| r as &dyn rusqlite::types::ToSql).collect();
+            bind_params.push(&node);
+            let rows: Vec<(i64, String, Option<i64>, String, String, String)> = downstream_stmt
+                .query_map(rusqlite::params_from_iter(bind_params), |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+                })?
+                .filter_map(|r| r.ok())
+                .collect();
+            for (neighbor_id, relationship, line_number, name, qname, fpath) in rows {
+                if forward_seen.contains(&neighbor_id) {
+                    continue;
+                }
+                forward_seen.insert(neighbor_id);
+                forward_parent.insert(neighbor_id, (node, relationship, line_number.unwrap_or(0)));
+                nodes.entry(neighbor_id).or_insert_with(|| {
+                    serde_json::json!({
+                        "id": neighbor_id, "name": name, "qualified_name": qname,
+                        "file_path": fpath, "role": "intermediate",
+                    })
+                });
+                visited_count += 1;
+                next_forward.push_back(neighbor_id);
+                if backward_seen.contains(&neighbor_id) {
+                    meeting_node = Some(neighbor_id);
+                    break;
+                }
+            }
+            if meeting_node.is_some() {
+                break;
+            }
+        }
+        forward_frontier = next_forward;
+        if meeting_node.is_some() {
+            break 'layers;
+        }
+
+        let mut next_backward = VecDeque::new();
+        for node in backward_frontier.drain(..) {
+            if visited_count >= dynamic_visited_cap {
+                break;
+            }
+            let mut bind_params: Vec<&dyn rusqlite::types::ToSql> =
+                relationships.iter().map(|r| r as &dyn rusqlite::types::ToSql).collect();
+            bind_params.push(&node);
+            let rows: Vec<(i64, String, Option<i64>, String, String, String)> = upstream_stmt
+                .query_map(rusqlite::params_from_iter(bind_params), |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+                })?
+                .filter_map(|r| r.ok())
+                .collect();
+            for (neighbor_id, relationship, line_number, name, qname, fpath) in rows {
+                if backward_seen.contains(&neighbor_id) {
+                    continue;
+                }
+                backward_seen.insert(neighbor_id);
+                backward_parent.insert(neighbor_id, (node, relationship, line_number.unwrap_or(0)));
+                nodes.entry(neighbor_id).or_insert_with(|| {
+                    serde_json::json!({
+                        "id": neighbor_id, "name": name, "qualified_name": qname,
+                        "file_path": fpath, "role": "intermediate",
+                    })
+                });
+                visited_count += 1;
+                next_backward.push_back(neighbor_id);
+                if forward_seen.contains(&neighbor_id) {
+                    meeting_node = Some(neighbor_id);
+                    break;
+                }
+            }
+            if meeting_node.is_some() {
+                break;
+            }
+        }
+        backward_frontier = next_backward;
+        if meeting_node.is_some() || visited_count >= dynamic_visited_cap {
+            break;
+        }
+    }
+
+    let Some(meeting) = meeting_node else {
+        let summary = format!(
+            "No path found from {source_name} to {target_name} within depth<={bounded_depth} \
+             over {} ({visited_count} symbols visited).",
+            relationships.join("/"),
+        );
+        return Ok(serde_json::json!({
+            "source": nodes[&source_id], "target": nodes[&target_id],
+            "relationships": relationships, "found": false, "path_length": serde_json::Value::Null,
+            "summary": summary, "nodes": [], "path": [],
+        }));
+    };
+
+    // source -> ... -> meeting, walking forward_parent back from `meeting`
+    // and reversing, since each step records (parent, rel, line) for an
+    // edge that points parent -> child.
+    let mut forward_chain: Vec<serde_json::Value> = Vec::new();
+    let mut cur = meeting;
+    while cur != source_id {
+        let (parent, relationship, line) = forward_parent[&cur].clone();
+        forward_chain.push(serde_json::json!({
+            "from_id": parent, "to_id": cur, "relationship": relationship, "line": line,
+        }));
+        cur = parent;
+    }
+    forward_chain.reverse();
+
+    // meeting -> ... -> target: each step in backward_parent already points
+    // from the node closer to `meeting` towards the node closer to
+    // `target`, so no reversal is needed here.
+    let mut backward_chain: Vec<serde_json::Value> = Vec::new();
+    let mut cur = meeting;
+    while cur != target_id {
+        let (parent, relationship, line) = backward_parent[&cur].clone();
+        backward_chain.push(serde_json::json!({
+            "from_id": cur, "to_id": parent, "relationship": relationship, "line": line,
+        }));
+        cur = parent;
+    }
+
+    let mut path = forward_chain;
+    path.extend(backward_chain);
+
+    let path_node_ids: HashSet<i64> = std::iter::once(source_id)
+        .chain(std::iter::once(target_id))
+        .chain(path.iter().filter_map(|e| e.get("from_id").and_then(|v| v.as_i64())))
+        .chain(path.iter().filter_map(|e| e.get("to_id").and_then(|v| v.as_i64())))
+        .collect();
+    let path_nodes: Vec<serde_json::Value> = path_node_ids
+        .into_iter()
+        .filter_map(|id| nodes.get(&id).cloned())
+        .collect();
+
+    let summary = format!(
+        "Found a path from {source_name} to {target_name} via {} edge(s) over {}.",
+        path.len(),
+        relationships.join("/"),
+    );
+
+    Ok(serde_json::json!({
+        "source": nodes[&source_id], "target": nodes[&target_id],
+        "relationships": relationships, "found": true, "path_length": path.len() as i64,
+        "summary": summary, "nodes": path_nodes, "path": path,
+    }))
+}
+
+#[pyfunction]
+#[pyo3(signature = (db, source, target, relationships=vec!["CALLS".to_string()], max_depth=6))]
+pub fn trace_data_flow_between(
+    py: Python<'_>,
+    db: &crate::store::database::Database,
+    source: &str,
+    target: &str,
+    relationships: Vec<String>,
+    max_depth: i64,
 ) -> PyResult<PyObject> {
     let conn = db.connect_internal()?;
-    let result = trace_data_flow_impl(&conn, symbol_name, direction, max_depth)?;
+    let result =
+        trace_data_flow_between_impl(&conn, source, target, &relationships, max_depth)?;
     let json_str = serde_json::to_string(&result)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
     let json_module = py.import("json")?;
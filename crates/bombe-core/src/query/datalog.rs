@@ -0,0 +1,236 @@
+//! Generic recursive (semi-naive Datalog) query engine over the `edges` table.
+//!
+//! `get_references_impl`, `trace_data_flow_impl`, and `get_blast_radius_impl`
+//! each hand-roll a fixed-shape BFS with a hardcoded depth and a fixed set of
+//! relationship types. This module generalizes that traversal into a single
+//! engine so callers can express arbitrary reachability queries: pick the
+//! seed nodes, the relationship types to follow, a direction, and an
+//! optional projection/filter predicate over the derived tuples.
+//!
+//! Evaluation follows classic semi-naive Datalog: seed a `delta` set with the
+//! initial facts, and on each round join only the newly derived tuples
+//! against `edges` (rather than the whole accumulated result), union the
+//! join output into the result set, and compute the next `delta` as whatever
+//! wasn't already known. Stop at fixpoint, or when a max-depth / max-tuples
+//! guard trips first.
+
+use std::collections::HashSet;
+
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+use crate::query::guards::{MAX_GRAPH_EDGES, MAX_GRAPH_VISITED};
+
+/// Direction to follow an edge relative to the node already reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Follow `source_id -> target_id` (forward / "callees"-style).
+    Forward,
+    /// Follow `target_id -> source_id` (backward / "callers"-style).
+    Backward,
+}
+
+/// A single derived tuple: the node reached, the edge relationship that
+/// produced it, the node it came from, and the depth at which it was
+/// derived (the seed nodes are depth 0).
+#[derive(Debug, Clone)]
+pub struct DerivedTuple {
+    pub node_id: i64,
+    pub from_id: i64,
+    pub relationship: String,
+    pub line_number: Option<i64>,
+    pub depth: i64,
+}
+
+/// A recursive reachability query over `edges`.
+pub struct DatalogQuery<'a> {
+    /// Rows to seed `delta` with.
+    pub seeds: &'a [i64],
+    /// `relationship` values in `edges` to join on. Empty means "any".
+    pub relationships: &'a [String],
+    /// Direction to traverse edges in.
+    pub direction: Direction,
+    /// Only follow edges where `source_type`/`target_type` (whichever side
+    /// is the "next node" for `direction`) equals this value, if set.
+    pub next_node_type: Option<&'a str>,
+    /// Fixpoint guard: stop growing the frontier past this depth.
+    pub max_depth: i64,
+    /// Fixpoint guard: stop once the result set reaches this many tuples.
+    pub max_tuples: i64,
+}
+
+impl<'a> DatalogQuery<'a> {
+    /// Evaluate the query to fixpoint (or until a guard trips), returning
+    /// every tuple derived along the way. `filter` is applied to each
+    /// candidate tuple before it is admitted into `delta`/the result set,
+    /// so it can both project away unwanted nodes and prune the branches
+    /// that grow from them.
+    pub fn evaluate(
+        &self,
+        conn: &Connection,
+        mut filter: impl FnMut(&DerivedTuple) -> bool,
+    ) -> BombeResult<Vec<DerivedTuple>> {
+        let sql = match self.direction {
+            Direction::Forward => {
+                "SELECT target_id, relationship, line_number, source_type, target_type \
+                 FROM edges WHERE source_id = ?1;"
+            }
+            Direction::Backward => {
+                "SELECT source_id, relationship, line_number, source_type, target_type \
+                 FROM edges WHERE target_id = ?1;"
+            }
+        };
+        let mut stmt = conn.prepare(sql)?;
+
+        let mut result: Vec<DerivedTuple> = Vec::new();
+        let mut known: HashSet<i64> = self.seeds.iter().copied().collect();
+        let mut delta: Vec<(i64, i64)> = self.seeds.iter().map(|&id| (id, 0)).collect();
+
+        while !delta.is_empty() {
+            if result.len() as i64 >= self.max_tuples || known.len() as i64 >= MAX_GRAPH_VISITED {
+                break;
+            }
+
+            let mut next_delta: Vec<(i64, i64)> = Vec::new();
+
+            for &(from_id, depth) in &delta {
+                if depth >= self.max_depth {
+                    continue;
+                }
+                if result.len() as i64 >= self.max_tuples {
+                    break;
+                }
+
+                let rows: Vec<(i64, String, Option<i64>, String, String)> = stmt
+                    .query_map(rusqlite::params![from_id], |row| {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                        ))
+                    })?
+                    .filter_map(|r| r.ok())
+                    .collect();
+
+                for (node_id, relationship, line_number, source_type, target_type) in rows {
+                    if !self.relationships.is_empty()
+                        && !self.relationships.iter().any(|r| r == &relationship)
+                    {
+                        continue;
+                    }
+                    let next_type = match self.direction {
+                        Direction::Forward => &target_type,
+                        Direction::Backward => &source_type,
+                    };
+                    if let Some(expected) = self.next_node_type {
+                        if next_type != expected {
+                            continue;
+                        }
+                    }
+
+                    let tuple = DerivedTuple {
+                        node_id,
+                        from_id,
+                        relationship,
+                        line_number,
+                        depth: depth + 1,
+                    };
+                    if !filter(&tuple) {
+                        continue;
+                    }
+
+                    if result.len() as i64 >= self.max_tuples
+                        || known.len() as i64 >= MAX_GRAPH_EDGES.min(MAX_GRAPH_VISITED * 2)
+                    {
+                        break;
+                    }
+
+                    // join-only-against-new-facts: re-deriving an already-known
+                    // node still records the tuple (it may arrive via a
+                    // different relationship/path) but doesn't re-expand it.
+                    let is_new = known.insert(node_id);
+                    if is_new {
+                        next_delta.push((node_id, tuple.depth));
+                    }
+                    result.push(tuple);
+                }
+            }
+
+            delta = next_delta;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE edges (
+                source_id INTEGER, target_id INTEGER, relationship TEXT,
+                source_type TEXT, target_type TEXT, line_number INTEGER
+            );
+            INSERT INTO edges VALUES (1, 2, 'CALLS', 'symbol', 'symbol', 10);
+            INSERT INTO edges VALUES (2, 3, 'CALLS', 'symbol', 'symbol', 20);
+            INSERT INTO edges VALUES (3, 1, 'CALLS', 'symbol', 'symbol', 30);
+            INSERT INTO edges VALUES (1, 4, 'IMPORTS', 'symbol', 'module', 0);",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn forward_reachability_stops_at_fixpoint_on_a_cycle() {
+        let conn = setup();
+        let query = DatalogQuery {
+            seeds: &[1],
+            relationships: &["CALLS".to_string()],
+            direction: Direction::Forward,
+            next_node_type: None,
+            max_depth: 10,
+            max_tuples: 100,
+        };
+        let tuples = query.evaluate(&conn, |_| true).unwrap();
+        let ids: HashSet<i64> = tuples.iter().map(|t| t.node_id).collect();
+        assert_eq!(ids, [2, 3].into_iter().collect());
+    }
+
+    #[test]
+    fn relationship_filter_excludes_non_matching_edges() {
+        let conn = setup();
+        let query = DatalogQuery {
+            seeds: &[1],
+            relationships: &["IMPORTS".to_string()],
+            direction: Direction::Forward,
+            next_node_type: Some("module"),
+            max_depth: 5,
+            max_tuples: 100,
+        };
+        let tuples = query.evaluate(&conn, |_| true).unwrap();
+        assert_eq!(tuples.len(), 1);
+        assert_eq!(tuples[0].node_id, 4);
+    }
+
+    #[test]
+    fn max_depth_guard_bounds_traversal() {
+        let conn = setup();
+        let query = DatalogQuery {
+            seeds: &[1],
+            relationships: &["CALLS".to_string()],
+            direction: Direction::Forward,
+            next_node_type: None,
+            max_depth: 1,
+            max_tuples: 100,
+        };
+        let tuples = query.evaluate(&conn, |_| true).unwrap();
+        assert_eq!(tuples.len(), 1);
+        assert_eq!(tuples[0].node_id, 2);
+    }
+}
@@ -0,0 +1,206 @@
+//! Query-workload evaluation harness.
+//!
+//! Replays a JSON workload of `{query, entry_points, token_budget,
+//! expected_symbols}` cases through [`crate::query::context::get_context_impl`]
+//! and scores each result for retrieval quality — precision@k, recall, and a
+//! rank-weighted hit metric — alongside latency and token-budget utilization,
+//! so ranking/scoring changes to `query::context` can be compared across runs
+//! instead of eyeballed.
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+
+use crate::errors::{BombeError, BombeResult};
+use crate::query::context::{get_context_impl, round4};
+
+/// Runs one workload case through `get_context_impl` with the same defaults
+/// `get_context` itself uses, and scores the result against `expected_symbols`.
+fn run_case(conn: &Connection, case: &serde_json::Value) -> BombeResult<serde_json::Value> {
+    let query = case
+        .get("query")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| BombeError::Query("workload case missing \"query\"".to_string()))?;
+    let entry_points: Vec<String> = case
+        .get("entry_points")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let token_budget = case
+        .get("token_budget")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(8000);
+    let expected_symbols: HashSet<String> = case
+        .get("expected_symbols")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let started = Instant::now();
+    let result = get_context_impl(
+        conn,
+        query,
+        &entry_points,
+        token_budget,
+        false,
+        2,
+        false,
+        0.35,
+        None,
+        0.85,
+        None,
+        None,
+        None,
+        0.0,
+        "product",
+        60.0,
+        None,
+        1e-6,
+        "",
+    )?;
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    // Flatten retrieved qualified names in inclusion order (files are
+    // already ordered by path; symbols within a file keep topology order),
+    // so "rank" below means "position in the assembled context bundle".
+    let retrieved: Vec<String> = result["context_bundle"]["files"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|file| file["symbols"].as_array().cloned().unwrap_or_default())
+        .filter_map(|sym| sym["qualified_name"].as_str().map(str::to_string))
+        .collect();
+
+    let retrieved_set: HashSet<&str> = retrieved.iter().map(String::as_str).collect();
+    let true_positives = retrieved_set
+        .iter()
+        .filter(|name| expected_symbols.contains(**name))
+        .count();
+
+    let precision_at_k = if retrieved.is_empty() {
+        0.0
+    } else {
+        true_positives as f64 / retrieved.len() as f64
+    };
+    let recall = if expected_symbols.is_empty() {
+        0.0
+    } else {
+        true_positives as f64 / expected_symbols.len() as f64
+    };
+
+    // Rank-weighted hit: each expected symbol contributes 1/(rank + 1) if
+    // found, so hits that surface near the top of the budget-limited result
+    // set count for more than hits buried near the bottom.
+    let rank_weighted_hit = if expected_symbols.is_empty() {
+        0.0
+    } else {
+        let gain: f64 = expected_symbols
+            .iter()
+            .filter_map(|name| retrieved.iter().position(|r| r == name))
+            .map(|rank| 1.0 / (rank as f64 + 1.0))
+            .sum();
+        gain / expected_symbols.len() as f64
+    };
+
+    let tokens_used = result["context_bundle"]["tokens_used"].as_i64().unwrap_or(0);
+    let token_budget_used = result["context_bundle"]["token_budget"]
+        .as_i64()
+        .unwrap_or(token_budget);
+    let token_utilization = if token_budget_used > 0 {
+        tokens_used as f64 / token_budget_used as f64
+    } else {
+        0.0
+    };
+    let duplicate_skips = result["context_bundle"]["quality_metrics"]["duplicate_skips"]
+        .as_i64()
+        .unwrap_or(0);
+
+    Ok(serde_json::json!({
+        "query": query,
+        "precision_at_k": round4(precision_at_k),
+        "recall": round4(recall),
+        "rank_weighted_hit": round4(rank_weighted_hit),
+        "latency_ms": round4(latency_ms),
+        "token_utilization": round4(token_utilization),
+        "duplicate_skips": duplicate_skips,
+        "quality_metrics": result["context_bundle"]["quality_metrics"].clone(),
+    }))
+}
+
+/// Mean of `field` across `case_reports`, treating missing/non-numeric
+/// values as absent rather than zero (so one malformed case doesn't silently
+/// drag the average toward zero).
+fn mean_field(case_reports: &[serde_json::Value], field: &str) -> f64 {
+    let values: Vec<f64> = case_reports
+        .iter()
+        .filter_map(|report| report[field].as_f64())
+        .collect();
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Replays the workload at `workload_path` against `conn` and returns
+/// `{cases: [...], summary: {...}}`.
+pub fn run_workload_impl(conn: &Connection, workload_path: &str) -> BombeResult<serde_json::Value> {
+    let content = std::fs::read_to_string(workload_path)?;
+    let workload: serde_json::Value = serde_json::from_str(&content)?;
+    let cases = workload.as_array().ok_or_else(|| {
+        BombeError::Query("workload file must be a JSON array of cases".to_string())
+    })?;
+
+    let mut case_reports = Vec::with_capacity(cases.len());
+    for case in cases {
+        case_reports.push(run_case(conn, case)?);
+    }
+
+    let total_duplicate_skips: i64 = case_reports
+        .iter()
+        .filter_map(|r| r["duplicate_skips"].as_i64())
+        .sum();
+
+    let summary = serde_json::json!({
+        "case_count": case_reports.len(),
+        "mean_precision_at_k": round4(mean_field(&case_reports, "precision_at_k")),
+        "mean_recall": round4(mean_field(&case_reports, "recall")),
+        "mean_rank_weighted_hit": round4(mean_field(&case_reports, "rank_weighted_hit")),
+        "mean_latency_ms": round4(mean_field(&case_reports, "latency_ms")),
+        "mean_token_utilization": round4(mean_field(&case_reports, "token_utilization")),
+        "total_duplicate_skips": total_duplicate_skips,
+    });
+
+    Ok(serde_json::json!({
+        "cases": case_reports,
+        "summary": summary,
+    }))
+}
+
+/// Benchmarking/evaluation entry point: replays a JSON workload file against
+/// `db` and returns a `{cases, summary}` report as a Python dict, so
+/// ranking/scoring changes to `get_context` can be compared across runs.
+#[pyfunction]
+pub fn run_workload(
+    py: Python<'_>,
+    db: &crate::store::database::Database,
+    workload_path: &str,
+) -> PyResult<PyObject> {
+    let conn = db.connect_internal()?;
+    let result = run_workload_impl(&conn, workload_path)?;
+    let json_str = serde_json::to_string(&result)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let json_module = py.import("json")?;
+    json_module
+        .call_method1("loads", (json_str,))
+        .map(|o| o.into())
+}
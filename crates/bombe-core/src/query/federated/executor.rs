@@ -1,30 +1,393 @@
 //! Federated query executor for cross-repo shard groups.
 
-use std::time::Instant;
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
+use pyo3::exceptions::{PyException, PyRuntimeError, PyTimeoutError};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 
+use crate::alloc_stats::{AllocDelta, Region};
+use crate::query::guards::FEDERATED_SHARD_TIMEOUT_MS;
+
+use super::merge;
+use super::planner::ShardQueryPlan;
+
+/// Raised by [`FederatedQueryExecutor`]'s `execute*` methods when fewer than
+/// `min_success_fraction` of the queried shards came back successfully — a
+/// caller that opted into a quorum wants to know their answer is
+/// known-incomplete rather than silently get partial results back.
+pyo3::create_exception!(_bombe_core, FederatedQuorumError, PyException);
+
+/// One shard's outcome from [`fan_out_with_timeout`]: either the operation's
+/// result or the error it failed with (including a timeout, surfaced as a
+/// [`PyTimeoutError`] so callers can tell it apart from a real shard error).
+/// `alloc` is `Some` only when the executor's `profile_allocations` flag is
+/// on — see [`fan_out_with_timeout`].
+struct ShardOutcome {
+    shard_id: String,
+    latency_ms: i64,
+    result: PyResult<PyObject>,
+    alloc: Option<AllocDelta>,
+}
+
+/// Fails with [`FederatedQuorumError`] if fewer than `min_success_fraction`
+/// of `shards_queried` shards succeeded; otherwise returns the coverage
+/// fraction (`1.0` when no shards were queried — there's nothing to be
+/// incomplete about).
+fn enforce_quorum(
+    shards_queried: usize,
+    shards_failed: i64,
+    min_success_fraction: f64,
+) -> PyResult<f64> {
+    if shards_queried == 0 {
+        return Ok(1.0);
+    }
+    let successful = shards_queried as i64 - shards_failed;
+    let coverage = successful as f64 / shards_queried as f64;
+    if coverage < min_success_fraction {
+        return Err(FederatedQuorumError::new_err(format!(
+            "only {successful}/{shards_queried} shards succeeded ({coverage:.2} coverage), below the required {min_success_fraction:.2} quorum"
+        )));
+    }
+    Ok(coverage)
+}
+
+/// Run `operation` against every shard in `shard_ids` in parallel, each on
+/// its own OS thread, so one slow shard doesn't stall the rest the way the
+/// old sequential loop did. Each shard retries up to `max_retries` times
+/// with exponential backoff (`retry_backoff_ms * 2^attempt`) before its
+/// error is reported, since a single transient failure (a lock contention
+/// blip, a dropped connection) shouldn't sink the whole shard.
+/// [`FEDERATED_SHARD_TIMEOUT_MS`] bounds the whole fan-out, not any single
+/// thread or retry: a shard that hasn't reported back by the deadline is
+/// recorded as a [`PyTimeoutError`] outcome and its thread is left to
+/// finish (or keep retrying) on its own — Rust has no way to cancel a
+/// running thread, and the response shouldn't wait on it regardless.
+/// Releases the GIL for the duration of the wait (`py.allow_threads`),
+/// since every spawned thread needs to acquire it to run its own share of
+/// `operation`. When `profile` is set, each attempt (including retries) is
+/// bracketed by an [`alloc_stats::Region`] snapshot and the per-attempt
+/// deltas accumulated, so a shard that retries reports the allocations of
+/// every attempt, not just the last.
+fn fan_out_with_timeout<F>(
+    py: Python<'_>,
+    shard_ids: &[String],
+    router: &PyObject,
+    max_retries: u32,
+    retry_backoff_ms: u64,
+    profile: bool,
+    operation: F,
+) -> Vec<ShardOutcome>
+where
+    F: Fn(Python<'_>, &Bound<'_, PyAny>) -> PyResult<PyObject> + Send + Sync + 'static,
+{
+    let operation = Arc::new(operation);
+    let (tx, rx) = mpsc::channel::<ShardOutcome>();
+
+    for shard_id in shard_ids {
+        let tx = tx.clone();
+        let router = router.clone_ref(py);
+        let operation = Arc::clone(&operation);
+        let shard_id = shard_id.clone();
+        std::thread::spawn(move || {
+            let shard_started = Instant::now();
+            let mut attempt = 0u32;
+            let mut alloc = AllocDelta::default();
+            let result = loop {
+                let before = profile.then(Region::snapshot);
+                let outcome = Python::with_gil(|py| {
+                    let db = router.call_method1(py, "get_shard_db", (shard_id.as_str(),))?;
+                    if db.is_none(py) {
+                        return Err(PyRuntimeError::new_err("shard database not accessible"));
+                    }
+                    operation(py, db.bind(py))
+                });
+                if let Some(before) = before {
+                    alloc.accumulate(before.delta(&Region::snapshot()));
+                }
+                match outcome {
+                    Ok(value) => break Ok(value),
+                    Err(_err) if attempt < max_retries => {
+                        attempt += 1;
+                        // `saturating_pow`/`saturating_mul`, not a bare `pow`:
+                        // `max_shard_retries` is a caller-supplied
+                        // constructor argument (`FederatedQueryExecutor::new`),
+                        // and an unbounded `2u64.pow(attempt - 1)` panics
+                        // once `attempt` climbs past 64 — reachable with a
+                        // legal (if unusual) retry count, on a background
+                        // thread the caller has no way to see panic.
+                        let backoff = 2u64
+                            .saturating_pow(attempt - 1)
+                            .saturating_mul(retry_backoff_ms);
+                        std::thread::sleep(Duration::from_millis(backoff));
+                    }
+                    Err(err) => break Err(err),
+                }
+            };
+            let _ = tx.send(ShardOutcome {
+                shard_id,
+                latency_ms: shard_started.elapsed().as_millis() as i64,
+                result,
+                alloc: profile.then_some(alloc),
+            });
+        });
+    }
+    drop(tx);
+
+    let budget = Duration::from_millis(FEDERATED_SHARD_TIMEOUT_MS as u64);
+    let mut outcomes = py.allow_threads(move || {
+        let deadline = Instant::now() + budget;
+        let mut outcomes: HashMap<String, ShardOutcome> = HashMap::new();
+        while outcomes.len() < shard_ids.len() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(remaining) {
+                Ok(outcome) => {
+                    outcomes.insert(outcome.shard_id.clone(), outcome);
+                }
+                Err(_) => break,
+            }
+        }
+        outcomes
+    });
+
+    shard_ids
+        .iter()
+        .map(|shard_id| {
+            outcomes.remove(shard_id).unwrap_or_else(|| ShardOutcome {
+                shard_id: shard_id.clone(),
+                latency_ms: FEDERATED_SHARD_TIMEOUT_MS,
+                result: Err(PyTimeoutError::new_err(
+                    "shard query exceeded the fan-out timeout budget",
+                )),
+                alloc: None,
+            })
+        })
+        .collect()
+}
+
+/// `true` if `err` is the [`PyTimeoutError`] [`fan_out_with_timeout`]
+/// synthesizes for a shard that missed the budget, so callers can report
+/// `status: "timeout"` instead of lumping it in with `"error"`.
+fn is_fan_out_timeout(py: Python<'_>, err: &PyErr) -> bool {
+    err.is_instance_of::<PyTimeoutError>(py)
+}
+
+/// Pulls the `symbols` list out of a shard's search response, which (for
+/// the `kind="hybrid"` branch of `execute_search`) is a plain dict rather
+/// than the `bombe.query.search`-returned object the lexical/semantic
+/// branches produce — checked dict-first the same way `merge::extract_f64`
+/// reads a shard item's `score`, since both shapes can appear depending on
+/// which branch a shard result came from.
+fn extract_symbol_list(py: Python<'_>, result: &PyObject) -> Vec<PyObject> {
+    let symbols = {
+        let obj = result.bind(py);
+        if let Ok(dict) = obj.downcast::<PyDict>() {
+            dict.get_item("symbols").ok().flatten().map(|v| v.unbind())
+        } else {
+            result.getattr(py, "symbols").ok()
+        }
+    };
+    let mut items = Vec::new();
+    if let Some(symbols) = symbols {
+        if let Ok(list) = symbols.downcast_bound::<PyList>(py) {
+            for item in list.iter() {
+                items.push(item.into());
+            }
+        }
+    }
+    items
+}
+
+/// Adds `bytes_allocated`/`allocations`/`reallocations` to `report` and
+/// folds them into `totals` when `alloc` is `Some` — i.e. whenever the
+/// executor's `profile_allocations` flag is on. A no-op otherwise, so
+/// `shard_reports` gain no extra keys when profiling is off.
+fn record_alloc(
+    report: &Bound<'_, PyDict>,
+    alloc: Option<AllocDelta>,
+    totals: &mut AllocDelta,
+) -> PyResult<()> {
+    if let Some(alloc) = alloc {
+        report.set_item("bytes_allocated", alloc.bytes_allocated)?;
+        report.set_item("allocations", alloc.allocations)?;
+        report.set_item("reallocations", alloc.reallocations)?;
+        totals.accumulate(alloc);
+    }
+    Ok(())
+}
+
 #[pyclass]
 pub struct FederatedQueryExecutor {
     #[allow(dead_code)]
     catalog: PyObject,
     router: PyObject,
     planner: PyObject,
+    min_success_fraction: f64,
+    max_shard_retries: u32,
+    retry_backoff_ms: u64,
+    profile_allocations: bool,
+    embedder: Option<PyObject>,
 }
 
 #[pymethods]
 impl FederatedQueryExecutor {
+    /// `min_success_fraction` is the consistency policy: if fewer than that
+    /// fraction of queried shards succeed (after retries), `execute*` raises
+    /// [`FederatedQuorumError`] instead of returning a partial answer.
+    /// Defaults to `0.0` (no quorum requirement — today's behavior).
+    /// `max_shard_retries`/`retry_backoff_ms` configure the bounded,
+    /// exponential-backoff retry [`fan_out_with_timeout`] gives each shard
+    /// before counting it as failed. `profile_allocations`, off by default,
+    /// additionally snapshots `alloc_stats::Region` around each shard
+    /// attempt and reports `bytes_allocated`/`allocations`/`reallocations`
+    /// per shard plus `total_bytes_allocated`/`total_allocations` overall —
+    /// left off, `execute*` never touches the allocator counters.
+    /// `embedder`, `None` by default, is the object `execute_search` calls
+    /// `embed(query)` on for its `kind="semantic"`/`"hybrid"` branches —
+    /// required only if a caller actually uses one of those kinds.
     #[new]
-    fn new(catalog: PyObject, router: PyObject, planner: PyObject) -> Self {
+    #[pyo3(signature = (catalog, router, planner, min_success_fraction=0.0, max_shard_retries=0, retry_backoff_ms=50, profile_allocations=false, embedder=None))]
+    fn new(
+        catalog: PyObject,
+        router: PyObject,
+        planner: PyObject,
+        min_success_fraction: f64,
+        max_shard_retries: u32,
+        retry_backoff_ms: u64,
+        profile_allocations: bool,
+        embedder: Option<PyObject>,
+    ) -> Self {
         Self {
             catalog,
             router,
             planner,
+            min_success_fraction,
+            max_shard_retries,
+            retry_backoff_ms,
+            profile_allocations,
+            embedder,
         }
     }
 
-    #[pyo3(signature = (query, kind, file_pattern=None, limit=20))]
+    /// Generic fan-out/merge: run `callback(db) -> list[result]` against
+    /// every shard in `plan.shard_ids` (re-fetching the live shard list from
+    /// the router instead when `plan.fan_out_strategy == "all"`, since an
+    /// `"all"` plan is meant to mean "every shard there is right now", not
+    /// whatever snapshot the planner saw), flatten the per-shard result
+    /// lists, merge them with [`merge::merge`] per `plan.merge_strategy`,
+    /// and stitch in `plan.cross_repo_edges`. Returns a single document
+    /// shaped like the single-repo backends' (`summary` plus `results` and
+    /// shard/timing bookkeeping), so callers with a plan + a query callable
+    /// don't need to hand-roll fan-out like `execute_search`/
+    /// `execute_references`/`execute_blast_radius` below do.
+    #[pyo3(signature = (plan, callback, limit=20))]
+    fn execute(
+        &self,
+        py: Python<'_>,
+        plan: &ShardQueryPlan,
+        callback: PyObject,
+        limit: i64,
+    ) -> PyResult<PyObject> {
+        let started = Instant::now();
+
+        let shard_ids: Vec<String> = if plan.fan_out_strategy == "all" {
+            self.router.call_method0(py, "all_shard_ids")?.extract(py)?
+        } else {
+            plan.shard_ids.clone()
+        };
+
+        let outcomes = fan_out_with_timeout(
+            py,
+            &shard_ids,
+            &self.router,
+            self.max_shard_retries,
+            self.retry_backoff_ms,
+            self.profile_allocations,
+            move |py, db| callback.call1(py, (db,)),
+        );
+
+        let mut all_results: Vec<PyObject> = Vec::new();
+        let mut shard_reports: Vec<PyObject> = Vec::new();
+        let mut shards_failed = 0i64;
+        let mut alloc_totals = AllocDelta::default();
+
+        for outcome in outcomes {
+            let report = PyDict::new(py);
+            report.set_item("shard_id", &outcome.shard_id)?;
+            report.set_item("latency_ms", outcome.latency_ms)?;
+            record_alloc(&report, outcome.alloc, &mut alloc_totals)?;
+            match outcome.result {
+                Ok(result) => {
+                    report.set_item("status", "ok")?;
+                    if let Ok(list) = result.downcast_bound::<PyList>(py) {
+                        for item in list.iter() {
+                            all_results.push(item.into());
+                        }
+                    }
+                }
+                Err(e) => {
+                    report.set_item(
+                        "status",
+                        if is_fan_out_timeout(py, &e) {
+                            "timeout"
+                        } else {
+                            "error"
+                        },
+                    )?;
+                    report.set_item("error", e.to_string())?;
+                    shards_failed += 1;
+                }
+            }
+            shard_reports.push(report.into());
+        }
+
+        let coverage = enforce_quorum(shard_ids.len(), shards_failed, self.min_success_fraction)?;
+
+        let total_matches = all_results.len() as i64;
+        let merged = merge::merge(py, &plan.merge_strategy, all_results, limit);
+
+        let elapsed_ms = started.elapsed().as_millis() as i64;
+        let summary = format!(
+            "Merged {} results from {} shard(s) ({} failed) via {}.",
+            merged.len(),
+            shard_ids.len(),
+            shards_failed,
+            plan.merge_strategy
+        );
+
+        let result = PyDict::new(py);
+        result.set_item("results", PyList::new(py, &merged)?)?;
+        result.set_item("cross_repo_edges", PyList::new(py, &plan.cross_repo_edges)?)?;
+        result.set_item("shard_reports", PyList::new(py, &shard_reports)?)?;
+        result.set_item("total_matches", total_matches)?;
+        result.set_item("shards_queried", shard_ids.len() as i64)?;
+        result.set_item("shards_failed", shards_failed)?;
+        result.set_item("degraded", shards_failed > 0)?;
+        result.set_item("coverage", coverage)?;
+        result.set_item("elapsed_ms", elapsed_ms)?;
+        result.set_item("summary", summary)?;
+        if self.profile_allocations {
+            result.set_item("total_bytes_allocated", alloc_totals.bytes_allocated)?;
+            result.set_item("total_allocations", alloc_totals.allocations)?;
+        }
+
+        Ok(result.into())
+    }
+
+    /// `kind = "semantic"` embeds `query` once via the constructor's
+    /// `embedder` (instead of per shard, which would call the embedding
+    /// model once per shard for the same query) and has each shard run
+    /// `search_symbols_vector` against the broadcast vector instead of
+    /// `search_symbols`. `kind = "hybrid"` runs both per shard and fuses
+    /// them with [`merge::hybrid_fuse`], weighted by `semantic_ratio`,
+    /// before this method's usual cross-shard `merge_strategy` runs on top.
+    /// Any other `kind` is unchanged: a lexical `search_symbols` filter.
+    #[pyo3(signature = (query, kind, file_pattern=None, limit=20, merge_strategy="concat", rrf_k=60.0, semantic_ratio=0.5))]
     fn execute_search(
         &self,
         py: Python<'_>,
@@ -32,6 +395,9 @@ impl FederatedQueryExecutor {
         kind: &str,
         file_pattern: Option<&str>,
         limit: i64,
+        merge_strategy: &str,
+        rrf_k: f64,
+        semantic_ratio: f64,
     ) -> PyResult<PyObject> {
         let started = Instant::now();
         let plan = self
@@ -39,59 +405,142 @@ impl FederatedQueryExecutor {
             .call_method1(py, "plan_search", (query, kind, limit))?;
         let shard_ids: Vec<String> = plan.getattr(py, "shard_ids")?.extract(py)?;
 
-        let mut all_results: Vec<PyObject> = Vec::new();
-        let mut shard_reports: Vec<PyObject> = Vec::new();
-        let mut shards_failed = 0i64;
+        let vector: Option<Vec<f64>> = if kind == "semantic" || kind == "hybrid" {
+            let embedder = self.embedder.as_ref().ok_or_else(|| {
+                PyRuntimeError::new_err(format!(
+                    "execute_search kind={kind:?} requires an embedder to be configured"
+                ))
+            })?;
+            Some(embedder.call_method1(py, "embed", (query,))?.extract(py)?)
+        } else {
+            None
+        };
 
-        for shard_id in &shard_ids {
-            let shard_started = Instant::now();
-            let report = PyDict::new(py);
-            report.set_item("shard_id", shard_id)?;
+        let query = query.to_string();
+        let kind = kind.to_string();
+        let file_pattern = file_pattern.map(str::to_string);
 
-            match self.execute_on_shard(py, shard_id, |py, db| {
+        let outcomes = fan_out_with_timeout(
+            py,
+            &shard_ids,
+            &self.router,
+            self.max_shard_retries,
+            self.retry_backoff_ms,
+            self.profile_allocations,
+            move |py, db| {
                 let search_mod = py.import("bombe.query.search")?;
                 let models_mod = py.import("bombe.models")?;
-                let req = models_mod.getattr("SymbolSearchRequest")?.call1((
-                    query,
-                    kind,
-                    file_pattern,
-                    limit,
-                ))?;
-                let response = search_mod.call_method1("search_symbols", (db, req))?;
-                Ok(response.into())
-            }) {
+                match kind.as_str() {
+                    "semantic" => {
+                        let vector = vector.clone().expect("embedded once above for this kind");
+                        let response =
+                            search_mod.call_method1("search_symbols_vector", (db, vector, limit))?;
+                        Ok(response.into())
+                    }
+                    "hybrid" => {
+                        let req = models_mod.getattr("SymbolSearchRequest")?.call1((
+                            query.as_str(),
+                            "any",
+                            file_pattern.as_deref(),
+                            limit,
+                        ))?;
+                        let lexical = search_mod.call_method1("search_symbols", (db, req))?;
+                        let vector = vector.clone().expect("embedded once above for this kind");
+                        let semantic =
+                            search_mod.call_method1("search_symbols_vector", (db, vector, limit))?;
+                        let lexical_items = extract_symbol_list(py, &lexical.into());
+                        let semantic_items = extract_symbol_list(py, &semantic.into());
+                        let fused = merge::hybrid_fuse(
+                            py,
+                            lexical_items,
+                            semantic_items,
+                            semantic_ratio,
+                            limit,
+                        );
+                        let result = PyDict::new(py);
+                        result.set_item("symbols", PyList::new(py, &fused)?)?;
+                        Ok(result.into())
+                    }
+                    _ => {
+                        let req = models_mod.getattr("SymbolSearchRequest")?.call1((
+                            query.as_str(),
+                            kind.as_str(),
+                            file_pattern.as_deref(),
+                            limit,
+                        ))?;
+                        let response = search_mod.call_method1("search_symbols", (db, req))?;
+                        Ok(response.into())
+                    }
+                }
+            },
+        );
+
+        let mut shard_results: Vec<(String, Vec<PyObject>)> = Vec::new();
+        let mut shard_reports: Vec<PyObject> = Vec::new();
+        let mut shards_failed = 0i64;
+        let mut alloc_totals = AllocDelta::default();
+
+        for outcome in outcomes {
+            let report = PyDict::new(py);
+            report.set_item("shard_id", &outcome.shard_id)?;
+            report.set_item("latency_ms", outcome.latency_ms)?;
+            record_alloc(&report, outcome.alloc, &mut alloc_totals)?;
+            match outcome.result {
                 Ok(result) => {
                     report.set_item("status", "ok")?;
-                    report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
-                    if let Ok(symbols) = result.getattr(py, "symbols") {
-                        if let Ok(list) = symbols.downcast_bound::<PyList>(py) {
-                            for item in list.iter() {
-                                all_results.push(item.into());
-                            }
-                        }
-                    }
+                    let items = extract_symbol_list(py, &result);
+                    shard_results.push((outcome.shard_id.clone(), items));
                 }
                 Err(e) => {
-                    report.set_item("status", "error")?;
+                    report.set_item(
+                        "status",
+                        if is_fan_out_timeout(py, &e) {
+                            "timeout"
+                        } else {
+                            "error"
+                        },
+                    )?;
                     report.set_item("error", e.to_string())?;
-                    report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
                     shards_failed += 1;
                 }
             }
             shard_reports.push(report.into());
         }
 
+        // "global_topk" keeps only the best `limit` matches across all shards
+        // (score-normalized per shard first); "rrf" fuses by per-shard rank
+        // instead of trusting raw scores at all; "concat" is today's
+        // behavior — every shard's matches, unbounded, in arrival order.
+        let results = match merge_strategy {
+            "global_topk" => merge::global_topk(
+                py,
+                shard_results.into_iter().map(|(_, items)| items).collect(),
+                limit,
+            ),
+            "rrf" => merge::rrf_merge(py, shard_results, rrf_k, limit)?,
+            _ => shard_results
+                .into_iter()
+                .flat_map(|(_, items)| items)
+                .collect(),
+        };
+        let total_matches = results.len() as i64;
+        let coverage = enforce_quorum(shard_ids.len(), shards_failed, self.min_success_fraction)?;
+
         let elapsed_ms = started.elapsed().as_millis() as i64;
-        let total_matches = all_results.len() as i64;
 
         let result = PyDict::new(py);
-        let results_list = PyList::new(py, &all_results)?;
-        result.set_item("results", results_list)?;
+        result.set_item("results", PyList::new(py, &results)?)?;
         result.set_item("shard_reports", PyList::new(py, &shard_reports)?)?;
         result.set_item("total_matches", total_matches)?;
         result.set_item("shards_queried", shard_ids.len() as i64)?;
         result.set_item("shards_failed", shards_failed)?;
+        result.set_item("degraded", shards_failed > 0)?;
+        result.set_item("coverage", coverage)?;
         result.set_item("elapsed_ms", elapsed_ms)?;
+        if self.profile_allocations {
+            result.set_item("total_bytes_allocated", alloc_totals.bytes_allocated)?;
+            result.set_item("total_allocations", alloc_totals.allocations)?;
+        }
 
         Ok(result.into())
     }
@@ -110,41 +559,61 @@ impl FederatedQueryExecutor {
                 .call_method1(py, "plan_references", (symbol_name, direction, depth))?;
         let shard_ids: Vec<String> = plan.getattr(py, "shard_ids")?.extract(py)?;
 
-        let mut all_results: Vec<PyObject> = Vec::new();
-        let mut shard_reports: Vec<PyObject> = Vec::new();
-        let mut shards_failed = 0i64;
-
-        for shard_id in &shard_ids {
-            let shard_started = Instant::now();
-            let report = PyDict::new(py);
-            report.set_item("shard_id", shard_id)?;
+        let symbol_name = symbol_name.to_string();
+        let direction = direction.to_string();
 
-            match self.execute_on_shard(py, shard_id, |py, db| {
+        let outcomes = fan_out_with_timeout(
+            py,
+            &shard_ids,
+            &self.router,
+            self.max_shard_retries,
+            self.retry_backoff_ms,
+            self.profile_allocations,
+            move |py, db| {
                 let refs_mod = py.import("bombe.query.references")?;
                 let models_mod = py.import("bombe.models")?;
                 let req = models_mod.getattr("ReferenceRequest")?.call1((
-                    symbol_name,
-                    direction,
+                    symbol_name.as_str(),
+                    direction.as_str(),
                     depth,
                     include_source,
                 ))?;
                 let response = refs_mod.call_method1("get_references", (db, req))?;
                 Ok(response.into())
-            }) {
+            },
+        );
+
+        let mut all_results: Vec<PyObject> = Vec::new();
+        let mut shard_reports: Vec<PyObject> = Vec::new();
+        let mut shards_failed = 0i64;
+        let mut alloc_totals = AllocDelta::default();
+
+        for outcome in outcomes {
+            let report = PyDict::new(py);
+            report.set_item("shard_id", &outcome.shard_id)?;
+            report.set_item("latency_ms", outcome.latency_ms)?;
+            record_alloc(&report, outcome.alloc, &mut alloc_totals)?;
+            match outcome.result {
                 Ok(result) => {
                     report.set_item("status", "ok")?;
-                    report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
                     all_results.push(result);
                 }
-                Err(_) => {
-                    report.set_item("status", "error")?;
-                    report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
+                Err(e) => {
+                    report.set_item(
+                        "status",
+                        if is_fan_out_timeout(py, &e) {
+                            "timeout"
+                        } else {
+                            "error"
+                        },
+                    )?;
                     shards_failed += 1;
                 }
             }
             shard_reports.push(report.into());
         }
 
+        let coverage = enforce_quorum(shard_ids.len(), shards_failed, self.min_success_fraction)?;
         let elapsed_ms = started.elapsed().as_millis() as i64;
 
         let result = PyDict::new(py);
@@ -153,7 +622,13 @@ impl FederatedQueryExecutor {
         result.set_item("total_matches", all_results.len() as i64)?;
         result.set_item("shards_queried", shard_ids.len() as i64)?;
         result.set_item("shards_failed", shards_failed)?;
+        result.set_item("degraded", shards_failed > 0)?;
+        result.set_item("coverage", coverage)?;
         result.set_item("elapsed_ms", elapsed_ms)?;
+        if self.profile_allocations {
+            result.set_item("total_bytes_allocated", alloc_totals.bytes_allocated)?;
+            result.set_item("total_allocations", alloc_totals.allocations)?;
+        }
 
         Ok(result.into())
     }
@@ -171,40 +646,60 @@ impl FederatedQueryExecutor {
             .call_method1(py, "plan_blast_radius", (symbol_name, max_depth))?;
         let shard_ids: Vec<String> = plan.getattr(py, "shard_ids")?.extract(py)?;
 
-        let mut all_results: Vec<PyObject> = Vec::new();
-        let mut shard_reports: Vec<PyObject> = Vec::new();
-        let mut shards_failed = 0i64;
+        let symbol_name = symbol_name.to_string();
+        let change_type = change_type.to_string();
 
-        for shard_id in &shard_ids {
-            let shard_started = Instant::now();
-            let report = PyDict::new(py);
-            report.set_item("shard_id", shard_id)?;
-
-            match self.execute_on_shard(py, shard_id, |py, db| {
+        let outcomes = fan_out_with_timeout(
+            py,
+            &shard_ids,
+            &self.router,
+            self.max_shard_retries,
+            self.retry_backoff_ms,
+            self.profile_allocations,
+            move |py, db| {
                 let blast_mod = py.import("bombe.query.blast")?;
                 let models_mod = py.import("bombe.models")?;
                 let req = models_mod.getattr("BlastRadiusRequest")?.call1((
-                    symbol_name,
-                    change_type,
+                    symbol_name.as_str(),
+                    change_type.as_str(),
                     max_depth,
                 ))?;
                 let response = blast_mod.call_method1("get_blast_radius", (db, req))?;
                 Ok(response.into())
-            }) {
+            },
+        );
+
+        let mut all_results: Vec<PyObject> = Vec::new();
+        let mut shard_reports: Vec<PyObject> = Vec::new();
+        let mut shards_failed = 0i64;
+        let mut alloc_totals = AllocDelta::default();
+
+        for outcome in outcomes {
+            let report = PyDict::new(py);
+            report.set_item("shard_id", &outcome.shard_id)?;
+            report.set_item("latency_ms", outcome.latency_ms)?;
+            record_alloc(&report, outcome.alloc, &mut alloc_totals)?;
+            match outcome.result {
                 Ok(result) => {
                     report.set_item("status", "ok")?;
-                    report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
                     all_results.push(result);
                 }
-                Err(_) => {
-                    report.set_item("status", "error")?;
-                    report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
+                Err(e) => {
+                    report.set_item(
+                        "status",
+                        if is_fan_out_timeout(py, &e) {
+                            "timeout"
+                        } else {
+                            "error"
+                        },
+                    )?;
                     shards_failed += 1;
                 }
             }
             shard_reports.push(report.into());
         }
 
+        let coverage = enforce_quorum(shard_ids.len(), shards_failed, self.min_success_fraction)?;
         let elapsed_ms = started.elapsed().as_millis() as i64;
 
         let result = PyDict::new(py);
@@ -213,28 +708,14 @@ impl FederatedQueryExecutor {
         result.set_item("total_matches", all_results.len() as i64)?;
         result.set_item("shards_queried", shard_ids.len() as i64)?;
         result.set_item("shards_failed", shards_failed)?;
+        result.set_item("degraded", shards_failed > 0)?;
+        result.set_item("coverage", coverage)?;
         result.set_item("elapsed_ms", elapsed_ms)?;
+        if self.profile_allocations {
+            result.set_item("total_bytes_allocated", alloc_totals.bytes_allocated)?;
+            result.set_item("total_allocations", alloc_totals.allocations)?;
+        }
 
         Ok(result.into())
     }
 }
-
-impl FederatedQueryExecutor {
-    fn execute_on_shard<F>(
-        &self,
-        py: Python<'_>,
-        shard_id: &str,
-        operation: F,
-    ) -> PyResult<PyObject>
-    where
-        F: FnOnce(Python<'_>, &Bound<'_, PyAny>) -> PyResult<PyObject>,
-    {
-        let db = self.router.call_method1(py, "get_shard_db", (shard_id,))?;
-        if db.is_none(py) {
-            return Err(pyo3::exceptions::PyRuntimeError::new_err(
-                "shard database not accessible",
-            ));
-        }
-        operation(py, db.bind(py))
-    }
-}
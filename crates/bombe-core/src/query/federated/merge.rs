@@ -0,0 +1,302 @@
+//! Mergers for [`super::executor::FederatedQueryExecutor::execute`]'s
+//! generic fan-out: turn one result list per shard into the single ordered
+//! list a `ShardQueryPlan`'s `merge_strategy` calls for.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+fn extract_f64(py: Python<'_>, item: &PyObject, key: &str) -> f64 {
+    let obj = item.bind(py);
+    if let Ok(dict) = obj.downcast::<PyDict>() {
+        if let Ok(Some(value)) = dict.get_item(key) {
+            if let Ok(n) = value.extract::<f64>() {
+                return n;
+            }
+        }
+    }
+    obj.getattr(key)
+        .ok()
+        .and_then(|v| v.extract::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+fn extract_string(py: Python<'_>, item: &PyObject, key: &str) -> String {
+    let obj = item.bind(py);
+    if let Ok(dict) = obj.downcast::<PyDict>() {
+        if let Ok(Some(value)) = dict.get_item(key) {
+            if let Ok(s) = value.extract::<String>() {
+                return s;
+            }
+        }
+    }
+    obj.getattr(key)
+        .ok()
+        .and_then(|v| v.extract::<String>().ok())
+        .unwrap_or_default()
+}
+
+/// k-way merge of every shard's results ordered by descending `score` (a
+/// dict key or attribute on each item, whichever it exposes), truncated to
+/// `limit`. Ties keep shard-arrival order since `sort_by` is stable.
+pub fn score_sort(py: Python<'_>, mut items: Vec<PyObject>, limit: i64) -> Vec<PyObject> {
+    items.sort_by(|a, b| {
+        let sa = extract_f64(py, a, "score");
+        let sb = extract_f64(py, b, "score");
+        sb.partial_cmp(&sa).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    items.truncate(limit.max(0) as usize);
+    items
+}
+
+/// Merge reference/blast-radius results by increasing `depth` then
+/// `line_number` — the same ordering `query::data_flow::trace_data_flow_impl`
+/// already sorts its own single-repo paths by — deduplicating by
+/// `qualified_name` so a symbol two shards both surface (e.g. because it's
+/// reachable via a cross-repo edge from each side) keeps only its shallowest
+/// occurrence.
+pub fn depth_merge(py: Python<'_>, mut items: Vec<PyObject>, limit: i64) -> Vec<PyObject> {
+    items.sort_by(|a, b| {
+        let da = extract_f64(py, a, "depth") as i64;
+        let db = extract_f64(py, b, "depth") as i64;
+        da.cmp(&db).then_with(|| {
+            let la = extract_f64(py, a, "line_number") as i64;
+            let lb = extract_f64(py, b, "line_number") as i64;
+            la.cmp(&lb)
+        })
+    });
+
+    let mut seen = std::collections::HashSet::new();
+    let mut merged = Vec::new();
+    for item in items {
+        let qname = extract_string(py, &item, "qualified_name");
+        if !qname.is_empty() && !seen.insert(qname) {
+            continue;
+        }
+        merged.push(item);
+        if limit > 0 && merged.len() as i64 >= limit {
+            break;
+        }
+    }
+    merged
+}
+
+/// One entry in [`global_topk`]'s bounded heap: ordered by ascending
+/// normalized score so the heap's max (the usual `BinaryHeap` direction)
+/// surfaces the *weakest* surviving match, i.e. the one to evict first.
+struct ScoredItem {
+    score: f64,
+    item: PyObject,
+}
+
+impl PartialEq for ScoredItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+
+impl Eq for ScoredItem {}
+
+impl PartialOrd for ScoredItem {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredItem {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .score
+            .partial_cmp(&self.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+/// Global top-`limit` merge across shards via a bounded min-heap, for
+/// `execute_search`'s `merge_strategy = "global_topk"`: unlike
+/// [`score_sort`], which just concatenates every shard's results and trusts
+/// their `score`s are comparable, this min-max scales each shard's score
+/// vector into `[0, 1]` first, since independent shards have no reason to
+/// agree on a scale. A shard whose scores are all equal (including a
+/// single-result shard) normalizes every item to `1.0` rather than
+/// dividing by zero. Returns the merged, score-descending items; the true
+/// post-merge count is simply their length.
+pub fn global_topk(py: Python<'_>, per_shard: Vec<Vec<PyObject>>, limit: i64) -> Vec<PyObject> {
+    use std::collections::BinaryHeap;
+
+    let limit = limit.max(0) as usize;
+    let mut heap: BinaryHeap<ScoredItem> = BinaryHeap::with_capacity(limit + 1);
+
+    for shard_items in per_shard {
+        let scores: Vec<f64> = shard_items
+            .iter()
+            .map(|item| extract_f64(py, item, "score"))
+            .collect();
+        let min = scores.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let spread = max - min;
+
+        for (item, raw) in shard_items.into_iter().zip(scores) {
+            if limit == 0 {
+                continue;
+            }
+            let normalized = if spread > 0.0 {
+                (raw - min) / spread
+            } else {
+                1.0
+            };
+            if heap.len() < limit {
+                heap.push(ScoredItem {
+                    score: normalized,
+                    item,
+                });
+            } else if heap
+                .peek()
+                .is_some_and(|weakest| normalized > weakest.score)
+            {
+                heap.pop();
+                heap.push(ScoredItem {
+                    score: normalized,
+                    item,
+                });
+            }
+        }
+    }
+
+    let mut merged: Vec<ScoredItem> = heap.into_vec();
+    merged.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    merged.into_iter().map(|scored| scored.item).collect()
+}
+
+/// Reciprocal Rank Fusion merge across shards, for `execute_search`'s
+/// `merge_strategy = "rrf"`: unlike [`global_topk`], which still needs
+/// per-shard scores to be min-max normalized before they're comparable,
+/// RRF only trusts each shard's own ranking. A symbol's fused score sums
+/// `1 / (k + r)` over every shard it appears in, where `r` is its 1-based
+/// rank within that shard's list; symbols are deduplicated by
+/// `qualified_name` + `kind` (summing their per-shard contributions) so a
+/// symbol several shards surface ranks higher than one only a single shard
+/// found. Returns dicts of `{"symbol": <original item>, "fused_score":
+/// ..., "contributing_shards": [...]}`, sorted by descending fused score
+/// and truncated to `limit`, so callers can see provenance alongside the
+/// ranking.
+pub fn rrf_merge(
+    py: Python<'_>,
+    per_shard: Vec<(String, Vec<PyObject>)>,
+    k: f64,
+    limit: i64,
+) -> PyResult<Vec<PyObject>> {
+    use std::collections::HashMap;
+
+    struct Fused {
+        item: PyObject,
+        score: f64,
+        shard_ids: Vec<String>,
+    }
+
+    let mut fused: HashMap<String, Fused> = HashMap::new();
+    for (shard_id, items) in per_shard {
+        for (idx, item) in items.into_iter().enumerate() {
+            let rank = (idx + 1) as f64;
+            let contribution = 1.0 / (k + rank);
+            let key = format!(
+                "{}::{}",
+                extract_string(py, &item, "qualified_name"),
+                extract_string(py, &item, "kind")
+            );
+            fused
+                .entry(key)
+                .and_modify(|f| {
+                    f.score += contribution;
+                    f.shard_ids.push(shard_id.clone());
+                })
+                .or_insert_with(|| Fused {
+                    item,
+                    score: contribution,
+                    shard_ids: vec![shard_id.clone()],
+                });
+        }
+    }
+
+    let mut ranked: Vec<Fused> = fused.into_values().collect();
+    ranked.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    ranked.truncate(limit.max(0) as usize);
+
+    ranked
+        .into_iter()
+        .map(|f| {
+            let dict = PyDict::new(py);
+            dict.set_item("symbol", f.item)?;
+            dict.set_item("fused_score", f.score)?;
+            dict.set_item("contributing_shards", f.shard_ids)?;
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// Weighted rank fusion of one shard's lexical and vector search lists, for
+/// `execute_search`'s `kind = "hybrid"`: each item's fused score is
+/// `(1 - semantic_ratio) / (k + lexical_rank) + semantic_ratio / (k +
+/// vector_rank)`, the same reciprocal-rank shape as [`rrf_merge`] (`k`
+/// fixed at its default, `60.0`) but weighting the two sides instead of
+/// trusting them equally. An item present in only one list contributes
+/// nothing from the other side rather than counting as an infinitely bad
+/// rank. Symbols are deduplicated by `qualified_name` + `kind`, as in
+/// `rrf_merge`. Unlike `rrf_merge`, returns the original items — not
+/// fused-score wrapper dicts — sorted by descending fused score and
+/// truncated to `limit`, since this is run once per shard and the result
+/// needs to look like a normal `symbols` list to the global merge stage
+/// that runs on top of every shard's fused list.
+pub fn hybrid_fuse(
+    py: Python<'_>,
+    lexical: Vec<PyObject>,
+    vector: Vec<PyObject>,
+    semantic_ratio: f64,
+    limit: i64,
+) -> Vec<PyObject> {
+    use std::collections::HashMap;
+
+    const K: f64 = 60.0;
+
+    let mut fused: HashMap<String, (PyObject, f64)> = HashMap::new();
+    let mut accumulate = |items: Vec<PyObject>, weight: f64, fused: &mut HashMap<String, (PyObject, f64)>| {
+        for (idx, item) in items.into_iter().enumerate() {
+            let contribution = weight / (K + (idx + 1) as f64);
+            let key = format!(
+                "{}::{}",
+                extract_string(py, &item, "qualified_name"),
+                extract_string(py, &item, "kind")
+            );
+            fused
+                .entry(key)
+                .and_modify(|(_, score)| *score += contribution)
+                .or_insert((item, contribution));
+        }
+    };
+    accumulate(lexical, 1.0 - semantic_ratio, &mut fused);
+    accumulate(vector, semantic_ratio, &mut fused);
+
+    let mut ranked: Vec<(PyObject, f64)> = fused.into_values().collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(limit.max(0) as usize);
+    ranked.into_iter().map(|(item, _)| item).collect()
+}
+
+/// Dispatch on a `ShardQueryPlan.merge_strategy` string. An unrecognized
+/// strategy falls back to `score_sort` rather than erroring, since a plan
+/// is usually produced by `FederatedQueryPlanner` itself and any new
+/// strategy name it learns should degrade gracefully against an older
+/// executor.
+pub fn merge(py: Python<'_>, strategy: &str, items: Vec<PyObject>, limit: i64) -> Vec<PyObject> {
+    match strategy {
+        "depth_merge" => depth_merge(py, items, limit),
+        _ => score_sort(py, items, limit),
+    }
+}
@@ -0,0 +1,134 @@
+//! Federated semantic (embedding-vector) search across shards.
+//!
+//! Each shard's local ranking reuses
+//! [`crate::query::semantic_index::semantic_search_impl`] unchanged -- this
+//! module only adds the federation layer: open every *enabled* shard in a
+//! `ShardGroupConfig` directly (the same `Connection::open(&shard.db_path)`
+//! pattern [`crate::store::sharding::catalog::ShardCatalog`] uses, since a
+//! shard's HNSW index is local to its own embedding table and there's no
+//! router/catalog handle attached to a plain `ShardGroupConfig`), collect
+//! each shard's local top-k hits, and merge everything by cosine similarity
+//! into one [`SymbolSearchResponse`].
+
+use std::time::Instant;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+use crate::models::{FederatedQueryResult, ShardGroupConfig, SymbolSearchResponse};
+use crate::query::semantic_index::semantic_search_impl;
+
+fn query_shard(
+    db_path: &str,
+    query_vector: &[f32],
+    model: &str,
+    top_k: i64,
+) -> BombeResult<serde_json::Value> {
+    let conn = Connection::open(db_path)?;
+    semantic_search_impl(&conn, query_vector, model, top_k)
+}
+
+/// Run [`semantic_search_impl`] against every enabled shard in `group`,
+/// merging all hits by similarity into one [`SymbolSearchResponse`] capped
+/// at `top_k`, and reporting per-shard status/latency in the returned
+/// [`FederatedQueryResult`].
+#[pyfunction]
+#[pyo3(signature = (group, query_vector, top_k=10, model="default"))]
+pub fn federated_semantic_search(
+    py: Python<'_>,
+    group: &ShardGroupConfig,
+    query_vector: Vec<f32>,
+    top_k: i64,
+    model: &str,
+) -> PyResult<FederatedQueryResult> {
+    let started = Instant::now();
+    let mut all_hits: Vec<(f64, serde_json::Value)> = Vec::new();
+    let shard_reports = PyList::empty(py);
+    let mut shards_queried = 0i64;
+    let mut shards_failed = 0i64;
+
+    for shard in &group.shards {
+        if !shard.enabled {
+            continue;
+        }
+        shards_queried += 1;
+        let shard_started = Instant::now();
+        let report = PyDict::new(py);
+        report.set_item("shard_id", &shard.repo_id)?;
+
+        match query_shard(&shard.db_path, &query_vector, model, top_k) {
+            Ok(result) => {
+                let nodes = result
+                    .get("nodes")
+                    .and_then(|v| v.as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                report.set_item("status", "ok")?;
+                report.set_item("bindings", nodes.len())?;
+                report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
+                for mut node in nodes {
+                    let similarity = node
+                        .get("similarity")
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(0.0);
+                    if let serde_json::Value::Object(ref mut map) = node {
+                        map.insert("shard_id".to_string(), serde_json::json!(shard.repo_id));
+                    }
+                    all_hits.push((similarity, node));
+                }
+            }
+            Err(e) => {
+                report.set_item("status", "error")?;
+                report.set_item("error", e.to_string())?;
+                report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
+                shards_failed += 1;
+            }
+        }
+        shard_reports.append(report)?;
+    }
+
+    all_hits.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    all_hits.truncate(top_k.max(0) as usize);
+
+    let symbols = PyList::empty(py);
+    for (_, node) in &all_hits {
+        let json_str = serde_json::to_string(node)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        let json_module = py.import("json")?;
+        let obj = json_module.call_method1("loads", (json_str,))?;
+        symbols.append(obj)?;
+    }
+
+    let response = SymbolSearchResponse {
+        symbols: symbols.into_any().unbind(),
+        total_matches: all_hits.len() as i64,
+    };
+
+    Ok(FederatedQueryResult {
+        results: Py::new(py, response)?.into_any(),
+        shard_reports: shard_reports.into_any().unbind(),
+        total_matches: all_hits.len() as i64,
+        shards_queried,
+        shards_failed,
+        elapsed_ms: started.elapsed().as_millis() as i64,
+        routes: PyList::empty(py).into_any().unbind(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_shard_returns_error_for_missing_db() {
+        let result = query_shard(
+            "/nonexistent/path/does-not-exist.db",
+            &[1.0, 0.0],
+            "default",
+            5,
+        );
+        assert!(result.is_err());
+    }
+}
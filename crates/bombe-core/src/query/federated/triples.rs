@@ -0,0 +1,308 @@
+//! Turn a repo's symbols and cross-repo edges into RDF-style triples, and
+//! run simple variable-binding pattern queries over them -- fanned out
+//! across every enabled shard in a `ShardGroupConfig` and merged into a
+//! `FederatedQueryResult`.
+//!
+//! This is deliberately lighter than
+//! [`crate::store::sharding::rdf_export`]'s oxigraph/SPARQL path: callers
+//! here hand over fixed triple *patterns* (each term either a literal or a
+//! `?variable`) plus numeric filters on bound variables, not a query
+//! string, so there's no query grammar to parse and no SPARQL engine to
+//! embed just to ask "every symbol in shard A calling into shard B above
+//! some confidence".
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::models::{
+    CrossRepoEdge, FederatedQueryResult, GlobalSymbolURI, ShardGroupConfig, SymbolRecord,
+};
+
+/// One RDF-style triple: `(subject_uri, predicate, object)`, where `object`
+/// is either another symbol's `bombe://` URI (for relationship predicates)
+/// or a plain literal string (for the built-in `kind`/`file`/`confidence`
+/// predicates).
+pub type Triple = (String, String, String);
+
+/// Build the triples for one repo's symbols plus its cross-repo edges:
+/// `kind`/`file` on every symbol, and `relationship` plus `confidence` on
+/// every edge (the latter attached to the edge's source URI, matching
+/// [`crate::store::sharding::rdf_export::edge_to_quads`]'s convention of
+/// not reifying the statement just to hang one extra property off it).
+#[pyfunction]
+pub fn build_graph_triples(
+    repo_id: String,
+    symbols: Vec<SymbolRecord>,
+    edges: Vec<CrossRepoEdge>,
+) -> Vec<Triple> {
+    let mut triples = Vec::new();
+    for symbol in &symbols {
+        let uri = GlobalSymbolURI {
+            repo_id: repo_id.clone(),
+            qualified_name: symbol.qualified_name.clone(),
+            file_path: symbol.file_path.clone(),
+        }
+        .uri();
+        triples.push((uri.clone(), "kind".to_string(), symbol.kind.clone()));
+        triples.push((uri, "file".to_string(), symbol.file_path.clone()));
+    }
+    for edge in &edges {
+        let source = edge.source_uri.uri();
+        triples.push((
+            source.clone(),
+            edge.relationship.clone(),
+            edge.target_uri.uri(),
+        ));
+        triples.push((
+            source,
+            "confidence".to_string(),
+            edge.confidence.to_string(),
+        ));
+    }
+    triples
+}
+
+fn try_match(
+    pattern: &Triple,
+    triple: &Triple,
+    binding: &HashMap<String, String>,
+) -> Option<HashMap<String, String>> {
+    let mut extended = binding.clone();
+    for (pat_term, value) in [
+        (&pattern.0, &triple.0),
+        (&pattern.1, &triple.1),
+        (&pattern.2, &triple.2),
+    ] {
+        if let Some(var) = pat_term.strip_prefix('?') {
+            match extended.get(var) {
+                Some(existing) if existing != value => return None,
+                Some(_) => {}
+                None => {
+                    extended.insert(var.to_string(), value.clone());
+                }
+            }
+        } else if pat_term != value {
+            return None;
+        }
+    }
+    Some(extended)
+}
+
+/// Join `patterns` against `triples` left to right, threading variable
+/// bindings through each join the way a SPARQL basic graph pattern would,
+/// then drop any binding that fails one of `filters` -- `(variable, op,
+/// threshold)` where `op` is one of `> < >= <= == !=` and the bound value
+/// must parse as `f64` (an unbound variable or a non-numeric literal fails
+/// the filter).
+fn run_pattern_query(
+    triples: &[Triple],
+    patterns: &[Triple],
+    filters: &[(String, String, f64)],
+) -> Vec<HashMap<String, String>> {
+    let mut bindings = vec![HashMap::new()];
+    for pattern in patterns {
+        let mut next = Vec::new();
+        for binding in &bindings {
+            for triple in triples {
+                if let Some(extended) = try_match(pattern, triple, binding) {
+                    next.push(extended);
+                }
+            }
+        }
+        bindings = next;
+        if bindings.is_empty() {
+            break;
+        }
+    }
+
+    bindings
+        .into_iter()
+        .filter(|binding| {
+            filters.iter().all(|(var, op, threshold)| {
+                let var = var.strip_prefix('?').unwrap_or(var);
+                binding
+                    .get(var)
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|value| match op.as_str() {
+                        ">" => value > *threshold,
+                        "<" => value < *threshold,
+                        ">=" => value >= *threshold,
+                        "<=" => value <= *threshold,
+                        "==" => value == *threshold,
+                        "!=" => value != *threshold,
+                        _ => false,
+                    })
+                    .unwrap_or(false)
+            })
+        })
+        .collect()
+}
+
+/// Run a pattern query over a fixed triple set (see [`build_graph_triples`]),
+/// returning each matching binding as a `{variable: value}` dict.
+#[pyfunction]
+#[pyo3(signature = (triples, patterns, filters=Vec::new()))]
+pub fn query_graph(
+    py: Python<'_>,
+    triples: Vec<Triple>,
+    patterns: Vec<Triple>,
+    filters: Vec<(String, String, f64)>,
+) -> PyResult<PyObject> {
+    let rows = PyList::empty(py);
+    for binding in run_pattern_query(&triples, &patterns, &filters) {
+        let dict = PyDict::new(py);
+        for (var, value) in &binding {
+            dict.set_item(var, value)?;
+        }
+        rows.append(dict)?;
+    }
+    Ok(rows.into_any().unbind())
+}
+
+/// Run [`query_graph`]'s pattern match against every *enabled* shard in
+/// `group`, merging bindings into one [`FederatedQueryResult`].
+/// `triples_for_shard(shard)` is a Python callable that loads and returns
+/// that shard's triples (e.g. by reading its symbols/cross-repo edges out
+/// of `shard.db_path` and feeding them to [`build_graph_triples`]) --
+/// mirroring
+/// [`crate::query::federated::executor::FederatedQueryExecutor::execute`]'s
+/// callback-per-shard fan-out, since this function has no `Database`/
+/// `ShardRouter` handle of its own to open shards with.
+#[pyfunction]
+#[pyo3(signature = (group, patterns, triples_for_shard, filters=Vec::new()))]
+pub fn federated_graph_query(
+    py: Python<'_>,
+    group: &ShardGroupConfig,
+    patterns: Vec<Triple>,
+    triples_for_shard: PyObject,
+    filters: Vec<(String, String, f64)>,
+) -> PyResult<FederatedQueryResult> {
+    let started = Instant::now();
+    let mut all_bindings: Vec<HashMap<String, String>> = Vec::new();
+    let shard_reports = PyList::empty(py);
+    let mut shards_queried = 0i64;
+    let mut shards_failed = 0i64;
+
+    for shard in &group.shards {
+        if !shard.enabled {
+            continue;
+        }
+        shards_queried += 1;
+        let shard_started = Instant::now();
+        let report = PyDict::new(py);
+        report.set_item("shard_id", &shard.repo_id)?;
+
+        match triples_for_shard
+            .call1(py, (shard.clone(),))
+            .and_then(|obj| obj.extract::<Vec<Triple>>(py))
+        {
+            Ok(triples) => {
+                let bindings = run_pattern_query(&triples, &patterns, &filters);
+                report.set_item("status", "ok")?;
+                report.set_item("bindings", bindings.len())?;
+                report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
+                all_bindings.extend(bindings);
+            }
+            Err(e) => {
+                report.set_item("status", "error")?;
+                report.set_item("error", e.to_string())?;
+                report.set_item("latency_ms", shard_started.elapsed().as_millis() as i64)?;
+                shards_failed += 1;
+            }
+        }
+        shard_reports.append(report)?;
+    }
+
+    let results = PyList::empty(py);
+    for binding in &all_bindings {
+        let dict = PyDict::new(py);
+        for (var, value) in binding {
+            dict.set_item(var, value)?;
+        }
+        results.append(dict)?;
+    }
+
+    Ok(FederatedQueryResult {
+        results: results.into_any().unbind(),
+        shard_reports: shard_reports.into_any().unbind(),
+        total_matches: all_bindings.len() as i64,
+        shards_queried,
+        shards_failed,
+        elapsed_ms: started.elapsed().as_millis() as i64,
+        routes: PyList::empty(py).into_any().unbind(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triples() -> Vec<Triple> {
+        vec![
+            (
+                "bombe://a/foo#f.py".to_string(),
+                "kind".to_string(),
+                "function".to_string(),
+            ),
+            (
+                "bombe://a/foo#f.py".to_string(),
+                "calls".to_string(),
+                "bombe://b/bar#g.py".to_string(),
+            ),
+            (
+                "bombe://a/foo#f.py".to_string(),
+                "confidence".to_string(),
+                "0.9".to_string(),
+            ),
+            (
+                "bombe://a/baz#h.py".to_string(),
+                "calls".to_string(),
+                "bombe://b/bar#g.py".to_string(),
+            ),
+            (
+                "bombe://a/baz#h.py".to_string(),
+                "confidence".to_string(),
+                "0.5".to_string(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_pattern_join_binds_shared_variable() {
+        let patterns = vec![(
+            "?caller".to_string(),
+            "calls".to_string(),
+            "bombe://b/bar#g.py".to_string(),
+        )];
+        let bindings = run_pattern_query(&triples(), &patterns, &[]);
+        let mut callers: Vec<&str> = bindings
+            .iter()
+            .map(|b| b.get("caller").unwrap().as_str())
+            .collect();
+        callers.sort();
+        assert_eq!(callers, vec!["bombe://a/baz#h.py", "bombe://a/foo#f.py"]);
+    }
+
+    #[test]
+    fn test_filter_drops_bindings_below_confidence_threshold() {
+        let patterns = vec![
+            (
+                "?caller".to_string(),
+                "calls".to_string(),
+                "?callee".to_string(),
+            ),
+            (
+                "?caller".to_string(),
+                "confidence".to_string(),
+                "?conf".to_string(),
+            ),
+        ];
+        let filters = vec![("conf".to_string(), ">".to_string(), 0.8)];
+        let bindings = run_pattern_query(&triples(), &patterns, &filters);
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].get("caller").unwrap(), "bombe://a/foo#f.py");
+    }
+}
@@ -0,0 +1,597 @@
+//! Boolean expression DSL for filtering symbols by attribute, e.g.
+//! `kind == "function" && pagerank > 0.25 && !qualified_name.starts_with("test.")`.
+//!
+//! [`parse`] produces a [`FilterExpr`] AST over a fixed set of identifiers
+//! (`name`, `qualified_name`, `kind`, `file_path`, `language`, `start_line`,
+//! `end_line`, `pagerank`) — any other identifier is rejected at parse time,
+//! not silently ignored. The AST then compiles to either a parameterized SQL
+//! `WHERE` fragment via [`FilterExpr::push_sql`] (mirrors [`BoundsRange`]'s
+//! `push_sql` convention: bound parameters only, literals are never
+//! interpolated into the SQL text) or an in-memory predicate via
+//! [`FilterExpr::matches`], for post-scoring filters the database can't
+//! express directly.
+//!
+//! [`BoundsRange`]: crate::query::bounds::BoundsRange
+
+use rusqlite::types::ToSql;
+
+use crate::errors::{BombeError, BombeResult};
+
+/// The symbol attributes a filter expression can reference. Narrower than
+/// [`crate::store::backend::SymbolProjection`] (adds `kind`/`language`,
+/// drops `id`/`signature`) since this is exactly the field set the DSL's
+/// identifiers resolve to.
+#[derive(Debug, Clone, Default)]
+pub struct FilterAttributes {
+    pub name: String,
+    pub qualified_name: String,
+    pub kind: String,
+    pub file_path: String,
+    pub language: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    /// Missing pagerank (a symbol that predates a PageRank recompute) reads
+    /// as `0.0`, same as `pagerank_score`'s `DEFAULT 0.0` column.
+    pub pagerank: f64,
+}
+
+/// One of the fixed identifiers a filter expression may reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Identifier {
+    Name,
+    QualifiedName,
+    Kind,
+    FilePath,
+    Language,
+    StartLine,
+    EndLine,
+    Pagerank,
+}
+
+impl Identifier {
+    fn parse(ident: &str) -> BombeResult<Self> {
+        match ident {
+            "name" => Ok(Identifier::Name),
+            "qualified_name" => Ok(Identifier::QualifiedName),
+            "kind" => Ok(Identifier::Kind),
+            "file_path" => Ok(Identifier::FilePath),
+            "language" => Ok(Identifier::Language),
+            "start_line" => Ok(Identifier::StartLine),
+            "end_line" => Ok(Identifier::EndLine),
+            "pagerank" => Ok(Identifier::Pagerank),
+            other => Err(BombeError::Parse(format!(
+                "unknown filter identifier '{other}'"
+            ))),
+        }
+    }
+
+    /// The SQL expression this identifier reads from. Everything but
+    /// `language` is a plain `symbols` column; `language` lives on `files`
+    /// (one row per file, not per symbol), so it reads through a correlated
+    /// subquery rather than requiring every caller to `JOIN files` just to
+    /// use the DSL.
+    fn column(self) -> &'static str {
+        match self {
+            Identifier::Name => "name",
+            Identifier::QualifiedName => "qualified_name",
+            Identifier::Kind => "kind",
+            Identifier::FilePath => "file_path",
+            Identifier::Language => "(SELECT language FROM files WHERE files.path = symbols.file_path)",
+            Identifier::StartLine => "start_line",
+            Identifier::EndLine => "end_line",
+            Identifier::Pagerank => "pagerank_score",
+        }
+    }
+
+    fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            Identifier::StartLine | Identifier::EndLine | Identifier::Pagerank
+        )
+    }
+}
+
+/// A literal on the right-hand side of a comparison.
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Str(String),
+    Num(f64),
+}
+
+/// A comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOp {
+    fn sql(self) -> &'static str {
+        match self {
+            CompareOp::Eq => "=",
+            CompareOp::Ne => "!=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+        }
+    }
+}
+
+/// A parsed filter expression: comparisons combined with `&&`/`||`/`!`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    Compare(Identifier, CompareOp, Literal),
+    /// `identifier.starts_with("literal")`, compiled to a SQL prefix `LIKE`
+    /// and to [`str::starts_with`] in-process.
+    StartsWith(Identifier, String),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+}
+
+impl FilterExpr {
+    /// Appends this expression to `sql` as a parenthesized `WHERE` fragment,
+    /// pushing bound parameters and advancing `param_idx` — every literal
+    /// goes through `params`, never through `format!`, so a string literal
+    /// can't break out of its parameter slot.
+    pub fn push_sql(
+        &self,
+        sql: &mut String,
+        params: &mut Vec<Box<dyn ToSql>>,
+        param_idx: &mut usize,
+    ) {
+        match self {
+            FilterExpr::Compare(ident, op, literal) => {
+                sql.push_str(&format!("({} {} ?{})", ident.column(), op.sql(), param_idx));
+                match literal {
+                    Literal::Str(s) => params.push(Box::new(s.clone())),
+                    Literal::Num(n) => params.push(Box::new(*n)),
+                }
+                *param_idx += 1;
+            }
+            FilterExpr::StartsWith(ident, prefix) => {
+                // Escape SQL LIKE wildcards in the literal itself, then
+                // append `%` as the actual wildcard, so the prefix matches
+                // literally rather than as a pattern.
+                let escaped = prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+                sql.push_str(&format!(
+                    "({} LIKE ?{} ESCAPE '\\')",
+                    ident.column(),
+                    param_idx
+                ));
+                params.push(Box::new(format!("{escaped}%")));
+                *param_idx += 1;
+            }
+            FilterExpr::And(lhs, rhs) => {
+                sql.push('(');
+                lhs.push_sql(sql, params, param_idx);
+                sql.push_str(" AND ");
+                rhs.push_sql(sql, params, param_idx);
+                sql.push(')');
+            }
+            FilterExpr::Or(lhs, rhs) => {
+                sql.push('(');
+                lhs.push_sql(sql, params, param_idx);
+                sql.push_str(" OR ");
+                rhs.push_sql(sql, params, param_idx);
+                sql.push(')');
+            }
+            FilterExpr::Not(inner) => {
+                sql.push_str("(NOT ");
+                inner.push_sql(sql, params, param_idx);
+                sql.push(')');
+            }
+        }
+    }
+
+    /// Evaluates this expression against `attrs` in-process, for callers
+    /// post-scoring results the SQL fast path didn't (or can't) filter.
+    pub fn matches(&self, attrs: &FilterAttributes) -> bool {
+        match self {
+            FilterExpr::Compare(ident, op, literal) => compare_matches(*ident, *op, literal, attrs),
+            FilterExpr::StartsWith(ident, prefix) => string_value(*ident, attrs).starts_with(prefix.as_str()),
+            FilterExpr::And(lhs, rhs) => lhs.matches(attrs) && rhs.matches(attrs),
+            FilterExpr::Or(lhs, rhs) => lhs.matches(attrs) || rhs.matches(attrs),
+            FilterExpr::Not(inner) => !inner.matches(attrs),
+        }
+    }
+}
+
+fn string_value(ident: Identifier, attrs: &FilterAttributes) -> String {
+    match ident {
+        Identifier::Name => attrs.name.clone(),
+        Identifier::QualifiedName => attrs.qualified_name.clone(),
+        Identifier::Kind => attrs.kind.clone(),
+        Identifier::FilePath => attrs.file_path.clone(),
+        Identifier::Language => attrs.language.clone(),
+        Identifier::StartLine => attrs.start_line.to_string(),
+        Identifier::EndLine => attrs.end_line.to_string(),
+        Identifier::Pagerank => attrs.pagerank.to_string(),
+    }
+}
+
+fn compare_matches(ident: Identifier, op: CompareOp, literal: &Literal, attrs: &FilterAttributes) -> bool {
+    match literal {
+        Literal::Num(n) => {
+            let value = match ident {
+                Identifier::StartLine => attrs.start_line as f64,
+                Identifier::EndLine => attrs.end_line as f64,
+                Identifier::Pagerank => attrs.pagerank,
+                _ => return false,
+            };
+            match op {
+                CompareOp::Eq => value == *n,
+                CompareOp::Ne => value != *n,
+                CompareOp::Gt => value > *n,
+                CompareOp::Ge => value >= *n,
+                CompareOp::Lt => value < *n,
+                CompareOp::Le => value <= *n,
+            }
+        }
+        Literal::Str(s) => {
+            let value = string_value(ident, attrs);
+            match op {
+                CompareOp::Eq => value == *s,
+                CompareOp::Ne => value != *s,
+                CompareOp::Gt => value > *s,
+                CompareOp::Ge => value >= *s,
+                CompareOp::Lt => value < *s,
+                CompareOp::Le => value <= *s,
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    AndAnd,
+    OrOr,
+    Bang,
+    Dot,
+    LParen,
+    RParen,
+    Comma,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+fn tokenize(input: &str) -> BombeResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = input.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '.' => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            '&' if chars.get(i + 1) == Some(&'&') => {
+                tokens.push(Token::AndAnd);
+                i += 2;
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                tokens.push(Token::OrOr);
+                i += 2;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Ne);
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Bang);
+                i += 1;
+            }
+            '=' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Eq);
+                i += 2;
+            }
+            '>' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Ge);
+                i += 2;
+            }
+            '>' => {
+                tokens.push(Token::Gt);
+                i += 1;
+            }
+            '<' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Le);
+                i += 2;
+            }
+            '<' => {
+                tokens.push(Token::Lt);
+                i += 1;
+            }
+            '"' => {
+                let mut s = String::new();
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(BombeError::Parse("unterminated string literal".to_string()));
+                }
+                i += 1; // closing quote
+                tokens.push(Token::Str(s));
+            }
+            c if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let num = text
+                    .parse::<f64>()
+                    .map_err(|_| BombeError::Parse(format!("invalid number literal '{text}'")))?;
+                tokens.push(Token::Num(num));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                tokens.push(Token::Ident(text));
+            }
+            other => {
+                return Err(BombeError::Parse(format!("unexpected character '{other}'")));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over [`Token`]s, one precedence level per
+/// method: `||` binds loosest, then `&&`, then unary `!`, then a single
+/// comparison or parenthesized expression.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn expect(&mut self, expected: &Token) -> BombeResult<()> {
+        match self.advance() {
+            Some(ref tok) if tok == expected => Ok(()),
+            other => Err(BombeError::Parse(format!(
+                "expected {expected:?}, found {other:?}"
+            ))),
+        }
+    }
+
+    fn parse_or(&mut self) -> BombeResult<FilterExpr> {
+        let mut lhs = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::OrOr)) {
+            self.advance();
+            let rhs = self.parse_and()?;
+            lhs = FilterExpr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> BombeResult<FilterExpr> {
+        let mut lhs = self.parse_unary()?;
+        while matches!(self.peek(), Some(Token::AndAnd)) {
+            self.advance();
+            let rhs = self.parse_unary()?;
+            lhs = FilterExpr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> BombeResult<FilterExpr> {
+        if matches!(self.peek(), Some(Token::Bang)) {
+            self.advance();
+            let inner = self.parse_unary()?;
+            return Ok(FilterExpr::Not(Box::new(inner)));
+        }
+        if matches!(self.peek(), Some(Token::LParen)) {
+            self.advance();
+            let inner = self.parse_or()?;
+            self.expect(&Token::RParen)?;
+            return Ok(inner);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> BombeResult<FilterExpr> {
+        let ident_name = match self.advance() {
+            Some(Token::Ident(name)) => name,
+            other => {
+                return Err(BombeError::Parse(format!(
+                    "expected identifier, found {other:?}"
+                )))
+            }
+        };
+        let ident = Identifier::parse(&ident_name)?;
+
+        if matches!(self.peek(), Some(Token::Dot)) {
+            self.advance();
+            match self.advance() {
+                Some(Token::Ident(method)) if method == "starts_with" => {}
+                other => {
+                    return Err(BombeError::Parse(format!(
+                        "expected method call after '.', found {other:?}"
+                    )))
+                }
+            }
+            self.expect(&Token::LParen)?;
+            let prefix = match self.advance() {
+                Some(Token::Str(s)) => s,
+                other => {
+                    return Err(BombeError::Parse(format!(
+                        "starts_with() expects a string literal, found {other:?}"
+                    )))
+                }
+            };
+            self.expect(&Token::RParen)?;
+            return Ok(FilterExpr::StartsWith(ident, prefix));
+        }
+
+        let op = match self.advance() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::Ne) => CompareOp::Ne,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::Ge) => CompareOp::Ge,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::Le) => CompareOp::Le,
+            other => {
+                return Err(BombeError::Parse(format!(
+                    "expected a comparison operator, found {other:?}"
+                )))
+            }
+        };
+
+        let literal = match self.advance() {
+            Some(Token::Str(s)) => Literal::Str(s),
+            Some(Token::Num(n)) => Literal::Num(n),
+            other => {
+                return Err(BombeError::Parse(format!(
+                    "expected a literal, found {other:?}"
+                )))
+            }
+        };
+
+        if ident.is_numeric() && matches!(literal, Literal::Str(_)) {
+            return Err(BombeError::Parse(format!(
+                "'{ident_name}' is numeric and can't be compared to a string literal"
+            )));
+        }
+
+        Ok(FilterExpr::Compare(ident, op, literal))
+    }
+}
+
+/// Parses `input` into a [`FilterExpr`], rejecting unknown identifiers,
+/// type-mismatched comparisons, and malformed syntax at parse time rather
+/// than deferring to the SQL engine or an in-process panic.
+pub fn parse(input: &str) -> BombeResult<FilterExpr> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(BombeError::Parse(format!(
+            "unexpected trailing input after position {}",
+            parser.pos
+        )));
+    }
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs() -> FilterAttributes {
+        FilterAttributes {
+            name: "handle".to_string(),
+            qualified_name: "com.example.Service.handle".to_string(),
+            kind: "function".to_string(),
+            file_path: "Service.java".to_string(),
+            language: "java".to_string(),
+            start_line: 10,
+            end_line: 20,
+            pagerank: 0.42,
+        }
+    }
+
+    #[test]
+    fn parses_and_matches_a_multi_clause_expression() {
+        let expr = parse(
+            r#"kind == "function" && pagerank > 0.25 && language == "java" && !qualified_name.starts_with("test.")"#,
+        )
+        .unwrap();
+        assert!(expr.matches(&attrs()));
+    }
+
+    #[test]
+    fn rejects_unknown_identifiers_at_parse_time() {
+        let err = parse(r#"bogus_field == "x""#).unwrap_err();
+        assert!(matches!(err, BombeError::Parse(_)));
+    }
+
+    #[test]
+    fn rejects_numeric_identifier_compared_to_string_literal() {
+        let err = parse(r#"pagerank == "high""#).unwrap_err();
+        assert!(matches!(err, BombeError::Parse(_)));
+    }
+
+    #[test]
+    fn missing_pagerank_reads_as_zero() {
+        let expr = parse("pagerank <= 0.0").unwrap();
+        let mut missing = attrs();
+        missing.pagerank = 0.0;
+        assert!(expr.matches(&missing));
+    }
+
+    #[test]
+    fn compiles_to_a_parameterized_sql_fragment() {
+        let expr = parse(r#"kind == "function" || pagerank >= 0.5"#).unwrap();
+        let mut sql = String::new();
+        let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+        let mut param_idx = 1usize;
+        expr.push_sql(&mut sql, &mut params, &mut param_idx);
+        assert_eq!(sql, "((kind = ?1) OR (pagerank_score >= ?2))");
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn string_literals_never_appear_interpolated_in_the_sql_text() {
+        let expr = parse(r#"name == "a' OR '1'='1""#).unwrap();
+        let mut sql = String::new();
+        let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+        let mut param_idx = 1usize;
+        expr.push_sql(&mut sql, &mut params, &mut param_idx);
+        assert!(!sql.contains('\''));
+        assert_eq!(params.len(), 1);
+    }
+
+    #[test]
+    fn or_binds_looser_than_and() {
+        let expr = parse(r#"kind == "function" && pagerank > 0.9 || language == "java""#).unwrap();
+        // `language == "java"` alone should be enough to satisfy the OR,
+        // even though the first AND clause (pagerank > 0.9) is false.
+        assert!(expr.matches(&attrs()));
+    }
+}
@@ -25,6 +25,11 @@ pub const MAX_CROSS_REPO_EDGES_PER_QUERY: i64 = 200;
 pub const MAX_FEDERATED_RESULTS: i64 = 500;
 pub const FEDERATED_SHARD_TIMEOUT_MS: i64 = 5000;
 pub const MAX_EXPORTED_SYMBOLS_REFRESH: i64 = 50000;
+pub const MAX_RDF_EXPORT_EDGES: i64 = 50000;
+pub const MAX_WILDCARD_IMPORT_MATCHES: i64 = 500;
+pub const MAX_REEXPORT_HOPS: i64 = 4;
+pub const MAX_CROSS_REPO_REACHABILITY_DEPTH: i64 = 6;
+pub const MAX_BATCH_EDGE_LOOKUP_SYMBOLS: usize = 500;
 
 #[pyfunction]
 pub fn clamp_int(value: i64, minimum: i64, maximum: i64) -> i64 {
@@ -36,6 +36,95 @@ pub fn semantic_vector_enabled() -> bool {
     }
 }
 
+/// Whether `search_symbols_impl` should re-rank candidates by rooted
+/// (personalized) PageRank seeded from the query's own top lexical matches.
+/// Off by default: it costs an extra power iteration per search, so it's
+/// opt-in like [`semantic_vector_enabled`].
+#[pyfunction]
+pub fn rooted_pagerank_enabled() -> bool {
+    match std::env::var("BOMBE_ROOTED_PAGERANK") {
+        Ok(val) => {
+            let v = val.trim().to_lowercase();
+            matches!(v.as_str(), "1" | "true" | "yes" | "on")
+        }
+        Err(_) => false,
+    }
+}
+
+/// Whether `search_symbols_impl` should score lexical relevance from SQLite
+/// FTS5's term-frequency/inverse-document-frequency aware `bm25()` (via
+/// `search_with_fts`), falling back to the hand-rolled [`lexical_score`]
+/// heuristic only for rows an FTS `MATCH` didn't surface. On by default,
+/// since BM25 accounts for term frequency and field length where the
+/// heuristic can't; disable to force the heuristic everywhere, e.g. to
+/// compare scorers or work around an FTS5 index that's missing or stale.
+#[pyfunction]
+pub fn bm25_lexical_scoring_enabled() -> bool {
+    match std::env::var("BOMBE_LEXICAL_SCORER") {
+        Ok(val) => {
+            let v = val.trim().to_lowercase();
+            !matches!(v.as_str(), "heuristic" | "0" | "false" | "no" | "off")
+        }
+        Err(_) => true,
+    }
+}
+
+/// Edit-distance budget for a query token's derivations: short tokens (≤5
+/// chars) tolerate a 1-edit typo, longer ones tolerate 2, mirroring how
+/// search engines widen the fuzzy radius for longer terms.
+fn derivation_budget(token: &str) -> usize {
+    if token.chars().count() <= 5 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Classic Wagner–Fischer bounded edit distance: returns `true` iff `a` and
+/// `b` are within `budget` edits of each other. Early-exits a row (and the
+/// whole computation) as soon as its running minimum exceeds `budget`, since
+/// no cell later in that row — or any subsequent row — can recover from it.
+fn edit_distance_within(a: &str, b: &str, budget: usize) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > budget {
+        return false;
+    }
+    let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut row = vec![0usize; b.len() + 1];
+        row[0] = i + 1;
+        let mut row_min = row[0];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            row[j + 1] = (prev_row[j] + cost)
+                .min(prev_row[j + 1] + 1)
+                .min(row[j] + 1);
+            row_min = row_min.min(row[j + 1]);
+        }
+        if row_min > budget {
+            return false;
+        }
+        prev_row = row;
+    }
+    prev_row[b.len()] <= budget
+}
+
+/// Scores one query token against one candidate token: 1.0 for an exact
+/// match, 0.7 for a "derivation" (within the token's edit-distance budget —
+/// catches typos like `getUsr` vs `getUser`), 0.5 for a prefix match, else 0.
+fn token_match_score(query_token: &str, candidate_token: &str, budget: usize) -> f64 {
+    if query_token == candidate_token {
+        1.0
+    } else if edit_distance_within(query_token, candidate_token, budget) {
+        0.7
+    } else if candidate_token.starts_with(query_token) || query_token.starts_with(candidate_token) {
+        0.5
+    } else {
+        0.0
+    }
+}
+
 #[pyfunction]
 pub fn lexical_score(query: &str, name: &str, qualified_name: &str) -> f64 {
     let q = query.trim().to_lowercase();
@@ -53,23 +142,54 @@ pub fn lexical_score(query: &str, name: &str, qualified_name: &str) -> f64 {
     if qn.contains(&q) {
         return 0.8;
     }
-    let query_tokens = tokens(query);
-    if query_tokens.is_empty() {
+    // Build each query token's derivation budget once, then reuse it across
+    // every candidate token below instead of recomputing it per comparison.
+    let query_derivations: Vec<(String, usize)> = tokens(query)
+        .into_iter()
+        .map(|t| {
+            let budget = derivation_budget(&t);
+            (t, budget)
+        })
+        .collect();
+    if query_derivations.is_empty() {
         return 0.0;
     }
     let target_tokens = tokens(&format!("{name} {qualified_name}"));
     if target_tokens.is_empty() {
         return 0.0;
     }
-    let overlap = query_tokens.intersection(&target_tokens).count();
-    overlap as f64 / query_tokens.len().max(1) as f64
+    let total: f64 = query_derivations
+        .iter()
+        .map(|(query_token, budget)| {
+            target_tokens
+                .iter()
+                .map(|candidate_token| token_match_score(query_token, candidate_token, *budget))
+                .fold(0.0, f64::max)
+        })
+        .sum();
+    total / query_derivations.len() as f64
+}
+
+/// Normalizes SQLite FTS5's `bm25()` score — where more negative means a
+/// better match, and the scale depends on corpus size/weights — into this
+/// crate's usual 0–1 "higher is better" lexical score, so it can stand in for
+/// [`lexical_score`]'s hand-rolled overlap ratio wherever FTS actually matched.
+pub(crate) fn normalize_bm25(raw_bm25: f64) -> f64 {
+    let better = (-raw_bm25).max(0.0);
+    better / (better + 1.0)
 }
 
 #[pyfunction]
-pub fn structural_score(pagerank: f64, callers: i64, callees: i64) -> f64 {
+#[pyo3(signature = (pagerank, callers, callees, rooted_pagerank=None))]
+pub fn structural_score(pagerank: f64, callers: i64, callees: i64, rooted_pagerank: Option<f64>) -> f64 {
     let pagerank_component = pagerank.max(0.0);
     let traffic_component = ((callers.max(0) + callees.max(0)) as f64 + 1.0).ln();
-    pagerank_component + (traffic_component * 0.1)
+    // Weighted well above the global component: a rooted score only exists
+    // when the caller seeded it from the query itself, so when present it's
+    // a much sharper importance-relative-to-this-search signal than the
+    // corpus-wide pagerank below it.
+    let rooted_component = rooted_pagerank.unwrap_or(0.0).max(0.0) * 0.5;
+    pagerank_component + (traffic_component * 0.1) + rooted_component
 }
 
 #[pyfunction]
@@ -92,7 +212,7 @@ pub fn semantic_score(query: &str, signature: Option<&str>, docstring: Option<&s
 }
 
 #[pyfunction]
-#[pyo3(signature = (*, query, name, qualified_name, signature=None, docstring=None, pagerank, callers, callees))]
+#[pyo3(signature = (*, query, name, qualified_name, signature=None, docstring=None, pagerank, callers, callees, bm25_lexical_score=None, rooted_pagerank=None))]
 #[allow(clippy::too_many_arguments)]
 pub fn rank_symbol(
     query: &str,
@@ -103,9 +223,14 @@ pub fn rank_symbol(
     pagerank: f64,
     callers: i64,
     callees: i64,
+    bm25_lexical_score: Option<f64>,
+    rooted_pagerank: Option<f64>,
 ) -> f64 {
-    let lex = lexical_score(query, name, qualified_name);
-    let struc = structural_score(pagerank, callers, callees);
+    // Prefer FTS5's term-frequency/inverse-document-frequency aware BM25
+    // score (normalized by `search_with_fts`) over the hand-rolled overlap
+    // ratio whenever the caller had an FTS match to compute it from.
+    let lex = bm25_lexical_score.unwrap_or_else(|| lexical_score(query, name, qualified_name));
+    let struc = structural_score(pagerank, callers, callees, rooted_pagerank);
     let sem = semantic_score(query, signature, docstring);
     if !hybrid_search_enabled() {
         return struc;
@@ -0,0 +1,353 @@
+//! K-shortest relationship-path explanations for context bundle symbols.
+//!
+//! `topology_order` labels each included symbol with a coarse reason
+//! (`seed`/`graph_neighbor`/`rank_fallback`), but doesn't say *how* a symbol
+//! connects back to the seed that pulled it in. This module finds up to `k`
+//! shortest relationship paths from a seed to a symbol over the shared
+//! [`CodeGraph`]'s directed, relationship-weighted adjacency, via a
+//! Yen-style K-shortest-paths search: Dijkstra for the best path, then
+//! spur-based deviations (temporarily blocking edges/nodes already used by
+//! shorter paths) for the rest. Path cost is the summed relationship edge
+//! weight; ties are broken by total PageRank along the path, preferring the
+//! path that routes through more central intermediate symbols.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::query::code_graph::CodeGraph;
+
+/// One hop of a relationship path: the relationship label of the edge used
+/// to reach `symbol_id` from the previous step (empty for the path's first
+/// symbol, the seed itself).
+#[derive(Clone, Debug)]
+pub struct PathStep {
+    pub symbol_id: i64,
+    pub relationship: String,
+}
+
+#[derive(Clone, Debug)]
+struct PathNode {
+    idx: usize,
+    relationship: String,
+    cumulative_cost: f64,
+}
+
+type PathRecord = Vec<PathNode>;
+
+fn path_to_steps(graph: &CodeGraph, path: &PathRecord) -> Vec<PathStep> {
+    path.iter()
+        .map(|node| PathStep {
+            symbol_id: graph.node_id(node.idx),
+            relationship: node.relationship.clone(),
+        })
+        .collect()
+}
+
+fn total_pagerank(graph: &CodeGraph, path: &PathRecord, pagerank: &HashMap<i64, f64>) -> f64 {
+    path.iter()
+        .map(|node| pagerank.get(&graph.node_id(node.idx)).copied().unwrap_or(0.0))
+        .sum()
+}
+
+/// Dijkstra's algorithm over `graph`'s directed out-edges, restricted to
+/// paths that avoid `blocked_nodes` and `blocked_edges` and are no longer
+/// than `max_hops` — the building block Yen's algorithm calls repeatedly
+/// with a growing exclusion set.
+fn shortest_path(
+    graph: &CodeGraph,
+    start: usize,
+    goal: usize,
+    blocked_edges: &HashSet<(usize, usize)>,
+    blocked_nodes: &HashSet<usize>,
+    max_hops: usize,
+) -> Option<PathRecord> {
+    if blocked_nodes.contains(&start) || blocked_nodes.contains(&goal) {
+        return None;
+    }
+    if start == goal {
+        return Some(vec![PathNode {
+            idx: start,
+            relationship: String::new(),
+            cumulative_cost: 0.0,
+        }]);
+    }
+
+    #[derive(Clone)]
+    struct State {
+        cost: f64,
+        node: usize,
+        hops: usize,
+    }
+    impl PartialEq for State {
+        fn eq(&self, other: &Self) -> bool {
+            self.cost == other.cost
+        }
+    }
+    impl Eq for State {}
+    impl PartialOrd for State {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for State {
+        fn cmp(&self, other: &Self) -> Ordering {
+            // Reversed, so `BinaryHeap` (a max-heap) pops the smallest cost first.
+            other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+        }
+    }
+
+    let mut best_cost: HashMap<usize, f64> = HashMap::new();
+    let mut prev: HashMap<usize, (usize, String)> = HashMap::new();
+    let mut heap: BinaryHeap<State> = BinaryHeap::new();
+
+    best_cost.insert(start, 0.0);
+    heap.push(State {
+        cost: 0.0,
+        node: start,
+        hops: 0,
+    });
+
+    while let Some(State { cost, node, hops }) = heap.pop() {
+        if node == goal {
+            let mut reversed: Vec<(usize, String)> = Vec::new();
+            let mut current = goal;
+            loop {
+                if current == start {
+                    reversed.push((current, String::new()));
+                    break;
+                }
+                let (parent, relationship) = prev[&current].clone();
+                reversed.push((current, relationship));
+                current = parent;
+            }
+            reversed.reverse();
+
+            let path: PathRecord = reversed
+                .into_iter()
+                .map(|(idx, relationship)| PathNode {
+                    idx,
+                    relationship,
+                    cumulative_cost: *best_cost.get(&idx).unwrap_or(&0.0),
+                })
+                .collect();
+            return Some(path);
+        }
+
+        if hops >= max_hops {
+            continue;
+        }
+        if cost > *best_cost.get(&node).unwrap_or(&f64::INFINITY) {
+            continue;
+        }
+
+        for (neighbor_idx, weight, relationship) in graph.out_edges(node) {
+            let neighbor = *neighbor_idx;
+            if blocked_nodes.contains(&neighbor) || blocked_edges.contains(&(node, neighbor)) {
+                continue;
+            }
+            let next_cost = cost + weight.max(1e-4);
+            let improves = match best_cost.get(&neighbor) {
+                Some(&existing) => next_cost < existing - 1e-9,
+                None => true,
+            };
+            if improves {
+                best_cost.insert(neighbor, next_cost);
+                prev.insert(neighbor, (node, relationship.clone()));
+                heap.push(State {
+                    cost: next_cost,
+                    node: neighbor,
+                    hops: hops + 1,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Ranks candidate deviation paths by cost ascending, then total PageRank
+/// descending (the request's tie-break), then by node path for determinism.
+fn rank_key(graph: &CodeGraph, path: &PathRecord, pagerank: &HashMap<i64, f64>) -> (f64, f64, Vec<i64>) {
+    let cost = path.last().map(|n| n.cumulative_cost).unwrap_or(0.0);
+    let pr = total_pagerank(graph, path, pagerank);
+    let ids: Vec<i64> = path.iter().map(|n| graph.node_id(n.idx)).collect();
+    (cost, -pr, ids)
+}
+
+/// Yen-style K-shortest-paths search from `source_id` to `goal_id`, capped
+/// at `max_hops` edges. Returns up to `k` paths ordered by cost ascending,
+/// ties broken by total PageRank descending.
+pub fn k_shortest_paths(
+    graph: &CodeGraph,
+    source_id: i64,
+    goal_id: i64,
+    pagerank: &HashMap<i64, f64>,
+    k: usize,
+    max_hops: usize,
+) -> Vec<(Vec<PathStep>, f64)> {
+    let (Some(start), Some(goal)) = (graph.dense_index(source_id), graph.dense_index(goal_id)) else {
+        return Vec::new();
+    };
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let no_edges = HashSet::new();
+    let no_nodes = HashSet::new();
+    let Some(first) = shortest_path(graph, start, goal, &no_edges, &no_nodes, max_hops) else {
+        return Vec::new();
+    };
+
+    let mut found: Vec<PathRecord> = vec![first];
+    let mut candidates: Vec<PathRecord> = Vec::new();
+    let mut seen_paths: HashSet<Vec<usize>> = HashSet::new();
+    seen_paths.insert(found[0].iter().map(|n| n.idx).collect());
+
+    while found.len() < k {
+        let last = found.last().unwrap().clone();
+        for i in 0..last.len().saturating_sub(1) {
+            let spur_idx = last[i].idx;
+            let root: &[PathNode] = &last[..=i];
+            let root_ids: Vec<usize> = root.iter().map(|n| n.idx).collect();
+
+            let mut blocked_edges: HashSet<(usize, usize)> = HashSet::new();
+            for p in found.iter().chain(candidates.iter()) {
+                if p.len() > i && p[..=i].iter().map(|n| n.idx).eq(root_ids.iter().copied()) {
+                    blocked_edges.insert((p[i].idx, p[i + 1].idx));
+                }
+            }
+            let blocked_nodes: HashSet<usize> = root_ids[..i].iter().copied().collect();
+            let remaining_hops = max_hops.saturating_sub(i);
+
+            let Some(spur_path) =
+                shortest_path(graph, spur_idx, goal, &blocked_edges, &blocked_nodes, remaining_hops)
+            else {
+                continue;
+            };
+
+            let root_cost = root[i].cumulative_cost;
+            let spur_node_relationship = root[i].relationship.clone();
+            let mut total: PathRecord = root[..i].to_vec();
+            for (j, node) in spur_path.into_iter().enumerate() {
+                // The spur sub-search treats `spur_idx` as a fresh start (so
+                // its first node carries no relationship); splice back in
+                // the relationship that actually reached it in `last`.
+                let relationship = if j == 0 {
+                    spur_node_relationship.clone()
+                } else {
+                    node.relationship
+                };
+                let cumulative_cost = if j == 0 {
+                    root_cost
+                } else {
+                    root_cost + node.cumulative_cost
+                };
+                total.push(PathNode {
+                    idx: node.idx,
+                    relationship,
+                    cumulative_cost,
+                });
+            }
+
+            let ids: Vec<usize> = total.iter().map(|n| n.idx).collect();
+            if seen_paths.insert(ids) {
+                candidates.push(total);
+            }
+        }
+
+        if candidates.is_empty() {
+            break;
+        }
+        candidates.sort_by(|a, b| rank_key(graph, a, pagerank).partial_cmp(&rank_key(graph, b, pagerank)).unwrap_or(Ordering::Equal));
+        found.push(candidates.remove(0));
+    }
+
+    found
+        .into_iter()
+        .map(|path| {
+            let cost = path.last().map(|n| n.cumulative_cost).unwrap_or(0.0);
+            (path_to_steps(graph, &path), cost)
+        })
+        .collect()
+}
+
+/// Explains how `symbol_id` connects back to whichever of `seeds` reaches it
+/// most cheaply, trying each seed and keeping the lowest-cost result.
+pub fn nearest_seed_paths(
+    graph: &CodeGraph,
+    seeds: &[i64],
+    symbol_id: i64,
+    pagerank: &HashMap<i64, f64>,
+    k: usize,
+    max_hops: usize,
+) -> Vec<Vec<PathStep>> {
+    let mut best: Option<(f64, Vec<(Vec<PathStep>, f64)>)> = None;
+    for &seed in seeds {
+        let paths = k_shortest_paths(graph, seed, symbol_id, pagerank, k, max_hops);
+        let Some((_, best_cost)) = paths.first().map(|(steps, cost)| (steps, *cost)) else {
+            continue;
+        };
+        if best.as_ref().map(|(cost, _)| best_cost < *cost).unwrap_or(true) {
+            best = Some((best_cost, paths));
+        }
+    }
+    best.map(|(_, paths)| paths.into_iter().map(|(steps, _)| steps).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE edges (
+                source_id INTEGER, source_type TEXT,
+                target_id INTEGER, target_type TEXT,
+                relationship TEXT
+            );
+            INSERT INTO edges VALUES (1, 'symbol', 2, 'symbol', 'CALLS');
+            INSERT INTO edges VALUES (2, 'symbol', 3, 'symbol', 'CALLS');
+            INSERT INTO edges VALUES (1, 'symbol', 4, 'symbol', 'HAS_METHOD');
+            INSERT INTO edges VALUES (4, 'symbol', 3, 'symbol', 'CALLS');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_shortest_path_prefers_lower_weight_edges() {
+        let conn = setup_conn();
+        let graph = CodeGraph::load(&conn).unwrap();
+        let pagerank: HashMap<i64, f64> = HashMap::new();
+        let paths = k_shortest_paths(&graph, 1, 3, &pagerank, 3, 4);
+        assert!(!paths.is_empty());
+        let (steps, _) = &paths[0];
+        let ids: Vec<i64> = steps.iter().map(|s| s.symbol_id).collect();
+        // Both 1->2->3 (two CALLS edges) and 1->4->3 (HAS_METHOD then CALLS)
+        // are two hops; CALLS is weighted higher than HAS_METHOD so the
+        // direct call chain should cost more and the HAS_METHOD route less.
+        assert_eq!(ids, vec![1, 4, 3]);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_returns_multiple_distinct_paths() {
+        let conn = setup_conn();
+        let graph = CodeGraph::load(&conn).unwrap();
+        let pagerank: HashMap<i64, f64> = HashMap::new();
+        let paths = k_shortest_paths(&graph, 1, 3, &pagerank, 2, 4);
+        assert_eq!(paths.len(), 2);
+        assert_ne!(paths[0].0.len(), 0);
+    }
+
+    #[test]
+    fn test_nearest_seed_paths_unreachable_returns_empty() {
+        let conn = setup_conn();
+        let graph = CodeGraph::load(&conn).unwrap();
+        let pagerank: HashMap<i64, f64> = HashMap::new();
+        let paths = nearest_seed_paths(&graph, &[99], 3, &pagerank, 3, 4);
+        assert!(paths.is_empty());
+    }
+}
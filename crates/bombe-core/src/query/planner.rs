@@ -1,34 +1,289 @@
-//! Query planner with lightweight in-memory response caching.
+//! Query planner with a W-TinyLFU-admission in-memory response cache.
+//!
+//! Plain LRU evicts purely by recency, so a handful of very hot tools get
+//! flushed out by a burst of one-off queries even though they're worth
+//! keeping. W-TinyLFU fixes this by routing every miss through a small
+//! recency-ordered "window" first, and only letting a window entry into the
+//! long-lived main segment if a [`CountMinSketch`] estimate says it's at
+//! least as popular as whatever the main segment would otherwise evict —
+//! see Einziger, Friedman & Manes, *TinyLFU: A Highly Efficient Cache
+//! Admission Policy* (ACM TOS 2017).
 
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 use std::time::Instant;
 
 use indexmap::IndexMap;
-use parking_lot::Mutex;
+use parking_lot::{Condvar, Mutex};
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
 struct CacheEntry {
     value: PyObject,
     expires_at: Instant,
+    cost: i64,
+}
+
+/// A single in-flight `compute()` call that concurrent callers for the same
+/// `cache_key` coalesce onto: the first caller (the "leader") runs
+/// `compute`, publishes its outcome here, and wakes every follower blocked
+/// on `condvar`. Stored as a simplified `Result<PyObject, String>` rather
+/// than the original `PyResult` so followers never need to touch a `PyErr`
+/// without holding the GIL.
+struct InFlightSlot {
+    result: Mutex<Option<Result<PyObject, String>>>,
+    condvar: Condvar,
+}
+
+/// Estimates the in-cache byte cost of a freshly computed value: if the
+/// caller supplied a `cost` callable (e.g. one that returns a serialized
+/// length), that wins; otherwise falls back to Python's own `sys.getsizeof`.
+fn estimate_cost(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+    cost: Option<&Bound<'_, PyAny>>,
+) -> PyResult<i64> {
+    if let Some(cost_fn) = cost {
+        return cost_fn.call1((value,))?.extract::<i64>();
+    }
+    py.import("sys")?
+        .call_method1("getsizeof", (value,))?
+        .extract::<i64>()
+}
+
+/// Extracts the `version_token` segment of a `_cache_key` result
+/// (`"{tool_name}:{version_token}:{normalized_payload}"`). Relies on
+/// `version_token` itself containing no `:` — true of every token this
+/// crate constructs, notably
+/// [`crate::indexer::semantic::compute_hints_version_token`], which uses
+/// `|` as its field separator for exactly this reason.
+fn version_token_of(cache_key: &str) -> &str {
+    cache_key.splitn(3, ':').nth(1).unwrap_or("")
+}
+
+/// Removes every entry from `segment` whose version token starts with
+/// `version_token_prefix` and returns `(evicted_bytes, evicted_count)`, so
+/// the caller can keep `CacheState::total_bytes` and `CacheState::evictions`
+/// in sync.
+fn invalidate_matching_from(
+    segment: &mut IndexMap<String, CacheEntry>,
+    version_token_prefix: &str,
+) -> (i64, u64) {
+    let matching: Vec<String> = segment
+        .keys()
+        .filter(|key| version_token_of(key).starts_with(version_token_prefix))
+        .cloned()
+        .collect();
+    let mut evicted_bytes = 0i64;
+    let mut evicted_count = 0u64;
+    for key in matching {
+        if let Some(entry) = segment.shift_remove(&key) {
+            evicted_bytes += entry.cost;
+            evicted_count += 1;
+        }
+    }
+    (evicted_bytes, evicted_count)
+}
+
+/// Removes every expired entry from `segment` and returns `(evicted_bytes,
+/// evicted_count)`, so the caller can keep `CacheState::total_bytes` and
+/// `CacheState::evictions` in sync.
+fn evict_expired_from(segment: &mut IndexMap<String, CacheEntry>, now: Instant) -> (i64, u64) {
+    let expired: Vec<String> = segment
+        .iter()
+        .filter(|(_, entry)| entry.expires_at <= now)
+        .map(|(key, _)| key.clone())
+        .collect();
+    let mut evicted_bytes = 0i64;
+    let mut evicted_count = 0u64;
+    for key in expired {
+        if let Some(entry) = segment.shift_remove(&key) {
+            evicted_bytes += entry.cost;
+            evicted_count += 1;
+        }
+    }
+    (evicted_bytes, evicted_count)
+}
+
+/// Four-row Count-Min sketch over cache keys, used only to estimate
+/// relative access frequency for admission decisions — not to bound
+/// segment membership itself (the segments below do that).
+const SKETCH_WIDTH: usize = 1024;
+const SKETCH_DEPTH: usize = 4;
+const SKETCH_SEEDS: [u64; SKETCH_DEPTH] = [
+    0x9E3779B97F4A7C15,
+    0xC2B2AE3D27D4EB4F,
+    0x165667B19E3779F9,
+    0x27D4EB2F165667C5,
+];
+
+struct CountMinSketch {
+    counters: Box<[[u16; SKETCH_WIDTH]; SKETCH_DEPTH]>,
+    additions: u64,
+    sample_size: u64,
+}
+
+impl CountMinSketch {
+    fn new(sample_size: u64) -> Self {
+        Self {
+            counters: Box::new([[0u16; SKETCH_WIDTH]; SKETCH_DEPTH]),
+            additions: 0,
+            sample_size: sample_size.max(1),
+        }
+    }
+
+    fn slot(row: usize, key: &str) -> usize {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        SKETCH_SEEDS[row].hash(&mut hasher);
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) % SKETCH_WIDTH
+    }
+
+    /// Increments the four counters for `key`, then halves every counter
+    /// (an "aging" pass, so stale popularity decays rather than only ever
+    /// growing) once `additions` reaches `sample_size`.
+    fn record(&mut self, key: &str) {
+        for row in 0..SKETCH_DEPTH {
+            let slot = Self::slot(row, key);
+            let counter = &mut self.counters[row][slot];
+            *counter = counter.saturating_add(1);
+        }
+        self.additions += 1;
+        if self.additions >= self.sample_size {
+            for row in self.counters.iter_mut() {
+                for counter in row.iter_mut() {
+                    *counter /= 2;
+                }
+            }
+            self.additions = 0;
+        }
+    }
+
+    fn estimate(&self, key: &str) -> u16 {
+        (0..SKETCH_DEPTH)
+            .map(|row| self.counters[row][Self::slot(row, key)])
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// W-TinyLFU cache state: a small recency-LRU `window`, plus a
+/// segmented-LRU main cache (`probation` for newly admitted/demoted
+/// entries, `protected` for entries that proved themselves with a second
+/// hit) admission-gated by `sketch`.
+struct CacheState {
+    window: IndexMap<String, CacheEntry>,
+    probation: IndexMap<String, CacheEntry>,
+    protected: IndexMap<String, CacheEntry>,
+    sketch: CountMinSketch,
+    hits: u64,
+    misses: u64,
+    rejected_admissions: u64,
+    total_bytes: i64,
+    evictions: u64,
+    lookup_histogram: LatencyHistogram,
+    compute_histogram: LatencyHistogram,
+}
+
+/// Upper bound (in ms) of each bucket in a [`LatencyHistogram`], log-spaced
+/// so both sub-millisecond cache hits and multi-second cold computes land
+/// in a meaningfully distinct bucket. A final implicit "+Inf" bucket holds
+/// anything above the last bound.
+const HISTOGRAM_BOUNDS_MS: [f64; 12] = [
+    0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0,
+];
+
+/// A compact, fixed log-spaced-bucket latency histogram — cheap enough to
+/// update on every lookup in production, not just in the bench harness (see
+/// [`crate::query::planner_bench`]).
+struct LatencyHistogram {
+    buckets: [u64; HISTOGRAM_BOUNDS_MS.len() + 1],
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: [0; HISTOGRAM_BOUNDS_MS.len() + 1],
+        }
+    }
+
+    fn record(&mut self, ms: f64) {
+        let bucket = HISTOGRAM_BOUNDS_MS
+            .iter()
+            .position(|&bound| ms <= bound)
+            .unwrap_or(HISTOGRAM_BOUNDS_MS.len());
+        self.buckets[bucket] += 1;
+    }
+
+    /// `{buckets: [{le_ms, count}, ..., {le_ms: "+Inf", count}]}`.
+    fn to_py_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        let entries = pyo3::types::PyList::empty(py);
+        for (bound, count) in HISTOGRAM_BOUNDS_MS.iter().zip(self.buckets.iter()) {
+            let entry = PyDict::new(py);
+            entry.set_item("le_ms", bound)?;
+            entry.set_item("count", count)?;
+            entries.append(entry)?;
+        }
+        let overflow = PyDict::new(py);
+        overflow.set_item("le_ms", "+Inf")?;
+        overflow.set_item("count", self.buckets[HISTOGRAM_BOUNDS_MS.len()])?;
+        entries.append(overflow)?;
+        dict.set_item("buckets", entries)?;
+        Ok(dict)
+    }
 }
 
 #[pyclass]
 pub struct QueryPlanner {
     max_entries: usize,
     ttl_seconds: f64,
-    cache: Mutex<IndexMap<String, CacheEntry>>,
+    max_bytes: i64,
+    window_capacity: usize,
+    main_capacity: usize,
+    protected_capacity: usize,
+    state: Mutex<CacheState>,
+    in_flight: Mutex<HashMap<String, Arc<InFlightSlot>>>,
 }
 
 #[pymethods]
 impl QueryPlanner {
     #[new]
-    #[pyo3(signature = (max_entries=512, ttl_seconds=15.0))]
-    fn new(max_entries: i64, ttl_seconds: f64) -> Self {
+    #[pyo3(signature = (max_entries=512, ttl_seconds=15.0, max_bytes=None))]
+    fn new(max_entries: i64, ttl_seconds: f64, max_bytes: Option<i64>) -> Self {
+        let max_entries = max_entries.max(1) as usize;
+        // ~1% of capacity for the window segment, floor of 1 entry, the
+        // rest for the main (probation + protected) segment.
+        let window_capacity = (max_entries / 100).max(1).min(max_entries);
+        let main_capacity = max_entries - window_capacity;
+        // Classic SLRU split: protected holds the bulk of the main segment
+        // (entries that have proven themselves with a second hit), and
+        // probation is where new/demoted entries land and compete for
+        // promotion.
+        let protected_capacity = (main_capacity * 4 / 5).min(main_capacity);
+
         Self {
-            max_entries: max_entries.max(1) as usize,
+            max_entries,
             ttl_seconds: ttl_seconds.max(0.1),
-            cache: Mutex::new(IndexMap::new()),
+            max_bytes: max_bytes.unwrap_or(i64::MAX).max(0),
+            window_capacity,
+            main_capacity,
+            protected_capacity,
+            state: Mutex::new(CacheState {
+                window: IndexMap::new(),
+                probation: IndexMap::new(),
+                protected: IndexMap::new(),
+                sketch: CountMinSketch::new((max_entries as u64).max(1) * 10),
+                hits: 0,
+                misses: 0,
+                rejected_admissions: 0,
+                total_bytes: 0,
+                evictions: 0,
+                lookup_histogram: LatencyHistogram::new(),
+                compute_histogram: LatencyHistogram::new(),
+            }),
+            in_flight: Mutex::new(HashMap::new()),
         }
     }
 
@@ -51,27 +306,73 @@ impl QueryPlanner {
         Ok(format!("{tool_name}:{suffix}:{normalized_str}"))
     }
 
+    /// Drops expired entries from all three segments. Unlike
+    /// `_evict_over_capacity`, expiry never competes on frequency — a
+    /// stale entry is removed outright regardless of how popular it was.
     fn _evict_expired(&self) {
-        let mut cache = self.cache.lock();
+        let mut state = self.state.lock();
         let now = Instant::now();
-        let expired_keys: Vec<String> = cache
-            .iter()
-            .filter(|(_, entry)| entry.expires_at <= now)
-            .map(|(key, _)| key.clone())
-            .collect();
-        for key in expired_keys {
-            cache.shift_remove(&key);
-        }
+        let (window_bytes, window_count) = evict_expired_from(&mut state.window, now);
+        let (probation_bytes, probation_count) = evict_expired_from(&mut state.probation, now);
+        let (protected_bytes, protected_count) = evict_expired_from(&mut state.protected, now);
+        state.total_bytes -= window_bytes + probation_bytes + protected_bytes;
+        state.evictions += window_count + probation_count + protected_count;
     }
 
+    /// Re-asserts the window/probation/protected capacity invariants, plus
+    /// the `max_bytes` byte budget. Normal inserts already enforce these as
+    /// they happen (see `insert_miss`); this exists so the invariants hold
+    /// even if capacities were shrunk by a prior call, or an external
+    /// caller wants to force a consistency pass.
     fn _evict_over_capacity(&self) {
-        let mut cache = self.cache.lock();
-        while cache.len() > self.max_entries {
-            cache.shift_remove_index(0);
+        let mut state = self.state.lock();
+        while state.window.len() > self.window_capacity {
+            if let Some((key, entry)) = state.window.shift_remove_index(0) {
+                Self::admit_to_main(
+                    &mut state,
+                    self.main_capacity,
+                    self.protected_capacity,
+                    key,
+                    entry,
+                );
+            }
+        }
+        while state.protected.len() > self.protected_capacity {
+            if let Some((key, entry)) = state.protected.shift_remove_index(0) {
+                state.probation.insert(key, entry);
+            }
         }
+        while state.probation.len() + state.protected.len() > self.main_capacity {
+            if let Some((_, entry)) = state.probation.shift_remove_index(0) {
+                state.total_bytes -= entry.cost;
+                state.evictions += 1;
+            }
+        }
+        Self::evict_for_byte_budget(&mut state, self.max_bytes);
+    }
+
+    /// Removes every cache entry (in any segment) whose `version_token`
+    /// starts with `version_token_prefix`, regardless of TTL. Callers that
+    /// derive their `version_token` from e.g.
+    /// [`crate::indexer::semantic::compute_hints_version_token`] use this to
+    /// force out entries computed under a superseded hint state the moment
+    /// the hint source changes, instead of waiting for TTL expiry to catch
+    /// up. Returns the number of entries removed.
+    fn invalidate(&self, version_token_prefix: &str) -> usize {
+        let mut state = self.state.lock();
+        let (window_bytes, window_count) =
+            invalidate_matching_from(&mut state.window, version_token_prefix);
+        let (probation_bytes, probation_count) =
+            invalidate_matching_from(&mut state.probation, version_token_prefix);
+        let (protected_bytes, protected_count) =
+            invalidate_matching_from(&mut state.protected, version_token_prefix);
+        state.total_bytes -= window_bytes + probation_bytes + protected_bytes;
+        let removed = window_count + probation_count + protected_count;
+        state.evictions += removed;
+        removed as usize
     }
 
-    #[pyo3(signature = (tool_name, payload, compute, version_token=None))]
+    #[pyo3(signature = (tool_name, payload, compute, version_token=None, cost=None))]
     fn get_or_compute(
         &self,
         py: Python<'_>,
@@ -79,13 +380,20 @@ impl QueryPlanner {
         payload: &Bound<'_, PyDict>,
         compute: &Bound<'_, PyAny>,
         version_token: Option<&str>,
+        cost: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<(PyObject, String)> {
-        let (result, mode, _) =
-            self.get_or_compute_with_trace(py, tool_name, payload, compute, version_token)?;
+        let (result, mode, _) = self.get_or_compute_with_trace(
+            py,
+            tool_name,
+            payload,
+            compute,
+            version_token,
+            cost,
+        )?;
         Ok((result, mode))
     }
 
-    #[pyo3(signature = (tool_name, payload, compute, version_token=None))]
+    #[pyo3(signature = (tool_name, payload, compute, version_token=None, cost=None))]
     fn get_or_compute_with_trace(
         &self,
         py: Python<'_>,
@@ -93,52 +401,95 @@ impl QueryPlanner {
         payload: &Bound<'_, PyDict>,
         compute: &Bound<'_, PyAny>,
         version_token: Option<&str>,
+        cost: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<(PyObject, String, PyObject)> {
         let cache_key = self._cache_key(tool_name, payload, version_token)?;
         let lookup_started = Instant::now();
 
-        // Check cache
         self._evict_expired();
-        {
-            let mut cache = self.cache.lock();
-            if let Some(entry) = cache.get(&cache_key) {
-                if entry.expires_at > Instant::now() {
-                    let value = entry.value.clone_ref(py);
-                    // Move to end for LRU
-                    let entry = cache.shift_remove(&cache_key).unwrap();
-                    cache.insert(cache_key, entry);
-
-                    let lookup_ms = lookup_started.elapsed().as_secs_f64() * 1000.0;
-                    let trace = PyDict::new(py);
-                    trace.set_item("lookup_ms", (lookup_ms * 1000.0).round() / 1000.0)?;
-                    trace.set_item("compute_ms", 0.0)?;
-                    trace.set_item("total_ms", (lookup_ms * 1000.0).round() / 1000.0)?;
-                    trace.set_item("version_token", version_token.unwrap_or("default"))?;
-                    return Ok((value, "cache_hit".to_string(), trace.into()));
+
+        if let Some(value) = self.try_hit(py, &cache_key) {
+            let lookup_ms = lookup_started.elapsed().as_secs_f64() * 1000.0;
+            self.record_latency(lookup_ms, 0.0);
+            let trace = PyDict::new(py);
+            trace.set_item("lookup_ms", (lookup_ms * 1000.0).round() / 1000.0)?;
+            trace.set_item("compute_ms", 0.0)?;
+            trace.set_item("total_ms", (lookup_ms * 1000.0).round() / 1000.0)?;
+            trace.set_item("version_token", version_token.unwrap_or("default"))?;
+            return Ok((value, "cache_hit".to_string(), trace.into()));
+        }
+
+        // Singleflight: if another caller is already computing this exact
+        // `cache_key`, join that call instead of recomputing — release the
+        // GIL while waiting so the leader (which needs the GIL to actually
+        // run `compute`) isn't blocked by us holding it.
+        let existing_slot = self.in_flight.lock().get(&cache_key).cloned();
+        if let Some(slot) = existing_slot {
+            let wait_started = Instant::now();
+            py.allow_threads(|| {
+                let mut guard = slot.result.lock();
+                while guard.is_none() {
+                    slot.condvar.wait(&mut guard);
                 }
-            }
+            });
+            let waited_ms = wait_started.elapsed().as_secs_f64() * 1000.0;
+            self.record_latency(0.0, 0.0);
+
+            let guard = slot.result.lock();
+            let outcome = match guard.as_ref().unwrap() {
+                Ok(value) => Ok(value.clone_ref(py)),
+                Err(message) => Err(pyo3::exceptions::PyRuntimeError::new_err(message.clone())),
+            };
+            drop(guard);
+            let value = outcome?;
+
+            let total_ms = lookup_started.elapsed().as_secs_f64() * 1000.0;
+            let trace = PyDict::new(py);
+            trace.set_item("lookup_ms", 0.0)?;
+            trace.set_item("compute_ms", 0.0)?;
+            trace.set_item("waited_ms", (waited_ms * 1000.0).round() / 1000.0)?;
+            trace.set_item("total_ms", (total_ms * 1000.0).round() / 1000.0)?;
+            trace.set_item("version_token", version_token.unwrap_or("default"))?;
+            return Ok((value, "cache_miss_coalesced".to_string(), trace.into()));
         }
 
+        let slot = Arc::new(InFlightSlot {
+            result: Mutex::new(None),
+            condvar: Condvar::new(),
+        });
+        self.in_flight.lock().insert(cache_key.clone(), slot.clone());
+
         // Compute
         let compute_started = Instant::now();
-        let result = compute.call0()?;
+        let compute_result = compute.call0();
         let compute_ms = compute_started.elapsed().as_secs_f64() * 1000.0;
 
+        // Publish to any followers that joined while we were computing,
+        // then wake them, before doing our own cache insert — a follower
+        // only needs the published value, not a finished cache entry.
+        let published: Result<PyObject, String> = match &compute_result {
+            Ok(value) => Ok(value.clone().unbind()),
+            Err(err) => Err(err.to_string()),
+        };
+        *slot.result.lock() = Some(published);
+        slot.condvar.notify_all();
+
+        let result = match compute_result {
+            Ok(value) => value,
+            Err(err) => {
+                self.in_flight.lock().remove(&cache_key);
+                return Err(err);
+            }
+        };
+
+        let entry_cost = estimate_cost(py, &result, cost)?;
         let expires_at = Instant::now() + std::time::Duration::from_secs_f64(self.ttl_seconds);
-        {
-            let mut cache = self.cache.lock();
-            cache.insert(
-                cache_key,
-                CacheEntry {
-                    value: result.clone().unbind(),
-                    expires_at,
-                },
-            );
-        }
-        self._evict_over_capacity();
+        self.insert_miss(cache_key.clone(), result.clone().unbind(), expires_at, entry_cost);
+        self.in_flight.lock().remove(&cache_key);
 
         let total_ms = lookup_started.elapsed().as_secs_f64() * 1000.0;
         let lookup_ms = (total_ms - compute_ms).max(0.0);
+        self.record_latency(lookup_ms, compute_ms);
         let trace = PyDict::new(py);
         trace.set_item("lookup_ms", (lookup_ms * 1000.0).round() / 1000.0)?;
         trace.set_item("compute_ms", (compute_ms * 1000.0).round() / 1000.0)?;
@@ -148,11 +499,211 @@ impl QueryPlanner {
         Ok((result.unbind(), "cache_miss".to_string(), trace.into()))
     }
 
-    fn stats(&self) -> HashMap<String, i64> {
-        let cache = self.cache.lock();
+    /// Returns a `{buckets: [...]}` dict for each of `lookup_ms` and
+    /// `compute_ms`, so the same log-spaced-bucket latency distribution
+    /// available to [`crate::query::planner_bench::run_planner_bench`] can
+    /// be scraped from a live `QueryPlanner` in production.
+    fn latency_histogram(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let state = self.state.lock();
+        let result = PyDict::new(py);
+        result.set_item("lookup_ms", state.lookup_histogram.to_py_dict(py)?)?;
+        result.set_item("compute_ms", state.compute_histogram.to_py_dict(py)?)?;
+        Ok(result.into())
+    }
+
+    fn stats(&self) -> HashMap<String, f64> {
+        let state = self.state.lock();
+        let total = state.hits + state.misses;
+        let hit_rate = if total > 0 {
+            state.hits as f64 / total as f64
+        } else {
+            0.0
+        };
         let mut result = HashMap::new();
-        result.insert("entries".to_string(), cache.len() as i64);
-        result.insert("max_entries".to_string(), self.max_entries as i64);
+        result.insert(
+            "entries".to_string(),
+            (state.window.len() + state.probation.len() + state.protected.len()) as f64,
+        );
+        result.insert("max_entries".to_string(), self.max_entries as f64);
+        result.insert("window_entries".to_string(), state.window.len() as f64);
+        result.insert("probation_entries".to_string(), state.probation.len() as f64);
+        result.insert("protected_entries".to_string(), state.protected.len() as f64);
+        result.insert("hits".to_string(), state.hits as f64);
+        result.insert("misses".to_string(), state.misses as f64);
+        result.insert("hit_rate".to_string(), hit_rate);
+        result.insert(
+            "rejected_admissions".to_string(),
+            state.rejected_admissions as f64,
+        );
+        result.insert("bytes".to_string(), state.total_bytes as f64);
+        result.insert("max_bytes".to_string(), self.max_bytes as f64);
+        result.insert("evictions".to_string(), state.evictions as f64);
         result
     }
 }
+
+impl QueryPlanner {
+    /// Records one `get_or_compute_with_trace` call's lookup/compute
+    /// latencies into the running histograms backing [`Self::latency_histogram`].
+    fn record_latency(&self, lookup_ms: f64, compute_ms: f64) {
+        let mut state = self.state.lock();
+        state.lookup_histogram.record(lookup_ms);
+        state.compute_histogram.record(compute_ms);
+    }
+
+    /// Looks `cache_key` up across all three segments. A window hit just
+    /// moves to the window's MRU end; a probation hit promotes into
+    /// `protected` (demoting protected's LRU back to probation if that
+    /// pushes `protected` over its sub-capacity); a protected hit moves to
+    /// protected's MRU end. Every lookup — hit or miss — records the key in
+    /// the sketch, since admission decisions need frequency for keys that
+    /// haven't been admitted yet too.
+    fn try_hit(&self, py: Python<'_>, cache_key: &str) -> Option<PyObject> {
+        let mut state = self.state.lock();
+        state.sketch.record(cache_key);
+        let now = Instant::now();
+
+        if let Some(entry) = state.window.get(cache_key) {
+            if entry.expires_at > now {
+                let value = entry.value.clone_ref(py);
+                let entry = state.window.shift_remove(cache_key).unwrap();
+                state.window.insert(cache_key.to_string(), entry);
+                state.hits += 1;
+                return Some(value);
+            }
+        }
+
+        if let Some(entry) = state.probation.get(cache_key) {
+            if entry.expires_at > now {
+                let value = entry.value.clone_ref(py);
+                let entry = state.probation.shift_remove(cache_key).unwrap();
+                state.protected.insert(cache_key.to_string(), entry);
+                if state.protected.len() > self.protected_capacity {
+                    if let Some((demoted_key, demoted_entry)) = state.protected.shift_remove_index(0)
+                    {
+                        state.probation.insert(demoted_key, demoted_entry);
+                    }
+                }
+                state.hits += 1;
+                return Some(value);
+            }
+        }
+
+        if let Some(entry) = state.protected.get(cache_key) {
+            if entry.expires_at > now {
+                let value = entry.value.clone_ref(py);
+                let entry = state.protected.shift_remove(cache_key).unwrap();
+                state.protected.insert(cache_key.to_string(), entry);
+                state.hits += 1;
+                return Some(value);
+            }
+        }
+
+        state.misses += 1;
+        None
+    }
+
+    /// Inserts a freshly computed value into the window, evicting the
+    /// window's LRU entry into the admission contest (see
+    /// [`Self::admit_to_main`]) if that insert pushes the window over
+    /// capacity, then evicts oldest entries (regardless of segment) until
+    /// the `max_bytes` byte budget is satisfied again.
+    fn insert_miss(&self, cache_key: String, value: PyObject, expires_at: Instant, cost: i64) {
+        let mut state = self.state.lock();
+        state.sketch.record(&cache_key);
+        state.total_bytes += cost;
+        state.window.insert(
+            cache_key,
+            CacheEntry {
+                value,
+                expires_at,
+                cost,
+            },
+        );
+
+        if state.window.len() > self.window_capacity {
+            if let Some((evicted_key, evicted_entry)) = state.window.shift_remove_index(0) {
+                Self::admit_to_main(
+                    &mut state,
+                    self.main_capacity,
+                    self.protected_capacity,
+                    evicted_key,
+                    evicted_entry,
+                );
+            }
+        }
+
+        Self::evict_for_byte_budget(&mut state, self.max_bytes);
+    }
+
+    /// Evicts the oldest entry — checked window first, then probation, then
+    /// protected — until `total_bytes` is back at or under `max_bytes`.
+    /// Unlike [`Self::admit_to_main`]'s admission contest, this never
+    /// consults the sketch: the request is strictly "evict oldest entries"
+    /// to reclaim memory, not a popularity contest.
+    fn evict_for_byte_budget(state: &mut CacheState, max_bytes: i64) {
+        while state.total_bytes > max_bytes {
+            let evicted = state
+                .window
+                .shift_remove_index(0)
+                .or_else(|| state.probation.shift_remove_index(0))
+                .or_else(|| state.protected.shift_remove_index(0));
+            match evicted {
+                Some((_, entry)) => {
+                    state.total_bytes -= entry.cost;
+                    state.evictions += 1;
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// A window entry that aged out competes for a spot in the main
+    /// segment. If there's free room, it's admitted outright. Otherwise it
+    /// goes up against `probation`'s LRU entry (the main segment's next
+    /// eviction victim): the candidate is admitted — evicting the victim —
+    /// only if the sketch estimates it at least as popular; otherwise the
+    /// candidate itself is dropped and the existing victim stays put. This
+    /// is the actual "TinyLFU" admission filter the window sits in front
+    /// of.
+    fn admit_to_main(
+        state: &mut CacheState,
+        main_capacity: usize,
+        protected_capacity: usize,
+        candidate_key: String,
+        candidate_entry: CacheEntry,
+    ) {
+        if state.probation.len() + state.protected.len() < main_capacity {
+            state.probation.insert(candidate_key, candidate_entry);
+            return;
+        }
+
+        let Some((victim_key, _)) = state.probation.get_index(0).map(|(k, v)| (k.clone(), v))
+        else {
+            // Nothing evictable in probation (everything promoted into
+            // protected) — admit unconditionally rather than stall.
+            state.probation.insert(candidate_key, candidate_entry);
+            if state.protected.len() > protected_capacity {
+                if let Some((demoted_key, demoted_entry)) = state.protected.shift_remove_index(0) {
+                    state.probation.insert(demoted_key, demoted_entry);
+                }
+            }
+            return;
+        };
+
+        let candidate_freq = state.sketch.estimate(&candidate_key);
+        let victim_freq = state.sketch.estimate(&victim_key);
+
+        if candidate_freq >= victim_freq {
+            if let Some(victim_entry) = state.probation.shift_remove(&victim_key) {
+                state.total_bytes -= victim_entry.cost;
+                state.evictions += 1;
+            }
+            state.probation.insert(candidate_key, candidate_entry);
+        } else {
+            state.total_bytes -= candidate_entry.cost;
+            state.evictions += 1;
+            state.rejected_admissions += 1;
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Workload-replay benchmark harness for [`crate::query::planner::QueryPlanner`].
+//!
+//! Mirrors `query::eval`'s workload-replay pattern but exercises the cache
+//! itself rather than a query engine: replays a JSON array of
+//! `{tool_name, payload, version_token}` entries through a fresh
+//! `QueryPlanner` with a stub compute (`builtins.dict`, a universally
+//! available zero-arg callable), and reports hit rate, p50/p95/p99 of
+//! `lookup_ms`/`compute_ms`, and the planner's own eviction counters — so
+//! `max_entries`/`ttl_seconds`/`max_bytes` can be tuned against a
+//! representative trace instead of guessed at.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::errors::{BombeError, BombeResult};
+use crate::query::planner::QueryPlanner;
+
+struct PlannerBenchCase {
+    tool_name: String,
+    payload: serde_json::Value,
+    version_token: Option<String>,
+}
+
+fn parse_cases(workload: &serde_json::Value) -> BombeResult<Vec<PlannerBenchCase>> {
+    let cases = workload.as_array().ok_or_else(|| {
+        BombeError::Query("workload file must be a JSON array of cases".to_string())
+    })?;
+    Ok(cases
+        .iter()
+        .filter_map(|case| {
+            let tool_name = case.get("tool_name")?.as_str()?.to_string();
+            let payload = case.get("payload").cloned().unwrap_or_else(|| serde_json::json!({}));
+            let version_token = case
+                .get("version_token")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            Some(PlannerBenchCase {
+                tool_name,
+                payload,
+                version_token,
+            })
+        })
+        .collect())
+}
+
+fn round_ms(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Nearest-rank p50/p95/p99 over a latency sample, in milliseconds.
+fn percentiles(mut samples: Vec<f64>) -> serde_json::Value {
+    if samples.is_empty() {
+        return serde_json::json!({"p50_ms": 0.0, "p95_ms": 0.0, "p99_ms": 0.0, "samples": 0});
+    }
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let pick = |p: f64| -> f64 {
+        let rank = ((samples.len() as f64) * p).ceil() as usize;
+        samples[rank.saturating_sub(1).min(samples.len() - 1)]
+    };
+    serde_json::json!({
+        "p50_ms": round_ms(pick(0.50)),
+        "p95_ms": round_ms(pick(0.95)),
+        "p99_ms": round_ms(pick(0.99)),
+        "samples": samples.len(),
+    })
+}
+
+/// Replays the workload at `workload_path` through a fresh `QueryPlanner`
+/// constructed with the given `max_entries`/`ttl_seconds`/`max_bytes`, and
+/// returns `{cases, hit_rate, lookup_ms, compute_ms, stats}`.
+pub fn run_planner_bench_impl(
+    py: Python<'_>,
+    workload_path: &str,
+    max_entries: i64,
+    ttl_seconds: f64,
+    max_bytes: Option<i64>,
+) -> PyResult<serde_json::Value> {
+    let content = std::fs::read_to_string(workload_path).map_err(BombeError::from)?;
+    let workload: serde_json::Value = serde_json::from_str(&content).map_err(BombeError::from)?;
+    let cases = parse_cases(&workload)?;
+
+    let planner = Py::new(py, QueryPlanner::new(max_entries, ttl_seconds, max_bytes))?;
+    let stub_compute = py.import("builtins")?.getattr("dict")?;
+
+    let mut lookup_samples = Vec::with_capacity(cases.len());
+    let mut compute_samples = Vec::with_capacity(cases.len());
+    let mut hits = 0i64;
+    let mut misses = 0i64;
+    let mut coalesced = 0i64;
+
+    for case in &cases {
+        let json_module = py.import("json")?;
+        let payload_str = serde_json::to_string(&case.payload).map_err(BombeError::from)?;
+        let payload = json_module.call_method1("loads", (payload_str,))?;
+        let payload = payload.downcast::<PyDict>().map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("case payload must be an object: {e}"))
+        })?;
+
+        let (_, mode, trace): (PyObject, String, Bound<'_, PyDict>) = planner
+            .call_method1(
+                py,
+                "get_or_compute_with_trace",
+                (
+                    case.tool_name.as_str(),
+                    payload,
+                    &stub_compute,
+                    case.version_token.as_deref(),
+                ),
+            )?
+            .extract(py)?;
+
+        let lookup_ms: f64 = trace.get_item("lookup_ms")?.map(|v| v.extract()).transpose()?.unwrap_or(0.0);
+        let compute_ms: f64 = trace.get_item("compute_ms")?.map(|v| v.extract()).transpose()?.unwrap_or(0.0);
+        lookup_samples.push(lookup_ms);
+        compute_samples.push(compute_ms);
+
+        match mode.as_str() {
+            "cache_hit" => hits += 1,
+            "cache_miss_coalesced" => coalesced += 1,
+            _ => misses += 1,
+        }
+    }
+
+    let stats: std::collections::HashMap<String, f64> =
+        planner.call_method0(py, "stats")?.extract(py)?;
+
+    Ok(serde_json::json!({
+        "cases": cases.len(),
+        "hits": hits,
+        "misses": misses,
+        "coalesced": coalesced,
+        "lookup_ms": percentiles(lookup_samples),
+        "compute_ms": percentiles(compute_samples),
+        "stats": stats,
+    }))
+}
+
+/// Benchmarking entry point exposed to Python: replays the workload JSON
+/// file at `workload_path` through a fresh `QueryPlanner` and returns a
+/// `{cases, hits, misses, coalesced, lookup_ms, compute_ms, stats}` report,
+/// the same way `query::eval::run_workload` reports retrieval quality.
+#[pyfunction]
+#[pyo3(signature = (workload_path, max_entries=512, ttl_seconds=15.0, max_bytes=None))]
+pub fn run_planner_bench(
+    py: Python<'_>,
+    workload_path: &str,
+    max_entries: i64,
+    ttl_seconds: f64,
+    max_bytes: Option<i64>,
+) -> PyResult<PyObject> {
+    let result = run_planner_bench_impl(py, workload_path, max_entries, ttl_seconds, max_bytes)?;
+    let json_str = serde_json::to_string(&result)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let json_module = py.import("json")?;
+    json_module
+        .call_method1("loads", (json_str,))
+        .map(|o| o.into())
+}
@@ -0,0 +1,124 @@
+//! Query graph for multi-term search queries.
+//!
+//! `search_symbols_impl` treats a whole query string as one opaque FTS/LIKE
+//! blob, which scores "parse json request" no differently than three
+//! independent terms that happen to co-occur anywhere in a symbol. This
+//! builds a small DAG over the query's terms — one node per term and one
+//! per pair of adjacent terms concatenated (since a two-word phrase is
+//! often written as a single identifier, e.g. `parseJson`) — and computes
+//! the "candidate universe": the union of FTS5 doc ids reachable by
+//! matching any node, each tagged with a phrase bonus for the longest
+//! contiguous run of original query terms any matching node covered. That
+//! universe bounds the expensive `count_refs`/`rank_symbol` work in
+//! `search_symbols_impl` to documents actually reachable in the graph, and
+//! rewards candidates that matched a full phrase over ones that only
+//! matched scattered single terms.
+
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+
+/// One node in the query graph: an FTS5 match expression plus the
+/// inclusive term-index span (into the original tokenized query) it
+/// covers.
+struct QueryGraphNode {
+    fts_term: String,
+    span: (usize, usize),
+}
+
+impl QueryGraphNode {
+    fn span_width(&self) -> usize {
+        self.span.1 - self.span.0 + 1
+    }
+}
+
+/// Returns `true` when `query` has more than one term — the case this
+/// module's candidate-universe restriction is worth the extra FTS queries
+/// for; single-term queries keep using the simpler direct FTS/LIKE path.
+pub fn is_multi_term(query: &str) -> bool {
+    query.split_whitespace().filter(|t| !t.trim().is_empty()).count() > 1
+}
+
+/// Tokenizes `query` into lowercase terms and builds one `Term` node per
+/// term plus one `Concatenation` node per pair of adjacent terms.
+fn build_nodes(query: &str) -> Vec<QueryGraphNode> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut nodes = Vec::with_capacity(terms.len() * 2);
+    for (i, term) in terms.iter().enumerate() {
+        nodes.push(QueryGraphNode {
+            fts_term: format!("\"{term}\" OR {term}*"),
+            span: (i, i),
+        });
+    }
+    for i in 0..terms.len().saturating_sub(1) {
+        let concat = format!("{}{}", terms[i], terms[i + 1]);
+        nodes.push(QueryGraphNode {
+            fts_term: format!("\"{concat}\" OR {concat}*"),
+            span: (i, i + 1),
+        });
+    }
+    nodes
+}
+
+/// Computes the candidate universe for a multi-term query: the union of
+/// symbol ids matched by any query-graph node, each mapped to a phrase
+/// bonus derived from the widest contiguous term-span any matching node
+/// covered (1.0 for a lone term, +0.2 per extra contiguous term).
+///
+/// Individual nodes that FTS5 rejects as malformed match syntax are skipped
+/// rather than failing the whole universe, the same tolerance
+/// `search_symbols_impl` already gives the plain FTS path.
+pub fn candidate_universe(
+    conn: &Connection,
+    query: &str,
+    kind: &str,
+    file_pattern: Option<&str>,
+) -> BombeResult<HashMap<i64, f64>> {
+    let nodes = build_nodes(query);
+    let mut universe: HashMap<i64, f64> = HashMap::new();
+
+    for node in &nodes {
+        let mut sql = String::from(
+            "SELECT s.id FROM symbol_fts f JOIN symbols s ON s.id = f.symbol_id \
+             WHERE symbol_fts MATCH ?1",
+        );
+        let mut params: Vec<Box<dyn rusqlite::types::ToSql>> =
+            vec![Box::new(node.fts_term.clone())];
+        let mut param_idx = 2;
+        if kind != "any" {
+            sql.push_str(&format!(" AND s.kind = ?{param_idx}"));
+            params.push(Box::new(kind.to_string()));
+            param_idx += 1;
+        }
+        if let Some(fp) = file_pattern {
+            sql.push_str(&format!(" AND s.file_path LIKE ?{param_idx}"));
+            params.push(Box::new(fp.replace('*', "%")));
+        }
+
+        let Ok(mut stmt) = conn.prepare(&sql) else {
+            continue;
+        };
+        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
+            params.iter().map(|p| p.as_ref()).collect();
+        let Ok(rows) = stmt.query_map(param_refs.as_slice(), |row| row.get::<_, i64>(0)) else {
+            continue;
+        };
+
+        let bonus = 1.0 + 0.2 * (node.span_width() as f64 - 1.0);
+        for id in rows.filter_map(|r| r.ok()) {
+            let entry = universe.entry(id).or_insert(1.0);
+            if bonus > *entry {
+                *entry = bonus;
+            }
+        }
+    }
+
+    Ok(universe)
+}
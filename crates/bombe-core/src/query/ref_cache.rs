@@ -0,0 +1,99 @@
+//! Per-request memoization for repeated caller/callee lookups.
+//!
+//! `search_symbols_impl` and `change_impact_impl` each re-query the `edges`
+//! table once per candidate/caller symbol, and candidates can recur across
+//! passes over the same result set. [`RefCountCache`] and [`CallerEdgeCache`]
+//! memoize those lookups for the lifetime of a single impl call (constructed
+//! fresh by each caller, never shared across requests), so a symbol's
+//! caller/callee count or outgoing-caller row set is computed at most once
+//! per request instead of once per place it's referenced. Because each cache
+//! is scoped to one request, index updates between requests can never leave
+//! it stale — there is nothing to invalidate globally.
+
+use std::collections::HashMap;
+
+use rusqlite::{Connection, Statement};
+
+use crate::errors::BombeResult;
+
+/// Memoizes `(callers_count, callees_count)` per symbol id.
+#[derive(Default)]
+pub struct RefCountCache {
+    counts: HashMap<i64, (i64, i64)>,
+}
+
+impl RefCountCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached `(callers_count, callees_count)` for `symbol_id`,
+    /// computing and caching it on first use.
+    pub fn get_or_compute(&mut self, conn: &Connection, symbol_id: i64) -> BombeResult<(i64, i64)> {
+        if let Some(&cached) = self.counts.get(&symbol_id) {
+            return Ok(cached);
+        }
+        let callers: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM edges WHERE relationship = 'CALLS' AND target_type = 'symbol' AND target_id = ?1;",
+            rusqlite::params![symbol_id],
+            |row| row.get(0),
+        )?;
+        let callees: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM edges WHERE relationship = 'CALLS' AND source_type = 'symbol' AND source_id = ?1;",
+            rusqlite::params![symbol_id],
+            |row| row.get(0),
+        )?;
+        let counts = (callers, callees);
+        self.counts.insert(symbol_id, counts);
+        Ok(counts)
+    }
+}
+
+/// One row of a CALLS edge into a symbol: the caller's id, the call-site
+/// line number, the caller's own identity/location, and whether the edge is
+/// a name-resolved `direct` call or a synthetic `virtual` dispatch edge (see
+/// `indexer::callgraph::expand_virtual_dispatch_edges`).
+pub type CallerEdge = (i64, Option<i64>, String, String, String, f64, String);
+
+/// Memoizes the outgoing-caller row set (`edges` rows whose `target_id` is a
+/// given symbol) per symbol id, so a BFS that happens to re-reach the same
+/// node doesn't re-issue its caller-edge query.
+#[derive(Default)]
+pub struct CallerEdgeCache {
+    callers: HashMap<i64, Vec<CallerEdge>>,
+}
+
+impl CallerEdgeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached caller-edge rows for `symbol_id` as queried by
+    /// `stmt` (expected to be the standard
+    /// `SELECT source_id, line_number, name, qualified_name, file_path, pagerank_score, dispatch ...`
+    /// caller-edge statement), computing and caching them on first use.
+    pub fn get_or_query(
+        &mut self,
+        stmt: &mut Statement,
+        symbol_id: i64,
+    ) -> BombeResult<&[CallerEdge]> {
+        if !self.callers.contains_key(&symbol_id) {
+            let rows: Vec<CallerEdge> = stmt
+                .query_map(rusqlite::params![symbol_id], |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get::<_, f64>(5).unwrap_or(0.0),
+                        row.get::<_, String>(6).unwrap_or_else(|_| "direct".to_string()),
+                    ))
+                })?
+                .filter_map(|r| r.ok())
+                .collect();
+            self.callers.insert(symbol_id, rows);
+        }
+        Ok(&self.callers[&symbol_id])
+    }
+}
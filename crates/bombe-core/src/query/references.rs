@@ -195,12 +195,34 @@ fn walk(
 // Main implementation (pure Rust, no Python dependency)
 // ---------------------------------------------------------------------------
 
+#[tracing::instrument(
+    skip(conn),
+    fields(operation = "get_references", depth, result_count = tracing::field::Empty)
+)]
 pub fn get_references_impl(
     conn: &Connection,
     symbol_name: &str,
     direction: &str,
     depth: i64,
     include_source: bool,
+) -> BombeResult<serde_json::Value> {
+    let result = crate::telemetry::timed_query("get_references", || {
+        get_references_impl_inner(conn, symbol_name, direction, depth, include_source)
+    })?;
+    let count = ["callers", "callees", "implementors", "supers"]
+        .iter()
+        .filter_map(|k| result.get(k).and_then(|v| v.as_array()).map(|a| a.len()))
+        .sum::<usize>() as i64;
+    tracing::Span::current().record("result_count", count);
+    Ok(result)
+}
+
+fn get_references_impl_inner(
+    conn: &Connection,
+    symbol_name: &str,
+    direction: &str,
+    depth: i64,
+    include_source: bool,
 ) -> BombeResult<serde_json::Value> {
     let normalized_symbol = truncate_query(symbol_name);
     let bounded_depth = clamp_depth(depth, MAX_REFERENCE_DEPTH);
@@ -212,9 +234,16 @@ pub fn get_references_impl(
     let dynamic_visited_cap = adaptive_graph_cap(total_symbols, MAX_GRAPH_VISITED, Some(200));
     let dynamic_edge_cap = 256i64.max(MAX_GRAPH_EDGES.min(dynamic_visited_cap * 2));
 
-    // Resolve the target symbol
-    let symbol_id = resolve_symbol_id(conn, &normalized_symbol)?
-        .ok_or_else(|| BombeError::Query(format!("Symbol not found: {normalized_symbol}")))?;
+    // Resolve the target symbol, falling back to a typo-tolerant match if
+    // neither exact lookup in `resolve_symbol_id` hits.
+    let symbol_id = match resolve_symbol_id(conn, &normalized_symbol)? {
+        Some(id) => id,
+        None => {
+            crate::query::symbol_resolution::resolve_symbol_fuzzy(conn, &normalized_symbol)?
+                .ok_or_else(|| BombeError::Query(format!("Symbol not found: {normalized_symbol}")))?
+                .id
+        }
+    };
 
     let target_symbol = load_symbol(conn, symbol_id)?
         .ok_or_else(|| BombeError::Query(format!("Symbol row missing for id: {symbol_id}")))?;
@@ -1,24 +1,43 @@
 //! Symbol search query backend.
 
+use std::collections::HashMap;
+
 use pyo3::prelude::*;
 use rusqlite::Connection;
 
 use crate::errors::BombeResult;
+use crate::indexer::pagerank::{personalized_pagerank_impl, PagerankWeights};
+use crate::query::bounds::BoundsRange;
 use crate::query::guards::{clamp_limit, truncate_query, MAX_SEARCH_LIMIT};
-use crate::query::hybrid::rank_symbol;
+use crate::query::hybrid::{
+    bm25_lexical_scoring_enabled, lexical_score, normalize_bm25, rank_symbol, rooted_pagerank_enabled,
+    semantic_score, structural_score,
+};
+use crate::query::query_graph::{candidate_universe, is_multi_term};
+use crate::query::ref_cache::RefCountCache;
 
-fn count_refs(conn: &Connection, symbol_id: i64) -> BombeResult<(i64, i64)> {
-    let callers: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM edges WHERE relationship = 'CALLS' AND target_type = 'symbol' AND target_id = ?1;",
-        rusqlite::params![symbol_id],
-        |row| row.get(0),
-    )?;
-    let callees: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM edges WHERE relationship = 'CALLS' AND source_type = 'symbol' AND source_id = ?1;",
-        rusqlite::params![symbol_id],
-        |row| row.get(0),
-    )?;
-    Ok((callers, callees))
+/// How many of the query's own top lexical matches seed the rooted PageRank
+/// pass, when enabled: enough to capture "the user's focus" as a set rather
+/// than a single point, without the power iteration's seed-membership checks
+/// dominating over a large result set.
+const ROOTED_PAGERANK_SEEDS: usize = 5;
+const ROOTED_PAGERANK_DAMPING: f64 = 0.85;
+const ROOTED_PAGERANK_TOL: f64 = 1e-6;
+
+/// Edit-distance budget for the fuzzy search path, scaled to query length: a
+/// short query is exact-only (a 1-edit typo on 4 characters is ambiguous with
+/// too much of the corpus to be useful), and the budget widens as the query
+/// gets long enough that a couple of typos still identify a unique intended
+/// symbol. Distinct from [`crate::query::hybrid`]'s `derivation_budget` (which
+/// scores already-fetched candidates token-by-token) because this one bounds
+/// a whole-string `edit_distance` SQL scan used to *find* candidates FTS/LIKE
+/// would otherwise miss entirely.
+fn fuzzy_edit_budget(query: &str) -> i64 {
+    match query.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
 }
 
 struct SymbolRow {
@@ -33,13 +52,19 @@ struct SymbolRow {
     docstring: Option<String>,
     visibility: Option<String>,
     pagerank_score: f64,
+    /// BM25-derived lexical score (0–1, higher is better), present only for
+    /// rows that actually came from an FTS5 match.
+    bm25_lexical: Option<f64>,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn search_with_like(
     conn: &Connection,
     query: &str,
     kind: &str,
     file_pattern: Option<&str>,
+    pagerank_range: &BoundsRange<f64>,
+    line_span_range: &BoundsRange<f64>,
     limit: i64,
 ) -> BombeResult<Vec<SymbolRow>> {
     let query_value = format!("%{}%", query.to_lowercase());
@@ -62,6 +87,8 @@ fn search_with_like(
         params.push(Box::new(fp.replace('*', "%")));
         param_idx += 1;
     }
+    pagerank_range.push_sql(&mut sql, &mut params, &mut param_idx, "pagerank_score");
+    line_span_range.push_sql(&mut sql, &mut params, &mut param_idx, "(end_line - start_line)");
     sql.push_str(&format!(
         " ORDER BY pagerank_score DESC, name ASC LIMIT ?{param_idx}"
     ));
@@ -83,6 +110,7 @@ fn search_with_like(
                 docstring: row.get(8)?,
                 visibility: row.get(9)?,
                 pagerank_score: row.get::<_, f64>(10).unwrap_or(0.0),
+                bm25_lexical: None,
             })
         })?
         .filter_map(|r| r.ok())
@@ -90,11 +118,14 @@ fn search_with_like(
     Ok(rows)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn search_with_fts(
     conn: &Connection,
     query: &str,
     kind: &str,
     file_pattern: Option<&str>,
+    pagerank_range: &BoundsRange<f64>,
+    line_span_range: &BoundsRange<f64>,
     limit: i64,
 ) -> BombeResult<Vec<SymbolRow>> {
     let query = query.trim();
@@ -102,9 +133,13 @@ fn search_with_fts(
         return Ok(vec![]);
     }
 
+    // `symbol_fts` columns are (name, qualified_name, docstring, signature);
+    // weight name highest so a query that matches a symbol's own name beats
+    // one that only matches its docstring or signature.
     let mut sql = String::from(
         "SELECT s.id, s.name, s.qualified_name, s.kind, s.file_path, s.start_line, s.end_line, \
-         s.signature, s.docstring, s.visibility, s.pagerank_score \
+         s.signature, s.docstring, s.visibility, s.pagerank_score, \
+         bm25(symbol_fts, 10.0, 5.0, 1.0, 2.0) AS bm25_score \
          FROM symbol_fts f JOIN symbols s ON s.id = f.symbol_id WHERE symbol_fts MATCH ?1",
     );
     let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(query.to_string())];
@@ -120,8 +155,91 @@ fn search_with_fts(
         params.push(Box::new(fp.replace('*', "%")));
         param_idx += 1;
     }
+    pagerank_range.push_sql(&mut sql, &mut params, &mut param_idx, "s.pagerank_score");
+    line_span_range.push_sql(
+        &mut sql,
+        &mut params,
+        &mut param_idx,
+        "(s.end_line - s.start_line)",
+    );
+    sql.push_str(&format!(
+        " ORDER BY bm25_score ASC, s.pagerank_score DESC LIMIT ?{param_idx}"
+    ));
+    params.push(Box::new(limit));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let rows = stmt
+        .query_map(param_refs.as_slice(), |row| {
+            let bm25_score: f64 = row.get::<_, f64>(11).unwrap_or(0.0);
+            Ok(SymbolRow {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                qualified_name: row.get(2)?,
+                kind: row.get(3)?,
+                file_path: row.get(4)?,
+                start_line: row.get(5)?,
+                end_line: row.get(6)?,
+                signature: row.get(7)?,
+                docstring: row.get(8)?,
+                visibility: row.get(9)?,
+                pagerank_score: row.get::<_, f64>(10).unwrap_or(0.0),
+                bm25_lexical: Some(normalize_bm25(bm25_score)),
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// Typo-tolerant candidate generation: finds symbols whose name or qualified
+/// name is within `max_typos` edits of `query`, using the `edit_distance`/
+/// `fuzzy_score` SQL scalar functions registered by [`crate::store::fuzzy`].
+/// Unlike `search_with_like`, this surfaces candidates FTS/LIKE can't — a
+/// misspelling like `getUzer` shares no substring with `getUser` for either
+/// to match on — at the cost of a full table scan, so it's opt-in via the
+/// `fuzzy` flag rather than always-on.
+#[allow(clippy::too_many_arguments)]
+fn search_with_fuzzy(
+    conn: &Connection,
+    query: &str,
+    max_typos: i64,
+    kind: &str,
+    file_pattern: Option<&str>,
+    pagerank_range: &BoundsRange<f64>,
+    line_span_range: &BoundsRange<f64>,
+    limit: i64,
+) -> BombeResult<Vec<SymbolRow>> {
+    let query_lower = query.trim().to_lowercase();
+    if query_lower.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut sql = String::from(
+        "SELECT id, name, qualified_name, kind, file_path, start_line, end_line, \
+         signature, docstring, visibility, pagerank_score, \
+         MAX(fuzzy_score(?1, LOWER(name)), fuzzy_score(?1, LOWER(qualified_name))) AS fuzzy_match \
+         FROM symbols WHERE \
+         (edit_distance(?1, LOWER(name), ?2) IS NOT NULL OR edit_distance(?1, LOWER(qualified_name), ?2) IS NOT NULL)",
+    );
+    let mut params: Vec<Box<dyn rusqlite::types::ToSql>> =
+        vec![Box::new(query_lower), Box::new(max_typos)];
+    let mut param_idx = 3;
+
+    if kind != "any" {
+        sql.push_str(&format!(" AND kind = ?{param_idx}"));
+        params.push(Box::new(kind.to_string()));
+        param_idx += 1;
+    }
+    if let Some(fp) = file_pattern {
+        sql.push_str(&format!(" AND file_path LIKE ?{param_idx}"));
+        params.push(Box::new(fp.replace('*', "%")));
+        param_idx += 1;
+    }
+    pagerank_range.push_sql(&mut sql, &mut params, &mut param_idx, "pagerank_score");
+    line_span_range.push_sql(&mut sql, &mut params, &mut param_idx, "(end_line - start_line)");
     sql.push_str(&format!(
-        " ORDER BY rank ASC, s.pagerank_score DESC LIMIT ?{param_idx}"
+        " ORDER BY fuzzy_match DESC, pagerank_score DESC LIMIT ?{param_idx}"
     ));
     params.push(Box::new(limit));
 
@@ -129,6 +247,7 @@ fn search_with_fts(
     let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
     let rows = stmt
         .query_map(param_refs.as_slice(), |row| {
+            let fuzzy_match: f64 = row.get::<_, f64>(11).unwrap_or(0.0);
             Ok(SymbolRow {
                 id: row.get(0)?,
                 name: row.get(1)?,
@@ -141,6 +260,7 @@ fn search_with_fts(
                 docstring: row.get(8)?,
                 visibility: row.get(9)?,
                 pagerank_score: row.get::<_, f64>(10).unwrap_or(0.0),
+                bm25_lexical: Some(fuzzy_match),
             })
         })?
         .filter_map(|r| r.ok())
@@ -148,21 +268,122 @@ fn search_with_fts(
     Ok(rows)
 }
 
+/// Span-and-metrics wrapper around [`search_symbols_impl_inner`]. Kept
+/// separate so the scoring/merge logic below isn't threaded through
+/// instrumentation concerns.
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(
+    skip(conn, pagerank_range, line_span_range, callers_range, callees_range),
+    fields(operation = "search_symbols", result_count = tracing::field::Empty)
+)]
 pub fn search_symbols_impl(
     conn: &Connection,
     query: &str,
     kind: &str,
     file_pattern: Option<&str>,
+    pagerank_range: &BoundsRange<f64>,
+    line_span_range: &BoundsRange<f64>,
+    callers_range: &BoundsRange<i64>,
+    callees_range: &BoundsRange<i64>,
     limit: i64,
+    fuzzy: bool,
+    max_typos: Option<i64>,
+) -> BombeResult<serde_json::Value> {
+    let result = crate::telemetry::timed_query("search_symbols", || {
+        search_symbols_impl_inner(
+            conn,
+            query,
+            kind,
+            file_pattern,
+            pagerank_range,
+            line_span_range,
+            callers_range,
+            callees_range,
+            limit,
+            fuzzy,
+            max_typos,
+        )
+    })?;
+    if let Some(total) = result.get("total_matches").and_then(|v| v.as_i64()) {
+        tracing::Span::current().record("result_count", total);
+    }
+    Ok(result)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn search_symbols_impl_inner(
+    conn: &Connection,
+    query: &str,
+    kind: &str,
+    file_pattern: Option<&str>,
+    pagerank_range: &BoundsRange<f64>,
+    line_span_range: &BoundsRange<f64>,
+    callers_range: &BoundsRange<i64>,
+    callees_range: &BoundsRange<i64>,
+    limit: i64,
+    fuzzy: bool,
+    max_typos: Option<i64>,
 ) -> BombeResult<serde_json::Value> {
     let normalized_query = truncate_query(query);
     let bounded_limit = clamp_limit(limit, MAX_SEARCH_LIMIT);
     let expanded_limit = clamp_limit(bounded_limit * 3, MAX_SEARCH_LIMIT);
 
-    // Try FTS first
-    let fts_rows = search_with_fts(conn, &normalized_query, kind, file_pattern, expanded_limit)
-        .unwrap_or_default();
-    let like_rows = search_with_like(conn, &normalized_query, kind, file_pattern, expanded_limit)?;
+    // Try FTS first, unless the BM25 scorer has been turned off in favor of
+    // the heuristic everywhere (see `bm25_lexical_scoring_enabled`).
+    let fts_rows = if bm25_lexical_scoring_enabled() {
+        search_with_fts(
+            conn,
+            &normalized_query,
+            kind,
+            file_pattern,
+            pagerank_range,
+            line_span_range,
+            expanded_limit,
+        )
+        .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let like_rows = search_with_like(
+        conn,
+        &normalized_query,
+        kind,
+        file_pattern,
+        pagerank_range,
+        line_span_range,
+        expanded_limit,
+    )?;
+
+    // Only worth the full-table fuzzy scan when the caller opted in and
+    // exact/substring matching didn't already find enough — a typo-tolerant
+    // pass over a corpus FTS/LIKE already satisfied would just add noise.
+    let fuzzy_rows = if fuzzy && like_rows.is_empty() && fts_rows.is_empty() {
+        let budget = max_typos.unwrap_or_else(|| fuzzy_edit_budget(&normalized_query));
+        search_with_fuzzy(
+            conn,
+            &normalized_query,
+            budget,
+            kind,
+            file_pattern,
+            pagerank_range,
+            line_span_range,
+            expanded_limit,
+        )
+        .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // For multi-term queries, compute the query-graph candidate universe
+    // up front so the per-candidate work below (count_refs + rank_symbol)
+    // is bounded to symbols actually reachable by some term/phrase node,
+    // instead of just whatever the FTS/LIKE over-fetch happened to return.
+    let multi_term = is_multi_term(&normalized_query);
+    let universe = if multi_term {
+        candidate_universe(conn, &normalized_query, kind, file_pattern).unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
 
     // Combine, FTS takes priority
     let mut combined: indexmap::IndexMap<i64, (SymbolRow, String)> = indexmap::IndexMap::new();
@@ -170,20 +391,69 @@ pub fn search_symbols_impl(
         let id = row.id;
         combined.entry(id).or_insert((row, "like".to_string()));
     }
+    for row in fuzzy_rows {
+        let id = row.id;
+        combined.entry(id).or_insert((row, "fuzzy".to_string()));
+    }
     for row in fts_rows {
         let id = row.id;
         combined.insert(id, (row, "fts".to_string()));
     }
+    if multi_term && !universe.is_empty() {
+        combined.retain(|id, _| universe.contains_key(id));
+    }
     let search_mode = if combined.values().any(|(_, s)| s == "fts") {
         "fts"
+    } else if combined.values().any(|(_, s)| s == "fuzzy") {
+        "fuzzy"
     } else {
         "like"
     };
 
+    // Rooted PageRank, when enabled: seed from this query's own strongest
+    // lexical matches so structural importance is measured relative to the
+    // user's focus instead of the whole corpus, then blend it into every
+    // candidate's ranking score below. Falls back to an empty map (a no-op
+    // blend) when the flag is off or there's nothing to seed from. Lexical
+    // scores computed here for seed selection are reused by `rank_symbol`
+    // below (via `lexical_lookup`) instead of being recomputed per row.
+    let mut lexical_lookup: HashMap<i64, f64> = HashMap::new();
+    let rooted_scores = if rooted_pagerank_enabled() && !combined.is_empty() {
+        for (&id, (row, _)) in &combined {
+            let lex = row
+                .bm25_lexical
+                .unwrap_or_else(|| lexical_score(&normalized_query, &row.name, &row.qualified_name));
+            lexical_lookup.insert(id, lex);
+        }
+        let mut by_lexical: Vec<(i64, f64)> = lexical_lookup.iter().map(|(&id, &lex)| (id, lex)).collect();
+        by_lexical.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        let seed_ids: Vec<i64> = by_lexical
+            .into_iter()
+            .take(ROOTED_PAGERANK_SEEDS)
+            .map(|(id, _)| id)
+            .collect();
+        personalized_pagerank_impl(
+            conn,
+            &seed_ids,
+            ROOTED_PAGERANK_DAMPING,
+            ROOTED_PAGERANK_TOL,
+            &PagerankWeights::default(),
+        )
+        .unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
+
+    let mut ref_cache = RefCountCache::new();
     let mut scored: Vec<(f64, serde_json::Value)> = Vec::new();
-    for (_, (row, strategy)) in &combined {
-        let (callers_count, callees_count) = count_refs(conn, row.id)?;
-        let ranking_score = rank_symbol(
+    for (id, (row, strategy)) in &combined {
+        let (callers_count, callees_count) = ref_cache.get_or_compute(conn, row.id)?;
+        if !callers_range.contains(callers_count) || !callees_range.contains(callees_count) {
+            continue;
+        }
+        let bm25_lexical = row.bm25_lexical.or_else(|| lexical_lookup.get(id).copied());
+        let rooted = rooted_scores.get(id).copied();
+        let mut ranking_score = rank_symbol(
             &normalized_query,
             &row.name,
             &row.qualified_name,
@@ -192,7 +462,21 @@ pub fn search_symbols_impl(
             row.pagerank_score,
             callers_count,
             callees_count,
+            bm25_lexical,
+            rooted,
         );
+        // The raw (pre-`rank_symbol`-weighting) lexical/structural/semantic
+        // values that produced `ranking_score` above, so a caller can see
+        // *which* signal drove a result's rank — not a literal decomposition
+        // of `score` itself, since `rank_symbol` blends these with its own
+        // 0.55/0.35/0.1 weights (or returns `structural_component` alone when
+        // hybrid search is disabled) before the phrase-bonus multiplier below.
+        let lexical_component =
+            bm25_lexical.unwrap_or_else(|| lexical_score(&normalized_query, &row.name, &row.qualified_name));
+        let structural_component = structural_score(row.pagerank_score, callers_count, callees_count, rooted);
+        let semantic_component = semantic_score(&normalized_query, row.signature.as_deref(), row.docstring.as_deref());
+        let phrase_bonus = universe.get(id).copied().unwrap_or(1.0);
+        ranking_score *= phrase_bonus;
         let file_pat = file_pattern.unwrap_or("*");
         let match_reason = format!(
             "{search_mode}:query='{}',kind='{}',file='{}'",
@@ -214,6 +498,13 @@ pub fn search_symbols_impl(
                 "callees_count": callees_count,
                 "match_strategy": strategy,
                 "match_reason": match_reason,
+                "score": ranking_score,
+                "score_components": {
+                    "lexical": lexical_component,
+                    "structural": structural_component,
+                    "semantic": semantic_component,
+                    "phrase_bonus": phrase_bonus,
+                },
             }),
         ));
     }
@@ -253,7 +544,17 @@ pub fn search_symbols_impl(
 }
 
 #[pyfunction]
-#[pyo3(signature = (db, query, kind="any", file_pattern=None, limit=20))]
+#[pyo3(signature = (
+    db, query, kind="any", file_pattern=None, limit=20,
+    pagerank_min=None, pagerank_min_exclusive=false,
+    pagerank_max=None, pagerank_max_exclusive=false,
+    min_lines=None, min_lines_exclusive=false,
+    max_lines=None, max_lines_exclusive=false,
+    min_callers=None, max_callers=None,
+    min_callees=None, max_callees=None,
+    fuzzy=false, max_typos=None,
+))]
+#[allow(clippy::too_many_arguments)]
 pub fn search_symbols(
     py: Python<'_>,
     db: &crate::store::database::Database,
@@ -261,9 +562,45 @@ pub fn search_symbols(
     kind: &str,
     file_pattern: Option<&str>,
     limit: i64,
+    pagerank_min: Option<f64>,
+    pagerank_min_exclusive: bool,
+    pagerank_max: Option<f64>,
+    pagerank_max_exclusive: bool,
+    min_lines: Option<f64>,
+    min_lines_exclusive: bool,
+    max_lines: Option<f64>,
+    max_lines_exclusive: bool,
+    min_callers: Option<i64>,
+    max_callers: Option<i64>,
+    min_callees: Option<i64>,
+    max_callees: Option<i64>,
+    fuzzy: bool,
+    max_typos: Option<i64>,
 ) -> PyResult<PyObject> {
     let conn = db.connect_internal()?;
-    let result = search_symbols_impl(&conn, query, kind, file_pattern, limit)?;
+    let pagerank_range = BoundsRange::from_min_max(
+        pagerank_min,
+        pagerank_min_exclusive,
+        pagerank_max,
+        pagerank_max_exclusive,
+    );
+    let line_span_range =
+        BoundsRange::from_min_max(min_lines, min_lines_exclusive, max_lines, max_lines_exclusive);
+    let callers_range = BoundsRange::from_min_max(min_callers, max_callers);
+    let callees_range = BoundsRange::from_min_max(min_callees, max_callees);
+    let result = search_symbols_impl(
+        &conn,
+        query,
+        kind,
+        file_pattern,
+        &pagerank_range,
+        &line_span_range,
+        &callers_range,
+        &callees_range,
+        limit,
+        fuzzy,
+        max_typos,
+    )?;
     let json_str = serde_json::to_string(&result)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
     let json_module = py.import("json")?;
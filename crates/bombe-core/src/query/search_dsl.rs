@@ -0,0 +1,621 @@
+//! A readable pattern-matching expression language for
+//! [`crate::models::SymbolSearchRequest`] name/pattern matching, compiling
+//! down to a [`regex::Regex`] -- the same engine
+//! [`crate::query::context::compile_custom_redaction_pattern`] compiles
+//! caller-supplied patterns with -- instead of asking users to hand-write
+//! raw regex for things like "three repeats of `get`" or "a capture group
+//! named `suffix`".
+//!
+//! [`compile_query`] is the entry point: it takes DSL or raw-regex source
+//! (per `mode`) and returns a [`CompiledSymbolPattern`] ready to match
+//! against symbol names. [`compile_symbol_pattern`] is the
+//! [`crate::models::SymbolSearchRequest`]-aware convenience wrapper that
+//! reads `query_mode` and returns `None` for plain `"text"` queries, which
+//! go through the existing FTS/LIKE/fuzzy pipeline in
+//! [`crate::query::search`] unchanged.
+//!
+//! Grammar (mirrors [`crate::query::filter_dsl`]'s hand-rolled
+//! recursive-descent style): an expression is a sequence of atoms --
+//! quoted string literals, `<word>`/`<digit>`/`<space>`/`<upper>`/`<lower>`
+//! character-class sugar, the `any`/`start`/`end` keywords, `N of atom`
+//! quantified repeats, `either of { atom, atom, ... }` alternation, and
+//! `capture name { atom atom ... }` named capture groups.
+
+use std::fmt;
+use std::ops::Range;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use regex::Regex;
+
+use crate::models::SymbolSearchRequest;
+
+/// A DSL compile error with the byte-offset span in the source that caused
+/// it, so callers can point a user at exactly what's wrong instead of just
+/// a message -- [`crate::query::filter_dsl`]'s `BombeError::Parse` carries
+/// no span, which is the gap this type exists to close.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DslError {
+    pub message: String,
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for DslError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (at {}..{})",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for DslError {}
+
+impl From<DslError> for PyErr {
+    fn from(err: DslError) -> PyErr {
+        pyo3::exceptions::PyValueError::new_err(err.to_string())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AST
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum AtomExpr {
+    Literal(String),
+    CharClass(&'static str),
+    Any,
+    Start,
+    End,
+    Quantified(u32, Box<AtomExpr>),
+    Either(Vec<AtomExpr>),
+    Capture(String, Box<AtomExpr>),
+    Seq(Vec<AtomExpr>),
+}
+
+fn render(expr: &AtomExpr, out: &mut String) {
+    match expr {
+        AtomExpr::Literal(s) => out.push_str(&regex::escape(s)),
+        AtomExpr::CharClass(class) => out.push_str(match *class {
+            "word" => r"\w",
+            "digit" => r"\d",
+            "space" => r"\s",
+            "upper" => "[A-Z]",
+            "lower" => "[a-z]",
+            other => unreachable!("unvalidated char class '{other}'"),
+        }),
+        AtomExpr::Any => out.push('.'),
+        AtomExpr::Start => out.push('^'),
+        AtomExpr::End => out.push('$'),
+        AtomExpr::Quantified(count, inner) => {
+            out.push_str("(?:");
+            render(inner, out);
+            out.push_str(&format!("){{{count}}}"));
+        }
+        AtomExpr::Either(branches) => {
+            out.push_str("(?:");
+            for (i, branch) in branches.iter().enumerate() {
+                if i > 0 {
+                    out.push('|');
+                }
+                render(branch, out);
+            }
+            out.push(')');
+        }
+        AtomExpr::Capture(name, inner) => {
+            out.push_str(&format!("(?P<{name}>"));
+            render(inner, out);
+            out.push(')');
+        }
+        AtomExpr::Seq(items) => {
+            for item in items {
+                render(item, out);
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Class(String),
+    Str(String),
+    Num(u32),
+    LBrace,
+    RBrace,
+    Comma,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, DslError> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = input.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '{' => {
+                tokens.push(Token {
+                    kind: TokenKind::LBrace,
+                    span: i..i + 1,
+                });
+                i += 1;
+            }
+            '}' => {
+                tokens.push(Token {
+                    kind: TokenKind::RBrace,
+                    span: i..i + 1,
+                });
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token {
+                    kind: TokenKind::Comma,
+                    span: i..i + 1,
+                });
+                i += 1;
+            }
+            '"' => {
+                let start = i;
+                let mut s = String::new();
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    s.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(DslError {
+                        message: "unterminated string literal".to_string(),
+                        span: start..i,
+                    });
+                }
+                i += 1; // closing quote
+                tokens.push(Token {
+                    kind: TokenKind::Str(s),
+                    span: start..i,
+                });
+            }
+            '<' => {
+                let start = i;
+                i += 1;
+                let class_start = i;
+                while i < chars.len() && chars[i] != '>' {
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(DslError {
+                        message: "unterminated character class, expected '>'".to_string(),
+                        span: start..i,
+                    });
+                }
+                let name: String = chars[class_start..i].iter().collect();
+                i += 1; // closing '>'
+                tokens.push(Token {
+                    kind: TokenKind::Class(name),
+                    span: start..i,
+                });
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let num = text.parse::<u32>().map_err(|_| DslError {
+                    message: format!("invalid quantifier count '{text}'"),
+                    span: start..i,
+                })?;
+                tokens.push(Token {
+                    kind: TokenKind::Num(num),
+                    span: start..i,
+                });
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                tokens.push(Token {
+                    kind: TokenKind::Ident(text),
+                    span: start..i,
+                });
+            }
+            other => {
+                return Err(DslError {
+                    message: format!("unexpected character '{other}'"),
+                    span: i..i + 1,
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+/// Recursive-descent parser over [`Token`]s -- one atom per iteration of
+/// [`Parser::parse_sequence`], mirroring [`crate::query::filter_dsl::Parser`].
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn eof_span(&self) -> Range<usize> {
+        self.tokens
+            .last()
+            .map(|t| t.span.end..t.span.end)
+            .unwrap_or(0..0)
+    }
+
+    fn expect(&mut self, expected: &TokenKind) -> Result<(), DslError> {
+        match self.advance() {
+            Some(tok) if &tok.kind == expected => Ok(()),
+            Some(tok) => Err(DslError {
+                message: format!("expected {expected:?}, found {:?}", tok.kind),
+                span: tok.span,
+            }),
+            None => Err(DslError {
+                message: format!("expected {expected:?}, found end of input"),
+                span: self.eof_span(),
+            }),
+        }
+    }
+
+    fn parse_sequence(&mut self, terminators: &[TokenKind]) -> Result<AtomExpr, DslError> {
+        let mut atoms = Vec::new();
+        while let Some(tok) = self.peek() {
+            if terminators.contains(&tok.kind) {
+                break;
+            }
+            atoms.push(self.parse_atom()?);
+        }
+        if atoms.is_empty() {
+            return Err(DslError {
+                message: "expected at least one pattern atom".to_string(),
+                span: self.eof_span(),
+            });
+        }
+        if atoms.len() == 1 {
+            Ok(atoms.into_iter().next().unwrap())
+        } else {
+            Ok(AtomExpr::Seq(atoms))
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<AtomExpr, DslError> {
+        let tok = self.advance().ok_or_else(|| DslError {
+            message: "expected a pattern atom, found end of input".to_string(),
+            span: self.eof_span(),
+        })?;
+        match tok.kind {
+            TokenKind::Str(s) => Ok(AtomExpr::Literal(s)),
+            TokenKind::Class(name) => match name.as_str() {
+                "word" => Ok(AtomExpr::CharClass("word")),
+                "digit" => Ok(AtomExpr::CharClass("digit")),
+                "space" => Ok(AtomExpr::CharClass("space")),
+                "upper" => Ok(AtomExpr::CharClass("upper")),
+                "lower" => Ok(AtomExpr::CharClass("lower")),
+                other => Err(DslError {
+                    message: format!(
+                        "unknown character class <{other}>; expected one of <word>, <digit>, <space>, <upper>, <lower>"
+                    ),
+                    span: tok.span,
+                }),
+            },
+            TokenKind::Num(count) => {
+                self.expect_ident("of", &tok.span)?;
+                let inner = self.parse_atom()?;
+                Ok(AtomExpr::Quantified(count, Box::new(inner)))
+            }
+            TokenKind::Ident(name) => match name.as_str() {
+                "any" => Ok(AtomExpr::Any),
+                "start" => Ok(AtomExpr::Start),
+                "end" => Ok(AtomExpr::End),
+                "either" => {
+                    self.expect_ident("of", &tok.span)?;
+                    self.expect(&TokenKind::LBrace)?;
+                    let mut branches = Vec::new();
+                    loop {
+                        branches.push(self.parse_sequence(&[TokenKind::Comma, TokenKind::RBrace])?);
+                        match self.peek().map(|t| t.kind.clone()) {
+                            Some(TokenKind::Comma) => {
+                                self.advance();
+                            }
+                            _ => break,
+                        }
+                    }
+                    self.expect(&TokenKind::RBrace)?;
+                    Ok(AtomExpr::Either(branches))
+                }
+                "capture" => {
+                    let name = match self.advance() {
+                        Some(Token {
+                            kind: TokenKind::Ident(name),
+                            ..
+                        }) => name,
+                        Some(other) => {
+                            return Err(DslError {
+                                message: format!(
+                                    "expected a capture name, found {:?}",
+                                    other.kind
+                                ),
+                                span: other.span,
+                            })
+                        }
+                        None => {
+                            return Err(DslError {
+                                message: "expected a capture name, found end of input".to_string(),
+                                span: self.eof_span(),
+                            })
+                        }
+                    };
+                    self.expect(&TokenKind::LBrace)?;
+                    let inner = self.parse_sequence(&[TokenKind::RBrace])?;
+                    self.expect(&TokenKind::RBrace)?;
+                    Ok(AtomExpr::Capture(name, Box::new(inner)))
+                }
+                other => Err(DslError {
+                    message: format!("unexpected keyword '{other}'"),
+                    span: tok.span,
+                }),
+            },
+            other => Err(DslError {
+                message: format!("unexpected token {other:?}"),
+                span: tok.span,
+            }),
+        }
+    }
+
+    fn expect_ident(&mut self, expected: &str, prev_span: &Range<usize>) -> Result<(), DslError> {
+        match self.advance() {
+            Some(Token {
+                kind: TokenKind::Ident(ref name),
+                ..
+            }) if name == expected => Ok(()),
+            Some(tok) => Err(DslError {
+                message: format!("expected '{expected}', found {:?}", tok.kind),
+                span: tok.span,
+            }),
+            None => Err(DslError {
+                message: format!("expected '{expected}', found end of input"),
+                span: prev_span.end..prev_span.end,
+            }),
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<AtomExpr, DslError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_sequence(&[])?;
+    if parser.pos != parser.tokens.len() {
+        let tok = &parser.tokens[parser.pos];
+        return Err(DslError {
+            message: "unexpected trailing input".to_string(),
+            span: tok.span.clone(),
+        });
+    }
+    Ok(expr)
+}
+
+// ---------------------------------------------------------------------------
+// Compiled pattern
+// ---------------------------------------------------------------------------
+
+/// A compiled symbol-name pattern: a [`regex::Regex`] plus the DSL/regex
+/// source it came from, for `__repr__`/diagnostics.
+#[derive(Debug, Clone)]
+pub struct CompiledPattern {
+    pub regex: Regex,
+    pub source: String,
+}
+
+/// Compiles `src` as [`search_dsl`](self) source into a [`CompiledPattern`].
+pub fn compile_dsl(src: &str) -> Result<CompiledPattern, DslError> {
+    let expr = parse(src)?;
+    let mut pattern = String::new();
+    render(&expr, &mut pattern);
+    let regex = Regex::new(&pattern).map_err(|e| DslError {
+        message: format!("compiled pattern {pattern:?} is not a valid regex: {e}"),
+        span: 0..src.len(),
+    })?;
+    Ok(CompiledPattern {
+        regex,
+        source: src.to_string(),
+    })
+}
+
+/// Compiles `src` as a raw `regex` crate pattern into a [`CompiledPattern`].
+pub fn compile_regex(src: &str) -> Result<CompiledPattern, DslError> {
+    let regex = Regex::new(src).map_err(|e| DslError {
+        message: format!("invalid regex {src:?}: {e}"),
+        span: 0..src.len(),
+    })?;
+    Ok(CompiledPattern {
+        regex,
+        source: src.to_string(),
+    })
+}
+
+/// Dispatches to [`compile_dsl`] or [`compile_regex`] by `mode` (`"dsl"` or
+/// `"regex"`).
+pub fn compile(src: &str, mode: &str) -> Result<CompiledPattern, DslError> {
+    match mode {
+        "dsl" => compile_dsl(src),
+        "regex" => compile_regex(src),
+        other => Err(DslError {
+            message: format!("unknown compile mode {other:?}; expected \"dsl\" or \"regex\""),
+            span: 0..src.len(),
+        }),
+    }
+}
+
+/// Compiles `request.query` per its `query_mode`, or returns `None` for
+/// `"text"` mode -- the caller should fall back to
+/// [`crate::query::search::search_symbols`]'s existing FTS/LIKE/fuzzy/BM25
+/// pipeline in that case, since plain free-text queries aren't pattern
+/// matches at all.
+pub fn compile_symbol_pattern(
+    request: &SymbolSearchRequest,
+) -> Result<Option<CompiledPattern>, DslError> {
+    match request.query_mode.as_str() {
+        "regex" => compile_regex(&request.query).map(Some),
+        "dsl" => compile_dsl(&request.query).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Python-facing wrapper around a [`CompiledPattern`], returned by
+/// [`compile_query`].
+#[pyclass]
+pub struct CompiledSymbolPattern {
+    compiled: CompiledPattern,
+}
+
+#[pymethods]
+impl CompiledSymbolPattern {
+    fn is_match(&self, text: &str) -> bool {
+        self.compiled.regex.is_match(text)
+    }
+
+    /// Named captures from matching `text`, as a `{name: value}` dict, or
+    /// `None` if the pattern doesn't match at all.
+    fn captures(&self, py: Python<'_>, text: &str) -> PyResult<Option<PyObject>> {
+        let Some(captures) = self.compiled.regex.captures(text) else {
+            return Ok(None);
+        };
+        let dict = PyDict::new(py);
+        for name in self.compiled.regex.capture_names().flatten() {
+            if let Some(value) = captures.name(name) {
+                dict.set_item(name, value.as_str())?;
+            }
+        }
+        Ok(Some(dict.into_any().unbind()))
+    }
+
+    fn pattern(&self) -> String {
+        self.compiled.regex.as_str().to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CompiledSymbolPattern(source={:?}, pattern={:?})",
+            self.compiled.source,
+            self.compiled.regex.as_str()
+        )
+    }
+}
+
+/// Compiles `src` (DSL or raw regex source, per `mode`) into a
+/// [`CompiledSymbolPattern`] ready to match symbol names -- the entry point
+/// [`crate::query::search_dsl`] exists to provide.
+#[pyfunction]
+#[pyo3(signature = (src, mode="dsl"))]
+pub fn compile_query(src: &str, mode: &str) -> PyResult<CompiledSymbolPattern> {
+    let compiled = compile(src, mode)?;
+    Ok(CompiledSymbolPattern { compiled })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiles_quantified_literal() {
+        let compiled = compile_dsl(r#"3 of "get""#).unwrap();
+        assert!(compiled.regex.is_match("getgetget_user"));
+        assert!(!compiled.regex.is_match("get_user"));
+    }
+
+    #[test]
+    fn compiles_char_class_sugar() {
+        let compiled = compile_dsl("<word>").unwrap();
+        assert!(compiled.regex.is_match("x"));
+    }
+
+    #[test]
+    fn compiles_anchors_and_any() {
+        let compiled = compile_dsl(r#"start "get" any end"#).unwrap();
+        assert!(compiled.regex.is_match("getX"));
+        assert!(!compiled.regex.is_match("getXY"));
+    }
+
+    #[test]
+    fn compiles_alternation() {
+        let compiled = compile_dsl(r#"either of { "get", "set", "is" }"#).unwrap();
+        assert!(compiled.regex.is_match("get"));
+        assert!(compiled.regex.is_match("set"));
+        assert!(!compiled.regex.is_match("has"));
+    }
+
+    #[test]
+    fn named_captures_surface_in_matches() {
+        let compiled = compile_dsl(r#""get" capture field { <word> }"#).unwrap();
+        let captures = compiled.regex.captures("getX").unwrap();
+        assert_eq!(captures.name("field").unwrap().as_str(), "X");
+    }
+
+    #[test]
+    fn rejects_unknown_char_class_with_a_span() {
+        let err = compile_dsl("<bogus>").unwrap_err();
+        assert_eq!(err.span, 0..7);
+    }
+
+    #[test]
+    fn rejects_unterminated_string_literal() {
+        let err = compile_dsl(r#""get"#).unwrap_err();
+        assert!(err.message.contains("unterminated"));
+    }
+
+    #[test]
+    fn literals_are_escaped_not_interpreted_as_regex() {
+        let compiled = compile_dsl(r#""a.b""#).unwrap();
+        assert!(compiled.regex.is_match("a.b"));
+        assert!(!compiled.regex.is_match("aXb"));
+    }
+
+    #[test]
+    fn raw_regex_mode_bypasses_the_dsl_grammar() {
+        let compiled = compile(r"^get_\w+$", "regex").unwrap();
+        assert!(compiled.regex.is_match("get_user"));
+    }
+
+    #[test]
+    fn compile_symbol_pattern_returns_none_for_text_mode() {
+        let request = SymbolSearchRequest::new(
+            "get".to_string(),
+            "any".to_string(),
+            None,
+            20,
+            "text".to_string(),
+        )
+        .unwrap();
+        assert!(compile_symbol_pattern(&request).unwrap().is_none());
+    }
+}
@@ -0,0 +1,365 @@
+//! In-process approximate nearest-neighbor index over symbol embeddings.
+//!
+//! Backs semantic seed retrieval in [`crate::query::context`]: lexical
+//! seeding (FTS5 + LIKE) misses queries that use different vocabulary than
+//! the code (e.g. "deduplicate rows" vs a function named `collapse_duplicates`).
+//! When a `symbol_embeddings` table is populated, this builds a small HNSW-style
+//! graph over the stored vectors so cosine-similarity search stays sub-linear
+//! on large repos, instead of scanning every row.
+
+use std::collections::{BinaryHeap, HashMap};
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+
+/// `SemanticIndex::search`'s `ef` for `semantic_search` — wider than
+/// `query::context`'s `SEMANTIC_SEARCH_EF` since this is the only seed
+/// source (no lexical fallback to fuse with), so a few extra candidates
+/// visited per query buys a better chance the true top-`limit` survive the
+/// greedy descent.
+const SEARCH_EF: usize = 96;
+
+/// One node's vector plus its neighbor list per layer, as in HNSW: higher
+/// layers are sparser "express lanes", layer 0 holds every node.
+struct IndexNode {
+    symbol_id: i64,
+    vector: Vec<f32>,
+    /// `neighbors[layer]` = ids of this node's neighbors at that layer.
+    neighbors: Vec<Vec<i64>>,
+}
+
+/// A lightweight HNSW-style approximate index over symbol embedding vectors.
+pub struct SemanticIndex {
+    nodes: HashMap<i64, IndexNode>,
+    entry_point: Option<i64>,
+    max_layer: usize,
+    /// Max neighbors kept per node per layer (HNSW's `M`).
+    m: usize,
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b.iter()) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+pub(crate) fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+/// Inverse of [`decode_vector`] — the little-endian f32 blob layout
+/// `symbol_embeddings.vector` is stored in (see the column's doc comment in
+/// `store::schema`).
+pub(crate) fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Candidate ordered by similarity, for the bounded `ef`-sized priority queue
+/// used during greedy descent.
+#[derive(PartialEq)]
+struct Candidate {
+    similarity: f32,
+    symbol_id: i64,
+}
+impl Eq for Candidate {}
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.similarity
+            .partial_cmp(&other.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+impl SemanticIndex {
+    /// Load all rows from `symbol_embeddings` and build the graph. Returns
+    /// `Ok(None)` (rather than an error) when the table doesn't exist yet,
+    /// so callers can fall back to lexical-only seeding silently.
+    pub fn build(conn: &Connection, model: &str) -> BombeResult<Option<Self>> {
+        let table_exists: bool = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'symbol_embeddings';",
+                [],
+                |_| Ok(true),
+            )
+            .unwrap_or(false);
+        if !table_exists {
+            return Ok(None);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT symbol_id, vector FROM symbol_embeddings WHERE model = ?1;",
+        )?;
+        let rows: Vec<(i64, Vec<u8>)> = stmt
+            .query_map(rusqlite::params![model], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let m = 8;
+        let mut index = SemanticIndex {
+            nodes: HashMap::new(),
+            entry_point: None,
+            max_layer: 0,
+            m,
+        };
+
+        for (symbol_id, blob) in rows {
+            index.insert(symbol_id, decode_vector(&blob));
+        }
+
+        Ok(Some(index))
+    }
+
+    /// Insert a vector, assigning it a random layer with exponential decay
+    /// (the usual HNSW level-assignment heuristic) and greedily wiring it to
+    /// its nearest already-inserted neighbors at each layer.
+    fn insert(&mut self, symbol_id: i64, vector: Vec<f32>) {
+        // Deterministic pseudo-random layer assignment keyed on symbol_id,
+        // since this index is rebuilt per-process and doesn't need a true RNG.
+        let mut layer = 0usize;
+        let mut seed = (symbol_id as u64).wrapping_mul(2654435761);
+        while seed % 4 == 0 && layer < 4 {
+            layer += 1;
+            seed = seed.wrapping_mul(2654435761).wrapping_add(1);
+        }
+
+        let neighbors = vec![Vec::new(); layer + 1];
+        let new_node = IndexNode {
+            symbol_id,
+            vector: vector.clone(),
+            neighbors,
+        };
+
+        if self.entry_point.is_none() {
+            self.entry_point = Some(symbol_id);
+            self.max_layer = layer;
+            self.nodes.insert(symbol_id, new_node);
+            return;
+        }
+
+        // Connect the new node to its nearest existing neighbors at every
+        // layer it participates in (bounded by `m` per layer), wiring the
+        // edge in both directions.
+        let mut new_node = new_node;
+        for l in 0..=layer.min(self.max_layer) {
+            let mut scored: Vec<(f32, i64)> = self
+                .nodes
+                .values()
+                .filter(|n| n.neighbors.len() > l)
+                .map(|n| (cosine_similarity(&vector, &n.vector), n.symbol_id))
+                .collect();
+            scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+            let picked: Vec<i64> = scored.into_iter().take(self.m).map(|(_, id)| id).collect();
+
+            for &neighbor_id in &picked {
+                if let Some(n) = self.nodes.get_mut(&neighbor_id) {
+                    if n.neighbors.len() > l {
+                        n.neighbors[l].push(symbol_id);
+                        n.neighbors[l].truncate(self.m);
+                    }
+                }
+            }
+            new_node.neighbors[l] = picked;
+        }
+
+        if layer > self.max_layer {
+            self.max_layer = layer;
+            self.entry_point = Some(symbol_id);
+        }
+        self.nodes.insert(symbol_id, new_node);
+    }
+
+    /// Greedy descent from the entry point, maintaining a bounded candidate
+    /// set of size `ef`, returning the top `top_k` symbols by cosine
+    /// similarity to `query_vector`.
+    pub fn search(&self, query_vector: &[f32], top_k: usize, ef: usize) -> Vec<(i64, f32)> {
+        let Some(entry) = self.entry_point else {
+            return Vec::new();
+        };
+
+        let mut visited: std::collections::HashSet<i64> = std::collections::HashSet::new();
+        let mut candidates: BinaryHeap<Candidate> = BinaryHeap::new();
+        let entry_similarity = self
+            .nodes
+            .get(&entry)
+            .map(|n| cosine_similarity(query_vector, &n.vector))
+            .unwrap_or(0.0);
+        candidates.push(Candidate {
+            similarity: entry_similarity,
+            symbol_id: entry,
+        });
+        visited.insert(entry);
+
+        let mut best: Vec<(i64, f32)> = Vec::new();
+
+        while let Some(Candidate { similarity, symbol_id }) = candidates.pop() {
+            best.push((symbol_id, similarity));
+            if let Some(node) = self.nodes.get(&symbol_id) {
+                for layer_neighbors in &node.neighbors {
+                    for &neighbor_id in layer_neighbors {
+                        if visited.insert(neighbor_id) {
+                            if let Some(n) = self.nodes.get(&neighbor_id) {
+                                let sim = cosine_similarity(query_vector, &n.vector);
+                                candidates.push(Candidate {
+                                    similarity: sim,
+                                    symbol_id: neighbor_id,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            if visited.len() >= ef {
+                break;
+            }
+        }
+
+        best.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        best.truncate(top_k);
+        best
+    }
+}
+
+/// Fuse lexical and semantic seed lists into one ordered candidate set,
+/// weighting each source by `semantic_weight` (0.0 = lexical only, 1.0 =
+/// semantic only) and deduplicating by symbol id, keeping the best rank.
+pub fn fuse_seeds(
+    lexical: &[i64],
+    semantic: &[(i64, f32)],
+    semantic_weight: f64,
+) -> Vec<i64> {
+    let mut scores: HashMap<i64, f64> = HashMap::new();
+    let lexical_weight = 1.0 - semantic_weight;
+    for (rank, &id) in lexical.iter().enumerate() {
+        let score = lexical_weight / (1.0 + rank as f64);
+        *scores.entry(id).or_insert(0.0) += score;
+    }
+    for (rank, &(id, _)) in semantic.iter().enumerate() {
+        let score = semantic_weight / (1.0 + rank as f64);
+        *scores.entry(id).or_insert(0.0) += score;
+    }
+    let mut ordered: Vec<(i64, f64)> = scores.into_iter().collect();
+    ordered.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ordered.into_iter().map(|(id, _)| id).collect()
+}
+
+/// Rank stored symbol embeddings by cosine similarity to `query_vector`,
+/// returning the same node shape as `query::data_flow::trace_data_flow`
+/// (`id`/`name`/`qualified_name`/`file_path`/`role`) plus a `similarity`
+/// score, so callers can splice semantic hits into the same downstream
+/// rendering as a data-flow trace. `role` is always `"match"` — unlike
+/// `trace_data_flow`'s upstream/downstream edges, these are independent
+/// candidates with no relationship to each other.
+pub fn semantic_search_impl(
+    conn: &Connection,
+    query_vector: &[f32],
+    model: &str,
+    limit: i64,
+) -> BombeResult<serde_json::Value> {
+    let Some(index) = SemanticIndex::build(conn, model)? else {
+        return Ok(serde_json::json!({ "model": model, "nodes": [] }));
+    };
+
+    let top_k = limit.max(1) as usize;
+    let hits = index.search(query_vector, top_k, SEARCH_EF.max(top_k * 2));
+
+    let mut nodes: Vec<serde_json::Value> = Vec::new();
+    for (symbol_id, similarity) in hits {
+        let row = conn.query_row(
+            "SELECT id, name, qualified_name, file_path FROM symbols WHERE id = ?1;",
+            rusqlite::params![symbol_id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            },
+        );
+        if let Ok((id, name, qualified_name, file_path)) = row {
+            nodes.push(serde_json::json!({
+                "id": id,
+                "name": name,
+                "qualified_name": qualified_name,
+                "file_path": file_path,
+                "role": "match",
+                "similarity": similarity,
+            }));
+        }
+    }
+
+    Ok(serde_json::json!({ "model": model, "nodes": nodes }))
+}
+
+/// Embed `query` via `provider` (see [`crate::indexer::embedding`]) and rank
+/// stored symbol embeddings against it by cosine similarity. `provider` is
+/// an `http(s)://` endpoint or a local ONNX/GGUF model path, resolved the
+/// same way on every call — there's no cached "default provider" the way
+/// `query::bpe_tokenizer` caches encoders, since an embedding model is much
+/// more expensive to misconfigure silently than a tokenizer table.
+#[pyfunction]
+#[pyo3(signature = (db, query, limit=10, model="default", provider=""))]
+pub fn semantic_search(
+    py: Python<'_>,
+    db: &crate::store::database::Database,
+    query: &str,
+    limit: i64,
+    model: &str,
+    provider: &str,
+) -> PyResult<PyObject> {
+    let conn = db.connect_internal()?;
+    let embedder = crate::indexer::embedding::resolve_provider(provider)?;
+    let query_vector = embedder.embed(query)?;
+    let result = semantic_search_impl(&conn, &query_vector, model, limit)?;
+    let json_str = serde_json::to_string(&result)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let json_module = py.import("json")?;
+    json_module
+        .call_method1("loads", (json_str,))
+        .map(|o| o.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_similarity_identical_vectors() {
+        let a = vec![1.0, 0.0, 0.0];
+        assert!((cosine_similarity(&a, &a) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fuse_seeds_prefers_overlap() {
+        let lexical = vec![1, 2, 3];
+        let semantic = vec![(2, 0.9), (4, 0.8)];
+        let fused = fuse_seeds(&lexical, &semantic, 0.5);
+        assert!(fused.contains(&2));
+        assert_eq!(fused[0], 2);
+    }
+}
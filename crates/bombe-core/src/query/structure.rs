@@ -6,20 +6,31 @@ use pyo3::prelude::*;
 use rusqlite::Connection;
 
 use crate::errors::BombeResult;
-
-fn approx_tokens(text: &str) -> i64 {
-    if text.is_empty() {
-        return 0;
-    }
-    (text.len() as f64 / 3.5).max(1.0) as i64
-}
+use crate::query::bpe_tokenizer::load_cached as load_bpe_encoder;
+use crate::query::tokenizer::estimate_tokens;
 
 pub fn get_structure_impl(
     conn: &Connection,
     path: &str,
     token_budget: i64,
     include_signatures: bool,
+    tokenizer: &str,
+    file_order: &str,
 ) -> BombeResult<String> {
+    // Same resolve-once-reuse-per-line approach as `query::context::get_context`:
+    // load the named encoder (if any) up front rather than per candidate line,
+    // falling back to the byte-length heuristic when `tokenizer` is empty or
+    // names an encoding with no resolvable merge table.
+    let bpe_encoder = if tokenizer.is_empty() {
+        None
+    } else {
+        load_bpe_encoder(tokenizer)
+    };
+    let count_tokens = |text: &str| match &bpe_encoder {
+        Some(encoder) => encoder.count_tokens(text),
+        None => estimate_tokens(text, None),
+    };
+
     let path_like = if path.is_empty() || path == "." {
         "%".to_string()
     } else {
@@ -37,6 +48,10 @@ pub fn get_structure_impl(
          ORDER BY pagerank_score DESC, file_path ASC, start_line ASC;",
     )?;
 
+    // Already ordered pagerank descending by the query, so `rank` below
+    // reflects each symbol's global importance regardless of which file it
+    // lives in (rather than an alphabetical-emission-order rank, which would
+    // make the `[TOP]` marker and `token_budget` cutoff disagree).
     let rows: Vec<(String, String, String, Option<String>, f64)> = stmt
         .query_map(rusqlite::params![path_like], |row| {
             Ok((
@@ -50,53 +65,89 @@ pub fn get_structure_impl(
         .filter_map(|r| r.ok())
         .collect();
 
-    let mut grouped: BTreeMap<String, Vec<(String, String, String, f64)>> = BTreeMap::new();
-    for (file_path, name, kind, signature, pagerank) in rows {
-        grouped.entry(file_path).or_default().push((
-            name,
-            kind,
-            signature.unwrap_or_default(),
-            pagerank,
-        ));
-    }
+    // Greedily admit symbols in pagerank order, charging the one-time cost
+    // of a file-header line the first time any symbol from that file is
+    // admitted. A symbol too big to fit is skipped (not a hard cutoff) so a
+    // smaller, lower-ranked symbol further down can still use the remaining
+    // budget.
+    let mut admitted: Vec<(String, String, String, String, usize)> = Vec::new();
+    let mut admitted_files: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut used_tokens = 0i64;
+    for (rank, (file_path, name, kind, signature, _pagerank)) in rows.into_iter().enumerate() {
+        let signature = signature.unwrap_or_default();
+        let detail = if include_signatures && !signature.is_empty() {
+            signature
+        } else {
+            format!("{kind} {name}")
+        };
+        let marker = if rank < 10 { "[TOP] " } else { "" };
+        let line = format!("  {marker}{detail}  [rank:{}]", rank + 1);
 
-    let mut lines: Vec<String> = Vec::new();
-    let mut rank = 0;
-    for (file_path, symbols) in &grouped {
-        lines.push(file_path.clone());
-        for (name, kind, signature, _score) in symbols {
-            rank += 1;
-            let marker = if rank <= 10 { "[TOP] " } else { "" };
-            let detail = if include_signatures && !signature.is_empty() {
-                signature.clone()
-            } else {
-                format!("{kind} {name}")
-            };
-            lines.push(format!("  {marker}{detail}  [rank:{rank}]"));
+        let header_cost = if admitted_files.contains(&file_path) {
+            0
+        } else {
+            count_tokens(&file_path)
+        };
+        let line_cost = count_tokens(&line);
+        if used_tokens + header_cost + line_cost > token_budget {
+            continue;
         }
+        used_tokens += header_cost + line_cost;
+        admitted_files.insert(file_path.clone());
+        admitted.push((file_path, name, kind, line, rank));
     }
 
+    // Regroup admitted symbols by file so each header is emitted once.
+    // `file_order` picks whether files appear alphabetically (the prior,
+    // default behaviour) or in the order their first symbol was admitted
+    // (highest combined pagerank first).
     let mut output_lines: Vec<String> = Vec::new();
-    let mut used_tokens = 0i64;
-    for line in &lines {
-        let line_tokens = approx_tokens(line);
-        if used_tokens + line_tokens > token_budget {
-            break;
+    if file_order == "admission" {
+        let mut order: Vec<String> = Vec::new();
+        let mut by_file: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
+        for (file_path, _name, _kind, line, _rank) in admitted {
+            if !by_file.contains_key(&file_path) {
+                order.push(file_path.clone());
+            }
+            by_file.entry(file_path).or_default().push(line);
+        }
+        for file_path in order {
+            output_lines.push(file_path.clone());
+            output_lines.extend(by_file.remove(&file_path).unwrap_or_default());
+        }
+    } else {
+        let mut by_file: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (file_path, _name, _kind, line, _rank) in admitted {
+            by_file.entry(file_path).or_default().push(line);
+        }
+        for (file_path, lines) in by_file {
+            output_lines.push(file_path);
+            output_lines.extend(lines);
         }
-        output_lines.push(line.clone());
-        used_tokens += line_tokens;
     }
 
     Ok(output_lines.join("\n"))
 }
 
+/// `tokenizer` selects a real BPE encoding (`"cl100k"`, `"o200k"`) to count
+/// tokens against `token_budget` exactly as that model would; empty (the
+/// default) keeps the cheap byte-length heuristic. See
+/// [`crate::query::bpe_tokenizer`]. Symbols are selected globally by
+/// `pagerank_score` so the highest-value symbols across the whole subtree
+/// survive the budget even when they live in scattered files; `file_order`
+/// then controls how the admitted files are grouped for display —
+/// `"alphabetical"` (default) or `"admission"` (highest combined pagerank
+/// first).
 #[pyfunction]
-#[pyo3(signature = (db, path=".", token_budget=4000, include_signatures=true))]
+#[pyo3(signature = (db, path=".", token_budget=4000, include_signatures=true, tokenizer="", file_order="alphabetical"))]
 pub fn get_structure(
     db: &crate::store::database::Database,
     path: &str,
     token_budget: i64,
     include_signatures: bool,
+    tokenizer: &str,
+    file_order: &str,
 ) -> PyResult<String> {
     let conn = db.connect_internal()?;
     Ok(get_structure_impl(
@@ -104,5 +155,7 @@ pub fn get_structure(
         path,
         token_budget,
         include_signatures,
+        tokenizer,
+        file_order,
     )?)
 }
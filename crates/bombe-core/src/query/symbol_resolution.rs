@@ -0,0 +1,160 @@
+//! Typo-tolerant symbol resolution, shared by the exact-match
+//! `resolve_symbol`/`resolve_symbol_id` helpers scattered across the query
+//! backends (`references.rs`, `blast.rs`, `data_flow.rs`, `call_path.rs`).
+//!
+//! Those helpers only try an exact `qualified_name` match, then an exact
+//! `name` match, and give up with "Symbol not found" the moment both miss —
+//! no help for a misspelled or partial name. [`resolve_symbol_fuzzy`] is the
+//! fallback [`get_references_impl`](crate::query::references::get_references_impl)
+//! and [`get_blast_radius_impl`](crate::query::blast::get_blast_radius_impl)
+//! reach for once their own exact lookups come up empty: pre-filter to
+//! symbols whose `name` length is within ±2 of the query (cheap in SQL,
+//! before paying for Levenshtein), compute
+//! [`crate::store::fuzzy::edit_distance`] in Rust, keep candidates within a
+//! length-scaled threshold (2 for short names, 3 for longer), and rank
+//! survivors by `(1 / (1 + edit_distance)) * normalized_pagerank_score`,
+//! with a bonus for a prefix match. [`suggest_symbols_impl`] exposes the
+//! same ranking standalone, so an agent can see the candidate list and
+//! disambiguate before paying for an expensive traversal on the wrong
+//! symbol.
+
+use std::cmp::Ordering;
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+use crate::store::fuzzy::edit_distance;
+
+/// A fuzzy-resolved symbol, ranked by [`suggest_symbols_impl`]/
+/// [`resolve_symbol_fuzzy`].
+#[derive(Clone, Debug)]
+pub struct SymbolCandidate {
+    pub id: i64,
+    pub name: String,
+    pub qualified_name: String,
+    pub file_path: String,
+    pub edit_distance: usize,
+    pub score: f64,
+}
+
+/// Max edit distance a candidate may sit at: short names tolerate fewer
+/// typos before they'd just as plausibly match something unrelated.
+fn typo_threshold(name_len: usize) -> usize {
+    if name_len <= 8 {
+        2
+    } else {
+        3
+    }
+}
+
+/// Rank every symbol within a length-bounded edit distance of `query`,
+/// highest score first. Distance ties break by score, then by shorter
+/// distance, then by id for determinism.
+pub fn suggest_symbols_impl(
+    conn: &Connection,
+    query: &str,
+    limit: i64,
+) -> BombeResult<Vec<SymbolCandidate>> {
+    let query_lower = query.to_lowercase();
+    let query_len = query_lower.chars().count() as i64;
+    let low = (query_len - 2).max(0);
+    let high = query_len + 2;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, qualified_name, file_path, pagerank_score FROM symbols \
+         WHERE LENGTH(name) BETWEEN ?1 AND ?2;",
+    )?;
+    let rows: Vec<(i64, String, String, String, Option<f64>)> = stmt
+        .query_map(rusqlite::params![low, high], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let max_pagerank = rows
+        .iter()
+        .filter_map(|(_, _, _, _, pr)| *pr)
+        .fold(0.0_f64, f64::max);
+
+    let mut candidates: Vec<SymbolCandidate> = Vec::new();
+    for (id, name, qualified_name, file_path, pagerank) in rows {
+        let name_lower = name.to_lowercase();
+        let threshold = typo_threshold(name_lower.chars().count());
+        let Some(distance) = edit_distance(&query_lower, &name_lower, Some(threshold)) else {
+            continue;
+        };
+        let normalized_pagerank = if max_pagerank > 0.0 {
+            pagerank.unwrap_or(0.0) / max_pagerank
+        } else {
+            0.0
+        };
+        let mut score = (1.0 / (1.0 + distance as f64)) * normalized_pagerank;
+        if name_lower.starts_with(&query_lower) {
+            score += 0.1;
+        }
+        candidates.push(SymbolCandidate {
+            id,
+            name,
+            qualified_name,
+            file_path,
+            edit_distance: distance,
+            score,
+        });
+    }
+
+    candidates.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then(a.edit_distance.cmp(&b.edit_distance))
+            .then(a.id.cmp(&b.id))
+    });
+    candidates.truncate(limit.max(0) as usize);
+    Ok(candidates)
+}
+
+/// The single best fuzzy match for `query`, or `None` if nothing falls
+/// within the length/edit-distance bounds [`suggest_symbols_impl`] applies.
+pub fn resolve_symbol_fuzzy(
+    conn: &Connection,
+    query: &str,
+) -> BombeResult<Option<SymbolCandidate>> {
+    Ok(suggest_symbols_impl(conn, query, 1)?.into_iter().next())
+}
+
+fn candidate_json(candidate: &SymbolCandidate) -> serde_json::Value {
+    serde_json::json!({
+        "id": candidate.id,
+        "name": candidate.name,
+        "qualified_name": candidate.qualified_name,
+        "file_path": candidate.file_path,
+        "edit_distance": candidate.edit_distance,
+        "score": candidate.score,
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (db, query, limit=10))]
+pub fn suggest_symbols(
+    py: Python<'_>,
+    db: &crate::store::database::Database,
+    query: &str,
+    limit: i64,
+) -> PyResult<PyObject> {
+    let conn = db.connect_internal()?;
+    let candidates = suggest_symbols_impl(&conn, query, limit)?;
+    let result: Vec<serde_json::Value> = candidates.iter().map(candidate_json).collect();
+    let json_str = serde_json::to_string(&result)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let json_module = py.import("json")?;
+    json_module
+        .call_method1("loads", (json_str,))
+        .map(|o| o.into())
+}
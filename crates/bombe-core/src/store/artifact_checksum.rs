@@ -0,0 +1,316 @@
+//! Canonical checksum + Ed25519 signing for [`crate::models::ArtifactBundle`].
+//!
+//! `ArtifactBundle` carries `signature_algo`/`signing_key_id`/`checksum`/
+//! `signature` fields but nothing populated or validated them until now.
+//! The checksum is a SHA-256 digest over a canonical byte serialization of
+//! everything *except* those four fields — `promoted_symbols` sorted by
+//! `SymbolKey` identity, `promoted_edges` sorted by `(source, target,
+//! relationship)`, and the `impact_priors`/`flow_hints` JSON payloads
+//! serialized with lexicographically sorted object keys — so two
+//! semantically identical bundles hash identically regardless of Python
+//! dict/list insertion order. Keys and signatures are hex-encoded crossing
+//! the Python boundary, consistent with [`crate::store::signing`] (itself
+//! matching the hex digest convention from
+//! `indexer::filesystem::compute_content_hash`) rather than introducing a
+//! second binary-to-text encoding into this crate.
+
+use ed25519_dalek::{Signer as _, SigningKey, Verifier as _};
+use pyo3::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::errors::{BombeError, BombeResult};
+use crate::models::{ArtifactBundle, EdgeContractRecord, SymbolKey};
+use crate::store::signing::{verify_one, TrustedKey};
+
+/// Append `bytes` to `buf` with a big-endian `u64` length prefix, so no
+/// field boundary in the concatenated byte stream is ambiguous.
+fn write_framed(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+/// `(qualified_name, file_path, start_line, end_line, signature_hash)` — the
+/// same fields [`SymbolKey::__eq__`] compares, used both to sort
+/// `promoted_symbols`/edge endpoints and to frame them into the checksum
+/// input deterministically.
+fn symbol_key_sort_tuple(key: &SymbolKey) -> (String, String, i64, i64, String) {
+    (
+        key.qualified_name.clone(),
+        key.file_path.clone(),
+        key.start_line,
+        key.end_line,
+        key.signature_hash.clone(),
+    )
+}
+
+fn write_symbol_key(buf: &mut Vec<u8>, key: &SymbolKey) {
+    write_framed(buf, key.qualified_name.as_bytes());
+    write_framed(buf, key.file_path.as_bytes());
+    buf.extend_from_slice(&key.start_line.to_be_bytes());
+    buf.extend_from_slice(&key.end_line.to_be_bytes());
+    write_framed(buf, key.signature_hash.as_bytes());
+}
+
+fn write_edge(buf: &mut Vec<u8>, edge: &EdgeContractRecord) {
+    write_symbol_key(buf, &edge.source);
+    write_symbol_key(buf, &edge.target);
+    write_framed(buf, edge.relationship.as_bytes());
+}
+
+/// Recursively convert a Python value into a [`serde_json::Value`].
+/// Containers (list/tuple/dict) recurse; anything this module doesn't model
+/// (custom objects) falls back to its `str()` so serialization never fails
+/// outright on an unexpected payload shape.
+fn pyany_to_json(obj: &Bound<'_, PyAny>) -> BombeResult<serde_json::Value> {
+    if obj.is_none() {
+        return Ok(serde_json::Value::Null);
+    }
+    if let Ok(v) = obj.extract::<bool>() {
+        return Ok(serde_json::Value::Bool(v));
+    }
+    if let Ok(v) = obj.extract::<i64>() {
+        return Ok(serde_json::Value::Number(v.into()));
+    }
+    if let Ok(v) = obj.extract::<f64>() {
+        return Ok(serde_json::Number::from_f64(v)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null));
+    }
+    if let Ok(v) = obj.extract::<String>() {
+        return Ok(serde_json::Value::String(v));
+    }
+    if let Ok(list) = obj.downcast::<pyo3::types::PyList>() {
+        let items = list
+            .iter()
+            .map(|item| pyany_to_json(&item))
+            .collect::<BombeResult<Vec<_>>>()?;
+        return Ok(serde_json::Value::Array(items));
+    }
+    if let Ok(tuple) = obj.downcast::<pyo3::types::PyTuple>() {
+        let items = tuple
+            .iter()
+            .map(|item| pyany_to_json(&item))
+            .collect::<BombeResult<Vec<_>>>()?;
+        return Ok(serde_json::Value::Array(items));
+    }
+    if let Ok(dict) = obj.downcast::<pyo3::types::PyDict>() {
+        // `serde_json::Map` defaults to a `BTreeMap` (this crate doesn't
+        // enable the `preserve_order` feature), so building the object from
+        // an arbitrary-order dict here still serializes with keys sorted
+        // lexicographically — exactly the canonical form this checksum needs.
+        let mut map = serde_json::Map::new();
+        for (k, v) in dict.iter() {
+            let key = k.extract::<String>().unwrap_or_else(|_| k.to_string());
+            map.insert(key, pyany_to_json(&v)?);
+        }
+        return Ok(serde_json::Value::Object(map));
+    }
+    Ok(serde_json::Value::String(obj.to_string()))
+}
+
+fn canonical_json_bytes(obj: &Bound<'_, PyAny>) -> BombeResult<Vec<u8>> {
+    let value = pyany_to_json(obj)?;
+    serde_json::to_vec(&value).map_err(BombeError::Json)
+}
+
+/// Build the canonical byte serialization of `bundle`'s content — every
+/// field except `signature_algo`/`signing_key_id`/`checksum`/`signature`.
+fn canonical_content_bytes(py: Python<'_>, bundle: &ArtifactBundle) -> BombeResult<Vec<u8>> {
+    let mut buf = Vec::new();
+
+    write_framed(&mut buf, bundle.artifact_id.as_bytes());
+    write_framed(&mut buf, bundle.repo_id.as_bytes());
+    write_framed(&mut buf, bundle.snapshot_id.as_bytes());
+    write_framed(
+        &mut buf,
+        bundle.parent_snapshot.as_deref().unwrap_or("").as_bytes(),
+    );
+    write_framed(&mut buf, bundle.tool_version.as_bytes());
+    buf.extend_from_slice(&bundle.schema_version.to_be_bytes());
+    write_framed(&mut buf, bundle.created_at_utc.as_bytes());
+
+    let mut symbols: Vec<&SymbolKey> = bundle.promoted_symbols.iter().collect();
+    symbols.sort_by_key(|k| symbol_key_sort_tuple(k));
+    buf.extend_from_slice(&(symbols.len() as u64).to_be_bytes());
+    for key in symbols {
+        write_symbol_key(&mut buf, key);
+    }
+
+    let mut edges: Vec<&EdgeContractRecord> = bundle.promoted_edges.iter().collect();
+    edges.sort_by_key(|e| {
+        (
+            symbol_key_sort_tuple(&e.source),
+            symbol_key_sort_tuple(&e.target),
+            e.relationship.clone(),
+        )
+    });
+    buf.extend_from_slice(&(edges.len() as u64).to_be_bytes());
+    for edge in edges {
+        write_edge(&mut buf, edge);
+    }
+
+    let impact_priors = bundle.impact_priors.bind(py);
+    write_framed(&mut buf, &canonical_json_bytes(impact_priors)?);
+    let flow_hints = bundle.flow_hints.bind(py);
+    write_framed(&mut buf, &canonical_json_bytes(flow_hints)?);
+
+    Ok(buf)
+}
+
+/// Compute the SHA-256 hex digest of `bundle`'s canonical content bytes.
+pub fn compute_checksum(py: Python<'_>, bundle: &ArtifactBundle) -> BombeResult<String> {
+    let bytes = canonical_content_bytes(py, bundle)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Recompute `bundle`'s checksum and sign the 32-byte digest with Ed25519,
+/// returning `(checksum_hex, signature_hex)`. `private_key_hex` is a
+/// hex-encoded 32-byte Ed25519 seed.
+pub fn sign(
+    py: Python<'_>,
+    bundle: &ArtifactBundle,
+    private_key_hex: &str,
+) -> BombeResult<(String, String)> {
+    let checksum = compute_checksum(py, bundle)?;
+    let seed_bytes = hex::decode(private_key_hex)
+        .map_err(|e| BombeError::Parse(format!("invalid ed25519 private key hex: {e}")))?;
+    let seed: [u8; 32] = seed_bytes
+        .try_into()
+        .map_err(|_| BombeError::Parse("ed25519 private key must be 32 bytes".to_string()))?;
+    let signing_key = SigningKey::from_bytes(&seed);
+    let digest = hex::decode(&checksum)
+        .map_err(|e| BombeError::Parse(format!("invalid checksum hex: {e}")))?;
+    let signature = signing_key.sign(&digest);
+    Ok((checksum, hex::encode(signature.to_bytes())))
+}
+
+/// Recompute `bundle`'s checksum and verify it against `bundle.checksum`,
+/// then verify `bundle.signature` over the checksum digest under
+/// `public_key_hex` (a hex-encoded 32-byte Ed25519 public key). Returns
+/// `false` (rather than erroring) on a checksum mismatch or missing
+/// signature fields, since "not verified" is the caller-relevant outcome
+/// either way.
+pub fn verify(py: Python<'_>, bundle: &ArtifactBundle, public_key_hex: &str) -> BombeResult<bool> {
+    let (Some(signature_algo), Some(checksum), Some(signature)) = (
+        bundle.signature_algo.as_deref(),
+        bundle.checksum.as_deref(),
+        bundle.signature.as_deref(),
+    ) else {
+        return Ok(false);
+    };
+    if signature_algo != "ed25519" {
+        return Err(BombeError::Query(format!(
+            "ArtifactBundle.verify: unsupported signature_algo '{signature_algo}'"
+        )));
+    }
+
+    let recomputed = compute_checksum(py, bundle)?;
+    if recomputed != checksum {
+        return Ok(false);
+    }
+
+    let digest = hex::decode(checksum)
+        .map_err(|e| BombeError::Parse(format!("invalid checksum hex: {e}")))?;
+    let trusted_key = TrustedKey {
+        key_id: bundle.signing_key_id.clone().unwrap_or_default(),
+        algorithm: "ed25519".to_string(),
+        public_key_hex: public_key_hex.to_string(),
+    };
+    verify_one(&trusted_key, &digest, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_bundle(py: Python<'_>) -> ArtifactBundle {
+        ArtifactBundle::new(
+            py,
+            "artifact-1".to_string(),
+            "repo-1".to_string(),
+            "snap-1".to_string(),
+            None,
+            "1.0".to_string(),
+            1,
+            "2026-01-01T00:00:00Z".to_string(),
+            Vec::new(),
+            Vec::new(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_checksum_is_stable_regardless_of_symbol_order() {
+        Python::with_gil(|py| {
+            let a = SymbolKey::new(
+                "a".to_string(),
+                "a.py".to_string(),
+                1,
+                2,
+                "h1".to_string(),
+                String::new(),
+            );
+            let b = SymbolKey::new(
+                "b".to_string(),
+                "b.py".to_string(),
+                1,
+                2,
+                "h2".to_string(),
+                String::new(),
+            );
+
+            let mut bundle_forward = test_bundle(py);
+            bundle_forward.promoted_symbols = vec![a.clone(), b.clone()];
+            let mut bundle_reversed = test_bundle(py);
+            bundle_reversed.promoted_symbols = vec![b, a];
+
+            let checksum_forward = compute_checksum(py, &bundle_forward).unwrap();
+            let checksum_reversed = compute_checksum(py, &bundle_reversed).unwrap();
+            assert_eq!(checksum_forward, checksum_reversed);
+        });
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        Python::with_gil(|py| {
+            let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+            let private_key_hex = hex::encode(signing_key.to_bytes());
+            let public_key_hex = hex::encode(signing_key.verifying_key().to_bytes());
+
+            let mut bundle = test_bundle(py);
+            let (checksum, signature) = sign(py, &bundle, &private_key_hex).unwrap();
+            bundle.signature_algo = Some("ed25519".to_string());
+            bundle.signing_key_id = Some("key-1".to_string());
+            bundle.checksum = Some(checksum);
+            bundle.signature = Some(signature);
+
+            assert!(verify(py, &bundle, &public_key_hex).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_content() {
+        Python::with_gil(|py| {
+            let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+            let private_key_hex = hex::encode(signing_key.to_bytes());
+            let public_key_hex = hex::encode(signing_key.verifying_key().to_bytes());
+
+            let mut bundle = test_bundle(py);
+            let (checksum, signature) = sign(py, &bundle, &private_key_hex).unwrap();
+            bundle.signature_algo = Some("ed25519".to_string());
+            bundle.signing_key_id = Some("key-1".to_string());
+            bundle.checksum = Some(checksum);
+            bundle.signature = Some(signature);
+
+            bundle.artifact_id = "tampered".to_string();
+            assert!(!verify(py, &bundle, &public_key_hex).unwrap());
+        });
+    }
+}
@@ -0,0 +1,338 @@
+//! Storage-backend abstraction over the operations the query/indexing
+//! `*_impl` functions actually need: symbol/edge upserts, the FTS index,
+//! a handful of parameterized reads (search/references/context/structure),
+//! and PageRank score read/write.
+//!
+//! Every `*_impl` function in `query`/`indexer` today takes a concrete
+//! `&rusqlite::Connection` and embeds its own SQL, so SQLite is the only
+//! possible store. [`StorageBackend`] pulls those operations out into a
+//! trait so the crate can eventually target an alternative backend (an
+//! in-memory graph for ephemeral runs, or a networked store for a shared
+//! multi-agent index) and run the same workload against each for a fair
+//! comparison — without forcing every query impl to migrate off direct SQL
+//! in one pass. [`SqliteBackend`] is the default implementation, backed by
+//! the same `Connection` the rest of the crate already uses; query impls
+//! adopt the trait incrementally, the same way they already vary by
+//! feature flag (e.g. `bm25_lexical_scoring_enabled`).
+
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+use crate::models::{EdgeRecord, SymbolRecord};
+
+/// A minimal symbol projection returned by backend reads — the fields
+/// `search`/`references`/`context`/`structure` all need regardless of
+/// which concrete query triggered the read.
+#[derive(Debug, Clone)]
+pub struct SymbolProjection {
+    pub id: i64,
+    pub name: String,
+    pub qualified_name: String,
+    pub file_path: String,
+    pub signature: Option<String>,
+    pub start_line: i64,
+    pub end_line: i64,
+    pub pagerank_score: f64,
+}
+
+/// One row of a call/data-flow/import edge, as read back from the store.
+#[derive(Debug, Clone)]
+pub struct EdgeProjection {
+    pub source_id: i64,
+    pub target_id: i64,
+    pub relationship: String,
+    pub line_number: Option<i64>,
+}
+
+/// Storage operations the query/indexing layer needs, independent of the
+/// concrete store. Method names mirror the SQL these replace 1:1 so a
+/// reviewer can diff a call site against the old inline query.
+pub trait StorageBackend {
+    /// Insert or replace a file's extracted symbols.
+    fn upsert_symbols(&self, file_path: &str, symbols: &[SymbolRecord]) -> BombeResult<()>;
+
+    /// Insert or replace a file's call/data-flow/import edges.
+    fn upsert_edges(&self, file_path: &str, edges: &[EdgeRecord]) -> BombeResult<()>;
+
+    /// Rebuild the full-text index entries for a file's symbols (name,
+    /// qualified_name, signature, docstring), dropping any prior entries
+    /// for that file first.
+    fn upsert_fts(&self, file_path: &str) -> BombeResult<()>;
+
+    /// Resolve `name` to a symbol, preferring an exact `qualified_name`
+    /// match and falling back to `name` ordered by `pagerank_score`.
+    fn resolve_symbol(&self, name: &str) -> BombeResult<Option<SymbolProjection>>;
+
+    /// Load a symbol projection by id.
+    fn load_symbol(&self, id: i64) -> BombeResult<Option<SymbolProjection>>;
+
+    /// Edges out of (or into) `node_id`, optionally filtered to a set of
+    /// `relationship` values. `forward = true` walks `source_id -> *`;
+    /// `forward = false` walks `* -> target_id`.
+    fn edges_for_node(
+        &self,
+        node_id: i64,
+        relationships: &[String],
+        forward: bool,
+    ) -> BombeResult<Vec<EdgeProjection>>;
+
+    /// Current `pagerank_score` for every symbol, keyed by id.
+    fn read_pagerank_scores(&self) -> BombeResult<Vec<(i64, f64)>>;
+
+    /// Persist newly computed PageRank scores.
+    fn write_pagerank_scores(&self, scores: &[(i64, f64)]) -> BombeResult<()>;
+}
+
+/// Default [`StorageBackend`]: the existing SQLite schema, over a borrowed
+/// `rusqlite::Connection`.
+pub struct SqliteBackend<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SqliteBackend<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+}
+
+impl<'a> StorageBackend for SqliteBackend<'a> {
+    /// Mirrors `store::database::Database::_replace_file_symbols` (minus
+    /// the `Py<SymbolRecord>`/GIL plumbing that method needs as a PyO3
+    /// entry point): delete the file's old symbols/parameters/FTS rows,
+    /// then insert the new ones, deduping by `(qualified_name, file_path)`.
+    fn upsert_symbols(&self, file_path: &str, symbols: &[SymbolRecord]) -> BombeResult<()> {
+        let mut old_id_stmt = self
+            .conn
+            .prepare_cached("SELECT id FROM symbols WHERE file_path = ?1;")?;
+        let old_ids: Vec<i64> = old_id_stmt
+            .query_map(rusqlite::params![file_path], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(old_id_stmt);
+        for sid in &old_ids {
+            if self
+                .conn
+                .execute("DELETE FROM symbol_fts WHERE symbol_id = ?1;", rusqlite::params![sid])
+                .is_err()
+            {
+                break; // FTS table may not exist
+            }
+        }
+        self.conn.execute(
+            "DELETE FROM parameters WHERE symbol_id IN \
+             (SELECT id FROM symbols WHERE file_path = ?1);",
+            rusqlite::params![file_path],
+        )?;
+        self.conn
+            .execute("DELETE FROM symbols WHERE file_path = ?1;", rusqlite::params![file_path])?;
+
+        let mut insert_symbol = self.conn.prepare_cached(
+            "INSERT INTO symbols ( \
+                 name, qualified_name, kind, file_path, start_line, end_line, \
+                 signature, return_type, visibility, is_async, is_static, \
+                 parent_symbol_id, docstring, pagerank_score \
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14);",
+        )?;
+        let mut insert_param = self.conn.prepare_cached(
+            "INSERT INTO parameters (symbol_id, name, type, position, default_value) \
+             VALUES (?1, ?2, ?3, ?4, ?5);",
+        )?;
+        let mut insert_fts = self.conn.prepare_cached(
+            "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
+             VALUES (?1, ?2, ?3, ?4, ?5);",
+        )?;
+
+        let mut seen: std::collections::HashSet<(String, String)> = std::collections::HashSet::new();
+        for sym in symbols {
+            let key = (sym.qualified_name.clone(), sym.file_path.clone());
+            if !seen.insert(key) {
+                continue;
+            }
+            insert_symbol.execute(rusqlite::params![
+                sym.name,
+                sym.qualified_name,
+                sym.kind,
+                sym.file_path,
+                sym.start_line,
+                sym.end_line,
+                sym.signature,
+                sym.return_type,
+                sym.visibility,
+                sym.is_async as i64,
+                sym.is_static as i64,
+                sym.parent_symbol_id,
+                sym.docstring,
+                sym.pagerank_score,
+            ])?;
+            let symbol_id = self.conn.last_insert_rowid();
+            for param in &sym.parameters {
+                insert_param.execute(rusqlite::params![
+                    symbol_id,
+                    param.name,
+                    param.type_,
+                    param.position,
+                    param.default_value,
+                ])?;
+            }
+            let _ = insert_fts.execute(rusqlite::params![
+                symbol_id,
+                sym.name,
+                sym.qualified_name,
+                sym.docstring.as_deref().unwrap_or(""),
+                sym.signature.as_deref().unwrap_or(""),
+            ]);
+        }
+        Ok(())
+    }
+
+    fn upsert_edges(&self, file_path: &str, edges: &[EdgeRecord]) -> BombeResult<()> {
+        self.conn
+            .execute("DELETE FROM edges WHERE file_path = ?1;", rusqlite::params![file_path])?;
+        let mut stmt = self.conn.prepare_cached(
+            "INSERT OR IGNORE INTO edges ( \
+                 source_id, target_id, source_type, target_type, relationship, \
+                 file_path, line_number, confidence \
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
+        )?;
+        for edge in edges {
+            stmt.execute(rusqlite::params![
+                edge.source_id,
+                edge.target_id,
+                edge.source_type,
+                edge.target_type,
+                edge.relationship,
+                edge.file_path,
+                edge.line_number,
+                edge.confidence,
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Rebuilds `symbol_fts` for `file_path` from the current `symbols`
+    /// rows. `upsert_symbols` already does this inline as part of the
+    /// insert loop; this is for backends/callers that update symbols
+    /// without going through `upsert_symbols` (e.g. a pagerank-only pass
+    /// that never touches FTS but still wants a consistency check).
+    fn upsert_fts(&self, file_path: &str) -> BombeResult<()> {
+        self.conn.execute(
+            "DELETE FROM symbol_fts WHERE symbol_id IN \
+             (SELECT id FROM symbols WHERE file_path = ?1);",
+            rusqlite::params![file_path],
+        )?;
+        self.conn.execute(
+            "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
+             SELECT id, name, qualified_name, \
+                 COALESCE(docstring, ''), COALESCE(signature, '') \
+             FROM symbols WHERE file_path = ?1;",
+            rusqlite::params![file_path],
+        )?;
+        Ok(())
+    }
+
+    fn resolve_symbol(&self, name: &str) -> BombeResult<Option<SymbolProjection>> {
+        let exact = self.load_by(
+            "SELECT id, name, qualified_name, file_path, signature, start_line, end_line, \
+             pagerank_score FROM symbols WHERE qualified_name = ?1 \
+             ORDER BY pagerank_score DESC LIMIT 1;",
+            name,
+        )?;
+        if exact.is_some() {
+            return Ok(exact);
+        }
+        self.load_by(
+            "SELECT id, name, qualified_name, file_path, signature, start_line, end_line, \
+             pagerank_score FROM symbols WHERE name = ?1 \
+             ORDER BY pagerank_score DESC LIMIT 1;",
+            name,
+        )
+    }
+
+    fn load_symbol(&self, id: i64) -> BombeResult<Option<SymbolProjection>> {
+        let result = self.conn.query_row(
+            "SELECT id, name, qualified_name, file_path, signature, start_line, end_line, \
+             pagerank_score FROM symbols WHERE id = ?1;",
+            rusqlite::params![id],
+            Self::row_to_projection,
+        );
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn edges_for_node(
+        &self,
+        node_id: i64,
+        relationships: &[String],
+        forward: bool,
+    ) -> BombeResult<Vec<EdgeProjection>> {
+        let sql = if forward {
+            "SELECT source_id, target_id, relationship, line_number FROM edges \
+             WHERE source_id = ?1;"
+        } else {
+            "SELECT source_id, target_id, relationship, line_number FROM edges \
+             WHERE target_id = ?1;"
+        };
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows: Vec<EdgeProjection> = stmt
+            .query_map(rusqlite::params![node_id], |row| {
+                Ok(EdgeProjection {
+                    source_id: row.get(0)?,
+                    target_id: row.get(1)?,
+                    relationship: row.get(2)?,
+                    line_number: row.get(3)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .filter(|e| relationships.is_empty() || relationships.contains(&e.relationship))
+            .collect();
+        Ok(rows)
+    }
+
+    fn read_pagerank_scores(&self) -> BombeResult<Vec<(i64, f64)>> {
+        let mut stmt = self.conn.prepare("SELECT id, pagerank_score FROM symbols;")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    }
+
+    fn write_pagerank_scores(&self, scores: &[(i64, f64)]) -> BombeResult<()> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("UPDATE symbols SET pagerank_score = ?1 WHERE id = ?2;")?;
+        for &(id, score) in scores {
+            stmt.execute(rusqlite::params![score, id])?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> SqliteBackend<'a> {
+    fn row_to_projection(row: &rusqlite::Row) -> rusqlite::Result<SymbolProjection> {
+        Ok(SymbolProjection {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            qualified_name: row.get(2)?,
+            file_path: row.get(3)?,
+            signature: row.get(4)?,
+            start_line: row.get(5)?,
+            end_line: row.get(6)?,
+            pagerank_score: row.get(7)?,
+        })
+    }
+
+    fn load_by(&self, sql: &str, name: &str) -> BombeResult<Option<SymbolProjection>> {
+        let result = self
+            .conn
+            .query_row(sql, rusqlite::params![name], Self::row_to_projection);
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Batched symbol insertion.
+//!
+//! `symbol_insert_500` (see `benches/core_bench.rs`) issues 500
+//! `conn.execute` calls, each re-preparing the same `INSERT` and each its
+//! own implicit autocommit transaction — exactly the overhead real
+//! indexing pays per file. [`SymbolWriter`] collapses that into one
+//! `BEGIN ... COMMIT` per chunk and a single `Connection::prepare_cached`
+//! statement reused across the whole chunk, flushing every `chunk_size`
+//! rows so a very large batch doesn't hold one unbounded transaction open.
+
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+
+/// The handful of columns `symbol_insert_500`'s bottleneck path writes.
+/// Intentionally narrower than [`crate::models::SymbolRecord`] — callers
+/// that need the full row shape (parameters, docstring, visibility, ...)
+/// go through `store::database::Database::replace_file_symbols` instead;
+/// this is for the hot bulk-insert path alone.
+#[derive(Debug, Clone)]
+pub struct BatchSymbolRow {
+    pub name: String,
+    pub qualified_name: String,
+    pub kind: String,
+    pub file_path: String,
+    pub start_line: i64,
+    pub end_line: i64,
+}
+
+/// Batched writer over a single connection. Reused across chunks so the
+/// cached statement in `conn`'s statement cache survives between them.
+pub struct SymbolWriter<'conn> {
+    conn: &'conn Connection,
+    chunk_size: usize,
+}
+
+impl<'conn> SymbolWriter<'conn> {
+    /// Rows per `BEGIN ... COMMIT` transaction.
+    pub const DEFAULT_CHUNK_SIZE: usize = 500;
+
+    pub fn new(conn: &'conn Connection) -> Self {
+        Self {
+            conn,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    pub fn with_chunk_size(conn: &'conn Connection, chunk_size: usize) -> Self {
+        Self {
+            conn,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    /// Writes every row, one transaction per `chunk_size`-sized slice.
+    pub fn write_all(&self, rows: &[BatchSymbolRow]) -> BombeResult<()> {
+        for chunk in rows.chunks(self.chunk_size) {
+            self.conn.execute_batch("BEGIN;")?;
+            let result = self.write_chunk(chunk);
+            match result {
+                Ok(()) => self.conn.execute_batch("COMMIT;")?,
+                Err(e) => {
+                    self.conn.execute_batch("ROLLBACK;").ok();
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_chunk(&self, rows: &[BatchSymbolRow]) -> BombeResult<()> {
+        let mut stmt = self.conn.prepare_cached(
+            "INSERT INTO symbols(name, qualified_name, kind, file_path, start_line, end_line) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6);",
+        )?;
+        for row in rows {
+            stmt.execute(rusqlite::params![
+                row.name,
+                row.qualified_name,
+                row.kind,
+                row.file_path,
+                row.start_line,
+                row.end_line,
+            ])?;
+        }
+        Ok(())
+    }
+}
+
+/// Convenience entry point over [`SymbolWriter`] with the default chunk
+/// size.
+pub fn insert_symbols_batch(conn: &Connection, rows: &[BatchSymbolRow]) -> BombeResult<()> {
+    SymbolWriter::new(conn).write_all(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE symbols (
+                id INTEGER PRIMARY KEY,
+                name TEXT, qualified_name TEXT, kind TEXT, file_path TEXT,
+                start_line INTEGER, end_line INTEGER
+            );",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn row(i: i64) -> BatchSymbolRow {
+        BatchSymbolRow {
+            name: format!("func_{i}"),
+            qualified_name: format!("pkg.func_{i}"),
+            kind: "function".to_string(),
+            file_path: "bench.java".to_string(),
+            start_line: i * 10,
+            end_line: i * 10 + 8,
+        }
+    }
+
+    #[test]
+    fn writes_every_row_across_chunk_boundaries() {
+        let conn = setup();
+        let rows: Vec<BatchSymbolRow> = (0..1203).map(row).collect();
+        SymbolWriter::with_chunk_size(&conn, 500).write_all(&rows).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM symbols;", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 1203);
+    }
+
+    #[test]
+    fn default_entry_point_matches_default_chunk_size() {
+        let conn = setup();
+        let rows: Vec<BatchSymbolRow> = (0..500).map(row).collect();
+        insert_symbols_batch(&conn, &rows).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM symbols;", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 500);
+    }
+}
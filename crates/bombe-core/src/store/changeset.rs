@@ -0,0 +1,89 @@
+//! Changeset-based incremental sync via SQLite's session extension.
+//!
+//! `begin_changeset`/`finish_changeset`/`apply_changeset` on `Database` let a
+//! caller ship one re-index as a binary diff instead of a full `backup_to`
+//! snapshot: attach a session to the connection the CRUD writers use, run
+//! `index_file` as normal, then serialize everything the session recorded
+//! into bytes that `apply_changeset` can replay against another copy of the
+//! database. Requires this crate's `session` cargo feature (`rusqlite`
+//! built with its own `session` feature, linking against a SQLite compiled
+//! with `SQLITE_ENABLE_SESSION`/`SQLITE_ENABLE_PREUPDATE_HOOK`).
+#![cfg(feature = "session")]
+
+use rusqlite::session::{ChangesetIter, ConflictAction, ConflictType, Session};
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+
+/// Tables a changeset records: every table the indexing-write CRUD path
+/// (`upsert_files`, `replace_file_symbols`, `replace_file_edges`,
+/// `replace_external_deps`) touches.
+const TRACKED_TABLES: &[&str] = &["files", "symbols", "parameters", "edges", "external_deps"];
+
+/// A `Session` paired with the `Connection` it is attached to, so both can
+/// be handed across separate `begin_changeset`/`finish_changeset` calls on
+/// `Database` without the borrow checker seeing the struct it's embedded in.
+///
+/// `Session<'conn>` borrows its connection, but `conn` is boxed (a stable
+/// heap address that this struct never moves again once constructed) and
+/// `session`'s lifetime is unsafely widened to match. That's sound only
+/// because `session` is declared before `conn`: Rust drops struct fields in
+/// declaration order, so the session is always torn down — and its FFI
+/// handle to the connection released — before `conn` itself is dropped or
+/// moved out via [`ChangesetRecorder::finish`].
+pub struct ChangesetRecorder {
+    session: Session<'static>,
+    conn: Box<Connection>,
+}
+
+impl ChangesetRecorder {
+    /// Box `conn`, attach a session tracking [`TRACKED_TABLES`], and take
+    /// ownership of both for the duration of the recording.
+    pub fn attach(conn: Connection) -> BombeResult<Self> {
+        let conn = Box::new(conn);
+        // SAFETY: `conn` lives in this heap allocation for as long as `Self`
+        // exists and is never touched except through `Self`, so a 'static
+        // borrow of it is valid until `finish` reclaims the box (after the
+        // session borrowing it has already been dropped).
+        let conn_ref: &'static Connection = unsafe { &*(conn.as_ref() as *const Connection) };
+        let mut session = Session::new(conn_ref)?;
+        for table in TRACKED_TABLES {
+            session.attach(Some(table))?;
+        }
+        Ok(Self { session, conn })
+    }
+
+    /// The connection writes should be routed through while recording is in
+    /// progress, so the session actually observes them.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Serialize everything recorded so far and hand back the underlying
+    /// connection so the caller can return it to normal (non-recording) use.
+    pub fn finish(self) -> BombeResult<(Vec<u8>, Connection)> {
+        let ChangesetRecorder { mut session, conn } = self;
+        let mut buf = Vec::new();
+        session.changeset_strm(&mut buf)?;
+        drop(session);
+        Ok((buf, *conn))
+    }
+}
+
+/// Conflict handler for [`apply_changeset`]: prefers the incoming row when
+/// it merely diverges from local data, but leaves the local row alone
+/// (`OMIT`) rather than violate a uniqueness/foreign-key constraint.
+fn prefer_incoming(conflict_type: ConflictType, _item: ChangesetIter<'_>) -> ConflictAction {
+    match conflict_type {
+        ConflictType::Data => ConflictAction::Replace,
+        _ => ConflictAction::Omit,
+    }
+}
+
+/// Apply a serialized changeset (as produced by [`ChangesetRecorder::finish`])
+/// to `conn`.
+pub fn apply_changeset(conn: &Connection, changeset: &[u8]) -> BombeResult<()> {
+    let mut bytes = changeset;
+    conn.apply_strm(&mut bytes, None::<fn(&str) -> bool>, prefer_incoming)?;
+    Ok(())
+}
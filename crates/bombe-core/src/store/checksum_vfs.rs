@@ -0,0 +1,46 @@
+//! Registration glue for SQLite's bundled checksum VFS
+//! (`ext/misc/checksumvfs.c`), gated behind the `checksum_vfs` cargo
+//! feature.
+//!
+//! The checksum VFS wraps the default VFS and stores an 8-byte checksum in
+//! each page's reserved bytes, returning `SQLITE_IOERR_DATA` on a read whose
+//! checksum doesn't match instead of silently handing back a corrupted
+//! page — which matters most for `trusted_signing_keys`, where a flipped
+//! bit would otherwise look like a valid-but-wrong public key rather than
+//! an I/O error. See [`crate::store::database::Database::connect`], which
+//! opens every connection through the `"checksum"` VFS when this feature is
+//! enabled.
+//!
+//! Registering a VFS by name has to happen before the first connection
+//! requests it by that name, and only once per process — SQLite's own
+//! mechanism for "run this before any `sqlite3_open`" is
+//! `sqlite3_auto_extension`, so that's what [`ensure_registered`] uses.
+
+use std::sync::Once;
+
+use rusqlite::ffi;
+
+extern "C" {
+    /// The checksum VFS's extension entry point — same signature as any
+    /// run-time loadable extension, which is what lets it be registered via
+    /// `sqlite3_auto_extension` and fire on every subsequent `sqlite3_open`.
+    fn sqlite3_checksumvfs_init(
+        db: *mut ffi::sqlite3,
+        pz_err_msg: *mut *mut std::os::raw::c_char,
+        p_api: *const ffi::sqlite3_api_routines,
+    ) -> std::os::raw::c_int;
+}
+
+static REGISTERED: Once = Once::new();
+
+/// Register the checksum VFS as an auto-extension exactly once, so it's
+/// available by name (`"checksum"`) to every connection this process opens
+/// afterwards.
+pub fn ensure_registered() {
+    REGISTERED.call_once(|| unsafe {
+        #[allow(clippy::missing_transmute_annotations)]
+        ffi::sqlite3_auto_extension(Some(std::mem::transmute(
+            sqlite3_checksumvfs_init as *const (),
+        )));
+    });
+}
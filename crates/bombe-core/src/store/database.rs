@@ -1,10 +1,70 @@
 //! SQLite storage layer for Bombe.
 //!
 //! Direct port of the Python `bombe.store.database.Database` class.
-//! Each public method opens its own connection (matching Python behaviour).
-
-use std::collections::HashSet;
+//! Most public methods open their own connection (matching Python
+//! behaviour), but the hot indexing-write path (`upsert_files`,
+//! `replace_file_symbols`, `replace_file_edges`, `replace_external_deps`,
+//! `delete_file_graph`, `rename_file`) instead reuses a single long-lived
+//! pooled connection and `Connection::prepare_cached`, so a full re-index
+//! doesn't re-open a connection and re-compile the same `INSERT`/`DELETE`
+//! statements for every file. `index_file`/`index_files` wrap those same
+//! CRUD steps in a `conn.unchecked_transaction()` so a file's symbols,
+//! edges, and deps are swapped atomically instead of across separate
+//! autocommit statements.
+//!
+//! With the `sqlcipher` cargo feature enabled (linking `rusqlite`/
+//! `libsqlite3-sys` against SQLCipher instead of stock SQLite), `Database`
+//! can encrypt the `.db` file at rest: `Database::new`'s optional `key`
+//! becomes the passphrase, and every connection this module opens issues
+//! `PRAGMA key` (plus `cipher_*` tuning pragmas) as its very first
+//! statement, before any other pragma or query. Without the feature, a
+//! supplied key is rejected up front rather than silently writing an
+//! unencrypted file.
+//!
+//! With the `hooks` cargo feature (`rusqlite`'s `hooks` feature), the pooled
+//! connection carries a commit hook that notices writes to
+//! [`CACHE_INVALIDATING_TABLES`] and marks the cache epoch dirty, so
+//! `get_cache_epoch()` advances it automatically instead of relying on every
+//! writer calling `bump_cache_epoch()` by hand; `on_commit` additionally
+//! lets callers register a Python callback fired on the same commits.
+//!
+//! With the `functions` cargo feature, every connection this module opens
+//! also gets the fuzzy-search scalar functions in
+//! [`crate::store::fuzzy`] (`edit_distance`, `fuzzy_score`) registered on
+//! it, so `query()` can rank candidates by similarity in plain SQL even
+//! without FTS5.
+//!
+//! The sync-queue, artifact, circuit-breaker, metrics, and diagnostics
+//! methods route through `read_pool`/`write_pool` (see [`ConnectionPool`])
+//! instead of opening a fresh connection per call: a handful of pooled,
+//! WAL-mode connections for the `list_*`/`recent_*`/`summarize_*` reads,
+//! and a single-connection write pool for their `INSERT`/`UPDATE`/`DELETE`
+//! counterparts, so concurrent MCP tool calls don't each pay to open and
+//! configure a connection.
+//!
+//! Schema versioning (a `repo_meta` row tracking `SCHEMA_VERSION`, an
+//! ordered `migrate_to_vN` sequence applied inside a `SAVEPOINT` per step,
+//! and a loud failure if the on-disk version is newer than this binary
+//! supports) lives in [`crate::store::schema`] and runs from
+//! [`Database::init_schema`]. Every connection [`Database::connect`] opens
+//! also applies `self`'s [`ConnectionOptions`] (`busy_timeout` — long by
+//! default — and optionally WAL mode) so a reader doesn't spuriously error
+//! out waiting on the writer under load; with the `checksum_vfs` cargo feature
+//! enabled, connections additionally open through SQLite's checksum VFS
+//! (`ext/misc/checksumvfs.c`, statically linked in by that feature), which
+//! stores a per-page checksum in the reserved bytes and fails reads with
+//! `SQLITE_IOERR_DATA` on mismatch — so silently corrupted pages (e.g. in
+//! the trusted-signing-key table) surface as a loud I/O error instead of
+//! handing back bad public keys.
+
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
+#[cfg(feature = "hooks")]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "hooks")]
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
@@ -60,33 +120,512 @@ fn row_to_pydict<'py>(
     Ok(dict)
 }
 
+/// Latency histogram bucket boundaries (milliseconds, inclusive upper bound)
+/// used by [`Database::export_prometheus_metrics`].
+const PROMETHEUS_LATENCY_BUCKETS_MS: &[f64] =
+    &[1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0];
+
+/// Escape a Prometheus label value: backslash, double-quote, and newline
+/// must be backslash-escaped per the text exposition format.
+fn prometheus_escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Base interval between trusted-signing-key refreshes (one week), before
+/// jitter. `Database::set_trusted_signing_key` draws a fresh jitter
+/// uniformly from `[0, 2 * KEY_REFRESH_INTERVAL_SECS)` on every upsert and
+/// stores it alongside the key, so `Database::list_keys_due_for_refresh` can
+/// compute a stable due-time without every key in a large set coming due at
+/// once.
+const KEY_REFRESH_INTERVAL_SECS: f64 = 7.0 * 24.0 * 3600.0;
+
+/// Split an `atomic_apply` `artifact_pins` key of the form
+/// `"{repo_id}::{snapshot_id}"` into its two parts.
+fn split_pin_key(key: &str) -> PyResult<(&str, &str)> {
+    key.split_once("::").ok_or_else(|| {
+        BombeError::Query(format!(
+            "atomic_apply: artifact_pins key '{key}' must be '<repo_id>::<snapshot_id>'"
+        ))
+        .into()
+    })
+}
+
+/// Algorithms this build knows how to verify. A key tagged with anything
+/// else is still stored and listed (never dropped — a newer binary may have
+/// written it) but is reported `unusable_by_this_version` by
+/// `Database::list_trusted_signing_keys` and treated as non-matching by
+/// verification, rather than erroring the whole call.
+const RECOGNIZED_ALGORITHMS: &[&str] = &["ed25519", "rsa_pkcs1_sha256", "bls12_381"];
+
+/// Normalize an algorithm tag before it's stored or compared: trimmed and
+/// lowercased, so `"Ed25519"` and `"ed25519"` are the same policy/recognition
+/// target.
+fn normalize_algorithm(algorithm: &str) -> String {
+    algorithm.trim().to_ascii_lowercase()
+}
+
+/// Check `algorithm` (already normalized) against `repo_id`'s
+/// `algorithm_policies` rows: any `deny` match rejects outright; if any
+/// `allow` rows exist for the repo, `algorithm` must be among them. A repo
+/// with no policy rows at all allows everything, preserving the prior
+/// (unrestricted) behaviour for repos that never call
+/// `Database::set_algorithm_policy`.
+fn check_algorithm_policy(conn: &Connection, repo_id: &str, algorithm: &str) -> BombeResult<()> {
+    let mut stmt = conn.prepare(
+        "SELECT algorithm, mode FROM algorithm_policies WHERE repo_id = ?1;",
+    )?;
+    let rows: Vec<(String, String)> = stmt
+        .query_map(params![repo_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if rows.iter().any(|(algo, mode)| mode == "deny" && algo == algorithm) {
+        return Err(BombeError::Query(format!(
+            "algorithm '{algorithm}' is denied by repo '{repo_id}' policy"
+        )));
+    }
+    let allow_rows: Vec<&str> = rows
+        .iter()
+        .filter(|(_, mode)| mode == "allow")
+        .map(|(algo, _)| algo.as_str())
+        .collect();
+    if !allow_rows.is_empty() && !allow_rows.contains(&algorithm) {
+        return Err(BombeError::Query(format!(
+            "algorithm '{algorithm}' is not on repo '{repo_id}''s allow list"
+        )));
+    }
+    Ok(())
+}
+
+/// Load a `trusted_signing_keys` row (active or not — endorsement chains
+/// need to walk through keys regardless of their current `active` flag) as
+/// a [`crate::store::signing::TrustedKey`], for `add_key_signature` and
+/// `get_trust_chain`.
+fn load_signing_key(
+    conn: &Connection,
+    repo_id: &str,
+    key_id: &str,
+) -> BombeResult<crate::store::signing::TrustedKey> {
+    let (algorithm, public_key_hex): (String, String) = conn
+        .query_row(
+            "SELECT algorithm, public_key FROM trusted_signing_keys \
+             WHERE repo_id = ?1 AND key_id = ?2 LIMIT 1;",
+            params![repo_id, key_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => BombeError::Query(format!(
+                "no trusted key '{key_id}' for repo '{repo_id}'"
+            )),
+            other => BombeError::from(other),
+        })?;
+    Ok(crate::store::signing::TrustedKey {
+        key_id: key_id.to_string(),
+        algorithm,
+        public_key_hex,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Database
 // ---------------------------------------------------------------------------
 
+/// Default capacity of the pooled connection's prepared-statement LRU cache,
+/// large enough to hold every distinct CRUD statement the hot indexing path
+/// issues without evicting between files.
+const DEFAULT_STATEMENT_CACHE_CAPACITY: usize = 64;
+
+/// Default idle-connection capacity of [`Database::read_pool`]: several
+/// concurrent read-only MCP tool calls (list/recent/summarize queries)
+/// shouldn't each pay to open and configure a fresh connection.
+const DEFAULT_READ_POOL_SIZE: usize = 4;
+
+/// Default idle-connection capacity of [`Database::write_pool`]. SQLite
+/// allows only one writer at a time, so there is nothing to gain from
+/// pooling more than a single idle write connection.
+const DEFAULT_WRITE_POOL_SIZE: usize = 1;
+
+// ---------------------------------------------------------------------------
+// ConnectionPool: a small capped pool of SQLite connections
+// ---------------------------------------------------------------------------
+
+/// A small capped pool of SQLite connections, modeled on the
+/// `read_pool`/`write_pool` split nostr-rs-relay builds on `r2d2_sqlite`:
+/// idle connections are handed out by [`ConnectionPool::acquire`] and
+/// returned to the pool when the caller drops the guard, up to
+/// [`ConnectionPool::set_max_size`]'s cap; callers that ask for a connection
+/// while the pool is empty (e.g. every idle slot checked out) just get a
+/// fresh one, so `acquire` never blocks waiting for a slot to free up.
+struct ConnectionPool {
+    idle: Mutex<Vec<Connection>>,
+    max_size: AtomicUsize,
+    active: AtomicUsize,
+}
+
+impl ConnectionPool {
+    fn new(max_size: usize) -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+            max_size: AtomicUsize::new(max_size.max(1)),
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    fn set_max_size(&self, max_size: usize) {
+        self.max_size.store(max_size.max(1), Ordering::Relaxed);
+    }
+
+    /// Check out a connection, opening one via `open` if the pool has no
+    /// idle connection to offer. Returns it wrapped in a guard that
+    /// releases it back to the pool (up to capacity) on drop.
+    fn acquire(
+        &self,
+        open: impl FnOnce() -> BombeResult<Connection>,
+    ) -> BombeResult<PooledConnection<'_>> {
+        let idle_conn = self
+            .idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .pop();
+        let conn = match idle_conn {
+            Some(conn) => conn,
+            None => open()?,
+        };
+        self.active.fetch_add(1, Ordering::SeqCst);
+        Ok(PooledConnection {
+            pool: self,
+            conn: Some(conn),
+        })
+    }
+
+    fn release(&self, conn: Connection) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        let mut idle = self
+            .idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if idle.len() < self.max_size.load(Ordering::Relaxed) {
+            idle.push(conn);
+        }
+    }
+
+    /// `(idle_count, active_count, max_size)`.
+    fn stats(&self) -> (usize, usize, usize) {
+        let idle_count = self
+            .idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len();
+        (
+            idle_count,
+            self.active.load(Ordering::SeqCst),
+            self.max_size.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// A connection checked out of a [`ConnectionPool`]. Derefs to the
+/// underlying [`Connection`]; returns it to the pool on drop.
+struct PooledConnection<'a> {
+    pool: &'a ConnectionPool,
+    conn: Option<Connection>,
+}
+
+impl std::ops::Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection present until drop")
+    }
+}
+
+impl std::ops::DerefMut for PooledConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection present until drop")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.release(conn);
+        }
+    }
+}
+
+/// Tables whose writes invalidate cached query results, and therefore should
+/// advance `cache_epoch`. Mirrors the tables the indexing-write CRUD path
+/// (`upsert_files`, `replace_file_symbols`, `replace_file_edges`) touches.
+#[cfg(feature = "hooks")]
+const CACHE_INVALIDATING_TABLES: &[&str] = &["files", "symbols", "edges"];
+
+/// Per-connection SQLite tuning knobs, applied by [`Database::connect`]
+/// right after opening every connection. [`Database::new`] defaults to the
+/// long-standing hardcoded behaviour (`busy_timeout` 30s, `foreign_keys` on,
+/// journal mode untouched); [`crate::store::sharding::router::ShardRouter`]
+/// instead passes a WAL-mode, shorter-timeout [`ConnectionOptions`] so that
+/// federated fan-out across many shard databases doesn't serialize on
+/// SQLite's default rollback-journal write lock.
+#[pyclass(get_all)]
+#[derive(Clone)]
+pub struct ConnectionOptions {
+    /// Milliseconds passed to `PRAGMA busy_timeout`. `None` leaves SQLite's
+    /// default (fail immediately on contention) in place.
+    pub busy_timeout_ms: Option<u64>,
+    /// Issue `PRAGMA journal_mode = WAL;` on connect.
+    pub wal_mode: bool,
+    /// Issue `PRAGMA foreign_keys = ON;` on connect.
+    pub enable_foreign_keys: bool,
+}
+
+#[pymethods]
+impl ConnectionOptions {
+    #[new]
+    #[pyo3(signature = (busy_timeout_ms=Some(30_000), wal_mode=false, enable_foreign_keys=true))]
+    fn new(busy_timeout_ms: Option<u64>, wal_mode: bool, enable_foreign_keys: bool) -> Self {
+        Self {
+            busy_timeout_ms,
+            wal_mode,
+            enable_foreign_keys,
+        }
+    }
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            busy_timeout_ms: Some(30_000),
+            wal_mode: false,
+            enable_foreign_keys: true,
+        }
+    }
+}
+
 /// SQLite graph store for Bombe.
 ///
-/// Mirrors the Python `Database` class: every public method opens its own
-/// connection so that the caller never has to manage connection lifetime.
+/// Most public methods open their own connection so that the caller never
+/// has to manage connection lifetime. The batch indexing writers instead
+/// route through a single pooled connection (see [`Database::with_pooled`])
+/// and `prepare_cached`, so repeated CRUD calls during a full re-index reuse
+/// both the connection and its compiled statements.
 #[pyclass]
 pub struct Database {
     db_path: PathBuf,
+    pooled: Mutex<Option<Connection>>,
+    statement_cache_capacity: AtomicUsize,
+    connection_options: ConnectionOptions,
+    #[cfg(feature = "sqlcipher")]
+    passphrase: Mutex<Option<String>>,
+    #[cfg(feature = "session")]
+    changeset: Mutex<Option<crate::store::changeset::ChangesetRecorder>>,
+    /// Set by the pooled connection's commit hook whenever a commit wrote to
+    /// a [`CACHE_INVALIDATING_TABLES`] table; observed and cleared by
+    /// [`Database::get_cache_epoch`], which bumps the epoch in its place.
+    #[cfg(feature = "hooks")]
+    cache_dirty: Arc<AtomicBool>,
+    /// Optional Python callback invoked (no args) after each such commit, so
+    /// server code can react to writes without polling `get_cache_epoch()`.
+    #[cfg(feature = "hooks")]
+    on_commit: Arc<Mutex<Option<PyObject>>>,
+    /// Read-only connection pool for the `list_*`/`recent_*`/`summarize_*`
+    /// queries (sync queue, artifacts, circuit breakers, metrics,
+    /// diagnostics), so concurrent MCP tool calls share a handful of
+    /// configured connections instead of each opening their own.
+    read_pool: ConnectionPool,
+    /// Write connection pool (capped at one idle connection — SQLite is
+    /// single-writer) for the INSERT/UPDATE/DELETE counterparts of the
+    /// above, separate from the long-lived `pooled` connection the hot
+    /// indexing-write path uses.
+    write_pool: ConnectionPool,
 }
 
 impl Database {
-    /// Open a new SQLite connection to `self.db_path`, enable `foreign_keys`,
-    /// and return it.
+    /// Open a new SQLite connection to `self.db_path`, apply the encryption
+    /// key (if any, `sqlcipher` feature only) as the very first statement,
+    /// then apply `self.connection_options` (`foreign_keys`, `busy_timeout`,
+    /// and WAL mode if requested), and return it.
     fn connect(&self) -> BombeResult<Connection> {
+        #[cfg(feature = "checksum_vfs")]
+        let conn = {
+            crate::store::checksum_vfs::ensure_registered();
+            Connection::open_with_flags_and_vfs(
+                &self.db_path,
+                rusqlite::OpenFlags::default(),
+                "checksum",
+            )?
+        };
+        #[cfg(not(feature = "checksum_vfs"))]
         let conn = Connection::open(&self.db_path)?;
-        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+
+        #[cfg(feature = "sqlcipher")]
+        {
+            let guard = self
+                .passphrase
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(passphrase) = guard.as_ref() {
+                Self::apply_key(&conn, passphrase)?;
+            }
+        }
+        let mut pragmas = String::new();
+        if self.connection_options.enable_foreign_keys {
+            pragmas.push_str("PRAGMA foreign_keys = ON;");
+        }
+        if let Some(ms) = self.connection_options.busy_timeout_ms {
+            pragmas.push_str(&format!("PRAGMA busy_timeout = {ms};"));
+        }
+        if self.connection_options.wal_mode {
+            pragmas.push_str("PRAGMA journal_mode = WAL;");
+        }
+        if !pragmas.is_empty() {
+            conn.execute_batch(&pragmas)?;
+        }
+        #[cfg(feature = "functions")]
+        crate::store::fuzzy::register(&conn)?;
         Ok(conn)
     }
 
-    /// Public alias for internal connect, used by query engines.
+    /// Issue `PRAGMA key` and SQLCipher's tuning pragmas against `conn`.
+    /// Must run before any other statement on the connection, so callers
+    /// that open their own `Connection` (backup/restore destinations) need
+    /// to call this immediately after `Connection::open`.
+    #[cfg(feature = "sqlcipher")]
+    fn apply_key(conn: &Connection, passphrase: &str) -> BombeResult<()> {
+        conn.execute_batch(&format!(
+            "PRAGMA key = '{}';",
+            passphrase.replace('\'', "''")
+        ))?;
+        conn.execute_batch("PRAGMA cipher_page_size = 4096; PRAGMA kdf_iter = 256000;")?;
+        Ok(())
+    }
+
+    /// Public alias for internal connect, used by query engines. Query
+    /// engines get a fresh connection (not the pooled one) so a long-running
+    /// read is isolated from concurrent indexing writes.
     pub fn connect_internal(&self) -> BombeResult<Connection> {
         self.connect()
     }
 
+    /// Run `f` against the single long-lived pooled connection, opening and
+    /// caching it on first use. Intended for the hot indexing-write path,
+    /// where callers use `conn.prepare_cached(..)` instead of `prepare` so
+    /// SQLite's statement cache (capacity set by
+    /// [`Database::set_statement_cache_capacity`]) reuses compiled
+    /// statements across calls instead of recompiling them every time.
+    ///
+    /// While a changeset recording is in progress (`session` feature,
+    /// between `begin_changeset` and `finish_changeset`), the pooled
+    /// connection has been handed to the [`crate::store::changeset::ChangesetRecorder`]
+    /// so its session observes these same writes; `f` runs against that
+    /// connection instead so recording doesn't change where writes land.
+    fn with_pooled<T>(&self, f: impl FnOnce(&Connection) -> BombeResult<T>) -> BombeResult<T> {
+        #[cfg(feature = "session")]
+        {
+            let changeset_guard = self
+                .changeset
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(recorder) = changeset_guard.as_ref() {
+                return f(recorder.connection());
+            }
+        }
+        let mut guard = self
+            .pooled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if guard.is_none() {
+            let conn = self.connect()?;
+            conn.set_prepared_statement_cache_capacity(
+                self.statement_cache_capacity.load(Ordering::Relaxed),
+            );
+            #[cfg(feature = "hooks")]
+            self.register_hooks(&conn);
+            *guard = Some(conn);
+        }
+        f(guard.as_ref().expect("pooled connection just initialised"))
+    }
+
+    /// Register the commit/update hooks (`hooks` cargo feature, i.e.
+    /// `rusqlite`'s `hooks` feature) that keep `cache_dirty` and the
+    /// `on_commit` callback in sync with writes to
+    /// [`CACHE_INVALIDATING_TABLES`] on the pooled connection: the update
+    /// hook notes, per row change, whether the in-progress transaction
+    /// touched one of those tables; the commit hook, on commit, checks that
+    /// note and — if set — marks the epoch dirty and fires `on_commit`.
+    #[cfg(feature = "hooks")]
+    fn register_hooks(&self, conn: &Connection) {
+        let touched = Arc::new(AtomicBool::new(false));
+        {
+            let touched = Arc::clone(&touched);
+            conn.update_hook(Some(
+                move |_action: rusqlite::hooks::Action, _db: &str, table: &str, _rowid: i64| {
+                    if CACHE_INVALIDATING_TABLES.contains(&table) {
+                        touched.store(true, Ordering::SeqCst);
+                    }
+                },
+            ));
+        }
+        let cache_dirty = Arc::clone(&self.cache_dirty);
+        let on_commit = Arc::clone(&self.on_commit);
+        conn.commit_hook(Some(move || {
+            if touched.swap(false, Ordering::SeqCst) {
+                cache_dirty.store(true, Ordering::SeqCst);
+                Python::with_gil(|py| {
+                    let callback = on_commit
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .as_ref()
+                        .map(|cb| cb.clone_ref(py));
+                    if let Some(callback) = callback {
+                        if let Err(err) = callback.call0(py) {
+                            err.print(py);
+                        }
+                    }
+                });
+            }
+            false
+        }));
+    }
+
+    /// Open a connection the same way [`Database::connect`] does, then set
+    /// `busy_timeout`/WAL mode so a reader never errors out waiting on the
+    /// single writer and vice versa. Used as the factory for both
+    /// [`Database::read_pool`] and [`Database::write_pool`].
+    fn open_pool_connection(&self) -> BombeResult<Connection> {
+        let conn = self.connect()?;
+        // `busy_timeout` is already set (and long) by `connect()`; WAL mode
+        // is what pooled connections additionally need so readers and the
+        // writer don't block each other.
+        conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+        Ok(conn)
+    }
+
+    /// Run `f` against a connection checked out of [`Database::read_pool`],
+    /// for read-only `SELECT`s (`list_*`/`recent_*`/`summarize_*`). `f`
+    /// returns `PyResult` rather than `BombeResult` (unlike
+    /// [`Database::with_pooled`]) because these callers build Python
+    /// dicts/lists from rows inline; map `rusqlite::Error` with
+    /// `BombeError::from` as the existing per-call bodies already do.
+    fn with_read_pool<T>(&self, f: impl FnOnce(&Connection) -> PyResult<T>) -> PyResult<T> {
+        let conn = self.read_pool.acquire(|| self.open_pool_connection())?;
+        f(&conn)
+    }
+
+    /// Run `f` against a connection checked out of [`Database::write_pool`],
+    /// for `INSERT`/`UPDATE`/`DELETE` writers outside the hot indexing path
+    /// (which instead uses [`Database::with_pooled`]). Takes `f` by `&mut
+    /// Connection` (not `&Connection`) so callers that need more than
+    /// `unchecked_transaction`'s hardcoded `Deferred` behavior — e.g.
+    /// [`Database::atomic_apply`]'s `transaction_with_behavior(Immediate)` —
+    /// can open one without a second, conflicting connection.
+    fn with_write_pool<T>(&self, f: impl FnOnce(&mut Connection) -> PyResult<T>) -> PyResult<T> {
+        let mut conn = self.write_pool.acquire(|| self.open_pool_connection())?;
+        f(&mut conn)
+    }
+
     // -- private helpers (matching Python private methods) -------------------
 
     fn _set_repo_meta(conn: &Connection, key: &str, value: &str) -> BombeResult<()> {
@@ -97,6 +636,205 @@ impl Database {
         )?;
         Ok(())
     }
+
+    /// Upsert a batch of file records against `conn`. Shared by the
+    /// standalone `upsert_files` pymethod and `index_files`, so both the
+    /// pooled-connection and transaction call sites compile the same SQL.
+    fn _upsert_files(conn: &Connection, records: &[Py<FileRecord>]) -> BombeResult<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let mut stmt = conn.prepare_cached(
+            "INSERT INTO files (path, language, content_hash, size_bytes) \
+             VALUES (?1, ?2, ?3, ?4) \
+             ON CONFLICT(path) DO UPDATE SET \
+                 language = excluded.language, \
+                 content_hash = excluded.content_hash, \
+                 size_bytes = excluded.size_bytes, \
+                 last_indexed_at = CURRENT_TIMESTAMP;",
+        )?;
+        Python::with_gil(|py| -> BombeResult<()> {
+            for rec_py in records {
+                let rec: PyRef<'_, FileRecord> = rec_py.bind(py).borrow();
+                stmt.execute(params![
+                    rec.path,
+                    rec.language,
+                    rec.content_hash,
+                    rec.size_bytes
+                ])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Replace all symbols (and their parameters + FTS entries) for
+    /// `file_path` against `conn`. Shared by `replace_file_symbols` and
+    /// `index_files`.
+    fn _replace_file_symbols(
+        conn: &Connection,
+        file_path: &str,
+        symbols: &[Py<SymbolRecord>],
+    ) -> BombeResult<()> {
+        // Collect old symbol ids for FTS cleanup.
+        let mut old_id_stmt =
+            conn.prepare_cached("SELECT id FROM symbols WHERE file_path = ?1;")?;
+        let old_ids: Vec<i64> = old_id_stmt
+            .query_map(params![file_path], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(old_id_stmt);
+
+        // Delete old FTS rows (best-effort).
+        for sid in &old_ids {
+            match conn.execute("DELETE FROM symbol_fts WHERE symbol_id = ?1;", params![sid]) {
+                Ok(_) => {}
+                Err(_) => break, // FTS table may not exist
+            }
+        }
+
+        // Delete old parameters and symbols.
+        conn.prepare_cached(
+            "DELETE FROM parameters WHERE symbol_id IN \
+             (SELECT id FROM symbols WHERE file_path = ?1);",
+        )?
+        .execute(params![file_path])?;
+        conn.prepare_cached("DELETE FROM symbols WHERE file_path = ?1;")?
+            .execute(params![file_path])?;
+
+        let mut insert_symbol = conn.prepare_cached(
+            "INSERT INTO symbols ( \
+                 name, qualified_name, kind, file_path, start_line, end_line, \
+                 signature, return_type, visibility, is_async, is_static, \
+                 parent_symbol_id, docstring, pagerank_score \
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14);",
+        )?;
+        let mut insert_param = conn.prepare_cached(
+            "INSERT INTO parameters (symbol_id, name, type, position, default_value) \
+             VALUES (?1, ?2, ?3, ?4, ?5);",
+        )?;
+        let mut insert_fts = conn.prepare_cached(
+            "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
+             VALUES (?1, ?2, ?3, ?4, ?5);",
+        )?;
+
+        // Dedup symbols by (qualified_name, file_path).
+        Python::with_gil(|py| -> BombeResult<()> {
+            let mut seen: HashSet<(String, String)> = HashSet::new();
+            for sym_py in symbols {
+                let sym: PyRef<'_, SymbolRecord> = sym_py.bind(py).borrow();
+                let key = (sym.qualified_name.clone(), sym.file_path.clone());
+                if seen.contains(&key) {
+                    continue;
+                }
+                seen.insert(key);
+
+                // Insert symbol.
+                insert_symbol.execute(params![
+                    sym.name,
+                    sym.qualified_name,
+                    sym.kind,
+                    sym.file_path,
+                    sym.start_line,
+                    sym.end_line,
+                    sym.signature,
+                    sym.return_type,
+                    sym.visibility,
+                    sym.is_async as i64,
+                    sym.is_static as i64,
+                    sym.parent_symbol_id,
+                    sym.docstring,
+                    sym.pagerank_score,
+                ])?;
+
+                let symbol_id = conn.last_insert_rowid();
+
+                // Insert parameters.
+                for param in &sym.parameters {
+                    insert_param.execute(params![
+                        symbol_id,
+                        param.name,
+                        param.type_,
+                        param.position,
+                        param.default_value,
+                    ])?;
+                }
+
+                // Insert FTS (best-effort).
+                let _ = insert_fts.execute(params![
+                    symbol_id,
+                    sym.name,
+                    sym.qualified_name,
+                    sym.docstring.as_deref().unwrap_or(""),
+                    sym.signature.as_deref().unwrap_or(""),
+                ]);
+            }
+            Ok(())
+        })
+    }
+
+    /// Replace all edges for `file_path` against `conn`. Shared by
+    /// `replace_file_edges` and `index_files`.
+    fn _replace_file_edges(
+        conn: &Connection,
+        file_path: &str,
+        edges: &[Py<EdgeRecord>],
+    ) -> BombeResult<()> {
+        conn.prepare_cached("DELETE FROM edges WHERE file_path = ?1;")?
+            .execute(params![file_path])?;
+
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR IGNORE INTO edges ( \
+                 source_id, target_id, source_type, target_type, relationship, \
+                 file_path, line_number, confidence \
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
+        )?;
+
+        Python::with_gil(|py| -> BombeResult<()> {
+            for edge_py in edges {
+                let e: PyRef<'_, EdgeRecord> = edge_py.bind(py).borrow();
+                stmt.execute(params![
+                    e.source_id,
+                    e.target_id,
+                    e.source_type,
+                    e.target_type,
+                    e.relationship,
+                    e.file_path,
+                    e.line_number,
+                    e.confidence,
+                ])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Replace all external dependency records for `file_path` against
+    /// `conn`. Shared by `replace_external_deps` and `index_files`.
+    fn _replace_external_deps(
+        conn: &Connection,
+        file_path: &str,
+        deps: &[Py<ExternalDepRecord>],
+    ) -> BombeResult<()> {
+        conn.prepare_cached("DELETE FROM external_deps WHERE file_path = ?1;")?
+            .execute(params![file_path])?;
+
+        let mut stmt = conn.prepare_cached(
+            "INSERT INTO external_deps (file_path, import_statement, module_name, line_number) \
+             VALUES (?1, ?2, ?3, ?4);",
+        )?;
+
+        Python::with_gil(|py| -> BombeResult<()> {
+            for dep_py in deps {
+                let d: PyRef<'_, ExternalDepRecord> = dep_py.bind(py).borrow();
+                stmt.execute(params![
+                    d.file_path,
+                    d.import_statement,
+                    d.module_name,
+                    d.line_number
+                ])?;
+            }
+            Ok(())
+        })
+    }
 }
 
 #[pymethods]
@@ -107,8 +845,23 @@ impl Database {
 
     /// Create a new `Database`.  The path is expanded and parent directories
     /// are created if they do not already exist (matching Python behaviour).
+    ///
+    /// `key`, when given, is the SQLCipher passphrase used to encrypt the
+    /// database at rest; it is only honoured when this crate is built with
+    /// the `sqlcipher` feature, and rejected otherwise so a key never
+    /// silently goes unused against a plain SQLite file.
+    ///
+    /// `connection_options`, when given, overrides the default
+    /// [`ConnectionOptions`] every connection [`Database::connect`] opens is
+    /// tuned with; defaults to `ConnectionOptions::default()` (a long
+    /// `busy_timeout`, `foreign_keys` on, journal mode untouched).
     #[new]
-    pub fn new(db_path: std::path::PathBuf) -> PyResult<Self> {
+    #[pyo3(signature = (db_path, key=None, connection_options=None))]
+    pub fn new(
+        db_path: std::path::PathBuf,
+        key: Option<String>,
+        connection_options: Option<ConnectionOptions>,
+    ) -> PyResult<Self> {
         let db_str = db_path.to_string_lossy();
         let expanded = expand_tilde(&db_str);
         let resolved = if expanded.is_absolute() {
@@ -121,7 +874,31 @@ impl Database {
         if let Some(parent) = resolved.parent() {
             std::fs::create_dir_all(parent).map_err(BombeError::Io)?;
         }
-        Ok(Self { db_path: resolved })
+        #[cfg(not(feature = "sqlcipher"))]
+        if key.is_some() {
+            return Err(BombeError::Database(
+                "an encryption key was supplied but this build was not compiled with \
+                 the `sqlcipher` feature"
+                    .to_string(),
+            )
+            .into());
+        }
+        Ok(Self {
+            db_path: resolved,
+            pooled: Mutex::new(None),
+            statement_cache_capacity: AtomicUsize::new(DEFAULT_STATEMENT_CACHE_CAPACITY),
+            connection_options: connection_options.unwrap_or_default(),
+            #[cfg(feature = "sqlcipher")]
+            passphrase: Mutex::new(key),
+            #[cfg(feature = "session")]
+            changeset: Mutex::new(None),
+            #[cfg(feature = "hooks")]
+            cache_dirty: Arc::new(AtomicBool::new(false)),
+            #[cfg(feature = "hooks")]
+            on_commit: Arc::new(Mutex::new(None)),
+            read_pool: ConnectionPool::new(DEFAULT_READ_POOL_SIZE),
+            write_pool: ConnectionPool::new(DEFAULT_WRITE_POOL_SIZE),
+        })
     }
 
     /// Return the resolved database path as a string.
@@ -130,6 +907,170 @@ impl Database {
         self.db_path.to_string_lossy().into_owned()
     }
 
+    /// Set the prepared-statement cache capacity used by the pooled
+    /// connection (see [`Database::with_pooled`]). Takes effect immediately
+    /// if a pooled connection already exists, and on next use otherwise.
+    fn set_statement_cache_capacity(&self, capacity: usize) -> PyResult<()> {
+        self.statement_cache_capacity
+            .store(capacity, Ordering::Relaxed);
+        let guard = self
+            .pooled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(conn) = guard.as_ref() {
+            conn.set_prepared_statement_cache_capacity(capacity);
+        }
+        Ok(())
+    }
+
+    /// Resize [`Database::read_pool`]'s idle-connection capacity. Already
+    /// checked-out connections are unaffected; the new cap applies the next
+    /// time a connection is released back to the pool.
+    fn set_read_pool_size(&self, max_size: usize) -> PyResult<()> {
+        self.read_pool.set_max_size(max_size);
+        Ok(())
+    }
+
+    /// Resize [`Database::write_pool`]'s idle-connection capacity. SQLite
+    /// allows only one writer at a time, so sizes above 1 have no effect on
+    /// throughput but can still be set (e.g. for testing).
+    fn set_write_pool_size(&self, max_size: usize) -> PyResult<()> {
+        self.write_pool.set_max_size(max_size);
+        Ok(())
+    }
+
+    /// Snapshot idle/active/max-size counts for both connection pools.
+    fn pool_stats(&self, py: Python<'_>) -> PyResult<Py<crate::models::PoolStats>> {
+        let (read_idle, read_active, read_max) = self.read_pool.stats();
+        let (write_idle, write_active, write_max) = self.write_pool.stats();
+        Py::new(
+            py,
+            crate::models::PoolStats {
+                read_idle: read_idle as i64,
+                read_active: read_active as i64,
+                read_max: read_max as i64,
+                write_idle: write_idle as i64,
+                write_active: write_active as i64,
+                write_max: write_max as i64,
+            },
+        )
+    }
+
+    /// Re-encrypt the database under `new_key` (SQLCipher's `PRAGMA rekey`)
+    /// and remember it for connections opened from now on. Requires the
+    /// `sqlcipher` feature.
+    #[cfg(feature = "sqlcipher")]
+    fn rekey(&self, new_key: &str) -> PyResult<()> {
+        let conn = self.connect()?;
+        conn.execute_batch(&format!("PRAGMA rekey = '{}';", new_key.replace('\'', "''")))
+            .map_err(BombeError::from)?;
+        *self
+            .passphrase
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(new_key.to_string());
+        // Drop the pooled connection so the next hot-path write reopens
+        // under the new key instead of reusing one keyed with the old one.
+        *self
+            .pooled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    fn rekey(&self, _new_key: &str) -> PyResult<()> {
+        Err(BombeError::Database(
+            "rekey requires this crate to be built with the `sqlcipher` feature".to_string(),
+        )
+        .into())
+    }
+
+    /// Start recording a changeset: attach a `sqlite3session` to the
+    /// connection the hot indexing-write path uses, tracking `files`,
+    /// `symbols`, `parameters`, `edges`, and `external_deps`. CRUD calls
+    /// (`upsert_files`, `replace_file_symbols`, `index_file`, ...) made
+    /// before the matching [`Database::finish_changeset`] are captured.
+    /// Requires the `session` feature.
+    #[cfg(feature = "session")]
+    fn begin_changeset(&self) -> PyResult<()> {
+        let conn = {
+            let mut pooled_guard = self
+                .pooled
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            match pooled_guard.take() {
+                Some(conn) => conn,
+                None => self.connect()?,
+            }
+        };
+        let recorder = crate::store::changeset::ChangesetRecorder::attach(conn)?;
+        *self
+            .changeset
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(recorder);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "session"))]
+    fn begin_changeset(&self) -> PyResult<()> {
+        Err(BombeError::Database(
+            "begin_changeset requires this crate to be built with the `session` feature"
+                .to_string(),
+        )
+        .into())
+    }
+
+    /// Stop recording and return the serialized changeset, restoring the
+    /// connection it was attached to as the normal pooled connection.
+    #[cfg(feature = "session")]
+    fn finish_changeset(&self) -> PyResult<Vec<u8>> {
+        let recorder = self
+            .changeset
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+            .ok_or_else(|| {
+                BombeError::Database(
+                    "no changeset in progress; call begin_changeset() first".to_string(),
+                )
+            })?;
+        let (bytes, conn) = recorder.finish()?;
+        *self
+            .pooled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(conn);
+        Ok(bytes)
+    }
+
+    #[cfg(not(feature = "session"))]
+    fn finish_changeset(&self) -> PyResult<Vec<u8>> {
+        Err(BombeError::Database(
+            "finish_changeset requires this crate to be built with the `session` feature"
+                .to_string(),
+        )
+        .into())
+    }
+
+    /// Apply a serialized changeset (as produced by [`Database::finish_changeset`]
+    /// on another `Database`) to this one, preferring the incoming row
+    /// except where that would violate a constraint (see
+    /// [`crate::store::changeset::apply_changeset`]). Requires the `session`
+    /// feature.
+    #[cfg(feature = "session")]
+    fn apply_changeset(&self, changeset: Vec<u8>) -> PyResult<()> {
+        self.with_pooled(|conn| crate::store::changeset::apply_changeset(conn, &changeset))?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "session"))]
+    fn apply_changeset(&self, _changeset: Vec<u8>) -> PyResult<()> {
+        Err(BombeError::Database(
+            "apply_changeset requires this crate to be built with the `session` feature"
+                .to_string(),
+        )
+        .into())
+    }
+
     /// Return a Python ``sqlite3.Connection`` to the database.
     ///
     /// This allows callers (tests, server code) to drop into raw SQL when the
@@ -139,6 +1080,17 @@ impl Database {
         let sqlite3 = py.import("sqlite3")?;
         let path_str = self.db_path.to_string_lossy().into_owned();
         let conn = sqlite3.call_method1("connect", (path_str,))?;
+        #[cfg(feature = "sqlcipher")]
+        {
+            let guard = self
+                .passphrase
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(passphrase) = guard.as_ref() {
+                let key_pragma = format!("PRAGMA key = '{}';", passphrase.replace('\'', "''"));
+                conn.call_method1("execute", (key_pragma,))?;
+            }
+        }
         // Set row_factory so rows are accessible by column name (matching
         // the previous Python Database.connect() behaviour).
         let row_cls = sqlite3.getattr("Row")?;
@@ -151,13 +1103,13 @@ impl Database {
     // Schema / meta
     // -----------------------------------------------------------------------
 
-    /// Initialise the database schema: set WAL mode, create all tables and
-    /// indexes, attempt FTS5 creation (ignoring errors for builds without it),
-    /// then run pending migrations.
+    /// Initialise the database schema: apply the startup PRAGMAs (WAL mode,
+    /// cache/mmap tuning, etc. — see [`schema::apply_startup_pragmas`]),
+    /// create all tables and indexes, attempt FTS5 creation (ignoring errors
+    /// for builds without it), then run pending migrations.
     pub fn init_schema(&self) -> PyResult<()> {
         let conn = self.connect()?;
-        conn.execute_batch("PRAGMA journal_mode = WAL;")
-            .map_err(BombeError::from)?;
+        schema::apply_startup_pragmas(&conn, &schema::PragmaConfig::default())?;
 
         for stmt in schema::SCHEMA_STATEMENTS {
             conn.execute_batch(stmt).map_err(BombeError::from)?;
@@ -166,12 +1118,20 @@ impl Database {
             // Best-effort: some SQLite builds lack FTS5.
             let _ = conn.execute_batch(stmt);
         }
-        schema::migrate_schema(&conn)?;
+        schema::migrate_schema(&conn, None)?;
         // rusqlite runs in autocommit mode by default, so DDL statements
         // are committed immediately.  No explicit COMMIT needed.
         Ok(())
     }
 
+    /// The schema version currently stored on disk (`repo_meta.schema_version`),
+    /// after applying any pending migrations. Matches [`schema::SCHEMA_VERSION`]
+    /// once `init_schema` has run to completion.
+    fn schema_version(&self) -> PyResult<i32> {
+        let conn = self.connect()?;
+        Ok(schema::get_schema_version(&conn))
+    }
+
     /// Execute an arbitrary SQL statement and return a list of Python dicts.
     ///
     /// `params` is a Python list of positional bind values.
@@ -252,8 +1212,37 @@ impl Database {
         Ok(())
     }
 
-    /// Return the current cache epoch (initialising to 1 if absent).
+    /// Return this repo's configured monorepo source roots (`repo_meta`
+    /// key `"source_roots"`, JSON-encoded), or an empty list if none are
+    /// configured — matching Pants' `subproject_roots`: the set of
+    /// directory/module prefixes under which this physical repo's separate
+    /// logical projects live.
+    pub fn get_source_roots(&self) -> PyResult<Vec<String>> {
+        match self.get_repo_meta("source_roots")? {
+            Some(raw) => Ok(serde_json::from_str(&raw).unwrap_or_default()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Set this repo's configured monorepo source roots.
+    pub fn set_source_roots(&self, roots: Vec<String>) -> PyResult<()> {
+        let encoded = serde_json::to_string(&roots)
+            .map_err(|e| BombeError::Database(format!("failed to encode source_roots: {e}")))?;
+        self.set_repo_meta("source_roots", &encoded)
+    }
+
+    /// Return the current cache epoch (initialising to 1 if absent).
+    ///
+    /// With the `hooks` feature, this first checks `cache_dirty` (set by the
+    /// pooled connection's commit hook whenever a commit wrote to
+    /// [`CACHE_INVALIDATING_TABLES`]) and transparently bumps the epoch if
+    /// it is set, so callers that forget to call `bump_cache_epoch()` by
+    /// hand still observe a fresh epoch after a write.
     fn get_cache_epoch(&self) -> PyResult<i64> {
+        #[cfg(feature = "hooks")]
+        if self.cache_dirty.swap(false, Ordering::SeqCst) {
+            return self.bump_cache_epoch();
+        }
         let value = self.get_repo_meta("cache_epoch")?;
         match value {
             None => {
@@ -284,41 +1273,36 @@ impl Database {
         Ok(next_epoch)
     }
 
+    /// Register `callback` to be invoked (with no arguments) after each
+    /// commit on the pooled connection that wrote to a
+    /// [`CACHE_INVALIDATING_TABLES`] table, so server code can react to
+    /// writes (e.g. push invalidation events) without polling
+    /// `get_cache_epoch()`. Replaces any previously registered callback.
+    /// Requires the `hooks` feature.
+    #[cfg(feature = "hooks")]
+    fn on_commit(&self, callback: PyObject) -> PyResult<()> {
+        *self
+            .on_commit
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(callback);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "hooks"))]
+    fn on_commit(&self, _callback: PyObject) -> PyResult<()> {
+        Err(BombeError::Database(
+            "on_commit requires this crate to be built with the `hooks` feature".to_string(),
+        )
+        .into())
+    }
+
     // -----------------------------------------------------------------------
     // File / symbol CRUD
     // -----------------------------------------------------------------------
 
     /// Upsert a batch of file records into the `files` table.
     fn upsert_files(&self, records: Vec<Py<FileRecord>>) -> PyResult<()> {
-        if records.is_empty() {
-            return Ok(());
-        }
-        let conn = self.connect()?;
-        let mut stmt = conn
-            .prepare(
-                "INSERT INTO files (path, language, content_hash, size_bytes) \
-                 VALUES (?1, ?2, ?3, ?4) \
-                 ON CONFLICT(path) DO UPDATE SET \
-                     language = excluded.language, \
-                     content_hash = excluded.content_hash, \
-                     size_bytes = excluded.size_bytes, \
-                     last_indexed_at = CURRENT_TIMESTAMP;",
-            )
-            .map_err(BombeError::from)?;
-
-        Python::with_gil(|py| -> PyResult<()> {
-            for rec_py in &records {
-                let rec: PyRef<'_, FileRecord> = rec_py.bind(py).borrow();
-                stmt.execute(params![
-                    rec.path,
-                    rec.language,
-                    rec.content_hash,
-                    rec.size_bytes
-                ])
-                .map_err(BombeError::from)?;
-            }
-            Ok(())
-        })?;
+        self.with_pooled(|conn| Self::_upsert_files(conn, &records))?;
         Ok(())
     }
 
@@ -329,147 +1313,13 @@ impl Database {
         file_path: &str,
         symbols: Vec<Py<SymbolRecord>>,
     ) -> PyResult<()> {
-        let conn = self.connect()?;
-
-        // Collect old symbol ids for FTS cleanup.
-        let mut old_id_stmt = conn
-            .prepare("SELECT id FROM symbols WHERE file_path = ?1;")
-            .map_err(BombeError::from)?;
-        let old_ids: Vec<i64> = old_id_stmt
-            .query_map(params![file_path], |row| row.get(0))
-            .map_err(BombeError::from)?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        // Delete old FTS rows (best-effort).
-        for sid in &old_ids {
-            match conn.execute("DELETE FROM symbol_fts WHERE symbol_id = ?1;", params![sid]) {
-                Ok(_) => {}
-                Err(_) => break, // FTS table may not exist
-            }
-        }
-
-        // Delete old parameters and symbols.
-        conn.execute(
-            "DELETE FROM parameters WHERE symbol_id IN \
-             (SELECT id FROM symbols WHERE file_path = ?1);",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute(
-            "DELETE FROM symbols WHERE file_path = ?1;",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
-
-        // Dedup symbols by (qualified_name, file_path).
-        Python::with_gil(|py| -> PyResult<()> {
-            let mut seen: HashSet<(String, String)> = HashSet::new();
-            for sym_py in &symbols {
-                let sym: PyRef<'_, SymbolRecord> = sym_py.bind(py).borrow();
-                let key = (sym.qualified_name.clone(), sym.file_path.clone());
-                if seen.contains(&key) {
-                    continue;
-                }
-                seen.insert(key);
-
-                // Insert symbol.
-                conn.execute(
-                    "INSERT INTO symbols ( \
-                         name, qualified_name, kind, file_path, start_line, end_line, \
-                         signature, return_type, visibility, is_async, is_static, \
-                         parent_symbol_id, docstring, pagerank_score \
-                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14);",
-                    params![
-                        sym.name,
-                        sym.qualified_name,
-                        sym.kind,
-                        sym.file_path,
-                        sym.start_line,
-                        sym.end_line,
-                        sym.signature,
-                        sym.return_type,
-                        sym.visibility,
-                        sym.is_async as i64,
-                        sym.is_static as i64,
-                        sym.parent_symbol_id,
-                        sym.docstring,
-                        sym.pagerank_score,
-                    ],
-                )
-                .map_err(BombeError::from)?;
-
-                let symbol_id = conn.last_insert_rowid();
-
-                // Insert parameters.
-                for param in &sym.parameters {
-                    conn.execute(
-                        "INSERT INTO parameters (symbol_id, name, type, position, default_value) \
-                         VALUES (?1, ?2, ?3, ?4, ?5);",
-                        params![
-                            symbol_id,
-                            param.name,
-                            param.type_,
-                            param.position,
-                            param.default_value,
-                        ],
-                    )
-                    .map_err(BombeError::from)?;
-                }
-
-                // Insert FTS (best-effort).
-                let _ = conn.execute(
-                    "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
-                     VALUES (?1, ?2, ?3, ?4, ?5);",
-                    params![
-                        symbol_id,
-                        sym.name,
-                        sym.qualified_name,
-                        sym.docstring.as_deref().unwrap_or(""),
-                        sym.signature.as_deref().unwrap_or(""),
-                    ],
-                );
-            }
-            Ok(())
-        })?;
+        self.with_pooled(|conn| Self::_replace_file_symbols(conn, file_path, &symbols))?;
         Ok(())
     }
 
     /// Replace all edges for a given file path.
     fn replace_file_edges(&self, file_path: &str, edges: Vec<Py<EdgeRecord>>) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "DELETE FROM edges WHERE file_path = ?1;",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
-
-        let mut stmt = conn
-            .prepare(
-                "INSERT OR IGNORE INTO edges ( \
-                     source_id, target_id, source_type, target_type, relationship, \
-                     file_path, line_number, confidence \
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
-            )
-            .map_err(BombeError::from)?;
-
-        Python::with_gil(|py| -> PyResult<()> {
-            for edge_py in &edges {
-                let e: PyRef<'_, EdgeRecord> = edge_py.bind(py).borrow();
-                stmt.execute(params![
-                    e.source_id,
-                    e.target_id,
-                    e.source_type,
-                    e.target_type,
-                    e.relationship,
-                    e.file_path,
-                    e.line_number,
-                    e.confidence,
-                ])
-                .map_err(BombeError::from)?;
-            }
-            Ok(())
-        })?;
+        self.with_pooled(|conn| Self::_replace_file_edges(conn, file_path, &edges))?;
         Ok(())
     }
 
@@ -479,145 +1329,160 @@ impl Database {
         file_path: &str,
         deps: Vec<Py<ExternalDepRecord>>,
     ) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "DELETE FROM external_deps WHERE file_path = ?1;",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
+        self.with_pooled(|conn| Self::_replace_external_deps(conn, file_path, &deps))?;
+        Ok(())
+    }
 
-        let mut stmt = conn
-            .prepare(
-                "INSERT INTO external_deps (file_path, import_statement, module_name, line_number) \
-                 VALUES (?1, ?2, ?3, ?4);",
-            )
-            .map_err(BombeError::from)?;
+    /// Atomically re-index a single file: upsert its file row and replace its
+    /// symbols, edges, and external deps in one `conn.unchecked_transaction()`,
+    /// so a crash or error midway rolls back the whole file instead of
+    /// leaving it half-deleted. Equivalent to `index_files` with one file and
+    /// `batch_size=1`.
+    fn index_file(
+        &self,
+        file_path: &str,
+        file_record: Py<FileRecord>,
+        symbols: Vec<Py<SymbolRecord>>,
+        edges: Vec<Py<EdgeRecord>>,
+        external_deps: Vec<Py<ExternalDepRecord>>,
+    ) -> PyResult<()> {
+        self.index_files(
+            vec![(
+                file_path.to_string(),
+                file_record,
+                symbols,
+                edges,
+                external_deps,
+            )],
+            1,
+        )
+    }
 
-        Python::with_gil(|py| -> PyResult<()> {
-            for dep_py in &deps {
-                let d: PyRef<'_, ExternalDepRecord> = dep_py.bind(py).borrow();
-                stmt.execute(params![
-                    d.file_path,
-                    d.import_statement,
-                    d.module_name,
-                    d.line_number
-                ])
-                .map_err(BombeError::from)?;
-            }
-            Ok(())
-        })?;
+    /// Atomically re-index many files, committing one transaction per
+    /// `batch_size` files (`batch_size=1` gives `index_file`'s per-file
+    /// atomicity; a larger batch amortizes commit overhead across a full
+    /// re-index while still bounding how much uncommitted work a crash mid
+    /// batch can lose). Readers never observe a partially-reindexed file:
+    /// each file's upsert/replace steps land in the same `BEGIN`/`COMMIT`.
+    #[pyo3(signature = (files, batch_size=1))]
+    #[allow(clippy::type_complexity)]
+    fn index_files(
+        &self,
+        files: Vec<(
+            String,
+            Py<FileRecord>,
+            Vec<Py<SymbolRecord>>,
+            Vec<Py<EdgeRecord>>,
+            Vec<Py<ExternalDepRecord>>,
+        )>,
+        batch_size: usize,
+    ) -> PyResult<()> {
+        let batch_size = batch_size.max(1);
+        for chunk in files.chunks(batch_size) {
+            self.with_pooled(|conn| {
+                let tx = conn.unchecked_transaction()?;
+                for (file_path, file_record, symbols, edges, external_deps) in chunk {
+                    Self::_upsert_files(&tx, std::slice::from_ref(file_record))?;
+                    Self::_replace_file_symbols(&tx, file_path, symbols)?;
+                    Self::_replace_file_edges(&tx, file_path, edges)?;
+                    Self::_replace_external_deps(&tx, file_path, external_deps)?;
+                }
+                tx.commit()?;
+                Ok(())
+            })?;
+        }
         Ok(())
     }
 
     /// Delete all graph data (symbols, edges, parameters, FTS, file row) for
     /// a given file path.
     fn delete_file_graph(&self, file_path: &str) -> PyResult<()> {
-        let conn = self.connect()?;
-
-        // Collect symbol ids for FTS cleanup.
-        let mut id_stmt = conn
-            .prepare("SELECT id FROM symbols WHERE file_path = ?1;")
-            .map_err(BombeError::from)?;
-        let symbol_ids: Vec<i64> = id_stmt
-            .query_map(params![file_path], |row| row.get(0))
-            .map_err(BombeError::from)?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        for sid in &symbol_ids {
-            match conn.execute("DELETE FROM symbol_fts WHERE symbol_id = ?1;", params![sid]) {
-                Ok(_) => {}
-                Err(_) => break,
+        self.with_pooled(|conn| {
+            // Collect symbol ids for FTS cleanup.
+            let mut id_stmt = conn.prepare_cached("SELECT id FROM symbols WHERE file_path = ?1;")?;
+            let symbol_ids: Vec<i64> = id_stmt
+                .query_map(params![file_path], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect();
+            drop(id_stmt);
+
+            for sid in &symbol_ids {
+                match conn.execute("DELETE FROM symbol_fts WHERE symbol_id = ?1;", params![sid]) {
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
             }
-        }
 
-        conn.execute(
-            "DELETE FROM edges WHERE file_path = ?1;",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute(
-            "DELETE FROM external_deps WHERE file_path = ?1;",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute(
-            "DELETE FROM parameters WHERE symbol_id IN \
-             (SELECT id FROM symbols WHERE file_path = ?1);",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute(
-            "DELETE FROM symbols WHERE file_path = ?1;",
-            params![file_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute("DELETE FROM files WHERE path = ?1;", params![file_path])
-            .map_err(BombeError::from)?;
+            conn.prepare_cached("DELETE FROM edges WHERE file_path = ?1;")?
+                .execute(params![file_path])?;
+            conn.prepare_cached("DELETE FROM external_deps WHERE file_path = ?1;")?
+                .execute(params![file_path])?;
+            conn.prepare_cached(
+                "DELETE FROM parameters WHERE symbol_id IN \
+                 (SELECT id FROM symbols WHERE file_path = ?1);",
+            )?
+            .execute(params![file_path])?;
+            conn.prepare_cached("DELETE FROM symbols WHERE file_path = ?1;")?
+                .execute(params![file_path])?;
+            conn.prepare_cached("DELETE FROM files WHERE path = ?1;")?
+                .execute(params![file_path])?;
+            Ok(())
+        })?;
         Ok(())
     }
 
     /// Rename a file in the index, moving all associated symbols, edges, and
     /// external deps to the new path.
     fn rename_file(&self, old_path: &str, new_path: &str) -> PyResult<()> {
-        let conn = self.connect()?;
-
-        // Fetch old file row.
-        let source = conn.query_row(
-            "SELECT language, content_hash, size_bytes, last_indexed_at \
-             FROM files WHERE path = ?1;",
-            params![old_path],
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<i64>>(2)?,
-                    row.get::<_, Option<String>>(3)?,
-                ))
-            },
-        );
-
-        let (language, content_hash, size_bytes, last_indexed_at) = match source {
-            Ok(v) => v,
-            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(()),
-            Err(e) => return Err(BombeError::from(e).into()),
-        };
-
-        conn.execute(
-            "INSERT INTO files (path, language, content_hash, size_bytes, last_indexed_at) \
-             VALUES (?1, ?2, ?3, ?4, ?5) \
-             ON CONFLICT(path) DO UPDATE SET \
-                 language = excluded.language, \
-                 content_hash = excluded.content_hash, \
-                 size_bytes = excluded.size_bytes, \
-                 last_indexed_at = excluded.last_indexed_at;",
-            params![
+        self.with_pooled(|conn| {
+            // Fetch old file row.
+            let source = conn
+                .prepare_cached(
+                    "SELECT language, content_hash, size_bytes, last_indexed_at \
+                     FROM files WHERE path = ?1;",
+                )?
+                .query_row(params![old_path], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<i64>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                    ))
+                });
+
+            let (language, content_hash, size_bytes, last_indexed_at) = match source {
+                Ok(v) => v,
+                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(()),
+                Err(e) => return Err(BombeError::from(e)),
+            };
+
+            conn.prepare_cached(
+                "INSERT INTO files (path, language, content_hash, size_bytes, last_indexed_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5) \
+                 ON CONFLICT(path) DO UPDATE SET \
+                     language = excluded.language, \
+                     content_hash = excluded.content_hash, \
+                     size_bytes = excluded.size_bytes, \
+                     last_indexed_at = excluded.last_indexed_at;",
+            )?
+            .execute(params![
                 new_path,
                 language,
                 content_hash,
                 size_bytes,
                 last_indexed_at
-            ],
-        )
-        .map_err(BombeError::from)?;
-
-        conn.execute(
-            "UPDATE symbols SET file_path = ?1 WHERE file_path = ?2;",
-            params![new_path, old_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute(
-            "UPDATE edges SET file_path = ?1 WHERE file_path = ?2;",
-            params![new_path, old_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute(
-            "UPDATE external_deps SET file_path = ?1 WHERE file_path = ?2;",
-            params![new_path, old_path],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute("DELETE FROM files WHERE path = ?1;", params![old_path])
-            .map_err(BombeError::from)?;
+            ])?;
+
+            conn.prepare_cached("UPDATE symbols SET file_path = ?1 WHERE file_path = ?2;")?
+                .execute(params![new_path, old_path])?;
+            conn.prepare_cached("UPDATE edges SET file_path = ?1 WHERE file_path = ?2;")?
+                .execute(params![new_path, old_path])?;
+            conn.prepare_cached("UPDATE external_deps SET file_path = ?1 WHERE file_path = ?2;")?
+                .execute(params![new_path, old_path])?;
+            conn.prepare_cached("DELETE FROM files WHERE path = ?1;")?
+                .execute(params![old_path])?;
+            Ok(())
+        })?;
         Ok(())
     }
 
@@ -626,8 +1491,23 @@ impl Database {
     // -----------------------------------------------------------------------
 
     /// Create a backup of the database at `destination` using the SQLite
-    /// backup API.  Returns the resolved path as a string.
-    fn backup_to(&self, destination: std::path::PathBuf) -> PyResult<String> {
+    /// online-backup API, copying `pages_per_step` pages at a time with a
+    /// `sleep_ms` pause in between so a large backup doesn't starve
+    /// concurrent indexing writers (which open their own writing
+    /// connections). If `progress` is given, it is called after every step
+    /// as `progress(remaining_pages, total_pages)`. The backup API restarts
+    /// the copy from scratch if the source is written mid-backup; the
+    /// returned [`crate::models::BackupReport`] reports whether that
+    /// happened, along with the final page count copied.
+    #[pyo3(signature = (destination, progress=None, pages_per_step=100, sleep_ms=10))]
+    fn backup_to(
+        &self,
+        py: Python<'_>,
+        destination: std::path::PathBuf,
+        progress: Option<PyObject>,
+        pages_per_step: i32,
+        sleep_ms: u64,
+    ) -> PyResult<Py<crate::models::BackupReport>> {
         let backup_path = expand_tilde(&destination.to_string_lossy());
         let resolved = if backup_path.is_absolute() {
             backup_path
@@ -642,12 +1522,57 @@ impl Database {
 
         let src_conn = self.connect()?;
         let mut dst_conn = Connection::open(&resolved).map_err(BombeError::from)?;
+        // SQLCipher needs the destination keyed before the backup API
+        // touches it, so the copy on disk is encrypted too.
+        #[cfg(feature = "sqlcipher")]
+        {
+            let guard = self
+                .passphrase
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(passphrase) = guard.as_ref() {
+                Self::apply_key(&dst_conn, passphrase)?;
+            }
+        }
         let backup =
             rusqlite::backup::Backup::new(&src_conn, &mut dst_conn).map_err(BombeError::from)?;
+
+        let pages_per_step = if pages_per_step <= 0 { -1 } else { pages_per_step };
+        let mut last_remaining: Option<i32> = None;
+        let mut restarted = false;
+        let mut pages_copied: i32 = 0;
         backup
-            .run_to_completion(100, std::time::Duration::from_millis(10), None)
+            .run_to_completion(
+                pages_per_step,
+                std::time::Duration::from_millis(sleep_ms),
+                Some(|step: rusqlite::backup::Progress| {
+                    // A restart resets SQLite's internal page count, which
+                    // shows up here as `remaining` jumping back up instead
+                    // of continuing to shrink.
+                    if let Some(last) = last_remaining {
+                        if step.remaining > last {
+                            restarted = true;
+                        }
+                    }
+                    last_remaining = Some(step.remaining);
+                    pages_copied = step.pagecount - step.remaining;
+                    if let Some(callback) = progress.as_ref() {
+                        if let Err(err) = callback.call1(py, (step.remaining, step.pagecount)) {
+                            err.print(py);
+                        }
+                    }
+                }),
+            )
             .map_err(BombeError::from)?;
-        Ok(resolved.to_string_lossy().into_owned())
+
+        Py::new(
+            py,
+            crate::models::BackupReport {
+                path: resolved.to_string_lossy().into_owned(),
+                pages_copied: pages_copied as i64,
+                restarted,
+            },
+        )
     }
 
     /// Restore the database from a backup file.
@@ -668,6 +1593,18 @@ impl Database {
             .into());
         }
         let src_conn = Connection::open(&resolved).map_err(BombeError::from)?;
+        // The backup file is expected to be keyed under the same passphrase
+        // as this `Database`, so key it before any other statement touches it.
+        #[cfg(feature = "sqlcipher")]
+        {
+            let guard = self
+                .passphrase
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(passphrase) = guard.as_ref() {
+                Self::apply_key(&src_conn, passphrase)?;
+            }
+        }
         let mut dst_conn = self.connect()?;
         let backup =
             rusqlite::backup::Backup::new(&src_conn, &mut dst_conn).map_err(BombeError::from)?;
@@ -688,14 +1625,15 @@ impl Database {
         local_snapshot: &str,
         payload_json: &str,
     ) -> PyResult<i64> {
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT INTO sync_queue(repo_id, local_snapshot, payload_json, status) \
-             VALUES (?1, ?2, ?3, 'queued');",
-            params![repo_id, local_snapshot, payload_json],
-        )
-        .map_err(BombeError::from)?;
-        Ok(conn.last_insert_rowid())
+        self.with_write_pool(|conn| {
+            conn.execute(
+                "INSERT INTO sync_queue(repo_id, local_snapshot, payload_json, status) \
+                 VALUES (?1, ?2, ?3, 'queued');",
+                params![repo_id, local_snapshot, payload_json],
+            )
+            .map_err(BombeError::from)?;
+            Ok(conn.last_insert_rowid())
+        })
     }
 
     /// List pending (queued or retry) sync deltas for a repo, up to `limit`.
@@ -707,84 +1645,209 @@ impl Database {
         limit: Option<i64>,
     ) -> PyResult<PyObject> {
         let effective_limit = std::cmp::max(1, limit.unwrap_or(20));
-        let conn = self.connect()?;
-        let mut stmt = conn
-            .prepare(
-                "SELECT id, repo_id, local_snapshot, payload_json, status, \
-                        attempt_count, last_error, created_at, updated_at \
-                 FROM sync_queue \
-                 WHERE repo_id = ?1 AND status IN ('queued', 'retry') \
-                 ORDER BY created_at ASC \
-                 LIMIT ?2;",
-            )
-            .map_err(BombeError::from)?;
+        self.with_read_pool(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, repo_id, local_snapshot, payload_json, status, \
+                            attempt_count, last_error, created_at, updated_at \
+                     FROM sync_queue \
+                     WHERE repo_id = ?1 AND status IN ('queued', 'retry') \
+                     ORDER BY created_at ASC \
+                     LIMIT ?2;",
+                )
+                .map_err(BombeError::from)?;
 
-        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
-        let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
-        let mut rows = stmt
-            .query(params![repo_id, effective_limit])
-            .map_err(BombeError::from)?;
-        while let Some(row) = rows.next().map_err(BombeError::from)? {
-            result.push(row_to_pydict(py, row, &col_names)?);
-        }
-        let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
-        Ok(list.into_any().unbind())
+            let col_names: Vec<String> =
+                stmt.column_names().iter().map(|s| s.to_string()).collect();
+            let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
+            let mut rows = stmt
+                .query(params![repo_id, effective_limit])
+                .map_err(BombeError::from)?;
+            while let Some(row) = rows.next().map_err(BombeError::from)? {
+                result.push(row_to_pydict(py, row, &col_names)?);
+            }
+            let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
+            Ok(list.into_any().unbind())
+        })
     }
 
     /// Mark a sync delta with a new status and optionally record an error.
-    #[pyo3(signature = (queue_id, status, last_error=None))]
+    ///
+    /// When `status` is `'retry'`, also schedules `next_attempt_at` using
+    /// exponential backoff with jitter:
+    /// `now + min(base_secs * 2^attempt_count, cap_secs) + rand(0..1s)`, so
+    /// `claim_sync_deltas` won't hand the row back out until the backoff
+    /// window has passed and concurrent retries don't all land at once.
+    #[pyo3(signature = (queue_id, status, last_error=None, base_secs=None, cap_secs=None))]
     fn mark_sync_delta_status(
         &self,
         queue_id: i64,
         status: &str,
         last_error: Option<&str>,
+        base_secs: Option<f64>,
+        cap_secs: Option<f64>,
     ) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "UPDATE sync_queue \
-             SET status = ?1, last_error = ?2, \
-                 attempt_count = attempt_count + 1, \
-                 updated_at = CURRENT_TIMESTAMP \
-             WHERE id = ?3;",
-            params![status, last_error, queue_id],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        let base_secs = base_secs.unwrap_or(2.0).max(0.0);
+        let cap_secs = cap_secs.unwrap_or(300.0).max(0.0);
+        self.with_write_pool(|conn| {
+            conn.execute(
+                "UPDATE sync_queue \
+                 SET status = ?1, last_error = ?2, \
+                     attempt_count = attempt_count + 1, \
+                     updated_at = CURRENT_TIMESTAMP, \
+                     next_attempt_at = CASE WHEN ?1 = 'retry' THEN \
+                         datetime( \
+                             'now', \
+                             '+' || ( \
+                                 MIN(?4, ?3 * (1 << MIN(attempt_count + 1, 20))) \
+                                 + (ABS(RANDOM() % 1000) / 1000.0) \
+                             ) || ' seconds' \
+                         ) \
+                     ELSE NULL END \
+                 WHERE id = ?5;",
+                params![status, last_error, base_secs, cap_secs, queue_id],
+            )
+            .map_err(BombeError::from)?;
+            Ok(())
+        })
+    }
+
+    /// Atomically claim up to `limit` claimable sync deltas for `repo_id` —
+    /// `queued` rows plus `retry` rows whose `next_attempt_at` has passed —
+    /// stamping them `in_flight` with `worker_id` and a lease expiring in
+    /// `lease_secs`, and returning the claimed rows. Safe for multiple
+    /// workers to call concurrently: the select-then-update happens inside
+    /// one transaction, so two callers never claim the same row.
+    #[pyo3(signature = (repo_id, worker_id, lease_secs, limit=None))]
+    fn claim_sync_deltas(
+        &self,
+        py: Python<'_>,
+        repo_id: &str,
+        worker_id: &str,
+        lease_secs: i64,
+        limit: Option<i64>,
+    ) -> PyResult<PyObject> {
+        let effective_limit = std::cmp::max(1, limit.unwrap_or(20));
+        let effective_lease = std::cmp::max(1, lease_secs);
+        self.with_write_pool(|conn| {
+            let tx = conn.unchecked_transaction().map_err(BombeError::from)?;
+
+            let claimable_ids: Vec<i64> = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT id FROM sync_queue \
+                         WHERE repo_id = ?1 \
+                           AND (status = 'queued' \
+                                OR (status = 'retry' \
+                                    AND (next_attempt_at IS NULL \
+                                         OR next_attempt_at <= datetime('now')))) \
+                         ORDER BY created_at ASC \
+                         LIMIT ?2;",
+                    )
+                    .map_err(BombeError::from)?;
+                stmt.query_map(params![repo_id, effective_limit], |row| row.get(0))
+                    .map_err(BombeError::from)?
+                    .filter_map(|r| r.ok())
+                    .collect()
+            };
+
+            for queue_id in &claimable_ids {
+                tx.execute(
+                    "UPDATE sync_queue \
+                     SET status = 'in_flight', \
+                         worker_id = ?1, \
+                         lease_expires_at = datetime('now', '+' || ?2 || ' seconds'), \
+                         updated_at = CURRENT_TIMESTAMP \
+                     WHERE id = ?3;",
+                    params![worker_id, effective_lease, queue_id],
+                )
+                .map_err(BombeError::from)?;
+            }
+
+            let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
+            if !claimable_ids.is_empty() {
+                // `claimable_ids` came from our own id-only SELECT above, so
+                // inlining them is safe (no user input reaches this SQL).
+                let id_list = claimable_ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let sql = format!(
+                    "SELECT id, repo_id, local_snapshot, payload_json, status, \
+                            attempt_count, last_error, worker_id, lease_expires_at, \
+                            next_attempt_at, created_at, updated_at \
+                     FROM sync_queue WHERE id IN ({id_list}) ORDER BY created_at ASC;"
+                );
+                let mut stmt = tx.prepare(&sql).map_err(BombeError::from)?;
+                let col_names: Vec<String> =
+                    stmt.column_names().iter().map(|s| s.to_string()).collect();
+                let mut rows = stmt.query([]).map_err(BombeError::from)?;
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    result.push(row_to_pydict(py, row, &col_names)?);
+                }
+            }
+
+            tx.commit().map_err(BombeError::from)?;
+            let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
+            Ok(list.into_any().unbind())
+        })
+    }
+
+    /// Revert `in_flight` rows whose lease has expired back to `retry`, so a
+    /// worker that died mid-processing doesn't strand them forever. Returns
+    /// the number of rows reclaimed.
+    fn reclaim_expired_leases(&self) -> PyResult<i64> {
+        self.with_write_pool(|conn| {
+            let changed = conn
+                .execute(
+                    "UPDATE sync_queue \
+                     SET status = 'retry', worker_id = NULL, lease_expires_at = NULL, \
+                         updated_at = CURRENT_TIMESTAMP \
+                     WHERE status = 'in_flight' \
+                       AND lease_expires_at IS NOT NULL \
+                       AND lease_expires_at <= datetime('now');",
+                    [],
+                )
+                .map_err(BombeError::from)?;
+            Ok(changed as i64)
+        })
     }
 
     /// Normalise sync queue entries with unknown statuses back to 'retry'.
     /// Returns the number of rows fixed.
     fn normalize_sync_queue_statuses(&self) -> PyResult<i64> {
-        let allowed: HashSet<&str> = ["queued", "retry", "pushed", "failed"]
+        let allowed: HashSet<&str> = ["queued", "retry", "pushed", "failed", "in_flight"]
             .iter()
             .copied()
             .collect();
-        let conn = self.connect()?;
-        let mut stmt = conn
-            .prepare("SELECT id, status FROM sync_queue;")
-            .map_err(BombeError::from)?;
-        let rows: Vec<(i64, String)> = stmt
-            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
-            .map_err(BombeError::from)?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        let to_fix: Vec<i64> = rows
-            .iter()
-            .filter(|(_, s)| !allowed.contains(s.as_str()))
-            .map(|(id, _)| *id)
-            .collect();
-
-        for queue_id in &to_fix {
-            conn.execute(
-                "UPDATE sync_queue \
-                 SET status = 'retry', updated_at = CURRENT_TIMESTAMP \
-                 WHERE id = ?1;",
-                params![queue_id],
-            )
-            .map_err(BombeError::from)?;
-        }
-        Ok(to_fix.len() as i64)
+        self.with_write_pool(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT id, status FROM sync_queue;")
+                .map_err(BombeError::from)?;
+            let rows: Vec<(i64, String)> = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(BombeError::from)?
+                .filter_map(|r| r.ok())
+                .collect();
+
+            let to_fix: Vec<i64> = rows
+                .iter()
+                .filter(|(_, s)| !allowed.contains(s.as_str()))
+                .map(|(id, _)| *id)
+                .collect();
+
+            for queue_id in &to_fix {
+                conn.execute(
+                    "UPDATE sync_queue \
+                     SET status = 'retry', updated_at = CURRENT_TIMESTAMP \
+                     WHERE id = ?1;",
+                    params![queue_id],
+                )
+                .map_err(BombeError::from)?;
+            }
+            Ok(to_fix.len() as i64)
+        })
     }
 
     // -----------------------------------------------------------------------
@@ -798,85 +1861,93 @@ impl Database {
         snapshot_id: &str,
         artifact_id: &str,
     ) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT INTO artifact_pins(repo_id, snapshot_id, artifact_id, pinned_at) \
-             VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP) \
-             ON CONFLICT(repo_id, snapshot_id) DO UPDATE SET \
-                 artifact_id = excluded.artifact_id, \
-                 pinned_at = excluded.pinned_at;",
-            params![repo_id, snapshot_id, artifact_id],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        self.with_write_pool(|conn| {
+            conn.execute(
+                "INSERT INTO artifact_pins(repo_id, snapshot_id, artifact_id, pinned_at, version) \
+                 VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP, 1) \
+                 ON CONFLICT(repo_id, snapshot_id) DO UPDATE SET \
+                     artifact_id = excluded.artifact_id, \
+                     pinned_at = excluded.pinned_at, \
+                     version = artifact_pins.version + 1;",
+                params![repo_id, snapshot_id, artifact_id],
+            )
+            .map_err(BombeError::from)?;
+            Ok(())
+        })
     }
 
     /// Get the artifact id pinned to a (repo_id, snapshot_id) pair, or None.
     fn get_artifact_pin(&self, repo_id: &str, snapshot_id: &str) -> PyResult<Option<String>> {
-        let conn = self.connect()?;
-        let result: Result<String, _> = conn.query_row(
-            "SELECT artifact_id FROM artifact_pins \
-             WHERE repo_id = ?1 AND snapshot_id = ?2 LIMIT 1;",
-            params![repo_id, snapshot_id],
-            |row| row.get(0),
-        );
-        match result {
-            Ok(v) => Ok(Some(v)),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(BombeError::from(e).into()),
-        }
+        self.with_read_pool(|conn| {
+            let result: Result<String, _> = conn.query_row(
+                "SELECT artifact_id FROM artifact_pins \
+                 WHERE repo_id = ?1 AND snapshot_id = ?2 LIMIT 1;",
+                params![repo_id, snapshot_id],
+                |row| row.get(0),
+            );
+            match result {
+                Ok(v) => Ok(Some(v)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(BombeError::from(e).into()),
+            }
+        })
     }
 
     /// Quarantine an artifact, recording the reason.
     fn quarantine_artifact(&self, artifact_id: &str, reason: &str) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT INTO artifact_quarantine(artifact_id, reason, quarantined_at) \
-             VALUES (?1, ?2, CURRENT_TIMESTAMP) \
-             ON CONFLICT(artifact_id) DO UPDATE SET \
-                 reason = excluded.reason, \
-                 quarantined_at = excluded.quarantined_at;",
-            params![artifact_id, reason],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        self.with_write_pool(|conn| {
+            conn.execute(
+                "INSERT INTO artifact_quarantine(artifact_id, reason, quarantined_at, version) \
+                 VALUES (?1, ?2, CURRENT_TIMESTAMP, 1) \
+                 ON CONFLICT(artifact_id) DO UPDATE SET \
+                     reason = excluded.reason, \
+                     quarantined_at = excluded.quarantined_at, \
+                     version = artifact_quarantine.version + 1;",
+                params![artifact_id, reason],
+            )
+            .map_err(BombeError::from)?;
+            Ok(())
+        })
     }
 
     /// Check whether an artifact has been quarantined.
     fn is_artifact_quarantined(&self, artifact_id: &str) -> PyResult<bool> {
-        let conn = self.connect()?;
-        let result: Result<String, _> = conn.query_row(
-            "SELECT artifact_id FROM artifact_quarantine WHERE artifact_id = ?1 LIMIT 1;",
-            params![artifact_id],
-            |row| row.get(0),
-        );
-        Ok(result.is_ok())
+        self.with_read_pool(|conn| {
+            let result: Result<String, _> = conn.query_row(
+                "SELECT artifact_id FROM artifact_quarantine WHERE artifact_id = ?1 LIMIT 1;",
+                params![artifact_id],
+                |row| row.get(0),
+            );
+            Ok(result.is_ok())
+        })
     }
 
     /// List quarantined artifacts, most recent first.
     #[pyo3(signature = (limit=None))]
     fn list_quarantined_artifacts(&self, py: Python<'_>, limit: Option<i64>) -> PyResult<PyObject> {
         let effective_limit = std::cmp::max(1, limit.unwrap_or(100));
-        let conn = self.connect()?;
-        let mut stmt = conn
-            .prepare(
-                "SELECT artifact_id, reason, quarantined_at \
-                 FROM artifact_quarantine \
-                 ORDER BY quarantined_at DESC \
-                 LIMIT ?1;",
-            )
-            .map_err(BombeError::from)?;
+        self.with_read_pool(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT artifact_id, reason, quarantined_at \
+                     FROM artifact_quarantine \
+                     ORDER BY quarantined_at DESC \
+                     LIMIT ?1;",
+                )
+                .map_err(BombeError::from)?;
 
-        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
-        let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
-        let mut rows = stmt
-            .query(params![effective_limit])
-            .map_err(BombeError::from)?;
-        while let Some(row) = rows.next().map_err(BombeError::from)? {
-            result.push(row_to_pydict(py, row, &col_names)?);
-        }
-        let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
-        Ok(list.into_any().unbind())
+            let col_names: Vec<String> =
+                stmt.column_names().iter().map(|s| s.to_string()).collect();
+            let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
+            let mut rows = stmt
+                .query(params![effective_limit])
+                .map_err(BombeError::from)?;
+            while let Some(row) = rows.next().map_err(BombeError::from)? {
+                result.push(row_to_pydict(py, row, &col_names)?);
+            }
+            let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
+            Ok(list.into_any().unbind())
+        })
     }
 
     // -----------------------------------------------------------------------
@@ -892,23 +1963,25 @@ impl Database {
         failure_count: i64,
         opened_at_utc: Option<&str>,
     ) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT INTO circuit_breakers(repo_id, state, failure_count, opened_at_utc) \
-             VALUES (?1, ?2, ?3, ?4) \
-             ON CONFLICT(repo_id) DO UPDATE SET \
-                 state = excluded.state, \
-                 failure_count = excluded.failure_count, \
-                 opened_at_utc = excluded.opened_at_utc;",
-            params![
-                repo_id,
-                state,
-                std::cmp::max(0, failure_count),
-                opened_at_utc
-            ],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        self.with_write_pool(|conn| {
+            conn.execute(
+                "INSERT INTO circuit_breakers(repo_id, state, failure_count, opened_at_utc, version) \
+                 VALUES (?1, ?2, ?3, ?4, 1) \
+                 ON CONFLICT(repo_id) DO UPDATE SET \
+                     state = excluded.state, \
+                     failure_count = excluded.failure_count, \
+                     opened_at_utc = excluded.opened_at_utc, \
+                     version = circuit_breakers.version + 1;",
+                params![
+                    repo_id,
+                    state,
+                    std::cmp::max(0, failure_count),
+                    opened_at_utc
+                ],
+            )
+            .map_err(BombeError::from)?;
+            Ok(())
+        })
     }
 
     /// Get the circuit breaker state for a repo, or None if not set.
@@ -917,33 +1990,416 @@ impl Database {
         py: Python<'_>,
         repo_id: &str,
     ) -> PyResult<Option<PyObject>> {
-        let conn = self.connect()?;
-        let result = conn.query_row(
-            "SELECT state, failure_count, opened_at_utc \
-             FROM circuit_breakers WHERE repo_id = ?1 LIMIT 1;",
-            params![repo_id],
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, i64>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                ))
-            },
-        );
-        match result {
-            Ok((state, failure_count, opened_at_utc)) => {
-                let dict = PyDict::new(py);
-                dict.set_item("state", state)?;
-                dict.set_item("failure_count", failure_count)?;
-                match opened_at_utc {
-                    Some(v) => dict.set_item("opened_at_utc", v)?,
-                    None => dict.set_item("opened_at_utc", py.None())?,
+        self.with_read_pool(|conn| {
+            let result = conn.query_row(
+                "SELECT state, failure_count, opened_at_utc \
+                 FROM circuit_breakers WHERE repo_id = ?1 LIMIT 1;",
+                params![repo_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                },
+            );
+            match result {
+                Ok((state, failure_count, opened_at_utc)) => {
+                    let dict = PyDict::new(py);
+                    dict.set_item("state", state)?;
+                    dict.set_item("failure_count", failure_count)?;
+                    match opened_at_utc {
+                        Some(v) => dict.set_item("opened_at_utc", v)?,
+                        None => dict.set_item("opened_at_utc", py.None())?,
+                    }
+                    Ok(Some(dict.into_any().unbind()))
                 }
-                Ok(Some(dict.into_any().unbind()))
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(BombeError::from(e).into()),
             }
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(BombeError::from(e).into()),
-        }
+        })
+    }
+
+    /// Record the outcome of a call made against `repo_id` and let the
+    /// breaker's state machine react server-side, inside one transaction so
+    /// concurrent tool calls can't race a read-modify-write.
+    ///
+    /// A success always closes the circuit and zeroes `failure_count`. A
+    /// failure increments `failure_count`; once it reaches `threshold` (or
+    /// the call fails while the circuit is `half_open`) the circuit opens
+    /// and `opened_at_utc` is (re)stamped. `threshold` defaults to the value
+    /// already stored for this repo (or 5 for a never-seen repo) when not
+    /// given, so later calls don't need to keep repeating it.
+    #[pyo3(signature = (repo_id, success, threshold=None))]
+    fn record_circuit_outcome(
+        &self,
+        py: Python<'_>,
+        repo_id: &str,
+        success: bool,
+        threshold: Option<i64>,
+    ) -> PyResult<PyObject> {
+        self.with_write_pool(|conn| {
+            let tx = conn.unchecked_transaction().map_err(BombeError::from)?;
+
+            let existing: Result<(String, i64, i64), _> = tx.query_row(
+                "SELECT state, failure_count, threshold FROM circuit_breakers \
+                 WHERE repo_id = ?1 LIMIT 1;",
+                params![repo_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            );
+            let (state, failure_count, stored_threshold) = match existing {
+                Ok(row) => row,
+                Err(rusqlite::Error::QueryReturnedNoRows) => ("closed".to_string(), 0, 5),
+                Err(e) => return Err(BombeError::from(e).into()),
+            };
+            let effective_threshold = threshold.unwrap_or(stored_threshold).max(1);
+
+            let (new_state, new_failure_count) = if success {
+                ("closed", 0)
+            } else {
+                let bumped = failure_count + 1;
+                if state == "half_open" || bumped >= effective_threshold {
+                    ("open", bumped)
+                } else {
+                    (state.as_str(), bumped)
+                }
+            };
+            // `half_open` is only ever entered by `evaluate_circuit`, so an
+            // outcome always leaves the circuit either closed or open, and
+            // `opened_at_utc` follows suit: stamped on open, cleared otherwise.
+            let opened_at_expr = if new_state == "open" {
+                "CURRENT_TIMESTAMP"
+            } else {
+                "NULL"
+            };
+
+            tx.execute(
+                &format!(
+                    "INSERT INTO circuit_breakers( \
+                         repo_id, state, failure_count, opened_at_utc, threshold, \
+                         half_open_probes_used \
+                     ) VALUES (?1, '{new_state}', ?2, {opened_at_expr}, ?3, 0) \
+                     ON CONFLICT(repo_id) DO UPDATE SET \
+                         state = '{new_state}', \
+                         failure_count = ?2, \
+                         opened_at_utc = {opened_at_expr}, \
+                         threshold = ?3, \
+                         half_open_probes_used = 0;",
+                ),
+                params![repo_id, new_failure_count, effective_threshold],
+            )
+            .map_err(BombeError::from)?;
+            tx.commit().map_err(BombeError::from)?;
+
+            let dict = PyDict::new(py);
+            dict.set_item("state", new_state)?;
+            dict.set_item("failure_count", new_failure_count)?;
+            dict.set_item("threshold", effective_threshold)?;
+            Ok(dict.into_any().unbind())
+        })
+    }
+
+    /// Return whether a call against `repo_id` is currently permitted,
+    /// transitioning the circuit as a side effect: an `open` circuit whose
+    /// cooldown has elapsed atomically becomes `half_open` and allows its
+    /// first probe; a `half_open` circuit allows probes up to
+    /// `half_open_probe_count` before denying further calls until a probe
+    /// outcome is recorded. `cooldown_secs`/`half_open_probe_count` default
+    /// to whatever was last persisted for this repo (or 60s / 1 probe for a
+    /// never-seen repo) and, when given, overwrite the stored values.
+    #[pyo3(signature = (repo_id, cooldown_secs=None, half_open_probe_count=None))]
+    fn evaluate_circuit(
+        &self,
+        repo_id: &str,
+        cooldown_secs: Option<i64>,
+        half_open_probe_count: Option<i64>,
+    ) -> PyResult<bool> {
+        self.with_write_pool(|conn| {
+            let tx = conn.unchecked_transaction().map_err(BombeError::from)?;
+
+            let existing: Result<(String, i64, i64), _> = tx.query_row(
+                "SELECT state, cooldown_secs, half_open_probe_count FROM circuit_breakers \
+                 WHERE repo_id = ?1 LIMIT 1;",
+                params![repo_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            );
+            let (state, stored_cooldown, stored_probe_count) = match existing {
+                Ok(row) => row,
+                // No breaker row yet means nothing has ever failed for this
+                // repo: the circuit is implicitly closed.
+                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(true),
+                Err(e) => return Err(BombeError::from(e).into()),
+            };
+            let effective_cooldown = cooldown_secs.unwrap_or(stored_cooldown).max(0);
+            let effective_probe_count = half_open_probe_count.unwrap_or(stored_probe_count).max(1);
+
+            tx.execute(
+                "UPDATE circuit_breakers SET cooldown_secs = ?2, half_open_probe_count = ?3 \
+                 WHERE repo_id = ?1;",
+                params![repo_id, effective_cooldown, effective_probe_count],
+            )
+            .map_err(BombeError::from)?;
+
+            let permitted = match state.as_str() {
+                "half_open" => {
+                    let probes_used: i64 = tx
+                        .query_row(
+                            "SELECT half_open_probes_used FROM circuit_breakers \
+                             WHERE repo_id = ?1;",
+                            params![repo_id],
+                            |row| row.get(0),
+                        )
+                        .map_err(BombeError::from)?;
+                    if probes_used < effective_probe_count {
+                        tx.execute(
+                            "UPDATE circuit_breakers \
+                             SET half_open_probes_used = half_open_probes_used + 1 \
+                             WHERE repo_id = ?1;",
+                            params![repo_id],
+                        )
+                        .map_err(BombeError::from)?;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                "open" => {
+                    let cooldown_elapsed: bool = tx
+                        .query_row(
+                            "SELECT (julianday('now') - julianday(opened_at_utc)) * 86400.0 \
+                                    > ?2 \
+                             FROM circuit_breakers WHERE repo_id = ?1;",
+                            params![repo_id, effective_cooldown],
+                            |row| row.get(0),
+                        )
+                        .map_err(BombeError::from)?;
+                    if cooldown_elapsed {
+                        tx.execute(
+                            "UPDATE circuit_breakers \
+                             SET state = 'half_open', half_open_probes_used = 1 \
+                             WHERE repo_id = ?1;",
+                            params![repo_id],
+                        )
+                        .map_err(BombeError::from)?;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                // "closed" (or any unrecognised state): nothing is tripped.
+                _ => true,
+            };
+
+            tx.commit().map_err(BombeError::from)?;
+            Ok(permitted)
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Atomic multi-table writes
+    // -----------------------------------------------------------------------
+
+    /// Deno-KV-style `AtomicWrite`: check a set of per-row versions and, only
+    /// if every check passes, apply an ordered list of mutations in one
+    /// transaction.
+    ///
+    /// `checks` is `(table, key, expected_version)`, where `expected_version`
+    /// of `None` means "this row must not exist". `table` is one of
+    /// `"artifact_pins"` (key `"<repo_id>::<snapshot_id>"`),
+    /// `"artifact_quarantine"` (key `artifact_id`), or `"circuit_breakers"`
+    /// (key `repo_id`) — the three tables that carry a `version` column.
+    ///
+    /// `mutations` is `(tag, key, arg1, arg2, arg3)`, `tag` one of:
+    /// - `"pin"`: key `"<repo_id>::<snapshot_id>"`, arg1 = artifact_id
+    /// - `"unpin"`: key `"<repo_id>::<snapshot_id>"`
+    /// - `"quarantine"`: key = artifact_id, arg1 = reason
+    /// - `"enqueue_delta"`: key = repo_id, arg1 = local_snapshot, arg2 = payload_json
+    /// - `"set_breaker"`: key = repo_id, arg1 = state, arg2 = failure_count, arg3 = opened_at_utc
+    ///
+    /// If any check fails, rolls back and returns `{"committed": false,
+    /// "failed_check": "<table>:<key>"}` without applying any mutation.
+    /// Otherwise applies every mutation, bumps the touched rows' versions,
+    /// commits, and returns `{"committed": true, "versionstamps": {"<table>:<key>": version, ...}}`.
+    #[allow(clippy::type_complexity)]
+    fn atomic_apply(
+        &self,
+        py: Python<'_>,
+        checks: Vec<(String, String, Option<i64>)>,
+        mutations: Vec<(String, String, Option<String>, Option<String>, Option<String>)>,
+    ) -> PyResult<PyObject> {
+        self.with_write_pool(|conn| {
+            // `Immediate`, not `unchecked_transaction`'s `Deferred`: this is a
+            // compare-and-swap, so the write lock must be held from `BEGIN`
+            // through every check, not acquired lazily at the first write —
+            // otherwise two concurrent `atomic_apply` calls (the write pool
+            // can hold more than one live connection) could both read the
+            // same pre-write `actual_version` under a shared lock before
+            // either takes the write lock, and the second to commit would
+            // apply its mutation against an already-stale version it
+            // thought it had validated. Same reasoning as
+            // `ShardCatalog::refresh_exported_symbols`'s
+            // `transaction_with_behavior(Immediate)`.
+            let tx = conn
+                .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+                .map_err(BombeError::from)?;
+
+            for (table, key, expected_version) in &checks {
+                let actual_version = match table.as_str() {
+                    "artifact_pins" => {
+                        let (repo_id, snapshot_id) = split_pin_key(key)?;
+                        let result: Result<i64, _> = tx.query_row(
+                            "SELECT version FROM artifact_pins \
+                             WHERE repo_id = ?1 AND snapshot_id = ?2;",
+                            params![repo_id, snapshot_id],
+                            |row| row.get(0),
+                        );
+                        result.ok()
+                    }
+                    "artifact_quarantine" => {
+                        let result: Result<i64, _> = tx.query_row(
+                            "SELECT version FROM artifact_quarantine WHERE artifact_id = ?1;",
+                            params![key],
+                            |row| row.get(0),
+                        );
+                        result.ok()
+                    }
+                    "circuit_breakers" => {
+                        let result: Result<i64, _> = tx.query_row(
+                            "SELECT version FROM circuit_breakers WHERE repo_id = ?1;",
+                            params![key],
+                            |row| row.get(0),
+                        );
+                        result.ok()
+                    }
+                    other => {
+                        return Err(BombeError::Query(format!(
+                            "atomic_apply: unknown check table '{other}'"
+                        ))
+                        .into());
+                    }
+                };
+
+                if actual_version != *expected_version {
+                    tx.rollback().map_err(BombeError::from)?;
+                    let result = PyDict::new(py);
+                    result.set_item("committed", false)?;
+                    result.set_item("failed_check", format!("{table}:{key}"))?;
+                    return Ok(result.into_any().unbind());
+                }
+            }
+
+            let mut versionstamps: Vec<(String, i64)> = Vec::new();
+            for (tag, key, arg1, arg2, arg3) in &mutations {
+                match tag.as_str() {
+                    "pin" => {
+                        let (repo_id, snapshot_id) = split_pin_key(key)?;
+                        let artifact_id = arg1.as_deref().unwrap_or_default();
+                        tx.execute(
+                            "INSERT INTO artifact_pins(repo_id, snapshot_id, artifact_id, pinned_at, version) \
+                             VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP, 1) \
+                             ON CONFLICT(repo_id, snapshot_id) DO UPDATE SET \
+                                 artifact_id = excluded.artifact_id, \
+                                 pinned_at = excluded.pinned_at, \
+                                 version = artifact_pins.version + 1;",
+                            params![repo_id, snapshot_id, artifact_id],
+                        )
+                        .map_err(BombeError::from)?;
+                        let new_version: i64 = tx
+                            .query_row(
+                                "SELECT version FROM artifact_pins \
+                                 WHERE repo_id = ?1 AND snapshot_id = ?2;",
+                                params![repo_id, snapshot_id],
+                                |row| row.get(0),
+                            )
+                            .map_err(BombeError::from)?;
+                        versionstamps.push((format!("artifact_pins:{key}"), new_version));
+                    }
+                    "unpin" => {
+                        let (repo_id, snapshot_id) = split_pin_key(key)?;
+                        tx.execute(
+                            "DELETE FROM artifact_pins WHERE repo_id = ?1 AND snapshot_id = ?2;",
+                            params![repo_id, snapshot_id],
+                        )
+                        .map_err(BombeError::from)?;
+                        versionstamps.push((format!("artifact_pins:{key}"), 0));
+                    }
+                    "quarantine" => {
+                        let reason = arg1.as_deref().unwrap_or_default();
+                        tx.execute(
+                            "INSERT INTO artifact_quarantine(artifact_id, reason, quarantined_at, version) \
+                             VALUES (?1, ?2, CURRENT_TIMESTAMP, 1) \
+                             ON CONFLICT(artifact_id) DO UPDATE SET \
+                                 reason = excluded.reason, \
+                                 quarantined_at = excluded.quarantined_at, \
+                                 version = artifact_quarantine.version + 1;",
+                            params![key, reason],
+                        )
+                        .map_err(BombeError::from)?;
+                        let new_version: i64 = tx
+                            .query_row(
+                                "SELECT version FROM artifact_quarantine WHERE artifact_id = ?1;",
+                                params![key],
+                                |row| row.get(0),
+                            )
+                            .map_err(BombeError::from)?;
+                        versionstamps.push((format!("artifact_quarantine:{key}"), new_version));
+                    }
+                    "enqueue_delta" => {
+                        let local_snapshot = arg1.as_deref().unwrap_or_default();
+                        let payload_json = arg2.as_deref().unwrap_or("{}");
+                        tx.execute(
+                            "INSERT INTO sync_queue(repo_id, local_snapshot, payload_json, status) \
+                             VALUES (?1, ?2, ?3, 'queued');",
+                            params![key, local_snapshot, payload_json],
+                        )
+                        .map_err(BombeError::from)?;
+                        versionstamps.push((format!("sync_queue:{}", tx.last_insert_rowid()), 1));
+                    }
+                    "set_breaker" => {
+                        let state = arg1.as_deref().unwrap_or("closed");
+                        let failure_count: i64 =
+                            arg2.as_deref().and_then(|s| s.parse().ok()).unwrap_or(0);
+                        let opened_at_utc = arg3.as_deref();
+                        tx.execute(
+                            "INSERT INTO circuit_breakers(repo_id, state, failure_count, opened_at_utc, version) \
+                             VALUES (?1, ?2, ?3, ?4, 1) \
+                             ON CONFLICT(repo_id) DO UPDATE SET \
+                                 state = excluded.state, \
+                                 failure_count = excluded.failure_count, \
+                                 opened_at_utc = excluded.opened_at_utc, \
+                                 version = circuit_breakers.version + 1;",
+                            params![key, state, std::cmp::max(0, failure_count), opened_at_utc],
+                        )
+                        .map_err(BombeError::from)?;
+                        let new_version: i64 = tx
+                            .query_row(
+                                "SELECT version FROM circuit_breakers WHERE repo_id = ?1;",
+                                params![key],
+                                |row| row.get(0),
+                            )
+                            .map_err(BombeError::from)?;
+                        versionstamps.push((format!("circuit_breakers:{key}"), new_version));
+                    }
+                    other => {
+                        return Err(BombeError::Query(format!(
+                            "atomic_apply: unknown mutation tag '{other}'"
+                        ))
+                        .into());
+                    }
+                }
+            }
+
+            tx.commit().map_err(BombeError::from)?;
+
+            let result = PyDict::new(py);
+            result.set_item("committed", true)?;
+            let stamps = PyDict::new(py);
+            for (k, v) in &versionstamps {
+                stamps.set_item(k, v)?;
+            }
+            result.set_item("versionstamps", stamps)?;
+            Ok(result.into_any().unbind())
+        })
     }
 
     // -----------------------------------------------------------------------
@@ -976,14 +2432,15 @@ impl Database {
             }
             None => None,
         };
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT INTO sync_events(repo_id, level, event_type, detail_json) \
-             VALUES (?1, ?2, ?3, ?4);",
-            params![repo_id, level, event_type, detail_json],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        self.with_write_pool(|conn| {
+            conn.execute(
+                "INSERT INTO sync_events(repo_id, level, event_type, detail_json) \
+                 VALUES (?1, ?2, ?3, ?4);",
+                params![repo_id, level, event_type, detail_json],
+            )
+            .map_err(BombeError::from)?;
+            Ok(())
+        })
     }
 
     /// Record a tool metric observation.
@@ -999,22 +2456,52 @@ impl Database {
         result_size: Option<i64>,
         error_message: Option<&str>,
     ) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT INTO tool_metrics(repo_id, tool_name, latency_ms, success, mode, result_size, error_message) \
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);",
-            params![
-                repo_id,
-                tool_name,
-                latency_ms,
-                success as i64,
-                mode,
-                result_size,
-                error_message,
-            ],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        self.with_write_pool(|conn| {
+            let tx = conn.unchecked_transaction().map_err(BombeError::from)?;
+            tx.execute(
+                "INSERT INTO tool_metrics(repo_id, tool_name, latency_ms, success, mode, result_size, error_message) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);",
+                params![
+                    repo_id,
+                    tool_name,
+                    latency_ms,
+                    success as i64,
+                    mode,
+                    result_size,
+                    error_message,
+                ],
+            )
+            .map_err(BombeError::from)?;
+
+            // Fold the sample into this (tool_name, mode)'s persisted
+            // t-digest, so `tool_latency_quantiles` stays O(centroids)
+            // regardless of how many rows `tool_metrics` accumulates.
+            let existing_digest: Result<String, _> = tx.query_row(
+                "SELECT digest_json FROM tool_latency_digests \
+                 WHERE tool_name = ?1 AND mode = ?2;",
+                params![tool_name, mode],
+                |row| row.get(0),
+            );
+            let mut digest = match existing_digest {
+                Ok(json) => crate::store::tdigest::TDigest::from_json(&json)?,
+                Err(rusqlite::Error::QueryReturnedNoRows) => crate::store::tdigest::TDigest::new(),
+                Err(e) => return Err(BombeError::from(e).into()),
+            };
+            digest.add(latency_ms);
+            tx.execute(
+                "INSERT INTO tool_latency_digests(tool_name, mode, digest_json, sample_count, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP) \
+                 ON CONFLICT(tool_name, mode) DO UPDATE SET \
+                     digest_json = excluded.digest_json, \
+                     sample_count = excluded.sample_count, \
+                     updated_at = CURRENT_TIMESTAMP;",
+                params![tool_name, mode, digest.to_json(), digest.total_count() as i64],
+            )
+            .map_err(BombeError::from)?;
+
+            tx.commit().map_err(BombeError::from)?;
+            Ok(())
+        })
     }
 
     /// Retrieve recent tool metrics for a given tool, most recent first.
@@ -1026,28 +2513,252 @@ impl Database {
         limit: Option<i64>,
     ) -> PyResult<PyObject> {
         let effective_limit = std::cmp::max(1, limit.unwrap_or(50));
-        let conn = self.connect()?;
-        let mut stmt = conn
-            .prepare(
-                "SELECT repo_id, tool_name, latency_ms, success, mode, \
-                        result_size, error_message, created_at \
-                 FROM tool_metrics \
-                 WHERE tool_name = ?1 \
-                 ORDER BY created_at DESC \
-                 LIMIT ?2;",
-            )
-            .map_err(BombeError::from)?;
+        self.with_read_pool(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT repo_id, tool_name, latency_ms, success, mode, \
+                            result_size, error_message, created_at \
+                     FROM tool_metrics \
+                     WHERE tool_name = ?1 \
+                     ORDER BY created_at DESC \
+                     LIMIT ?2;",
+                )
+                .map_err(BombeError::from)?;
+
+            let col_names: Vec<String> =
+                stmt.column_names().iter().map(|s| s.to_string()).collect();
+            let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
+            let mut rows = stmt
+                .query(params![tool_name, effective_limit])
+                .map_err(BombeError::from)?;
+            while let Some(row) = rows.next().map_err(BombeError::from)? {
+                result.push(row_to_pydict(py, row, &col_names)?);
+            }
+            let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
+            Ok(list.into_any().unbind())
+        })
+    }
+
+    /// Return p50/p95/p99-style latency quantiles for `tool_name`, read
+    /// from the persisted t-digests in `tool_latency_digests` — O(centroids)
+    /// regardless of how many `tool_metrics` rows contributed to them.
+    ///
+    /// Merges the digests of every `mode` observed for this tool into one
+    /// combined view. When `window_secs` is given, only digests updated
+    /// within that many seconds are included (a digest updates every time a
+    /// matching `record_tool_metric` call lands, so this is a coarse
+    /// recency filter rather than a true sliding window over individual
+    /// samples — the digest itself doesn't expire old data). `quantiles`
+    /// defaults to `[0.5, 0.95, 0.99]`.
+    #[pyo3(signature = (tool_name, window_secs=None, quantiles=None))]
+    fn tool_latency_quantiles(
+        &self,
+        py: Python<'_>,
+        tool_name: &str,
+        window_secs: Option<i64>,
+        quantiles: Option<Vec<f64>>,
+    ) -> PyResult<PyObject> {
+        let quantiles = quantiles.unwrap_or_else(|| vec![0.5, 0.95, 0.99]);
+        self.with_read_pool(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT mode, digest_json FROM tool_latency_digests \
+                     WHERE tool_name = ?1 \
+                       AND (?2 IS NULL \
+                            OR updated_at >= datetime('now', '-' || ?2 || ' seconds'));",
+                )
+                .map_err(BombeError::from)?;
+
+            let mut combined = crate::store::tdigest::TDigest::new();
+            let mut modes: Vec<String> = Vec::new();
+            let mut rows = stmt
+                .query(params![tool_name, window_secs])
+                .map_err(BombeError::from)?;
+            while let Some(row) = rows.next().map_err(BombeError::from)? {
+                let mode: String = row.get(0).map_err(BombeError::from)?;
+                let digest_json: String = row.get(1).map_err(BombeError::from)?;
+                combined.merge_from(&crate::store::tdigest::TDigest::from_json(&digest_json)?);
+                modes.push(mode);
+            }
+
+            let result = PyDict::new(py);
+            result.set_item("tool_name", tool_name)?;
+            result.set_item("modes", modes)?;
+            result.set_item("sample_count", combined.total_count() as i64)?;
+            let quantile_dict = PyDict::new(py);
+            for q in &quantiles {
+                let label = format!("p{}", (q * 100.0).round() as i64);
+                match combined.quantile(*q) {
+                    Some(v) => quantile_dict.set_item(label, v)?,
+                    None => quantile_dict.set_item(label, py.None())?,
+                }
+            }
+            result.set_item("quantiles", quantile_dict)?;
+            Ok(result.into_any().unbind())
+        })
+    }
+
+    /// Render `tool_metrics`, `sync_events`, `sync_queue`, and
+    /// `artifact_quarantine` as a Prometheus text-exposition-format string,
+    /// suitable for the Python layer to serve on a `/metrics` endpoint.
+    ///
+    /// Emits:
+    /// - `bombe_tool_latency_ms` (histogram, `le` buckets in milliseconds,
+    ///   labelled by `tool_name`/`mode`)
+    /// - `bombe_tool_calls_total{tool_name,mode,success}` (counter)
+    /// - `bombe_sync_events_total{repo_id,level,event_type}` (counter)
+    /// - `bombe_sync_queue_depth{status}` (gauge)
+    /// - `bombe_quarantined_artifacts` (gauge)
+    fn export_prometheus_metrics(&self) -> PyResult<String> {
+        self.with_read_pool(|conn| {
+            let mut out = String::new();
+
+            // -- bombe_tool_latency_ms -------------------------------------
+            out.push_str("# HELP bombe_tool_latency_ms Tool call latency in milliseconds.\n");
+            out.push_str("# TYPE bombe_tool_latency_ms histogram\n");
+            {
+                let mut stmt = conn
+                    .prepare("SELECT tool_name, mode, latency_ms FROM tool_metrics;")
+                    .map_err(BombeError::from)?;
+                let mut rows = stmt.query([]).map_err(BombeError::from)?;
+                let mut samples: Vec<(String, String, f64)> = Vec::new();
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    let tool_name: String = row.get(0).map_err(BombeError::from)?;
+                    let mode: String = row.get(1).map_err(BombeError::from)?;
+                    let latency_ms: f64 = row.get(2).map_err(BombeError::from)?;
+                    samples.push((tool_name, mode, latency_ms));
+                }
+
+                let mut groups: BTreeMap<(String, String), Vec<f64>> = BTreeMap::new();
+                for (tool_name, mode, latency_ms) in samples {
+                    groups.entry((tool_name, mode)).or_default().push(latency_ms);
+                }
+                for ((tool_name, mode), latencies) in &groups {
+                    let tool_label = prometheus_escape_label(tool_name);
+                    let mode_label = prometheus_escape_label(mode);
+                    let mut cumulative = 0u64;
+                    let mut sum = 0.0;
+                    for &bucket in PROMETHEUS_LATENCY_BUCKETS_MS {
+                        cumulative += latencies.iter().filter(|&&v| v <= bucket).count() as u64;
+                        out.push_str(&format!(
+                            "bombe_tool_latency_ms_bucket{{tool_name=\"{}\",mode=\"{}\",le=\"{}\"}} {}\n",
+                            tool_label, mode_label, bucket, cumulative
+                        ));
+                    }
+                    out.push_str(&format!(
+                        "bombe_tool_latency_ms_bucket{{tool_name=\"{}\",mode=\"{}\",le=\"+Inf\"}} {}\n",
+                        tool_label,
+                        mode_label,
+                        latencies.len()
+                    ));
+                    for &v in latencies {
+                        sum += v;
+                    }
+                    out.push_str(&format!(
+                        "bombe_tool_latency_ms_sum{{tool_name=\"{}\",mode=\"{}\"}} {}\n",
+                        tool_label, mode_label, sum
+                    ));
+                    out.push_str(&format!(
+                        "bombe_tool_latency_ms_count{{tool_name=\"{}\",mode=\"{}\"}} {}\n",
+                        tool_label,
+                        mode_label,
+                        latencies.len()
+                    ));
+                }
+            }
+
+            // -- bombe_tool_calls_total ------------------------------------
+            out.push_str("# HELP bombe_tool_calls_total Total tool calls by outcome.\n");
+            out.push_str("# TYPE bombe_tool_calls_total counter\n");
+            {
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT tool_name, mode, success, COUNT(*) AS count \
+                         FROM tool_metrics \
+                         GROUP BY tool_name, mode, success \
+                         ORDER BY tool_name, mode, success;",
+                    )
+                    .map_err(BombeError::from)?;
+                let mut rows = stmt.query([]).map_err(BombeError::from)?;
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    let tool_name: String = row.get(0).map_err(BombeError::from)?;
+                    let mode: String = row.get(1).map_err(BombeError::from)?;
+                    let success: i64 = row.get(2).map_err(BombeError::from)?;
+                    let count: i64 = row.get(3).map_err(BombeError::from)?;
+                    out.push_str(&format!(
+                        "bombe_tool_calls_total{{tool_name=\"{}\",mode=\"{}\",success=\"{}\"}} {}\n",
+                        prometheus_escape_label(&tool_name),
+                        prometheus_escape_label(&mode),
+                        if success != 0 { "true" } else { "false" },
+                        count
+                    ));
+                }
+            }
 
-        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
-        let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
-        let mut rows = stmt
-            .query(params![tool_name, effective_limit])
-            .map_err(BombeError::from)?;
-        while let Some(row) = rows.next().map_err(BombeError::from)? {
-            result.push(row_to_pydict(py, row, &col_names)?);
-        }
-        let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
-        Ok(list.into_any().unbind())
+            // -- bombe_sync_events_total -------------------------------------
+            out.push_str("# HELP bombe_sync_events_total Total sync events by level and type.\n");
+            out.push_str("# TYPE bombe_sync_events_total counter\n");
+            {
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT repo_id, level, event_type, COUNT(*) AS count \
+                         FROM sync_events \
+                         GROUP BY repo_id, level, event_type \
+                         ORDER BY repo_id, level, event_type;",
+                    )
+                    .map_err(BombeError::from)?;
+                let mut rows = stmt.query([]).map_err(BombeError::from)?;
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    let repo_id: String = row.get(0).map_err(BombeError::from)?;
+                    let level: String = row.get(1).map_err(BombeError::from)?;
+                    let event_type: String = row.get(2).map_err(BombeError::from)?;
+                    let count: i64 = row.get(3).map_err(BombeError::from)?;
+                    out.push_str(&format!(
+                        "bombe_sync_events_total{{repo_id=\"{}\",level=\"{}\",event_type=\"{}\"}} {}\n",
+                        prometheus_escape_label(&repo_id),
+                        prometheus_escape_label(&level),
+                        prometheus_escape_label(&event_type),
+                        count
+                    ));
+                }
+            }
+
+            // -- bombe_sync_queue_depth ---------------------------------------
+            out.push_str("# HELP bombe_sync_queue_depth Pending sync queue entries by status.\n");
+            out.push_str("# TYPE bombe_sync_queue_depth gauge\n");
+            {
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT status, COUNT(*) AS count FROM sync_queue \
+                         GROUP BY status ORDER BY status;",
+                    )
+                    .map_err(BombeError::from)?;
+                let mut rows = stmt.query([]).map_err(BombeError::from)?;
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    let status: String = row.get(0).map_err(BombeError::from)?;
+                    let count: i64 = row.get(1).map_err(BombeError::from)?;
+                    out.push_str(&format!(
+                        "bombe_sync_queue_depth{{status=\"{}\"}} {}\n",
+                        prometheus_escape_label(&status),
+                        count
+                    ));
+                }
+            }
+
+            // -- bombe_quarantined_artifacts -----------------------------------
+            out.push_str("# HELP bombe_quarantined_artifacts Number of quarantined artifacts.\n");
+            out.push_str("# TYPE bombe_quarantined_artifacts gauge\n");
+            {
+                let count: i64 = conn
+                    .query_row("SELECT COUNT(*) FROM artifact_quarantine;", [], |row| {
+                        row.get(0)
+                    })
+                    .map_err(BombeError::from)?;
+                out.push_str(&format!("bombe_quarantined_artifacts {}\n", count));
+            }
+
+            Ok(out)
+        })
     }
 
     // -----------------------------------------------------------------------
@@ -1069,24 +2780,25 @@ impl Database {
         severity: Option<&str>,
     ) -> PyResult<()> {
         let effective_severity = severity.unwrap_or("error");
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT INTO indexing_diagnostics( \
-                 run_id, stage, category, severity, file_path, language, message, hint \
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
-            params![
-                run_id,
-                stage,
-                category,
-                effective_severity,
-                file_path,
-                language,
-                message,
-                hint,
-            ],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        self.with_write_pool(|conn| {
+            conn.execute(
+                "INSERT INTO indexing_diagnostics( \
+                     run_id, stage, category, severity, file_path, language, message, hint \
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
+                params![
+                    run_id,
+                    stage,
+                    category,
+                    effective_severity,
+                    file_path,
+                    language,
+                    message,
+                    hint,
+                ],
+            )
+            .map_err(BombeError::from)?;
+            Ok(())
+        })
     }
 
     /// List indexing diagnostics with optional filters.
@@ -1138,22 +2850,24 @@ impl Database {
             where_sql
         );
 
-        let conn = self.connect()?;
-        let mut stmt = conn.prepare(&sql).map_err(BombeError::from)?;
+        self.with_read_pool(|conn| {
+            let mut stmt = conn.prepare(&sql).map_err(BombeError::from)?;
 
-        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            param_values.iter().map(|b| b.as_ref()).collect();
+            let col_names: Vec<String> =
+                stmt.column_names().iter().map(|s| s.to_string()).collect();
+            let param_refs: Vec<&dyn rusqlite::types::ToSql> =
+                param_values.iter().map(|b| b.as_ref()).collect();
 
-        let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
-        let mut rows = stmt
-            .query(param_refs.as_slice())
-            .map_err(BombeError::from)?;
-        while let Some(row) = rows.next().map_err(BombeError::from)? {
-            result.push(row_to_pydict(py, row, &col_names)?);
-        }
-        let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
-        Ok(list.into_any().unbind())
+            let mut result: Vec<Bound<'_, PyDict>> = Vec::new();
+            let mut rows = stmt
+                .query(param_refs.as_slice())
+                .map_err(BombeError::from)?;
+            while let Some(row) = rows.next().map_err(BombeError::from)? {
+                result.push(row_to_pydict(py, row, &col_names)?);
+            }
+            let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
+            Ok(list.into_any().unbind())
+        })
     }
 
     /// Return a summary dict of indexing diagnostics, optionally filtered by
@@ -1173,98 +2887,100 @@ impl Database {
             None => (String::new(), Vec::new()),
         };
 
-        let conn = self.connect()?;
-
-        // Total count.
-        let total_sql = format!(
-            "SELECT COUNT(*) AS count FROM indexing_diagnostics {};",
-            where_sql
-        );
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            where_params.iter().map(|b| b.as_ref()).collect();
-        let total: i64 = conn
-            .query_row(&total_sql, param_refs.as_slice(), |row| row.get(0))
-            .map_err(BombeError::from)?;
-
-        // Group by stage.
-        let by_stage_sql = format!(
-            "SELECT stage, COUNT(*) AS count FROM indexing_diagnostics {} \
-             GROUP BY stage ORDER BY stage ASC;",
-            where_sql
-        );
-        let mut by_stage_stmt = conn.prepare(&by_stage_sql).map_err(BombeError::from)?;
-        let by_stage_dict = PyDict::new(py);
-        {
-            let mut rows = by_stage_stmt
-                .query(param_refs.as_slice())
+        let result = self.with_read_pool(|conn| {
+            // Total count.
+            let total_sql = format!(
+                "SELECT COUNT(*) AS count FROM indexing_diagnostics {};",
+                where_sql
+            );
+            let param_refs: Vec<&dyn rusqlite::types::ToSql> =
+                where_params.iter().map(|b| b.as_ref()).collect();
+            let total: i64 = conn
+                .query_row(&total_sql, param_refs.as_slice(), |row| row.get(0))
                 .map_err(BombeError::from)?;
-            while let Some(row) = rows.next().map_err(BombeError::from)? {
-                let stage: String = row.get(0).map_err(BombeError::from)?;
-                let count: i64 = row.get(1).map_err(BombeError::from)?;
-                by_stage_dict.set_item(stage, count)?;
+
+            // Group by stage.
+            let by_stage_sql = format!(
+                "SELECT stage, COUNT(*) AS count FROM indexing_diagnostics {} \
+                 GROUP BY stage ORDER BY stage ASC;",
+                where_sql
+            );
+            let mut by_stage_stmt = conn.prepare(&by_stage_sql).map_err(BombeError::from)?;
+            let by_stage_dict = PyDict::new(py);
+            {
+                let mut rows = by_stage_stmt
+                    .query(param_refs.as_slice())
+                    .map_err(BombeError::from)?;
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    let stage: String = row.get(0).map_err(BombeError::from)?;
+                    let count: i64 = row.get(1).map_err(BombeError::from)?;
+                    by_stage_dict.set_item(stage, count)?;
+                }
             }
-        }
 
-        // Group by category.
-        let by_category_sql = format!(
-            "SELECT category, COUNT(*) AS count FROM indexing_diagnostics {} \
-             GROUP BY category ORDER BY category ASC;",
-            where_sql
-        );
-        let mut by_category_stmt = conn.prepare(&by_category_sql).map_err(BombeError::from)?;
-        let by_category_dict = PyDict::new(py);
-        {
-            let mut rows = by_category_stmt
-                .query(param_refs.as_slice())
-                .map_err(BombeError::from)?;
-            while let Some(row) = rows.next().map_err(BombeError::from)? {
-                let category: String = row.get(0).map_err(BombeError::from)?;
-                let count: i64 = row.get(1).map_err(BombeError::from)?;
-                by_category_dict.set_item(category, count)?;
+            // Group by category.
+            let by_category_sql = format!(
+                "SELECT category, COUNT(*) AS count FROM indexing_diagnostics {} \
+                 GROUP BY category ORDER BY category ASC;",
+                where_sql
+            );
+            let mut by_category_stmt = conn.prepare(&by_category_sql).map_err(BombeError::from)?;
+            let by_category_dict = PyDict::new(py);
+            {
+                let mut rows = by_category_stmt
+                    .query(param_refs.as_slice())
+                    .map_err(BombeError::from)?;
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    let category: String = row.get(0).map_err(BombeError::from)?;
+                    let count: i64 = row.get(1).map_err(BombeError::from)?;
+                    by_category_dict.set_item(category, count)?;
+                }
             }
-        }
 
-        // Group by severity.
-        let by_severity_sql = format!(
-            "SELECT severity, COUNT(*) AS count FROM indexing_diagnostics {} \
-             GROUP BY severity ORDER BY severity ASC;",
-            where_sql
-        );
-        let mut by_severity_stmt = conn.prepare(&by_severity_sql).map_err(BombeError::from)?;
-        let by_severity_dict = PyDict::new(py);
-        {
-            let mut rows = by_severity_stmt
-                .query(param_refs.as_slice())
-                .map_err(BombeError::from)?;
-            while let Some(row) = rows.next().map_err(BombeError::from)? {
-                let sev: String = row.get(0).map_err(BombeError::from)?;
-                let count: i64 = row.get(1).map_err(BombeError::from)?;
-                by_severity_dict.set_item(sev, count)?;
+            // Group by severity.
+            let by_severity_sql = format!(
+                "SELECT severity, COUNT(*) AS count FROM indexing_diagnostics {} \
+                 GROUP BY severity ORDER BY severity ASC;",
+                where_sql
+            );
+            let mut by_severity_stmt = conn.prepare(&by_severity_sql).map_err(BombeError::from)?;
+            let by_severity_dict = PyDict::new(py);
+            {
+                let mut rows = by_severity_stmt
+                    .query(param_refs.as_slice())
+                    .map_err(BombeError::from)?;
+                while let Some(row) = rows.next().map_err(BombeError::from)? {
+                    let sev: String = row.get(0).map_err(BombeError::from)?;
+                    let count: i64 = row.get(1).map_err(BombeError::from)?;
+                    by_severity_dict.set_item(sev, count)?;
+                }
             }
-        }
 
-        // Latest run_id.
-        let latest_sql = format!(
-            "SELECT run_id FROM indexing_diagnostics {} ORDER BY id DESC LIMIT 1;",
-            where_sql
-        );
-        let latest_run_id: Option<String> = conn
-            .query_row(&latest_sql, param_refs.as_slice(), |row| row.get(0))
-            .ok();
+            // Latest run_id.
+            let latest_sql = format!(
+                "SELECT run_id FROM indexing_diagnostics {} ORDER BY id DESC LIMIT 1;",
+                where_sql
+            );
+            let latest_run_id: Option<String> = conn
+                .query_row(&latest_sql, param_refs.as_slice(), |row| row.get(0))
+                .ok();
+
+            let result = PyDict::new(py);
+            result.set_item("total", total)?;
+            match run_id {
+                Some(rid) => result.set_item("run_id", rid)?,
+                None => result.set_item("run_id", py.None())?,
+            }
+            match &latest_run_id {
+                Some(v) => result.set_item("latest_run_id", v)?,
+                None => result.set_item("latest_run_id", py.None())?,
+            }
+            result.set_item("by_stage", by_stage_dict)?;
+            result.set_item("by_category", by_category_dict)?;
+            result.set_item("by_severity", by_severity_dict)?;
 
-        let result = PyDict::new(py);
-        result.set_item("total", total)?;
-        match run_id {
-            Some(rid) => result.set_item("run_id", rid)?,
-            None => result.set_item("run_id", py.None())?,
-        }
-        match &latest_run_id {
-            Some(v) => result.set_item("latest_run_id", v)?,
-            None => result.set_item("latest_run_id", py.None())?,
-        }
-        result.set_item("by_stage", by_stage_dict)?;
-        result.set_item("by_category", by_category_dict)?;
-        result.set_item("by_severity", by_severity_dict)?;
+            Ok(result)
+        })?;
 
         Ok(result.into_any().unbind())
     }
@@ -1273,18 +2989,20 @@ impl Database {
     /// Returns the number of rows deleted.
     #[pyo3(signature = (run_id=None))]
     fn clear_indexing_diagnostics(&self, run_id: Option<&str>) -> PyResult<i64> {
-        let conn = self.connect()?;
-        let deleted = match run_id {
-            Some(rid) => conn
-                .execute(
-                    "DELETE FROM indexing_diagnostics WHERE run_id = ?1;",
-                    params![rid],
-                )
-                .map_err(BombeError::from)?,
-            None => conn
-                .execute("DELETE FROM indexing_diagnostics;", [])
-                .map_err(BombeError::from)?,
-        };
+        let deleted = self.with_write_pool(|conn| {
+            let deleted = match run_id {
+                Some(rid) => conn
+                    .execute(
+                        "DELETE FROM indexing_diagnostics WHERE run_id = ?1;",
+                        params![rid],
+                    )
+                    .map_err(BombeError::from)?,
+                None => conn
+                    .execute("DELETE FROM indexing_diagnostics;", [])
+                    .map_err(BombeError::from)?,
+            };
+            Ok(deleted)
+        })?;
         Ok(deleted as i64)
     }
 
@@ -1292,8 +3010,15 @@ impl Database {
     // Signing keys
     // -----------------------------------------------------------------------
 
-    /// Upsert a trusted signing key for a repo.
-    #[pyo3(signature = (repo_id, key_id, algorithm, public_key, purpose=None, active=None))]
+    /// Upsert a trusted signing key for a repo. Stamps `last_refreshed_at`
+    /// to now and draws a new `refresh_jitter_secs` on every call, so
+    /// `list_keys_due_for_refresh` sees this key's due-time pushed out from
+    /// whenever it was last touched — not just on first insert. `algorithm`
+    /// is normalized (trimmed, lowercased) before being checked against
+    /// `repo_id`'s [`check_algorithm_policy`] and stored; a denied or
+    /// not-allow-listed algorithm raises rather than entering the trust set.
+    #[pyo3(signature = (repo_id, key_id, algorithm, public_key, purpose=None, active=None, expires_at=None))]
+    #[allow(clippy::too_many_arguments)]
     fn set_trusted_signing_key(
         &self,
         repo_id: &str,
@@ -1302,33 +3027,194 @@ impl Database {
         public_key: &str,
         purpose: Option<&str>,
         active: Option<bool>,
+        expires_at: Option<&str>,
     ) -> PyResult<()> {
         let effective_purpose = purpose.unwrap_or("default");
         let effective_active = active.unwrap_or(true) as i64;
+        let normalized_algorithm = normalize_algorithm(algorithm);
         let conn = self.connect()?;
+        check_algorithm_policy(&conn, repo_id, &normalized_algorithm)?;
         conn.execute(
-            "INSERT INTO trusted_signing_keys( \
-                 repo_id, key_id, algorithm, public_key, purpose, active, updated_at \
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, CURRENT_TIMESTAMP) \
-             ON CONFLICT(repo_id, key_id) DO UPDATE SET \
-                 algorithm = excluded.algorithm, \
-                 public_key = excluded.public_key, \
-                 purpose = excluded.purpose, \
-                 active = excluded.active, \
-                 updated_at = excluded.updated_at;",
+            &format!(
+                "INSERT INTO trusted_signing_keys( \
+                     repo_id, key_id, algorithm, public_key, purpose, active, \
+                     expires_at, updated_at, last_refreshed_at, refresh_jitter_secs \
+                 ) VALUES ( \
+                     ?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP, \
+                     ABS(RANDOM() % {jitter_span}) \
+                 ) \
+                 ON CONFLICT(repo_id, key_id) DO UPDATE SET \
+                     algorithm = excluded.algorithm, \
+                     public_key = excluded.public_key, \
+                     purpose = excluded.purpose, \
+                     active = excluded.active, \
+                     expires_at = excluded.expires_at, \
+                     updated_at = excluded.updated_at, \
+                     last_refreshed_at = excluded.last_refreshed_at, \
+                     refresh_jitter_secs = excluded.refresh_jitter_secs;",
+                jitter_span = (2.0 * KEY_REFRESH_INTERVAL_SECS) as i64,
+            ),
             params![
                 repo_id,
                 key_id,
-                algorithm,
+                normalized_algorithm,
                 public_key,
                 effective_purpose,
                 effective_active,
+                expires_at,
             ],
         )
         .map_err(BombeError::from)?;
         Ok(())
     }
 
+    /// Replace `repo_id`'s algorithm policy wholesale: `allow` and `deny`
+    /// are lists of (normalized at write time) algorithm tags. An empty
+    /// policy (both lists empty) means "no restriction", matching the
+    /// pre-policy default. See [`check_algorithm_policy`] for how the two
+    /// lists interact.
+    fn set_algorithm_policy(
+        &self,
+        repo_id: &str,
+        allow: Vec<String>,
+        deny: Vec<String>,
+    ) -> PyResult<()> {
+        self.with_write_pool(|conn| {
+            let tx = conn.unchecked_transaction().map_err(BombeError::from)?;
+            tx.execute(
+                "DELETE FROM algorithm_policies WHERE repo_id = ?1;",
+                params![repo_id],
+            )
+            .map_err(BombeError::from)?;
+            for algorithm in &allow {
+                tx.execute(
+                    "INSERT INTO algorithm_policies(repo_id, algorithm, mode) VALUES (?1, ?2, 'allow');",
+                    params![repo_id, normalize_algorithm(algorithm)],
+                )
+                .map_err(BombeError::from)?;
+            }
+            for algorithm in &deny {
+                tx.execute(
+                    "INSERT INTO algorithm_policies(repo_id, algorithm, mode) VALUES (?1, ?2, 'deny');",
+                    params![repo_id, normalize_algorithm(algorithm)],
+                )
+                .map_err(BombeError::from)?;
+            }
+            tx.commit().map_err(BombeError::from)?;
+            Ok(())
+        })
+    }
+
+    /// Return `repo_id`'s algorithm policy as `{"allow": [...], "deny":
+    /// [...]}` (both empty if no policy has been set).
+    fn get_algorithm_policy(&self, py: Python<'_>, repo_id: &str) -> PyResult<PyObject> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare("SELECT algorithm, mode FROM algorithm_policies WHERE repo_id = ?1 ORDER BY algorithm ASC;")
+            .map_err(BombeError::from)?;
+        let rows: Vec<(String, String)> = stmt
+            .query_map(params![repo_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(BombeError::from)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        let allow: Vec<&str> = rows
+            .iter()
+            .filter(|(_, mode)| mode == "allow")
+            .map(|(algo, _)| algo.as_str())
+            .collect();
+        let deny: Vec<&str> = rows
+            .iter()
+            .filter(|(_, mode)| mode == "deny")
+            .map(|(algo, _)| algo.as_str())
+            .collect();
+
+        let dict = PyDict::new(py);
+        dict.set_item("allow", allow)?;
+        dict.set_item("deny", deny)?;
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Return the trusted keys due for refresh, bucketed into `"due"` (past
+    /// their jittered refresh window, or never refreshed) and `"expired"`
+    /// (`expires_at` has passed), so callers can deactivate expired keys and
+    /// rotate due ones without polling every key individually.
+    ///
+    /// A key's refresh window is `last_refreshed_at +
+    /// KEY_REFRESH_INTERVAL_SECS + refresh_jitter_secs`, where
+    /// `refresh_jitter_secs` was drawn once (uniformly from
+    /// `[0, 2 * KEY_REFRESH_INTERVAL_SECS)`) the last time the key was
+    /// upserted — stable across calls, unlike re-rolling the jitter on every
+    /// check, which would make "is it due" flap from call to call.
+    #[pyo3(signature = (repo_id=None, now=None))]
+    fn list_keys_due_for_refresh(
+        &self,
+        py: Python<'_>,
+        repo_id: Option<&str>,
+        now: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT repo_id, key_id, algorithm, purpose, expires_at, \
+                        last_refreshed_at, refresh_jitter_secs \
+                 FROM trusted_signing_keys \
+                 WHERE active = 1 AND (?1 IS NULL OR repo_id = ?1);",
+            )
+            .map_err(BombeError::from)?;
+        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
+
+        let mut due: Vec<Bound<'_, PyDict>> = Vec::new();
+        let mut expired: Vec<Bound<'_, PyDict>> = Vec::new();
+        let mut rows = stmt.query(params![repo_id]).map_err(BombeError::from)?;
+        while let Some(row) = rows.next().map_err(BombeError::from)? {
+            let expires_at: Option<String> = row.get(4).map_err(BombeError::from)?;
+            let last_refreshed_at: Option<String> = row.get(5).map_err(BombeError::from)?;
+            let jitter_secs: f64 = row.get(6).map_err(BombeError::from)?;
+            let dict = row_to_pydict(py, row, &col_names)?;
+
+            let is_expired = match &expires_at {
+                Some(expiry) => conn
+                    .query_row(
+                        "SELECT COALESCE(?1, CURRENT_TIMESTAMP) >= ?2;",
+                        params![now, expiry],
+                        |row| row.get::<_, i64>(0),
+                    )
+                    .map_err(BombeError::from)?
+                    != 0,
+                None => false,
+            };
+            if is_expired {
+                expired.push(dict);
+                continue;
+            }
+
+            let is_due = match &last_refreshed_at {
+                None => true,
+                Some(last) => conn
+                    .query_row(
+                        "SELECT COALESCE(?1, CURRENT_TIMESTAMP) \
+                             >= datetime(?2, '+' || (?3 + ?4) || ' seconds');",
+                        params![now, last, KEY_REFRESH_INTERVAL_SECS, jitter_secs],
+                        |row| row.get::<_, i64>(0),
+                    )
+                    .map_err(BombeError::from)?
+                    != 0,
+            };
+            if is_due {
+                due.push(dict);
+            }
+        }
+
+        let result = PyDict::new(py);
+        result.set_item("due", PyList::new(py, due.iter().map(|d| d.as_any()))?)?;
+        result.set_item(
+            "expired",
+            PyList::new(py, expired.iter().map(|d| d.as_any()))?,
+        )?;
+        Ok(result.into_any().unbind())
+    }
+
     /// Get a single trusted signing key, or None.
     fn get_trusted_signing_key(
         &self,
@@ -1409,10 +3295,12 @@ impl Database {
                 row.get::<_, String>(0).map_err(BombeError::from)?,
             )?;
             dict.set_item("key_id", row.get::<_, String>(1).map_err(BombeError::from)?)?;
+            let algorithm: String = row.get(2).map_err(BombeError::from)?;
             dict.set_item(
-                "algorithm",
-                row.get::<_, String>(2).map_err(BombeError::from)?,
+                "unusable_by_this_version",
+                !RECOGNIZED_ALGORITHMS.contains(&algorithm.as_str()),
             )?;
+            dict.set_item("algorithm", algorithm)?;
             dict.set_item(
                 "public_key",
                 row.get::<_, String>(3).map_err(BombeError::from)?,
@@ -1434,4 +3322,303 @@ impl Database {
         let list = PyList::new(py, result.iter().map(|d| d.as_any()))?;
         Ok(list.into_any().unbind())
     }
+
+    /// Load the active key `(repo_id, key_id)` and verify `signature` (hex)
+    /// over `message` against it, dispatching on the key's `algorithm`
+    /// column. `purpose`, if given, must also match the stored key's
+    /// `purpose`. Raises if no matching active key exists. A key whose
+    /// algorithm this build doesn't recognize, or which `repo_id`'s current
+    /// [`check_algorithm_policy`] no longer allows, is treated as
+    /// not-verifying (`false`) rather than raising — the key itself stays
+    /// intact for `list_trusted_signing_keys`/newer binaries to see.
+    #[pyo3(signature = (repo_id, key_id, message, signature, purpose=None))]
+    fn verify_signature(
+        &self,
+        repo_id: &str,
+        key_id: &str,
+        message: &str,
+        signature: &str,
+        purpose: Option<&str>,
+    ) -> PyResult<bool> {
+        let conn = self.connect()?;
+        let key: (String, String) = conn
+            .query_row(
+                "SELECT algorithm, public_key FROM trusted_signing_keys \
+                 WHERE repo_id = ?1 AND key_id = ?2 AND active = 1 \
+                   AND (?3 IS NULL OR purpose = ?3) LIMIT 1;",
+                params![repo_id, key_id, purpose],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => BombeError::Query(format!(
+                    "no active trusted key '{key_id}' for repo '{repo_id}'"
+                )),
+                other => BombeError::from(other),
+            })?;
+        if !RECOGNIZED_ALGORITHMS.contains(&key.0.as_str())
+            || check_algorithm_policy(&conn, repo_id, &key.0).is_err()
+        {
+            return Ok(false);
+        }
+        let trusted_key = crate::store::signing::TrustedKey {
+            key_id: key_id.to_string(),
+            algorithm: key.0,
+            public_key_hex: key.1,
+        };
+        Ok(crate::store::signing::verify_one(&trusted_key, message.as_bytes(), signature)?)
+    }
+
+    /// Try every active (non-BLS) key for a repo and return `true` if any of
+    /// them verifies `signature` (hex) over `message`. Keys this build
+    /// doesn't recognize, or that `repo_id`'s algorithm policy currently
+    /// denies, are skipped rather than failing the whole call.
+    fn verify_any(&self, repo_id: &str, message: &str, signature: &str) -> PyResult<bool> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT key_id, algorithm, public_key FROM trusted_signing_keys \
+                 WHERE repo_id = ?1 AND active = 1 AND algorithm != 'bls12_381' \
+                 ORDER BY key_id ASC;",
+            )
+            .map_err(BombeError::from)?;
+        let keys: Vec<(String, String, String)> = stmt
+            .query_map(params![repo_id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .map_err(BombeError::from)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        for (key_id, algorithm, public_key_hex) in keys {
+            if !RECOGNIZED_ALGORITHMS.contains(&algorithm.as_str())
+                || check_algorithm_policy(&conn, repo_id, &algorithm).is_err()
+            {
+                continue;
+            }
+            let trusted_key = crate::store::signing::TrustedKey {
+                key_id,
+                algorithm,
+                public_key_hex,
+            };
+            if crate::store::signing::verify_one(&trusted_key, message.as_bytes(), signature).unwrap_or(false) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Validate a threshold-signed repo attestation in one call: load the
+    /// `key_ids` active keys for `repo_id` (all must be tagged
+    /// `algorithm = "bls12_381"`) and check that `agg_signature` (hex) is a
+    /// valid BLS12-381 aggregate signature over `message` under their
+    /// combined public key. See [`crate::store::signing::aggregate_verify`]
+    /// for the pairing check this performs.
+    fn aggregate_verify(
+        &self,
+        repo_id: &str,
+        key_ids: Vec<String>,
+        message: &str,
+        agg_signature: &str,
+    ) -> PyResult<bool> {
+        let conn = self.connect()?;
+        let mut keys = Vec::with_capacity(key_ids.len());
+        for key_id in &key_ids {
+            let (algorithm, public_key_hex): (String, String) = conn
+                .query_row(
+                    "SELECT algorithm, public_key FROM trusted_signing_keys \
+                     WHERE repo_id = ?1 AND key_id = ?2 AND active = 1 LIMIT 1;",
+                    params![repo_id, key_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .map_err(|e| match e {
+                    rusqlite::Error::QueryReturnedNoRows => BombeError::Query(format!(
+                        "no active trusted key '{key_id}' for repo '{repo_id}'"
+                    )),
+                    other => BombeError::from(other),
+                })?;
+            check_algorithm_policy(&conn, repo_id, &algorithm).map_err(|_| {
+                BombeError::Query(format!(
+                    "key '{key_id}' is no longer allowed by repo '{repo_id}''s algorithm policy"
+                ))
+            })?;
+            keys.push(crate::store::signing::TrustedKey {
+                key_id: key_id.clone(),
+                algorithm,
+                public_key_hex,
+            });
+        }
+        Ok(crate::store::signing::aggregate_verify(&keys, message.as_bytes(), agg_signature)?)
+    }
+
+    /// Record that `signer_key_id` vouches for `signed_key_id` (e.g. an old
+    /// signing key endorsing its replacement during rotation). `signature`
+    /// (hex) must verify under the signer's own algorithm over the signed
+    /// key's `public_key` column — both keys must already exist via
+    /// `set_trusted_signing_key`. Raises if either key is missing or the
+    /// signature doesn't verify, so a bad edge never enters `key_signatures`
+    /// for [`Database::get_trust_chain`] to walk.
+    fn add_key_signature(
+        &self,
+        repo_id: &str,
+        signer_key_id: &str,
+        signed_key_id: &str,
+        signature: &str,
+    ) -> PyResult<()> {
+        let conn = self.connect()?;
+        let signer = load_signing_key(&conn, repo_id, signer_key_id)?;
+        let signed = load_signing_key(&conn, repo_id, signed_key_id)?;
+        let verified =
+            crate::store::signing::verify_one(&signer, signed.public_key_hex.as_bytes(), signature)?;
+        if !verified {
+            return Err(BombeError::Query(format!(
+                "signature from '{signer_key_id}' over '{signed_key_id}' does not verify"
+            ))
+            .into());
+        }
+        conn.execute(
+            "INSERT INTO key_signatures(repo_id, signer_key_id, signed_key_id, signature) \
+             VALUES (?1, ?2, ?3, ?4) \
+             ON CONFLICT(repo_id, signer_key_id, signed_key_id) DO UPDATE SET \
+                 signature = excluded.signature, \
+                 created_at = CURRENT_TIMESTAMP;",
+            params![repo_id, signer_key_id, signed_key_id, signature],
+        )
+        .map_err(BombeError::from)?;
+        Ok(())
+    }
+
+    /// Walk `key_signatures` edges forward from `root_key_ids` looking for a
+    /// path of verified endorsements that reaches `key_id`, so a rotated key
+    /// can be trusted without re-pinning as long as some still-trusted
+    /// ancestor vouched for it. Returns `{"trusted": bool, "chain": [...]}`
+    /// where `chain` is the root-to-`key_id` path of key ids (empty if
+    /// untrusted). An edge is only followed if its stored signature still
+    /// verifies under the signer's current key — a revoked/rotated-away
+    /// signer whose key has since changed algorithm or gone inactive breaks
+    /// the chain rather than being trusted blindly.
+    fn get_trust_chain(
+        &self,
+        py: Python<'_>,
+        repo_id: &str,
+        key_id: &str,
+        root_key_ids: Vec<String>,
+    ) -> PyResult<PyObject> {
+        let conn = self.connect()?;
+
+        let make_result = |trusted: bool, chain: Vec<String>| -> PyResult<PyObject> {
+            let dict = PyDict::new(py);
+            dict.set_item("trusted", trusted)?;
+            dict.set_item("chain", chain)?;
+            Ok(dict.into_any().unbind())
+        };
+
+        if root_key_ids.iter().any(|root| root == key_id) {
+            return make_result(true, vec![key_id.to_string()]);
+        }
+
+        let mut visited: HashSet<String> = root_key_ids.iter().cloned().collect();
+        let mut parent: BTreeMap<String, String> = BTreeMap::new();
+        let mut queue: std::collections::VecDeque<String> = root_key_ids.into_iter().collect();
+
+        while let Some(signer_id) = queue.pop_front() {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT signed_key_id, signature FROM key_signatures \
+                     WHERE repo_id = ?1 AND signer_key_id = ?2;",
+                )
+                .map_err(BombeError::from)?;
+            let edges: Vec<(String, String)> = stmt
+                .query_map(params![repo_id, signer_id], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })
+                .map_err(BombeError::from)?
+                .filter_map(|r| r.ok())
+                .collect();
+
+            for (signed_id, signature) in edges {
+                if visited.contains(&signed_id) {
+                    continue;
+                }
+                let Ok(signer) = load_signing_key(&conn, repo_id, &signer_id) else {
+                    continue;
+                };
+                let Ok(signed) = load_signing_key(&conn, repo_id, &signed_id) else {
+                    continue;
+                };
+                let ok = crate::store::signing::verify_one(
+                    &signer,
+                    signed.public_key_hex.as_bytes(),
+                    &signature,
+                )
+                .unwrap_or(false);
+                if !ok {
+                    continue;
+                }
+                visited.insert(signed_id.clone());
+                parent.insert(signed_id.clone(), signer_id.clone());
+                if signed_id == key_id {
+                    let mut chain = vec![signed_id.clone()];
+                    let mut cur = signed_id;
+                    while let Some(p) = parent.get(&cur) {
+                        chain.push(p.clone());
+                        cur = p.clone();
+                    }
+                    chain.reverse();
+                    return make_result(true, chain);
+                }
+                queue.push_back(signed_id);
+            }
+        }
+
+        make_result(false, Vec::new())
+    }
+
+    /// Return the `content_hash` last stored for `symbol_id` under `model`,
+    /// so a caller re-embedding after a reindex can skip any symbol whose
+    /// chunk (see `indexer::embedding::chunk_symbols`) hashed the same as
+    /// last time, instead of recomputing an embedding for unchanged code.
+    fn get_symbol_embedding_content_hash(
+        &self,
+        symbol_id: i64,
+        model: &str,
+    ) -> PyResult<Option<String>> {
+        let conn = self.connect()?;
+        match conn.query_row(
+            "SELECT content_hash FROM symbol_embeddings WHERE symbol_id = ?1 AND model = ?2;",
+            params![symbol_id, model],
+            |row| row.get(0),
+        ) {
+            Ok(hash) => Ok(hash),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(BombeError::from(e).into()),
+        }
+    }
+
+    /// Upsert `symbol_id`'s embedding `vector` under `model`, stamping
+    /// `content_hash` so a later call to
+    /// [`get_symbol_embedding_content_hash`] can detect it's unchanged.
+    /// Vectors are stored as raw little-endian f32 bytes (see
+    /// `query::semantic_index::encode_vector`), matching how
+    /// `query::semantic_index::SemanticIndex::build` reads them back.
+    fn upsert_symbol_embedding(
+        &self,
+        symbol_id: i64,
+        model: &str,
+        vector: Vec<f32>,
+        content_hash: &str,
+    ) -> PyResult<()> {
+        let dim = vector.len() as i64;
+        let blob = crate::query::semantic_index::encode_vector(&vector);
+        let conn = self.connect()?;
+        conn.execute(
+            "INSERT INTO symbol_embeddings(symbol_id, model, dim, vector, content_hash, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP) \
+             ON CONFLICT(symbol_id) DO UPDATE SET \
+                model = excluded.model, dim = excluded.dim, vector = excluded.vector, \
+                content_hash = excluded.content_hash, updated_at = excluded.updated_at;",
+            params![symbol_id, model, dim, blob, content_hash],
+        )
+        .map_err(BombeError::from)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,110 @@
+//! Scalar SQL functions for fuzzy symbol search, registered on every
+//! connection [`Database::connect`](crate::store::database::Database) opens
+//! (see [`register`]), so `query()` can rank candidates with plain SQL
+//! (`ORDER BY fuzzy_score(?1, name)`) even on SQLite builds without FTS5.
+//!
+//! Requires this crate's `functions` cargo feature (`rusqlite` built with
+//! its own `functions` feature).
+#![cfg(feature = "functions")]
+
+use rusqlite::functions::FunctionFlags;
+use rusqlite::Connection;
+
+use crate::errors::BombeResult;
+
+/// Bounded Levenshtein distance between `a` and `b`, using the classic
+/// two-row dynamic-programming recurrence (`prev`/`cur`, each of length
+/// `len(b) + 1`). If `max` is given, returns `None` as soon as every entry
+/// in a row exceeds it, so a caller ranking many candidates against a short
+/// cutoff doesn't pay for the full `len(a) * len(b)` table on each one.
+pub fn edit_distance(a: &str, b: &str, max: Option<usize>) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0usize; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        let mut row_min = cur[0];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j + 1] + 1).min(cur[j] + 1).min(prev[j] + cost);
+            row_min = row_min.min(cur[j + 1]);
+        }
+        if let Some(max) = max {
+            if row_min > max {
+                return None;
+            }
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    let distance = prev[b.len()];
+    match max {
+        Some(max) if distance > max => None,
+        _ => Some(distance),
+    }
+}
+
+/// `true` if every character of `query` appears in `candidate`, in order
+/// (not necessarily contiguously) — the "abbreviation" match `getusr` makes
+/// against `get_user`.
+fn is_subsequence(query: &str, candidate: &str) -> bool {
+    let mut rest = candidate.chars();
+    query.chars().all(|qc| rest.any(|cc| cc == qc))
+}
+
+/// Normalize [`edit_distance`] to a `0.0..=1.0` similarity score
+/// (`1.0 - distance / max(len(query), len(candidate))`), with a bonus when
+/// `candidate` starts with `query` or contains it as a subsequence, so
+/// prefix/abbreviation-style queries (`getusr` -> `get_user`) outrank
+/// unrelated strings sitting at a similar raw edit distance.
+pub fn fuzzy_score(query: &str, candidate: &str) -> f64 {
+    let max_len = query.chars().count().max(candidate.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    let distance = edit_distance(query, candidate, None).unwrap_or(max_len);
+    let mut score = 1.0 - (distance as f64 / max_len as f64);
+    if candidate.starts_with(query) {
+        score += 0.1;
+    }
+    if is_subsequence(query, candidate) {
+        score += 0.1;
+    }
+    score.clamp(0.0, 1.0)
+}
+
+/// Register `edit_distance(a, b[, max])` and `fuzzy_score(query, candidate)`
+/// on `conn`. Both return `NULL` on any `NULL` input and are marked
+/// deterministic, so SQLite permits using them in expression indexes.
+pub fn register(conn: &Connection) -> BombeResult<()> {
+    let flags = FunctionFlags::SQLITE_DETERMINISTIC | FunctionFlags::SQLITE_UTF8;
+
+    conn.create_scalar_function("edit_distance", -1, flags, |ctx| {
+        if ctx.len() != 2 && ctx.len() != 3 {
+            return Err(rusqlite::Error::InvalidParameterCount(ctx.len(), 2));
+        }
+        let a: Option<String> = ctx.get(0)?;
+        let b: Option<String> = ctx.get(1)?;
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return Ok(None::<i64>),
+        };
+        let max = if ctx.len() == 3 {
+            ctx.get::<Option<i64>>(2)?.map(|m| m.max(0) as usize)
+        } else {
+            None
+        };
+        Ok(edit_distance(&a, &b, max).map(|d| d as i64))
+    })?;
+
+    conn.create_scalar_function("fuzzy_score", 2, flags, |ctx| {
+        let query: Option<String> = ctx.get(0)?;
+        let candidate: Option<String> = ctx.get(1)?;
+        match (query, candidate) {
+            (Some(query), Some(candidate)) => Ok(Some(fuzzy_score(&query, &candidate))),
+            _ => Ok(None::<f64>),
+        }
+    })?;
+
+    Ok(())
+}
@@ -3,20 +3,83 @@
 //! Direct port of the Python `bombe.store.database` schema layer.
 //! Every table, index, and migration step matches the Python implementation.
 
+use std::collections::{HashMap, HashSet};
+
 use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+
+use crate::errors::{BombeError, BombeResult};
 
-use crate::errors::BombeResult;
+/// Current schema version — derived from the last entry of [`MIGRATIONS`]
+/// rather than hand-maintained, so it can never drift out of sync with the
+/// registry. Migrations run from whatever the DB currently reports up to
+/// this value.
+pub const SCHEMA_VERSION: i32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
 
-/// Current schema version. Migrations run from whatever the DB currently
-/// reports up to this value.
-pub const SCHEMA_VERSION: i32 = 7;
+/// Tunable PRAGMAs [`apply_startup_pragmas`] issues on a freshly opened
+/// connection, before any DDL or migrations run. Defaults favor the common
+/// case; embedders indexing very large repos can raise `cache_size`/
+/// `mmap_size` to trade memory for fewer page faults during the
+/// symbol/edge-heavy migration and query workload.
+#[derive(Clone, Debug)]
+pub struct PragmaConfig {
+    /// `PRAGMA cache_size` value. Negative means kibibytes of cache rather
+    /// than a page count — SQLite's own convention for "size in memory,
+    /// not page count", which avoids having to know the configured page
+    /// size to reason about how much memory this actually reserves.
+    pub cache_size: i64,
+    /// `PRAGMA journal_size_limit`, in bytes — caps how large the `-wal`
+    /// file is allowed to grow before SQLite truncates it back down at the
+    /// next checkpoint, instead of letting it grow unboundedly under a
+    /// sustained write burst.
+    pub journal_size_limit: i64,
+    /// `PRAGMA mmap_size`, in bytes. `None` leaves SQLite's own default in
+    /// place; set it to let reads bypass the page cache entirely for large,
+    /// mostly-read-only databases.
+    pub mmap_size: Option<i64>,
+}
 
-/// Core DDL statements: 15 CREATE TABLE + 18 CREATE INDEX.
+impl Default for PragmaConfig {
+    fn default() -> Self {
+        PragmaConfig {
+            cache_size: -20_000, // ~20MB
+            journal_size_limit: 64 * 1024 * 1024,
+            mmap_size: None,
+        }
+    }
+}
+
+/// Applies performance PRAGMAs to a freshly opened connection, before any
+/// DDL or migrations run: `journal_mode = WAL` and `synchronous = NORMAL`
+/// (safe together — WAL only loses the last commit or two on an OS crash
+/// either way, so the extra fsyncs `FULL` would add buy nothing here),
+/// `foreign_keys = ON`, `temp_store = MEMORY` so the set-based FTS rebuild
+/// in [`migrate_to_v2`] doesn't spill its temp b-trees to disk, plus
+/// `cfg`'s `cache_size`/`journal_size_limit`/`mmap_size`. The symbol/edge
+/// tables and that same FTS rebuild are I/O heavy enough that WAL and a
+/// larger page cache measurably cut migration and query latency.
+pub fn apply_startup_pragmas(conn: &Connection, cfg: &PragmaConfig) -> BombeResult<()> {
+    conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+    conn.execute_batch("PRAGMA synchronous = NORMAL;")?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    conn.execute_batch("PRAGMA temp_store = MEMORY;")?;
+    conn.execute_batch(&format!("PRAGMA cache_size = {};", cfg.cache_size))?;
+    conn.execute_batch(&format!(
+        "PRAGMA journal_size_limit = {};",
+        cfg.journal_size_limit
+    ))?;
+    if let Some(mmap_size) = cfg.mmap_size {
+        conn.execute_batch(&format!("PRAGMA mmap_size = {mmap_size};"))?;
+    }
+    Ok(())
+}
+
+/// Core DDL statements: 18 CREATE TABLE + 19 CREATE INDEX.
 ///
 /// Executed with `CREATE … IF NOT EXISTS` so they are safe to replay on an
 /// already-initialised database.
 pub const SCHEMA_STATEMENTS: &[&str] = &[
-    // ── tables (15) ─────────────────────────────────────────────────────
+    // ── tables (16) ─────────────────────────────────────────────────────
     "CREATE TABLE IF NOT EXISTS repo_meta (
         key TEXT PRIMARY KEY,
         value TEXT
@@ -65,6 +128,7 @@ pub const SCHEMA_STATEMENTS: &[&str] = &[
         file_path TEXT,
         line_number INTEGER,
         confidence REAL DEFAULT 1.0,
+        dispatch TEXT NOT NULL DEFAULT 'direct',
         UNIQUE(source_id, target_id, source_type, target_type, relationship)
     );",
     "CREATE TABLE IF NOT EXISTS external_deps (
@@ -152,7 +216,31 @@ pub const SCHEMA_STATEMENTS: &[&str] = &[
         updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
         PRIMARY KEY(repo_id, key_id)
     );",
-    // ── indexes (18) ────────────────────────────────────────────────────
+    "CREATE TABLE IF NOT EXISTS tool_latency_digests (
+        tool_name TEXT NOT NULL,
+        mode TEXT NOT NULL,
+        digest_json TEXT NOT NULL,
+        sample_count INTEGER NOT NULL DEFAULT 0,
+        updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY(tool_name, mode)
+    );",
+    "CREATE TABLE IF NOT EXISTS key_signatures (
+        repo_id TEXT NOT NULL,
+        signer_key_id TEXT NOT NULL,
+        signed_key_id TEXT NOT NULL,
+        signature TEXT NOT NULL,
+        created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY(repo_id, signer_key_id, signed_key_id),
+        FOREIGN KEY(repo_id, signer_key_id) REFERENCES trusted_signing_keys(repo_id, key_id),
+        FOREIGN KEY(repo_id, signed_key_id) REFERENCES trusted_signing_keys(repo_id, key_id)
+    );",
+    "CREATE TABLE IF NOT EXISTS algorithm_policies (
+        repo_id TEXT NOT NULL,
+        algorithm TEXT NOT NULL,
+        mode TEXT NOT NULL CHECK(mode IN ('allow', 'deny')),
+        PRIMARY KEY(repo_id, algorithm)
+    );",
+    // ── indexes (19) ────────────────────────────────────────────────────
     "CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols(name);",
     "CREATE INDEX IF NOT EXISTS idx_symbols_qualified ON symbols(qualified_name);",
     "CREATE INDEX IF NOT EXISTS idx_symbols_file ON symbols(file_path);",
@@ -171,6 +259,7 @@ pub const SCHEMA_STATEMENTS: &[&str] = &[
     "CREATE INDEX IF NOT EXISTS idx_index_diag_file_created ON indexing_diagnostics(file_path, created_at);",
     "CREATE INDEX IF NOT EXISTS idx_index_diag_severity_created ON indexing_diagnostics(severity, created_at);",
     "CREATE INDEX IF NOT EXISTS idx_trusted_keys_repo_active ON trusted_signing_keys(repo_id, active, key_id);",
+    "CREATE INDEX IF NOT EXISTS idx_key_signatures_signed ON key_signatures(repo_id, signed_key_id);",
 ];
 
 /// FTS5 virtual table and its helper index.
@@ -183,38 +272,333 @@ pub const FTS_STATEMENTS: &[&str] = &[
     "CREATE INDEX IF NOT EXISTS idx_symbol_fts_symbol_id ON symbol_fts(symbol_id);",
 ];
 
+// ─── Application id guard ───────────────────────────────────────────────────
+
+/// Magic `PRAGMA application_id` value stamped onto every bombe-managed
+/// database (`'bomb'` read as big-endian ASCII bytes). SQLite reserves this
+/// 32-bit header field exactly for this purpose — distinguishing a file
+/// format from any other `.db` a user might point us at — so we use it as a
+/// coarse guard, independent of `repo_meta.schema_version`, which only
+/// exists once a bombe database has actually been created.
+pub const BOMBE_APPLICATION_ID: i32 = 0x626f_6d62;
+
+/// Reads `PRAGMA application_id` and rejects the connection if it is set to
+/// something other than [`BOMBE_APPLICATION_ID`]. A fresh SQLite file (or one
+/// predating this guard) reports `0`, which is treated as "not yet stamped"
+/// rather than foreign, so existing bombe databases keep opening normally.
+fn check_application_id(conn: &Connection) -> BombeResult<()> {
+    let id: i32 = conn.query_row("PRAGMA application_id;", [], |row| row.get(0))?;
+    if id != 0 && id != BOMBE_APPLICATION_ID {
+        return Err(BombeError::ForeignDatabase(format!(
+            "refusing to open: file has application_id {id}, which is not a bombe \
+             database (expected {BOMBE_APPLICATION_ID} or unset)"
+        )));
+    }
+    Ok(())
+}
+
+/// Stamps [`BOMBE_APPLICATION_ID`] onto the connection. Idempotent, so it is
+/// safe to call on every open rather than only on first initialization.
+fn stamp_application_id(conn: &Connection) -> BombeResult<()> {
+    conn.execute_batch(&format!("PRAGMA application_id = {BOMBE_APPLICATION_ID};"))?;
+    Ok(())
+}
+
 // ─── Migration framework ────────────────────────────────────────────────────
 
+/// One schema step, forward and (optionally) backward.
+///
+/// `down` is `None` for steps that can't be meaningfully reversed — most
+/// `ALTER TABLE ... ADD COLUMN` steps would need to drop the column (and
+/// whatever data it holds) to undo, which [`migrate_to_version`] refuses to
+/// do silently. Only steps with an explicit, safe inverse (e.g. dropping an
+/// index or a table this same step created) get a `down`.
+///
+/// `name` is a short, stable identifier (not the doc comment) that tooling
+/// can use to describe a pending migration without running it — see
+/// [`pending_migration_names`].
+struct Migration {
+    version: i32,
+    name: &'static str,
+    up: fn(&Connection) -> BombeResult<Option<u64>>,
+    down: Option<fn(&Connection) -> BombeResult<Option<u64>>>,
+}
+
+/// The full migration registry, in version order. [`SCHEMA_VERSION`] is
+/// derived from this list's last entry rather than hand-maintained, and
+/// [`migrate_schema`]/[`migrate_to_version`] run purely by looking entries
+/// up here — there is no separate `match` to keep in sync.
+const MIGRATIONS: [Migration; 20] = [
+    Migration {
+        version: 1,
+        name: "baseline",
+        up: migrate_to_v1,
+        down: Some(migrate_down_v1),
+    },
+    Migration {
+        version: 2,
+        name: "rebuild_symbol_fts",
+        up: migrate_to_v2,
+        down: None,
+    },
+    Migration {
+        version: 3,
+        name: "edges_file_line_index",
+        up: migrate_to_v3,
+        down: None,
+    },
+    Migration {
+        version: 4,
+        name: "create_sync_tables",
+        up: migrate_to_v4,
+        down: None,
+    },
+    Migration {
+        version: 5,
+        name: "create_trusted_signing_keys",
+        up: migrate_to_v5,
+        down: Some(migrate_down_v5),
+    },
+    Migration {
+        version: 6,
+        name: "create_indexing_diagnostics",
+        up: migrate_to_v6,
+        down: Some(migrate_down_v6),
+    },
+    Migration {
+        version: 7,
+        name: "external_deps_module_indexes",
+        up: migrate_to_v7,
+        down: Some(migrate_down_v7),
+    },
+    Migration {
+        version: 8,
+        name: "create_symbol_embeddings",
+        up: migrate_to_v8,
+        down: None,
+    },
+    Migration {
+        version: 9,
+        name: "circuit_breaker_tuning_columns",
+        up: migrate_to_v9,
+        down: None,
+    },
+    Migration {
+        version: 10,
+        name: "sync_queue_lease_columns",
+        up: migrate_to_v10,
+        down: None,
+    },
+    Migration {
+        version: 11,
+        name: "create_tool_latency_digests",
+        up: migrate_to_v11,
+        down: None,
+    },
+    Migration {
+        version: 12,
+        name: "optimistic_concurrency_versions",
+        up: migrate_to_v12,
+        down: None,
+    },
+    Migration {
+        version: 13,
+        name: "trusted_key_refresh_columns",
+        up: migrate_to_v13,
+        down: None,
+    },
+    Migration {
+        version: 14,
+        name: "create_key_signatures",
+        up: migrate_to_v14,
+        down: None,
+    },
+    Migration {
+        version: 15,
+        name: "create_algorithm_policies",
+        up: migrate_to_v15,
+        down: None,
+    },
+    Migration {
+        version: 16,
+        name: "symbol_embeddings_content_hash",
+        up: migrate_to_v16,
+        down: None,
+    },
+    Migration {
+        version: 17,
+        name: "edges_dispatch_column",
+        up: migrate_to_v17,
+        down: None,
+    },
+    Migration {
+        version: 18,
+        name: "symbols_supertypes_column",
+        up: migrate_to_v18,
+        down: None,
+    },
+    Migration {
+        version: 19,
+        name: "symbols_personalized_pagerank_column",
+        up: migrate_to_v19,
+        down: None,
+    },
+    Migration {
+        version: 20,
+        name: "migration_history_direction_column",
+        up: migrate_to_v20,
+        down: None,
+    },
+];
+
+fn find_migration(version: i32) -> BombeResult<&'static Migration> {
+    MIGRATIONS
+        .iter()
+        .find(|m| m.version == version)
+        .ok_or_else(|| {
+            BombeError::Database(format!("no migration registered for version {version}"))
+        })
+}
+
+/// Names of the migrations that have not yet run against `conn`, in the
+/// order they would be applied. Lets tooling describe what `migrate_schema`
+/// is about to do without actually running it.
+pub fn pending_migration_names(conn: &Connection) -> BombeResult<Vec<&'static str>> {
+    let current_version = get_schema_version(conn);
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current_version)
+        .map(|m| m.name)
+        .collect())
+}
+
+/// A status update emitted once per completed step by [`migrate_schema`] /
+/// [`migrate_to_version`], so callers can surface a heartbeat for
+/// long-running migrations instead of blocking silently.
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationProgress {
+    pub step_name: &'static str,
+    pub version: i32,
+    /// Rows touched by the step, where that's a meaningful thing to report
+    /// (e.g. the v2 FTS rebuild); `None` for steps that don't track it.
+    pub rows_affected: Option<u64>,
+}
+
 /// Run all pending migrations from the current stored version up to
 /// [`SCHEMA_VERSION`].  Each step is wrapped in a SAVEPOINT so a failure
 /// rolls back only that single step (matching the Python implementation).
-pub fn migrate_schema(conn: &Connection) -> BombeResult<()> {
+///
+/// `progress`, if given, is called once per completed step — see
+/// [`MigrationProgress`].
+///
+/// Fails loudly if the on-disk version is already newer than
+/// [`SCHEMA_VERSION`] — opening a database written by a newer build with an
+/// older binary would otherwise silently skip migrations it doesn't
+/// recognise and corrupt data instead. Also fails loudly, via a distinct
+/// [`BombeError::ForeignDatabase`], if the file's `application_id` marks it
+/// as something other than a bombe database — see [`check_application_id`].
+pub fn migrate_schema(
+    conn: &Connection,
+    progress: Option<&dyn Fn(MigrationProgress)>,
+) -> BombeResult<()> {
+    check_application_id(conn)?;
+    stamp_application_id(conn)?;
+
+    let current_version = get_schema_version(conn);
+    if current_version > SCHEMA_VERSION {
+        return Err(BombeError::Database(format!(
+            "database schema version {current_version} is newer than this build supports \
+             (max {SCHEMA_VERSION}); refusing to open it to avoid silent data loss"
+        )));
+    }
+
+    migrate_to_version(conn, SCHEMA_VERSION, progress)?;
+    store_schema_hash(conn)?;
+    Ok(())
+}
+
+/// Move `conn`'s schema to exactly `target`, walking the [`MIGRATIONS`] chain
+/// forward (running each step's `up`) or backward (running each step's
+/// `down`, highest version first) as needed, one per-step `SAVEPOINT` at a
+/// time, recording each attempt in `migration_history` with its direction.
+///
+/// If `progress` is given, it's called once per completed step with a
+/// [`MigrationProgress`] describing the step's name, the version it landed
+/// on, and (where the step reports one, e.g. the FTS rebuild) the number of
+/// rows it touched — a heartbeat for migrations that take a while on large
+/// repositories.
+///
+/// Unlike [`migrate_schema`], this will move the schema backwards on
+/// purpose — e.g. to debug a bad release, or downgrade a database to match
+/// an older binary — but only as far as a contiguous run of reversible steps
+/// reaches; it stops with an error at the first step whose `down` is `None`,
+/// leaving the schema at the last version it could safely reach.
+pub fn migrate_to_version(
+    conn: &Connection,
+    target: i32,
+    progress: Option<&dyn Fn(MigrationProgress)>,
+) -> BombeResult<()> {
+    if target > SCHEMA_VERSION {
+        return Err(BombeError::Database(format!(
+            "target version {target} is newer than this build supports (max {SCHEMA_VERSION})"
+        )));
+    }
+
     let mut current_version = get_schema_version(conn);
 
-    while current_version < SCHEMA_VERSION {
-        let next_version = current_version + 1;
+    while current_version != target {
+        let going_up = current_version < target;
+        // Going up, step N->N+1 is keyed by its destination version N+1;
+        // going down, step N-1->N is undone by keying on its origin N.
+        let step_version = if going_up {
+            current_version + 1
+        } else {
+            current_version
+        };
+        let migration = find_migration(step_version)?;
+        let next_version = if going_up {
+            step_version
+        } else {
+            step_version - 1
+        };
+        let direction = if going_up { "up" } else { "down" };
+
         conn.execute_batch("SAVEPOINT bombe_migrate_step;")?;
 
-        let step_result = (|| -> BombeResult<()> {
-            match next_version {
-                1 => migrate_to_v1(conn)?,
-                2 => migrate_to_v2(conn)?,
-                3 => migrate_to_v3(conn)?,
-                4 => migrate_to_v4(conn)?,
-                5 => migrate_to_v5(conn)?,
-                6 => migrate_to_v6(conn)?,
-                7 => migrate_to_v7(conn)?,
-                _ => {} // future versions: no-op until migration is defined
-            }
+        let step_result = (|| -> BombeResult<Option<u64>> {
+            let rows_affected = if going_up {
+                (migration.up)(conn)?
+            } else {
+                let down = migration.down.ok_or_else(|| {
+                    BombeError::Database(format!(
+                        "migration v{step_version} has no down step; cannot downgrade below it"
+                    ))
+                })?;
+                down(conn)?
+            };
             set_schema_version(conn, next_version)?;
-            record_migration_step(conn, current_version, next_version, "success", None)?;
+            record_migration_step(
+                conn,
+                current_version,
+                next_version,
+                "success",
+                None,
+                direction,
+            )?;
             conn.execute_batch("RELEASE SAVEPOINT bombe_migrate_step;")?;
-            Ok(())
+            Ok(rows_affected)
         })();
 
         match step_result {
-            Ok(()) => {
+            Ok(rows_affected) => {
                 current_version = next_version;
+                if let Some(cb) = progress {
+                    cb(MigrationProgress {
+                        step_name: migration.name,
+                        version: next_version,
+                        rows_affected,
+                    });
+                }
             }
             Err(e) => {
                 // Roll back just this step, then release the savepoint.
@@ -226,6 +610,7 @@ pub fn migrate_schema(conn: &Connection) -> BombeResult<()> {
                     next_version,
                     "failed",
                     Some(&e.to_string()),
+                    direction,
                 );
                 return Err(e);
             }
@@ -237,7 +622,7 @@ pub fn migrate_schema(conn: &Connection) -> BombeResult<()> {
 
 /// Read the current schema version from `repo_meta`.
 /// Returns 0 when the key is absent or unparseable.
-fn get_schema_version(conn: &Connection) -> i32 {
+pub(crate) fn get_schema_version(conn: &Connection) -> i32 {
     let result: Result<String, _> = conn.query_row(
         "SELECT value FROM repo_meta WHERE key = 'schema_version';",
         [],
@@ -260,6 +645,18 @@ fn set_schema_version(conn: &Connection, version: i32) -> BombeResult<()> {
     Ok(())
 }
 
+/// `true` once [`migrate_to_v20`] has added `migration_history.direction` on
+/// this connection. Needed because [`record_migration_step`] is called while
+/// walking versions 1..19 too, before that column exists.
+fn has_migration_direction_column(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT 1 FROM pragma_table_info('migration_history') WHERE name = 'direction';",
+        [],
+        |_| Ok(()),
+    )
+    .is_ok()
+}
+
 /// Insert one row into `migration_history` (best-effort; never fails the
 /// caller).
 fn record_migration_step(
@@ -268,74 +665,254 @@ fn record_migration_step(
     to_v: i32,
     status: &str,
     error_msg: Option<&str>,
+    direction: &str,
 ) -> BombeResult<()> {
+    if has_migration_direction_column(conn) {
+        conn.execute(
+            "INSERT INTO migration_history(from_version, to_version, status, error_message, direction) \
+             VALUES (?1, ?2, ?3, ?4, ?5);",
+            rusqlite::params![from_v, to_v, status, error_msg, direction],
+        )?;
+    } else {
+        conn.execute(
+            "INSERT INTO migration_history(from_version, to_version, status, error_message) \
+             VALUES (?1, ?2, ?3, ?4);",
+            rusqlite::params![from_v, to_v, status, error_msg],
+        )?;
+    }
+    Ok(())
+}
+
+// ─── Schema drift detection ─────────────────────────────────────────────────
+
+/// Tables/indexes we expect but don't see, ones present that we don't
+/// expect, and ones present under the right name with different DDL —
+/// see [`verify_schema_integrity`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriftReport {
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+    pub altered: Vec<String>,
+}
+
+impl DriftReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.altered.is_empty()
+    }
+}
+
+/// Collapses whitespace runs to single spaces and strips `IF NOT EXISTS` so
+/// two CREATE statements that differ only in incidental formatting compare
+/// equal.
+fn normalize_ddl(sql: &str) -> String {
+    let collapsed = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+    collapsed.replace("IF NOT EXISTS ", "").trim().to_string()
+}
+
+/// Extracts `("table" | "index", name)` from one of our own `CREATE ...`
+/// statements.
+fn parse_statement_identity(stmt: &str) -> Option<(&'static str, String)> {
+    const MARKERS: &[(&str, &str)] = &[
+        ("CREATE TABLE IF NOT EXISTS ", "table"),
+        ("CREATE VIRTUAL TABLE IF NOT EXISTS ", "table"),
+        ("CREATE INDEX IF NOT EXISTS ", "index"),
+    ];
+    for &(marker, kind) in MARKERS {
+        if let Some(pos) = stmt.find(marker) {
+            let rest = &stmt[pos + marker.len()..];
+            let name = rest.split_whitespace().next()?;
+            return Some((kind, name.to_string()));
+        }
+    }
+    None
+}
+
+/// Names of the shadow tables SQLite creates automatically behind each FTS5
+/// virtual table (`{name}_data`, `_idx`, `_docsize`, `_config`, `_content`).
+/// These aren't declared by any statement of ours and must be excluded from
+/// drift comparison.
+fn fts_shadow_table_names() -> HashSet<String> {
+    let mut names = HashSet::new();
+    for stmt in FTS_STATEMENTS {
+        if let Some(("table", base)) = parse_statement_identity(stmt) {
+            for suffix in ["_data", "_idx", "_docsize", "_config", "_content"] {
+                names.insert(format!("{base}{suffix}"));
+            }
+        }
+    }
+    names
+}
+
+/// `(type, name, normalized_sql)` for every table/index/FTS statement this
+/// build declares in [`SCHEMA_STATEMENTS`]/[`FTS_STATEMENTS`].
+///
+/// This reflects only the *base* shape each object was created with —
+/// columns added later by `ALTER TABLE` migrations (see `migrate_to_v9`
+/// onward) aren't reflected here, so a fully, correctly migrated database
+/// will legitimately show those tables as "altered" in
+/// [`verify_schema_integrity`]; that function answers "does `sqlite_master`
+/// match `SCHEMA_STATEMENTS`", not "is this database fully migrated" (use
+/// [`get_schema_version`] for that).
+fn expected_schema_entries() -> Vec<(&'static str, String, String)> {
+    SCHEMA_STATEMENTS
+        .iter()
+        .chain(FTS_STATEMENTS.iter())
+        .filter_map(|stmt| {
+            parse_statement_identity(stmt).map(|(kind, name)| (kind, name, normalize_ddl(stmt)))
+        })
+        .collect()
+}
+
+/// `(type, name, normalized_sql)` for every live table/index in `conn`,
+/// excluding SQLite's own internal objects (`sqlite_*`) and FTS5 shadow
+/// tables.
+fn actual_schema_entries(conn: &Connection) -> BombeResult<Vec<(String, String, String)>> {
+    let shadow = fts_shadow_table_names();
+    let mut stmt = conn.prepare(
+        "SELECT type, name, sql FROM sqlite_master \
+         WHERE sql IS NOT NULL AND type IN ('table', 'index');",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    let mut entries = Vec::new();
+    for row in rows {
+        let (kind, name, sql) = row?;
+        if name.starts_with("sqlite_") || shadow.contains(&name) {
+            continue;
+        }
+        entries.push((kind, name, normalize_ddl(&sql)));
+    }
+    Ok(entries)
+}
+
+/// Stable SHA-256 fingerprint over a sorted, normalized `(type, name, sql)`
+/// entry set — order-independent so fingerprinting doesn't depend on
+/// `sqlite_master`'s scan order.
+fn schema_fingerprint<K: AsRef<str>>(entries: &[(K, String, String)]) -> String {
+    let mut lines: Vec<String> = entries
+        .iter()
+        .map(|(kind, name, sql)| format!("{}:{}:{}", kind.as_ref(), name, sql))
+        .collect();
+    lines.sort();
+    let mut hasher = Sha256::new();
+    for line in &lines {
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Computes the expected-schema fingerprint (see [`expected_schema_entries`])
+/// and upserts it into `repo_meta` as `schema_hash`. Called at the end of
+/// [`migrate_schema`] so it always reflects the current build's
+/// `SCHEMA_STATEMENTS`/`FTS_STATEMENTS`, independent of which version the
+/// database happened to migrate from.
+fn store_schema_hash(conn: &Connection) -> BombeResult<()> {
+    let hash = schema_fingerprint(&expected_schema_entries());
     conn.execute(
-        "INSERT INTO migration_history(from_version, to_version, status, error_message) \
-         VALUES (?1, ?2, ?3, ?4);",
-        rusqlite::params![from_v, to_v, status, error_msg],
+        "INSERT INTO repo_meta(key, value) VALUES('schema_hash', ?1) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value;",
+        rusqlite::params![hash],
     )?;
     Ok(())
 }
 
+/// Compares the live schema in `sqlite_master` against this build's expected
+/// DDL and reports any drift. Catches manual edits, partial migrations, and
+/// FTS-less builds where `symbol_fts` was skipped — none of which the
+/// count-only `schema_statement_counts` test can.
+pub fn verify_schema_integrity(conn: &Connection) -> BombeResult<DriftReport> {
+    let expected = expected_schema_entries();
+    let actual = actual_schema_entries(conn)?;
+
+    let expected_map: HashMap<(String, String), String> = expected
+        .into_iter()
+        .map(|(kind, name, sql)| ((kind.to_string(), name), sql))
+        .collect();
+    let actual_map: HashMap<(String, String), String> = actual
+        .into_iter()
+        .map(|(kind, name, sql)| ((kind, name), sql))
+        .collect();
+
+    let mut report = DriftReport::default();
+    for (key, sql) in &expected_map {
+        match actual_map.get(key) {
+            None => report.missing.push(format!("{}:{}", key.0, key.1)),
+            Some(actual_sql) if actual_sql != sql => {
+                report.altered.push(format!("{}:{}", key.0, key.1))
+            }
+            Some(_) => {}
+        }
+    }
+    for key in actual_map.keys() {
+        if !expected_map.contains_key(key) {
+            report.extra.push(format!("{}:{}", key.0, key.1));
+        }
+    }
+    report.missing.sort();
+    report.extra.sort();
+    report.altered.sort();
+    Ok(report)
+}
+
 // ─── Individual migration steps ─────────────────────────────────────────────
 
 /// v0 -> v1: baseline, no-op.
-fn migrate_to_v1(_conn: &Connection) -> BombeResult<()> {
+fn migrate_to_v1(_conn: &Connection) -> BombeResult<Option<u64>> {
     // Intentionally empty -- baseline schema already created by SCHEMA_STATEMENTS.
-    Ok(())
+    Ok(None)
+}
+
+/// v1 -> v0: also a no-op, for the same reason `migrate_to_v1` is — the
+/// baseline tables belong to `SCHEMA_STATEMENTS`, not this step.
+fn migrate_down_v1(_conn: &Connection) -> BombeResult<Option<u64>> {
+    Ok(None)
 }
 
 /// v1 -> v2: rebuild FTS index from the `symbols` table.
-fn migrate_to_v2(conn: &Connection) -> BombeResult<()> {
+///
+/// Set-based rather than a per-row prepared `INSERT` — a single
+/// `INSERT ... SELECT` is one statement regardless of table size, where the
+/// old row-at-a-time loop was O(n) statements and crawled on repos with
+/// hundreds of thousands of symbols. Returns the row count so callers with a
+/// `progress` callback (see [`migrate_to_version`]) get a heartbeat out of
+/// this, the slowest step in the chain.
+fn migrate_to_v2(conn: &Connection) -> BombeResult<Option<u64>> {
     // Check whether the FTS table exists at all; if not, nothing to rebuild.
     let fts_exists = conn
         .query_row("SELECT 1 FROM symbol_fts LIMIT 1;", [], |_| Ok(()))
         .is_ok();
     if !fts_exists {
-        return Ok(());
+        return Ok(None);
     }
 
     conn.execute_batch("DELETE FROM symbol_fts;")?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, name, qualified_name, \
-                COALESCE(docstring, '') AS docstring, \
-                COALESCE(signature, '') AS signature \
+    let rows_affected = conn.execute(
+        "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
+         SELECT id, name, qualified_name, COALESCE(docstring, ''), COALESCE(signature, '') \
          FROM symbols;",
+        [],
     )?;
 
-    let rows = stmt.query_map([], |row| {
-        Ok((
-            row.get::<_, i64>(0)?,
-            row.get::<_, String>(1)?,
-            row.get::<_, String>(2)?,
-            row.get::<_, String>(3)?,
-            row.get::<_, String>(4)?,
-        ))
-    })?;
-
-    for row_result in rows {
-        let (id, name, qualified_name, docstring, signature) = row_result?;
-        conn.execute(
-            "INSERT INTO symbol_fts(symbol_id, name, qualified_name, docstring, signature) \
-             VALUES (?1, ?2, ?3, ?4, ?5);",
-            rusqlite::params![id, name, qualified_name, docstring, signature],
-        )?;
-    }
-
-    Ok(())
+    Ok(Some(rows_affected as u64))
 }
 
 /// v2 -> v3: add `idx_edges_file_line` index.
-fn migrate_to_v3(conn: &Connection) -> BombeResult<()> {
+fn migrate_to_v3(conn: &Connection) -> BombeResult<Option<u64>> {
     conn.execute_batch(
         "CREATE INDEX IF NOT EXISTS idx_edges_file_line ON edges(file_path, line_number);",
     )?;
-    Ok(())
+    Ok(None)
 }
 
 /// v3 -> v4: create sync-related tables and their indexes.
-fn migrate_to_v4(conn: &Connection) -> BombeResult<()> {
+fn migrate_to_v4(conn: &Connection) -> BombeResult<Option<u64>> {
     conn.execute_batch(
         "CREATE TABLE IF NOT EXISTS migration_history (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -418,11 +995,11 @@ fn migrate_to_v4(conn: &Connection) -> BombeResult<()> {
         "CREATE INDEX IF NOT EXISTS idx_tool_metrics_tool_created \
          ON tool_metrics(tool_name, created_at);",
     )?;
-    Ok(())
+    Ok(None)
 }
 
 /// v4 -> v5: create `trusted_signing_keys` table and index.
-fn migrate_to_v5(conn: &Connection) -> BombeResult<()> {
+fn migrate_to_v5(conn: &Connection) -> BombeResult<Option<u64>> {
     conn.execute_batch(
         "CREATE TABLE IF NOT EXISTS trusted_signing_keys (
             repo_id TEXT NOT NULL,
@@ -439,11 +1016,17 @@ fn migrate_to_v5(conn: &Connection) -> BombeResult<()> {
         "CREATE INDEX IF NOT EXISTS idx_trusted_keys_repo_active \
          ON trusted_signing_keys(repo_id, active, key_id);",
     )?;
-    Ok(())
+    Ok(None)
+}
+
+/// v5 -> v4: drop `trusted_signing_keys` (its index goes with the table).
+fn migrate_down_v5(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("DROP TABLE IF EXISTS trusted_signing_keys;")?;
+    Ok(None)
 }
 
 /// v5 -> v6: create `indexing_diagnostics` table and indexes.
-fn migrate_to_v6(conn: &Connection) -> BombeResult<()> {
+fn migrate_to_v6(conn: &Connection) -> BombeResult<Option<u64>> {
     conn.execute_batch(
         "CREATE TABLE IF NOT EXISTS indexing_diagnostics (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -474,11 +1057,18 @@ fn migrate_to_v6(conn: &Connection) -> BombeResult<()> {
         "CREATE INDEX IF NOT EXISTS idx_index_diag_severity_created \
          ON indexing_diagnostics(severity, created_at);",
     )?;
-    Ok(())
+    Ok(None)
+}
+
+/// v6 -> v5: drop `indexing_diagnostics` and its indexes (the indexes go
+/// with the table).
+fn migrate_down_v6(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("DROP TABLE IF EXISTS indexing_diagnostics;")?;
+    Ok(None)
 }
 
 /// v6 -> v7: add indexes on `external_deps` for module-name lookups.
-fn migrate_to_v7(conn: &Connection) -> BombeResult<()> {
+fn migrate_to_v7(conn: &Connection) -> BombeResult<Option<u64>> {
     conn.execute_batch(
         "CREATE INDEX IF NOT EXISTS idx_external_deps_module \
          ON external_deps(module_name);",
@@ -487,7 +1077,211 @@ fn migrate_to_v7(conn: &Connection) -> BombeResult<()> {
         "CREATE INDEX IF NOT EXISTS idx_external_deps_file_module \
          ON external_deps(file_path, module_name);",
     )?;
-    Ok(())
+    Ok(None)
+}
+
+/// v7 -> v6: drop the `external_deps` module-lookup indexes added by
+/// `migrate_to_v7`; the table itself predates v7 and is left alone.
+fn migrate_down_v7(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("DROP INDEX IF EXISTS idx_external_deps_module;")?;
+    conn.execute_batch("DROP INDEX IF EXISTS idx_external_deps_file_module;")?;
+    Ok(None)
+}
+
+/// v7 -> v8: create `symbol_embeddings` for semantic seed retrieval.
+///
+/// Vectors are stored as raw little-endian f32 bytes rather than a JSON
+/// array so loading them for the in-process HNSW-lite index (see
+/// `query::semantic_index`) avoids a parse pass per row.
+fn migrate_to_v8(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS symbol_embeddings (
+            symbol_id INTEGER PRIMARY KEY REFERENCES symbols(id),
+            model TEXT NOT NULL,
+            dim INTEGER NOT NULL,
+            vector BLOB NOT NULL,
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );",
+    )?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_symbol_embeddings_model \
+         ON symbol_embeddings(model);",
+    )?;
+    Ok(None)
+}
+
+/// v8 -> v9: give `circuit_breakers` the columns `record_circuit_outcome`/
+/// `evaluate_circuit` need to run the open/half-open/closed state machine
+/// server-side instead of in Python: per-repo `threshold`/`cooldown_secs`/
+/// `half_open_probe_count` configuration, plus `half_open_probes_used` to
+/// track how many probe calls a half-open circuit has already let through.
+fn migrate_to_v9(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "ALTER TABLE circuit_breakers ADD COLUMN threshold INTEGER NOT NULL DEFAULT 5;",
+    )?;
+    conn.execute_batch(
+        "ALTER TABLE circuit_breakers ADD COLUMN cooldown_secs INTEGER NOT NULL DEFAULT 60;",
+    )?;
+    conn.execute_batch(
+        "ALTER TABLE circuit_breakers ADD COLUMN half_open_probe_count INTEGER NOT NULL DEFAULT 1;",
+    )?;
+    conn.execute_batch(
+        "ALTER TABLE circuit_breakers ADD COLUMN half_open_probes_used INTEGER NOT NULL DEFAULT 0;",
+    )?;
+    Ok(None)
+}
+
+/// v9 -> v10: give `sync_queue` the columns `claim_sync_deltas`/
+/// `reclaim_expired_leases`/`mark_sync_delta_status` need for lease-based,
+/// multi-worker dequeue: `worker_id`/`lease_expires_at` track who currently
+/// holds an `in_flight` row and until when, and `next_attempt_at` is the
+/// earliest a `retry` row becomes claimable again (exponential backoff with
+/// jitter, computed by `mark_sync_delta_status`).
+fn migrate_to_v10(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("ALTER TABLE sync_queue ADD COLUMN worker_id TEXT;")?;
+    conn.execute_batch("ALTER TABLE sync_queue ADD COLUMN lease_expires_at TEXT;")?;
+    conn.execute_batch("ALTER TABLE sync_queue ADD COLUMN next_attempt_at TEXT;")?;
+    Ok(None)
+}
+
+/// v10 -> v11: create `tool_latency_digests`, the persisted t-digest
+/// summaries `Database::record_tool_metric`/`Database::tool_latency_quantiles`
+/// use for O(centroids) p50/p95/p99 latency queries (see
+/// `crate::store::tdigest`).
+fn migrate_to_v11(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tool_latency_digests (
+            tool_name TEXT NOT NULL,
+            mode TEXT NOT NULL,
+            digest_json TEXT NOT NULL,
+            sample_count INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY(tool_name, mode)
+        );",
+    )?;
+    Ok(None)
+}
+
+/// v11 -> v12: give `artifact_pins`, `artifact_quarantine`, and
+/// `circuit_breakers` a monotonic `version` column, so `Database::atomic_apply`
+/// can do Deno-KV-style optimistic compare-and-swap checks against them
+/// (`None` meaning "row must not exist").
+fn migrate_to_v12(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("ALTER TABLE artifact_pins ADD COLUMN version INTEGER NOT NULL DEFAULT 1;")?;
+    conn.execute_batch(
+        "ALTER TABLE artifact_quarantine ADD COLUMN version INTEGER NOT NULL DEFAULT 1;",
+    )?;
+    conn.execute_batch(
+        "ALTER TABLE circuit_breakers ADD COLUMN version INTEGER NOT NULL DEFAULT 1;",
+    )?;
+    Ok(None)
+}
+
+/// v12 -> v13: give `trusted_signing_keys` an expiry/refresh-scheduling
+/// trio — `expires_at`, `last_refreshed_at`, and `refresh_jitter_secs` —
+/// for `Database::list_keys_due_for_refresh` (see
+/// `Database::set_trusted_signing_key`, which stamps all three on upsert).
+fn migrate_to_v13(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("ALTER TABLE trusted_signing_keys ADD COLUMN expires_at TEXT;")?;
+    conn.execute_batch("ALTER TABLE trusted_signing_keys ADD COLUMN last_refreshed_at TEXT;")?;
+    conn.execute_batch(
+        "ALTER TABLE trusted_signing_keys ADD COLUMN refresh_jitter_secs REAL NOT NULL DEFAULT 0;",
+    )?;
+    Ok(None)
+}
+
+/// v13 -> v14: create the `key_signatures` table and its index, so one
+/// trusted key can endorse (cross-sign) another, modeling rotation/trust
+/// chains for `Database::add_key_signature`/`Database::get_trust_chain`.
+fn migrate_to_v14(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS key_signatures (
+            repo_id TEXT NOT NULL,
+            signer_key_id TEXT NOT NULL,
+            signed_key_id TEXT NOT NULL,
+            signature TEXT NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY(repo_id, signer_key_id, signed_key_id),
+            FOREIGN KEY(repo_id, signer_key_id) REFERENCES trusted_signing_keys(repo_id, key_id),
+            FOREIGN KEY(repo_id, signed_key_id) REFERENCES trusted_signing_keys(repo_id, key_id)
+        );",
+    )?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_key_signatures_signed ON key_signatures(repo_id, signed_key_id);",
+    )?;
+    Ok(None)
+}
+
+/// v14 -> v15: create the `algorithm_policies` table backing
+/// `Database::set_algorithm_policy`/`Database::get_algorithm_policy`,
+/// a per-repo allow/deny list enforced on signing-key upsert and again at
+/// verification time.
+fn migrate_to_v15(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS algorithm_policies (
+            repo_id TEXT NOT NULL,
+            algorithm TEXT NOT NULL,
+            mode TEXT NOT NULL CHECK(mode IN ('allow', 'deny')),
+            PRIMARY KEY(repo_id, algorithm)
+        );",
+    )?;
+    Ok(None)
+}
+
+/// v15 -> v16: give `symbol_embeddings` a `content_hash` column, so
+/// `Database::upsert_symbol_embedding` can skip re-embedding a chunk whose
+/// source hasn't changed since the stored vector was written (see
+/// `indexer::embedding`).
+fn migrate_to_v16(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("ALTER TABLE symbol_embeddings ADD COLUMN content_hash TEXT;")?;
+    Ok(None)
+}
+
+/// v16 -> v17: give `edges` a `dispatch` column (`'direct'` or `'virtual'`),
+/// so `indexer::callgraph::expand_virtual_dispatch_edges` can materialize
+/// synthetic `CALLS` edges for polymorphic dispatch (caller -> concrete
+/// override, via the target's `IMPLEMENTS`/`EXTENDS` edges) without being
+/// mistaken for a name-resolved direct call. `get_blast_radius_impl` and
+/// `change_impact_impl` surface this on every caller they report so callers
+/// can tell a certain impact from a merely possible one.
+fn migrate_to_v17(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "ALTER TABLE edges ADD COLUMN dispatch TEXT NOT NULL DEFAULT 'direct';",
+    )?;
+    Ok(None)
+}
+
+/// v17 -> v18: give `symbols` a `supertypes` column (comma-joined names, or
+/// NULL), so `indexer::callgraph::build_call_edges` can resolve a call
+/// through an interface-typed receiver to every implementor's method
+/// instead of dropping it — see `resolve_targets`'s interface-dispatch
+/// strategy and `indexer::symbols::ExtractedSymbol::supertypes`.
+fn migrate_to_v18(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch("ALTER TABLE symbols ADD COLUMN supertypes TEXT;")?;
+    Ok(None)
+}
+
+/// v18 -> v19: give `symbols` a `personalized_pagerank_score` column so
+/// `indexer::pagerank::recompute_pagerank_personalized`'s seed-rooted scores
+/// can be persisted alongside the global `pagerank_score` instead of
+/// overwriting it — the two answer different questions ("important in the
+/// whole graph" vs. "important relative to this seed set") and callers may
+/// want both at once.
+fn migrate_to_v19(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "ALTER TABLE symbols ADD COLUMN personalized_pagerank_score REAL DEFAULT 0.0;",
+    )?;
+    Ok(None)
+}
+
+/// v19 -> v20: give `migration_history` a `direction` column (`'up'` or
+/// `'down'`) so [`migrate_to_version`] can log which way each recorded step
+/// actually ran, now that steps can run either way.
+fn migrate_to_v20(conn: &Connection) -> BombeResult<Option<u64>> {
+    conn.execute_batch(
+        "ALTER TABLE migration_history ADD COLUMN direction TEXT NOT NULL DEFAULT 'up';",
+    )?;
+    Ok(None)
 }
 
 #[cfg(test)]
@@ -497,11 +1291,27 @@ mod tests {
     /// Verify that the constant arrays have the expected sizes.
     #[test]
     fn schema_statement_counts() {
-        // 15 tables + 18 indexes = 33 statements
-        assert_eq!(SCHEMA_STATEMENTS.len(), 33);
+        // 18 tables + 19 indexes = 37 statements
+        assert_eq!(SCHEMA_STATEMENTS.len(), 37);
         assert_eq!(FTS_STATEMENTS.len(), 2);
     }
 
+    /// [`MIGRATIONS`] must be a strictly contiguous run starting at 1, with
+    /// no gaps or duplicates — tooling and [`find_migration`] both assume
+    /// `version == index + 1`.
+    #[test]
+    fn migrations_are_contiguous() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(
+                migration.version,
+                (i + 1) as i32,
+                "migration at index {i} has version {} (expected {})",
+                migration.version,
+                i + 1
+            );
+        }
+    }
+
     /// A fresh in-memory database should migrate cleanly to the current version.
     #[test]
     fn migrate_fresh_database() {
@@ -518,7 +1328,7 @@ mod tests {
         }
 
         // Run migrations.
-        migrate_schema(&conn).unwrap();
+        migrate_schema(&conn, None).unwrap();
 
         // Version should now be current.
         assert_eq!(get_schema_version(&conn), SCHEMA_VERSION);
@@ -537,9 +1347,100 @@ mod tests {
             let _ = conn.execute_batch(stmt);
         }
 
-        migrate_schema(&conn).unwrap();
-        migrate_schema(&conn).unwrap();
+        migrate_schema(&conn, None).unwrap();
+        migrate_schema(&conn, None).unwrap();
 
         assert_eq!(get_schema_version(&conn), SCHEMA_VERSION);
     }
+
+    /// Downgrading through a contiguous run of reversible steps (v7 -> v6 ->
+    /// v5) should land exactly on the target version and leave the tables
+    /// those steps own gone.
+    #[test]
+    fn migrate_to_version_downgrades_reversible_steps() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+
+        for stmt in SCHEMA_STATEMENTS {
+            conn.execute_batch(stmt).unwrap();
+        }
+        for stmt in FTS_STATEMENTS {
+            let _ = conn.execute_batch(stmt);
+        }
+
+        migrate_to_version(&conn, 7, None).unwrap();
+        assert_eq!(get_schema_version(&conn), 7);
+
+        migrate_to_version(&conn, 5, None).unwrap();
+        assert_eq!(get_schema_version(&conn), 5);
+        assert!(conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'trusted_signing_keys';",
+                [],
+                |_| Ok(())
+            )
+            .is_err());
+
+        // And back up again.
+        migrate_to_version(&conn, 7, None).unwrap();
+        assert_eq!(get_schema_version(&conn), 7);
+    }
+
+    /// Downgrading past a step with no `down` (e.g. v8, an `ALTER TABLE ADD
+    /// COLUMN`) must fail rather than silently dropping data.
+    #[test]
+    fn migrate_to_version_refuses_irreversible_downgrade() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+
+        for stmt in SCHEMA_STATEMENTS {
+            conn.execute_batch(stmt).unwrap();
+        }
+        for stmt in FTS_STATEMENTS {
+            let _ = conn.execute_batch(stmt);
+        }
+
+        migrate_to_version(&conn, 8, None).unwrap();
+        assert!(migrate_to_version(&conn, 4, None).is_err());
+        // The failed step should not have moved the stored version.
+        assert_eq!(get_schema_version(&conn), 8);
+    }
+
+    /// A freshly migrated database has a symbol_embeddings table (added only
+    /// by `migrate_to_v8`, never in `SCHEMA_STATEMENTS`) and several columns
+    /// added by later `ALTER TABLE` steps, so it should report that gap as
+    /// "extra"/"altered" rather than falsely claiming a clean match.
+    #[test]
+    fn verify_schema_integrity_reports_migration_added_objects() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        for stmt in SCHEMA_STATEMENTS {
+            conn.execute_batch(stmt).unwrap();
+        }
+        for stmt in FTS_STATEMENTS {
+            let _ = conn.execute_batch(stmt);
+        }
+        migrate_schema(&conn, None).unwrap();
+
+        let report = verify_schema_integrity(&conn).unwrap();
+        assert!(!report.is_clean());
+        assert!(report
+            .extra
+            .contains(&"table:symbol_embeddings".to_string()));
+        assert!(report.altered.contains(&"table:symbols".to_string()));
+    }
+
+    /// A table we expect but that was manually dropped shows up as missing.
+    #[test]
+    fn verify_schema_integrity_reports_missing_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        for stmt in SCHEMA_STATEMENTS {
+            conn.execute_batch(stmt).unwrap();
+        }
+        conn.execute_batch("DROP TABLE parameters;").unwrap();
+
+        let report = verify_schema_integrity(&conn).unwrap();
+        assert!(report.missing.contains(&"table:parameters".to_string()));
+    }
 }
@@ -0,0 +1,480 @@
+//! Pluggable shard storage backends.
+//!
+//! `ShardRouter` has so far assumed every shard is a SQLite [`Database`].
+//! [`StoreBackend`] pulls the operations it actually needs — open a shard,
+//! make sure its schema exists, run a read query, and count rows in a table
+//! — behind a trait, so a shard can eventually live on a different engine
+//! (an embedded KV store for large, read-heavy shards that would otherwise
+//! contend on SQLite's single-writer lock) without the router caring which
+//! one it's talking to. This mirrors [`crate::store::backend::StorageBackend`]:
+//! that trait generalizes the symbol/edge operations the query/indexing
+//! layer needs, this one generalizes the raw-query/count operations the
+//! sharding layer needs — same idea, different call site.
+//!
+//! [`SqliteShardBackend`] is the only backend this build compiles in by
+//! default. [`RocksShardBackend`] is gated behind the `rocksdb_backend`
+//! feature, the same way [`crate::store::database::Database`] gates
+//! SQLCipher support behind `sqlcipher` — declared for the engines that want
+//! it, not built into every target. RocksDB has no query planner, so
+//! `RocksShardBackend::query` only supports what it can actually do (a full
+//! table scan by key prefix), not arbitrary SQL; callers that need joins or
+//! `WHERE` clauses still belong on a `"sqlite"` shard.
+//!
+//! [`ShardConnectionPool`] is a separate concern from [`StoreBackend`]: it
+//! doesn't abstract over engines, it just avoids paying `Connection::open`
+//! per call when the same query needs to run across many SQLite shard
+//! files at once, fanning the work out over `crossbeam` scoped threads.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use rusqlite::Connection;
+
+use crate::errors::{BombeError, BombeResult};
+use crate::store::database::{ConnectionOptions, Database};
+use crate::store::sharding::catalog::ShardCatalog;
+
+/// Tables `convert_shard` knows how to stream. Kept in sync with the core
+/// tables [`crate::store::backend::StorageBackend`] writes through.
+const MIGRATABLE_TABLES: &[&str] = &["symbols", "parameters", "edges"];
+
+fn validate_table_name(table: &str) -> BombeResult<()> {
+    if MIGRATABLE_TABLES.contains(&table) {
+        Ok(())
+    } else {
+        Err(BombeError::Database(format!(
+            "unknown or unsupported shard table: {table}"
+        )))
+    }
+}
+
+/// One row, as `(column_name, text_value)` pairs in column order. Values are
+/// stringified at the backend boundary so callers don't need a typed column
+/// schema that a KV backend can't supply.
+pub type BackendRow = Vec<(String, Option<String>)>;
+
+/// Storage operations `ShardRouter` needs from a shard, independent of the
+/// engine backing it. The catalog's `shards.backend` column (`"sqlite"` by
+/// default) selects which implementation `open_backend` constructs.
+pub trait StoreBackend: Send + Sync {
+    /// Create any tables/indexes the backend needs, if they don't exist yet.
+    fn init_schema(&self) -> BombeResult<()>;
+
+    /// Run a read query and return its rows. `params` are bound positionally
+    /// as text; backends that can't run arbitrary queries (key-value stores)
+    /// return an error instead of a best-effort partial result.
+    fn query(&self, sql: &str, params: &[String]) -> BombeResult<Vec<BackendRow>>;
+
+    /// Number of rows in `table` (one of [`MIGRATABLE_TABLES`]).
+    fn count(&self, table: &str) -> BombeResult<i64>;
+
+    /// Write `rows` into `table`, keyed by `columns` (same order as each
+    /// row). Only used by `convert_shard` — the read-heavy query paths this
+    /// trait was designed for never call it.
+    fn insert_rows(&self, table: &str, columns: &[String], rows: &[BackendRow]) -> BombeResult<()>;
+}
+
+/// Default [`StoreBackend`]: the existing SQLite [`Database`].
+pub struct SqliteShardBackend {
+    db: Database,
+}
+
+impl SqliteShardBackend {
+    pub fn open(path: &Path, options: ConnectionOptions) -> BombeResult<Self> {
+        let db = Database::new(path.to_path_buf(), None, Some(options))
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        Ok(Self { db })
+    }
+}
+
+impl StoreBackend for SqliteShardBackend {
+    fn init_schema(&self) -> BombeResult<()> {
+        self.db
+            .init_schema()
+            .map_err(|e| BombeError::Database(e.to_string()))
+    }
+
+    fn query(&self, sql: &str, params: &[String]) -> BombeResult<Vec<BackendRow>> {
+        let conn = self.db.connect_internal()?;
+        let mut stmt = conn.prepare(sql)?;
+        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
+        let param_refs: Vec<&dyn rusqlite::types::ToSql> = params
+            .iter()
+            .map(|p| p as &dyn rusqlite::types::ToSql)
+            .collect();
+        let mut rows_out = Vec::new();
+        let mut rows = stmt.query(param_refs.as_slice())?;
+        while let Some(row) = rows.next()? {
+            let out_row: BackendRow = col_names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    (
+                        name.clone(),
+                        row.get::<_, Option<String>>(i).unwrap_or(None),
+                    )
+                })
+                .collect();
+            rows_out.push(out_row);
+        }
+        Ok(rows_out)
+    }
+
+    fn count(&self, table: &str) -> BombeResult<i64> {
+        validate_table_name(table)?;
+        let conn = self.db.connect_internal()?;
+        let count = conn.query_row(&format!("SELECT COUNT(*) FROM {table};"), [], |row| {
+            row.get(0)
+        })?;
+        Ok(count)
+    }
+
+    fn insert_rows(&self, table: &str, columns: &[String], rows: &[BackendRow]) -> BombeResult<()> {
+        validate_table_name(table)?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let conn = self.db.connect_internal()?;
+        let placeholders = vec!["?"; columns.len()].join(", ");
+        let sql = format!(
+            "INSERT INTO {table} ({}) VALUES ({placeholders});",
+            columns.join(", ")
+        );
+        let mut stmt = conn.prepare_cached(&sql)?;
+        for row in rows {
+            let values: Vec<Option<String>> = columns
+                .iter()
+                .map(|col| {
+                    row.iter()
+                        .find(|(name, _)| name == col)
+                        .and_then(|(_, v)| v.clone())
+                })
+                .collect();
+            let param_refs: Vec<&dyn rusqlite::types::ToSql> = values
+                .iter()
+                .map(|v| v as &dyn rusqlite::types::ToSql)
+                .collect();
+            stmt.execute(param_refs.as_slice())?;
+        }
+        Ok(())
+    }
+}
+
+/// Embedded-KV [`StoreBackend`] for large, read-heavy shards that would
+/// otherwise contend on SQLite's single-writer lock. Each row is stored
+/// under the key `"{table}:{row_index}"`; since RocksDB has no query
+/// planner, `query` is left unimplemented (callers that need it belong on a
+/// `"sqlite"` shard) and only `count`/`insert_rows` — the operations a
+/// prefix scan can actually answer — are supported.
+#[cfg(feature = "rocksdb_backend")]
+pub struct RocksShardBackend {
+    db: rocksdb::DB,
+}
+
+#[cfg(feature = "rocksdb_backend")]
+impl RocksShardBackend {
+    pub fn open(path: &Path, _options: ConnectionOptions) -> BombeResult<Self> {
+        let db =
+            rocksdb::DB::open_default(path).map_err(|e| BombeError::Database(e.to_string()))?;
+        Ok(Self { db })
+    }
+
+    fn row_key(table: &str, index: usize) -> String {
+        format!("{table}:{index}")
+    }
+
+    fn encode_row(columns: &[String], row: &BackendRow) -> String {
+        columns
+            .iter()
+            .map(|col| {
+                row.iter()
+                    .find(|(name, _)| name == col)
+                    .and_then(|(_, v)| v.clone())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join("\u{1f}")
+    }
+}
+
+#[cfg(feature = "rocksdb_backend")]
+impl StoreBackend for RocksShardBackend {
+    fn init_schema(&self) -> BombeResult<()> {
+        // Column families are implicit (key-prefixed), nothing to create.
+        Ok(())
+    }
+
+    fn query(&self, _sql: &str, _params: &[String]) -> BombeResult<Vec<BackendRow>> {
+        Err(BombeError::Database(
+            "RocksShardBackend does not support arbitrary SQL queries".into(),
+        ))
+    }
+
+    fn count(&self, table: &str) -> BombeResult<i64> {
+        validate_table_name(table)?;
+        let prefix = format!("{table}:");
+        Ok(self.db.prefix_iterator(prefix.as_bytes()).count() as i64)
+    }
+
+    fn insert_rows(&self, table: &str, columns: &[String], rows: &[BackendRow]) -> BombeResult<()> {
+        validate_table_name(table)?;
+        let existing = self.count(table)?;
+        for (i, row) in rows.iter().enumerate() {
+            let key = Self::row_key(table, existing as usize + i);
+            let value = Self::encode_row(columns, row);
+            self.db
+                .put(key.as_bytes(), value.as_bytes())
+                .map_err(|e| BombeError::Database(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Construct the [`StoreBackend`] named by a shard's `backend` catalog
+/// column. `"sqlite"` is always available; `"rocksdb"` requires this crate
+/// to be built with the `rocksdb_backend` feature, and is otherwise rejected
+/// with a clear error instead of silently falling back to SQLite.
+pub fn open_backend(
+    backend: &str,
+    path: &Path,
+    options: ConnectionOptions,
+) -> BombeResult<Box<dyn StoreBackend>> {
+    match backend {
+        "sqlite" => Ok(Box::new(SqliteShardBackend::open(path, options)?)),
+        #[cfg(feature = "rocksdb_backend")]
+        "rocksdb" => Ok(Box::new(RocksShardBackend::open(path, options)?)),
+        #[cfg(not(feature = "rocksdb_backend"))]
+        "rocksdb" => Err(BombeError::Database(
+            "rocksdb shard backend requires the rocksdb_backend feature".into(),
+        )),
+        other => Err(BombeError::Database(format!(
+            "unknown shard backend: {other}"
+        ))),
+    }
+}
+
+/// Stream `src`'s rows for every table in [`MIGRATABLE_TABLES`] into `dst`.
+/// `dst` must already have its schema initialised (callers typically call
+/// `dst.init_schema()` right after `open_backend`). Returns the total number
+/// of rows copied.
+fn copy_tables(src: &dyn StoreBackend, dst: &dyn StoreBackend) -> BombeResult<i64> {
+    let mut total = 0i64;
+    for &table in MIGRATABLE_TABLES {
+        let rows = src.query(&format!("SELECT * FROM {table};"), &[])?;
+        let Some(first) = rows.first() else {
+            continue;
+        };
+        let columns: Vec<String> = first.iter().map(|(name, _)| name.clone()).collect();
+        dst.insert_rows(table, &columns, &rows)?;
+        total += rows.len() as i64;
+    }
+    Ok(total)
+}
+
+/// Migrate a registered shard's symbols/parameters/edges from whatever
+/// backend it's currently on to `dst_backend` at `dst_path`, so operators
+/// can change a shard's storage engine without reindexing. Does not update
+/// the catalog — callers that want the shard to actually use the new
+/// location/backend afterwards should follow up with
+/// `register_shard(src_repo_id, repo_path, dst_path, dst_backend)`.
+///
+/// Returns the number of rows copied.
+#[pyfunction]
+pub fn convert_shard(
+    catalog: &ShardCatalog,
+    src_repo_id: &str,
+    dst_path: &str,
+    dst_backend: &str,
+) -> PyResult<i64> {
+    let src_path = catalog
+        .get_shard_db_path(src_repo_id)?
+        .ok_or_else(|| BombeError::Database(format!("unknown shard: {src_repo_id}")))?;
+    let src_backend_name = catalog
+        .get_shard_backend(src_repo_id)?
+        .unwrap_or_else(|| "sqlite".to_string());
+
+    let src = open_backend(
+        &src_backend_name,
+        Path::new(&src_path),
+        ConnectionOptions::default(),
+    )?;
+    let dst = open_backend(
+        dst_backend,
+        Path::new(dst_path),
+        ConnectionOptions::default(),
+    )?;
+    dst.init_schema()?;
+
+    Ok(copy_tables(src.as_ref(), dst.as_ref())?)
+}
+
+// ---------------------------------------------------------------------------
+// ShardConnectionPool: read fan-out across many shard SQLite files
+// ---------------------------------------------------------------------------
+
+/// A reusable read connection per shard `db_path`, so a query that touches
+/// many shards (e.g. looking up a symbol's local `edges` across every repo
+/// that might reference it) doesn't pay `Connection::open`'s cost on every
+/// call the way `SqliteShardBackend::query` does today. Unlike
+/// [`Database::read_pool`], which pools several interchangeable connections
+/// to the *same* database, this pools one connection *per distinct shard* —
+/// each shard keeps its own dedicated slot rather than competing for a
+/// shared handful.
+///
+/// Each slot is its own `Mutex`, so [`ShardConnectionPool::fan_out_query`]
+/// querying shard A and shard B at the same time contends on nothing;
+/// queries landing on the *same* shard concurrently still serialize behind
+/// that shard's lock, same as SQLite would serialize them anyway.
+#[pyclass]
+pub struct ShardConnectionPool {
+    connections: Mutex<HashMap<String, Arc<Mutex<Connection>>>>,
+}
+
+impl ShardConnectionPool {
+    /// The pooled connection for `db_path`, opening and caching one (with
+    /// `options` applied) if this is the first request for that shard.
+    fn connection_for(
+        &self,
+        db_path: &str,
+        options: &ConnectionOptions,
+    ) -> BombeResult<Arc<Mutex<Connection>>> {
+        let mut connections = self
+            .connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(conn) = connections.get(db_path) {
+            return Ok(Arc::clone(conn));
+        }
+        let conn = Connection::open(db_path)?;
+        let mut pragmas = String::new();
+        if options.enable_foreign_keys {
+            pragmas.push_str("PRAGMA foreign_keys = ON;");
+        }
+        if let Some(ms) = options.busy_timeout_ms {
+            pragmas.push_str(&format!("PRAGMA busy_timeout = {ms};"));
+        }
+        if options.wal_mode {
+            pragmas.push_str("PRAGMA journal_mode = WAL;");
+        }
+        if !pragmas.is_empty() {
+            conn.execute_batch(&pragmas)?;
+        }
+        let conn = Arc::new(Mutex::new(conn));
+        connections.insert(db_path.to_string(), Arc::clone(&conn));
+        Ok(conn)
+    }
+}
+
+#[pymethods]
+impl ShardConnectionPool {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            connections: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Number of distinct shard `db_path`s with an open pooled connection.
+    pub fn pooled_shard_count(&self) -> usize {
+        self.connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    /// Run `sql`/`params` against every shard in `repo_ids` concurrently,
+    /// one `crossbeam` scoped thread per shard, each reusing this pool's
+    /// connection for its `db_path` instead of opening a fresh one. A
+    /// `repo_id` with no registered, enabled shard (`get_shard_db_path`
+    /// returns `None`) is skipped rather than erroring, since a fan-out
+    /// caller resolving `repo_id`s from a cross-repo-edge lookup expects
+    /// some of them to not be locally indexed.
+    ///
+    /// Returns a dict mapping each queried `repo_id` to its list of row
+    /// dicts, in `repo_ids` order — not arrival order — so ordering stays
+    /// stable regardless of which shard happened to answer first.
+    pub fn fan_out_query(
+        &self,
+        py: Python<'_>,
+        catalog: &ShardCatalog,
+        repo_ids: Vec<String>,
+        sql: &str,
+        params: Vec<String>,
+    ) -> PyResult<PyObject> {
+        let mut targets: Vec<(String, String)> = Vec::with_capacity(repo_ids.len());
+        for repo_id in &repo_ids {
+            if let Some(db_path) = catalog.get_shard_db_path(repo_id)? {
+                targets.push((repo_id.clone(), db_path));
+            }
+        }
+
+        let options = ConnectionOptions::default();
+        let outcomes: Vec<BombeResult<Vec<BackendRow>>> = crossbeam::thread::scope(|scope| {
+            let handles: Vec<_> = targets
+                .iter()
+                .map(|(_, db_path)| {
+                    let sql = sql;
+                    let params = &params;
+                    let options = &options;
+                    scope.spawn(move |_| -> BombeResult<Vec<BackendRow>> {
+                        let conn = self.connection_for(db_path, options)?;
+                        let conn = conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                        let mut stmt = conn.prepare(sql)?;
+                        let col_names: Vec<String> =
+                            stmt.column_names().iter().map(|s| s.to_string()).collect();
+                        let param_refs: Vec<&dyn rusqlite::types::ToSql> = params
+                            .iter()
+                            .map(|p| p as &dyn rusqlite::types::ToSql)
+                            .collect();
+                        let mut rows_out = Vec::new();
+                        let mut rows = stmt.query(param_refs.as_slice())?;
+                        while let Some(row) = rows.next()? {
+                            let out_row: BackendRow = col_names
+                                .iter()
+                                .enumerate()
+                                .map(|(i, name)| {
+                                    (
+                                        name.clone(),
+                                        row.get::<_, Option<String>>(i).unwrap_or(None),
+                                    )
+                                })
+                                .collect();
+                            rows_out.push(out_row);
+                        }
+                        Ok(rows_out)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(BombeError::Database("shard fan-out thread panicked".into())))
+                })
+                .collect()
+        })
+        .map_err(|_| BombeError::Database("shard fan-out scope panicked".into()))?;
+
+        let result = PyDict::new(py);
+        for ((repo_id, _), outcome) in targets.into_iter().zip(outcomes) {
+            let rows = outcome.map_err(PyErr::from)?;
+            let row_dicts: Vec<Bound<'_, PyDict>> = rows
+                .iter()
+                .map(|row| {
+                    let dict = PyDict::new(py);
+                    for (column, value) in row {
+                        dict.set_item(column, value)?;
+                    }
+                    Ok::<_, PyErr>(dict)
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            let list = PyList::new(py, row_dicts.iter().map(|d| d.as_any()))?;
+            result.set_item(repo_id, list)?;
+        }
+        Ok(result.into_any().unbind())
+    }
+}
@@ -5,21 +5,24 @@
 //! exported symbols, and cross-repo edges between symbols in different repos.
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
 
 use crate::errors::{BombeError, BombeResult};
 use crate::query::guards::MAX_EXPORTED_SYMBOLS_REFRESH;
 use crate::store::database::Database;
+use crate::store::sharding::graph_backend::{open_graph_backend, GraphBackend};
 
 // ---------------------------------------------------------------------------
 // Schema constants
 // ---------------------------------------------------------------------------
 
 /// Current catalog schema version.
-const CATALOG_SCHEMA_VERSION: i64 = 1;
+const CATALOG_SCHEMA_VERSION: i64 = 4;
 
 /// DDL statements to create the catalog tables and indexes.
 const CATALOG_SCHEMA_STATEMENTS: &[&str] = &[
@@ -36,7 +39,9 @@ const CATALOG_SCHEMA_STATEMENTS: &[&str] = &[
         symbol_count INTEGER DEFAULT 0,
         edge_count INTEGER DEFAULT 0,
         last_seen_epoch INTEGER DEFAULT 0,
-        updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+        backend TEXT NOT NULL DEFAULT 'sqlite',
+        export_hash TEXT
     );",
     "CREATE TABLE IF NOT EXISTS cross_repo_edges (
         id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -71,8 +76,393 @@ const CATALOG_SCHEMA_STATEMENTS: &[&str] = &[
     "CREATE INDEX IF NOT EXISTS idx_exported_name ON exported_symbols(name);",
     "CREATE INDEX IF NOT EXISTS idx_exported_qualified ON exported_symbols(qualified_name);",
     "CREATE INDEX IF NOT EXISTS idx_exported_kind ON exported_symbols(kind);",
+    "CREATE TABLE IF NOT EXISTS external_dep_classifications (
+        repo_id TEXT NOT NULL,
+        module_name TEXT NOT NULL,
+        language TEXT NOT NULL,
+        classification TEXT NOT NULL,
+        file_path TEXT,
+        updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY(repo_id, module_name, language)
+    );",
+    "CREATE INDEX IF NOT EXISTS idx_dep_classifications_repo \
+         ON external_dep_classifications(repo_id);",
+    "CREATE INDEX IF NOT EXISTS idx_dep_classifications_classification \
+         ON external_dep_classifications(classification);",
+    "CREATE TABLE IF NOT EXISTS cross_repo_target_hashes (
+        source_repo_id TEXT NOT NULL,
+        target_repo_id TEXT NOT NULL,
+        target_export_hash TEXT,
+        updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY(source_repo_id, target_repo_id)
+    );",
+    "CREATE INDEX IF NOT EXISTS idx_target_hashes_target \
+         ON cross_repo_target_hashes(target_repo_id);",
 ];
 
+// ---------------------------------------------------------------------------
+// Schema migrations
+// ---------------------------------------------------------------------------
+
+/// One schema migration step, modeled on the rusqlite_migration/mailpot
+/// pattern: a static ordered list instead of an imperative if-chain, so
+/// adding a migration is just appending an entry rather than editing the
+/// function that applies them. Versioned against `PRAGMA user_version`
+/// (see [`ShardCatalog::get_schema_version`]), the same counter
+/// rusqlite_migration itself uses, rather than a row in a metadata table.
+/// `up` is split into its individual statements (rather than one
+/// `execute_batch` string) so a catalog created fresh by
+/// [`CATALOG_SCHEMA_STATEMENTS`] — which already has everything a
+/// migration would add — can tolerate a `duplicate column name` failure on
+/// one statement (e.g. an `ALTER TABLE ADD COLUMN`) without that aborting
+/// the rest of the migration. `down` is kept for tooling/completeness;
+/// [`ShardCatalog::migrate_schema`] never runs it.
+struct Migration {
+    version: i64,
+    up: &'static [&'static str],
+    #[allow(dead_code)]
+    down: Option<&'static [&'static str]>,
+}
+
+/// Every migration after the initial schema (version 1, which
+/// [`CATALOG_SCHEMA_STATEMENTS`] creates directly). Ordered by `version`;
+/// [`ShardCatalog::migrate_schema`] applies every entry newer than the
+/// catalog's `PRAGMA user_version`, each inside its own transaction, and
+/// refuses to open a catalog whose `user_version` is newer than this list
+/// knows about — see [`ShardCatalog::migrate_schema`].
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        // Pluggable shard storage backends (`"sqlite"` default).
+        version: 2,
+        up: &["ALTER TABLE shards ADD COLUMN backend TEXT NOT NULL DEFAULT 'sqlite';"],
+        down: Some(&["ALTER TABLE shards DROP COLUMN backend;"]),
+    },
+    Migration {
+        // Classify external_deps as stdlib/known-external/unresolved
+        // before they reach the resolver.
+        version: 3,
+        up: &[
+            "CREATE TABLE IF NOT EXISTS external_dep_classifications (
+                repo_id TEXT NOT NULL,
+                module_name TEXT NOT NULL,
+                language TEXT NOT NULL,
+                classification TEXT NOT NULL,
+                file_path TEXT,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY(repo_id, module_name, language)
+            );",
+            "CREATE INDEX IF NOT EXISTS idx_dep_classifications_repo \
+                 ON external_dep_classifications(repo_id);",
+            "CREATE INDEX IF NOT EXISTS idx_dep_classifications_classification \
+                 ON external_dep_classifications(classification);",
+        ],
+        down: Some(&["DROP TABLE IF EXISTS external_dep_classifications;"]),
+    },
+    Migration {
+        // Incremental cross-repo edge recomputation, keyed on a content
+        // hash of each shard's exported symbols.
+        version: 4,
+        up: &[
+            "ALTER TABLE shards ADD COLUMN export_hash TEXT;",
+            "CREATE TABLE IF NOT EXISTS cross_repo_target_hashes (
+                source_repo_id TEXT NOT NULL,
+                target_repo_id TEXT NOT NULL,
+                target_export_hash TEXT,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY(source_repo_id, target_repo_id)
+            );",
+            "CREATE INDEX IF NOT EXISTS idx_target_hashes_target \
+                 ON cross_repo_target_hashes(target_repo_id);",
+        ],
+        down: Some(&["DROP TABLE IF EXISTS cross_repo_target_hashes;"]),
+    },
+];
+
+/// `true` if `err` is SQLite's "duplicate column name" failure — the shape
+/// one of [`MIGRATIONS`]'s `ALTER TABLE ADD COLUMN` statements fails with
+/// on a catalog [`CATALOG_SCHEMA_STATEMENTS`] already created with that
+/// column, which a migration should tolerate rather than treat as a real
+/// failure.
+fn is_duplicate_column_error(err: &rusqlite::Error) -> bool {
+    err.to_string().contains("duplicate column name")
+}
+
+// ---------------------------------------------------------------------------
+// Per-language import resolver registry
+// ---------------------------------------------------------------------------
+
+/// A matching strategy [`ShardCatalog::resolve_external_import`] can use
+/// against `exported_symbols`, selected per-language via
+/// [`ShardCatalog::register_import_resolver`] -- cozo's `register_fixed_rule`
+/// extensibility idea applied to import resolution, since no single
+/// strategy fits every ecosystem's module-path conventions (`name = x` for a
+/// plain import, `crate::mod::Item`-style paths for Rust, scoped npm
+/// packages, ...).
+#[derive(Debug, Clone, PartialEq)]
+enum ImportResolverStrategy {
+    /// `name = module_name`, unmodified.
+    ExactName,
+    /// Normalize `/` to `.`, split on `separator`, and match the last
+    /// segment against `name` -- the shape TypeScript's default resolver
+    /// has always used (last path segment is the imported identifier).
+    LastSegment { separator: String },
+    /// `qualified_name LIKE 'module_name%'` -- the default for every
+    /// language without a registered or built-in strategy.
+    QualifiedPrefix,
+    /// `qualified_name LIKE '%module_name'`, for paths only known by their
+    /// trailing segment(s) (e.g. a partial `mod::Item` against a qualified
+    /// name rooted at the crate).
+    Suffix,
+    /// Replace `separator` with `.` throughout `module_name`, then
+    /// `qualified_name LIKE '<normalized>%'` -- for package-path imports
+    /// (Go, Java) whose on-disk/import syntax doesn't already match this
+    /// catalog's dotted `qualified_name` convention.
+    NormalizedPathToDotted { separator: String },
+}
+
+impl ImportResolverStrategy {
+    const NAMES: &'static [&'static str] = &[
+        "exact-name",
+        "last-segment",
+        "qualified-prefix",
+        "suffix",
+        "normalized-path-to-dotted",
+    ];
+
+    fn parse(name: &str, separator: Option<&str>) -> BombeResult<Self> {
+        match name {
+            "exact-name" => Ok(Self::ExactName),
+            "last-segment" => Ok(Self::LastSegment {
+                separator: separator.unwrap_or(".").to_string(),
+            }),
+            "qualified-prefix" => Ok(Self::QualifiedPrefix),
+            "suffix" => Ok(Self::Suffix),
+            "normalized-path-to-dotted" => Ok(Self::NormalizedPathToDotted {
+                separator: separator.unwrap_or("/").to_string(),
+            }),
+            other => Err(BombeError::Query(format!(
+                "unknown import resolver strategy {other:?}; expected one of {}",
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::ExactName => serde_json::json!({"strategy": "exact-name"}),
+            Self::LastSegment { separator } => {
+                serde_json::json!({"strategy": "last-segment", "separator": separator})
+            }
+            Self::QualifiedPrefix => serde_json::json!({"strategy": "qualified-prefix"}),
+            Self::Suffix => serde_json::json!({"strategy": "suffix"}),
+            Self::NormalizedPathToDotted { separator } => {
+                serde_json::json!({"strategy": "normalized-path-to-dotted", "separator": separator})
+            }
+        }
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let strategy = value.get("strategy")?.as_str()?;
+        let separator = value.get("separator").and_then(|s| s.as_str());
+        Self::parse(strategy, separator).ok()
+    }
+
+    /// The strategy `resolve_external_import` used before this registry
+    /// existed: TypeScript's last-path-segment match, qualified-name prefix
+    /// match for everything else.
+    fn default_for_language(language: &str) -> Self {
+        if language.eq_ignore_ascii_case("typescript") {
+            Self::LastSegment {
+                separator: ".".to_string(),
+            }
+        } else {
+            Self::QualifiedPrefix
+        }
+    }
+
+    /// The `WHERE` clause fragment and its single bound pattern for
+    /// `resolve_external_import`'s query against `module_name`.
+    fn build_match(&self, module_name: &str) -> (&'static str, String) {
+        match self {
+            Self::ExactName => ("name = ?1", module_name.to_string()),
+            Self::LastSegment { separator } => {
+                let normalized = module_name.replace('/', ".");
+                let last = normalized
+                    .split(separator.as_str())
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .last()
+                    .unwrap_or(module_name)
+                    .to_string();
+                ("name = ?1", last)
+            }
+            Self::QualifiedPrefix => ("qualified_name LIKE ?1", format!("{module_name}%")),
+            Self::Suffix => ("qualified_name LIKE ?1", format!("%{module_name}")),
+            Self::NormalizedPathToDotted { separator } => {
+                let normalized = module_name.replace(separator.as_str(), ".");
+                ("qualified_name LIKE ?1", format!("{normalized}%"))
+            }
+        }
+    }
+}
+
+/// `catalog_meta` key under which `language`'s registered
+/// [`ImportResolverStrategy`] is stored, lowercased so lookups aren't
+/// case-sensitive on the language name.
+fn import_resolver_key(language: &str) -> String {
+    format!("import_resolver:{}", language.to_lowercase())
+}
+
+/// The [`ImportResolverStrategy`] `language` should resolve imports with:
+/// whatever [`ShardCatalog::register_import_resolver`] last stored for it,
+/// or [`ImportResolverStrategy::default_for_language`] if nothing's
+/// registered (including on a pre-registry catalog, since the lookup is
+/// just a `catalog_meta` `SELECT` that comes back empty).
+fn lookup_import_resolver(conn: &Connection, language: &str) -> ImportResolverStrategy {
+    let stored: Result<String, _> = conn.query_row(
+        "SELECT value FROM catalog_meta WHERE key = ?1;",
+        params![import_resolver_key(language)],
+        |row| row.get(0),
+    );
+    stored
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|v| ImportResolverStrategy::from_json(&v))
+        .unwrap_or_else(|| ImportResolverStrategy::default_for_language(language))
+}
+
+// ---------------------------------------------------------------------------
+// FTS5 ranked search over exported_symbols
+// ---------------------------------------------------------------------------
+
+/// Statements that index `exported_symbols(name, qualified_name)` for
+/// [`ShardCatalog::search_exported_symbols_ranked`]'s `MATCH` queries, as an
+/// external-content FTS5 table (`content='exported_symbols'`) so it stores
+/// no data of its own — just an inverted index over the two columns,
+/// looked up by `rowid` against the table it mirrors. Kept in sync by
+/// triggers rather than rebuilt on every [`ShardCatalog::refresh_exported_symbols`]
+/// call, so a catalog with many shards doesn't pay for a full FTS rebuild
+/// every time one of them resyncs.
+///
+/// Not every SQLite build ships the FTS5 extension, so [`ensure_fts5`]
+/// applies these best-effort and [`has_fts5_table`] is what callers actually
+/// trust to decide whether the ranked path is usable.
+const FTS5_SCHEMA_STATEMENTS: &[&str] = &[
+    "CREATE VIRTUAL TABLE IF NOT EXISTS exported_symbols_fts USING fts5(
+        name, qualified_name, content='exported_symbols', content_rowid='rowid'
+    );",
+    "CREATE TRIGGER IF NOT EXISTS exported_symbols_fts_ai AFTER INSERT ON exported_symbols BEGIN
+        INSERT INTO exported_symbols_fts(rowid, name, qualified_name)
+            VALUES (new.rowid, new.name, new.qualified_name);
+    END;",
+    "CREATE TRIGGER IF NOT EXISTS exported_symbols_fts_ad AFTER DELETE ON exported_symbols BEGIN
+        INSERT INTO exported_symbols_fts(exported_symbols_fts, rowid, name, qualified_name)
+            VALUES('delete', old.rowid, old.name, old.qualified_name);
+    END;",
+    "CREATE TRIGGER IF NOT EXISTS exported_symbols_fts_au AFTER UPDATE ON exported_symbols BEGIN
+        INSERT INTO exported_symbols_fts(exported_symbols_fts, rowid, name, qualified_name)
+            VALUES('delete', old.rowid, old.name, old.qualified_name);
+        INSERT INTO exported_symbols_fts(rowid, name, qualified_name)
+            VALUES (new.rowid, new.name, new.qualified_name);
+    END;",
+];
+
+/// Best-effort create [`FTS5_SCHEMA_STATEMENTS`], stopping at the first
+/// statement that fails (e.g. `CREATE VIRTUAL TABLE ... USING fts5` itself,
+/// on a build with no FTS5 extension) rather than trying the rest against a
+/// table that was never created. Idempotent via `IF NOT EXISTS`, so calling
+/// this on every [`ShardCatalog::init_schema`] is cheap once FTS5 is set up.
+fn ensure_fts5(conn: &Connection) -> bool {
+    for stmt in FTS5_SCHEMA_STATEMENTS {
+        if conn.execute_batch(stmt).is_err() {
+            break;
+        }
+    }
+    has_fts5_table(conn)
+}
+
+/// `true` if `exported_symbols_fts` exists on this connection — checked
+/// directly against `sqlite_master` rather than trusting [`ensure_fts5`]'s
+/// own return value cached somewhere, since it's cheap and avoids a second
+/// place that can go stale.
+fn has_fts5_table(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'exported_symbols_fts';",
+        [],
+        |_| Ok(()),
+    )
+    .is_ok()
+}
+
+// ---------------------------------------------------------------------------
+// Connection options and busy retry
+// ---------------------------------------------------------------------------
+
+/// Per-[`ShardCatalog`] connection settings [`ShardCatalog::connect`] applies
+/// to every connection it opens, in the style of the upend database
+/// module's `ConnectionOptions`. `busy_timeout_ms` is SQLite's own wait
+/// before giving up on a lock (`PRAGMA busy_timeout`) — the first line of
+/// defense against the `SQLITE_BUSY` several concurrent federation indexer
+/// processes can otherwise cause on `exported_symbols`/`cross_repo_edges`
+/// writes, since the catalog runs in WAL mode and [`ShardCatalog::connect`]
+/// opens a fresh connection per method call rather than sharing one.
+/// [`retry_on_busy`] is the second line of defense, for a lock that
+/// outlasts even this timeout.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionOptions {
+    pub busy_timeout_ms: u32,
+    pub foreign_keys: bool,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            busy_timeout_ms: 5_000,
+            foreign_keys: true,
+        }
+    }
+}
+
+/// `true` if `err` is `SQLITE_BUSY` or `SQLITE_LOCKED` — the two codes a
+/// concurrent writer can still hit after `PRAGMA busy_timeout` gives up,
+/// and the ones [`retry_on_busy`] retries on.
+fn is_busy_or_locked(err: &rusqlite::Error) -> bool {
+    matches!(
+        err,
+        rusqlite::Error::SqliteFailure(e, _)
+            if matches!(
+                e.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            )
+    )
+}
+
+/// Bounded retry for the catalog's write paths: `PRAGMA busy_timeout`
+/// handles one connection waiting on a single lock, but several federation
+/// indexer processes refreshing different shards concurrently can still
+/// have a write come back `SQLITE_BUSY`/`SQLITE_LOCKED` once that timeout
+/// is exhausted. Retries the whole operation (opening a fresh connection
+/// each time, same as every other catalog method) up to `MAX_ATTEMPTS`
+/// times with a short linear backoff before surfacing the error, so
+/// indexing from multiple workers doesn't spuriously fail on a transient
+/// lock.
+pub(crate) fn retry_on_busy<T>(mut op: impl FnMut() -> BombeResult<T>) -> BombeResult<T> {
+    const MAX_ATTEMPTS: u32 = 5;
+    const BACKOFF_MS: u64 = 50;
+
+    let mut attempt = 0u32;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(BombeError::Sqlite(ref err)) if attempt < MAX_ATTEMPTS && is_busy_or_locked(err) => {
+                attempt += 1;
+                std::thread::sleep(Duration::from_millis(BACKOFF_MS * attempt as u64));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helper: tilde expansion
 // ---------------------------------------------------------------------------
@@ -91,6 +481,56 @@ fn expand_tilde(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+// ---------------------------------------------------------------------------
+// Helper: monorepo source-root stripping
+// ---------------------------------------------------------------------------
+
+/// Strip the longest matching configured source root from `module_name`,
+/// mirroring Pants' `split_on_longest_dir_prefix` against
+/// `subproject_roots`: a monorepo shard often holds several logical
+/// projects under different root prefixes, so a raw module name must be
+/// de-rooted before it's comparable to another shard's root-relative
+/// exported symbols — otherwise the same logical module looks different
+/// depending on which root it happens to sit under in this particular repo.
+///
+/// Roots may use `.`, `/`, or `::` as their separator (whatever convention
+/// this repo's `module_name`s already use); matching tries all three so a
+/// single configured root list works across a multi-language monorepo.
+/// Returns `(root_relative_name, matched_root)` — when no configured root is
+/// a prefix, `module_name` is returned unchanged and `matched_root` is
+/// `None`.
+pub(crate) fn strip_source_root(
+    module_name: &str,
+    source_roots: &[String],
+) -> (String, Option<String>) {
+    let mut best_root: Option<&str> = None;
+    for root in source_roots {
+        let root = root.trim_end_matches(['.', '/', ':']);
+        if root.is_empty() {
+            continue;
+        }
+        let matches = module_name == root
+            || module_name.starts_with(&format!("{root}."))
+            || module_name.starts_with(&format!("{root}/"))
+            || module_name.starts_with(&format!("{root}::"));
+        if matches && best_root.is_none_or(|b| root.len() > b.len()) {
+            best_root = Some(root);
+        }
+    }
+    match best_root {
+        Some(root) => {
+            let rest = module_name[root.len()..].trim_start_matches(['.', '/', ':']);
+            let relative = if rest.is_empty() {
+                module_name.to_string()
+            } else {
+                rest.to_string()
+            };
+            (relative, Some(root.to_string()))
+        }
+        None => (module_name.to_string(), None),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helper: convert a rusqlite row into a Python dict using column names.
 // ---------------------------------------------------------------------------
@@ -116,6 +556,49 @@ fn row_to_pydict<'py>(
     Ok(dict)
 }
 
+// ---------------------------------------------------------------------------
+// Cross-repo edge row (Rust-only)
+// ---------------------------------------------------------------------------
+
+/// One row of `cross_repo_edges`, for Rust consumers (e.g.
+/// [`crate::store::sharding::rdf_export`]) that want typed access instead of
+/// the Python-dict shape [`ShardCatalog::get_cross_repo_edges_from`] returns.
+#[derive(Debug, Clone)]
+pub struct CrossRepoEdge {
+    pub source_repo_id: String,
+    pub source_qualified_name: String,
+    pub source_file_path: String,
+    pub target_repo_id: String,
+    pub target_qualified_name: String,
+    pub target_file_path: String,
+    pub relationship: String,
+    pub confidence: f64,
+    pub provenance: String,
+}
+
+/// Convert a list of [`CrossRepoEdge`]s into the same `list[dict]` shape
+/// [`ShardCatalog::get_cross_repo_edges_from`]/`_to` returned before their
+/// SQL moved behind [`GraphBackend`] — one dict per edge, same nine keys in
+/// the same order.
+fn edges_to_pylist(py: Python<'_>, edges: &[CrossRepoEdge]) -> PyResult<PyObject> {
+    let mut rows_out: Vec<Bound<'_, PyDict>> = Vec::with_capacity(edges.len());
+    for edge in edges {
+        let dict = PyDict::new(py);
+        dict.set_item("source_repo_id", &edge.source_repo_id)?;
+        dict.set_item("source_qualified_name", &edge.source_qualified_name)?;
+        dict.set_item("source_file_path", &edge.source_file_path)?;
+        dict.set_item("target_repo_id", &edge.target_repo_id)?;
+        dict.set_item("target_qualified_name", &edge.target_qualified_name)?;
+        dict.set_item("target_file_path", &edge.target_file_path)?;
+        dict.set_item("relationship", &edge.relationship)?;
+        dict.set_item("confidence", edge.confidence)?;
+        dict.set_item("provenance", &edge.provenance)?;
+        rows_out.push(dict);
+    }
+    let list = PyList::new(py, rows_out.iter().map(|d| d.as_any()))?;
+    Ok(list.into_any().unbind())
+}
+
 // ---------------------------------------------------------------------------
 // ShardCatalog
 // ---------------------------------------------------------------------------
@@ -128,55 +611,280 @@ fn row_to_pydict<'py>(
 #[pyclass]
 pub struct ShardCatalog {
     db_path: PathBuf,
+    options: ConnectionOptions,
+    /// Backs the cross-repo-edge operations below; `"sqlite"` unless the
+    /// constructor's `edge_backend` argument opts into an alternate engine.
+    /// See [`crate::store::sharding::graph_backend`].
+    graph_backend: Box<dyn GraphBackend>,
 }
 
 impl ShardCatalog {
-    /// Open a new SQLite connection to `self.db_path`, enable foreign keys,
+    /// Open a new SQLite connection to `self.db_path`, apply this
+    /// catalog's [`ConnectionOptions`] (`busy_timeout_ms`, `foreign_keys`),
     /// and set row factory-like behaviour through column-name-based access.
     fn connect(&self) -> BombeResult<Connection> {
         let conn = Connection::open(&self.db_path)?;
-        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(&format!(
+            "PRAGMA busy_timeout = {};",
+            self.options.busy_timeout_ms
+        ))?;
+        conn.execute_batch(if self.options.foreign_keys {
+            "PRAGMA foreign_keys = ON;"
+        } else {
+            "PRAGMA foreign_keys = OFF;"
+        })?;
         Ok(conn)
     }
 
-    /// Read the current schema version from catalog_meta.
+    /// Read the current schema version from `PRAGMA user_version` — a
+    /// counter SQLite stores in the database file header itself (defaulting
+    /// to `0` for a database that never set it), rather than a row in
+    /// `catalog_meta`: it's readable before that table necessarily exists,
+    /// and every migration's version bump commits atomically with that
+    /// migration's own transaction instead of being a separate write a
+    /// crash between the two could desync.
     fn get_schema_version(conn: &Connection) -> i64 {
-        let result: Result<String, _> = conn.query_row(
-            "SELECT value FROM catalog_meta WHERE key = 'schema_version';",
-            [],
-            |row| row.get(0),
-        );
-        match result {
-            Ok(v) => v.parse::<i64>().unwrap_or(0),
-            Err(_) => 0,
-        }
+        conn.query_row("PRAGMA user_version;", [], |row| row.get(0))
+            .unwrap_or(0)
     }
 
-    /// Set the schema version in catalog_meta.
+    /// Set `PRAGMA user_version`. Bare statement — `PRAGMA` doesn't accept
+    /// bound parameters — but `version` only ever comes from a
+    /// [`Migration::version`] constant, never external input.
     fn set_schema_version(conn: &Connection, version: i64) -> BombeResult<()> {
-        conn.execute(
-            "INSERT INTO catalog_meta(key, value) \
-             VALUES('schema_version', ?1) \
-             ON CONFLICT(key) DO UPDATE SET value = excluded.value;",
-            params![version.to_string()],
-        )?;
+        conn.execute_batch(&format!("PRAGMA user_version = {version};"))?;
         Ok(())
     }
 
-    /// Run any pending schema migrations.
+    /// Run any pending schema migrations, each inside its own `BEGIN
+    /// IMMEDIATE`…`COMMIT` transaction so a failure partway through a
+    /// migration rolls that migration back rather than leaving the catalog
+    /// with some of its statements applied and a stale `schema_version`.
+    /// Refuses to proceed — rather than silently truncating — if the
+    /// catalog's stored version is *newer* than [`CATALOG_SCHEMA_VERSION`],
+    /// which means an older binary opened a catalog a newer one wrote.
     fn migrate_schema(conn: &Connection) -> BombeResult<()> {
-        let mut current = Self::get_schema_version(conn);
-        while current < CATALOG_SCHEMA_VERSION {
-            let next = current + 1;
-            // Version 1: initial schema created by CATALOG_SCHEMA_STATEMENTS.
-            if next == 1 {
-                // No additional migration needed for the initial schema.
+        let current = Self::get_schema_version(conn);
+        if current > CATALOG_SCHEMA_VERSION {
+            return Err(BombeError::Database(format!(
+                "catalog schema version {current} is newer than this binary supports \
+                 (CATALOG_SCHEMA_VERSION = {CATALOG_SCHEMA_VERSION}); refusing to open it"
+            )));
+        }
+        for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+            Self::apply_migration(conn, migration)?;
+        }
+        Ok(())
+    }
+
+    /// Apply one [`Migration`]'s `up` statements and bump `schema_version`
+    /// to its `version`, all inside one transaction; rolls back and
+    /// returns a [`BombeError`] on any statement failure other than
+    /// [`is_duplicate_column_error`] (tolerated — see [`Migration`]).
+    fn apply_migration(conn: &Connection, migration: &Migration) -> BombeResult<()> {
+        conn.execute_batch("BEGIN IMMEDIATE;")?;
+        for stmt in migration.up {
+            if let Err(err) = conn.execute_batch(stmt) {
+                if is_duplicate_column_error(&err) {
+                    continue;
+                }
+                let _ = conn.execute_batch("ROLLBACK;");
+                return Err(BombeError::from(err));
             }
-            Self::set_schema_version(conn, next)?;
-            current = next;
         }
+        if let Err(err) = Self::set_schema_version(conn, migration.version) {
+            let _ = conn.execute_batch("ROLLBACK;");
+            return Err(err);
+        }
+        conn.execute_batch("COMMIT;")?;
         Ok(())
     }
+
+    /// Build [`Self::export_catalog`]'s whole document: the stored
+    /// `schema_version` plus each of `shards`/`exported_symbols`/
+    /// `cross_repo_edges` via [`export_table_json`].
+    fn export_document(conn: &Connection) -> BombeResult<serde_json::Value> {
+        Ok(serde_json::json!({
+            "schema_version": Self::get_schema_version(conn),
+            "shards": export_table_json(conn, "SELECT * FROM shards ORDER BY repo_id ASC;")?,
+            "exported_symbols": export_table_json(
+                conn,
+                "SELECT * FROM exported_symbols \
+                 ORDER BY repo_id ASC, qualified_name ASC, file_path ASC;",
+            )?,
+            "cross_repo_edges": export_table_json(
+                conn,
+                "SELECT * FROM cross_repo_edges ORDER BY id ASC;",
+            )?,
+        }))
+    }
+}
+
+/// Run `sql` (a bare `SELECT * FROM <table> ...`) and collect it into
+/// [`Self::export_catalog`]'s `{"headers": [...], "rows": [[...], ...]}`
+/// shape, reading each cell as whatever SQLite type it actually stored
+/// (`NULL`/`INTEGER`/`REAL`/`TEXT`; `BLOB` isn't used by any of the exported
+/// tables and maps to `null`) rather than assuming a fixed column typing.
+fn export_table_json(conn: &Connection, sql: &str) -> BombeResult<serde_json::Value> {
+    let mut stmt = conn.prepare(sql)?;
+    let headers: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
+    let mut rows_out = Vec::new();
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let mut row_values = Vec::with_capacity(headers.len());
+        for i in 0..headers.len() {
+            let value = match row.get_ref(i)? {
+                rusqlite::types::ValueRef::Null => serde_json::Value::Null,
+                rusqlite::types::ValueRef::Integer(n) => serde_json::Value::from(n),
+                rusqlite::types::ValueRef::Real(f) => serde_json::Value::from(f),
+                rusqlite::types::ValueRef::Text(t) => {
+                    serde_json::Value::from(String::from_utf8_lossy(t).into_owned())
+                }
+                rusqlite::types::ValueRef::Blob(_) => serde_json::Value::Null,
+            };
+            row_values.push(value);
+        }
+        rows_out.push(serde_json::Value::Array(row_values));
+    }
+    Ok(serde_json::json!({"headers": headers, "rows": rows_out}))
+}
+
+/// Convert one JSON cell from an [`export_table_json`]-shaped `rows` array
+/// back into a bindable SQL value for [`import_table`]'s `INSERT OR
+/// REPLACE`.
+fn json_value_to_sql(value: &serde_json::Value) -> Box<dyn rusqlite::types::ToSql> {
+    match value {
+        serde_json::Value::Null => Box::new(rusqlite::types::Null),
+        serde_json::Value::Bool(b) => Box::new(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Box::new(i),
+            None => Box::new(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => Box::new(s.clone()),
+        other => Box::new(other.to_string()),
+    }
+}
+
+/// The columns `import_table` will accept in a `headers` array for each of
+/// the three tables [`ShardCatalog::import_catalog`] imports — `data` is
+/// ingested from another, untrusted federation peer, so `headers` can't be
+/// spliced into SQL unvalidated the way a locally-produced
+/// [`export_table_json`] document's headers could be trusted to be. Mirrors
+/// [`crate::store::sharding::backend::validate_table_name`]'s allowlist, one
+/// level down: that guards which *table* a caller can name, this guards
+/// which *columns* of it.
+fn import_table_allowed_columns(table: &str) -> BombeResult<&'static [&'static str]> {
+    match table {
+        "shards" => Ok(&[
+            "repo_id",
+            "repo_path",
+            "db_path",
+            "enabled",
+            "last_indexed_at",
+            "symbol_count",
+            "edge_count",
+            "last_seen_epoch",
+            "updated_at",
+            "backend",
+            "export_hash",
+        ]),
+        "exported_symbols" => Ok(&[
+            "repo_id",
+            "qualified_name",
+            "name",
+            "kind",
+            "file_path",
+            "visibility",
+            "pagerank_score",
+            "updated_at",
+        ]),
+        "cross_repo_edges" => Ok(&[
+            "id",
+            "source_repo_id",
+            "source_qualified_name",
+            "source_file_path",
+            "target_repo_id",
+            "target_qualified_name",
+            "target_file_path",
+            "relationship",
+            "confidence",
+            "provenance",
+            "updated_at",
+        ]),
+        other => Err(BombeError::Database(format!(
+            "import_table does not support table: {other}"
+        ))),
+    }
+}
+
+/// Import one [`export_table_json`]-shaped table value into `table` inside
+/// `tx`, truncating it first when `truncate` (`mode = "replace"`). Builds
+/// the `INSERT OR REPLACE` column list from the document's own `headers`
+/// rather than a hardcoded one, so an older export missing a column this
+/// version added just leaves that column at its default. Every header is
+/// checked against [`import_table_allowed_columns`] first — `headers` comes
+/// from a remote peer's export via [`ShardCatalog::import_catalog`], and
+/// splicing an unvalidated header into the `INSERT` column list would let a
+/// crafted header turn the statement into something other than a plain
+/// `INSERT` (e.g. an `INSERT ... SELECT` pulling rows out of another
+/// table). A missing or malformed table value (e.g. an export that only
+/// covers some tables) imports zero rows rather than erroring; an
+/// unrecognized header is treated the same as malformed input and also
+/// imports zero rows for that table, rather than risking a partial import
+/// of an export that doesn't look like what this binary expects. Returns
+/// the row count imported.
+fn import_table(
+    tx: &rusqlite::Transaction<'_>,
+    table: &str,
+    value: Option<&serde_json::Value>,
+    truncate: bool,
+) -> BombeResult<i64> {
+    if truncate {
+        tx.execute_batch(&format!("DELETE FROM {table};"))?;
+    }
+
+    let Some(value) = value else {
+        return Ok(0);
+    };
+    let headers: Vec<String> = value
+        .get("headers")
+        .and_then(|h| h.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(ToString::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let rows = value.get("rows").and_then(|r| r.as_array());
+    let (Some(rows), false) = (rows, headers.is_empty()) else {
+        return Ok(0);
+    };
+
+    let allowed = import_table_allowed_columns(table)?;
+    if !headers.iter().all(|h| allowed.contains(&h.as_str())) {
+        return Ok(0);
+    }
+
+    let placeholders: Vec<String> = (1..=headers.len()).map(|i| format!("?{i}")).collect();
+    let sql = format!(
+        "INSERT OR REPLACE INTO {table}({cols}) VALUES ({placeholders});",
+        cols = headers.join(", "),
+        placeholders = placeholders.join(", "),
+    );
+    let mut stmt = tx.prepare(&sql)?;
+
+    let mut count = 0i64;
+    for row in rows {
+        let cells = row
+            .as_array()
+            .ok_or_else(|| BombeError::Parse(format!("{table} export row is not a JSON array")))?;
+        let bound: Vec<Box<dyn rusqlite::types::ToSql>> =
+            cells.iter().map(json_value_to_sql).collect();
+        let refs: Vec<&dyn rusqlite::types::ToSql> = bound.iter().map(|b| b.as_ref()).collect();
+        stmt.execute(refs.as_slice())?;
+        count += 1;
+    }
+    Ok(count)
 }
 
 #[pymethods]
@@ -187,8 +895,20 @@ impl ShardCatalog {
 
     /// Create a new `ShardCatalog`.  The path is expanded and parent
     /// directories are created if they do not already exist.
+    /// `busy_timeout_ms`/`foreign_keys` seed this catalog's
+    /// [`ConnectionOptions`] — see [`Self::set_pragmas`] to change them
+    /// after construction. `edge_backend` selects the
+    /// [`crate::store::sharding::graph_backend::GraphBackend`] that stores
+    /// `cross_repo_edges` — `"sqlite"` (the default) keeps them in this
+    /// catalog's own database; `"lmdb"` requires the `lmdb_backend` feature.
     #[new]
-    fn new(catalog_db_path: PathBuf) -> PyResult<Self> {
+    #[pyo3(signature = (catalog_db_path, busy_timeout_ms=5_000, foreign_keys=true, edge_backend="sqlite"))]
+    fn new(
+        catalog_db_path: PathBuf,
+        busy_timeout_ms: u32,
+        foreign_keys: bool,
+        edge_backend: &str,
+    ) -> PyResult<Self> {
         let db_str = catalog_db_path.to_string_lossy();
         let expanded = expand_tilde(&db_str);
         let resolved = if expanded.is_absolute() {
@@ -201,12 +921,35 @@ impl ShardCatalog {
         if let Some(parent) = resolved.parent() {
             std::fs::create_dir_all(parent).map_err(BombeError::Io)?;
         }
-        let catalog = Self { db_path: resolved };
+        let options = ConnectionOptions {
+            busy_timeout_ms,
+            foreign_keys,
+        };
+        let graph_backend = open_graph_backend(edge_backend, &resolved, options)?;
+        let catalog = Self {
+            db_path: resolved,
+            options,
+            graph_backend,
+        };
         // Initialise schema on construction (matching Python __init__ + init_schema pattern).
         catalog.init_schema()?;
         Ok(catalog)
     }
 
+    /// Change this catalog's [`ConnectionOptions`] — `busy_timeout_ms`
+    /// and/or `foreign_keys` — applied to every connection [`Self::connect`]
+    /// opens from now on. Either argument left `None` keeps its current
+    /// value.
+    #[pyo3(signature = (busy_timeout_ms=None, foreign_keys=None))]
+    pub fn set_pragmas(&mut self, busy_timeout_ms: Option<u32>, foreign_keys: Option<bool>) {
+        if let Some(v) = busy_timeout_ms {
+            self.options.busy_timeout_ms = v;
+        }
+        if let Some(v) = foreign_keys {
+            self.options.foreign_keys = v;
+        }
+    }
+
     /// Initialise the catalog schema: set WAL mode, create all tables and
     /// indexes, then run pending migrations.
     pub fn init_schema(&self) -> PyResult<()> {
@@ -217,9 +960,32 @@ impl ShardCatalog {
             conn.execute_batch(stmt).map_err(BombeError::from)?;
         }
         Self::migrate_schema(&conn)?;
+        ensure_fts5(&conn);
         Ok(())
     }
 
+    /// The schema version currently stored in this catalog's
+    /// `catalog_meta`, so tooling can report upgrade status without
+    /// triggering a migration just by opening the catalog.
+    pub fn current_schema_version(&self) -> PyResult<i64> {
+        let conn = self.connect()?;
+        Ok(Self::get_schema_version(&conn))
+    }
+
+    /// The versions this catalog would still migrate through if opened
+    /// right now — every [`MIGRATIONS`] entry newer than the stored
+    /// `schema_version` — so tooling can report pending upgrades before
+    /// running them. Empty once the catalog is fully migrated.
+    pub fn pending_migrations(&self) -> PyResult<Vec<i64>> {
+        let conn = self.connect()?;
+        let current = Self::get_schema_version(&conn);
+        Ok(MIGRATIONS
+            .iter()
+            .filter(|m| m.version > current)
+            .map(|m| m.version)
+            .collect())
+    }
+
     // -----------------------------------------------------------------------
     // Generic query (exposed to Python)
     // -----------------------------------------------------------------------
@@ -279,37 +1045,45 @@ impl ShardCatalog {
     // Shard management
     // -----------------------------------------------------------------------
 
-    /// Register a shard by repo_id, repo_path, and db_path.
-    /// Uses INSERT OR REPLACE into the shards table.
-    pub fn register_shard(&self, repo_id: &str, repo_path: &str, db_path: &str) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "INSERT OR REPLACE INTO shards(\
-                 repo_id, repo_path, db_path, enabled, updated_at\
-             ) VALUES (?1, ?2, ?3, 1, CURRENT_TIMESTAMP);",
-            params![repo_id, repo_path, db_path],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+    /// Register a shard by repo_id, repo_path, and db_path, storing which
+    /// [`crate::store::sharding::backend::StoreBackend`] it uses
+    /// (`"sqlite"` unless a caller opts a large, read-heavy shard into an
+    /// embedded-KV backend). Uses INSERT OR REPLACE into the shards table.
+    #[pyo3(signature = (repo_id, repo_path, db_path, backend="sqlite"))]
+    pub fn register_shard(
+        &self,
+        repo_id: &str,
+        repo_path: &str,
+        db_path: &str,
+        backend: &str,
+    ) -> PyResult<()> {
+        retry_on_busy(|| {
+            let conn = self.connect()?;
+            conn.execute(
+                "INSERT OR REPLACE INTO shards(\
+                     repo_id, repo_path, db_path, enabled, backend, updated_at\
+                 ) VALUES (?1, ?2, ?3, 1, ?4, CURRENT_TIMESTAMP);",
+                params![repo_id, repo_path, db_path, backend],
+            )?;
+            Ok(())
+        })
+        .map_err(Into::into)
     }
 
     /// Unregister a shard: delete its cross-repo edges, exported symbols,
     /// and shard row.
     pub fn unregister_shard(&self, repo_id: &str) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "DELETE FROM cross_repo_edges WHERE source_repo_id = ?1 OR target_repo_id = ?1;",
-            params![repo_id],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute(
-            "DELETE FROM exported_symbols WHERE repo_id = ?1;",
-            params![repo_id],
-        )
-        .map_err(BombeError::from)?;
-        conn.execute("DELETE FROM shards WHERE repo_id = ?1;", params![repo_id])
-            .map_err(BombeError::from)?;
-        Ok(())
+        self.graph_backend.delete_edges_for_repo(repo_id)?;
+        retry_on_busy(|| {
+            let conn = self.connect()?;
+            conn.execute(
+                "DELETE FROM exported_symbols WHERE repo_id = ?1;",
+                params![repo_id],
+            )?;
+            conn.execute("DELETE FROM shards WHERE repo_id = ?1;", params![repo_id])?;
+            Ok(())
+        })
+        .map_err(Into::into)
     }
 
     /// List all shards, optionally filtered to enabled only.
@@ -319,11 +1093,11 @@ impl ShardCatalog {
         let conn = self.connect()?;
         let sql = if enabled_only {
             "SELECT repo_id, repo_path, db_path, enabled, last_indexed_at, \
-                    symbol_count, edge_count \
+                    symbol_count, edge_count, backend \
              FROM shards WHERE enabled = 1 ORDER BY repo_id ASC;"
         } else {
             "SELECT repo_id, repo_path, db_path, enabled, last_indexed_at, \
-                    symbol_count, edge_count \
+                    symbol_count, edge_count, backend \
              FROM shards ORDER BY repo_id ASC;"
         };
         let mut stmt = conn.prepare(sql).map_err(BombeError::from)?;
@@ -344,7 +1118,7 @@ impl ShardCatalog {
         let mut stmt = conn
             .prepare(
                 "SELECT repo_id, repo_path, db_path, enabled, last_indexed_at, \
-                        symbol_count, edge_count \
+                        symbol_count, edge_count, backend \
                  FROM shards WHERE repo_id = ?1 LIMIT 1;",
             )
             .map_err(BombeError::from)?;
@@ -367,18 +1141,20 @@ impl ShardCatalog {
         symbol_count: i64,
         edge_count: i64,
     ) -> PyResult<()> {
-        let conn = self.connect()?;
-        conn.execute(
-            "UPDATE shards \
-             SET symbol_count = ?1, \
-                 edge_count = ?2, \
-                 last_indexed_at = CURRENT_TIMESTAMP, \
-                 updated_at = CURRENT_TIMESTAMP \
-             WHERE repo_id = ?3;",
-            params![symbol_count, edge_count, repo_id],
-        )
-        .map_err(BombeError::from)?;
-        Ok(())
+        retry_on_busy(|| {
+            let conn = self.connect()?;
+            conn.execute(
+                "UPDATE shards \
+                 SET symbol_count = ?1, \
+                     edge_count = ?2, \
+                     last_indexed_at = CURRENT_TIMESTAMP, \
+                     updated_at = CURRENT_TIMESTAMP \
+                 WHERE repo_id = ?3;",
+                params![symbol_count, edge_count, repo_id],
+            )?;
+            Ok(())
+        })
+        .map_err(Into::into)
     }
 
     // -----------------------------------------------------------------------
@@ -395,6 +1171,11 @@ impl ShardCatalog {
         repo_id: &str,
         db: &Database,
     ) -> PyResult<i64> {
+        // Monorepo shards may hold several logical projects under
+        // different source roots; de-root each qualified_name so it's
+        // comparable to how dependents reference this shard's symbols.
+        let source_roots = db.get_source_roots()?;
+
         // Query symbols from the shard database.
         let limit_params: Vec<PyObject> = vec![MAX_EXPORTED_SYMBOLS_REFRESH
             .into_pyobject(py)?
@@ -409,24 +1190,29 @@ impl ShardCatalog {
             Some(limit_params),
         )?;
 
-        // Extract the list of dicts from the returned PyObject.
+        // Extract the list of dicts from the returned PyObject. Done before
+        // `retry_on_busy` below since none of this can raise a retryable
+        // SQLite error and a retry must not re-run Python-side work.
         let symbols_list = symbols_obj.bind(py);
         let symbols: &Bound<'_, PyList> = symbols_list.downcast::<PyList>()?;
 
-        let conn = self.connect()?;
-        conn.execute(
-            "DELETE FROM exported_symbols WHERE repo_id = ?1;",
-            params![repo_id],
-        )
-        .map_err(BombeError::from)?;
+        struct Row {
+            qualified_name: String,
+            name: String,
+            kind: String,
+            file_path: String,
+            visibility: Option<String>,
+            pagerank_score: f64,
+        }
 
-        let mut count: i64 = 0;
+        let mut rows = Vec::with_capacity(symbols.len());
         for sym_obj in symbols.iter() {
             let sym: &Bound<'_, PyDict> = sym_obj.downcast::<PyDict>()?;
-            let qualified_name: String = sym
+            let raw_qualified_name: String = sym
                 .get_item("qualified_name")?
                 .ok_or_else(|| BombeError::Database("missing qualified_name".into()))?
                 .extract()?;
+            let (qualified_name, _) = strip_source_root(&raw_qualified_name, &source_roots);
             let name: String = sym
                 .get_item("name")?
                 .ok_or_else(|| BombeError::Database("missing name".into()))?
@@ -445,26 +1231,53 @@ impl ShardCatalog {
                 .get_item("pagerank_score")?
                 .map(|v| v.extract().unwrap_or(0.0))
                 .unwrap_or(0.0);
-
-            conn.execute(
-                "INSERT OR REPLACE INTO exported_symbols(\
-                     repo_id, qualified_name, name, kind, file_path, \
-                     visibility, pagerank_score, updated_at\
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP);",
-                params![
-                    repo_id,
-                    qualified_name,
-                    name,
-                    kind,
-                    file_path,
-                    visibility,
-                    pagerank_score,
-                ],
-            )
-            .map_err(BombeError::from)?;
-            count += 1;
+            rows.push(Row {
+                qualified_name,
+                name,
+                kind,
+                file_path,
+                visibility,
+                pagerank_score,
+            });
         }
-        Ok(count)
+
+        // One `BEGIN IMMEDIATE`…`COMMIT` transaction with the INSERT
+        // prepared once and reused across every row, instead of `rows.len()`
+        // implicit autocommit transactions each re-parsing the same SQL —
+        // the dominant cost of a federation-wide refresh otherwise. Letting
+        // `tx` drop without a `commit()` (any `?` above returns early)
+        // rolls the whole transaction back, so a failure partway through
+        // never leaves `exported_symbols` half-refreshed.
+        retry_on_busy(|| {
+            let mut conn = self.connect()?;
+            let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+            tx.execute(
+                "DELETE FROM exported_symbols WHERE repo_id = ?1;",
+                params![repo_id],
+            )?;
+            {
+                let mut stmt = tx.prepare(
+                    "INSERT OR REPLACE INTO exported_symbols(\
+                         repo_id, qualified_name, name, kind, file_path, \
+                         visibility, pagerank_score, updated_at\
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP);",
+                )?;
+                for row in &rows {
+                    stmt.execute(params![
+                        repo_id,
+                        row.qualified_name,
+                        row.name,
+                        row.kind,
+                        row.file_path,
+                        row.visibility,
+                        row.pagerank_score,
+                    ])?;
+                }
+            }
+            tx.commit()?;
+            Ok(rows.len() as i64)
+        })
+        .map_err(Into::into)
     }
 
     /// Search exported symbols by name LIKE pattern.
@@ -525,58 +1338,207 @@ impl ShardCatalog {
         Ok(list.into_any().unbind())
     }
 
-    /// Find exported symbols matching module_name for cross-repo resolution.
+    /// Like [`Self::search_exported_symbols`], but ranks via the
+    /// `exported_symbols_fts` FTS5 index instead of `name LIKE '%…%'`,
+    /// which can't use `idx_exported_name` for a leading wildcard and
+    /// degrades as the federated symbol set grows. `query` is matched as a
+    /// literal phrase (quoted, with embedded `"` doubled) rather than
+    /// passed through raw, so a query containing FTS5 operators like `AND`/
+    /// `NEAR`/`-` is still treated as plain text. Each match's score blends
+    /// its normalized `bm25()` relevance with its `pagerank_score`
+    /// (70/30 — relevance dominates, pagerank breaks ties among
+    /// similarly-relevant matches), the same min-max-then-combine shape as
+    /// `query::federated::merge::global_topk`. Falls back to
+    /// [`Self::search_exported_symbols`]'s plain `LIKE` path when this
+    /// SQLite build has no FTS5 extension (see [`has_fts5_table`]).
+    #[pyo3(signature = (query, kind="any", limit=20))]
+    pub fn search_exported_symbols_ranked(
+        &self,
+        py: Python<'_>,
+        query: &str,
+        kind: &str,
+        limit: i64,
+    ) -> PyResult<PyObject> {
+        let safe_limit = std::cmp::max(1, limit);
+        let conn = self.connect()?;
+
+        if !has_fts5_table(&conn) {
+            return self.search_exported_symbols(py, query, kind, limit);
+        }
+
+        let match_query = format!("\"{}\"", query.replace('"', "\"\""));
+        // Over-fetch candidates beyond `limit` so the pagerank blend below
+        // has enough of them to actually re-rank before truncating.
+        let candidate_limit = safe_limit.saturating_mul(5).min(500);
+
+        let (sql, params_vec): (&str, Vec<Box<dyn rusqlite::types::ToSql>>) = if kind == "any" {
+            (
+                "SELECT e.repo_id, e.qualified_name, e.name, e.kind, e.file_path, \
+                        e.visibility, e.pagerank_score, bm25(exported_symbols_fts) AS bm25_score \
+                 FROM exported_symbols_fts \
+                 JOIN exported_symbols e ON e.rowid = exported_symbols_fts.rowid \
+                 WHERE exported_symbols_fts MATCH ?1 \
+                 LIMIT ?2;",
+                vec![
+                    Box::new(match_query) as Box<dyn rusqlite::types::ToSql>,
+                    Box::new(candidate_limit),
+                ],
+            )
+        } else {
+            (
+                "SELECT e.repo_id, e.qualified_name, e.name, e.kind, e.file_path, \
+                        e.visibility, e.pagerank_score, bm25(exported_symbols_fts) AS bm25_score \
+                 FROM exported_symbols_fts \
+                 JOIN exported_symbols e ON e.rowid = exported_symbols_fts.rowid \
+                 WHERE exported_symbols_fts MATCH ?1 AND e.kind = ?2 \
+                 LIMIT ?3;",
+                vec![
+                    Box::new(match_query) as Box<dyn rusqlite::types::ToSql>,
+                    Box::new(kind.to_string()),
+                    Box::new(candidate_limit),
+                ],
+            )
+        };
+
+        struct RankedRow {
+            repo_id: String,
+            qualified_name: String,
+            name: String,
+            kind: String,
+            file_path: String,
+            visibility: Option<String>,
+            pagerank_score: f64,
+            bm25_score: f64,
+        }
+
+        let mut stmt = conn.prepare(sql).map_err(BombeError::from)?;
+        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
+            params_vec.iter().map(|b| b.as_ref()).collect();
+        let mut candidates = Vec::new();
+        let mut rows = stmt
+            .query(param_refs.as_slice())
+            .map_err(BombeError::from)?;
+        while let Some(row) = rows.next().map_err(BombeError::from)? {
+            candidates.push(RankedRow {
+                repo_id: row.get(0).map_err(BombeError::from)?,
+                qualified_name: row.get(1).map_err(BombeError::from)?,
+                name: row.get(2).map_err(BombeError::from)?,
+                kind: row.get(3).map_err(BombeError::from)?,
+                file_path: row.get(4).map_err(BombeError::from)?,
+                visibility: row.get(5).map_err(BombeError::from)?,
+                pagerank_score: row.get(6).map_err(BombeError::from)?,
+                bm25_score: row.get(7).map_err(BombeError::from)?,
+            });
+        }
+        drop(rows);
+        drop(stmt);
+
+        // bm25() is negative with lower (more negative) meaning more
+        // relevant; flip the sign so bigger is better, then min-max
+        // normalize into [0, 1] to put it on the same scale as
+        // pagerank_score before blending the two.
+        const RELEVANCE_WEIGHT: f64 = 0.7;
+        const PAGERANK_WEIGHT: f64 = 0.3;
+
+        let relevance: Vec<f64> = candidates.iter().map(|r| -r.bm25_score).collect();
+        let min = relevance.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = relevance.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let spread = max - min;
+
+        let mut scored: Vec<(f64, RankedRow)> = candidates
+            .into_iter()
+            .zip(relevance)
+            .map(|(row, raw)| {
+                let normalized = if spread > 0.0 { (raw - min) / spread } else { 1.0 };
+                let blended = RELEVANCE_WEIGHT * normalized + PAGERANK_WEIGHT * row.pagerank_score;
+                (blended, row)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(safe_limit as usize);
+
+        let mut rows_out: Vec<Bound<'_, PyDict>> = Vec::with_capacity(scored.len());
+        for (_, row) in scored {
+            let dict = PyDict::new(py);
+            dict.set_item("repo_id", row.repo_id)?;
+            dict.set_item("qualified_name", row.qualified_name)?;
+            dict.set_item("name", row.name)?;
+            dict.set_item("kind", row.kind)?;
+            dict.set_item("file_path", row.file_path)?;
+            dict.set_item("visibility", row.visibility)?;
+            dict.set_item("pagerank_score", row.pagerank_score)?;
+            rows_out.push(dict);
+        }
+        let list = PyList::new(py, rows_out.iter().map(|d| d.as_any()))?;
+        Ok(list.into_any().unbind())
+    }
+
+    /// Register `language`'s import resolution strategy for
+    /// [`Self::resolve_external_import`], persisted in `catalog_meta` so it
+    /// survives across connections. `strategy` is one of
+    /// `"exact-name"`/`"last-segment"`/`"qualified-prefix"`/`"suffix"`/
+    /// `"normalized-path-to-dotted"`; `separator` is the path separator
+    /// `"last-segment"` splits on (default `"."`) or `"normalized-path-to-
+    /// dotted"` replaces with `.` (default `"/"`), ignored by the other
+    /// three strategies. Overwrites any strategy previously registered for
+    /// the same language (case-insensitive).
+    #[pyo3(signature = (language, strategy, separator=None))]
+    pub fn register_import_resolver(
+        &self,
+        language: &str,
+        strategy: &str,
+        separator: Option<&str>,
+    ) -> PyResult<()> {
+        let parsed = ImportResolverStrategy::parse(strategy, separator)?;
+        let value = serde_json::to_string(&parsed.to_json()).map_err(BombeError::from)?;
+        let conn = self.connect()?;
+        conn.execute(
+            "INSERT INTO catalog_meta(key, value) VALUES(?1, ?2) \
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value;",
+            params![import_resolver_key(language), value],
+        )
+        .map_err(BombeError::from)?;
+        Ok(())
+    }
+
+    /// Find exported symbols matching module_name for cross-repo
+    /// resolution, using whatever [`ImportResolverStrategy`] is registered
+    /// for `language` (see [`Self::register_import_resolver`]), falling
+    /// back to [`ImportResolverStrategy::default_for_language`] --
+    /// TypeScript's historical last-segment match, qualified-name prefix
+    /// match for everything else -- when nothing's registered.
     ///
-    /// Language-aware matching:
-    /// - TypeScript: match the last segment of the module path as a name.
-    /// - Python/Java/Go/other: prefix match on qualified_name.
+    /// `limit` caps the number of matches; a precise (non-wildcard) import
+    /// passes a small limit, while a wildcard/glob import passes
+    /// [`crate::query::guards::MAX_WILDCARD_IMPORT_MATCHES`] to expand
+    /// against the target shard's full exported-symbol set.
+    #[pyo3(signature = (module_name, language, limit=20))]
     pub fn resolve_external_import(
         &self,
         py: Python<'_>,
         module_name: &str,
         language: &str,
+        limit: i64,
     ) -> PyResult<PyObject> {
-        let lang_lower = language.to_lowercase();
+        let safe_limit = std::cmp::max(1, limit);
         let conn = self.connect()?;
 
-        let (sql, params_vec): (&str, Vec<Box<dyn rusqlite::types::ToSql>>) =
-            if lang_lower == "typescript" {
-                let normalized = module_name.replace('/', ".");
-                let segments: Vec<&str> = normalized
-                    .split('.')
-                    .map(|s| s.trim())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                let last_segment = segments.last().copied().unwrap_or(module_name).to_string();
-                (
-                    "SELECT repo_id, qualified_name, name, kind, file_path, \
-                            visibility, pagerank_score \
-                     FROM exported_symbols \
-                     WHERE name = ?1 \
-                     ORDER BY pagerank_score DESC \
-                     LIMIT 20;",
-                    vec![Box::new(last_segment) as Box<dyn rusqlite::types::ToSql>],
-                )
-            } else {
-                let prefix = format!("{module_name}%");
-                (
-                    "SELECT repo_id, qualified_name, name, kind, file_path, \
-                            visibility, pagerank_score \
-                     FROM exported_symbols \
-                     WHERE qualified_name LIKE ?1 \
-                     ORDER BY pagerank_score DESC \
-                     LIMIT 20;",
-                    vec![Box::new(prefix) as Box<dyn rusqlite::types::ToSql>],
-                )
-            };
+        let strategy = lookup_import_resolver(&conn, language);
+        let (match_clause, pattern) = strategy.build_match(module_name);
+        let sql = format!(
+            "SELECT repo_id, qualified_name, name, kind, file_path, \
+                    visibility, pagerank_score \
+             FROM exported_symbols \
+             WHERE {match_clause} \
+             ORDER BY pagerank_score DESC \
+             LIMIT ?2;"
+        );
 
-        let mut stmt = conn.prepare(sql).map_err(BombeError::from)?;
+        let mut stmt = conn.prepare(&sql).map_err(BombeError::from)?;
         let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            params_vec.iter().map(|b| b.as_ref()).collect();
         let mut rows_out: Vec<Bound<'_, PyDict>> = Vec::new();
         let mut rows = stmt
-            .query(param_refs.as_slice())
+            .query(params![pattern, safe_limit])
             .map_err(BombeError::from)?;
         while let Some(row) = rows.next().map_err(BombeError::from)? {
             rows_out.push(row_to_pydict(py, row, &col_names)?);
@@ -585,6 +1547,136 @@ impl ShardCatalog {
         Ok(list.into_any().unbind())
     }
 
+    /// Return the export hash currently recorded for a shard, or `None` if
+    /// the shard has never been hashed (never synced, or pre-v4 catalog).
+    pub fn get_export_hash(&self, repo_id: &str) -> PyResult<Option<String>> {
+        let conn = self.connect()?;
+        let result: Result<Option<String>, _> = conn.query_row(
+            "SELECT export_hash FROM shards WHERE repo_id = ?1 LIMIT 1;",
+            params![repo_id],
+            |row| row.get(0),
+        );
+        match result {
+            Ok(v) => Ok(v),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(BombeError::from(e).into()),
+        }
+    }
+
+    /// Recompute *repo_id*'s export hash — SHA-256 over its currently cached
+    /// `exported_symbols` rows, sorted by `(qualified_name, file_path)` —
+    /// store it, and report whether it changed since the last sync.
+    ///
+    /// Call this after [`Self::refresh_exported_symbols`] has repopulated
+    /// the cache. A shard that was never hashed before (first sync, or a
+    /// catalog created before schema v4) always reports changed, so callers
+    /// fall back to a full resolve rather than assuming nothing moved.
+    pub fn refresh_export_hash(&self, repo_id: &str) -> PyResult<bool> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT qualified_name, file_path FROM exported_symbols \
+                 WHERE repo_id = ?1 ORDER BY qualified_name ASC, file_path ASC;",
+            )
+            .map_err(BombeError::from)?;
+        let mut rows = stmt.query(params![repo_id]).map_err(BombeError::from)?;
+        let mut hasher = Sha256::new();
+        while let Some(row) = rows.next().map_err(BombeError::from)? {
+            let qualified_name: String = row.get(0).map_err(BombeError::from)?;
+            let file_path: String = row.get(1).map_err(BombeError::from)?;
+            hasher.update(qualified_name.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(file_path.as_bytes());
+            hasher.update(b"\n");
+        }
+        let new_hash = format!("{:x}", hasher.finalize());
+        drop(rows);
+        drop(stmt);
+
+        let previous = self.get_export_hash(repo_id)?;
+        conn.execute(
+            "UPDATE shards SET export_hash = ?1 WHERE repo_id = ?2;",
+            params![new_hash, repo_id],
+        )
+        .map_err(BombeError::from)?;
+        Ok(previous.as_deref() != Some(new_hash.as_str()))
+    }
+
+    // -----------------------------------------------------------------------
+    // Dependency classification
+    // -----------------------------------------------------------------------
+
+    /// Record how one `module_name`+`language` external dependency was
+    /// classified for *repo_id* (see
+    /// [`crate::store::sharding::stdlib_registry::classify_dependency`]) —
+    /// `"stdlib"`, `"known_external_package"`, or `"unresolved_external"`.
+    /// `"candidate_cross_repo"` classifications that *did* resolve aren't
+    /// recorded here; they already show up as a cross-repo edge.
+    pub fn record_dependency_classification(
+        &self,
+        repo_id: &str,
+        module_name: &str,
+        language: &str,
+        classification: &str,
+        file_path: &str,
+    ) -> PyResult<()> {
+        let conn = self.connect()?;
+        conn.execute(
+            "INSERT INTO external_dep_classifications(\
+                 repo_id, module_name, language, classification, file_path, updated_at\
+             ) VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP) \
+             ON CONFLICT(repo_id, module_name, language) DO UPDATE SET \
+                 classification = excluded.classification, \
+                 file_path = excluded.file_path, \
+                 updated_at = excluded.updated_at;",
+            params![repo_id, module_name, language, classification, file_path],
+        )
+        .map_err(BombeError::from)?;
+        Ok(())
+    }
+
+    /// Delete every recorded dependency classification for *repo_id*, so a
+    /// re-sync starts from a clean slate (mirrors
+    /// [`Self::delete_cross_repo_edges_for_repo`]).
+    pub fn delete_dependency_classifications_for_repo(&self, repo_id: &str) -> PyResult<i64> {
+        let conn = self.connect()?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM external_dep_classifications WHERE repo_id = ?1;",
+                params![repo_id],
+            )
+            .map_err(BombeError::from)?;
+        Ok(deleted as i64)
+    }
+
+    /// Count recorded dependency classifications for *repo_id*, grouped by
+    /// classification. Returns a dict of `classification -> count`, used by
+    /// `post_index_cross_repo_sync` to distinguish "expected external
+    /// dependency" from "genuinely missing shard" in its summary.
+    pub fn count_dependency_classifications(
+        &self,
+        py: Python<'_>,
+        repo_id: &str,
+    ) -> PyResult<PyObject> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT classification, COUNT(*) AS cnt \
+                 FROM external_dep_classifications \
+                 WHERE repo_id = ?1 \
+                 GROUP BY classification;",
+            )
+            .map_err(BombeError::from)?;
+        let dict = PyDict::new(py);
+        let mut rows = stmt.query(params![repo_id]).map_err(BombeError::from)?;
+        while let Some(row) = rows.next().map_err(BombeError::from)? {
+            let classification: String = row.get(0).map_err(BombeError::from)?;
+            let count: i64 = row.get(1).map_err(BombeError::from)?;
+            dict.set_item(classification, count)?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+
     // -----------------------------------------------------------------------
     // Cross-repo edge management
     // -----------------------------------------------------------------------
@@ -595,18 +1687,32 @@ impl ShardCatalog {
     /// source_file_path, target_repo_id, target_qualified_name,
     /// target_file_path, relationship, confidence, provenance.
     ///
+    /// `chunk_size` bounds how many edges each commit covers: left at its
+    /// default (`None`), all of `edges` goes through one transaction, so a
+    /// malformed row anywhere in the batch rolls the whole ingest back
+    /// rather than leaving `cross_repo_edges` half-populated. Passing a
+    /// `chunk_size` trades that all-or-nothing guarantee for bounded commit
+    /// batches on very large ingests: earlier chunks that already committed
+    /// stay committed if a later chunk fails. (The LMDB `edge_backend`
+    /// ignores `chunk_size` — see
+    /// [`crate::store::sharding::graph_backend::LmdbGraphBackend`].)
+    ///
     /// Returns the count of edges upserted.
+    #[pyo3(signature = (edges, chunk_size=None))]
     pub fn upsert_cross_repo_edges(
         &self,
         py: Python<'_>,
         edges: &Bound<'_, PyList>,
+        chunk_size: Option<usize>,
     ) -> PyResult<i64> {
         if edges.len() == 0 {
             return Ok(0);
         }
-        let conn = self.connect()?;
-        let mut count: i64 = 0;
 
+        // Extracted before handing off to the graph backend since none of
+        // this can raise a retryable SQLite error and a retry must not
+        // re-run Python-side work.
+        let mut rows = Vec::with_capacity(edges.len());
         for edge_obj in edges.iter() {
             let edge: &Bound<'_, PyDict> = edge_obj.downcast::<PyDict>()?;
 
@@ -650,32 +1756,21 @@ impl ShardCatalog {
                 })
                 .unwrap_or_else(|| "import_resolution".to_string());
 
-            conn.execute(
-                "INSERT OR REPLACE INTO cross_repo_edges(\
-                     source_repo_id, source_qualified_name, source_file_path, \
-                     target_repo_id, target_qualified_name, target_file_path, \
-                     relationship, confidence, provenance, updated_at\
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, CURRENT_TIMESTAMP);",
-                params![
-                    source_repo_id,
-                    source_qualified_name,
-                    source_file_path,
-                    target_repo_id,
-                    target_qualified_name,
-                    target_file_path,
-                    relationship,
-                    confidence,
-                    provenance,
-                ],
-            )
-            .map_err(BombeError::from)?;
-            count += 1;
+            rows.push(CrossRepoEdge {
+                source_repo_id,
+                source_qualified_name,
+                source_file_path,
+                target_repo_id,
+                target_qualified_name,
+                target_file_path,
+                relationship,
+                confidence,
+                provenance,
+            });
         }
-        // In rusqlite autocommit mode, each execute is committed.
-        // For batch efficiency we could use a transaction, but matching Python
-        // pattern of individual inserts for simplicity.
         let _ = py;
-        Ok(count)
+
+        Ok(self.graph_backend.store_edges(&rows, chunk_size)?)
     }
 
     /// Get outgoing cross-repo edges from a symbol.
@@ -686,27 +1781,39 @@ impl ShardCatalog {
         repo_id: &str,
         symbol_name: &str,
     ) -> PyResult<PyObject> {
-        let conn = self.connect()?;
-        let mut stmt = conn
-            .prepare(
-                "SELECT source_repo_id, source_qualified_name, source_file_path, \
-                        target_repo_id, target_qualified_name, target_file_path, \
-                        relationship, confidence, provenance \
-                 FROM cross_repo_edges \
-                 WHERE source_repo_id = ?1 AND source_qualified_name = ?2 \
-                 ORDER BY id ASC;",
-            )
-            .map_err(BombeError::from)?;
-        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
-        let mut rows_out: Vec<Bound<'_, PyDict>> = Vec::new();
-        let mut rows = stmt
-            .query(params![repo_id, symbol_name])
-            .map_err(BombeError::from)?;
-        while let Some(row) = rows.next().map_err(BombeError::from)? {
-            rows_out.push(row_to_pydict(py, row, &col_names)?);
+        let edges = self.graph_backend.get_edges_from(repo_id, symbol_name)?;
+        edges_to_pylist(py, &edges)
+    }
+
+    /// Batched [`Self::get_cross_repo_edges_from`]: looks up outgoing edges
+    /// for every name in `symbol_names` in a single [`GraphBackend`] call
+    /// instead of one per symbol, so resolving a whole module's imports is
+    /// one round trip rather than N. `symbol_names` is truncated to
+    /// [`crate::query::guards::MAX_BATCH_EDGE_LOOKUP_SYMBOLS`] silently, the
+    /// same cap convention [`crate::query::guards::MAX_CONTEXT_SEEDS`] uses.
+    ///
+    /// Returns a dict mapping each requested symbol name to its list of
+    /// outgoing edge dicts; a symbol with no outgoing edges still gets an
+    /// entry, mapped to an empty list.
+    pub fn get_cross_repo_edges_from_many(
+        &self,
+        py: Python<'_>,
+        repo_id: &str,
+        symbol_names: Vec<String>,
+    ) -> PyResult<PyObject> {
+        let mut symbol_names = symbol_names;
+        symbol_names.truncate(crate::query::guards::MAX_BATCH_EDGE_LOOKUP_SYMBOLS);
+
+        let by_name = self
+            .graph_backend
+            .get_edges_from_many(repo_id, &symbol_names)?;
+
+        let dict = PyDict::new(py);
+        for name in &symbol_names {
+            let edges = by_name.get(name).map(Vec::as_slice).unwrap_or(&[]);
+            dict.set_item(name, edges_to_pylist(py, edges)?)?;
         }
-        let list = PyList::new(py, rows_out.iter().map(|d| d.as_any()))?;
-        Ok(list.into_any().unbind())
+        Ok(dict.into_any().unbind())
     }
 
     /// Get incoming cross-repo edges to a symbol.
@@ -717,53 +1824,526 @@ impl ShardCatalog {
         repo_id: &str,
         symbol_name: &str,
     ) -> PyResult<PyObject> {
+        let edges = self.graph_backend.get_edges_to(repo_id, symbol_name)?;
+        edges_to_pylist(py, &edges)
+    }
+
+    /// Delete all cross-repo edges involving a repo.
+    /// Returns the count of deleted rows.
+    pub fn delete_cross_repo_edges_for_repo(&self, repo_id: &str) -> PyResult<i64> {
+        Ok(self.graph_backend.delete_edges_for_repo(repo_id)?)
+    }
+
+    /// Breadth-first multi-hop reachability over `cross_repo_edges`,
+    /// starting from `(repo_id, symbol_name)`: repeatedly expands the
+    /// frontier via [`GraphBackend::get_edges_from`], following only edges
+    /// whose `confidence >= min_confidence`, until `max_depth` hops or
+    /// [`crate::query::guards::MAX_GRAPH_VISITED`] distinct symbols is
+    /// reached. Cycles are broken by tracking every visited `(repo_id,
+    /// qualified_name)` pair, so a symbol already reached isn't re-expanded.
+    ///
+    /// Returns a list of dicts — one per reached symbol (the seed itself is
+    /// excluded) — each carrying `repo_id`, `qualified_name`, `file_path`,
+    /// `depth` (hop count from the seed), and `confidence` (the product of
+    /// every edge confidence along the discovered path, so a long chain of
+    /// weak links ranks lower than a short chain of strong ones even at the
+    /// same depth).
+    #[pyo3(signature = (repo_id, symbol_name, max_depth=6, min_confidence=0.0))]
+    pub fn reachable_from(
+        &self,
+        py: Python<'_>,
+        repo_id: &str,
+        symbol_name: &str,
+        max_depth: i64,
+        min_confidence: f64,
+    ) -> PyResult<PyObject> {
+        let max_depth = crate::query::guards::clamp_depth(
+            max_depth,
+            crate::query::guards::MAX_CROSS_REPO_REACHABILITY_DEPTH,
+        );
+
+        struct Reached {
+            repo_id: String,
+            qualified_name: String,
+            file_path: String,
+            depth: i64,
+            confidence: f64,
+        }
+
+        let mut visited: std::collections::HashSet<(String, String)> =
+            std::collections::HashSet::new();
+        visited.insert((repo_id.to_string(), symbol_name.to_string()));
+        let mut frontier = vec![(repo_id.to_string(), symbol_name.to_string(), 0i64, 1.0f64)];
+        let mut reached: Vec<Reached> = Vec::new();
+
+        for _ in 0..max_depth {
+            if frontier.is_empty() || reached.len() as i64 >= crate::query::guards::MAX_GRAPH_VISITED
+            {
+                break;
+            }
+            let mut next_frontier = Vec::new();
+            'frontier: for (cur_repo, cur_name, depth, confidence) in frontier {
+                let edges = self.graph_backend.get_edges_from(&cur_repo, &cur_name)?;
+                for edge in edges {
+                    if edge.confidence < min_confidence {
+                        continue;
+                    }
+                    let key = (edge.target_repo_id.clone(), edge.target_qualified_name.clone());
+                    if !visited.insert(key) {
+                        continue;
+                    }
+                    let path_confidence = confidence * edge.confidence;
+                    let next_depth = depth + 1;
+                    reached.push(Reached {
+                        repo_id: edge.target_repo_id.clone(),
+                        qualified_name: edge.target_qualified_name.clone(),
+                        file_path: edge.target_file_path.clone(),
+                        depth: next_depth,
+                        confidence: path_confidence,
+                    });
+                    if reached.len() as i64 >= crate::query::guards::MAX_GRAPH_VISITED {
+                        break 'frontier;
+                    }
+                    next_frontier.push((
+                        edge.target_repo_id,
+                        edge.target_qualified_name,
+                        next_depth,
+                        path_confidence,
+                    ));
+                }
+            }
+            frontier = next_frontier;
+        }
+
+        let dicts: Vec<Bound<'_, PyDict>> = reached
+            .iter()
+            .map(|r| {
+                let dict = PyDict::new(py);
+                dict.set_item("repo_id", &r.repo_id)?;
+                dict.set_item("qualified_name", &r.qualified_name)?;
+                dict.set_item("file_path", &r.file_path)?;
+                dict.set_item("depth", r.depth)?;
+                dict.set_item("confidence", r.confidence)?;
+                Ok::<_, PyErr>(dict)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let list = PyList::new(py, dicts.iter().map(|d| d.as_any()))?;
+        Ok(list.into_any().unbind())
+    }
+
+    /// Count `source_repo_id`'s outgoing cross-repo edges, optionally
+    /// restricted to a set of target repos. Used to report how many
+    /// existing edges a sync reused vs recomputed.
+    #[pyo3(signature = (source_repo_id, target_repo_ids=None))]
+    pub fn count_cross_repo_edges_from(
+        &self,
+        source_repo_id: &str,
+        target_repo_ids: Option<Vec<String>>,
+    ) -> PyResult<i64> {
+        let conn = self.connect()?;
+        match target_repo_ids {
+            None => {
+                let count: i64 = conn
+                    .query_row(
+                        "SELECT COUNT(*) FROM cross_repo_edges WHERE source_repo_id = ?1;",
+                        params![source_repo_id],
+                        |row| row.get(0),
+                    )
+                    .map_err(BombeError::from)?;
+                Ok(count)
+            }
+            Some(targets) => {
+                let mut count = 0i64;
+                for target in targets {
+                    count += conn
+                        .query_row(
+                            "SELECT COUNT(*) FROM cross_repo_edges \
+                             WHERE source_repo_id = ?1 AND target_repo_id = ?2;",
+                            params![source_repo_id, target],
+                            |row| row.get(0),
+                        )
+                        .map_err(BombeError::from)?;
+                }
+                Ok(count)
+            }
+        }
+    }
+
+    /// Aggregate health numbers over `cross_repo_edges` and `shards`, for
+    /// operators who want a metrics surface rather than pulling every row
+    /// into Python to count it themselves. Every number comes from a `SELECT
+    /// ... GROUP BY`, never a full table scan into Rust or Python structures.
+    /// Reads the catalog's own SQLite tables directly, like
+    /// [`Self::list_all_cross_repo_edges`] and
+    /// [`Self::count_cross_repo_edges_from`] — an `"lmdb"` `edge_backend`
+    /// keeps `cross_repo_edges` out of this database, so the edge-derived
+    /// numbers below only reflect reality with the default `"sqlite"`
+    /// backend; `shards` always lives here regardless.
+    ///
+    /// Returns a dict with `total_edges`, `edges_by_relationship` (dict),
+    /// `edges_from_by_repo` (dict, outgoing counts keyed by source repo),
+    /// `edges_to_by_repo` (dict, incoming counts keyed by target repo),
+    /// `shards_enabled`, `shards_disabled`, `confidence_histogram` (dict of
+    /// `"0.0-0.2"`-style bucket labels to counts), and
+    /// `provenance_distribution` (dict).
+    pub fn graph_metrics(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let conn = self.connect()?;
+
+        let total_edges: i64 = conn
+            .query_row("SELECT COUNT(*) FROM cross_repo_edges;", [], |row| {
+                row.get(0)
+            })
+            .map_err(BombeError::from)?;
+
+        let edges_by_relationship = PyDict::new(py);
+        let mut stmt = conn.prepare(
+            "SELECT relationship, COUNT(*) FROM cross_repo_edges \
+             GROUP BY relationship;",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        for row in rows {
+            let (relationship, count) = row.map_err(BombeError::from)?;
+            edges_by_relationship.set_item(relationship, count)?;
+        }
+
+        let edges_from_by_repo = PyDict::new(py);
+        let mut stmt = conn.prepare(
+            "SELECT source_repo_id, COUNT(*) FROM cross_repo_edges \
+             GROUP BY source_repo_id;",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        for row in rows {
+            let (repo_id, count) = row.map_err(BombeError::from)?;
+            edges_from_by_repo.set_item(repo_id, count)?;
+        }
+
+        let edges_to_by_repo = PyDict::new(py);
+        let mut stmt = conn.prepare(
+            "SELECT target_repo_id, COUNT(*) FROM cross_repo_edges \
+             GROUP BY target_repo_id;",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        for row in rows {
+            let (repo_id, count) = row.map_err(BombeError::from)?;
+            edges_to_by_repo.set_item(repo_id, count)?;
+        }
+
+        let shards_enabled: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM shards WHERE enabled = 1;",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(BombeError::from)?;
+        let shards_disabled: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM shards WHERE enabled = 0;",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(BombeError::from)?;
+
+        const CONFIDENCE_BUCKETS: [&str; 5] =
+            ["0.0-0.2", "0.2-0.4", "0.4-0.6", "0.6-0.8", "0.8-1.0"];
+        let confidence_histogram = PyDict::new(py);
+        for label in CONFIDENCE_BUCKETS {
+            confidence_histogram.set_item(label, 0i64)?;
+        }
+        let mut stmt = conn.prepare(
+            "SELECT CASE WHEN confidence >= 1.0 THEN 4 \
+                         ELSE CAST(confidence * 5 AS INTEGER) END AS bucket, \
+                    COUNT(*) \
+             FROM cross_repo_edges \
+             GROUP BY bucket;",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        for row in rows {
+            let (bucket, count) = row.map_err(BombeError::from)?;
+            let label = CONFIDENCE_BUCKETS
+                .get(bucket.clamp(0, 4) as usize)
+                .copied()
+                .unwrap_or("0.8-1.0");
+            confidence_histogram.set_item(label, count)?;
+        }
+
+        let provenance_distribution = PyDict::new(py);
+        let mut stmt = conn.prepare(
+            "SELECT provenance, COUNT(*) FROM cross_repo_edges \
+             GROUP BY provenance;",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        for row in rows {
+            let (provenance, count) = row.map_err(BombeError::from)?;
+            provenance_distribution.set_item(provenance, count)?;
+        }
+
+        let metrics = PyDict::new(py);
+        metrics.set_item("total_edges", total_edges)?;
+        metrics.set_item("edges_by_relationship", edges_by_relationship)?;
+        metrics.set_item("edges_from_by_repo", edges_from_by_repo)?;
+        metrics.set_item("edges_to_by_repo", edges_to_by_repo)?;
+        metrics.set_item("shards_enabled", shards_enabled)?;
+        metrics.set_item("shards_disabled", shards_disabled)?;
+        metrics.set_item("confidence_histogram", confidence_histogram)?;
+        metrics.set_item("provenance_distribution", provenance_distribution)?;
+        Ok(metrics.into_any().unbind())
+    }
+
+    /// Delete `source_repo_id`'s outgoing cross-repo edges that point at any
+    /// of `target_repo_ids`, leaving edges to other (unaffected) targets in
+    /// place. Returns the count of deleted rows.
+    pub fn delete_cross_repo_edges_for_targets(
+        &self,
+        source_repo_id: &str,
+        target_repo_ids: Vec<String>,
+    ) -> PyResult<i64> {
+        let conn = self.connect()?;
+        let mut deleted = 0i64;
+        for target in target_repo_ids {
+            deleted += conn
+                .execute(
+                    "DELETE FROM cross_repo_edges \
+                     WHERE source_repo_id = ?1 AND target_repo_id = ?2;",
+                    params![source_repo_id, target],
+                )
+                .map_err(BombeError::from)? as i64;
+        }
+        Ok(deleted)
+    }
+
+    /// Return the distinct target repos `source_repo_id` resolved against on
+    /// its last sync (from [`Self::record_target_hashes`]'s bookkeeping
+    /// table), used to decide which dependencies are even worth
+    /// re-resolving.
+    pub fn get_known_target_repos(&self, source_repo_id: &str) -> PyResult<Vec<String>> {
         let conn = self.connect()?;
         let mut stmt = conn
             .prepare(
-                "SELECT source_repo_id, source_qualified_name, source_file_path, \
-                        target_repo_id, target_qualified_name, target_file_path, \
-                        relationship, confidence, provenance \
-                 FROM cross_repo_edges \
-                 WHERE target_repo_id = ?1 AND target_qualified_name = ?2 \
-                 ORDER BY id ASC;",
+                "SELECT target_repo_id FROM cross_repo_target_hashes \
+                 WHERE source_repo_id = ?1 ORDER BY target_repo_id ASC;",
             )
             .map_err(BombeError::from)?;
-        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
-        let mut rows_out: Vec<Bound<'_, PyDict>> = Vec::new();
-        let mut rows = stmt
-            .query(params![repo_id, symbol_name])
+        let rows = stmt
+            .query_map(params![source_repo_id], |row| row.get::<_, String>(0))
             .map_err(BombeError::from)?;
-        while let Some(row) = rows.next().map_err(BombeError::from)? {
-            rows_out.push(row_to_pydict(py, row, &col_names)?);
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row.map_err(BombeError::from)?);
         }
-        let list = PyList::new(py, rows_out.iter().map(|d| d.as_any()))?;
-        Ok(list.into_any().unbind())
+        Ok(out)
     }
 
-    /// Delete all cross-repo edges involving a repo.
-    /// Returns the count of deleted rows.
-    pub fn delete_cross_repo_edges_for_repo(&self, repo_id: &str) -> PyResult<i64> {
+    /// Of `source_repo_id`'s known target repos, return the ones whose
+    /// `shards.export_hash` no longer matches the hash recorded the last
+    /// time `source_repo_id` resolved against them — i.e. the targets whose
+    /// exported symbols actually moved since the last sync, and so are the
+    /// only ones worth re-resolving.
+    pub fn get_stale_target_repos(&self, source_repo_id: &str) -> PyResult<Vec<String>> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.target_repo_id \
+                 FROM cross_repo_target_hashes t \
+                 LEFT JOIN shards s ON s.repo_id = t.target_repo_id \
+                 WHERE t.source_repo_id = ?1 \
+                   AND (s.export_hash IS NULL OR s.export_hash IS NOT t.target_export_hash) \
+                 ORDER BY t.target_repo_id ASC;",
+            )
+            .map_err(BombeError::from)?;
+        let rows = stmt
+            .query_map(params![source_repo_id], |row| row.get::<_, String>(0))
+            .map_err(BombeError::from)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row.map_err(BombeError::from)?);
+        }
+        Ok(out)
+    }
+
+    /// Record, for each of `target_repo_ids`, the current export hash of
+    /// that target shard as the hash `source_repo_id` last resolved against
+    /// — so the next sync's [`Self::get_stale_target_repos`] call can tell
+    /// whether it moved. Targets with no recorded export hash yet (never
+    /// synced) are stored as `NULL` and so always come back stale.
+    pub fn record_target_hashes(
+        &self,
+        source_repo_id: &str,
+        target_repo_ids: Vec<String>,
+    ) -> PyResult<()> {
+        let conn = self.connect()?;
+        for target in target_repo_ids {
+            let target_hash = self.get_export_hash(&target)?;
+            conn.execute(
+                "INSERT INTO cross_repo_target_hashes(\
+                     source_repo_id, target_repo_id, target_export_hash, updated_at\
+                 ) VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP) \
+                 ON CONFLICT(source_repo_id, target_repo_id) DO UPDATE SET \
+                     target_export_hash = excluded.target_export_hash, \
+                     updated_at = excluded.updated_at;",
+                params![source_repo_id, target, target_hash],
+            )
+            .map_err(BombeError::from)?;
+        }
+        Ok(())
+    }
+
+    /// Return the distinct repos that resolved against `target_repo_id` on
+    /// their last sync, so that when `target_repo_id`'s own export hash
+    /// changes, callers know which dependents need to treat it as stale.
+    pub fn get_dependent_repos(&self, target_repo_id: &str) -> PyResult<Vec<String>> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT source_repo_id FROM cross_repo_target_hashes \
+                 WHERE target_repo_id = ?1 ORDER BY source_repo_id ASC;",
+            )
+            .map_err(BombeError::from)?;
+        let rows = stmt
+            .query_map(params![target_repo_id], |row| row.get::<_, String>(0))
+            .map_err(BombeError::from)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row.map_err(BombeError::from)?);
+        }
+        Ok(out)
+    }
+
+    /// Invalidate every dependent's recorded hash for `target_repo_id` (used
+    /// when `target_repo_id`'s own export hash just changed), so each
+    /// dependent's next sync sees a missing/mismatched hash and re-resolves
+    /// against it instead of reusing stale edges. Returns the number of
+    /// dependents invalidated.
+    pub fn invalidate_target_hash_for_dependents(&self, target_repo_id: &str) -> PyResult<i64> {
         let conn = self.connect()?;
         let deleted = conn
             .execute(
-                "DELETE FROM cross_repo_edges \
-                 WHERE source_repo_id = ?1 OR target_repo_id = ?1;",
-                params![repo_id],
+                "DELETE FROM cross_repo_target_hashes WHERE target_repo_id = ?1;",
+                params![target_repo_id],
             )
             .map_err(BombeError::from)?;
         Ok(deleted as i64)
     }
 
+    // -----------------------------------------------------------------------
+    // Portable export/import
+    // -----------------------------------------------------------------------
+
+    /// Serialize this catalog's `shards`, `exported_symbols`, and
+    /// `cross_repo_edges` tables, plus its `schema_version`, into one JSON
+    /// document -- a machine-to-machine transfer format for moving or
+    /// backing up a federation without re-indexing every shard. Each table
+    /// is `{"headers": [...], "rows": [[...], ...]}`, cozo `export_relations`
+    /// style, so [`Self::import_catalog`] can reconstruct the `INSERT`s
+    /// without this version having to know the exact column list.
+    ///
+    /// Written to `path` (returning `None`) if given, otherwise returned as
+    /// a string.
+    #[pyo3(signature = (path=None))]
+    pub fn export_catalog(&self, path: Option<&str>) -> PyResult<Option<String>> {
+        let conn = self.connect()?;
+        let document = Self::export_document(&conn)?;
+        let text = serde_json::to_string_pretty(&document).map_err(BombeError::from)?;
+        match path {
+            Some(p) => {
+                std::fs::write(p, &text).map_err(BombeError::from)?;
+                Ok(None)
+            }
+            None => Ok(Some(text)),
+        }
+    }
+
+    /// Reload `data` (as produced by [`Self::export_catalog`]) into this
+    /// catalog inside one transaction. Refuses the import outright if the
+    /// embedded `schema_version` is newer than [`CATALOG_SCHEMA_VERSION`] --
+    /// the same refusal [`Self::migrate_schema`] applies when opening a
+    /// catalog a newer binary wrote -- since this binary has no idea what
+    /// columns that version's tables might carry. An embedded version at or
+    /// below the current one imports fine: this catalog's own tables are
+    /// already fully migrated by [`Self::init_schema`], so an older export
+    /// just leaves any newer column at its default.
+    ///
+    /// `mode = "replace"` truncates all three tables first; `mode = "merge"`
+    /// leaves existing rows in place and `INSERT OR REPLACE`s on top of
+    /// them, so re-importing the same export (or one with overlapping rows)
+    /// dedupes correctly against the existing `UNIQUE`/`PRIMARY KEY`
+    /// constraints instead of erroring. Returns a dict of the row count
+    /// imported per table.
+    #[pyo3(signature = (data, mode="merge"))]
+    pub fn import_catalog(&self, py: Python<'_>, data: &str, mode: &str) -> PyResult<PyObject> {
+        if mode != "replace" && mode != "merge" {
+            return Err(BombeError::Query(format!(
+                "unknown import mode {mode:?}; expected \"replace\" or \"merge\""
+            ))
+            .into());
+        }
+        let truncate = mode == "replace";
+
+        let document: serde_json::Value = serde_json::from_str(data).map_err(BombeError::from)?;
+        let schema_version = document
+            .get("schema_version")
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| BombeError::Parse("catalog export is missing schema_version".into()))?;
+        if schema_version > CATALOG_SCHEMA_VERSION {
+            return Err(BombeError::Database(format!(
+                "catalog export schema version {schema_version} is newer than this binary \
+                 supports (CATALOG_SCHEMA_VERSION = {CATALOG_SCHEMA_VERSION}); refusing to \
+                 import it"
+            ))
+            .into());
+        }
+
+        let counts = retry_on_busy(|| {
+            let mut conn = self.connect()?;
+            let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+            let shards = import_table(&tx, "shards", document.get("shards"), truncate)?;
+            let exported_symbols =
+                import_table(&tx, "exported_symbols", document.get("exported_symbols"), truncate)?;
+            let cross_repo_edges = import_table(
+                &tx,
+                "cross_repo_edges",
+                document.get("cross_repo_edges"),
+                truncate,
+            )?;
+            tx.commit()?;
+            Ok((shards, exported_symbols, cross_repo_edges))
+        })?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("shards", counts.0)?;
+        dict.set_item("exported_symbols", counts.1)?;
+        dict.set_item("cross_repo_edges", counts.2)?;
+        Ok(dict.into_any().unbind())
+    }
+
     // -----------------------------------------------------------------------
     // Rust-side helpers for use by router and resolver
     // -----------------------------------------------------------------------
 
     /// Return the db_path for a shard, or None if the shard doesn't exist.
-    /// (Rust-only helper, not exposed to Python.)
+    /// (Rust-only helper, not exposed to Python.) Delegates to
+    /// [`GraphBackend::get_shard_db_path`] — shard registration always lives
+    /// in this catalog's own SQLite database regardless of `edge_backend`.
     pub fn get_shard_db_path(&self, repo_id: &str) -> PyResult<Option<String>> {
+        Ok(self.graph_backend.get_shard_db_path(repo_id)?)
+    }
+
+    /// Return the `StoreBackend` name (`"sqlite"`, ...) a shard is
+    /// registered under, or None if the shard doesn't exist. (Rust-only
+    /// helper, not exposed to Python.)
+    pub fn get_shard_backend(&self, repo_id: &str) -> PyResult<Option<String>> {
         let conn = self.connect()?;
         let result: Result<String, _> = conn.query_row(
-            "SELECT db_path FROM shards WHERE repo_id = ?1 AND enabled = 1 LIMIT 1;",
+            "SELECT backend FROM shards WHERE repo_id = ?1 AND enabled = 1 LIMIT 1;",
             params![repo_id],
             |row| row.get(0),
         );
@@ -773,4 +2353,41 @@ impl ShardCatalog {
             Err(e) => Err(BombeError::from(e).into()),
         }
     }
+
+    /// Return every row of `cross_repo_edges`, up to
+    /// [`crate::query::guards::MAX_RDF_EXPORT_EDGES`]. (Rust-only helper,
+    /// not exposed to Python.) Reads the catalog's own SQLite table
+    /// directly rather than through [`GraphBackend`] — an `"lmdb"`
+    /// `edge_backend` has no equivalent bulk scan, so callers that need a
+    /// full export (e.g. [`crate::store::sharding::rdf_export`]) still
+    /// require the default `"sqlite"` backend.
+    pub fn list_all_cross_repo_edges(&self) -> BombeResult<Vec<CrossRepoEdge>> {
+        let conn = self.connect()?;
+        let mut stmt = conn.prepare(
+            "SELECT source_repo_id, source_qualified_name, source_file_path, \
+                    target_repo_id, target_qualified_name, target_file_path, \
+                    relationship, confidence, provenance \
+             FROM cross_repo_edges \
+             ORDER BY id ASC \
+             LIMIT ?1;",
+        )?;
+        let rows = stmt.query_map(params![crate::query::guards::MAX_RDF_EXPORT_EDGES], |row| {
+            Ok(CrossRepoEdge {
+                source_repo_id: row.get(0)?,
+                source_qualified_name: row.get(1)?,
+                source_file_path: row.get(2)?,
+                target_repo_id: row.get(3)?,
+                target_qualified_name: row.get(4)?,
+                target_file_path: row.get(5)?,
+                relationship: row.get(6)?,
+                confidence: row.get(7)?,
+                provenance: row.get(8)?,
+            })
+        })?;
+        let mut edges = Vec::new();
+        for row in rows {
+            edges.push(row?);
+        }
+        Ok(edges)
+    }
 }
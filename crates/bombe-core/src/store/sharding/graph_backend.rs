@@ -0,0 +1,577 @@
+//! Pluggable cross-repo-edge graph backends.
+//!
+//! `ShardCatalog`'s cross-repo-edge operations have so far been hardwired to
+//! the catalog's own SQLite connection, sharing its coarse whole-database
+//! lock with every other catalog table — see the note on
+//! [`crate::store::sharding::catalog::ShardCatalog::connect`] about opening a
+//! fresh connection per call rather than sharing one. [`GraphBackend`] pulls
+//! those operations behind a trait, mirroring
+//! [`crate::store::sharding::backend::StoreBackend`] one layer up: that trait
+//! lets a shard's own symbol/edge tables live on a different engine; this one
+//! lets the *catalog's* cross-repo edge graph do the same, so a read-heavy
+//! fan-out workload (looking up edges for many symbols across many shards)
+//! can escape SQLite's locking without touching how shards themselves are
+//! stored.
+//!
+//! Shard registration (`shards.db_path`, used by `get_shard_db_path`) always
+//! lives in the catalog's own SQLite database regardless of which
+//! `GraphBackend` is selected — only `cross_repo_edges` itself moves.
+//!
+//! [`SqliteGraphBackend`] is the default and the only backend this build
+//! compiles in unless the `lmdb_backend` feature is enabled, the same
+//! opt-in-engine convention `StoreBackend`/`RocksShardBackend` use.
+//! [`LmdbGraphBackend`] stores one entry per `(direction, repo_id,
+//! qualified_name)` key holding that key's whole edge list as JSON (the same
+//! hand-rolled encoding `ShardCatalog::export_catalog` uses, rather than a
+//! typed serializer), so a read never waits behind SQLite's single-writer
+//! lock — at the cost of `delete_edges_for_repo` needing a full scan to
+//! scrub a repo's edges out of its neighbours' lists, since LMDB has no
+//! secondary index to delete by.
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection};
+
+use crate::errors::{BombeError, BombeResult};
+use crate::store::sharding::catalog::{retry_on_busy, ConnectionOptions, CrossRepoEdge};
+
+/// Cross-repo-edge operations `ShardCatalog` needs, independent of the
+/// engine storing them. Selected once, at `ShardCatalog` construction, via
+/// its `edge_backend` constructor argument.
+pub trait GraphBackend: Send + Sync {
+    /// Upsert `edges`, committing at most `chunk_size` per transaction/batch
+    /// (`None` = everything in one go, backend permitting). Returns the
+    /// count stored.
+    fn store_edges(&self, edges: &[CrossRepoEdge], chunk_size: Option<usize>) -> BombeResult<i64>;
+
+    /// Outgoing edges from `(repo_id, qualified_name)`, in the order they
+    /// were stored.
+    fn get_edges_from(
+        &self,
+        repo_id: &str,
+        qualified_name: &str,
+    ) -> BombeResult<Vec<CrossRepoEdge>>;
+
+    /// Incoming edges to `(repo_id, qualified_name)`, in the order they were
+    /// stored.
+    fn get_edges_to(&self, repo_id: &str, qualified_name: &str) -> BombeResult<Vec<CrossRepoEdge>>;
+
+    /// [`Self::get_edges_from`] for every name in `qualified_names` at once,
+    /// keyed by the requested name. The default implementation just calls
+    /// [`Self::get_edges_from`] in a loop — fine for a backend (like LMDB)
+    /// where each lookup is already a single cheap key read; a backend
+    /// whose per-call overhead is what actually needs eliminating (like
+    /// [`SqliteGraphBackend`], with its per-call connection and prepared
+    /// statement) overrides this with one batched query instead.
+    fn get_edges_from_many(
+        &self,
+        repo_id: &str,
+        qualified_names: &[String],
+    ) -> BombeResult<std::collections::HashMap<String, Vec<CrossRepoEdge>>> {
+        let mut out = std::collections::HashMap::with_capacity(qualified_names.len());
+        for name in qualified_names {
+            out.insert(name.clone(), self.get_edges_from(repo_id, name)?);
+        }
+        Ok(out)
+    }
+
+    /// Delete every edge where `repo_id` is either endpoint. Returns the
+    /// count deleted.
+    fn delete_edges_for_repo(&self, repo_id: &str) -> BombeResult<i64>;
+
+    /// The `db_path` of a registered, enabled shard. Shard registration
+    /// always lives in the catalog's own SQLite `shards` table, so every
+    /// `GraphBackend` answers this the same way regardless of where it
+    /// keeps `cross_repo_edges`.
+    fn get_shard_db_path(&self, repo_id: &str) -> BombeResult<Option<String>>;
+}
+
+// ---------------------------------------------------------------------------
+// SQLite (default)
+// ---------------------------------------------------------------------------
+
+/// Default [`GraphBackend`]: `cross_repo_edges` in the catalog's own SQLite
+/// database, same as before this trait existed.
+pub struct SqliteGraphBackend {
+    db_path: PathBuf,
+    options: ConnectionOptions,
+}
+
+impl SqliteGraphBackend {
+    pub fn open(db_path: &Path, options: ConnectionOptions) -> Self {
+        Self {
+            db_path: db_path.to_path_buf(),
+            options,
+        }
+    }
+
+    /// Open a fresh connection to `self.db_path`, same pragmas as
+    /// [`crate::store::sharding::catalog::ShardCatalog::connect`] applies.
+    fn connect(&self) -> BombeResult<Connection> {
+        let conn = Connection::open(&self.db_path)?;
+        conn.execute_batch(&format!(
+            "PRAGMA busy_timeout = {};",
+            self.options.busy_timeout_ms
+        ))?;
+        conn.execute_batch(if self.options.foreign_keys {
+            "PRAGMA foreign_keys = ON;"
+        } else {
+            "PRAGMA foreign_keys = OFF;"
+        })?;
+        Ok(conn)
+    }
+}
+
+impl GraphBackend for SqliteGraphBackend {
+    fn store_edges(&self, edges: &[CrossRepoEdge], chunk_size: Option<usize>) -> BombeResult<i64> {
+        if edges.is_empty() {
+            return Ok(0);
+        }
+        let chunk_size = chunk_size.filter(|&n| n > 0).unwrap_or(edges.len());
+        let mut total = 0i64;
+        for chunk in edges.chunks(chunk_size) {
+            total += retry_on_busy(|| {
+                let mut conn = self.connect()?;
+                let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+                {
+                    let mut stmt = tx.prepare(
+                        "INSERT OR REPLACE INTO cross_repo_edges(\
+                             source_repo_id, source_qualified_name, source_file_path, \
+                             target_repo_id, target_qualified_name, target_file_path, \
+                             relationship, confidence, provenance, updated_at\
+                         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, CURRENT_TIMESTAMP);",
+                    )?;
+                    for edge in chunk {
+                        stmt.execute(params![
+                            edge.source_repo_id,
+                            edge.source_qualified_name,
+                            edge.source_file_path,
+                            edge.target_repo_id,
+                            edge.target_qualified_name,
+                            edge.target_file_path,
+                            edge.relationship,
+                            edge.confidence,
+                            edge.provenance,
+                        ])?;
+                    }
+                }
+                tx.commit()?;
+                Ok(chunk.len() as i64)
+            })?;
+        }
+        Ok(total)
+    }
+
+    fn get_edges_from(
+        &self,
+        repo_id: &str,
+        qualified_name: &str,
+    ) -> BombeResult<Vec<CrossRepoEdge>> {
+        let conn = self.connect()?;
+        let mut stmt = conn.prepare(
+            "SELECT source_repo_id, source_qualified_name, source_file_path, \
+                    target_repo_id, target_qualified_name, target_file_path, \
+                    relationship, confidence, provenance \
+             FROM cross_repo_edges \
+             WHERE source_repo_id = ?1 AND source_qualified_name = ?2 \
+             ORDER BY id ASC;",
+        )?;
+        let rows = stmt.query_map(params![repo_id, qualified_name], row_to_edge)?;
+        rows.map(|r| r.map_err(BombeError::from)).collect()
+    }
+
+    fn get_edges_to(&self, repo_id: &str, qualified_name: &str) -> BombeResult<Vec<CrossRepoEdge>> {
+        let conn = self.connect()?;
+        let mut stmt = conn.prepare(
+            "SELECT source_repo_id, source_qualified_name, source_file_path, \
+                    target_repo_id, target_qualified_name, target_file_path, \
+                    relationship, confidence, provenance \
+             FROM cross_repo_edges \
+             WHERE target_repo_id = ?1 AND target_qualified_name = ?2 \
+             ORDER BY id ASC;",
+        )?;
+        let rows = stmt.query_map(params![repo_id, qualified_name], row_to_edge)?;
+        rows.map(|r| r.map_err(BombeError::from)).collect()
+    }
+
+    fn get_edges_from_many(
+        &self,
+        repo_id: &str,
+        qualified_names: &[String],
+    ) -> BombeResult<std::collections::HashMap<String, Vec<CrossRepoEdge>>> {
+        let mut out: std::collections::HashMap<String, Vec<CrossRepoEdge>> =
+            qualified_names
+                .iter()
+                .map(|name| (name.clone(), Vec::new()))
+                .collect();
+        if qualified_names.is_empty() {
+            return Ok(out);
+        }
+
+        let conn = self.connect()?;
+        let placeholders = (2..=qualified_names.len() + 1)
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "SELECT source_repo_id, source_qualified_name, source_file_path, \
+                    target_repo_id, target_qualified_name, target_file_path, \
+                    relationship, confidence, provenance \
+             FROM cross_repo_edges \
+             WHERE source_repo_id = ?1 AND source_qualified_name IN ({placeholders}) \
+             ORDER BY id ASC;"
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let mut params: Vec<&dyn rusqlite::ToSql> = Vec::with_capacity(qualified_names.len() + 1);
+        params.push(&repo_id);
+        for name in qualified_names {
+            params.push(name);
+        }
+        let rows = stmt.query_map(params.as_slice(), row_to_edge)?;
+        for row in rows {
+            let edge = row.map_err(BombeError::from)?;
+            out.entry(edge.source_qualified_name.clone())
+                .or_default()
+                .push(edge);
+        }
+        Ok(out)
+    }
+
+    fn delete_edges_for_repo(&self, repo_id: &str) -> BombeResult<i64> {
+        let conn = self.connect()?;
+        let deleted = conn.execute(
+            "DELETE FROM cross_repo_edges \
+             WHERE source_repo_id = ?1 OR target_repo_id = ?1;",
+            params![repo_id],
+        )?;
+        Ok(deleted as i64)
+    }
+
+    fn get_shard_db_path(&self, repo_id: &str) -> BombeResult<Option<String>> {
+        let conn = self.connect()?;
+        let result: Result<String, _> = conn.query_row(
+            "SELECT db_path FROM shards WHERE repo_id = ?1 AND enabled = 1 LIMIT 1;",
+            params![repo_id],
+            |row| row.get(0),
+        );
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(BombeError::from(e)),
+        }
+    }
+}
+
+fn row_to_edge(row: &rusqlite::Row<'_>) -> rusqlite::Result<CrossRepoEdge> {
+    Ok(CrossRepoEdge {
+        source_repo_id: row.get(0)?,
+        source_qualified_name: row.get(1)?,
+        source_file_path: row.get(2)?,
+        target_repo_id: row.get(3)?,
+        target_qualified_name: row.get(4)?,
+        target_file_path: row.get(5)?,
+        relationship: row.get(6)?,
+        confidence: row.get(7)?,
+        provenance: row.get(8)?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// LMDB
+// ---------------------------------------------------------------------------
+
+/// Embedded-KV [`GraphBackend`] for read-heavy fan-out workloads that would
+/// otherwise contend on the catalog's SQLite lock. Each key is
+/// `"{direction}\x1f{repo_id}\x1f{qualified_name}"` (`direction` one of
+/// `"from"`/`"to"`), holding that key's whole edge list JSON-encoded; a
+/// lookup is a single key read instead of an indexed table scan, and never
+/// blocks behind a writer the way SQLite's whole-table read lock can.
+#[cfg(feature = "lmdb_backend")]
+pub struct LmdbGraphBackend {
+    /// `shards` registration still lives here — see the module doc comment.
+    catalog_db_path: PathBuf,
+    options: ConnectionOptions,
+    env: lmdb::Environment,
+    db: lmdb::Database,
+}
+
+#[cfg(feature = "lmdb_backend")]
+impl LmdbGraphBackend {
+    pub fn open(
+        lmdb_dir: &Path,
+        catalog_db_path: &Path,
+        options: ConnectionOptions,
+    ) -> BombeResult<Self> {
+        std::fs::create_dir_all(lmdb_dir).map_err(BombeError::Io)?;
+        let env = lmdb::Environment::new()
+            .set_map_size(1 << 30)
+            .open(lmdb_dir)
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        let db = env
+            .open_db(None)
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        Ok(Self {
+            catalog_db_path: catalog_db_path.to_path_buf(),
+            options,
+            env,
+            db,
+        })
+    }
+
+    fn key(direction: &str, repo_id: &str, qualified_name: &str) -> Vec<u8> {
+        format!("{direction}\u{1f}{repo_id}\u{1f}{qualified_name}").into_bytes()
+    }
+
+    /// Same hand-rolled JSON shape `ShardCatalog::export_catalog` uses for
+    /// `cross_repo_edges` rows, rather than a typed serializer — this crate
+    /// doesn't derive `Serialize`/`Deserialize` anywhere.
+    fn encode(edges: &[CrossRepoEdge]) -> Vec<u8> {
+        let value = serde_json::Value::Array(
+            edges
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "source_repo_id": e.source_repo_id,
+                        "source_qualified_name": e.source_qualified_name,
+                        "source_file_path": e.source_file_path,
+                        "target_repo_id": e.target_repo_id,
+                        "target_qualified_name": e.target_qualified_name,
+                        "target_file_path": e.target_file_path,
+                        "relationship": e.relationship,
+                        "confidence": e.confidence,
+                        "provenance": e.provenance,
+                    })
+                })
+                .collect(),
+        );
+        value.to_string().into_bytes()
+    }
+
+    /// Malformed or unreadable bytes decode to an empty list rather than
+    /// erroring, the same tolerant-decode stance
+    /// [`crate::store::sharding::catalog::import_table`] takes on a
+    /// malformed export.
+    fn decode(bytes: &[u8]) -> Vec<CrossRepoEdge> {
+        let Ok(text) = std::str::from_utf8(bytes) else {
+            return Vec::new();
+        };
+        let Ok(serde_json::Value::Array(items)) = serde_json::from_str(text) else {
+            return Vec::new();
+        };
+        items
+            .iter()
+            .filter_map(|v| {
+                Some(CrossRepoEdge {
+                    source_repo_id: v.get("source_repo_id")?.as_str()?.to_string(),
+                    source_qualified_name: v.get("source_qualified_name")?.as_str()?.to_string(),
+                    source_file_path: v.get("source_file_path")?.as_str()?.to_string(),
+                    target_repo_id: v.get("target_repo_id")?.as_str()?.to_string(),
+                    target_qualified_name: v.get("target_qualified_name")?.as_str()?.to_string(),
+                    target_file_path: v.get("target_file_path")?.as_str()?.to_string(),
+                    relationship: v.get("relationship")?.as_str()?.to_string(),
+                    confidence: v.get("confidence").and_then(|c| c.as_f64()).unwrap_or(1.0),
+                    provenance: v
+                        .get("provenance")
+                        .and_then(|p| p.as_str())
+                        .unwrap_or("import_resolution")
+                        .to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Two edges are "the same row" by the same column set
+    /// `cross_repo_edges`'s `UNIQUE` constraint covers, so re-storing an
+    /// edge replaces its prior value instead of appending a duplicate.
+    fn same_edge(a: &CrossRepoEdge, b: &CrossRepoEdge) -> bool {
+        a.source_repo_id == b.source_repo_id
+            && a.source_qualified_name == b.source_qualified_name
+            && a.source_file_path == b.source_file_path
+            && a.target_repo_id == b.target_repo_id
+            && a.target_qualified_name == b.target_qualified_name
+            && a.target_file_path == b.target_file_path
+            && a.relationship == b.relationship
+    }
+
+    fn append(&self, key: &[u8], edge: &CrossRepoEdge) -> BombeResult<()> {
+        let mut txn = self
+            .env
+            .begin_rw_txn()
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        let mut existing = match txn.get(self.db, &key) {
+            Ok(bytes) => Self::decode(bytes),
+            Err(lmdb::Error::NotFound) => Vec::new(),
+            Err(e) => return Err(BombeError::Database(e.to_string())),
+        };
+        match existing.iter_mut().find(|e| Self::same_edge(e, edge)) {
+            Some(slot) => *slot = edge.clone(),
+            None => existing.push(edge.clone()),
+        }
+        let encoded = Self::encode(&existing);
+        txn.put(self.db, &key, &encoded, lmdb::WriteFlags::empty())
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        txn.commit()
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    fn get(&self, key: &[u8]) -> BombeResult<Vec<CrossRepoEdge>> {
+        let txn = self
+            .env
+            .begin_ro_txn()
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        match txn.get(self.db, &key) {
+            Ok(bytes) => Ok(Self::decode(bytes)),
+            Err(lmdb::Error::NotFound) => Ok(Vec::new()),
+            Err(e) => Err(BombeError::Database(e.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "lmdb_backend")]
+impl GraphBackend for LmdbGraphBackend {
+    fn store_edges(&self, edges: &[CrossRepoEdge], _chunk_size: Option<usize>) -> BombeResult<i64> {
+        // `chunk_size` has no analogue here: each edge's two key writes are
+        // already their own small LMDB transaction, unlike SQLite's
+        // BEGIN/COMMIT batching over many rows.
+        for edge in edges {
+            let from_key = Self::key("from", &edge.source_repo_id, &edge.source_qualified_name);
+            self.append(&from_key, edge)?;
+            let to_key = Self::key("to", &edge.target_repo_id, &edge.target_qualified_name);
+            self.append(&to_key, edge)?;
+        }
+        Ok(edges.len() as i64)
+    }
+
+    fn get_edges_from(
+        &self,
+        repo_id: &str,
+        qualified_name: &str,
+    ) -> BombeResult<Vec<CrossRepoEdge>> {
+        self.get(&Self::key("from", repo_id, qualified_name))
+    }
+
+    fn get_edges_to(&self, repo_id: &str, qualified_name: &str) -> BombeResult<Vec<CrossRepoEdge>> {
+        self.get(&Self::key("to", repo_id, qualified_name))
+    }
+
+    /// Unlike SQLite's indexed `DELETE ... WHERE source_repo_id = ?1 OR
+    /// target_repo_id = ?1`, LMDB has no secondary index on `repo_id`: this
+    /// drops `repo_id`'s own `from`/`to` keys outright, then scans every
+    /// other key's edge list to scrub any edge that still names `repo_id` on
+    /// either side (e.g. an edge from another repo filed under that repo's
+    /// own `from` key, but targeting `repo_id`). O(total edges) rather than
+    /// O(repo_id's edges) — acceptable since unregistering a shard is rare,
+    /// and this backend is chosen to make reads, not deletes, fast.
+    fn delete_edges_for_repo(&self, repo_id: &str) -> BombeResult<i64> {
+        let mut txn = self
+            .env
+            .begin_rw_txn()
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        let mut deleted = 0i64;
+        let mut removed_keys = Vec::new();
+        let mut rewritten: Vec<(Vec<u8>, Vec<CrossRepoEdge>)> = Vec::new();
+        let own_from = format!("from\u{1f}{repo_id}\u{1f}").into_bytes();
+        let own_to = format!("to\u{1f}{repo_id}\u{1f}").into_bytes();
+        {
+            let mut cursor = txn
+                .open_ro_cursor(self.db)
+                .map_err(|e| BombeError::Database(e.to_string()))?;
+            for entry in cursor.iter() {
+                let (key, value) = entry.map_err(|e| BombeError::Database(e.to_string()))?;
+                let edges = Self::decode(value);
+                if key.starts_with(own_from.as_slice()) || key.starts_with(own_to.as_slice()) {
+                    deleted += edges.len() as i64;
+                    removed_keys.push(key.to_vec());
+                    continue;
+                }
+                let kept: Vec<CrossRepoEdge> = edges
+                    .iter()
+                    .filter(|e| e.source_repo_id != repo_id && e.target_repo_id != repo_id)
+                    .cloned()
+                    .collect();
+                if kept.len() != edges.len() {
+                    deleted += (edges.len() - kept.len()) as i64;
+                    rewritten.push((key.to_vec(), kept));
+                }
+            }
+        }
+        for key in removed_keys {
+            txn.del(self.db, &key, None)
+                .map_err(|e| BombeError::Database(e.to_string()))?;
+        }
+        for (key, kept) in rewritten {
+            if kept.is_empty() {
+                txn.del(self.db, &key, None)
+                    .map_err(|e| BombeError::Database(e.to_string()))?;
+            } else {
+                let encoded = Self::encode(&kept);
+                txn.put(self.db, &key, &encoded, lmdb::WriteFlags::empty())
+                    .map_err(|e| BombeError::Database(e.to_string()))?;
+            }
+        }
+        txn.commit()
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+        Ok(deleted)
+    }
+
+    fn get_shard_db_path(&self, repo_id: &str) -> BombeResult<Option<String>> {
+        // `shards` registration isn't part of the edge graph — always read
+        // it from the catalog's own SQLite database regardless of backend.
+        let conn = Connection::open(&self.catalog_db_path)?;
+        conn.execute_batch(&format!(
+            "PRAGMA busy_timeout = {};",
+            self.options.busy_timeout_ms
+        ))?;
+        let result: Result<String, _> = conn.query_row(
+            "SELECT db_path FROM shards WHERE repo_id = ?1 AND enabled = 1 LIMIT 1;",
+            params![repo_id],
+            |row| row.get(0),
+        );
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(BombeError::from(e)),
+        }
+    }
+}
+
+/// Directory an `"lmdb"` edge backend's environment lives in, alongside the
+/// catalog's own SQLite file: `<catalog_db_path>.edges.lmdb`.
+#[cfg(feature = "lmdb_backend")]
+fn lmdb_env_path(catalog_db_path: &Path) -> PathBuf {
+    let mut name = catalog_db_path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push(".edges.lmdb");
+    catalog_db_path.with_file_name(name)
+}
+
+/// Construct the [`GraphBackend`] named by a `ShardCatalog`'s `edge_backend`
+/// constructor argument. `"sqlite"` (the default) is always available;
+/// `"lmdb"` requires this crate to be built with the `lmdb_backend` feature,
+/// and is otherwise rejected with a clear error instead of silently falling
+/// back to SQLite.
+pub fn open_graph_backend(
+    backend: &str,
+    catalog_db_path: &Path,
+    options: ConnectionOptions,
+) -> BombeResult<Box<dyn GraphBackend>> {
+    match backend {
+        "sqlite" => Ok(Box::new(SqliteGraphBackend::open(catalog_db_path, options))),
+        #[cfg(feature = "lmdb_backend")]
+        "lmdb" => Ok(Box::new(LmdbGraphBackend::open(
+            &lmdb_env_path(catalog_db_path),
+            catalog_db_path,
+            options,
+        )?)),
+        #[cfg(not(feature = "lmdb_backend"))]
+        "lmdb" => Err(BombeError::Database(
+            "lmdb graph backend requires the lmdb_backend feature".into(),
+        )),
+        other => Err(BombeError::Database(format!(
+            "unknown graph backend: {other}"
+        ))),
+    }
+}
@@ -0,0 +1,456 @@
+//! Merkle-Patricia-trie digests over a shard's symbol set.
+//!
+//! Each shard's [`crate::models::ShardInfo::merkle_root`] content-addresses
+//! its full `GlobalSymbolURI -> content_hash` mapping: two shards with equal
+//! roots are known, by construction, to hold identical symbol sets without
+//! comparing a single symbol. [`diff_shards`] descends into two *different*
+//! roots, pruning subtrees whose hashes already match, so a federated
+//! re-sync transfers only the `GlobalSymbolURI`s that actually changed
+//! instead of shipping the whole shard.
+//!
+//! Trie keys are `GlobalSymbolURI` strings, nibble-decomposed (two nibbles
+//! per byte, high nibble first) the way Ethereum's Merkle-Patricia trie
+//! does; leaf values are the caller-supplied content hash of the symbol's
+//! definition/signature (e.g. [`crate::store::artifact_checksum`]'s
+//! checksum, or any other stable digest of the symbol's signature/body).
+
+use std::collections::{BTreeSet, HashMap};
+
+use pyo3::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// One nibble (half a byte), 0..=15.
+type Nibble = u8;
+
+fn to_nibbles(bytes: &[u8]) -> Vec<Nibble> {
+    let mut nibbles = Vec::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        nibbles.push(b >> 4);
+        nibbles.push(b & 0x0f);
+    }
+    nibbles
+}
+
+fn nibbles_to_bytes(nibbles: &[Nibble]) -> Vec<u8> {
+    nibbles
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair.get(1).copied().unwrap_or(0))
+        .collect()
+}
+
+fn nibbles_to_hex(nibbles: &[Nibble]) -> String {
+    nibbles.iter().map(|n| format!("{n:x}")).collect()
+}
+
+fn hex_digest(input: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(input);
+    format!("{:x}", hasher.finalize())
+}
+
+fn common_prefix_len(a: &[Nibble], b: &[Nibble]) -> usize {
+    a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count()
+}
+
+fn new_branch() -> [Option<Box<TrieNode>>; 16] {
+    std::array::from_fn(|_| None)
+}
+
+/// One node in the trie. Hashing follows the request's node-hashing rules:
+/// a leaf hashes `(remaining_key, value_hash)`, a branch hashes the
+/// concatenation of its 16 child hashes plus any value, and an extension
+/// hashes `(shared_nibbles, child_hash)` -- see [`TrieNode::hash`].
+enum TrieNode {
+    Leaf {
+        remaining: Vec<Nibble>,
+        value_hash: String,
+    },
+    Extension {
+        shared: Vec<Nibble>,
+        child: Box<TrieNode>,
+    },
+    Branch {
+        children: [Option<Box<TrieNode>>; 16],
+        value: Option<String>,
+    },
+}
+
+impl TrieNode {
+    fn hash(&self) -> String {
+        match self {
+            TrieNode::Leaf {
+                remaining,
+                value_hash,
+            } => hex_digest(format!("leaf:{}:{value_hash}", nibbles_to_hex(remaining)).as_bytes()),
+            TrieNode::Extension { shared, child } => {
+                hex_digest(format!("ext:{}:{}", nibbles_to_hex(shared), child.hash()).as_bytes())
+            }
+            TrieNode::Branch { children, value } => {
+                let mut buf = String::from("branch:");
+                for child in children.iter() {
+                    buf.push_str(&child.as_ref().map(|c| c.hash()).unwrap_or_default());
+                    buf.push(':');
+                }
+                buf.push_str(value.as_deref().unwrap_or(""));
+                hex_digest(buf.as_bytes())
+            }
+        }
+    }
+}
+
+/// Wrap `child` in an `Extension` over `shared` nibbles, or return `child`
+/// unwrapped if `shared` is empty (an extension with zero shared nibbles is
+/// just its child).
+fn with_extension(shared: Vec<Nibble>, child: Box<TrieNode>) -> Box<TrieNode> {
+    if shared.is_empty() {
+        child
+    } else {
+        Box::new(TrieNode::Extension { shared, child })
+    }
+}
+
+fn insert(node: Option<Box<TrieNode>>, key: &[Nibble], value_hash: String) -> Box<TrieNode> {
+    match node {
+        None => Box::new(TrieNode::Leaf {
+            remaining: key.to_vec(),
+            value_hash,
+        }),
+        Some(n) => match *n {
+            TrieNode::Leaf {
+                remaining,
+                value_hash: old_value,
+            } => {
+                let common = common_prefix_len(&remaining, key);
+                if common == remaining.len() && common == key.len() {
+                    return Box::new(TrieNode::Leaf {
+                        remaining,
+                        value_hash,
+                    });
+                }
+                let mut children = new_branch();
+                let mut branch_value = None;
+                if common == remaining.len() {
+                    branch_value = Some(old_value);
+                } else {
+                    let idx = remaining[common] as usize;
+                    children[idx] = Some(Box::new(TrieNode::Leaf {
+                        remaining: remaining[common + 1..].to_vec(),
+                        value_hash: old_value,
+                    }));
+                }
+                if common == key.len() {
+                    branch_value = Some(value_hash);
+                } else {
+                    let idx = key[common] as usize;
+                    children[idx] = Some(Box::new(TrieNode::Leaf {
+                        remaining: key[common + 1..].to_vec(),
+                        value_hash,
+                    }));
+                }
+                let branch = Box::new(TrieNode::Branch {
+                    children,
+                    value: branch_value,
+                });
+                with_extension(remaining[..common].to_vec(), branch)
+            }
+            TrieNode::Extension { shared, child } => {
+                let common = common_prefix_len(&shared, key);
+                if common == shared.len() {
+                    let new_child = insert(Some(child), &key[common..], value_hash);
+                    return with_extension(shared, new_child);
+                }
+                let mut children = new_branch();
+                let ext_idx = shared[common] as usize;
+                children[ext_idx] = Some(with_extension(shared[common + 1..].to_vec(), child));
+                let mut branch_value = None;
+                if common == key.len() {
+                    branch_value = Some(value_hash);
+                } else {
+                    let idx = key[common] as usize;
+                    children[idx] = Some(Box::new(TrieNode::Leaf {
+                        remaining: key[common + 1..].to_vec(),
+                        value_hash,
+                    }));
+                }
+                let branch = Box::new(TrieNode::Branch {
+                    children,
+                    value: branch_value,
+                });
+                with_extension(shared[..common].to_vec(), branch)
+            }
+            TrieNode::Branch {
+                mut children,
+                value,
+            } => {
+                if key.is_empty() {
+                    return Box::new(TrieNode::Branch {
+                        children,
+                        value: Some(value_hash),
+                    });
+                }
+                let idx = key[0] as usize;
+                let existing_child = children[idx].take();
+                children[idx] = Some(insert(existing_child, &key[1..], value_hash));
+                Box::new(TrieNode::Branch { children, value })
+            }
+        },
+    }
+}
+
+fn collect_leaves(node: &TrieNode, prefix: &[Nibble], out: &mut HashMap<Vec<Nibble>, String>) {
+    match node {
+        TrieNode::Leaf {
+            remaining,
+            value_hash,
+        } => {
+            let mut key = prefix.to_vec();
+            key.extend(remaining.iter());
+            out.insert(key, value_hash.clone());
+        }
+        TrieNode::Extension { shared, child } => {
+            let mut next_prefix = prefix.to_vec();
+            next_prefix.extend(shared.iter());
+            collect_leaves(child, &next_prefix, out);
+        }
+        TrieNode::Branch { children, value } => {
+            if let Some(v) = value {
+                out.insert(prefix.to_vec(), v.clone());
+            }
+            for (i, child) in children.iter().enumerate() {
+                if let Some(c) = child {
+                    let mut next_prefix = prefix.to_vec();
+                    next_prefix.push(i as Nibble);
+                    collect_leaves(c, &next_prefix, out);
+                }
+            }
+        }
+    }
+}
+
+fn collect_keys(node: &TrieNode, prefix: &[Nibble], changed: &mut BTreeSet<Vec<Nibble>>) {
+    let mut leaves = HashMap::new();
+    collect_leaves(node, prefix, &mut leaves);
+    changed.extend(leaves.into_keys());
+}
+
+fn diff_nodes(
+    local: Option<&TrieNode>,
+    remote: Option<&TrieNode>,
+    prefix: &[Nibble],
+    changed: &mut BTreeSet<Vec<Nibble>>,
+) {
+    let (l, r) = match (local, remote) {
+        (None, None) => return,
+        (Some(l), None) => {
+            collect_keys(l, prefix, changed);
+            return;
+        }
+        (None, Some(r)) => {
+            collect_keys(r, prefix, changed);
+            return;
+        }
+        (Some(l), Some(r)) => (l, r),
+    };
+    if l.hash() == r.hash() {
+        // Subtree hashes match: prune without visiting a single leaf.
+        return;
+    }
+    match (l, r) {
+        (
+            TrieNode::Branch {
+                children: lc,
+                value: lv,
+            },
+            TrieNode::Branch {
+                children: rc,
+                value: rv,
+            },
+        ) => {
+            if lv != rv {
+                changed.insert(prefix.to_vec());
+            }
+            for i in 0..16 {
+                let mut child_prefix = prefix.to_vec();
+                child_prefix.push(i as Nibble);
+                diff_nodes(lc[i].as_deref(), rc[i].as_deref(), &child_prefix, changed);
+            }
+        }
+        (
+            TrieNode::Extension {
+                shared: ls,
+                child: lch,
+            },
+            TrieNode::Extension {
+                shared: rs,
+                child: rch,
+            },
+        ) if ls == rs => {
+            let mut child_prefix = prefix.to_vec();
+            child_prefix.extend(ls.iter());
+            diff_nodes(Some(lch), Some(rch), &child_prefix, changed);
+        }
+        _ => {
+            // Shapes don't line up at this point (e.g. a Leaf vs Branch, or
+            // two Extensions with different shared prefixes) -- this only
+            // happens where the two shards' key sets actually diverge, so
+            // falling back to a direct leaf-by-leaf comparison of both
+            // subtrees costs no more than the number of leaves that changed.
+            let mut local_leaves = HashMap::new();
+            let mut remote_leaves = HashMap::new();
+            collect_leaves(l, prefix, &mut local_leaves);
+            collect_leaves(r, prefix, &mut remote_leaves);
+            for (key, value) in &local_leaves {
+                if remote_leaves.get(key) != Some(value) {
+                    changed.insert(key.clone());
+                }
+            }
+            for key in remote_leaves.keys() {
+                if !local_leaves.contains_key(key) {
+                    changed.insert(key.clone());
+                }
+            }
+        }
+    }
+}
+
+/// A Merkle-Patricia trie keyed by `GlobalSymbolURI` strings, built once
+/// from a shard's full symbol set via [`MerkleTrie::insert`]. Its
+/// [`MerkleTrie::root_hash`] is what gets stored in
+/// [`crate::models::ShardInfo::merkle_root`].
+#[derive(Default)]
+pub struct MerkleTrie {
+    root: Option<Box<TrieNode>>,
+}
+
+impl MerkleTrie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: &str, value_hash: String) {
+        let nibbles = to_nibbles(key.as_bytes());
+        self.root = Some(insert(self.root.take(), &nibbles, value_hash));
+    }
+
+    /// The trie's content-addressed root hash, or a fixed sentinel for an
+    /// empty trie (so an empty shard has a stable, comparable root too).
+    pub fn root_hash(&self) -> String {
+        match &self.root {
+            Some(node) => node.hash(),
+            None => hex_digest(b"empty"),
+        }
+    }
+}
+
+/// Build a [`MerkleTrie`] from `(GlobalSymbolURI, content_hash)` pairs and
+/// return its root hash -- the value to store as a shard's
+/// [`crate::models::ShardInfo::merkle_root`].
+pub fn compute_merkle_root(entries: &[(String, String)]) -> String {
+    let mut trie = MerkleTrie::new();
+    for (key, value_hash) in entries {
+        trie.insert(key, value_hash.clone());
+    }
+    trie.root_hash()
+}
+
+/// Diff two Merkle-Patricia tries, returning the `GlobalSymbolURI`s whose
+/// leaf (content hash) differs or is present on only one side. Subtrees
+/// whose node hash matches on both sides are pruned without visiting their
+/// leaves, which is what keeps a re-sync between two mostly-identical
+/// shards proportional to the number of changed symbols rather than the
+/// shard's full size.
+pub fn diff_shards(local: &MerkleTrie, remote: &MerkleTrie) -> Vec<String> {
+    let mut changed = BTreeSet::new();
+    diff_nodes(
+        local.root.as_deref(),
+        remote.root.as_deref(),
+        &[],
+        &mut changed,
+    );
+    changed
+        .into_iter()
+        .filter_map(|nibbles| String::from_utf8(nibbles_to_bytes(&nibbles)).ok())
+        .collect()
+}
+
+/// `#[pyfunction]` front door for [`compute_merkle_root`]: build a trie from
+/// `(GlobalSymbolURI, content_hash)` pairs and return its root hash.
+#[pyfunction]
+pub fn compute_shard_merkle_root(entries: Vec<(String, String)>) -> String {
+    compute_merkle_root(&entries)
+}
+
+/// `#[pyfunction]` front door for [`diff_shards`]: build a trie from each
+/// side's `(GlobalSymbolURI, content_hash)` entries and return the
+/// `GlobalSymbolURI`s that differ between them.
+#[pyfunction]
+pub fn diff_shard_entries(
+    local_entries: Vec<(String, String)>,
+    remote_entries: Vec<(String, String)>,
+) -> Vec<String> {
+    let mut local = MerkleTrie::new();
+    for (key, value_hash) in local_entries {
+        local.insert(&key, value_hash);
+    }
+    let mut remote = MerkleTrie::new();
+    for (key, value_hash) in remote_entries {
+        remote.insert(&key, value_hash);
+    }
+    diff_shards(&local, &remote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie_of(entries: &[(&str, &str)]) -> MerkleTrie {
+        let mut trie = MerkleTrie::new();
+        for (key, value) in entries {
+            trie.insert(key, value.to_string());
+        }
+        trie
+    }
+
+    #[test]
+    fn test_identical_entries_produce_equal_roots() {
+        let a = trie_of(&[("bombe://r/foo#f.py", "h1"), ("bombe://r/bar#b.py", "h2")]);
+        let b = trie_of(&[("bombe://r/bar#b.py", "h2"), ("bombe://r/foo#f.py", "h1")]);
+        assert_eq!(a.root_hash(), b.root_hash());
+    }
+
+    #[test]
+    fn test_changing_one_value_changes_the_root() {
+        let a = trie_of(&[("bombe://r/foo#f.py", "h1")]);
+        let b = trie_of(&[("bombe://r/foo#f.py", "h2")]);
+        assert_ne!(a.root_hash(), b.root_hash());
+    }
+
+    #[test]
+    fn test_diff_shards_finds_only_the_changed_key() {
+        let local = trie_of(&[
+            ("bombe://r/foo#f.py", "h1"),
+            ("bombe://r/bar#b.py", "h2"),
+            ("bombe://r/baz#z.py", "h3"),
+        ]);
+        let remote = trie_of(&[
+            ("bombe://r/foo#f.py", "h1-changed"),
+            ("bombe://r/bar#b.py", "h2"),
+            ("bombe://r/baz#z.py", "h3"),
+        ]);
+        assert_eq!(diff_shards(&local, &remote), vec!["bombe://r/foo#f.py"]);
+    }
+
+    #[test]
+    fn test_diff_shards_finds_added_and_removed_keys() {
+        let local = trie_of(&[("bombe://r/foo#f.py", "h1")]);
+        let remote = trie_of(&[("bombe://r/foo#f.py", "h1"), ("bombe://r/bar#b.py", "h2")]);
+        assert_eq!(diff_shards(&local, &remote), vec!["bombe://r/bar#b.py"]);
+    }
+
+    #[test]
+    fn test_diff_shards_is_empty_for_identical_tries() {
+        let local = trie_of(&[("bombe://r/foo#f.py", "h1"), ("bombe://r/bar#b.py", "h2")]);
+        let remote = trie_of(&[("bombe://r/foo#f.py", "h1"), ("bombe://r/bar#b.py", "h2")]);
+        assert!(diff_shards(&local, &remote).is_empty());
+    }
+}
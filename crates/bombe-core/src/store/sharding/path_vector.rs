@@ -0,0 +1,335 @@
+//! BGP-style path-vector routing over `GlobalSymbolURI` namespace prefixes.
+//!
+//! [`crate::query::federated::semantic::federated_semantic_search`] and
+//! [`crate::query::federated::triples::federated_graph_query`] both
+//! broadcast to every enabled shard in a `ShardGroupConfig`, which is fine
+//! for a handful of shards but doesn't scale to many. This module gives
+//! each shard a [`PathVectorTable`]: it learns, from advertisements exchanged
+//! with peers, which namespace prefix (typically a `repo_id`) is reachable
+//! via which neighbor and how many hops away, the same way BGP routers learn
+//! AS-path reachability instead of flooding every packet to every AS.
+//! [`route_shards_for_group`] is what a query router calls per lookup to
+//! pick the shard(s) that can actually answer it.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use pyo3::prelude::*;
+
+use crate::models::ShardGroupConfig;
+
+/// What a shard knows about reaching a namespace prefix: `next_hop` is the
+/// neighbor that advertised it (the shard to forward lookups to), and
+/// `path` is the hop chain the advertisement carried when it arrived here
+/// (nearest hop first, origin last) -- its length is the route's cost, used
+/// to pick the shortest of several candidate routes.
+#[derive(Clone, Debug)]
+struct Route {
+    next_hop: String,
+    path: Vec<String>,
+}
+
+/// Learned `GlobalSymbolURI` namespace-prefix reachability for one shard.
+/// Feed it advertisements received from peers via
+/// [`PathVectorTable::receive_advertisement`]; consult it via
+/// [`PathVectorTable::route_for`] or [`route_shards_for_group`].
+#[pyclass]
+pub struct PathVectorTable {
+    local_shard_id: String,
+    routes: Mutex<HashMap<String, Route>>,
+}
+
+#[pymethods]
+impl PathVectorTable {
+    #[new]
+    fn new(local_shard_id: String) -> Self {
+        Self {
+            local_shard_id,
+            routes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Process one advertisement (or withdrawal) received from a peer for
+    /// `namespace_prefix`, originated by `origin_shard_id` and having
+    /// already traversed `path` (nearest hop first, origin last).
+    ///
+    /// Returns the advertisement to re-advertise to this shard's *other*
+    /// peers (with this shard's ID prepended to `path`), or `None` if
+    /// `path` already contains this shard's ID -- the advertisement has
+    /// looped back and dies here instead of being re-broadcast.
+    ///
+    /// A non-withdrawal only replaces this shard's current route for
+    /// `namespace_prefix` if the new path is shorter than the one already
+    /// known, mirroring BGP's preference for the shortest AS-path. A
+    /// withdrawal removes the route only if it matches the one being
+    /// withdrawn (a stale withdrawal for a route this shard has since
+    /// replaced with a better one is ignored).
+    #[pyo3(signature = (namespace_prefix, origin_shard_id, path, withdrawn=false))]
+    fn receive_advertisement(
+        &self,
+        namespace_prefix: String,
+        origin_shard_id: String,
+        path: Vec<String>,
+        withdrawn: bool,
+    ) -> Option<(String, String, Vec<String>, bool)> {
+        if path.iter().any(|hop| hop == &self.local_shard_id) {
+            return None;
+        }
+
+        let mut routes = self.routes.lock();
+        if withdrawn {
+            if routes
+                .get(&namespace_prefix)
+                .is_some_and(|existing| existing.path == path)
+            {
+                routes.remove(&namespace_prefix);
+            }
+        } else {
+            let next_hop = path
+                .first()
+                .cloned()
+                .unwrap_or_else(|| origin_shard_id.clone());
+            let candidate = Route {
+                next_hop,
+                path: path.clone(),
+            };
+            let is_better = routes
+                .get(&namespace_prefix)
+                .is_none_or(|existing| candidate.path.len() < existing.path.len());
+            if is_better {
+                routes.insert(namespace_prefix.clone(), candidate);
+            }
+        }
+        drop(routes);
+
+        let mut forwarded_path = path;
+        forwarded_path.insert(0, self.local_shard_id.clone());
+        Some((namespace_prefix, origin_shard_id, forwarded_path, withdrawn))
+    }
+
+    /// The `(shard_id, path)` that should answer a lookup for `uri`: the
+    /// route whose namespace prefix is the longest match for `uri`,
+    /// breaking ties by shortest path. `None` if no advertised namespace
+    /// covers `uri` -- the caller should fall back to broadcasting.
+    fn route_for(&self, uri: &str) -> Option<(String, Vec<String>)> {
+        let routes = self.routes.lock();
+        routes
+            .iter()
+            .filter(|(prefix, _)| uri.starts_with(prefix.as_str()))
+            .min_by_key(|(prefix, route)| (std::cmp::Reverse(prefix.len()), route.path.len()))
+            .map(|(_, route)| (route.next_hop.clone(), route.path.clone()))
+    }
+
+    fn known_namespaces(&self) -> Vec<String> {
+        self.routes.lock().keys().cloned().collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PathVectorTable(local_shard_id={:?}, known_namespaces={})",
+            self.local_shard_id,
+            self.routes.lock().len(),
+        )
+    }
+}
+
+/// Decide which shards in `group` should be queried for `uri`, consulting
+/// `table`'s learned routes instead of broadcasting to every enabled shard.
+/// Falls back to every enabled shard (with an empty path, signalling
+/// "broadcast") if no advertisement covers `uri`'s namespace, or if the
+/// learned route names a shard that isn't in (or is disabled in) `group`.
+pub fn route_shards_for_group(
+    table: &PathVectorTable,
+    group: &ShardGroupConfig,
+    uri: &str,
+) -> (Vec<String>, Vec<String>) {
+    if let Some((shard_id, path)) = table.route_for(uri) {
+        if group
+            .shards
+            .iter()
+            .any(|shard| shard.enabled && shard.repo_id == shard_id)
+        {
+            return (vec![shard_id], path);
+        }
+    }
+    let broadcast = group
+        .shards
+        .iter()
+        .filter(|shard| shard.enabled)
+        .map(|shard| shard.repo_id.clone())
+        .collect();
+    (broadcast, Vec::new())
+}
+
+/// `#[pyfunction]` front door for [`route_shards_for_group`].
+#[pyfunction]
+pub fn route_lookup(
+    table: &PathVectorTable,
+    group: &ShardGroupConfig,
+    uri: &str,
+) -> (Vec<String>, Vec<String>) {
+    route_shards_for_group(table, group, uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ShardInfo;
+
+    fn shard(repo_id: &str, enabled: bool) -> ShardInfo {
+        ShardInfo {
+            repo_id: repo_id.to_string(),
+            repo_path: format!("/repos/{repo_id}"),
+            db_path: format!("/repos/{repo_id}/.bombe/index.db"),
+            enabled,
+            last_indexed_at: None,
+            symbol_count: 0,
+            edge_count: 0,
+            merkle_root: None,
+        }
+    }
+
+    fn group(shards: Vec<ShardInfo>) -> ShardGroupConfig {
+        ShardGroupConfig {
+            name: "group".to_string(),
+            catalog_db_path: "/catalog.db".to_string(),
+            shards,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_receive_advertisement_prepends_self_and_forwards() {
+        let table = PathVectorTable::new("b".to_string());
+        let forwarded = table
+            .receive_advertisement(
+                "bombe://a/".to_string(),
+                "a".to_string(),
+                vec!["a".to_string()],
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            forwarded,
+            (
+                "bombe://a/".to_string(),
+                "a".to_string(),
+                vec!["b".to_string(), "a".to_string()],
+                false,
+            )
+        );
+    }
+
+    #[test]
+    fn test_receive_advertisement_rejects_loop() {
+        let table = PathVectorTable::new("a".to_string());
+        let forwarded = table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["c".to_string(), "b".to_string(), "a".to_string()],
+            false,
+        );
+        assert!(forwarded.is_none());
+    }
+
+    #[test]
+    fn test_shorter_path_replaces_longer_known_route() {
+        let table = PathVectorTable::new("b".to_string());
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["x".to_string(), "y".to_string(), "a".to_string()],
+            false,
+        );
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["a".to_string()],
+            false,
+        );
+        let (shard_id, path) = table.route_for("bombe://a/foo#f.py").unwrap();
+        assert_eq!(shard_id, "a");
+        assert_eq!(path, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_longer_path_does_not_replace_shorter_known_route() {
+        let table = PathVectorTable::new("b".to_string());
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["a".to_string()],
+            false,
+        );
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["x".to_string(), "y".to_string(), "a".to_string()],
+            false,
+        );
+        let (_, path) = table.route_for("bombe://a/foo#f.py").unwrap();
+        assert_eq!(path, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_withdrawal_removes_matching_route() {
+        let table = PathVectorTable::new("b".to_string());
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["a".to_string()],
+            false,
+        );
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["a".to_string()],
+            true,
+        );
+        assert!(table.route_for("bombe://a/foo#f.py").is_none());
+    }
+
+    #[test]
+    fn test_route_for_prefers_longest_matching_namespace() {
+        let table = PathVectorTable::new("b".to_string());
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["a".to_string()],
+            false,
+        );
+        table.receive_advertisement(
+            "bombe://a/sub/".to_string(),
+            "sub".to_string(),
+            vec!["sub".to_string()],
+            false,
+        );
+        let (shard_id, _) = table.route_for("bombe://a/sub/foo#f.py").unwrap();
+        assert_eq!(shard_id, "sub");
+    }
+
+    #[test]
+    fn test_route_shards_for_group_uses_learned_route_when_target_enabled() {
+        let table = PathVectorTable::new("b".to_string());
+        table.receive_advertisement(
+            "bombe://a/".to_string(),
+            "a".to_string(),
+            vec!["a".to_string()],
+            false,
+        );
+        let g = group(vec![shard("a", true), shard("c", true)]);
+        let (shards, path) = route_shards_for_group(&table, &g, "bombe://a/foo#f.py");
+        assert_eq!(shards, vec!["a".to_string()]);
+        assert_eq!(path, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_route_shards_for_group_falls_back_to_broadcast_without_route() {
+        let table = PathVectorTable::new("b".to_string());
+        let g = group(vec![shard("a", true), shard("c", true), shard("d", false)]);
+        let (mut shards, path) = route_shards_for_group(&table, &g, "bombe://unknown/foo#f.py");
+        shards.sort();
+        assert_eq!(shards, vec!["a".to_string(), "c".to_string()]);
+        assert!(path.is_empty());
+    }
+}
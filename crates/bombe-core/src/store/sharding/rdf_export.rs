@@ -0,0 +1,175 @@
+//! Export the shard catalog's cross-repo edge graph as RDF, and run SPARQL
+//! queries over it.
+//!
+//! The edges [`crate::store::sharding::resolver::resolve_cross_repo_imports`]
+//! produces are already effectively RDF triples: `source_uri`/`target_uri`
+//! (the same `bombe://repo_id/qualified_name#file_path` IRIs used for catalog
+//! dedup) and `relationship` as a predicate. This module reconstructs those
+//! IRIs from the persisted [`CrossRepoEdge`] rows, maps `relationship` to a
+//! predicate under the `bombe:` namespace, and loads the result into an
+//! embedded oxigraph [`Store`] so it can be serialized (N-Triples/Turtle) or
+//! queried with SPARQL. `confidence`/`provenance` are attached as two extra
+//! triples on the source IRI rather than full statement reification, since no
+//! consumer here needs to quote the statement itself -- just look up how (and
+//! how confidently) it was derived.
+
+use oxigraph::io::RdfFormat;
+use oxigraph::model::{GraphNameRef, Literal, NamedNode, Quad};
+use oxigraph::sparql::QueryResults;
+use oxigraph::store::Store;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::errors::{BombeError, BombeResult};
+use crate::store::sharding::catalog::{CrossRepoEdge, ShardCatalog};
+
+/// Namespace IRI for bombe's RDF vocabulary (relationship predicates, plus
+/// the `confidence`/`provenance` properties attached to each edge).
+const BOMBE_NS: &str = "https://bombe.dev/ns#";
+
+fn edge_iri(repo_id: &str, qualified_name: &str, file_path: &str) -> String {
+    format!("bombe://{repo_id}/{qualified_name}#{file_path}")
+}
+
+fn named_node(iri: String) -> BombeResult<NamedNode> {
+    NamedNode::new(iri.clone()).map_err(|e| BombeError::Query(format!("invalid IRI '{iri}': {e}")))
+}
+
+/// Build the quads for one cross-repo edge: the primary
+/// `source relationship target` triple, plus `confidence`/`provenance`
+/// triples on the source IRI (see the module doc comment).
+fn edge_to_quads(edge: &CrossRepoEdge) -> BombeResult<Vec<Quad>> {
+    let source = named_node(edge_iri(
+        &edge.source_repo_id,
+        &edge.source_qualified_name,
+        &edge.source_file_path,
+    ))?;
+    let target = named_node(edge_iri(
+        &edge.target_repo_id,
+        &edge.target_qualified_name,
+        &edge.target_file_path,
+    ))?;
+    let predicate = named_node(format!("{BOMBE_NS}{}", edge.relationship))?;
+    let confidence_predicate = named_node(format!("{BOMBE_NS}confidence"))?;
+    let provenance_predicate = named_node(format!("{BOMBE_NS}provenance"))?;
+
+    Ok(vec![
+        Quad::new(
+            source.clone(),
+            predicate,
+            target,
+            oxigraph::model::GraphName::DefaultGraph,
+        ),
+        Quad::new(
+            source.clone(),
+            confidence_predicate,
+            Literal::from(edge.confidence),
+            oxigraph::model::GraphName::DefaultGraph,
+        ),
+        Quad::new(
+            source,
+            provenance_predicate,
+            Literal::new_simple_literal(&edge.provenance),
+            oxigraph::model::GraphName::DefaultGraph,
+        ),
+    ])
+}
+
+/// Load every cross-repo edge in `catalog` into a fresh, in-memory oxigraph
+/// store.
+fn build_store(catalog: &ShardCatalog) -> BombeResult<Store> {
+    let store =
+        Store::new().map_err(|e| BombeError::Database(format!("failed to open RDF store: {e}")))?;
+    for edge in catalog.list_all_cross_repo_edges()? {
+        for quad in edge_to_quads(&edge)? {
+            store
+                .insert(&quad)
+                .map_err(|e| BombeError::Database(format!("failed to insert RDF quad: {e}")))?;
+        }
+    }
+    Ok(store)
+}
+
+fn parse_rdf_format(format: &str) -> BombeResult<RdfFormat> {
+    match format.to_ascii_lowercase().as_str() {
+        "ntriples" | "nt" => Ok(RdfFormat::NTriples),
+        "turtle" | "ttl" => Ok(RdfFormat::Turtle),
+        other => Err(BombeError::Query(format!(
+            "unsupported RDF format '{other}' (expected 'ntriples' or 'turtle')"
+        ))),
+    }
+}
+
+/// Serialize `catalog`'s accumulated cross-repo edges as RDF.
+///
+/// `format` is `"ntriples"` (default) or `"turtle"`.
+#[pyfunction]
+#[pyo3(signature = (catalog, format="ntriples"))]
+pub fn export_cross_repo_graph(catalog: &ShardCatalog, format: &str) -> PyResult<String> {
+    let rdf_format = parse_rdf_format(format)?;
+    let store = build_store(catalog)?;
+    let mut buf = Vec::new();
+    store
+        .dump_graph(&mut buf, rdf_format, GraphNameRef::DefaultGraph)
+        .map_err(|e| BombeError::Database(format!("failed to serialize RDF graph: {e}")))?;
+    String::from_utf8(buf)
+        .map_err(|e| BombeError::Database(format!("RDF output was not valid UTF-8: {e}")).into())
+}
+
+/// Run a SPARQL `SELECT` or `CONSTRUCT`/`DESCRIBE` query over `catalog`'s
+/// cross-repo edge graph — e.g. "all repos transitively importing symbol X"
+/// without hand-writing a recursive SQL join against the catalog tables.
+///
+/// `SELECT` results come back as a list of dicts (variable name -> the
+/// term's string form). `CONSTRUCT`/`DESCRIBE` results are serialized as
+/// N-Triples text, matching [`export_cross_repo_graph`]'s default format.
+/// `ASK` results come back as a bool.
+#[pyfunction]
+pub fn query_cross_repo_graph(
+    py: Python<'_>,
+    catalog: &ShardCatalog,
+    sparql: &str,
+) -> PyResult<PyObject> {
+    let store = build_store(catalog)?;
+    let results = store
+        .query(sparql)
+        .map_err(|e| BombeError::Query(format!("invalid SPARQL query: {e}")))?;
+
+    match results {
+        QueryResults::Solutions(solutions) => {
+            let rows = PyList::empty(py);
+            for solution in solutions {
+                let solution = solution
+                    .map_err(|e| BombeError::Query(format!("SPARQL evaluation error: {e}")))?;
+                let dict = PyDict::new(py);
+                for (variable, term) in solution.iter() {
+                    dict.set_item(variable.as_str(), term.to_string())?;
+                }
+                rows.append(dict)?;
+            }
+            Ok(rows.into_any().unbind())
+        }
+        QueryResults::Graph(triples) => {
+            let mut buf = Vec::new();
+            {
+                let mut writer = oxigraph::io::RdfSerializer::from_format(RdfFormat::NTriples)
+                    .for_writer(&mut buf);
+                for triple in triples {
+                    let triple = triple
+                        .map_err(|e| BombeError::Query(format!("SPARQL evaluation error: {e}")))?;
+                    writer.write_triple(triple.as_ref()).map_err(|e| {
+                        BombeError::Database(format!("failed to serialize RDF triple: {e}"))
+                    })?;
+                }
+                writer.finish().map_err(|e| {
+                    BombeError::Database(format!("failed to finish RDF serialization: {e}"))
+                })?;
+            }
+            let text = String::from_utf8(buf).map_err(|e| {
+                BombeError::Database(format!("RDF output was not valid UTF-8: {e}"))
+            })?;
+            Ok(text.into_pyobject(py)?.into_any().unbind())
+        }
+        QueryResults::Boolean(value) => Ok(value.into_pyobject(py)?.into_any().unbind()),
+    }
+}
